@@ -1,9 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+use crate::core::audit::{self, Change};
 use crate::core::{Ticket, TicketId};
 
+mod watch;
+
+pub use watch::CacheWatcher;
+
 /// A cached ticket entry with timestamp
 #[derive(Clone)]
 struct CachedEntry<T> {
@@ -37,6 +42,24 @@ enum CacheValue {
     Tickets(Vec<Ticket>),
 }
 
+/// Differences between the cached tickets and a fresh load
+#[derive(Debug, Default)]
+pub struct TicketChanges {
+    /// Tickets that were not cached
+    pub added: Vec<Ticket>,
+    /// Cached tickets missing from the fresh load
+    pub removed: Vec<Ticket>,
+    /// Tickets that changed, with their changes
+    pub updated: Vec<(Ticket, Vec<Change>)>,
+}
+
+impl TicketChanges {
+    /// Returns true if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
 impl TicketCache {
     /// Creates a new cache with the specified TTL
     pub fn new(ttl: Duration) -> Self {
@@ -112,6 +135,45 @@ impl TicketCache {
         }
     }
 
+    /// Replaces the cached tickets with `tickets` and returns what changed
+    ///
+    /// The previous tickets are compared even if they have expired; without
+    /// any, all tickets count as added.
+    pub fn refresh_all_tickets(&self, tickets: &[Ticket]) -> TicketChanges {
+        let previous: Vec<Ticket> = self
+            .cache
+            .read()
+            .ok()
+            .and_then(|cache| match &cache.get(&CacheKey::AllTickets)?.data {
+                CacheValue::Tickets(tickets) => Some(tickets.clone()),
+                CacheValue::Ticket(_) => None,
+            })
+            .unwrap_or_default();
+        self.invalidate_all();
+        self.cache_all_tickets(tickets);
+
+        let before: HashMap<&TicketId, &Ticket> = previous.iter().map(|t| (&t.id, t)).collect();
+        let mut changes = TicketChanges::default();
+        for ticket in tickets {
+            match before.get(&ticket.id) {
+                None => changes.added.push(ticket.clone()),
+                Some(old) if *old != ticket => {
+                    changes
+                        .updated
+                        .push((ticket.clone(), audit::diff(Some(old), ticket)));
+                },
+                Some(_) => {},
+            }
+        }
+        let after: HashSet<&TicketId> = tickets.iter().map(|t| &t.id).collect();
+        changes.removed = previous
+            .iter()
+            .filter(|t| !after.contains(&t.id))
+            .cloned()
+            .collect();
+        changes
+    }
+
     /// Invalidates a specific ticket in the cache
     pub fn invalidate_ticket(&self, id: &TicketId) {
         if let Ok(mut cache) = self.cache.write() {
@@ -151,10 +213,12 @@ mod tests {
         let id = format!("12345678-1234-1234-1234-{:0>12}", suffix);
         Ticket {
             id: TicketId::parse_str(&id).unwrap(),
+            short_id: None,
             slug: format!("test-{}", suffix),
             title: format!("Test Ticket {}", suffix),
             description: String::new(),
             priority: Priority::Medium,
+            severity: None,
             status: Status::Todo,
             tags: vec![],
             created_at: chrono::Utc::now(),
@@ -162,6 +226,16 @@ mod tests {
             closed_at: None,
             assignee: None,
             tasks: vec![],
+            work_log: Vec::new(),
+            comments: Vec::new(),
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: Vec::new(),
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: HashMap::new(),
         }
     }
@@ -231,6 +305,31 @@ mod tests {
         assert!(cache.get_ticket(&ticket.id).is_none());
     }
 
+    #[test]
+    fn test_refresh_all_tickets() {
+        let cache = TicketCache::new(Duration::from_millis(10));
+        let mut tickets = vec![create_test_ticket("9"), create_test_ticket("10")];
+        assert_eq!(cache.refresh_all_tickets(&tickets).added.len(), 2);
+        assert!(cache.refresh_all_tickets(&tickets).is_empty());
+
+        // Expired entries are still compared against
+        thread::sleep(Duration::from_millis(20));
+        tickets[0].status = Status::Doing;
+        tickets.remove(1);
+        tickets.push(create_test_ticket("11"));
+        let changes = cache.refresh_all_tickets(&tickets);
+        assert_eq!(changes.added[0].slug, "test-11");
+        assert_eq!(changes.removed[0].slug, "test-10");
+        assert_eq!(
+            changes.updated[0].1,
+            vec![Change::StatusChanged {
+                from: Status::Todo,
+                to: Status::Doing
+            }]
+        );
+        assert_eq!(cache.get_all_tickets().unwrap().len(), 2);
+    }
+
     #[test]
     fn test_cleanup_expired() {
         let cache = TicketCache::new(Duration::from_millis(100));
@@ -0,0 +1,151 @@
+//! Invalidation of cached tickets when their files change
+//!
+//! Entries of a [`TicketCache`] otherwise live until their TTL runs out, so
+//! a long-running process such as the MCP server would serve tickets that
+//! another process has changed since. [`TicketCache::watch`] watches the
+//! tickets directory and drops the entry of every ticket whose file is
+//! written, renamed or deleted. The TTL remains as the fallback for file
+//! systems that do not report changes.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::{CacheKey, CacheValue, CachedEntry, TicketCache};
+use crate::core::TicketId;
+use crate::error::{Result, VibeTicketError};
+
+/// Watcher keeping a [`TicketCache`] in sync with the tickets directory
+///
+/// Watching stops when the watcher is dropped.
+pub struct CacheWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl std::fmt::Debug for CacheWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheWatcher").finish_non_exhaustive()
+    }
+}
+
+impl TicketCache {
+    /// Invalidates cached tickets whenever a file in `tickets_dir` changes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be watched
+    pub fn watch(&self, tickets_dir: &Path) -> Result<CacheWatcher> {
+        let entries = Arc::clone(&self.cache);
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(mut cache) = entries.write() {
+                invalidate(&mut cache, event);
+            }
+        })
+        .map_err(|e| watch_error(tickets_dir, &e))?;
+        watcher
+            .watch(tickets_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| watch_error(tickets_dir, &e))?;
+
+        Ok(CacheWatcher { _watcher: watcher })
+    }
+}
+
+/// Drops the cache entries made stale by `event`
+///
+/// Events about files other than tickets, such as lock files, are ignored.
+/// When the watcher reports an error or lost events, the whole cache is
+/// dropped since any ticket may have changed.
+fn invalidate(
+    cache: &mut HashMap<CacheKey, CachedEntry<CacheValue>>,
+    event: notify::Result<Event>,
+) {
+    let event = match event {
+        Ok(event) if !event.need_rescan() => event,
+        _ => {
+            cache.clear();
+            return;
+        },
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return;
+    }
+
+    let mut changed = false;
+    for id in event.paths.iter().filter_map(|path| ticket_id(path)) {
+        cache.remove(&CacheKey::Ticket(id));
+        changed = true;
+    }
+    if changed {
+        cache.remove(&CacheKey::AllTickets);
+    }
+}
+
+/// ID of the ticket stored at `path`, if it is a ticket file
+fn ticket_id(path: &Path) -> Option<TicketId> {
+    if path.extension()? != "yaml" {
+        return None;
+    }
+    TicketId::parse_str(path.file_stem()?.to_str()?).ok()
+}
+
+fn watch_error(tickets_dir: &Path, error: &notify::Error) -> VibeTicketError {
+    VibeTicketError::custom(format!(
+        "Failed to watch {}: {error}",
+        tickets_dir.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use notify::event::{CreateKind, Flag};
+
+    #[test]
+    fn test_invalidate_changed_tickets() {
+        let cache = TicketCache::with_default_ttl();
+        let changed = Ticket::new("changed", "Changed");
+        let untouched = Ticket::new("untouched", "Untouched");
+        cache.cache_ticket(&changed);
+        cache.cache_ticket(&untouched);
+        cache.cache_all_tickets(&[changed.clone(), untouched.clone()]);
+
+        let apply = |event| invalidate(&mut cache.cache.write().unwrap(), event);
+
+        // Lock files are not tickets
+        apply(Ok(Event::new(EventKind::Create(CreateKind::File))
+            .add_path(
+                format!("/p/tickets/{}.yaml.lock", changed.id).into(),
+            )));
+        assert!(cache.get_all_tickets().is_some());
+
+        apply(Ok(Event::new(EventKind::Create(CreateKind::File))
+            .add_path(format!("/p/tickets/{}.yaml", changed.id).into())));
+        assert!(cache.get_ticket(&changed.id).is_none());
+        assert!(cache.get_all_tickets().is_none());
+        assert!(cache.get_ticket(&untouched.id).is_some());
+
+        apply(Ok(Event::new(EventKind::Other).set_flag(Flag::Rescan)));
+        assert!(cache.get_ticket(&untouched.id).is_none());
+    }
+
+    #[test]
+    fn test_watch_invalidates_on_external_write() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = TicketCache::with_default_ttl();
+        let ticket = Ticket::new("watched", "Watched");
+        cache.cache_ticket(&ticket);
+        let _watcher = cache.watch(temp.path()).unwrap();
+
+        let path = temp.path().join(format!("{}.yaml", ticket.id));
+        std::fs::write(&path, serde_yaml::to_string(&ticket).unwrap()).unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while cache.get_ticket(&ticket.id).is_some() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(cache.get_ticket(&ticket.id).is_none());
+    }
+}
@@ -0,0 +1,540 @@
+//! Chaos testing harness for concurrent storage access
+//!
+//! This module is compiled with the `chaos` feature. It runs several workers
+//! (threads or separate processes) that perform random ticket operations
+//! against one `.vibe-ticket` store, then checks the final state:
+//!
+//! - every worker finished before the timeout (no deadlocks or crashes)
+//! - every ticket file still parses and no lock files are left behind
+//! - every ticket created and not deleted exists, and nothing else does
+//! - every task reported as added is present exactly once
+//!
+//! Operations that fail (for example because a lock could not be acquired in
+//! time) are recorded as failures and are not expected to leave a trace.
+//! Only operations reported as successful must be reflected in the store.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use vibe_ticket::chaos::{ChaosConfig, run_threads};
+//!
+//! let report = run_threads(".vibe-ticket", &ChaosConfig::default())?;
+//! report.assert_consistent();
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Environment variable holding the store directory of a worker process
+pub const ENV_DIR: &str = "VIBE_TICKET_CHAOS_DIR";
+
+/// Environment variable holding the worker number of a worker process
+pub const ENV_WORKER: &str = "VIBE_TICKET_CHAOS_WORKER";
+
+/// Environment variable holding the number of operations of a worker process
+pub const ENV_OPERATIONS: &str = "VIBE_TICKET_CHAOS_OPERATIONS";
+
+/// Environment variable holding the random seed of a worker process
+pub const ENV_SEED: &str = "VIBE_TICKET_CHAOS_SEED";
+
+/// Settings of a chaos run
+#[derive(Debug, Clone)]
+pub struct ChaosConfig {
+    /// Number of concurrent workers
+    pub workers: usize,
+
+    /// Number of random operations performed by each worker
+    pub operations: usize,
+
+    /// Number of tickets shared by all workers
+    pub shared_tickets: usize,
+
+    /// Seed of the pseudo-random operation sequence
+    pub seed: u64,
+
+    /// Maximum duration of the run before workers are considered deadlocked
+    pub timeout: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            operations: 50,
+            shared_tickets: 2,
+            seed: 0x5EED,
+            timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// Record of the operations a worker performed successfully
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WorkerLog {
+    /// Worker number
+    pub worker: usize,
+
+    /// Tickets created by the worker
+    pub created: Vec<TicketId>,
+
+    /// Tickets deleted by the worker
+    pub deleted: Vec<TicketId>,
+
+    /// Tasks added by the worker, by ticket
+    pub tasks: Vec<(TicketId, String)>,
+
+    /// Number of successful operations
+    pub succeeded: usize,
+
+    /// Errors of failed operations
+    pub failed: Vec<String>,
+}
+
+/// Outcome of a chaos run
+#[derive(Debug, Default, Clone)]
+pub struct ChaosReport {
+    /// Logs of the workers that finished
+    pub workers: Vec<WorkerLog>,
+
+    /// Number of tickets in the store at the end of the run
+    pub tickets: usize,
+
+    /// Number of tasks in the store at the end of the run
+    pub tasks: usize,
+
+    /// Invariants that did not hold
+    pub violations: Vec<String>,
+
+    /// Wall-clock duration of the run
+    pub elapsed: Duration,
+}
+
+impl ChaosReport {
+    /// Returns true if all invariants held
+    pub fn is_consistent(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Total number of successful operations
+    pub fn succeeded(&self) -> usize {
+        self.workers.iter().map(|w| w.succeeded).sum()
+    }
+
+    /// Total number of failed operations
+    pub fn failed(&self) -> usize {
+        self.workers.iter().map(|w| w.failed.len()).sum()
+    }
+
+    /// Asserts that all invariants held
+    ///
+    /// # Panics
+    ///
+    /// Panics with the list of violations if any invariant did not hold.
+    pub fn assert_consistent(&self) {
+        assert!(
+            self.is_consistent(),
+            "chaos run found {} violation(s):\n  {}",
+            self.violations.len(),
+            self.violations.join("\n  ")
+        );
+    }
+}
+
+/// Operations a worker picks from
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Create,
+    AddSharedTask,
+    AddOwnTask,
+    Rename,
+    Delete,
+    ReadAll,
+}
+
+/// Small xorshift generator so runs are reproducible without extra dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        usize::try_from(self.next() % n as u64).unwrap_or_default()
+    }
+
+    fn operation(&mut self) -> Operation {
+        match self.below(10) {
+            0..=1 => Operation::Create,
+            2..=4 => Operation::AddSharedTask,
+            5 => Operation::AddOwnTask,
+            6 => Operation::Rename,
+            7 => Operation::Delete,
+            _ => Operation::ReadAll,
+        }
+    }
+}
+
+fn chaos_dir(store: &Path) -> PathBuf {
+    store.join("chaos")
+}
+
+fn shared_path(store: &Path) -> PathBuf {
+    chaos_dir(store).join("shared.json")
+}
+
+fn log_path(store: &Path, worker: usize) -> PathBuf {
+    chaos_dir(store).join(format!("worker-{worker}.json"))
+}
+
+/// Creates the shared tickets all workers contend on
+fn prepare(store: &Path, config: &ChaosConfig) -> Result<Vec<TicketId>> {
+    let storage = FileStorage::new(store);
+    storage.ensure_directories()?;
+    fs::create_dir_all(chaos_dir(store)).context("Failed to create chaos directory")?;
+
+    let mut shared = Vec::new();
+    for i in 0..config.shared_tickets {
+        let ticket = Ticket::new(format!("chaos-shared-{i}"), format!("Shared {i}"));
+        storage.save(&ticket)?;
+        shared.push(ticket.id);
+    }
+
+    fs::write(shared_path(store), serde_json::to_string(&shared)?)
+        .context("Failed to write shared ticket list")?;
+    Ok(shared)
+}
+
+/// Runs one worker against the store and writes its log next to it
+pub fn run_worker(store: &Path, worker: usize, operations: usize, seed: u64) -> Result<WorkerLog> {
+    let content =
+        fs::read_to_string(shared_path(store)).context("Failed to read shared ticket list")?;
+    let shared: Vec<TicketId> = serde_json::from_str(&content)?;
+
+    let storage = FileStorage::new(store);
+    let mut rng = Rng::new(seed ^ ((worker as u64 + 1) << 32));
+    let mut log = WorkerLog {
+        worker,
+        ..WorkerLog::default()
+    };
+    let mut own: Vec<TicketId> = Vec::new();
+
+    for n in 0..operations {
+        let operation = rng.operation();
+        match perform(
+            &storage, operation, &shared, &mut own, &mut rng, &mut log, n,
+        ) {
+            Ok(()) => log.succeeded += 1,
+            Err(e) => log.failed.push(format!("{operation:?}: {e}")),
+        }
+    }
+
+    fs::write(log_path(store, worker), serde_json::to_string(&log)?)
+        .context("Failed to write worker log")?;
+    Ok(log)
+}
+
+/// Performs a single operation, recording its effect in the log on success
+fn perform(
+    storage: &FileStorage,
+    operation: Operation,
+    shared: &[TicketId],
+    own: &mut Vec<TicketId>,
+    rng: &mut Rng,
+    log: &mut WorkerLog,
+    n: usize,
+) -> Result<()> {
+    let worker = log.worker;
+    match operation {
+        Operation::AddSharedTask if !shared.is_empty() => {
+            let id = &shared[rng.below(shared.len())];
+            let title = format!("w{worker}-op{n}");
+            storage.update_ticket(id, |t| {
+                t.add_task(title.clone());
+            })?;
+            log.tasks.push((id.clone(), title));
+        },
+        Operation::AddOwnTask | Operation::Rename | Operation::Delete if !own.is_empty() => {
+            let index = rng.below(own.len());
+            let id = own[index].clone();
+            match operation {
+                Operation::AddOwnTask => {
+                    let title = format!("w{worker}-op{n}");
+                    storage.update_ticket(&id, |t| {
+                        t.add_task(title.clone());
+                    })?;
+                    log.tasks.push((id, title));
+                },
+                Operation::Rename => {
+                    storage.update_ticket(&id, |t| t.title = format!("Renamed by op {n}"))?;
+                },
+                _ => {
+                    storage.delete(&id)?;
+                    own.swap_remove(index);
+                    log.deleted.push(id);
+                },
+            }
+        },
+        Operation::ReadAll => {
+            storage.load_all()?;
+        },
+        _ => {
+            let ticket = Ticket::new(format!("chaos-w{worker}-{n}"), format!("Worker {worker}"));
+            storage.save(&ticket)?;
+            own.push(ticket.id.clone());
+            log.created.push(ticket.id);
+        },
+    }
+    Ok(())
+}
+
+/// Runs a worker configured through the `VIBE_TICKET_CHAOS_*` environment
+///
+/// Returns `Ok(false)` without doing anything when the environment is not
+/// set, so the function can back a test that is only active when spawned by
+/// [`run_processes`].
+pub fn worker_from_env() -> Result<bool> {
+    let Ok(dir) = std::env::var(ENV_DIR) else {
+        return Ok(false);
+    };
+
+    let parse = |name: &str| -> Result<u64> {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| VibeTicketError::custom(format!("{name} is missing or invalid")))
+    };
+    let worker = parse(ENV_WORKER)?;
+    let operations = parse(ENV_OPERATIONS)?;
+    let seed = parse(ENV_SEED)?;
+
+    run_worker(
+        Path::new(&dir),
+        usize::try_from(worker).unwrap_or_default(),
+        usize::try_from(operations).unwrap_or_default(),
+        seed,
+    )?;
+    Ok(true)
+}
+
+/// Runs the workers as threads of the current process
+pub fn run_threads(store: impl AsRef<Path>, config: &ChaosConfig) -> Result<ChaosReport> {
+    let store = store.as_ref();
+    let started = Instant::now();
+    prepare(store, config)?;
+
+    let handles: Vec<_> = (0..config.workers)
+        .map(|worker| {
+            let store = store.to_path_buf();
+            let (operations, seed) = (config.operations, config.seed);
+            thread::spawn(move || run_worker(&store, worker, operations, seed))
+        })
+        .collect();
+
+    let mut violations = Vec::new();
+    for (worker, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok(Ok(_)) => {},
+            Ok(Err(e)) => violations.push(format!("worker {worker} failed: {e}")),
+            Err(_) => violations.push(format!("worker {worker} panicked")),
+        }
+    }
+
+    Ok(verify(store, config, started, violations))
+}
+
+/// Runs each worker as a separate process
+///
+/// `command` builds the command for a worker; it must end up calling
+/// [`worker_from_env`]. The worker settings are passed through the
+/// environment. Workers still running after the timeout are killed and
+/// reported as deadlocked.
+pub fn run_processes<F>(
+    store: impl AsRef<Path>,
+    config: &ChaosConfig,
+    command: F,
+) -> Result<ChaosReport>
+where
+    F: Fn(usize) -> Command,
+{
+    let store = store.as_ref();
+    let started = Instant::now();
+    prepare(store, config)?;
+
+    let mut children = Vec::new();
+    for worker in 0..config.workers {
+        let child = command(worker)
+            .env(ENV_DIR, store)
+            .env(ENV_WORKER, worker.to_string())
+            .env(ENV_OPERATIONS, config.operations.to_string())
+            .env(ENV_SEED, config.seed.to_string())
+            .spawn()
+            .with_context(|| format!("Failed to spawn chaos worker {worker}"))?;
+        children.push((worker, child));
+    }
+
+    let mut violations = Vec::new();
+    let deadline = started + config.timeout;
+    for (worker, mut child) in children {
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) if status.success() => break,
+                Ok(Some(status)) => {
+                    violations.push(format!("worker {worker} exited with {status}"));
+                    break;
+                },
+                Ok(None) if Instant::now() < deadline => {
+                    thread::sleep(Duration::from_millis(20));
+                },
+                Ok(None) => {
+                    let _ = child.kill();
+                    violations.push(format!("worker {worker} did not finish (deadlock?)"));
+                    break;
+                },
+                Err(e) => {
+                    violations.push(format!("worker {worker} could not be waited on: {e}"));
+                    break;
+                },
+            }
+        }
+    }
+
+    Ok(verify(store, config, started, violations))
+}
+
+/// Checks the final state of the store against the worker logs
+fn verify(
+    store: &Path,
+    config: &ChaosConfig,
+    started: Instant,
+    mut violations: Vec<String>,
+) -> ChaosReport {
+    let workers = read_logs(store, config, &mut violations);
+    let found = read_tickets(store, &mut violations);
+
+    // Tickets that must exist: shared ones plus created minus deleted
+    let mut expected: HashSet<TicketId> = fs::read_to_string(shared_path(store))
+        .ok()
+        .and_then(|s| serde_json::from_str::<Vec<TicketId>>(&s).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    for log in &workers {
+        expected.extend(log.created.iter().cloned());
+    }
+    for log in &workers {
+        for id in &log.deleted {
+            expected.remove(id);
+        }
+    }
+
+    for id in &expected {
+        if !found.contains_key(id) {
+            violations.push(format!("ticket {id} was lost"));
+        }
+    }
+    for id in found.keys() {
+        if !expected.contains(id) {
+            violations.push(format!("ticket {id} exists but should not"));
+        }
+    }
+
+    // Every reported task is present exactly once
+    for (id, title) in workers.iter().flat_map(|w| &w.tasks) {
+        let Some(ticket) = found.get(id) else {
+            continue;
+        };
+        let count = ticket.tasks.iter().filter(|t| &t.title == title).count();
+        if count != 1 {
+            violations.push(format!("task '{title}' found {count} times on {id}"));
+        }
+    }
+
+    ChaosReport {
+        tickets: found.len(),
+        tasks: found.values().map(|t| t.tasks.len()).sum(),
+        workers,
+        violations,
+        elapsed: started.elapsed(),
+    }
+}
+
+fn read_logs(store: &Path, config: &ChaosConfig, violations: &mut Vec<String>) -> Vec<WorkerLog> {
+    (0..config.workers)
+        .filter_map(|worker| {
+            let log = fs::read_to_string(log_path(store, worker))
+                .ok()
+                .and_then(|s| serde_json::from_str::<WorkerLog>(&s).ok());
+            if log.is_none() {
+                violations.push(format!("worker {worker} left no log"));
+            }
+            log
+        })
+        .collect()
+}
+
+fn read_tickets(store: &Path, violations: &mut Vec<String>) -> HashMap<TicketId, Ticket> {
+    let mut tickets = HashMap::new();
+    let Ok(entries) = fs::read_dir(store.join("tickets")) else {
+        violations.push("tickets directory is missing".to_string());
+        return tickets;
+    };
+
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("yaml") => {
+                let ticket = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|s| serde_yaml::from_str::<Ticket>(&s).ok());
+                match ticket {
+                    Some(ticket) => {
+                        tickets.insert(ticket.id.clone(), ticket);
+                    },
+                    None => violations.push(format!("{} is corrupt", path.display())),
+                }
+            },
+            Some("lock") => violations.push(format!("stale lock {}", path.display())),
+            _ => {},
+        }
+    }
+
+    tickets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_chaos_threads() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ChaosConfig {
+            workers: 3,
+            operations: 20,
+            ..ChaosConfig::default()
+        };
+
+        let report = run_threads(temp_dir.path(), &config).unwrap();
+        report.assert_consistent();
+        assert_eq!(report.workers.len(), 3);
+        assert_eq!(report.succeeded() + report.failed(), 60);
+    }
+}
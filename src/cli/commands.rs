@@ -18,10 +18,23 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_color: bool,
 
+    /// Show absolute dates even if `ui.relative_dates` is enabled
+    #[arg(long, global = true)]
+    pub absolute_dates: bool,
+
     /// Path to the project directory
     #[arg(short, long, global = true)]
     pub project: Option<String>,
 
+    /// Print usage examples of the command instead of running it
+    #[arg(long, global = true)]
+    pub examples: bool,
+
+    /// Wait up to SECS for ticket locks held by other processes, overriding
+    /// `storage.lock` of the configuration
+    #[arg(long, global = true, value_name = "SECS")]
+    pub wait: Option<u64>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -50,13 +63,38 @@ pub enum Commands {
     /// Create a new ticket
     New {
         /// Ticket slug (e.g., fix-login-bug)
-        slug: String,
+        #[arg(required_unless_present_any = ["from_url", "from_json"])]
+        slug: Option<String>,
+
+        /// Prefill the ticket from a GitHub or GitLab issue or pull request URL
+        ///
+        /// The title, description and labels are taken from the issue unless
+        /// given, and the URL is stored as the ticket's origin. Private
+        /// repositories need `GITHUB_TOKEN` (or `GH_TOKEN`) or `GITLAB_TOKEN`.
+        #[arg(long, value_name = "URL")]
+        from_url: Option<String>,
+
+        /// Read the ticket as JSON from a file, or from stdin with `-`
+        ///
+        /// The JSON object may hold `slug`, `title`, `description`,
+        /// `priority`, `severity`, `template`, `tags`, `parent`, `assignee`,
+        /// `fields` (an object of field values), `tasks` (a list of task
+        /// titles) and `from_url`. Unknown keys are rejected.
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = [
+                "from_url", "title", "description", "priority", "severity",
+                "template", "tags", "parent", "fields",
+            ]
+        )]
+        from_json: Option<String>,
 
         /// Ticket title
         #[arg(short, long)]
         title: Option<String>,
 
-        /// Ticket description
+        /// Ticket description (`-` reads it from stdin)
         #[arg(short, long)]
         description: Option<String>,
 
@@ -64,13 +102,33 @@ pub enum Commands {
         #[arg(long, default_value = "medium")]
         priority: String,
 
+        /// Incident severity (S1, S2, S3, S4)
+        #[arg(long)]
+        severity: Option<String>,
+
+        /// Ticket template (incident, bug)
+        #[arg(long)]
+        template: Option<String>,
+
         /// Tags (comma-separated)
         #[arg(long)]
         tags: Option<String>,
 
+        /// Parent ticket (epic) this ticket belongs to
+        #[arg(long, value_name = "TICKET")]
+        parent: Option<String>,
+
         /// Start working on the ticket immediately
         #[arg(short, long)]
         start: bool,
+
+        /// Assign the least-loaded qualified team member
+        #[arg(long)]
+        auto_assign: bool,
+
+        /// Set a user-defined field (name=value; repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List all tickets
@@ -83,11 +141,19 @@ pub enum Commands {
         #[arg(long)]
         priority: Option<String>,
 
-        /// Filter by assignee
+        /// Filter by severity (S1, S2, S3, S4)
+        #[arg(long)]
+        severity: Option<String>,
+
+        /// Filter by assignee (`me` for the current user)
         #[arg(short, long)]
         assignee: Option<String>,
 
-        /// Sort by field (created, updated, priority, status, slug)
+        /// Filter by milestone (`none` for tickets without milestone)
+        #[arg(short, long)]
+        milestone: Option<String>,
+
+        /// Sort by field (created, updated, priority, severity, status, slug)
         #[arg(long, default_value = "slug")]
         sort: String,
 
@@ -115,9 +181,59 @@ pub enum Commands {
         #[arg(long)]
         until: Option<String>,
 
+        /// Only list tickets that entered this status; `--since` and
+        /// `--until` then bound when, e.g. `--entered-status doing --since monday`
+        ///
+        /// Done tickets are included unless `--status` excludes them.
+        #[arg(long, value_name = "STATUS", conflicts_with = "left_status")]
+        entered_status: Option<String>,
+
+        /// Only list tickets that left this status; `--since` and `--until`
+        /// then bound when
+        ///
+        /// Done tickets are included unless `--status` excludes them.
+        #[arg(long, value_name = "STATUS")]
+        left_status: Option<String>,
+
         /// Include done tickets (by default they are hidden)
         #[arg(long)]
         include_done: bool,
+
+        /// Apply a saved filter from the `filters` section of the configuration
+        ///
+        /// Done tickets are included unless the filter excludes them.
+        #[arg(long, value_name = "NAME")]
+        filter: Option<String>,
+
+        /// Only list tickets matching a query, e.g.
+        /// "status:doing AND (tag:backend OR priority:high) created:>2025-01-01"
+        ///
+        /// Done tickets are included unless the query excludes them.
+        #[arg(long = "where", value_name = "QUERY")]
+        where_clause: Option<String>,
+
+        /// Exit with an error if a count condition holds, e.g. "count(open,critical) > 0"
+        ///
+        /// Counts cover all non-archived tickets regardless of the other
+        /// filters. Terms: `open` (any status but done), a status, priority
+        /// or severity, or `key=value` for status, priority, severity and
+        /// assignee. May be repeated.
+        #[arg(long, value_name = "CONDITION")]
+        fail_if: Vec<String>,
+
+        /// Show the tickets as a tree of epics and their children
+        #[arg(long)]
+        tree: bool,
+
+        /// List the tickets of all projects registered with `workspace add`
+        ///
+        /// Adds a project column; filters and sorting apply across projects.
+        #[arg(long, conflicts_with_all = ["tree", "fail_if", "watch"])]
+        all_projects: bool,
+
+        /// Re-render the list whenever tickets change, until interrupted
+        #[arg(short, long)]
+        watch: bool,
     },
 
     /// Start working on a ticket
@@ -173,6 +289,61 @@ pub enum Commands {
         /// Create a merge/pull request
         #[arg(long)]
         pr: bool,
+
+        /// Link an existing pull request to the ticket
+        #[arg(long, value_name = "URL", conflicts_with = "pr")]
+        pr_url: Option<String>,
+
+        /// Close even if the ticket depends on open tickets or misses
+        /// required fields
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Reopen a closed ticket
+    ///
+    /// With `project.immutable_closed` set, closed tickets are read-only and
+    /// must be reopened before they can be changed.
+    Reopen {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Status of the reopened ticket (todo, doing, review, blocked)
+        #[arg(short, long, default_value = "todo")]
+        status: String,
+
+        /// Why the ticket is reopened
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
+
+    /// Move a ticket to the trash
+    ///
+    /// Trashed tickets can be restored with `trash restore` until the trash
+    /// is emptied or `gc` removes them after `project.trash_retention_days`.
+    Delete {
+        /// Ticket ID or slug
+        ticket: String,
+    },
+
+    /// Manage deleted tickets
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
+
+    /// Revert the most recent operations
+    ///
+    /// Every change to a ticket is journaled with the ticket's previous
+    /// state, so closes, edits, task removals and imports can be reverted.
+    Undo {
+        /// Number of operations to revert
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+
+        /// List the operations that can be reverted instead
+        #[arg(long)]
+        list: bool,
     },
 
     /// Check the current status
@@ -184,8 +355,19 @@ pub enum Commands {
         /// Include statistics
         #[arg(short, long)]
         stats: bool,
+
+        /// Re-render the status whenever tickets change, until interrupted
+        #[arg(short, long)]
+        watch: bool,
     },
 
+    /// Show the ticket of the current git branch
+    ///
+    /// The ticket is inferred from the branch name: the branch naming
+    /// template, a slug or a short ID. Commands taking a ticket accept
+    /// `@branch` for the same ticket.
+    Current,
+
     /// Edit a ticket
     Edit {
         /// Ticket ID or slug (defaults to active ticket)
@@ -203,10 +385,22 @@ pub enum Commands {
         #[arg(long)]
         priority: Option<String>,
 
+        /// New severity (S1, S2, S3, S4, or "none" to clear)
+        #[arg(long)]
+        severity: Option<String>,
+
         /// New status
         #[arg(long)]
         status: Option<String>,
 
+        /// Due date (YYYY-MM-DD, or "none" to clear)
+        #[arg(long)]
+        due: Option<String>,
+
+        /// Parent ticket (epic), or "none" to clear
+        #[arg(long, value_name = "TICKET")]
+        parent: Option<String>,
+
         /// Add tags (comma-separated)
         #[arg(long)]
         add_tags: Option<String>,
@@ -215,6 +409,10 @@ pub enum Commands {
         #[arg(long)]
         remove_tags: Option<String>,
 
+        /// Set a user-defined field (name=value, empty value to clear; repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
+
         /// Open in editor
         #[arg(short, long)]
         editor: bool,
@@ -236,6 +434,18 @@ pub enum Commands {
         /// Show in markdown format
         #[arg(short, long)]
         markdown: bool,
+
+        /// Inline the full description when it was moved to an attachment
+        #[arg(long)]
+        full_description: bool,
+
+        /// Sections to show (description, tasks, links, history, spec, worktree, all)
+        #[arg(long, value_delimiter = ',')]
+        sections: Vec<String>,
+
+        /// Expand collapsed sections such as long histories
+        #[arg(long)]
+        expand: bool,
     },
 
     /// Manage tasks within a ticket
@@ -244,20 +454,70 @@ pub enum Commands {
         command: TaskCommands,
     },
 
+    /// List incomplete tasks across all open tickets
+    ///
+    /// Tasks are grouped by ticket, highest priority first. A task belongs
+    /// to whoever its ticket is assigned to.
+    Tasks {
+        /// Only tickets assigned to this user (`me` for the current user)
+        #[arg(short, long)]
+        assignee: Option<String>,
+
+        /// Only tickets assigned to you (the current user)
+        #[arg(long, conflicts_with = "assignee")]
+        mine: bool,
+
+        /// Only tickets with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only tickets with this priority (low, medium, high, critical)
+        #[arg(long)]
+        priority: Option<String>,
+    },
+
     /// Archive or unarchive tickets
+    ///
+    /// Without a ticket, archives every ticket matching the filters, e.g.
+    /// `archive --status done --until "3 months ago"`. The matching tickets
+    /// are saved together: either all of them change or none.
     Archive {
         /// Ticket ID or slug
-        ticket: String,
+        #[arg(required_unless_present_any = ["status", "until", "tag"])]
+        ticket: Option<String>,
 
         /// Unarchive instead of archive
         #[arg(short, long)]
         unarchive: bool,
+
+        /// Only tickets with this status
+        #[arg(long, conflicts_with = "ticket")]
+        status: Option<String>,
+
+        /// Only tickets closed (or, if open, created) before this date
+        /// (e.g. "3 months ago", "2025-07-01")
+        #[arg(long, conflicts_with = "ticket")]
+        until: Option<String>,
+
+        /// Only tickets with this tag
+        #[arg(long, conflicts_with = "ticket")]
+        tag: Option<String>,
+
+        /// List the matching tickets without changing them
+        #[arg(long, conflicts_with = "ticket")]
+        dry_run: bool,
     },
 
     /// Search tickets
     Search {
-        /// Search query
-        query: String,
+        /// Text to search for
+        #[arg(required_unless_present = "expression")]
+        query: Option<String>,
+
+        /// Only search tickets matching a query expression, e.g.
+        /// "status:doing AND (tag:backend OR priority:high)"
+        #[arg(long = "query", value_name = "QUERY")]
+        expression: Option<String>,
 
         /// Search in title only
         #[arg(long)]
@@ -274,11 +534,23 @@ pub enum Commands {
         /// Use regex
         #[arg(short, long)]
         regex: bool,
+
+        /// Only search tickets matching a saved filter
+        #[arg(long, value_name = "NAME")]
+        filter: Option<String>,
+
+        /// Stop after this many matches
+        #[arg(short, long)]
+        limit: Option<usize>,
+
+        /// Number of threads searching tickets (defaults to the available cores)
+        #[arg(long)]
+        jobs: Option<usize>,
     },
 
     /// Export tickets
     Export {
-        /// Output format (json, yaml, csv, markdown)
+        /// Output format (json, yaml, csv, markdown, html, dot, xlsx)
         #[arg(short, long, default_value = "json")]
         format: String,
 
@@ -289,6 +561,19 @@ pub enum Commands {
         /// Include archived tickets
         #[arg(long)]
         include_archived: bool,
+
+        /// Ticket order (created, id, slug)
+        #[arg(long, default_value = "created")]
+        sort_by: String,
+
+        /// Omit the export timestamp so unchanged tickets produce identical files
+        #[arg(long)]
+        reproducible: bool,
+
+        /// Also export one row per task (csv only), after the tickets or
+        /// into `<file>.tasks.csv`
+        #[arg(long)]
+        include_tasks: bool,
     },
 
     /// Import tickets
@@ -296,7 +581,7 @@ pub enum Commands {
         /// Input file
         file: String,
 
-        /// Input format (json, yaml, csv)
+        /// Input format (json, yaml, csv, markdown-plan)
         #[arg(short, long)]
         format: Option<String>,
 
@@ -307,6 +592,14 @@ pub enum Commands {
         /// Dry run (don't actually import)
         #[arg(long)]
         dry_run: bool,
+
+        /// Check the file against the manifest written by `export` before importing
+        #[arg(long)]
+        verify: bool,
+
+        /// Group the tickets of a markdown plan under an epic for the plan
+        #[arg(long)]
+        epic: bool,
     },
 
     /// Manage project configuration
@@ -332,6 +625,407 @@ pub enum Commands {
         #[command(subcommand)]
         command: McpCommands,
     },
+
+    /// Manage queued integration deliveries
+    Outbox {
+        #[command(subcommand)]
+        command: OutboxCommands,
+    },
+
+    /// Post ticket events to webhooks
+    Webhook {
+        #[command(subcommand)]
+        command: WebhookCommands,
+    },
+
+    /// Manage notifications of the built-in Slack plugin
+    Notify {
+        #[command(subcommand)]
+        command: NotifyCommands,
+    },
+
+    /// Move a ticket to another project
+    Move {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Path of the target project, or name of a project registered
+        /// with `workspace add`
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Record a work-log note on a ticket
+    Log {
+        /// Note describing the work done
+        #[arg(required_unless_present = "standup")]
+        message: Option<String>,
+
+        /// Ticket ID or slug (defaults to active ticket)
+        #[arg(long)]
+        ticket: Option<String>,
+
+        /// Print a standup report of recent work-log entries instead
+        #[arg(long, conflicts_with = "message")]
+        standup: bool,
+
+        /// Start of the standup window (e.g., "yesterday", "2 days ago", "2025-07-18")
+        #[arg(long, default_value = "yesterday")]
+        since: String,
+    },
+
+    /// Show the full history of a ticket
+    ///
+    /// Lists recorded changes (status, assignee, edits, tasks), work-log
+    /// notes and comments in chronological order.
+    History {
+        /// Ticket ID or slug
+        ticket: String,
+    },
+
+    /// Discuss a ticket with comments
+    Comment {
+        #[command(subcommand)]
+        command: CommentCommands,
+    },
+
+    /// Attach files such as screenshots and logs to a ticket
+    ///
+    /// Files are copied to `.vibe-ticket/attachments/<ticket-id>/`.
+    Attach {
+        #[command(subcommand)]
+        command: AttachCommands,
+    },
+
+    /// Show open tickets in a priority matrix of urgency and importance
+    ///
+    /// Urgency comes from the due date, importance from the priority
+    /// (high and critical are important).
+    Matrix {
+        /// Tickets due within this many days, or overdue, are urgent
+        #[arg(long, default_value_t = crate::core::matrix::DEFAULT_URGENT_DAYS)]
+        urgent_days: i64,
+
+        /// Most tickets listed per quadrant
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+    },
+
+    /// Show statistics on ticket activity from the history log
+    ///
+    /// Counts the recorded changes per day and per hour of the week, for
+    /// the whole project or for the tickets of one assignee.
+    Stats {
+        /// Render a contribution heatmap of the activity
+        #[arg(long)]
+        heatmap: bool,
+
+        /// Show the heatmap by weekday and hour of the day instead of by date
+        #[arg(long, requires = "heatmap")]
+        by_hour: bool,
+
+        /// Number of weeks covered, ending today
+        #[arg(long, default_value_t = crate::core::heatmap::DEFAULT_WEEKS)]
+        weeks: u32,
+
+        /// Only count tickets assigned to this user (`me` for the current user)
+        #[arg(long)]
+        assignee: Option<String>,
+
+        /// Only count tickets closed within a date range, e.g. "2025-01-01..2025-03-31"
+        ///
+        /// Both ends are inclusive and either may be left out.
+        #[arg(long, value_name = "START..END")]
+        closed_between: Option<String>,
+    },
+
+    /// Capture a thought into the inbox, to turn into a ticket later
+    ///
+    /// `in "random idea about caching"` records the text without asking for
+    /// a slug. Without text, lists the inbox; `in process` goes through it
+    /// and turns each entry into a ticket or discards it.
+    #[command(name = "in", args_conflicts_with_subcommands = true)]
+    Inbox {
+        #[command(subcommand)]
+        command: Option<InboxCommands>,
+
+        /// Text to capture
+        text: Vec<String>,
+    },
+
+    /// Group tickets into milestones such as releases or sprints
+    Milestone {
+        #[command(subcommand)]
+        command: MilestoneCommands,
+    },
+
+    /// Record incident timelines and write postmortems
+    ///
+    /// Incidents are tickets created with `--template incident`, tagged
+    /// `incident` or given a severity.
+    Incident {
+        #[command(subcommand)]
+        command: IncidentCommands,
+    },
+
+    /// Render a ticket as a self-contained document to share
+    ///
+    /// Includes the description, tasks, history, linked spec excerpts and a
+    /// manifest of the attachments. Printed to stdout unless a target is given.
+    Share {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Document format (markdown, html)
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+
+        /// Write the document to a file
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Copy the document to the clipboard
+        #[arg(long)]
+        clipboard: bool,
+
+        /// Publish the document as a GitHub gist (needs a GitHub token)
+        #[arg(long)]
+        gist: bool,
+
+        /// Make the gist public instead of secret
+        #[arg(long, requires = "gist")]
+        public: bool,
+    },
+
+    /// Find data left behind by deleted tickets and specs
+    ///
+    /// Reports attachments and audit logs of tickets that no longer exist and
+    /// spec directories without spec metadata, with the space they take.
+    Gc {
+        /// Delete the orphaned data
+        #[arg(long)]
+        prune: bool,
+
+        /// Delete without asking for confirmation
+        #[arg(short, long, requires = "prune")]
+        force: bool,
+    },
+
+    /// Check the project storage for problems
+    ///
+    /// Compares the listing index (`.vibe-ticket/index.json`), which `list`,
+    /// `search` and ticket lookups read instead of every ticket file, with
+    /// the ticket files. `doctor locks` lists the held lock files.
+    #[command(args_conflicts_with_subcommands = true)]
+    Doctor {
+        #[command(subcommand)]
+        command: Option<DoctorCommands>,
+
+        /// Rebuild the listing index from the ticket files
+        #[arg(long)]
+        rebuild_index: bool,
+    },
+
+    /// Run periodic upkeep such as the aging policy
+    ///
+    /// Tags tickets untouched for `aging.stale_after_days` as stale and
+    /// closes them after `aging.close_after_days` unless activity resumes.
+    /// Meant to be run on a schedule (e.g., from cron or CI).
+    Maintain {
+        /// Show what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Watch a ticket for status, priority and due date changes
+    ///
+    /// Watchers are notified through the webhook configured as
+    /// `notifications.webhook_url`.
+    Watch {
+        /// Ticket ID or slug (defaults to active ticket)
+        ticket: Option<String>,
+
+        /// User to add (defaults to the current user)
+        #[arg(short, long)]
+        user: Option<String>,
+
+        /// Stop watching instead
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Record dependencies and relations between tickets
+    ///
+    /// `link a --depends-on b` means `a` cannot be closed before `b`.
+    /// Duplicate, related and supersede relations are recorded on both
+    /// tickets.
+    Link {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Tickets this ticket depends on (comma-separated or repeated)
+        #[arg(
+            long,
+            value_delimiter = ',',
+            required_unless_present_any = ["blocks", "duplicates", "relates_to", "supersedes"]
+        )]
+        depends_on: Vec<String>,
+
+        /// Tickets that depend on this ticket (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        blocks: Vec<String>,
+
+        /// Tickets this ticket duplicates (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        duplicates: Vec<String>,
+
+        /// Tickets related to this ticket (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        relates_to: Vec<String>,
+
+        /// Tickets this ticket replaces (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        supersedes: Vec<String>,
+
+        /// Remove the links instead of adding them
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Remove dependencies and relations between tickets
+    ///
+    /// Same as `link --remove`.
+    Unlink {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Tickets this ticket no longer depends on (comma-separated or repeated)
+        #[arg(
+            long,
+            value_delimiter = ',',
+            required_unless_present_any = ["blocks", "duplicates", "relates_to", "supersedes"]
+        )]
+        depends_on: Vec<String>,
+
+        /// Tickets that no longer depend on this ticket (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        blocks: Vec<String>,
+
+        /// Tickets this ticket no longer duplicates (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        duplicates: Vec<String>,
+
+        /// Tickets no longer related to this ticket (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        relates_to: Vec<String>,
+
+        /// Tickets this ticket no longer replaces (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        supersedes: Vec<String>,
+    },
+
+    /// Record a typed relation between tickets
+    ///
+    /// The relation is recorded on both tickets. Kinds: `relates_to`,
+    /// `duplicates`, `duplicated_by`, `supersedes`, `superseded_by`,
+    /// `caused_by`, `causes`, `follows` and `followed_by`. All kinds but
+    /// `relates_to` are hierarchical and cannot form cycles.
+    Relate {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Kind of relation from the ticket to the others, such as `caused_by`
+        kind: String,
+
+        /// Ticket IDs or slugs the ticket has the relation to
+        #[arg(required = true)]
+        tickets: Vec<String>,
+    },
+
+    /// Remove a typed relation between tickets
+    Unrelate {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Kind of relation to remove
+        kind: String,
+
+        /// Ticket IDs or slugs the ticket no longer has the relation to
+        #[arg(required = true)]
+        tickets: Vec<String>,
+    },
+
+    /// Suggest who should work on a ticket based on team capacity
+    SuggestAssignee {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Assign the ticket to the top suggestion
+        #[arg(long)]
+        assign: bool,
+    },
+
+    /// Ingest CI results into tickets
+    Ci {
+        #[command(subcommand)]
+        command: CiCommands,
+    },
+
+    /// Run a command contributed by a plugin
+    ///
+    /// Without arguments, lists the installed plugins and their commands.
+    /// Arguments after the command name are checked against the plugin's
+    /// manifest.
+    #[command(name = "x")]
+    Plugin {
+        /// Plugin name
+        plugin: Option<String>,
+
+        /// Plugin command followed by its arguments (e.g., sync --project ABC)
+        ///
+        /// Everything after the command is passed to the plugin, including
+        /// options that vibe-ticket itself knows, such as --project.
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Manage the registry of projects listed by `list --all-projects`
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommands,
+    },
+
+    /// Rename the project or move it to another directory
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommands,
+    },
+
+    /// Summarize the project for an AI session
+    Context {
+        #[command(subcommand)]
+        command: ContextCommands,
+    },
+
+    /// Inspect the JSON Schemas of the `--json` output
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
+
+    /// Generate a shell completion script
+    ///
+    /// Ticket arguments of commands such as `start`, `show` and `edit`
+    /// complete the slugs and short IDs of the current project.
+    /// For example: `vibe-ticket completions bash > ~/.local/share/bash-completion/completions/vibe-ticket`
+    Completions {
+        /// Shell (bash, zsh, fish)
+        shell: String,
+    },
+
+    /// List ticket references for completion scripts
+    #[command(name = "__complete-tickets", hide = true)]
+    CompleteTickets,
 }
 
 #[derive(Subcommand, Debug)]
@@ -460,124 +1154,530 @@ pub enum SpecCommands {
         /// Initial tags (comma-separated)
         #[arg(long)]
         tags: Option<String>,
+
+        /// Custom metadata field (KEY=VALUE, repeatable)
+        #[arg(long = "field", value_name = "KEY=VALUE")]
+        fields: Vec<String>,
+
+        /// Completed spec this spec amends; its requirements are inherited
+        /// with change markers
+        #[arg(long, value_name = "SPEC")]
+        amends: Option<String>,
+
+        /// Template pack in `.vibe-ticket/templates/specs/` to generate the
+        /// documents from
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+    },
+
+    /// Set or remove custom metadata fields on a specification
+    Set {
+        /// Specification ID
+        spec: String,
+
+        /// Fields to set
+        #[arg(value_name = "KEY=VALUE")]
+        fields: Vec<String>,
+
+        /// Fields to remove (repeatable)
+        #[arg(long, value_name = "KEY")]
+        unset: Vec<String>,
+    },
+
+    /// Link a specification to the ticket it is written for
+    Link {
+        /// Specification ID
+        spec: String,
+
+        /// Ticket ID or slug
+        #[arg(required_unless_present = "unlink", conflicts_with = "unlink")]
+        ticket: Option<String>,
+
+        /// Remove the link to the ticket
+        #[arg(long)]
+        unlink: bool,
+    },
+
+    /// Create or update requirements document
+    Requirements {
+        /// Specification ID (defaults to active spec)
+        #[arg(short, long)]
+        spec: Option<String>,
+
+        /// Open in editor
+        #[arg(short, long)]
+        editor: bool,
+
+        /// Mark as complete
+        #[arg(long)]
+        complete: bool,
+    },
+
+    /// Create or update design document
+    Design {
+        /// Specification ID (defaults to active spec)
+        #[arg(short, long)]
+        spec: Option<String>,
+
+        /// Open in editor
+        #[arg(short, long)]
+        editor: bool,
+
+        /// Mark as complete
+        #[arg(long)]
+        complete: bool,
+    },
+
+    /// Create or update implementation tasks
+    Tasks {
+        /// Specification ID (defaults to active spec)
+        #[arg(short, long)]
+        spec: Option<String>,
+
+        /// Open in editor
+        #[arg(short, long)]
+        editor: bool,
+
+        /// Mark as complete
+        #[arg(long)]
+        complete: bool,
+
+        /// Export tasks to tickets
+        #[arg(long)]
+        export_tickets: bool,
+    },
+
+    /// Show specification status
+    Status {
+        /// Specification ID (defaults to active spec)
+        #[arg(short, long)]
+        spec: Option<String>,
+
+        /// Show detailed progress
+        #[arg(short, long)]
+        detailed: bool,
+    },
+
+    /// List the versions of a specification's documents
+    History {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+    },
+
+    /// Show the changes to a specification's documents between versions
+    Diff {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+
+        /// Version to compare from (defaults to the version before --to)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Version to compare to (defaults to the current documents)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Only diff this document (requirements, design, tasks)
+        #[arg(short, long)]
+        document: Option<String>,
+    },
+
+    /// Check that the design references every requirement by ID
+    ///
+    /// Flags requirements (`REQ-1`, `FR-2`, `NFR-3`) that no design section
+    /// cites, and design sections citing IDs no requirement defines.
+    CheckRefs {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+    },
+
+    /// Check the structure of a specification
+    ///
+    /// Checks that each document has its required sections, that the design
+    /// references every requirement and that tasks cover every design
+    /// section citing requirements, and counts the open tasks. Fails if
+    /// anything is missing; use --json for CI.
+    Lint {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+    },
+
+    /// Estimate the tasks of a specification in story points
+    ///
+    /// Prompts for the points of every task without an estimate and writes
+    /// them into the tasks document as `(3pt)`. With --voters, runs planning
+    /// poker: every voter picks a card and differing votes are settled on an
+    /// agreed value.
+    Estimate {
+        /// Specification ID (defaults to active spec)
+        #[arg(short, long)]
+        spec: Option<String>,
+
+        /// Also revisit tasks that are done or already estimated
+        #[arg(long)]
+        all: bool,
+
+        /// Voters for planning poker (comma-separated)
+        #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+        voters: Vec<String>,
+
+        /// Number of a single task to estimate without prompting (1-based)
+        #[arg(long, requires = "points")]
+        task: Option<usize>,
+
+        /// Points for the task given with --task
+        #[arg(long, requires = "task")]
+        points: Option<u32>,
+    },
+
+    /// List all specifications
+    List {
+        /// Filter by status (draft, `in_progress`, completed, approved)
+        #[arg(short, long)]
+        status: Option<String>,
+
+        /// Filter by phase (requirements, design, tasks)
+        #[arg(long)]
+        phase: Option<String>,
+
+        /// Show archived specs
+        #[arg(long)]
+        archived: bool,
+
+        /// Filter by custom field (KEY=VALUE, repeatable)
+        #[arg(long = "where", value_name = "KEY=VALUE")]
+        filters: Vec<String>,
+    },
+
+    /// Show specification details
+    Show {
+        /// Specification ID
+        spec: String,
+
+        /// Show all documents
+        #[arg(short, long)]
+        all: bool,
+
+        /// Show in markdown format
+        #[arg(short, long)]
+        markdown: bool,
+
+        /// Show review comments
+        #[arg(long)]
+        comments: bool,
+    },
+
+    /// Manage review comments on specification documents
+    Comment {
+        #[command(subcommand)]
+        command: SpecCommentCommands,
+    },
+
+    /// Manage specification templates
+    Template {
+        #[command(subcommand)]
+        command: SpecTemplateCommands,
+    },
+
+    /// Delete a specification
+    Delete {
+        /// Specification ID
+        spec: String,
+
+        /// Force deletion without confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Approve a specification phase, or request changes to it
+    Approve {
+        /// Specification ID
+        spec: String,
+
+        /// Phase to approve (requirements, design, tasks)
+        phase: String,
+
+        /// Approval message
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Request changes instead of approving
+        #[arg(long)]
+        request_changes: bool,
+
+        /// Reviewer name (defaults to the current user)
+        #[arg(long)]
+        reviewer: Option<String>,
+    },
+
+    /// Set active specification
+    Activate {
+        /// Specification ID
+        spec: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SpecCommentCommands {
+    /// Comment on a specification document
+    Add {
+        /// Specification ID
+        spec: String,
+
+        /// Text of the comment
+        body: String,
+
+        /// Document commented on (requirements, design, tasks)
+        #[arg(long)]
+        phase: String,
+
+        /// Line of the document the comment refers to
+        #[arg(long)]
+        line: Option<usize>,
+
+        /// Author of the comment (defaults to the current user)
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// List the review comments on a specification
+    List {
+        /// Specification ID
+        spec: String,
+
+        /// Only list comments on this document (requirements, design, tasks)
+        #[arg(long)]
+        phase: Option<String>,
+
+        /// Only list unresolved comments
+        #[arg(long)]
+        unresolved: bool,
+    },
+
+    /// Mark a review comment as resolved
+    Resolve {
+        /// Specification ID
+        spec: String,
+
+        /// Comment number
+        id: u32,
+
+        /// Reopen the comment instead
+        #[arg(long)]
+        reopen: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SpecTemplateCommands {
+    /// List the template packs of the project
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CommentCommands {
+    /// Add a comment to a ticket
+    Add {
+        /// Text of the comment
+        body: String,
+
+        /// Ticket ID or slug (defaults to active ticket)
+        #[arg(short, long)]
+        ticket: Option<String>,
+
+        /// Author of the comment (defaults to the current user)
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// List the comments on a ticket
+    List {
+        /// Ticket ID or slug (defaults to active ticket)
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+
+    /// Change the text of a comment
+    Edit {
+        /// Comment number
+        id: u32,
+
+        /// New text of the comment
+        body: String,
+
+        /// Ticket ID or slug (defaults to active ticket)
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+
+    /// Delete a comment
+    Delete {
+        /// Comment number
+        id: u32,
+
+        /// Ticket ID or slug (defaults to active ticket)
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AttachCommands {
+    /// Copy a file into the ticket's attachments
+    Add {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// File to attach
+        file: String,
+
+        /// Name of the attachment (defaults to the file name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// List the files attached to a ticket
+    List {
+        /// Ticket ID or slug
+        ticket: String,
     },
 
-    /// Create or update requirements document
-    Requirements {
-        /// Specification ID (defaults to active spec)
-        #[arg(short, long)]
-        spec: Option<String>,
+    /// Remove an attached file
+    Remove {
+        /// Ticket ID or slug
+        ticket: String,
 
-        /// Open in editor
-        #[arg(short, long)]
-        editor: bool,
+        /// Name of the attachment
+        name: String,
+    },
+}
 
-        /// Mark as complete
-        #[arg(long)]
-        complete: bool,
+#[derive(Subcommand, Debug)]
+pub enum IncidentCommands {
+    /// Record and review the timeline of an incident
+    Timeline {
+        #[command(subcommand)]
+        command: TimelineCommands,
     },
 
-    /// Create or update design document
-    Design {
-        /// Specification ID (defaults to active spec)
-        #[arg(short, long)]
-        spec: Option<String>,
+    /// Write a postmortem pre-filled from the timeline and work log
+    Postmortem {
+        /// Ticket ID or slug (defaults to active ticket)
+        ticket: Option<String>,
 
-        /// Open in editor
+        /// Output file (defaults to stdout)
         #[arg(short, long)]
-        editor: bool,
-
-        /// Mark as complete
-        #[arg(long)]
-        complete: bool,
+        output: Option<String>,
     },
+}
 
-    /// Create or update implementation tasks
-    Tasks {
-        /// Specification ID (defaults to active spec)
-        #[arg(short, long)]
-        spec: Option<String>,
+#[derive(Subcommand, Debug)]
+pub enum TimelineCommands {
+    /// Add an entry to the timeline
+    Add {
+        /// What happened
+        message: String,
 
-        /// Open in editor
-        #[arg(short, long)]
-        editor: bool,
+        /// Ticket ID or slug (defaults to active ticket)
+        #[arg(long)]
+        ticket: Option<String>,
 
-        /// Mark as complete
+        /// Stage the entry marks (detected, mitigated, resolved)
         #[arg(long)]
-        complete: bool,
+        phase: Option<String>,
 
-        /// Export tasks to tickets
+        /// When it happened (e.g., "14:05", "2025-07-20 14:05", "2 hours ago"; defaults to now)
         #[arg(long)]
-        export_tickets: bool,
+        at: Option<String>,
     },
 
-    /// Show specification status
-    Status {
-        /// Specification ID (defaults to active spec)
-        #[arg(short, long)]
-        spec: Option<String>,
-
-        /// Show detailed progress
-        #[arg(short, long)]
-        detailed: bool,
+    /// List the timeline with the time to mitigate and resolve
+    List {
+        /// Ticket ID or slug (defaults to active ticket)
+        ticket: Option<String>,
     },
+}
 
-    /// List all specifications
-    List {
-        /// Filter by status (draft, `in_progress`, completed, approved)
-        #[arg(short, long)]
-        status: Option<String>,
+#[derive(Subcommand, Debug)]
+pub enum DoctorCommands {
+    /// List the lock files in the project storage with their holders
+    ///
+    /// Shows the process and operation holding each lock and for how long.
+    /// Locks older than `storage.lock.stale_after_secs` are stale and taken
+    /// over by the next writer.
+    Locks,
+}
 
-        /// Filter by phase (requirements, design, tasks)
-        #[arg(long)]
-        phase: Option<String>,
+#[derive(Subcommand, Debug)]
+pub enum InboxCommands {
+    /// List the inbox entries
+    List,
+
+    /// Turn each inbox entry into a ticket or discard it, interactively
+    ///
+    /// Answers are read from standard input, one per line, so they can also
+    /// be piped in.
+    Process,
+}
 
-        /// Show archived specs
+#[derive(Subcommand, Debug)]
+pub enum MilestoneCommands {
+    /// Create a milestone
+    Create {
+        /// Unique name (e.g., v1.0, sprint-12)
+        name: String,
+
+        /// Due date (YYYY-MM-DD)
         #[arg(long)]
-        archived: bool,
+        due: Option<String>,
+
+        /// What the milestone is about
+        #[arg(short, long)]
+        description: Option<String>,
     },
 
-    /// Show specification details
+    /// List milestones with their progress
+    List,
+
+    /// Show the progress and tickets of a milestone
     Show {
-        /// Specification ID
-        spec: String,
+        /// Milestone name
+        name: String,
+    },
 
-        /// Show all documents
-        #[arg(short, long)]
-        all: bool,
+    /// Change the due date or description of a milestone
+    Edit {
+        /// Milestone name
+        name: String,
 
-        /// Show in markdown format
+        /// New due date (YYYY-MM-DD; `none` clears it)
+        #[arg(long)]
+        due: Option<String>,
+
+        /// New description (an empty string clears it)
         #[arg(short, long)]
-        markdown: bool,
+        description: Option<String>,
     },
 
-    /// Delete a specification
+    /// Delete a milestone and remove it from its tickets
     Delete {
-        /// Specification ID
-        spec: String,
-
-        /// Force deletion without confirmation
-        #[arg(short, long)]
-        force: bool,
+        /// Milestone name
+        name: String,
     },
 
-    /// Approve a specification phase
-    Approve {
-        /// Specification ID
-        spec: String,
-
-        /// Phase to approve (requirements, design, tasks)
-        phase: String,
+    /// Add tickets to a milestone
+    Assign {
+        /// Milestone name
+        name: String,
 
-        /// Approval message
-        #[arg(short, long)]
-        message: Option<String>,
+        /// Ticket IDs or slugs (defaults to the active ticket)
+        tickets: Vec<String>,
     },
 
-    /// Set active specification
-    Activate {
-        /// Specification ID
-        spec: String,
+    /// Remove tickets from their milestone
+    Unassign {
+        /// Ticket IDs or slugs (defaults to the active ticket)
+        tickets: Vec<String>,
     },
 }
 
@@ -594,10 +1694,74 @@ pub enum McpCommands {
         #[arg(short = 'P', long, default_value = "3033")]
         port: Option<u16>,
 
-        /// Run as daemon
+        /// Transport to serve on (stdio, tcp, http)
+        #[arg(short, long, default_value = "stdio")]
+        transport: String,
+
+        /// Run in the background, serving over TCP or HTTP
+        ///
+        /// Stdio falls back to TCP. The server's output goes to `mcp.log_file` from the configuration.
         #[arg(short, long)]
         daemon: bool,
     },
+
+    /// Show whether a background MCP server is running
+    Status,
+
+    /// Stop the background MCP server
+    Stop,
+
+    /// Manage API tokens for the HTTP transport
+    Token {
+        #[command(subcommand)]
+        command: McpTokenCommands,
+    },
+
+    /// Show the access log of the tool calls made to the MCP server
+    Audit {
+        /// Only show calls of this tool (e.g. vibe-ticket_edit)
+        #[arg(long)]
+        tool: Option<String>,
+
+        /// Only show calls made with this API token
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Only show calls with this outcome (ok, error, denied)
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Only show calls since this date (e.g., "today", "2 days ago", "2025-07-18")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Number of most recent calls to show
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+    },
+}
+
+#[cfg(feature = "mcp")]
+#[derive(Subcommand, Debug)]
+pub enum McpTokenCommands {
+    /// Create an API token, shown once
+    Create {
+        /// Name identifying the token
+        name: String,
+
+        /// What the token may do (read-only, write, admin)
+        #[arg(long, default_value = "read-only")]
+        scope: String,
+    },
+
+    /// List the API tokens
+    List,
+
+    /// Revoke an API token
+    Revoke {
+        /// Name of the token
+        name: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -647,6 +1811,194 @@ pub enum WorktreeCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum TrashCommands {
+    /// List tickets in the trash
+    List,
+
+    /// Restore a ticket from the trash
+    Restore {
+        /// Ticket ID, ID prefix or slug
+        ticket: String,
+    },
+
+    /// Remove tickets from the trash for good
+    Empty {
+        /// Only remove tickets older than the retention period
+        #[arg(long)]
+        expired: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OutboxCommands {
+    /// List deliveries waiting to be retried
+    List,
+
+    /// Retry all queued deliveries now
+    Flush,
+
+    /// Show the most recent delivery attempts
+    Log {
+        /// Number of attempts to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NotifyCommands {
+    /// Send a test message to Slack to verify the configuration
+    Test {
+        /// Send along the route of this priority (low, medium, high, critical)
+        #[arg(long)]
+        priority: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WebhookCommands {
+    /// Register a webhook
+    Add {
+        /// Name of the webhook
+        name: String,
+
+        /// URL the events are posted to
+        url: String,
+
+        /// Secret signing the payloads with HMAC-SHA256
+        #[arg(long)]
+        secret: Option<String>,
+
+        /// Event kinds to post (comma-separated; all events if omitted)
+        #[arg(long, value_delimiter = ',')]
+        events: Vec<String>,
+    },
+
+    /// List the webhooks
+    List,
+
+    /// Remove a webhook
+    Remove {
+        /// Name of the webhook
+        name: String,
+    },
+
+    /// Send a test event to a webhook
+    Test {
+        /// Name of the webhook
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCommands {
+    /// Register a project
+    Add {
+        /// Directory of the project (defaults to the current project)
+        path: Option<String>,
+
+        /// Name of the project (defaults to its directory name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// List the registered projects; `*` marks the current one
+    List,
+
+    /// Use a registered project when running outside of any project
+    Switch {
+        /// Name of the project
+        name: String,
+    },
+
+    /// Unregister a project, leaving its files alone
+    Remove {
+        /// Name of the project
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProjectCommands {
+    /// Rename the project
+    ///
+    /// Updates `project.name` and the workspace registry. Ticket worktrees
+    /// named after the project through `{project}` in `git.worktree_prefix`
+    /// are listed, and moved with --move-worktrees. Branches are named
+    /// after tickets only and keep their names.
+    Rename {
+        /// New name of the project
+        name: String,
+
+        /// Move the ticket worktrees to the paths derived from the new name
+        #[arg(long)]
+        move_worktrees: bool,
+    },
+
+    /// Move `.vibe-ticket` to another directory, the new project root
+    ///
+    /// Rewrites configured paths under the old root and updates the
+    /// workspace registry. Ticket worktrees are listed, and moved next to
+    /// the new root with --move-worktrees.
+    Relocate {
+        /// Existing directory to move the project into
+        path: String,
+
+        /// Move the ticket worktrees to the paths derived from the new root
+        #[arg(long)]
+        move_worktrees: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ContextCommands {
+    /// Print a compact project snapshot to paste into an AI session
+    ///
+    /// Lists the active ticket, the most pressing open tickets, the phase of
+    /// the current spec and the conventions from CLAUDE.md, cut to fit the
+    /// token budget.
+    Pack {
+        /// Token budget, such as 8000, 8k or 8000-tokens
+        #[arg(long, default_value = "4000")]
+        budget: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchemaCommands {
+    /// Write the JSON Schemas of the output documents
+    ///
+    /// Every JSON document carries a `schema_version` that only changes when
+    /// a field is removed or changes its type; new fields are added without
+    /// a bump.
+    Dump {
+        /// Directory to write `<name>.schema.json` files to (prints them if omitted)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CiCommands {
+    /// Turn failing tests or findings of a report into tasks on a ticket
+    ///
+    /// Findings are deduplicated by fingerprint. Tasks whose finding no longer
+    /// appears in a later report of the same kind are completed automatically.
+    Report {
+        /// Ticket ID or slug
+        ticket: String,
+
+        /// Report file (junit XML or SARIF JSON)
+        #[arg(long)]
+        from: String,
+
+        /// Report format (junit, sarif); detected from the file if omitted
+        #[arg(long)]
+        format: Option<String>,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -735,19 +2087,58 @@ mod tests {
                 title,
                 description,
                 priority,
+                severity,
+                template,
                 tags,
                 start,
+                ..
             } => {
-                assert_eq!(slug, "fix-bug");
+                assert_eq!(slug.as_deref(), Some("fix-bug"));
                 assert!(title.is_none());
                 assert!(description.is_none());
                 assert_eq!(priority, "medium");
+                assert!(severity.is_none());
+                assert!(template.is_none());
                 assert!(tags.is_none());
                 assert!(!start);
             },
             _ => panic!("Expected New command"),
         }
 
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "new",
+            "db-outage",
+            "--template",
+            "incident",
+            "--severity",
+            "S1",
+        ]);
+        match cli.command {
+            Commands::New {
+                severity, template, ..
+            } => {
+                assert_eq!(severity, Some("S1".to_string()));
+                assert_eq!(template, Some("incident".to_string()));
+            },
+            _ => panic!("Expected New command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "new", "--from-json", "-"]);
+        match cli.command {
+            Commands::New {
+                slug, from_json, ..
+            } => {
+                assert!(slug.is_none());
+                assert_eq!(from_json.as_deref(), Some("-"));
+            },
+            _ => panic!("Expected New command"),
+        }
+        assert!(
+            Cli::try_parse_from(["vibe-ticket", "new", "--from-json", "-", "--title", "T"])
+                .is_err()
+        );
+
         let cli = Cli::parse_from([
             "vibe-ticket",
             "new",
@@ -769,7 +2160,7 @@ mod tests {
                 start,
                 ..
             } => {
-                assert_eq!(slug, "feature-auth");
+                assert_eq!(slug.as_deref(), Some("feature-auth"));
                 assert_eq!(title, Some("Add authentication".to_string()));
                 assert_eq!(priority, "high");
                 assert_eq!(tags, Some("auth,security".to_string()));
@@ -777,6 +2168,24 @@ mod tests {
             },
             _ => panic!("Expected New command"),
         }
+
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "new",
+            "--from-url",
+            "https://github.com/org/repo/issues/42",
+        ]);
+        match cli.command {
+            Commands::New { slug, from_url, .. } => {
+                assert!(slug.is_none());
+                assert_eq!(
+                    from_url.as_deref(),
+                    Some("https://github.com/org/repo/issues/42")
+                );
+            },
+            _ => panic!("Expected New command"),
+        }
+        assert!(Cli::try_parse_from(["vibe-ticket", "new"]).is_err());
     }
 
     /// Test list command with various filters
@@ -1000,7 +2409,14 @@ mod tests {
     /// Test spec subcommands
     #[test]
     fn test_spec_commands() {
-        let cli = Cli::parse_from(["vibe-ticket", "spec", "init", "New Feature Spec"]);
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "spec",
+            "init",
+            "New Feature Spec",
+            "--template",
+            "rfc",
+        ]);
         match cli.command {
             Commands::Spec { command } => match command {
                 SpecCommands::Init {
@@ -1008,11 +2424,17 @@ mod tests {
                     description,
                     ticket,
                     tags,
+                    fields,
+                    amends,
+                    template,
                 } => {
                     assert_eq!(title, "New Feature Spec");
                     assert!(description.is_none());
                     assert!(ticket.is_none());
                     assert!(tags.is_none());
+                    assert!(fields.is_empty());
+                    assert!(amends.is_none());
+                    assert_eq!(template.as_deref(), Some("rfc"));
                 },
                 _ => panic!("Expected Spec Init command"),
             },
@@ -1101,11 +2523,28 @@ mod tests {
         }
 
         // Test search with regex
-        let cli = Cli::parse_from(["vibe-ticket", "search", "bug.*fix", "--regex"]);
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "search",
+            "bug.*fix",
+            "--regex",
+            "--limit",
+            "5",
+            "--jobs",
+            "2",
+        ]);
         match cli.command {
-            Commands::Search { query, regex, .. } => {
-                assert_eq!(query, "bug.*fix");
+            Commands::Search {
+                query,
+                regex,
+                limit,
+                jobs,
+                ..
+            } => {
+                assert_eq!(query.as_deref(), Some("bug.*fix"));
                 assert!(regex);
+                assert_eq!(limit, Some(5));
+                assert_eq!(jobs, Some(2));
             },
             _ => panic!("Expected Search command"),
         }
@@ -1125,10 +2564,16 @@ mod tests {
                 format,
                 output,
                 include_archived,
+                sort_by,
+                reproducible,
+                include_tasks,
             } => {
+                assert!(!include_tasks);
                 assert_eq!(format, "yaml");
                 assert_eq!(output, Some("tickets.yaml".to_string()));
                 assert!(include_archived);
+                assert_eq!(sort_by, "created");
+                assert!(!reproducible);
             },
             _ => panic!("Expected Export command"),
         }
@@ -1166,11 +2611,15 @@ mod tests {
                 message,
                 archive,
                 pr,
+                pr_url,
+                force,
             } => {
                 assert_eq!(ticket, Some("feature-123".to_string()));
                 assert_eq!(message, Some("Completed feature".to_string()));
                 assert!(archive);
                 assert!(pr);
+                assert_eq!(pr_url, None);
+                assert!(!force);
             },
             _ => panic!("Expected Close command"),
         }
@@ -1248,11 +2697,15 @@ mod tests {
                 format,
                 skip_validation,
                 dry_run,
+                verify,
+                epic,
             } => {
                 assert_eq!(file, "data.json");
                 assert!(format.is_none());
                 assert!(!skip_validation);
                 assert!(!dry_run);
+                assert!(!verify);
+                assert!(!epic);
             },
             _ => panic!("Expected Import command"),
         }
@@ -1272,6 +2725,7 @@ mod tests {
                 format,
                 skip_validation,
                 dry_run,
+                ..
             } => {
                 assert_eq!(file, "tickets.csv");
                 assert_eq!(format, Some("csv".to_string()));
@@ -1292,11 +2746,14 @@ mod tests {
                 tasks,
                 history,
                 markdown,
+                full_description,
+                ..
             } => {
                 assert_eq!(ticket, "ABC-123");
                 assert!(!tasks);
                 assert!(!history);
                 assert!(!markdown);
+                assert!(!full_description);
             },
             _ => panic!("Expected Show command"),
         }
@@ -1308,6 +2765,7 @@ mod tests {
             "--tasks",
             "--history",
             "--markdown",
+            "--full-description",
         ]);
         match cli.command {
             Commands::Show {
@@ -1315,11 +2773,32 @@ mod tests {
                 tasks,
                 history,
                 markdown,
+                full_description,
+                ..
             } => {
                 assert_eq!(ticket, "feature-1");
                 assert!(tasks);
                 assert!(history);
                 assert!(markdown);
+                assert!(full_description);
+            },
+            _ => panic!("Expected Show command"),
+        }
+
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "show",
+            "feature-1",
+            "--sections",
+            "tasks,history",
+            "--expand",
+        ]);
+        match cli.command {
+            Commands::Show {
+                sections, expand, ..
+            } => {
+                assert_eq!(sections, vec!["tasks", "history"]);
+                assert!(expand);
             },
             _ => panic!("Expected Show command"),
         }
@@ -1330,18 +2809,28 @@ mod tests {
     fn test_check_command() {
         let cli = Cli::parse_from(["vibe-ticket", "check"]);
         match cli.command {
-            Commands::Check { detailed, stats } => {
+            Commands::Check {
+                detailed,
+                stats,
+                watch,
+            } => {
                 assert!(!detailed);
                 assert!(!stats);
+                assert!(!watch);
             },
             _ => panic!("Expected Check command"),
         }
 
-        let cli = Cli::parse_from(["vibe-ticket", "check", "--detailed", "--stats"]);
+        let cli = Cli::parse_from(["vibe-ticket", "check", "--detailed", "--stats", "--watch"]);
         match cli.command {
-            Commands::Check { detailed, stats } => {
+            Commands::Check {
+                detailed,
+                stats,
+                watch,
+            } => {
                 assert!(detailed);
                 assert!(stats);
+                assert!(watch);
             },
             _ => panic!("Expected Check command"),
         }
@@ -1352,8 +2841,10 @@ mod tests {
     fn test_archive_command() {
         let cli = Cli::parse_from(["vibe-ticket", "archive", "old-ticket"]);
         match cli.command {
-            Commands::Archive { ticket, unarchive } => {
-                assert_eq!(ticket, "old-ticket");
+            Commands::Archive {
+                ticket, unarchive, ..
+            } => {
+                assert_eq!(ticket.as_deref(), Some("old-ticket"));
                 assert!(!unarchive);
             },
             _ => panic!("Expected Archive command"),
@@ -1361,14 +2852,51 @@ mod tests {
 
         let cli = Cli::parse_from(["vibe-ticket", "archive", "ticket-123", "--unarchive"]);
         match cli.command {
-            Commands::Archive { ticket, unarchive } => {
-                assert_eq!(ticket, "ticket-123");
+            Commands::Archive {
+                ticket, unarchive, ..
+            } => {
+                assert_eq!(ticket.as_deref(), Some("ticket-123"));
                 assert!(unarchive);
             },
             _ => panic!("Expected Archive command"),
         }
     }
 
+    /// Test bulk archive filters
+    #[test]
+    fn test_archive_bulk_filters() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "archive",
+            "--status",
+            "done",
+            "--until",
+            "3 months ago",
+            "--dry-run",
+        ]);
+        match cli.command {
+            Commands::Archive {
+                ticket,
+                status,
+                until,
+                dry_run,
+                ..
+            } => {
+                assert!(ticket.is_none());
+                assert_eq!(status.as_deref(), Some("done"));
+                assert_eq!(until.as_deref(), Some("3 months ago"));
+                assert!(dry_run);
+            },
+            _ => panic!("Expected Archive command"),
+        }
+
+        assert!(Cli::try_parse_from(["vibe-ticket", "archive"]).is_err());
+        assert!(
+            Cli::try_parse_from(["vibe-ticket", "archive", "old-ticket", "--status", "done"])
+                .is_err()
+        );
+    }
+
     /// Test search command filters
     #[test]
     fn test_search_filters() {
@@ -1381,7 +2909,7 @@ mod tests {
                 tags,
                 ..
             } => {
-                assert_eq!(query, "auth");
+                assert_eq!(query.as_deref(), Some("auth"));
                 assert!(title);
                 assert!(!description);
                 assert!(!tags);
@@ -1404,7 +2932,7 @@ mod tests {
                 tags,
                 ..
             } => {
-                assert_eq!(query, "security");
+                assert_eq!(query.as_deref(), Some("security"));
                 assert!(!title);
                 assert!(description);
                 assert!(tags);
@@ -1432,10 +2960,12 @@ mod tests {
                     status,
                     phase,
                     archived,
+                    filters,
                 } => {
                     assert_eq!(status, Some("draft".to_string()));
                     assert_eq!(phase, Some("requirements".to_string()));
                     assert!(archived);
+                    assert!(filters.is_empty());
                 },
                 _ => panic!("Expected Spec List command"),
             },
@@ -1450,6 +2980,8 @@ mod tests {
             "design",
             "--message",
             "LGTM",
+            "--reviewer",
+            "alice",
         ]);
         match cli.command {
             Commands::Spec { command } => match command {
@@ -1457,10 +2989,14 @@ mod tests {
                     spec,
                     phase,
                     message,
+                    request_changes,
+                    reviewer,
                 } => {
                     assert_eq!(spec, "spec-123");
                     assert_eq!(phase, "design");
                     assert_eq!(message, Some("LGTM".to_string()));
+                    assert!(!request_changes);
+                    assert_eq!(reviewer.as_deref(), Some("alice"));
                 },
                 _ => panic!("Expected Spec Approve command"),
             },
@@ -1468,6 +3004,53 @@ mod tests {
         }
     }
 
+    /// Test spec review comment commands
+    #[test]
+    fn test_spec_comment() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "spec",
+            "comment",
+            "add",
+            "spec-123",
+            "--phase",
+            "design",
+            "--line",
+            "42",
+            "why not use queue?",
+        ]);
+        match cli.command {
+            Commands::Spec {
+                command:
+                    SpecCommands::Comment {
+                        command:
+                            SpecCommentCommands::Add {
+                                spec,
+                                body,
+                                phase,
+                                line,
+                                author,
+                            },
+                    },
+            } => {
+                assert_eq!(spec, "spec-123");
+                assert_eq!(body, "why not use queue?");
+                assert_eq!(phase, "design");
+                assert_eq!(line, Some(42));
+                assert!(author.is_none());
+            },
+            _ => panic!("Expected Spec Comment Add command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "spec", "show", "spec-123", "--comments"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Spec {
+                command: SpecCommands::Show { comments: true, .. }
+            }
+        ));
+    }
+
     /// Test worktree prune options
     #[test]
     fn test_worktree_prune() {
@@ -1512,4 +3095,521 @@ mod tests {
             _ => panic!("Expected Worktree command"),
         }
     }
+
+    /// Test outbox subcommands
+    #[test]
+    fn test_outbox_commands() {
+        let cli = Cli::parse_from(["vibe-ticket", "outbox", "list"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Outbox {
+                command: OutboxCommands::List
+            }
+        ));
+
+        let cli = Cli::parse_from(["vibe-ticket", "outbox", "flush"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Outbox {
+                command: OutboxCommands::Flush
+            }
+        ));
+
+        let cli = Cli::parse_from(["vibe-ticket", "outbox", "log", "--limit", "5"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Outbox {
+                command: OutboxCommands::Log { limit: 5 }
+            }
+        ));
+    }
+
+    /// Test notify subcommands
+    #[test]
+    fn test_notify_commands() {
+        let cli = Cli::parse_from(["vibe-ticket", "notify", "test", "--priority", "critical"]);
+        match cli.command {
+            Commands::Notify {
+                command: NotifyCommands::Test { priority },
+            } => assert_eq!(priority.as_deref(), Some("critical")),
+            _ => panic!("Expected notify test command"),
+        }
+    }
+
+    /// Test incident subcommands
+    #[test]
+    fn test_incident_commands() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "incident",
+            "timeline",
+            "add",
+            "Rolled back",
+            "--phase",
+            "mitigated",
+            "--at",
+            "14:05",
+        ]);
+        match cli.command {
+            Commands::Incident {
+                command:
+                    IncidentCommands::Timeline {
+                        command:
+                            TimelineCommands::Add {
+                                message,
+                                ticket,
+                                phase,
+                                at,
+                            },
+                    },
+            } => {
+                assert_eq!(message, "Rolled back");
+                assert!(ticket.is_none());
+                assert_eq!(phase.as_deref(), Some("mitigated"));
+                assert_eq!(at.as_deref(), Some("14:05"));
+            },
+            _ => panic!("Expected incident timeline add command"),
+        }
+
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "incident",
+            "postmortem",
+            "api-down",
+            "-o",
+            "pm.md",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Commands::Incident {
+                command: IncidentCommands::Postmortem {
+                    ticket: Some(_),
+                    output: Some(_)
+                }
+            }
+        ));
+    }
+
+    /// Test webhook subcommands
+    #[test]
+    fn test_webhook_commands() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "webhook",
+            "add",
+            "ci",
+            "https://ci.example.com/hook",
+            "--secret",
+            "s3cret",
+            "--events",
+            "created,closed",
+        ]);
+        match cli.command {
+            Commands::Webhook {
+                command:
+                    WebhookCommands::Add {
+                        name,
+                        url,
+                        secret,
+                        events,
+                    },
+            } => {
+                assert_eq!(name, "ci");
+                assert_eq!(url, "https://ci.example.com/hook");
+                assert_eq!(secret.as_deref(), Some("s3cret"));
+                assert_eq!(events, ["created", "closed"]);
+            },
+            _ => panic!("Expected webhook add command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "webhook", "test", "ci"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Webhook {
+                command: WebhookCommands::Test { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_schema_dump_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "schema", "dump", "--output", "schemas"]);
+        match cli.command {
+            Commands::Schema {
+                command: SchemaCommands::Dump { output },
+            } => assert_eq!(output.as_deref(), Some("schemas")),
+            _ => panic!("Expected Schema command"),
+        }
+    }
+
+    #[test]
+    fn test_suggest_assignee_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "suggest-assignee", "migrate", "--assign"]);
+        match cli.command {
+            Commands::SuggestAssignee { ticket, assign } => {
+                assert_eq!(ticket, "migrate");
+                assert!(assign);
+            },
+            _ => panic!("Expected SuggestAssignee command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "new", "migrate", "--auto-assign"]);
+        assert!(matches!(
+            cli.command,
+            Commands::New {
+                auto_assign: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_workspace_commands() {
+        let cli = Cli::parse_from(["vibe-ticket", "workspace", "add", "../api", "--name", "api"]);
+        match cli.command {
+            Commands::Workspace {
+                command: WorkspaceCommands::Add { path, name },
+            } => {
+                assert_eq!(path.as_deref(), Some("../api"));
+                assert_eq!(name.as_deref(), Some("api"));
+            },
+            _ => panic!("Expected Workspace Add command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "workspace", "switch", "web"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Workspace {
+                command: WorkspaceCommands::Switch { ref name },
+            } if name == "web"
+        ));
+
+        let cli = Cli::parse_from(["vibe-ticket", "list", "--all-projects", "--open"]);
+        assert!(matches!(
+            cli.command,
+            Commands::List {
+                all_projects: true,
+                ..
+            }
+        ));
+        assert!(Cli::try_parse_from(["vibe-ticket", "list", "--all-projects", "--tree"]).is_err());
+    }
+
+    #[test]
+    fn test_project_commands() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "project",
+            "rename",
+            "api",
+            "--move-worktrees",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Commands::Project {
+                command: ProjectCommands::Rename {
+                    ref name,
+                    move_worktrees: true,
+                },
+            } if name == "api"
+        ));
+
+        let cli = Cli::parse_from(["vibe-ticket", "project", "relocate", "../api"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Project {
+                command: ProjectCommands::Relocate {
+                    ref path,
+                    move_worktrees: false,
+                },
+            } if path == "../api"
+        ));
+    }
+
+    #[test]
+    fn test_context_pack_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "context", "pack", "--budget", "8000-tokens"]);
+        match cli.command {
+            Commands::Context {
+                command: ContextCommands::Pack { budget },
+            } => assert_eq!(budget, "8000-tokens"),
+            _ => panic!("Expected Context Pack command"),
+        }
+    }
+
+    #[test]
+    fn test_ci_report_command() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "ci",
+            "report",
+            "fix-build",
+            "--from",
+            "target/junit.xml",
+        ]);
+        match cli.command {
+            Commands::Ci {
+                command:
+                    CiCommands::Report {
+                        ticket,
+                        from,
+                        format,
+                    },
+            } => {
+                assert_eq!(ticket, "fix-build");
+                assert_eq!(from, "target/junit.xml");
+                assert!(format.is_none());
+            },
+            _ => panic!("Expected Ci command"),
+        }
+    }
+
+    #[test]
+    fn test_link_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "link", "api", "--depends-on", "db,auth"]);
+        match cli.command {
+            Commands::Link {
+                ticket,
+                depends_on,
+                blocks,
+                duplicates,
+                remove,
+                ..
+            } => {
+                assert_eq!(ticket, "api");
+                assert_eq!(depends_on, vec!["db", "auth"]);
+                assert!(blocks.is_empty());
+                assert!(duplicates.is_empty());
+                assert!(!remove);
+            },
+            _ => panic!("Expected Link command"),
+        }
+
+        assert!(Cli::try_parse_from(["vibe-ticket", "link", "api"]).is_err());
+        assert!(Cli::try_parse_from(["vibe-ticket", "link", "api", "--relates-to", "web"]).is_ok());
+
+        let cli = Cli::parse_from(["vibe-ticket", "unlink", "api", "--duplicates", "old-api"]);
+        match cli.command {
+            Commands::Unlink {
+                ticket,
+                depends_on,
+                duplicates,
+                ..
+            } => {
+                assert_eq!(ticket, "api");
+                assert!(depends_on.is_empty());
+                assert_eq!(duplicates, vec!["old-api"]);
+            },
+            _ => panic!("Expected Unlink command"),
+        }
+        assert!(Cli::try_parse_from(["vibe-ticket", "unlink", "api"]).is_err());
+
+        let cli = Cli::parse_from(["vibe-ticket", "relate", "crash", "caused_by", "refactor"]);
+        match cli.command {
+            Commands::Relate {
+                ticket,
+                kind,
+                tickets,
+            } => {
+                assert_eq!(ticket, "crash");
+                assert_eq!(kind, "caused_by");
+                assert_eq!(tickets, vec!["refactor"]);
+            },
+            _ => panic!("Expected Relate command"),
+        }
+        assert!(Cli::try_parse_from(["vibe-ticket", "unrelate", "crash", "follows"]).is_err());
+    }
+
+    #[test]
+    fn test_plugin_command_parsing() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "x",
+            "jira",
+            "sync",
+            "--project",
+            "ABC",
+            "--dry-run",
+        ]);
+        match cli.command {
+            Commands::Plugin { plugin, args } => {
+                assert_eq!(plugin.as_deref(), Some("jira"));
+                assert_eq!(args, vec!["sync", "--project", "ABC", "--dry-run"]);
+            },
+            _ => panic!("Expected Plugin command"),
+        }
+    }
+
+    #[test]
+    fn test_log_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "log", "suspect race", "--ticket", "flaky"]);
+        match cli.command {
+            Commands::Log {
+                message,
+                ticket,
+                standup,
+                since,
+            } => {
+                assert_eq!(message, Some("suspect race".to_string()));
+                assert_eq!(ticket, Some("flaky".to_string()));
+                assert!(!standup);
+                assert_eq!(since, "yesterday");
+            },
+            _ => panic!("Expected Log command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "log", "--standup", "--since", "2 days ago"]);
+        match cli.command {
+            Commands::Log {
+                message,
+                standup,
+                since,
+                ..
+            } => {
+                assert!(message.is_none());
+                assert!(standup);
+                assert_eq!(since, "2 days ago");
+            },
+            _ => panic!("Expected Log command"),
+        }
+
+        assert!(Cli::try_parse_from(["vibe-ticket", "log"]).is_err());
+    }
+
+    #[test]
+    fn test_spec_custom_fields() {
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "spec",
+            "set",
+            "spec-1",
+            "team=core",
+            "risk=high",
+            "--unset",
+            "release",
+        ]);
+        match cli.command {
+            Commands::Spec {
+                command:
+                    SpecCommands::Set {
+                        spec,
+                        fields,
+                        unset,
+                    },
+            } => {
+                assert_eq!(spec, "spec-1");
+                assert_eq!(fields, vec!["team=core", "risk=high"]);
+                assert_eq!(unset, vec!["release"]);
+            },
+            _ => panic!("Expected Spec Set command"),
+        }
+
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "spec",
+            "diff",
+            "spec-1",
+            "--from",
+            "0.1.0",
+            "--to",
+            "0.2.0",
+            "-d",
+            "design",
+        ]);
+        match cli.command {
+            Commands::Spec {
+                command:
+                    SpecCommands::Diff {
+                        spec,
+                        from,
+                        to,
+                        document,
+                    },
+            } => {
+                assert_eq!(spec.as_deref(), Some("spec-1"));
+                assert_eq!(from.as_deref(), Some("0.1.0"));
+                assert_eq!(to.as_deref(), Some("0.2.0"));
+                assert_eq!(document.as_deref(), Some("design"));
+            },
+            _ => panic!("Expected Spec Diff command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "spec", "check-refs", "spec-1"]);
+        match cli.command {
+            Commands::Spec {
+                command: SpecCommands::CheckRefs { spec },
+            } => assert_eq!(spec.as_deref(), Some("spec-1")),
+            _ => panic!("Expected Spec CheckRefs command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "spec", "lint", "spec-1"]);
+        match cli.command {
+            Commands::Spec {
+                command: SpecCommands::Lint { spec },
+            } => assert_eq!(spec.as_deref(), Some("spec-1")),
+            _ => panic!("Expected Spec Lint command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "spec", "link", "spec-1", "fix-login"]);
+        match cli.command {
+            Commands::Spec {
+                command:
+                    SpecCommands::Link {
+                        spec,
+                        ticket,
+                        unlink,
+                    },
+            } => {
+                assert_eq!(spec, "spec-1");
+                assert_eq!(ticket.as_deref(), Some("fix-login"));
+                assert!(!unlink);
+            },
+            _ => panic!("Expected Spec Link command"),
+        }
+        assert!(Cli::try_parse_from(["vibe-ticket", "spec", "link", "spec-1"]).is_err());
+        assert!(Cli::try_parse_from(["vibe-ticket", "spec", "link", "spec-1", "--unlink"]).is_ok());
+
+        let cli = Cli::parse_from(["vibe-ticket", "spec", "list", "--where", "team=core"]);
+        match cli.command {
+            Commands::Spec {
+                command: SpecCommands::List { filters, .. },
+            } => assert_eq!(filters, vec!["team=core"]),
+            _ => panic!("Expected Spec List command"),
+        }
+    }
+
+    #[test]
+    fn test_inbox_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "in", "random", "idea about caching"]);
+        match cli.command {
+            Commands::Inbox { command, text } => {
+                assert!(command.is_none());
+                assert_eq!(text, vec!["random", "idea about caching"]);
+            },
+            _ => panic!("Expected Inbox command"),
+        }
+
+        let cli = Cli::parse_from(["vibe-ticket", "in", "process"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Inbox {
+                command: Some(InboxCommands::Process),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_move_command() {
+        let cli = Cli::parse_from(["vibe-ticket", "move", "fix-login", "--to", "../other"]);
+        match cli.command {
+            Commands::Move { ticket, to } => {
+                assert_eq!(ticket, "fix-login");
+                assert_eq!(to, "../other");
+            },
+            _ => panic!("Expected Move command"),
+        }
+
+        assert!(Cli::try_parse_from(["vibe-ticket", "move", "fix-login"]).is_err());
+    }
 }
@@ -0,0 +1,311 @@
+//! Shell completion scripts
+//!
+//! The scripts are generated from the clap definition of the CLI, so new
+//! commands and flags complete without further changes. Arguments taking a
+//! ticket reference complete the current slugs and short IDs by calling the
+//! hidden `__complete-tickets` command at completion time.
+
+use std::fmt::Write as FmtWrite;
+
+use clap::Command;
+
+use crate::error::{Result, VibeTicketError};
+
+/// Name of the hidden command listing ticket references
+pub const COMPLETE_TICKETS: &str = "__complete-tickets";
+
+/// Argument IDs whose values are ticket references
+const TICKET_ARGS: [&str; 8] = [
+    "ticket",
+    "tickets",
+    "parent",
+    "depends_on",
+    "blocks",
+    "duplicates",
+    "relates_to",
+    "supersedes",
+];
+
+/// Shell to generate a completion script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl std::str::FromStr for Shell {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Unsupported shell: {s}. Supported shells: bash, zsh, fish"
+            ))),
+        }
+    }
+}
+
+/// Completion data of one command or subcommand
+#[derive(Debug)]
+struct CommandSpec {
+    /// Subcommand names from the root, empty for the root itself
+    path: Vec<String>,
+    /// Visible subcommands with their descriptions
+    subcommands: Vec<(String, String)>,
+    /// Long and short flags
+    options: Vec<String>,
+    /// Flags whose value is a ticket reference
+    ticket_options: Vec<String>,
+    /// Whether a positional argument is a ticket reference
+    ticket_positional: bool,
+}
+
+/// Generates the completion script of `cmd` for `shell`
+pub fn generate(shell: Shell, mut cmd: Command) -> String {
+    // Building propagates the global flags to the subcommands
+    cmd.build();
+    let name = cmd.get_name().to_string();
+    let mut specs = Vec::new();
+    collect(&cmd, &mut Vec::new(), &mut specs);
+
+    match shell {
+        Shell::Bash => bash(&name, &specs),
+        Shell::Zsh => format!(
+            "#compdef {name}\n\nautoload -U +X bashcompinit && bashcompinit\n\n{}",
+            bash(&name, &specs)
+        ),
+        Shell::Fish => fish(&name, &specs),
+    }
+}
+
+fn collect(cmd: &Command, path: &mut Vec<String>, specs: &mut Vec<CommandSpec>) {
+    let subcommands: Vec<&Command> = cmd
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name() != "help")
+        .collect();
+    let is_ticket = |id: &str| TICKET_ARGS.contains(&id);
+    let arguments: Vec<_> = cmd.get_arguments().filter(|a| !a.is_hide_set()).collect();
+
+    specs.push(CommandSpec {
+        path: path.clone(),
+        subcommands: subcommands
+            .iter()
+            .map(|sub| {
+                let about = sub.get_about().map(ToString::to_string).unwrap_or_default();
+                (sub.get_name().to_string(), about)
+            })
+            .collect(),
+        options: arguments
+            .iter()
+            .flat_map(|arg| {
+                let long = arg.get_long().map(|long| format!("--{long}"));
+                let short = arg.get_short().map(|short| format!("-{short}"));
+                long.into_iter().chain(short)
+            })
+            .collect(),
+        ticket_options: arguments
+            .iter()
+            .filter(|arg| is_ticket(arg.get_id().as_str()))
+            .flat_map(|arg| {
+                let long = arg.get_long().map(|long| format!("--{long}"));
+                let short = arg.get_short().map(|short| format!("-{short}"));
+                long.into_iter().chain(short)
+            })
+            .collect(),
+        ticket_positional: arguments
+            .iter()
+            .any(|arg| arg.is_positional() && is_ticket(arg.get_id().as_str())),
+    });
+
+    for sub in subcommands {
+        path.push(sub.get_name().to_string());
+        collect(sub, path, specs);
+        path.pop();
+    }
+}
+
+/// Bash script: tables of the command tree and a completion function
+fn bash(name: &str, specs: &[CommandSpec]) -> String {
+    let function = format!("_{}", name.replace('-', "_"));
+    let table = |select: &dyn Fn(&CommandSpec) -> Option<String>| {
+        let mut cases = String::new();
+        for spec in specs {
+            if let Some(words) = select(spec) {
+                let key = spec.path.join(" ");
+                let _ = writeln!(cases, "        \"{key}\") echo \"{words}\" ;;");
+            }
+        }
+        cases
+    };
+    let subcommands = table(&|spec| {
+        (!spec.subcommands.is_empty()).then(|| {
+            spec.subcommands
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    });
+    let options = table(&|spec| Some(spec.options.join(" ")));
+    let ticket_options =
+        table(&|spec| (!spec.ticket_options.is_empty()).then(|| spec.ticket_options.join(" ")));
+    let ticket_positional = table(&|spec| spec.ticket_positional.then(|| "1".to_string()));
+
+    format!(
+        r#"# {name} completion for bash
+{function}_subcommands() {{
+    case "$1" in
+{subcommands}    esac
+}}
+
+{function}_options() {{
+    case "$1" in
+{options}    esac
+}}
+
+{function}_ticket_options() {{
+    case "$1" in
+{ticket_options}    esac
+}}
+
+{function}_ticket_positional() {{
+    case "$1" in
+{ticket_positional}    esac
+}}
+
+{function}_tickets() {{
+    {name} {COMPLETE_TICKETS} 2>/dev/null | cut -f1
+}}
+
+{function}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local path="" word i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        word="${{COMP_WORDS[i]}}"
+        case " $({function}_subcommands "$path") " in
+            *" $word "*) path="${{path:+$path }}$word" ;;
+        esac
+    done
+
+    case " $({function}_ticket_options "$path") " in
+        *" $prev "*)
+            COMPREPLY=($(compgen -W "$({function}_tickets)" -- "$cur"))
+            return
+            ;;
+    esac
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "$({function}_options "$path")" -- "$cur"))
+        return
+    fi
+    local subcommands
+    subcommands="$({function}_subcommands "$path")"
+    if [[ -n "$subcommands" ]]; then
+        COMPREPLY=($(compgen -W "$subcommands" -- "$cur"))
+    elif [[ -n "$({function}_ticket_positional "$path")" ]]; then
+        COMPREPLY=($(compgen -W "$({function}_tickets)" -- "$cur"))
+    fi
+}}
+
+complete -F {function} -o default {name}
+"#
+    )
+}
+
+/// Fish script: one `complete` line per subcommand and flag
+fn fish(name: &str, specs: &[CommandSpec]) -> String {
+    let mut script = format!(
+        "# {name} completion for fish\ncomplete -c {name} -f\n\
+         function __{name}_tickets\n    {name} {COMPLETE_TICKETS} 2>/dev/null\nend\n\n"
+    );
+    for spec in specs {
+        let condition = fish_condition(spec, specs);
+        for (sub, about) in &spec.subcommands {
+            let _ = writeln!(
+                script,
+                "complete -c {name} -n '{condition}' -a '{sub}' -d '{}'",
+                about.replace('\'', "\\'")
+            );
+        }
+        for option in &spec.options {
+            let flag = option.strip_prefix("--").map_or_else(
+                || format!("-s {}", option.trim_start_matches('-')),
+                |long| format!("-l {long}"),
+            );
+            let _ = writeln!(script, "complete -c {name} -n '{condition}' {flag}");
+        }
+        for option in &spec.ticket_options {
+            if let Some(long) = option.strip_prefix("--") {
+                let _ = writeln!(
+                    script,
+                    "complete -c {name} -n '{condition}' -l {long} -x -a '(__{name}_tickets)'"
+                );
+            }
+        }
+        if spec.ticket_positional {
+            let _ = writeln!(
+                script,
+                "complete -c {name} -n '{condition}' -a '(__{name}_tickets)'"
+            );
+        }
+    }
+    script
+}
+
+/// Condition matching the command line of `spec` and not of its subcommands
+fn fish_condition(spec: &CommandSpec, specs: &[CommandSpec]) -> String {
+    let mut parts: Vec<String> = if spec.path.is_empty() {
+        vec!["__fish_use_subcommand".to_string()]
+    } else {
+        spec.path
+            .iter()
+            .map(|name| format!("__fish_seen_subcommand_from {name}"))
+            .collect()
+    };
+    if !spec.path.is_empty() && !spec.subcommands.is_empty() {
+        let children: Vec<&str> = specs
+            .iter()
+            .filter(|other| other.path.len() == spec.path.len() + 1)
+            .filter(|other| other.path.starts_with(&spec.path))
+            .filter_map(|other| other.path.last().map(String::as_str))
+            .collect();
+        parts.push(format!(
+            "not __fish_seen_subcommand_from {}",
+            children.join(" ")
+        ));
+    }
+    parts.join("; and ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_generate_completions() {
+        let bash = generate(Shell::Bash, Cli::command());
+        assert!(bash.contains("complete -F _vibe_ticket -o default vibe-ticket"));
+        assert!(bash.contains("        \"show\") echo \"1\" ;;"));
+        assert!(bash.contains("        \"task add\") echo \"--ticket -t\" ;;"));
+        assert!(bash.contains(&format!("vibe-ticket {COMPLETE_TICKETS}")));
+        assert!(!bash.contains(&format!("\"{COMPLETE_TICKETS}\"")));
+        assert!(!bash.contains(" help"));
+
+        let zsh = generate(Shell::Zsh, Cli::command());
+        assert!(zsh.starts_with("#compdef vibe-ticket"));
+
+        let fish = generate(Shell::Fish, Cli::command());
+        assert!(fish.contains(
+            "complete -c vibe-ticket -n '__fish_seen_subcommand_from show' -a '(__vibe-ticket_tickets)'"
+        ));
+        assert!(fish.contains("-n '__fish_use_subcommand' -a 'list'"));
+
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Date formatting and parsing for terminal output
+//!
+//! Dates are shown in local time with the `ui.date_format` strftime pattern
+//! of the project, or relative to now (`3 days ago`) when
+//! `ui.relative_dates` is enabled. The global `--absolute-dates` flag turns
+//! relative dates off for scripts. Machine-readable output (JSON, CSV, YAML)
+//! keeps RFC 3339 timestamps.
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+use super::handlers::parse_date_filter;
+use crate::cli::find_project_root;
+use crate::config::{Config, UiConfig};
+use crate::error::{Result, VibeTicketError};
+
+/// Default `ui.date_format`
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Units of relative dates, longest first
+const UNITS: [(i64, &str); 6] = [
+    (365 * 86_400, "year"),
+    (30 * 86_400, "month"),
+    (7 * 86_400, "week"),
+    (86_400, "day"),
+    (3_600, "hour"),
+    (60, "minute"),
+];
+
+/// How dates are shown to and read from the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateFormat {
+    pattern: String,
+    relative: bool,
+}
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        Self {
+            pattern: DEFAULT_DATE_FORMAT.to_string(),
+            relative: false,
+        }
+    }
+}
+
+impl DateFormat {
+    /// Builds the date format from the UI configuration
+    ///
+    /// An invalid `ui.date_format` falls back to [`DEFAULT_DATE_FORMAT`].
+    pub fn from_config(ui: &UiConfig) -> Self {
+        let pattern = if validate(&ui.date_format).is_ok() {
+            ui.date_format.clone()
+        } else {
+            DEFAULT_DATE_FORMAT.to_string()
+        };
+        Self {
+            pattern,
+            relative: ui.relative_dates,
+        }
+    }
+
+    /// Builds the date format from the configuration of the given project
+    ///
+    /// Falls back to the default format outside a project or when the
+    /// configuration cannot be read.
+    pub fn for_project(project_dir: Option<&str>) -> Self {
+        find_project_root(project_dir)
+            .ok()
+            .and_then(|root| Config::load_for_project(&root).ok())
+            .map_or_else(Self::default, |config| Self::from_config(&config.ui))
+    }
+
+    /// Always shows absolute dates
+    #[must_use]
+    pub const fn absolute(mut self) -> Self {
+        self.relative = false;
+        self
+    }
+
+    /// Returns the strftime pattern of absolute dates
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Formats a timestamp, relative to now if relative dates are enabled
+    pub fn format(&self, at: DateTime<Utc>) -> String {
+        if self.relative {
+            relative(at, Utc::now())
+        } else {
+            self.format_absolute(at)
+        }
+    }
+
+    /// Formats a timestamp in local time with the configured pattern
+    pub fn format_absolute(&self, at: DateTime<Utc>) -> String {
+        at.with_timezone(&Local).format(&self.pattern).to_string()
+    }
+
+    /// Formats a timestamp in UTC with the configured pattern
+    pub fn format_utc(&self, at: DateTime<Utc>) -> String {
+        at.format(&self.pattern).to_string()
+    }
+
+    /// Parses a date given by the user
+    ///
+    /// Accepts the formats of [`parse_date_filter`] and dates written in the
+    /// configured pattern, in local time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the date is in none of these formats
+    pub fn parse(&self, input: &str) -> Result<DateTime<Utc>> {
+        parse_date_filter(input).or_else(|e| {
+            let input = input.trim();
+            NaiveDateTime::parse_from_str(input, &self.pattern)
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(input, &self.pattern)
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+                .and_then(|local| Local.from_local_datetime(&local).earliest())
+                .map(|local| local.with_timezone(&Utc))
+                .ok_or(e)
+        })
+    }
+
+    /// Parses a date range `START..END`; either end may be left out
+    ///
+    /// Both ends are inclusive, and an end at midnight, such as a plain
+    /// date, covers that whole day.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no `..` or an end is not a date
+    pub fn parse_range(&self, input: &str) -> Result<DateRange> {
+        let (start, end) = input.split_once("..").ok_or_else(|| {
+            VibeTicketError::custom(format!(
+                "Invalid date range: '{input}'. Use START..END, e.g. 2025-01-01..2025-03-31"
+            ))
+        })?;
+        let parse = |date: &str| {
+            Some(date.trim())
+                .filter(|date| !date.is_empty())
+                .map(|date| self.parse(date))
+                .transpose()
+        };
+        let end = parse(end)?.map(|end| {
+            if end.time() == NaiveTime::MIN || end.with_timezone(&Local).time() == NaiveTime::MIN {
+                end + Duration::days(1) - Duration::nanoseconds(1)
+            } else {
+                end
+            }
+        });
+        Ok(DateRange {
+            start: parse(start)?,
+            end,
+        })
+    }
+}
+
+/// Inclusive range of times, open where an end is `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DateRange {
+    /// Earliest time in the range
+    pub start: Option<DateTime<Utc>>,
+
+    /// Latest time in the range
+    pub end: Option<DateTime<Utc>>,
+}
+
+/// Checks that a strftime pattern is valid
+///
+/// # Errors
+///
+/// Returns an error if the pattern is empty or has an unknown specifier
+pub fn validate(pattern: &str) -> Result<()> {
+    if pattern.trim().is_empty() || StrftimeItems::new(pattern).any(|item| item == Item::Error) {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Invalid date format '{pattern}'. Use strftime specifiers such as {DEFAULT_DATE_FORMAT}"
+        )));
+    }
+    Ok(())
+}
+
+/// Describes `at` relative to `now`, such as `3 days ago` or `in 2 hours`
+pub fn relative(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - at).num_seconds();
+    let Some(&(unit, name)) = UNITS.iter().find(|(unit, _)| seconds.abs() >= *unit) else {
+        return "just now".to_string();
+    };
+    let n = seconds.abs() / unit;
+    let plural = if n == 1 { "" } else { "s" };
+    if seconds < 0 {
+        format!("in {n} {name}{plural}")
+    } else {
+        format!("{n} {name}{plural} ago")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative() {
+        let now = Utc::now();
+        assert_eq!(relative(now - Duration::seconds(30), now), "just now");
+        assert_eq!(relative(now - Duration::minutes(1), now), "1 minute ago");
+        assert_eq!(relative(now - Duration::hours(5), now), "5 hours ago");
+        assert_eq!(relative(now - Duration::days(2), now), "2 days ago");
+        assert_eq!(relative(now - Duration::days(45), now), "1 month ago");
+        assert_eq!(relative(now - Duration::days(800), now), "2 years ago");
+        assert_eq!(relative(now + Duration::days(14), now), "in 2 weeks");
+    }
+
+    #[test]
+    fn test_format_and_parse() {
+        let ui = UiConfig {
+            date_format: "%d/%m/%Y".to_string(),
+            relative_dates: true,
+            ..Config::default().ui
+        };
+        let dates = DateFormat::from_config(&ui);
+        let at = Utc::now() - Duration::days(3);
+        assert_eq!(dates.format(at), "3 days ago");
+
+        let dates = dates.absolute();
+        let text = dates.format(at);
+        assert_eq!(
+            text,
+            at.with_timezone(&Local).format("%d/%m/%Y").to_string()
+        );
+        assert_eq!(dates.format_absolute(dates.parse(&text).unwrap()), text);
+        assert!(dates.parse("3 days ago").is_ok());
+        assert!(dates.parse("2025-07-15").is_ok());
+        assert!(dates.parse("15.07.2025").is_err());
+
+        let range = dates.parse_range("2025-01-01..2025-03-31").unwrap();
+        assert_eq!(range.start, Some(dates.parse("2025-01-01").unwrap()));
+        let end = range.end.unwrap();
+        assert!(end > dates.parse("2025-03-31").unwrap());
+        assert!(end < dates.parse("2025-04-01").unwrap());
+        assert_eq!(dates.parse_range("..").unwrap(), DateRange::default());
+        assert!(dates.parse_range("2025-01-01").is_err());
+
+        assert!(validate("%Y-%m-%d").is_ok());
+        assert!(validate("%Q").is_err());
+        assert!(validate("").is_err());
+        let ui = UiConfig {
+            date_format: "%Q".to_string(),
+            ..ui
+        };
+        assert_eq!(DateFormat::from_config(&ui).pattern(), DEFAULT_DATE_FORMAT);
+    }
+}
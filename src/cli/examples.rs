@@ -0,0 +1,528 @@
+//! Usage examples of the CLI commands
+//!
+//! The examples are kept in one registry, [`EXAMPLES`], and shown in three
+//! places: after the long help of each command (`vibe-ticket new --help`),
+//! on their own with `vibe-ticket new --examples`, and as shell snippets in
+//! the generated `CLAUDE.md`. Every example is parsed against the clap
+//! definition in the tests, so they cannot drift from the actual flags.
+
+use std::fmt::Write as FmtWrite;
+
+use clap::{Command, CommandFactory};
+
+use super::Cli;
+
+/// Flag printing the examples of a command instead of running it
+pub const EXAMPLES_FLAG: &str = "--examples";
+
+/// An example invocation
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    /// What the example does
+    pub description: &'static str,
+    /// Command line, starting with `vibe-ticket`
+    pub command_line: &'static str,
+}
+
+/// The examples of one command
+#[derive(Debug, Clone, Copy)]
+pub struct CommandExamples {
+    /// Subcommand names from the root, separated by spaces (e.g. `task add`)
+    pub command: &'static str,
+    /// Examples of the command
+    pub examples: &'static [Example],
+}
+
+const fn example(description: &'static str, command_line: &'static str) -> Example {
+    Example {
+        description,
+        command_line,
+    }
+}
+
+/// Examples of the commands, in the order they are presented
+pub const EXAMPLES: &[CommandExamples] = &[
+    CommandExamples {
+        command: "init",
+        examples: &[example(
+            "Initialize a project and generate CLAUDE.md",
+            r#"vibe-ticket init --name "My Project" --claude-md"#,
+        )],
+    },
+    CommandExamples {
+        command: "new",
+        examples: &[
+            example(
+                "Create a high-priority ticket",
+                r#"vibe-ticket new fix-login --title "Fix login redirect" --priority high --tags "auth,bug""#,
+            ),
+            example(
+                "Create a ticket and start working on it",
+                r#"vibe-ticket new add-search --title "Add ticket search" --start"#,
+            ),
+            example(
+                "Prefill a ticket from a GitHub issue",
+                "vibe-ticket new --from-url https://github.com/owner/repo/issues/42",
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "in",
+        examples: &[example(
+            "Capture an idea without choosing a slug",
+            r#"vibe-ticket in "random idea about caching""#,
+        )],
+    },
+    CommandExamples {
+        command: "in process",
+        examples: &[example(
+            "Turn the captured ideas into tickets",
+            "vibe-ticket in process",
+        )],
+    },
+    CommandExamples {
+        command: "list",
+        examples: &[
+            example(
+                "List tickets in progress",
+                "vibe-ticket list --status doing",
+            ),
+            example(
+                "Ten open high-priority tickets, newest first",
+                "vibe-ticket list --open --priority high --sort created --reverse --limit 10",
+            ),
+            example(
+                "Filter with a query expression",
+                r#"vibe-ticket list --where "status:todo AND tag:backend""#,
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "show",
+        examples: &[
+            example("Show a ticket", "vibe-ticket show fix-login"),
+            example(
+                "Show a ticket as JSON for scripts",
+                "vibe-ticket show fix-login --json",
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "start",
+        examples: &[
+            example(
+                "Start a ticket (creates a worktree if enabled)",
+                "vibe-ticket start fix-login",
+            ),
+            example(
+                "Start a ticket on a branch in the current checkout",
+                "vibe-ticket start fix-login --no-worktree",
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "check",
+        examples: &[example(
+            "Show the active ticket and project status",
+            "vibe-ticket check --detailed",
+        )],
+    },
+    CommandExamples {
+        command: "edit",
+        examples: &[
+            example(
+                "Move a ticket to review",
+                "vibe-ticket edit fix-login --status review",
+            ),
+            example(
+                "Tag a ticket and set its due date",
+                "vibe-ticket edit fix-login --add-tags urgent --due 2025-08-01",
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "close",
+        examples: &[
+            example(
+                "Close the active ticket",
+                r#"vibe-ticket close --message "Fixed the redirect after login""#,
+            ),
+            example(
+                "Close and archive a ticket",
+                r#"vibe-ticket close fix-login --message "Done" --archive"#,
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "task add",
+        examples: &[
+            example(
+                "Add a task to the active ticket",
+                r#"vibe-ticket task add "Write unit tests""#,
+            ),
+            example(
+                "Add a task to another ticket",
+                r#"vibe-ticket task add "Update docs" --ticket fix-login"#,
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "task complete",
+        examples: &[example("Complete task 1", "vibe-ticket task complete 1")],
+    },
+    CommandExamples {
+        command: "task list",
+        examples: &[example(
+            "List the tasks of the active ticket",
+            "vibe-ticket task list",
+        )],
+    },
+    CommandExamples {
+        command: "log",
+        examples: &[
+            example(
+                "Record a work-log note on the active ticket",
+                r#"vibe-ticket log "Narrowed it down to the session cookie""#,
+            ),
+            example(
+                "Print a standup report since yesterday",
+                "vibe-ticket log --standup",
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "link",
+        examples: &[example(
+            "Record that a ticket depends on another",
+            "vibe-ticket link fix-login --depends-on add-search",
+        )],
+    },
+    CommandExamples {
+        command: "search",
+        examples: &[
+            example(
+                "Search titles, descriptions and tags",
+                "vibe-ticket search login",
+            ),
+            example(
+                "Search with a regular expression",
+                r#"vibe-ticket search "timeout|deadlock" --regex"#,
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "archive",
+        examples: &[example(
+            "Preview archiving every done ticket",
+            "vibe-ticket archive --status done --dry-run",
+        )],
+    },
+    CommandExamples {
+        command: "export",
+        examples: &[example(
+            "Export tickets to CSV",
+            "vibe-ticket export --format csv --output tickets.csv",
+        )],
+    },
+    CommandExamples {
+        command: "import",
+        examples: &[
+            example(
+                "Import tickets from an export",
+                "vibe-ticket import tickets.json",
+            ),
+            example(
+                "Create tickets from a PRD, grouped under an epic",
+                "vibe-ticket import prd.md --format markdown-plan --epic",
+            ),
+        ],
+    },
+    CommandExamples {
+        command: "config set",
+        examples: &[example(
+            "Change the default priority",
+            "vibe-ticket config set project.default_priority high",
+        )],
+    },
+    CommandExamples {
+        command: "config claude",
+        examples: &[example(
+            "Regenerate CLAUDE.md with the advanced template",
+            "vibe-ticket config claude --template advanced",
+        )],
+    },
+    CommandExamples {
+        command: "spec init",
+        examples: &[example(
+            "Start a specification for a ticket",
+            r#"vibe-ticket spec init "Search API" --ticket add-search"#,
+        )],
+    },
+    CommandExamples {
+        command: "spec requirements",
+        examples: &[example(
+            "Write the requirements of the active spec in your editor",
+            "vibe-ticket spec requirements --editor",
+        )],
+    },
+    CommandExamples {
+        command: "spec approve",
+        examples: &[example(
+            "Approve the requirements phase",
+            r#"vibe-ticket spec approve <spec-id> requirements --message "Reviewed""#,
+        )],
+    },
+    CommandExamples {
+        command: "spec comment add",
+        examples: &[example(
+            "Comment on line 12 of the design",
+            r#"vibe-ticket spec comment add <spec-id> "Why not a queue?" --phase design --line 12"#,
+        )],
+    },
+    CommandExamples {
+        command: "worktree list",
+        examples: &[example(
+            "List the worktrees of tickets",
+            "vibe-ticket worktree list",
+        )],
+    },
+    CommandExamples {
+        command: "context pack",
+        examples: &[example(
+            "Print a project snapshot for an AI session",
+            "vibe-ticket context pack",
+        )],
+    },
+];
+
+/// Examples of `command` and its subcommands
+///
+/// `command` is a space-separated path such as `task` or `task add`; an empty
+/// path selects every example.
+pub fn for_command(command: &str) -> Vec<&'static CommandExamples> {
+    EXAMPLES
+        .iter()
+        .filter(|entry| {
+            command.is_empty()
+                || entry.command == command
+                || entry
+                    .command
+                    .strip_prefix(command)
+                    .is_some_and(|rest| rest.starts_with(' '))
+        })
+        .collect()
+}
+
+/// Command path selected by a command line containing [`EXAMPLES_FLAG`]
+///
+/// Returns `None` if the flag is absent. Arguments naming a subcommand of the
+/// command selected so far descend into it; all others, such as flag values
+/// and positional arguments, are skipped.
+pub fn requested(args: &[String]) -> Option<String> {
+    if !args.iter().any(|arg| arg == EXAMPLES_FLAG) {
+        return None;
+    }
+    let mut root = Cli::command();
+    // Building propagates the global flags to the subcommands
+    root.build();
+    let mut current = &root;
+    let mut path = Vec::new();
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg.starts_with('-') {
+            if takes_value(current, arg) {
+                args.next();
+            }
+        } else if let Some(sub) = current.find_subcommand(arg) {
+            path.push(sub.get_name().to_string());
+            current = sub;
+        }
+    }
+    Some(path.join(" "))
+}
+
+/// Whether `flag` is an option of `cmd` followed by a separate value
+fn takes_value(cmd: &Command, flag: &str) -> bool {
+    let takes_value = |arg: &clap::Arg| arg.get_action().takes_values();
+    if let Some(long) = flag.strip_prefix("--") {
+        return !long.contains('=')
+            && cmd
+                .get_arguments()
+                .any(|arg| arg.get_long() == Some(long) && takes_value(arg));
+    }
+    let mut shorts = flag.chars().skip(1);
+    match (shorts.next(), shorts.next()) {
+        (Some(short), None) => cmd
+            .get_arguments()
+            .any(|arg| arg.get_short() == Some(short) && takes_value(arg)),
+        _ => false,
+    }
+}
+
+/// Plain-text listing of the examples of `command`, for `--examples`
+pub fn render_text(command: &str) -> String {
+    let entries = for_command(command);
+    let name = if command.is_empty() {
+        "vibe-ticket".to_string()
+    } else {
+        format!("vibe-ticket {command}")
+    };
+    if entries.is_empty() {
+        return format!("No examples for `{name}`; see `{name} --help`\n");
+    }
+
+    let mut text = format!("Examples for `{name}`:\n");
+    for entry in entries {
+        for example in entry.examples {
+            let _ = write!(
+                text,
+                "\n  # {}\n  {}\n",
+                example.description, example.command_line
+            );
+        }
+    }
+    text
+}
+
+/// Markdown shell snippets of every example, for the generated `CLAUDE.md`
+///
+/// Each top-level command gets a `###` heading (under a `##` section written
+/// by the caller) followed by a `bash` code block.
+pub fn render_markdown() -> String {
+    let mut markdown = String::new();
+    let mut heading = "";
+    for entry in EXAMPLES {
+        let top = entry.command.split(' ').next().unwrap_or(entry.command);
+        if top == heading {
+            markdown.push('\n');
+        } else {
+            if !heading.is_empty() {
+                markdown.push_str("```\n\n");
+            }
+            let _ = write!(markdown, "### `{top}`\n```bash\n");
+            heading = top;
+        }
+        for (i, example) in entry.examples.iter().enumerate() {
+            if i > 0 {
+                markdown.push('\n');
+            }
+            let _ = write!(
+                markdown,
+                "# {}\n{}\n",
+                example.description, example.command_line
+            );
+        }
+    }
+    if !heading.is_empty() {
+        markdown.push_str("```\n");
+    }
+    markdown
+}
+
+/// Adds the examples after the long help of every command that has some
+pub fn with_examples(cmd: Command) -> Command {
+    annotate(cmd, &mut Vec::new())
+}
+
+fn annotate(mut cmd: Command, path: &mut Vec<String>) -> Command {
+    let names: Vec<String> = cmd
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in names {
+        path.push(name.clone());
+        let mut sub_path = path.clone();
+        cmd = cmd.mut_subcommand(name, |sub| annotate(sub, &mut sub_path));
+        path.pop();
+    }
+
+    let command = path.join(" ");
+    if let Some(entry) = EXAMPLES.iter().find(|entry| entry.command == command) {
+        let mut help = "Examples:\n".to_string();
+        for example in entry.examples {
+            let _ = write!(
+                help,
+                "\n  # {}\n  {}\n",
+                example.description, example.command_line
+            );
+        }
+        cmd = cmd.after_long_help(help);
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// Splits a command line into arguments, honouring double quotes
+    fn split(command_line: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut quoted = false;
+        for c in command_line.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                ' ' if !quoted => {
+                    if !current.is_empty() {
+                        args.push(std::mem::take(&mut current));
+                    }
+                },
+                _ => current.push(c),
+            }
+        }
+        if !current.is_empty() {
+            args.push(current);
+        }
+        args
+    }
+
+    #[test]
+    fn test_examples_parse() {
+        for entry in EXAMPLES {
+            for example in entry.examples {
+                let args = split(example.command_line);
+                assert_eq!(
+                    requested(&[args.clone(), vec![EXAMPLES_FLAG.to_string()]].concat()).as_deref(),
+                    Some(entry.command),
+                    "{} is not an example of {}",
+                    example.command_line,
+                    entry.command
+                );
+                if let Err(e) = Cli::try_parse_from(&args) {
+                    panic!("{} does not parse: {e}", example.command_line);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_requested_command() {
+        let args = |line: &str| split(line);
+        assert_eq!(requested(&args("vibe-ticket list")), None);
+        assert_eq!(
+            requested(&args("vibe-ticket --examples")).as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            requested(&args("vibe-ticket -p spec task add --examples")).as_deref(),
+            Some("task add")
+        );
+
+        assert_eq!(for_command("task").len(), 3);
+        assert!(render_text("task add").contains("vibe-ticket task add \"Write unit tests\""));
+        assert!(render_text("gc").starts_with("No examples"));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render_markdown();
+        assert!(markdown.starts_with("### `init`\n```bash\n"));
+        assert!(markdown.contains("### `task`\n```bash\n# Add a task to the active ticket\n"));
+        assert_eq!(
+            markdown.matches("```bash").count() * 2,
+            markdown.matches("```").count()
+        );
+    }
+}
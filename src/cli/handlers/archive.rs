@@ -1,8 +1,11 @@
 //! Handler for the `archive` command
 //!
-//! This module implements the logic for archiving and unarchiving tickets.
+//! This module implements the logic for archiving and unarchiving tickets,
+//! one at a time or every ticket matching a set of filters.
 
+use super::parse_date_filter;
 use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::{Status, Ticket};
 use crate::error::{Result, VibeTicketError};
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 
@@ -46,11 +49,7 @@ pub fn handle_archive_command(
     let mut ticket = storage.load(&ticket_id)?;
 
     // Check if already in desired state
-    let is_archived = ticket
-        .metadata
-        .get("archived")
-        .and_then(serde_json::Value::as_bool)
-        .unwrap_or(false);
+    let is_archived = is_archived(&ticket);
 
     if unarchive {
         // Unarchiving
@@ -59,8 +58,7 @@ pub fn handle_archive_command(
         }
 
         // Remove archive metadata
-        ticket.metadata.remove("archived");
-        ticket.metadata.remove("archived_at");
+        set_archived(&mut ticket, false);
 
         // Save the updated ticket
         storage.save(&ticket)?;
@@ -80,7 +78,10 @@ pub fn handle_archive_command(
         } else {
             output.success(&format!("Unarchived ticket: {}", ticket.slug));
             output.info(&format!("Title: {}", ticket.title));
-            output.info(&format!("Status: {}", ticket.status));
+            output.info(&format!(
+                "Status: {}",
+                output.visuals().status_text(ticket.status)
+            ));
         }
     } else {
         // Archiving
@@ -108,13 +109,7 @@ pub fn handle_archive_command(
         }
 
         // Add archive metadata
-        ticket
-            .metadata
-            .insert("archived".to_string(), serde_json::Value::Bool(true));
-        ticket.metadata.insert(
-            "archived_at".to_string(),
-            serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
-        );
+        set_archived(&mut ticket, true);
 
         // Save the updated ticket
         storage.save(&ticket)?;
@@ -134,7 +129,10 @@ pub fn handle_archive_command(
         } else {
             output.success(&format!("Archived ticket: {}", ticket.slug));
             output.info(&format!("Title: {}", ticket.title));
-            output.info(&format!("Status: {}", ticket.status));
+            output.info(&format!(
+                "Status: {}",
+                output.visuals().status_text(ticket.status)
+            ));
             output.info("\nThe ticket has been archived and will not appear in regular listings.");
             output.info("Use --archived flag with list command to see archived tickets.");
             output.info("Use --unarchive flag to restore this ticket.");
@@ -144,8 +142,193 @@ pub fn handle_archive_command(
     Ok(())
 }
 
+/// Filters selecting the tickets of a bulk `archive`
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveFilter {
+    /// Only tickets with this status
+    pub status: Option<String>,
+
+    /// Only tickets closed, or if still open created, before this date
+    pub until: Option<String>,
+
+    /// Only tickets with this tag
+    pub tag: Option<String>,
+}
+
+impl ArchiveFilter {
+    /// Keeps the tickets matching every filter
+    fn select(&self, tickets: Vec<Ticket>) -> Result<Vec<Ticket>> {
+        let status = self
+            .status
+            .as_deref()
+            .map(|status| {
+                Status::try_from(status).map_err(|_| VibeTicketError::InvalidStatus {
+                    status: status.to_string(),
+                })
+            })
+            .transpose()?;
+        let until = self.until.as_deref().map(parse_date_filter).transpose()?;
+
+        Ok(tickets
+            .into_iter()
+            .filter(|ticket| status.is_none_or(|status| ticket.status == status))
+            .filter(|ticket| {
+                until.is_none_or(|until| ticket.closed_at.unwrap_or(ticket.created_at) < until)
+            })
+            .filter(|ticket| {
+                self.tag
+                    .as_ref()
+                    .is_none_or(|tag| ticket.tags.contains(tag))
+            })
+            .collect())
+    }
+}
+
+/// Handler for the bulk mode of the `archive` command
+///
+/// Archives (or unarchives) every ticket matching `filter` that is not
+/// archived (or is) yet. The active ticket is never archived. The tickets
+/// are saved together, so either all of them change or none, and a single
+/// `undo` reverts them.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project is not initialized
+/// - A filter is invalid
+/// - A ticket cannot be saved
+pub fn handle_bulk_archive_command(
+    filter: &ArchiveFilter,
+    unarchive: bool,
+    dry_run: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    let active = storage.get_active().ok().flatten();
+    let mut skipped = Vec::new();
+    let mut tickets = filter.select(storage.load_all()?)?;
+    tickets.retain(|ticket| is_archived(ticket) == unarchive);
+    tickets.retain(|ticket| {
+        let is_active = !unarchive && active.as_ref() == Some(&ticket.id);
+        if is_active {
+            skipped.push(ticket.slug.clone());
+        }
+        !is_active
+    });
+    tickets.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+    if !dry_run {
+        for ticket in &mut tickets {
+            set_archived(ticket, !unarchive);
+        }
+        storage.save_all(&tickets)?;
+    }
+
+    let (action, done) = if unarchive {
+        ("unarchive", "Unarchived")
+    } else {
+        ("archive", "Archived")
+    };
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "action": format!("{action}d"),
+            "dry_run": dry_run,
+            "count": tickets.len(),
+            "tickets": tickets.iter().map(|ticket| serde_json::json!({
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+                "title": ticket.title,
+                "status": ticket.status.to_string(),
+            })).collect::<Vec<_>>(),
+            "skipped": skipped,
+        }))?;
+        return Ok(());
+    }
+
+    if dry_run {
+        output.warning("DRY RUN MODE - No changes will be made");
+    }
+    if tickets.is_empty() {
+        output.info(&format!("No tickets to {action}"));
+    } else if dry_run {
+        output.info(&format!("Would {action} {} ticket(s):", tickets.len()));
+    } else {
+        output.success(&format!("{done} {} ticket(s):", tickets.len()));
+    }
+    for ticket in &tickets {
+        output.info(&format!(
+            "  • {} - {} ({})",
+            ticket.slug,
+            ticket.title,
+            output.visuals().status_text(ticket.status)
+        ));
+    }
+    for slug in skipped {
+        output.warning(&format!("Skipped the active ticket '{slug}'"));
+    }
+
+    Ok(())
+}
+
+/// Whether a ticket is archived
+fn is_archived(ticket: &Ticket) -> bool {
+    ticket
+        .metadata
+        .get("archived")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Adds or removes the archive metadata of a ticket
+fn set_archived(ticket: &mut Ticket, archived: bool) {
+    if archived {
+        ticket
+            .metadata
+            .insert("archived".to_string(), serde_json::Value::Bool(true));
+        ticket.metadata.insert(
+            "archived_at".to_string(),
+            serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
+        );
+    } else {
+        ticket.metadata.remove("archived");
+        ticket.metadata.remove("archived_at");
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_archive_filter_selects_old_closed_tickets() {
+        let mut old = Ticket::new("old", "Old");
+        old.status = Status::Done;
+        old.closed_at = Some(Utc::now() - Duration::days(200));
+        let mut recent = Ticket::new("recent", "Recent");
+        recent.status = Status::Done;
+        recent.closed_at = Some(Utc::now());
+        let open = Ticket::new("open", "Open");
+
+        let filter = ArchiveFilter {
+            status: Some("done".to_string()),
+            until: Some("3 months ago".to_string()),
+            tag: None,
+        };
+        let selected = filter.select(vec![old, recent, open]).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].slug, "old");
+
+        let invalid = ArchiveFilter {
+            status: Some("finished".to_string()),
+            ..ArchiveFilter::default()
+        };
+        assert!(invalid.select(Vec::new()).is_err());
+    }
 
     #[test]
     fn test_archive_metadata() {
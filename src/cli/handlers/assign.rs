@@ -0,0 +1,106 @@
+//! Handler for the `suggest-assignee` command
+//!
+//! This module ranks the team for a ticket by skills and current load (see
+//! [`crate::core::assignment`]) and optionally assigns the top suggestion.
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::config::Config;
+use crate::core::assignment;
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Handler for the `suggest-assignee` command
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `assign` - Whether to assign the ticket to the top suggestion
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, the ticket is not
+/// found, or `assign` is set and nobody qualified has capacity.
+pub fn handle_suggest_assignee(
+    ticket_ref: &str,
+    assign: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let config = Config::load_for_project(&project_root)?;
+
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+    let mut ticket = storage.load(&ticket_id)?;
+    let tickets = storage.load_all()?;
+
+    let estimate = assignment::estimate(&ticket);
+    let candidates = assignment::rank_candidates(&ticket, &tickets, &config.team);
+    let suggestion = assignment::suggest_assignee(&ticket, &tickets, &config.team);
+
+    if assign {
+        let candidate = suggestion.as_ref().ok_or_else(|| {
+            VibeTicketError::custom("No qualified team member has capacity for this ticket")
+        })?;
+        ticket.assignee = Some(candidate.name.clone());
+        storage.save(&ticket)?;
+    }
+
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "estimate": estimate,
+            "required_skills": assignment::required_skills(&ticket, &config.team),
+            "suggestion": suggestion.as_ref().map(|c| &c.name),
+            "assigned": assign,
+            "candidates": candidates,
+        }))?;
+        return Ok(());
+    }
+
+    if candidates.is_empty() {
+        output.warning("No team members found. Add them under `team.members` in the config.");
+        return Ok(());
+    }
+
+    output.info(&format!(
+        "Candidates for '{}' ({estimate} points):",
+        ticket.slug
+    ));
+    for candidate in &candidates {
+        let capacity = candidate
+            .capacity
+            .map_or_else(String::new, |capacity| format!("/{capacity}"));
+        let mut notes = Vec::new();
+        if !candidate.is_qualified() {
+            notes.push(format!("missing: {}", candidate.missing_skills.join(", ")));
+        }
+        if candidate.is_over_capacity(estimate) {
+            notes.push("over capacity".to_string());
+        }
+        let notes = if notes.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", notes.join("; "))
+        };
+        output.info(&format!(
+            "  {:<20} {} open, load {}{capacity}{notes}",
+            candidate.name, candidate.open_tickets, candidate.load
+        ));
+    }
+
+    match (&suggestion, assign) {
+        (Some(candidate), true) => {
+            output.success(&format!("Assigned '{}' to {}", ticket.slug, candidate.name));
+        },
+        (Some(candidate), false) => output.success(&format!("Suggested: {}", candidate.name)),
+        (None, _) => output.warning("No qualified team member has capacity for this ticket"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Handlers for the `attach` command and its subcommands
+//!
+//! Screenshots, logs and other files are copied into the ticket's attachment
+//! directory (see [`crate::storage::Attachment`]), listed by `show` and
+//! referenced in exports.
+
+use std::path::Path;
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::TicketId;
+use crate::error::Result;
+use crate::storage::FileStorage;
+
+/// Handler for the `attach add` subcommand
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `file` - File to attach
+/// * `name` - Optional name of the attachment (defaults to the file name)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not found, the name is taken or the
+/// file cannot be copied
+pub fn handle_attach_add(
+    ticket_ref: &str,
+    file: &str,
+    name: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (storage, ticket_id) = open(ticket_ref, project_dir)?;
+    let attachment = storage.attach_file(&ticket_id, Path::new(file), name)?;
+    let path = storage.attachment_path(&ticket_id, &attachment.name);
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket_id.to_string(),
+            "attachment": attachment,
+            "path": path,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Attached '{}' ({} bytes) to ticket {}",
+            attachment.name,
+            attachment.size,
+            ticket_id.short()
+        ));
+        output.info(&format!("Stored at: {}", path.display()));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `attach list` subcommand
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not found
+pub fn handle_attach_list(
+    ticket_ref: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (storage, ticket_id) = open(ticket_ref, project_dir)?;
+    let attachments = storage.list_attachments(&ticket_id);
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "ticket_id": ticket_id.to_string(),
+            "directory": storage.attachments_dir(&ticket_id),
+            "attachments": attachments,
+        }));
+    }
+
+    if attachments.is_empty() {
+        output.info(&format!("Ticket {} has no attachments", ticket_id.short()));
+        return Ok(());
+    }
+    for attachment in &attachments {
+        output.info(&format!("{} ({} bytes)", attachment.name, attachment.size));
+    }
+    output.info("");
+    output.info(&format!(
+        "Directory: {}",
+        storage.attachments_dir(&ticket_id).display()
+    ));
+
+    Ok(())
+}
+
+/// Handler for the `attach remove` subcommand
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not found or has no such attachment
+pub fn handle_attach_remove(
+    ticket_ref: &str,
+    name: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (storage, ticket_id) = open(ticket_ref, project_dir)?;
+    storage.detach_file(&ticket_id, name)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket_id.to_string(),
+            "removed": name,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Removed attachment '{name}' from ticket {}",
+            ticket_id.short()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Opens the project storage and resolves the ticket
+fn open(ticket_ref: &str, project_dir: Option<&str>) -> Result<(FileStorage, TicketId)> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+    Ok((storage, ticket_id))
+}
@@ -4,10 +4,12 @@
 //! including active ticket information and project statistics.
 
 use crate::cli::{OutputFormatter, find_project_root};
-use crate::core::{Status, Ticket};
+use crate::core::{MilestoneProgress, Status};
 use crate::error::Result;
-use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
-use chrono::{DateTime, Local, Utc};
+use crate::storage::{
+    ActiveTicketRepository, FileStorage, StatusProjection, TicketRepository, TicketSummary,
+};
+use chrono::{Local, Utc};
 
 /// Handler for the `check` command
 ///
@@ -15,7 +17,7 @@ use chrono::{DateTime, Local, Utc};
 /// 1. Project information
 /// 2. Active ticket details (if any)
 /// 3. Current Git branch
-/// 4. Project statistics (optional)
+/// 4. Project statistics and milestone progress (optional)
 /// 5. Recent tickets (in detailed mode)
 ///
 /// # Arguments
@@ -106,7 +108,7 @@ pub fn handle_check_command(
         output.info(&format!("Path: {}", project_root.display()));
         output.info(&format!(
             "Created: {}",
-            format_datetime(project_state.created_at)
+            output.dates().format(project_state.created_at)
         ));
 
         // Display Git branch
@@ -122,8 +124,14 @@ pub fn handle_check_command(
             output.info(&format!("  ID: {}", ticket.id));
             output.info(&format!("  Slug: {}", ticket.slug));
             output.info(&format!("  Title: {}", ticket.title));
-            output.info(&format!("  Status: {}", ticket.status));
-            output.info(&format!("  Priority: {}", ticket.priority));
+            output.info(&format!(
+                "  Status: {}",
+                output.visuals().status_text(ticket.status)
+            ));
+            output.info(&format!(
+                "  Priority: {}",
+                output.visuals().priority_text(ticket.priority)
+            ));
 
             if let Some(started_at) = ticket.started_at {
                 let duration = Utc::now() - started_at;
@@ -159,23 +167,29 @@ pub fn handle_check_command(
                 output.info(&format!("  Medium: {}", stats.medium));
                 output.info(&format!("  Low: {}", stats.low));
             }
+
+            if !stats.milestones.is_empty() {
+                output.info("");
+                output.info("Milestones:");
+                for milestone in &stats.milestones {
+                    output.info(&format!("  {}: {}", milestone.name, milestone.summary()));
+                    output.info(&format!("    Burndown: {}", milestone.burndown_line()));
+                }
+            }
         }
 
         // Display recent tickets in detailed mode
         if detailed && !recent_tickets.is_empty() {
             output.info("");
             output.info("Recent tickets:");
+            let visuals = output.visuals();
             for ticket in &recent_tickets {
-                let status_emoji = match ticket.status {
-                    Status::Todo => "📋",
-                    Status::Doing => "🔄",
-                    Status::Review => "👀",
-                    Status::Blocked => "🚫",
-                    Status::Done => "✅",
-                };
                 output.info(&format!(
-                    "  {} {} - {} ({})",
-                    status_emoji, ticket.slug, ticket.title, ticket.priority
+                    "  {}{} - {} ({})",
+                    visuals.status_prefix(ticket.status),
+                    ticket.slug,
+                    ticket.title,
+                    visuals.priority(ticket.priority).label
                 ));
             }
         }
@@ -197,11 +211,12 @@ struct Statistics {
     high: usize,
     medium: usize,
     low: usize,
+    milestones: Vec<MilestoneProgress>,
 }
 
 /// Calculate project statistics
 fn calculate_statistics(storage: &FileStorage) -> Result<Statistics> {
-    let tickets = storage.load_all()?;
+    let tickets = storage.load_projections::<StatusProjection>()?;
 
     let mut stats = Statistics {
         total: tickets.len(),
@@ -214,6 +229,7 @@ fn calculate_statistics(storage: &FileStorage) -> Result<Statistics> {
         high: 0,
         medium: 0,
         low: 0,
+        milestones: Vec::new(),
     };
 
     for ticket in &tickets {
@@ -235,12 +251,23 @@ fn calculate_statistics(storage: &FileStorage) -> Result<Statistics> {
         }
     }
 
+    // Full tickets are only needed for the burndown of milestones
+    let milestones = storage.load_milestones()?;
+    if !milestones.is_empty() {
+        let tickets = storage.load_all()?;
+        let today = Local::now().date_naive();
+        stats.milestones = milestones
+            .iter()
+            .map(|milestone| MilestoneProgress::compute(milestone, &tickets, today))
+            .collect();
+    }
+
     Ok(stats)
 }
 
 /// Get recent tickets sorted by creation date
-fn get_recent_tickets(storage: &FileStorage, limit: usize) -> Result<Vec<Ticket>> {
-    let mut tickets = storage.load_all()?;
+fn get_recent_tickets(storage: &FileStorage, limit: usize) -> Result<Vec<TicketSummary>> {
+    let mut tickets = storage.summaries()?;
 
     // Sort by creation date (descending)
     tickets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
@@ -270,21 +297,15 @@ fn get_current_git_branch(project_root: &std::path::Path) -> Option<String> {
     }
 }
 
-/// Format datetime for display
-fn format_datetime(dt: DateTime<Utc>) -> String {
-    dt.with_timezone(&Local)
-        .format("%Y-%m-%d %H:%M")
-        .to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::DateFormat;
 
     #[test]
     fn test_format_datetime() {
         let dt = Utc::now();
-        let formatted = format_datetime(dt);
+        let formatted = DateFormat::default().format(dt);
         assert!(!formatted.is_empty());
     }
 }
@@ -0,0 +1,93 @@
+//! Handler for the `ci` command
+//!
+//! This module implements ingesting CI reports (`JUnit` XML, SARIF) into a
+//! ticket as machine-created tasks.
+
+use std::fs;
+use std::path::Path;
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::error::{ErrorContext, Result};
+use crate::integration::ci::{CiReport, ReportFormat, apply_report};
+use crate::storage::FileStorage;
+
+/// Handler for the `ci report` command
+///
+/// Parses the report and synchronizes the ticket's CI tasks with it.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `from` - Path of the report file
+/// * `format` - Optional report format (detected from the file if `None`)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, the ticket is not
+/// found, or the report cannot be read or parsed.
+pub fn handle_ci_report(
+    ticket_ref: &str,
+    from: &str,
+    format: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    // Parse the report before touching the ticket
+    let path = Path::new(from);
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read report {from}"))?;
+    let format = match format {
+        Some(format) => format.parse()?,
+        None => ReportFormat::detect(path, &content)?,
+    };
+    let report = CiReport::parse(format, &content)?;
+
+    // Apply it under the ticket lock
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+    let mut summary = None;
+    let ticket = storage.update_ticket(&ticket_id, |ticket| {
+        summary = Some(apply_report(ticket, &report));
+    })?;
+    let summary = summary.unwrap_or_default();
+
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "findings": report.findings.len(),
+            "added": summary.added,
+            "reopened": summary.reopened,
+            "resolved": summary.resolved,
+            "unchanged": summary.unchanged,
+        }))?;
+        return Ok(());
+    }
+
+    output.success(&format!(
+        "Processed {} findings for ticket '{}'",
+        report.findings.len(),
+        ticket.slug
+    ));
+    for title in &summary.added {
+        output.info(&format!("  + {title}"));
+    }
+    for title in &summary.reopened {
+        output.info(&format!("  ! {title} (reopened)"));
+    }
+    for title in &summary.resolved {
+        output.info(&format!("  ✓ {title}"));
+    }
+    if summary.unchanged > 0 {
+        output.info(&format!("{} findings already tracked", summary.unchanged));
+    }
+
+    Ok(())
+}
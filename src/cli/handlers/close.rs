@@ -4,8 +4,11 @@
 //! including status updates and optional archiving.
 
 use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
-use crate::core::Status;
+use crate::core::closing::{self, CLOSE_MESSAGE_KEY, CloseRequirement, PR_URL_KEY};
+use crate::core::{Status, Ticket};
 use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::specs::{SpecManager, SpecMetadata, link};
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 use chrono::Utc;
 
@@ -13,11 +16,14 @@ use chrono::Utc;
 ///
 /// This function performs the following operations:
 /// 1. Loads the specified ticket (or active ticket if none specified)
-/// 2. Updates the ticket status to "done"
-/// 3. Sets the `closed_at` timestamp
+/// 2. Optionally creates a pull request and links it to the ticket
+/// 3. Updates the ticket status to "done" and sets the `closed_at` timestamp;
+///    storage refuses this while the ticket depends on open tickets or
+///    misses the project's close requirements, unless the close is forced,
+///    and completes the tasks phase of the spec the ticket was exported
+///    from once all the spec's exported tickets are closed
 /// 4. Clears the active ticket if it was the one being closed
 /// 5. Optionally archives the ticket
-/// 6. Optionally creates a pull request
 ///
 /// # Arguments
 ///
@@ -25,6 +31,9 @@ use chrono::Utc;
 /// * `message` - Optional close message
 /// * `archive` - Whether to archive the ticket
 /// * `create_pr` - Whether to create a pull request
+/// * `pr_url` - Optional URL of a pull request to link to the ticket
+/// * `force` - Whether to close the ticket despite open dependencies or
+///   missing close requirements
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
 ///
@@ -35,11 +44,16 @@ use chrono::Utc;
 /// - No ticket is specified and there's no active ticket
 /// - The ticket is not found
 /// - The ticket is already closed
+/// - The ticket depends on open tickets and `force` is not set
+/// - The ticket misses close requirements and `force` is not set
+#[allow(clippy::too_many_arguments)]
 pub fn handle_close_command(
     ticket_ref: Option<String>,
-    message: Option<String>,
+    message: Option<&str>,
     archive: bool,
     create_pr: bool,
+    pr_url: Option<String>,
+    force: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -48,7 +62,7 @@ pub fn handle_close_command(
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
 
     // Initialize storage
-    let storage = FileStorage::new(&vibe_ticket_dir);
+    let storage = FileStorage::new(&vibe_ticket_dir).with_forced_close(force);
 
     // Get the active ticket if no ticket specified
     let ticket_id = if let Some(ref_str) = ticket_ref {
@@ -71,21 +85,40 @@ pub fn handle_close_command(
         )));
     }
 
+    // Create pull request if requested, linking it like --pr-url
+    let pr_url = match (pr_url, create_pr) {
+        (Some(url), _) => Some(url),
+        (None, true) => create_pull_request(&project_root, &ticket, output)?,
+        (None, false) => None,
+    };
+
     // Update ticket status and close time
     let previous_status = ticket.status;
     ticket.status = Status::Done;
     ticket.closed_at = Some(Utc::now());
 
-    // Add close message to metadata if provided
-    if let Some(msg) = &message {
+    // Add close message and pull request to metadata if provided
+    if let Some(msg) = message {
+        ticket.metadata.insert(
+            CLOSE_MESSAGE_KEY.to_string(),
+            serde_json::Value::String(msg.to_string()),
+        );
+    }
+    if let Some(url) = &pr_url {
         ticket.metadata.insert(
-            "close_message".to_string(),
-            serde_json::Value::String(msg.clone()),
+            PR_URL_KEY.to_string(),
+            serde_json::Value::String(url.clone()),
         );
     }
 
-    // Save the updated ticket
+    // Save the updated ticket; storage checks the dependencies and the
+    // close requirements
+    let waived = closing::missing(&ticket, storage.close_requirements());
     storage.save(&ticket)?;
+    emit_cli_event(
+        &project_root,
+        &TicketEvent::Closed(ticket.id.clone(), message.unwrap_or_default().to_string()),
+    );
 
     // Clear active ticket if this was the active one
     if let Some(active_id) = storage.get_active()? {
@@ -94,11 +127,6 @@ pub fn handle_close_command(
         }
     }
 
-    // Create pull request if requested
-    if create_pr {
-        create_pull_request(&project_root, &ticket, output)?;
-    }
-
     // Archive if requested (for now, just add a flag to metadata)
     if archive {
         // In a real implementation, we might move the ticket to an archive directory
@@ -113,6 +141,9 @@ pub fn handle_close_command(
         storage.save(&archived_ticket)?;
     }
 
+    let completed_spec =
+        link::completed_spec(&SpecManager::new(vibe_ticket_dir.join("specs")), &ticket);
+
     // Output results
     if output.is_json() {
         output.print_json(&serde_json::json!({
@@ -127,44 +158,90 @@ pub fn handle_close_command(
             },
             "message": message,
             "pr_created": create_pr,
+            "pr_url": pr_url,
+            "waived_requirements": waived.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            "completed_spec": completed_spec.as_ref().map(|spec| &spec.id),
         }))?;
     } else {
-        output.success(&format!("Closed ticket: {}", ticket.slug));
-        output.info(&format!("Title: {}", ticket.title));
-        output.info(&format!("Status: {} → {}", previous_status, Status::Done));
+        print_closed(
+            &ticket,
+            previous_status,
+            message,
+            archive,
+            pr_url.as_deref(),
+            &waived,
+            output,
+        );
+        print_completed_spec(completed_spec.as_ref(), output);
+    }
 
-        if let Some(msg) = message {
-            output.info(&format!("Close message: {msg}"));
-        }
+    Ok(())
+}
 
-        if archive {
-            output.info("Ticket has been archived");
-        }
+/// Prints the result of closing a ticket
+fn print_closed(
+    ticket: &Ticket,
+    previous_status: Status,
+    message: Option<&str>,
+    archive: bool,
+    pr_url: Option<&str>,
+    waived: &[CloseRequirement],
+    output: &OutputFormatter,
+) {
+    output.success(&format!("Closed ticket: {}", ticket.slug));
+    output.info(&format!("Title: {}", ticket.title));
+    output.info(&format!("Status: {} → {}", previous_status, Status::Done));
 
-        if create_pr {
-            output.info("Pull request creation initiated");
-        }
+    if let Some(msg) = message {
+        output.info(&format!("Close message: {msg}"));
+    }
+
+    if archive {
+        output.info("Ticket has been archived");
+    }
+
+    if let Some(url) = pr_url {
+        output.info(&format!("Pull request: {url}"));
+    }
+
+    if !waived.is_empty() {
+        let missing: Vec<&str> = waived.iter().map(|r| r.description()).collect();
+        output.warning(&format!(
+            "Closed without {}; recorded in the audit log",
+            missing.join(", ")
+        ));
+    }
 
-        // Calculate duration if started_at is available
-        if let Some(started_at) = ticket.started_at {
-            if let Some(closed_at) = ticket.closed_at {
-                let duration = closed_at - started_at;
-                let hours = duration.num_hours();
-                let minutes = duration.num_minutes() % 60;
-                output.info(&format!("\nTime spent: {hours}h {minutes}m"));
-            }
+    // Calculate duration if started_at is available
+    if let Some(started_at) = ticket.started_at {
+        if let Some(closed_at) = ticket.closed_at {
+            let duration = closed_at - started_at;
+            let hours = duration.num_hours();
+            let minutes = duration.num_minutes() % 60;
+            output.info(&format!("\nTime spent: {hours}h {minutes}m"));
         }
     }
+}
 
-    Ok(())
+/// Tells that closing the ticket completed the tasks phase of `spec`
+fn print_completed_spec(spec: Option<&SpecMetadata>, output: &OutputFormatter) {
+    if let Some(spec) = spec {
+        output.info(&format!(
+            "All tickets of spec '{}' are closed; its tasks phase is complete",
+            spec.title
+        ));
+    }
 }
 
 /// Create a pull request for the ticket
+///
+/// Returns the URL of the created pull request, or `None` if it could not be
+/// created.
 fn create_pull_request(
     project_root: &std::path::Path,
     ticket: &crate::core::Ticket,
     output: &OutputFormatter,
-) -> Result<()> {
+) -> Result<Option<String>> {
     use std::process::Command;
 
     // Get current branch name
@@ -194,7 +271,7 @@ fn create_pull_request(
         output.info(&format!(
             "You can create a pull request manually for branch: {branch_name}"
         ));
-        return Ok(());
+        return Ok(None);
     }
 
     // Create PR using GitHub CLI
@@ -229,13 +306,13 @@ fn create_pull_request(
             .trim()
             .to_string();
         output.success(&format!("Created pull request: {pr_url}"));
+        Ok(Some(pr_url))
     } else {
         let error_msg = String::from_utf8_lossy(&create_pr.stderr);
         output.warning(&format!("Failed to create pull request: {error_msg}"));
         output.info("You can create the pull request manually");
+        Ok(None)
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -0,0 +1,227 @@
+//! Handler for the `comment` command and its subcommands
+//!
+//! This module implements the discussion on a ticket: adding, listing,
+//! editing and deleting comments.
+
+use crate::cli::{DateFormat, OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::{Comment, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+/// Handler for the `comment add` subcommand
+///
+/// Adds a comment to a ticket.
+///
+/// # Arguments
+///
+/// * `body` - Text of the comment
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `author` - Optional author (defaults to the current user)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_comment_add(
+    body: &str,
+    ticket_ref: Option<String>,
+    author: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let body = non_empty(body)?;
+    let (storage, mut ticket) = load_ticket(ticket_ref, project_dir)?;
+
+    let author = author
+        .filter(|a| !a.trim().is_empty())
+        .unwrap_or_else(|| storage.identity().name.clone());
+    let id = ticket.add_comment(author.trim(), body);
+    storage.save(&ticket)?;
+
+    let comment = ticket.comments.last();
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "comment": comment,
+            "total_comments": ticket.comments.len(),
+        }))?;
+    } else {
+        output.success(&format!("Added comment #{id} to ticket '{}'", ticket.slug));
+        output.info(&format!("Total comments: {}", ticket.comments.len()));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `comment list` subcommand
+///
+/// Prints the comments on a ticket in the order they were written.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_comment_list(
+    ticket_ref: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (_, ticket) = load_ticket(ticket_ref, project_dir)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "comments": ticket.comments,
+        }))?;
+        return Ok(());
+    }
+
+    if ticket.comments.is_empty() {
+        output.info(&format!("No comments on ticket '{}'", ticket.slug));
+        return Ok(());
+    }
+
+    output.info(&format!("Comments on ticket '{}':", ticket.slug));
+    for comment in &ticket.comments {
+        output.info("");
+        output.info(&header(comment, output.dates()));
+        for line in comment.body.lines() {
+            output.info(&format!("  {line}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `comment edit` subcommand
+///
+/// Replaces the text of a comment and marks it as edited.
+///
+/// # Arguments
+///
+/// * `id` - Comment number
+/// * `body` - New text of the comment
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_comment_edit(
+    id: u32,
+    body: &str,
+    ticket_ref: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let body = non_empty(body)?;
+    let (storage, mut ticket) = load_ticket(ticket_ref, project_dir)?;
+
+    let slug = ticket.slug.clone();
+    let comment = ticket
+        .comment_mut(id)
+        .ok_or_else(|| comment_not_found(id, &slug))?;
+    comment.edit(body);
+    let comment = comment.clone();
+    storage.save(&ticket)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "comment": comment,
+        }))?;
+    } else {
+        output.success(&format!("Edited comment #{id} on ticket '{}'", ticket.slug));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `comment delete` subcommand
+///
+/// Removes a comment from a ticket.
+///
+/// # Arguments
+///
+/// * `id` - Comment number
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_comment_delete(
+    id: u32,
+    ticket_ref: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (storage, mut ticket) = load_ticket(ticket_ref, project_dir)?;
+
+    let comment = ticket
+        .remove_comment(id)
+        .ok_or_else(|| comment_not_found(id, &ticket.slug))?;
+    storage.save(&ticket)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "deleted_comment": comment,
+            "total_comments": ticket.comments.len(),
+        }))?;
+    } else {
+        output.success(&format!(
+            "Deleted comment #{id} from ticket '{}'",
+            ticket.slug
+        ));
+    }
+
+    Ok(())
+}
+
+/// Loads the referenced ticket, or the active ticket if none is given
+fn load_ticket(
+    ticket_ref: Option<String>,
+    project_dir: Option<&str>,
+) -> Result<(FileStorage, Ticket)> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    let ticket_id = if let Some(ref_str) = ticket_ref {
+        resolve_ticket_ref(&storage, &ref_str)?
+    } else {
+        storage
+            .get_active()?
+            .ok_or(VibeTicketError::NoActiveTicket)?
+    };
+
+    let ticket = storage.load(&ticket_id)?;
+    Ok((storage, ticket))
+}
+
+/// Heading line of a comment in plain output
+fn header(comment: &Comment, dates: &DateFormat) -> String {
+    let edited = comment
+        .edited_at
+        .map_or_else(String::new, |at| format!(" (edited {})", dates.format(at)));
+    format!(
+        "#{} {} - {}{edited}",
+        comment.id,
+        comment.author,
+        dates.format(comment.created_at)
+    )
+}
+
+fn non_empty(body: &str) -> Result<&str> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Comment cannot be empty".to_string(),
+        ));
+    }
+    Ok(body)
+}
+
+fn comment_not_found(id: u32, ticket_slug: &str) -> VibeTicketError {
+    VibeTicketError::InvalidInput(format!("Ticket '{ticket_slug}' has no comment #{id}"))
+}
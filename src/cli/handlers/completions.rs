@@ -0,0 +1,90 @@
+//! Handlers for the `completions` and `__complete-tickets` commands
+
+use crate::cli::completions::{self, Shell};
+use crate::cli::{Cli, find_project_root};
+use crate::core::branch::BRANCH_REF;
+use crate::error::Result;
+use crate::storage::{FileStorage, TicketSummary};
+use clap::CommandFactory;
+
+/// Handler for the `completions` command
+///
+/// Prints the completion script for `shell` to stdout.
+///
+/// # Errors
+///
+/// Returns an error if the shell is not supported
+pub fn handle_completions_command(shell: &str) -> Result<()> {
+    let shell: Shell = shell.parse()?;
+    print!("{}", completions::generate(shell, Cli::command()));
+    Ok(())
+}
+
+/// Handler for the hidden `__complete-tickets` command
+///
+/// Prints one `reference<TAB>title` line per slug and short ID of the
+/// tickets that are not archived. Completion runs on every key press, so
+/// nothing is printed outside a project and unreadable tickets are skipped.
+pub fn handle_complete_tickets(project_dir: Option<&str>) {
+    let Ok(project_root) = find_project_root(project_dir) else {
+        return;
+    };
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    for (reference, title) in ticket_references(&storage) {
+        println!("{reference}\t{title}");
+    }
+}
+
+/// Ticket references offered for completion, with their descriptions
+fn ticket_references(storage: &FileStorage) -> Vec<(String, String)> {
+    let mut tickets: Vec<TicketSummary> = storage
+        .summaries()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|ticket| !ticket.is_archived())
+        .collect();
+    tickets.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+    let mut references: Vec<(String, String)> = tickets
+        .iter()
+        .map(|ticket| (ticket.slug.clone(), ticket.title.clone()))
+        .collect();
+    references.extend(
+        tickets
+            .iter()
+            .map(|ticket| (ticket.display_id(), ticket.title.clone())),
+    );
+    references.push((
+        BRANCH_REF.to_string(),
+        "Ticket of the current branch".to_string(),
+    ));
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ticket_references_skip_archived() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        storage.ensure_directories().unwrap();
+        let open = Ticket::new("open-ticket", "Open");
+        let mut archived = Ticket::new("archived-ticket", "Archived");
+        archived
+            .metadata
+            .insert("archived".to_string(), true.into());
+        storage.save(&open).unwrap();
+        storage.save(&archived).unwrap();
+
+        let references = ticket_references(&storage);
+        let names: Vec<&str> = references.iter().map(|(r, _)| r.as_str()).collect();
+        let short_id = storage.load(&open.id).unwrap().display_id();
+        assert_eq!(names, vec!["open-ticket", short_id.as_str(), BRANCH_REF]);
+        assert_eq!(references[0].1, "Open");
+    }
+}
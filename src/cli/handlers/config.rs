@@ -3,7 +3,7 @@
 //! This module implements the logic for managing project configuration.
 
 use crate::cli::{ConfigCommands, OutputFormatter, find_project_root};
-use crate::config::Config;
+use crate::config::{AgingConfig, Config, Identity, LockConfig};
 use crate::error::{Result, VibeTicketError};
 
 /// Handler for the `config` subcommands
@@ -48,6 +48,50 @@ pub fn handle_config_command(
     }
 }
 
+/// Prints the `[project]` section of the configuration
+fn print_project_section(config: &Config, output: &OutputFormatter) {
+    output.info("[project]");
+    output.info(&format!("  name: {}", config.project.name));
+    if let Some(desc) = &config.project.description {
+        output.info(&format!("  description: {desc}"));
+    }
+    if let Some(assignee) = &config.project.default_assignee {
+        output.info(&format!("  default_assignee: {assignee}"));
+    }
+    output.info(&format!(
+        "  default_priority: {}",
+        config.project.default_priority
+    ));
+    output.info(&format!(
+        "  description_size_limit: {}",
+        config.project.description_size_limit
+    ));
+    output.info(&format!(
+        "  immutable_closed: {}",
+        config.project.immutable_closed
+    ));
+    output.info(&format!(
+        "  trash_retention_days: {}",
+        config.project.trash_retention_days
+    ));
+    output.info(&format!(
+        "  short_id_length: {}",
+        config.project.short_id_length
+    ));
+    if !config.project.close_requirements.is_empty() {
+        let requirements: Vec<String> = config
+            .project
+            .close_requirements
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        output.info(&format!(
+            "  close_requirements: {}",
+            requirements.join(", ")
+        ));
+    }
+}
+
 /// Show configuration values
 fn handle_show(
     key: Option<String>,
@@ -75,19 +119,7 @@ fn handle_show(
             output.success("Project Configuration:");
             output.info("");
 
-            // Project section
-            output.info("[project]");
-            output.info(&format!("  name: {}", config.project.name));
-            if let Some(desc) = &config.project.description {
-                output.info(&format!("  description: {desc}"));
-            }
-            if let Some(assignee) = &config.project.default_assignee {
-                output.info(&format!("  default_assignee: {assignee}"));
-            }
-            output.info(&format!(
-                "  default_priority: {}",
-                config.project.default_priority
-            ));
+            print_project_section(&config, output);
             output.info("");
 
             // UI section
@@ -96,6 +128,7 @@ fn handle_show(
             output.info(&format!("  emoji: {}", config.ui.emoji));
             output.info(&format!("  page_size: {}", config.ui.page_size));
             output.info(&format!("  date_format: {}", config.ui.date_format));
+            output.info(&format!("  relative_dates: {}", config.ui.relative_dates));
             output.info("");
 
             // Git section
@@ -112,6 +145,80 @@ fn handle_show(
             output.info("[plugins]");
             output.info(&format!("  enabled: {:?}", config.plugins.enabled));
             output.info(&format!("  directory: {}", config.plugins.directory));
+            if let Some(slack) = &config.plugins.slack {
+                print_slack(slack, output);
+            }
+            output.info("");
+
+            // MCP section
+            output.info("[mcp]");
+            output.info(&format!("  log_file: {}", config.mcp.log_file));
+            output.info(&format!(
+                "  write_coalesce_ms: {}",
+                config.mcp.write_coalesce_ms
+            ));
+            output.info("");
+
+            print_lock(&config.storage.lock, output);
+
+            // User section
+            let identity = Identity::resolve(&config.user);
+            output.info("[user]");
+            output.info(&format!("  name: {}", identity.name));
+            if let Some(email) = &identity.email {
+                output.info(&format!("  email: {email}"));
+            }
+            if config.user.is_empty() {
+                output.info("  (from git config; set user.name and user.email to override)");
+            }
+            output.info("");
+
+            // Notifications section
+            output.info("[notifications]");
+            if let Some(url) = &config.notifications.webhook_url {
+                output.info(&format!("  webhook_url: {url}"));
+            }
+
+            print_aging(&config.aging, output);
+
+            // Spec section
+            if config.spec.strict_review || !config.spec.required_approvals.is_empty() {
+                output.info("");
+                output.info("[spec]");
+                if config.spec.strict_review {
+                    output.info("  strict_review: true");
+                }
+                for (phase, required) in &config.spec.required_approvals {
+                    output.info(&format!("  required_approvals.{phase}: {required}"));
+                }
+            }
+
+            // Webhooks, without their secrets
+            if !config.webhooks.is_empty() {
+                output.info("");
+                output.info("[webhooks]");
+                for webhook in &config.webhooks {
+                    output.info(&format!("  {}: {}", webhook.name, webhook.url));
+                }
+            }
+
+            // Saved filters
+            if !config.filters.is_empty() {
+                output.info("");
+                output.info("[filters]");
+                for (name, query) in &config.filters {
+                    output.info(&format!("  {name}: {query}"));
+                }
+            }
+
+            // User-defined fields
+            if !config.fields.is_empty() {
+                output.info("");
+                output.info("[fields]");
+                for (name, definition) in &config.fields {
+                    output.info(&format!("  {name}: {definition}"));
+                }
+            }
         }
     }
 
@@ -190,10 +297,64 @@ fn handle_reset(
     Ok(())
 }
 
+/// Prints the lock section of `config show`
+fn print_lock(lock: &LockConfig, output: &OutputFormatter) {
+    output.info("[storage.lock]");
+    output.info(&format!("  retries: {}", lock.retries));
+    output.info(&format!("  retry_delay_ms: {}", lock.retry_delay_ms));
+    output.info(&format!("  stale_after_secs: {}", lock.stale_after_secs));
+    output.info("");
+}
+
+/// Prints the aging section of `config show`, if aging is configured
+fn print_aging(aging: &AgingConfig, output: &OutputFormatter) {
+    let Some(days) = aging.stale_after_days else {
+        return;
+    };
+    output.info("");
+    output.info("[aging]");
+    output.info(&format!("  stale_after_days: {days}"));
+    if let Some(days) = aging.close_after_days {
+        output.info(&format!("  close_after_days: {days}"));
+    }
+    if !aging.exempt_tags.is_empty() {
+        output.info(&format!("  exempt_tags: {}", aging.exempt_tags.join(", ")));
+    }
+    if !aging.exempt_priorities.is_empty() {
+        output.info(&format!(
+            "  exempt_priorities: {:?}",
+            aging.exempt_priorities
+        ));
+    }
+    if !aging.exempt_statuses.is_empty() {
+        output.info(&format!("  exempt_statuses: {:?}", aging.exempt_statuses));
+    }
+}
+
+/// Prints the Slack plugin settings, except the webhook URLs which are secret
+fn print_slack(slack: &crate::config::SlackConfig, output: &OutputFormatter) {
+    output.info(&format!(
+        "  slack: {} ({})",
+        slack.channel.as_deref().unwrap_or("default channel"),
+        slack.events.join(", ")
+    ));
+    for (priority, route) in &slack.channels {
+        let route = if route.starts_with("http") {
+            "separate webhook"
+        } else {
+            route
+        };
+        output.info(&format!("    {priority}: {route}"));
+    }
+}
+
 /// Get a configuration value by key path
+///
+/// `user` keys report the resolved identity, including values taken from git.
 fn get_config_value(config: &Config, key: &str) -> Result<serde_json::Value> {
     // Convert config to JSON for easy path access
-    let json = serde_json::to_value(config)?;
+    let mut json = serde_json::to_value(config)?;
+    json["user"] = serde_json::to_value(Identity::resolve(&config.user))?;
 
     // Split the key path
     let parts: Vec<&str> = key.split('.').collect();
@@ -214,11 +375,11 @@ fn get_config_value(config: &Config, key: &str) -> Result<serde_json::Value> {
 }
 
 /// Set a configuration value by key path
+///
+/// Keys whose values need more than a type check are handled here; all
+/// others go through [`set_by_path`].
 fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
     match key {
-        "project.name" => config.project.name = value.to_string(),
-        "project.description" => config.project.description = Some(value.to_string()),
-        "project.default_assignee" => config.project.default_assignee = Some(value.to_string()),
         "project.default_priority" => {
             // Validate priority
             if !["low", "medium", "high", "critical"].contains(&value) {
@@ -228,6 +389,12 @@ fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
             }
             config.project.default_priority = value.to_string();
         },
+        "project.short_id_length" => config.project.short_id_length = parse_short_id_length(value)?,
+        "project.close_requirements" => {
+            config.project.close_requirements = split_list(value)
+                .map(|r| r.parse())
+                .collect::<Result<_>>()?;
+        },
         "ui.theme" => {
             // Validate theme
             if !["light", "dark", "auto"].contains(&value) {
@@ -237,40 +404,300 @@ fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
             }
             config.ui.theme = value.to_string();
         },
-        "ui.emoji" => {
-            config.ui.emoji = value
-                .parse::<bool>()
-                .map_err(|_| VibeTicketError::custom("Value must be true or false"))?;
+        "ui.date_format" => {
+            crate::cli::dates::validate(value)?;
+            config.ui.date_format = value.to_string();
         },
-        "ui.page_size" => {
-            config.ui.page_size = value
-                .parse::<usize>()
-                .map_err(|_| VibeTicketError::custom("Value must be a positive number"))?;
+        "aging.stale_after_days" => config.aging.stale_after_days = parse_days(value)?,
+        "aging.close_after_days" => config.aging.close_after_days = parse_days(value)?,
+        "aging.exempt_priorities" => {
+            config.aging.exempt_priorities = split_list(value)
+                .map(|p| crate::core::Priority::try_from(p.as_str()))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(VibeTicketError::custom)?;
         },
-        "ui.date_format" => config.ui.date_format = value.to_string(),
-        "git.enabled" => {
-            config.git.enabled = value
-                .parse::<bool>()
-                .map_err(|_| VibeTicketError::custom("Value must be true or false"))?;
+        "aging.exempt_statuses" => {
+            config.aging.exempt_statuses = split_list(value)
+                .map(|s| crate::core::Status::try_from(s.as_str()))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(VibeTicketError::custom)?;
         },
-        "git.branch_prefix" => config.git.branch_prefix = value.to_string(),
-        "git.auto_branch" => {
-            config.git.auto_branch = value
-                .parse::<bool>()
-                .map_err(|_| VibeTicketError::custom("Value must be true or false"))?;
+        key if key.starts_with("spec.required_approvals") => {
+            set_required_approvals(config, &key["spec.required_approvals".len()..], value)?;
         },
-        "git.commit_template" => config.git.commit_template = Some(value.to_string()),
-        "plugins.directory" => config.plugins.directory = value.to_string(),
-        _ => {
+        key if key.starts_with("filters.") => {
+            set_saved_filter(config, &key["filters.".len()..], value)?;
+        },
+        key if key.starts_with("fields.") => {
+            set_field_definition(config, &key["fields.".len()..], value)?;
+        },
+        key if key.starts_with("plugins.slack.") => {
+            set_slack_value(config, &key["plugins.slack.".len()..], value)?;
+        },
+        _ => set_by_path(config, key, value)?,
+    }
+
+    Ok(())
+}
+
+/// Sets any configuration key through the serialized form of the config
+///
+/// The value is read as the type of the key's current value: a boolean, a
+/// number, a string or a comma-separated list (JSON is accepted for lists
+/// and objects). Keys without a value, such as unset optional ones or empty
+/// lists, take a JSON value, a string or a list, and an empty value unsets
+/// optional keys.
+/// Missing objects along the path are created, so that entries of maps such
+/// as `ui.statuses.doing.emoji` can be added. The updated config must
+/// deserialize and still hold the value, which rejects unknown keys and
+/// values of the wrong type.
+fn set_by_path(config: &mut Config, key: &str, value: &str) -> Result<()> {
+    let json = serde_json::to_value(&*config)?;
+    let current = lookup(&json, key);
+    let holds = |candidate: &serde_json::Value| {
+        let mut updated = json.clone();
+        insert(&mut updated, key, candidate.clone())?;
+        let parsed: Config = serde_json::from_value(updated).ok()?;
+        let stored = serde_json::to_value(&parsed).ok()?;
+        (lookup(&stored, key).unwrap_or(&serde_json::Value::Null) == candidate).then_some(parsed)
+    };
+
+    if value.trim().is_empty() {
+        let known = current.is_some()
+            || [
+                serde_json::json!(""),
+                serde_json::json!(0),
+                serde_json::json!(false),
+                serde_json::json!([]),
+            ]
+            .iter()
+            .any(|probe| holds(probe).is_some());
+        if let Some(parsed) = holds(&serde_json::Value::Null).filter(|_| known) {
+            *config = parsed;
+            return Ok(());
+        }
+    }
+    if let Some(parsed) = value_candidates(current, value).iter().find_map(&holds) {
+        *config = parsed;
+        return Ok(());
+    }
+
+    let expected = match current {
+        Some(serde_json::Value::Bool(_)) => "true or false",
+        Some(serde_json::Value::Number(_)) => "a number",
+        Some(serde_json::Value::String(_)) => "a string",
+        Some(serde_json::Value::Array(_)) => "a comma-separated list",
+        Some(serde_json::Value::Object(_)) => "a JSON object",
+        Some(serde_json::Value::Null) | None => {
             return Err(VibeTicketError::custom(format!(
                 "Configuration key '{key}' cannot be set or doesn't exist"
             )));
         },
+    };
+    Err(VibeTicketError::custom(format!(
+        "Invalid value '{value}' for '{key}': expected {expected}"
+    )))
+}
+
+/// The values `value` may stand for at a key holding `current`, most
+/// specific first
+fn value_candidates(current: Option<&serde_json::Value>, value: &str) -> Vec<serde_json::Value> {
+    use serde_json::Value;
+
+    let parsed = serde_json::from_str::<Value>(value.trim()).ok();
+    match current {
+        Some(Value::String(_)) => vec![Value::String(value.to_string())],
+        Some(Value::Bool(_)) => parsed.filter(Value::is_boolean).into_iter().collect(),
+        Some(Value::Number(_)) => parsed.filter(Value::is_number).into_iter().collect(),
+        Some(Value::Array(_)) => parsed
+            .filter(Value::is_array)
+            .into_iter()
+            .chain([Value::Array(split_list(value).map(Value::String).collect())])
+            .collect(),
+        Some(Value::Object(_)) => parsed.filter(Value::is_object).into_iter().collect(),
+        Some(Value::Null) | None => parsed
+            .into_iter()
+            .chain([
+                Value::String(value.to_string()),
+                Value::Array(split_list(value).map(Value::String).collect()),
+            ])
+            .collect(),
+    }
+}
+
+/// The value at a dotted key path, if there is one
+fn lookup<'a>(json: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    key.split('.')
+        .try_fold(json, |current, part| match current {
+            serde_json::Value::Array(items) => items.get(part.parse::<usize>().ok()?),
+            _ => current.get(part),
+        })
+}
+
+/// Puts `value` at a dotted key path, creating missing objects on the way;
+/// `None` if the path runs through a value that is not an object or list
+fn insert(json: &mut serde_json::Value, key: &str, value: serde_json::Value) -> Option<()> {
+    let (parents, last) = key.rsplit_once('.').map_or(("", key), |(p, l)| (p, l));
+    let mut current = json;
+    for part in parents.split('.').filter(|part| !part.is_empty()) {
+        current = match current {
+            serde_json::Value::Array(items) => items.get_mut(part.parse::<usize>().ok()?)?,
+            serde_json::Value::Object(map) => map
+                .entry(part)
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new())),
+            _ => return None,
+        };
+    }
+    match current {
+        serde_json::Value::Array(items) => *items.get_mut(last.parse::<usize>().ok()?)? = value,
+        serde_json::Value::Object(map) => {
+            map.insert(last.to_string(), value);
+        },
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Returns `None` for an empty value, which unsets an optional key
+fn optional(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Saves the query `value` as the filter `name`
+fn set_saved_filter(config: &mut Config, name: &str, value: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(VibeTicketError::custom("Filter name must not be empty"));
+    }
+    value.parse::<crate::core::Query>()?;
+    config.filters.insert(name.to_string(), value.to_string());
+    Ok(())
+}
+
+/// Sets the approvals a spec phase needs, where `phase` is `.<phase>` or
+/// empty for all phases; 0 removes the requirement
+fn set_required_approvals(config: &mut Config, phase: &str, value: &str) -> Result<()> {
+    use crate::specs::SpecDocumentType;
+
+    let phases = match phase.strip_prefix('.') {
+        Some(phase) => vec![phase.parse::<SpecDocumentType>()?],
+        None if phase.is_empty() => vec![
+            SpecDocumentType::Requirements,
+            SpecDocumentType::Design,
+            SpecDocumentType::Tasks,
+        ],
+        None => {
+            return Err(VibeTicketError::custom(format!(
+                "Configuration key 'spec.required_approvals{phase}' cannot be set or doesn't exist"
+            )));
+        },
+    };
+    let required = value
+        .parse::<u32>()
+        .map_err(|_| VibeTicketError::custom("Value must be a number of approvals"))?;
+    for phase in phases {
+        if required == 0 {
+            config.spec.required_approvals.remove(phase.as_str());
+        } else {
+            config
+                .spec
+                .required_approvals
+                .insert(phase.as_str().to_string(), required);
+        }
     }
+    Ok(())
+}
 
+/// Declares the user-defined field `name`; `none` or an empty value removes it
+fn set_field_definition(config: &mut Config, name: &str, value: &str) -> Result<()> {
+    if name.is_empty() || name.contains(['=', ' ']) {
+        return Err(VibeTicketError::custom(format!(
+            "Invalid field name: '{name}'"
+        )));
+    }
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        config.fields.remove(name);
+    } else {
+        config.fields.insert(name.to_string(), value.parse()?);
+    }
+    Ok(())
+}
+
+/// Sets a key of the Slack plugin; an empty `webhook_url` removes the plugin
+fn set_slack_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
+    if key == "webhook_url" && value.trim().is_empty() {
+        config.plugins.slack = None;
+        return Ok(());
+    }
+    let slack = config.plugins.slack.get_or_insert_with(Default::default);
+    match key {
+        "webhook_url" => slack.webhook_url = value.trim().to_string(),
+        "channel" => slack.channel = optional(value),
+        "events" => {
+            let events: Vec<String> = split_list(value).collect();
+            if let Some(unknown) = events
+                .iter()
+                .find(|event| !crate::events::TicketEvent::KINDS.contains(&event.as_str()))
+            {
+                return Err(VibeTicketError::custom(format!(
+                    "Unknown event '{unknown}'. Must be one of: {}",
+                    crate::events::TicketEvent::KINDS.join(", ")
+                )));
+            }
+            slack.events = events;
+        },
+        key if key.starts_with("channels.") => {
+            let priority = crate::core::Priority::try_from(&key["channels.".len()..])
+                .map_err(VibeTicketError::custom)?;
+            match optional(value) {
+                Some(route) => slack.channels.insert(priority, route),
+                None => slack.channels.remove(&priority),
+            };
+        },
+        _ => {
+            return Err(VibeTicketError::custom(format!(
+                "Configuration key 'plugins.slack.{key}' cannot be set or doesn't exist"
+            )));
+        },
+    }
     Ok(())
 }
 
+/// Parses a number of days; `none` or an empty value unsets it
+fn parse_days(value: &str) -> Result<Option<u32>> {
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    value
+        .parse::<u32>()
+        .map(Some)
+        .map_err(|_| VibeTicketError::custom("Value must be a number of days or 'none'"))
+}
+
+/// Parses a short ID length, which must be within 6–8
+fn parse_short_id_length(value: &str) -> Result<usize> {
+    use crate::core::{MAX_SHORT_ID_LENGTH, MIN_SHORT_ID_LENGTH};
+
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|length| (MIN_SHORT_ID_LENGTH..=MAX_SHORT_ID_LENGTH).contains(length))
+        .ok_or_else(|| {
+            VibeTicketError::custom(format!(
+                "Value must be between {MIN_SHORT_ID_LENGTH} and {MAX_SHORT_ID_LENGTH}"
+            ))
+        })
+}
+
+/// Splits a comma-separated list, skipping empty items
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToString::to_string)
+}
+
 /// Handle the claude subcommand for generating CLAUDE.md
 fn handle_claude(
     append: bool,
@@ -346,8 +773,9 @@ fn generate_basic_claude_md(config: &Config, project_root: &std::path::Path) ->
         .filter(|t| matches!(t.status, crate::core::Status::Doing))
         .count();
 
+    let examples = crate::cli::examples::render_markdown();
     let content = format!(
-        r#"# vibe-ticket Project: {}
+        r"# vibe-ticket Project: {}
 
 {}
 
@@ -357,36 +785,7 @@ This project uses vibe-ticket for ticket management. This document provides guid
 
 ## Common vibe-ticket Commands
 
-### Basic Operations
-```bash
-# Create a new ticket
-vibe-ticket new <slug> --title "<title>" --priority <priority>
-
-# List all tickets
-vibe-ticket list
-
-# Show ticket details
-vibe-ticket show <ticket>
-
-# Start working on a ticket
-vibe-ticket start <ticket>
-
-# Close a ticket
-vibe-ticket close <ticket> --message "<completion message>"
-```
-
-### Task Management
-```bash
-# Add a task to current ticket
-vibe-ticket task add "<task description>"
-
-# Complete a task
-vibe-ticket task complete <task-number>
-
-# List tasks
-vibe-ticket task list
-```
-
+{examples}
 ## Current Configuration
 
 - **Project Name**: {}
@@ -418,7 +817,7 @@ vibe-ticket task list
 
 ---
 Generated on: {}
-"#,
+",
         config.project.name,
         config
             .project
@@ -458,33 +857,6 @@ vibe-ticket config set git.worktree_enabled true
 vibe-ticket start <ticket> --worktree
 ```
 
-### Search and Filter
-```bash
-# Search tickets by content
-vibe-ticket search "<query>"
-
-# Filter by status
-vibe-ticket list --status doing
-
-# Filter by priority
-vibe-ticket list --priority high
-
-# Complex filters
-vibe-ticket list --status todo --priority high --assignee me
-```
-
-### Export and Import
-```bash
-# Export tickets to JSON
-vibe-ticket export json -o tickets.json
-
-# Export to CSV
-vibe-ticket export csv -o tickets.csv
-
-# Import tickets
-vibe-ticket import tickets.json
-```
-
 ## Integration Points
 
 ### Environment Variables
@@ -570,5 +942,148 @@ mod tests {
         // Test invalid values
         assert!(set_config_value(&mut config, "project.default_priority", "invalid").is_err());
         assert!(set_config_value(&mut config, "ui.emoji", "not_a_bool").is_err());
+        assert!(set_config_value(&mut config, "ui.date_format", "%d/%m/%Y").is_ok());
+        assert!(set_config_value(&mut config, "ui.date_format", "%Q").is_err());
+        assert_eq!(config.ui.date_format, "%d/%m/%Y");
+        assert!(set_config_value(&mut config, "project.short_id_length", "5").is_err());
+        assert!(set_config_value(&mut config, "project.short_id_length", "7").is_ok());
+        assert_eq!(config.project.short_id_length, 7);
+    }
+
+    #[test]
+    fn test_set_aging_policy() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "aging.stale_after_days", "30").unwrap();
+        set_config_value(&mut config, "aging.close_after_days", "14").unwrap();
+        set_config_value(&mut config, "aging.exempt_tags", "incident, pinned").unwrap();
+        set_config_value(&mut config, "aging.exempt_priorities", "critical,high").unwrap();
+        assert_eq!(config.aging.stale_after_days, Some(30));
+        assert_eq!(config.aging.exempt_tags, vec!["incident", "pinned"]);
+        assert_eq!(config.aging.exempt_priorities.len(), 2);
+
+        set_config_value(&mut config, "aging.close_after_days", "none").unwrap();
+        assert_eq!(config.aging.close_after_days, None);
+        assert!(set_config_value(&mut config, "aging.stale_after_days", "soon").is_err());
+        assert!(set_config_value(&mut config, "aging.exempt_statuses", "sleeping").is_err());
+    }
+
+    #[test]
+    fn test_set_saved_filter() {
+        let mut config = Config::default();
+        set_config_value(
+            &mut config,
+            "filters.my-urgent",
+            "status:todo priority:high",
+        )
+        .unwrap();
+        assert!(config.filter("my-urgent").is_ok());
+        assert!(config.filter("other").is_err());
+
+        assert!(set_config_value(&mut config, "filters.bad", "status:nope").is_err());
+        assert!(set_config_value(&mut config, "filters.", "is:open").is_err());
+        assert_eq!(
+            get_config_value(&config, "filters.my-urgent").unwrap(),
+            "status:todo priority:high"
+        );
+    }
+
+    #[test]
+    fn test_set_required_approvals() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "spec.required_approvals", "1").unwrap();
+        set_config_value(&mut config, "spec.required_approvals.design", "2").unwrap();
+        set_config_value(&mut config, "spec.required_approvals.tasks", "0").unwrap();
+        assert_eq!(
+            get_config_value(&config, "spec.required_approvals").unwrap(),
+            serde_json::json!({ "requirements": 1, "design": 2 })
+        );
+
+        assert!(set_config_value(&mut config, "spec.required_approvals.review", "1").is_err());
+        assert!(set_config_value(&mut config, "spec.required_approvals.design", "two").is_err());
+    }
+
+    #[test]
+    fn test_declare_field() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "fields.team", "enum:core, web").unwrap();
+        set_config_value(&mut config, "fields.estimate", "number").unwrap();
+        assert_eq!(config.fields.len(), 2);
+        assert_eq!(
+            get_config_value(&config, "fields.team").unwrap(),
+            serde_json::json!({ "type": "enum", "values": ["core", "web"] })
+        );
+
+        assert!(set_config_value(&mut config, "fields.due", "timestamp").is_err());
+        assert!(set_config_value(&mut config, "fields.", "string").is_err());
+        set_config_value(&mut config, "fields.estimate", "none").unwrap();
+        assert!(!config.fields.contains_key("estimate"));
+    }
+
+    #[test]
+    fn test_set_user_identity() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "user.name", "Alice").unwrap();
+        set_config_value(&mut config, "user.email", "alice@example.com").unwrap();
+        assert_eq!(
+            get_config_value(&config, "user.name").unwrap(),
+            serde_json::json!("Alice")
+        );
+        assert_eq!(
+            get_config_value(&config, "user.email").unwrap(),
+            serde_json::json!("alice@example.com")
+        );
+
+        set_config_value(&mut config, "user.email", "").unwrap();
+        assert!(config.user.email.is_none());
+    }
+
+    #[test]
+    fn test_set_slack_plugin() {
+        let mut config = Config::default();
+        let url = "https://hooks.slack.com/services/T000/B000/XXXX";
+        set_config_value(&mut config, "plugins.slack.webhook_url", url).unwrap();
+        set_config_value(&mut config, "plugins.slack.channels.critical", "#incidents").unwrap();
+        set_config_value(&mut config, "plugins.slack.events", "created, closed").unwrap();
+        let slack = config.plugins.slack.as_ref().unwrap();
+        assert_eq!(slack.webhook_url, url);
+        assert_eq!(
+            slack.channels[&crate::core::Priority::Critical],
+            "#incidents"
+        );
+        assert_eq!(slack.events, vec!["created", "closed"]);
+
+        assert!(set_config_value(&mut config, "plugins.slack.events", "renamed").is_err());
+        assert!(set_config_value(&mut config, "plugins.slack.channels.someday", "#x").is_err());
+        set_config_value(&mut config, "plugins.slack.webhook_url", "").unwrap();
+        assert!(config.plugins.slack.is_none());
+    }
+
+    #[test]
+    fn test_set_by_path() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "git.worktree_enabled", "false").unwrap();
+        set_config_value(&mut config, "git.worktree_prefix", "../{project}-wt-").unwrap();
+        set_config_value(&mut config, "git.worktree_cleanup_on_close", "true").unwrap();
+        set_config_value(&mut config, "mcp.write_coalesce_ms", "250").unwrap();
+        set_config_value(&mut config, "ui.statuses.doing.emoji", "🚧").unwrap();
+        set_config_value(&mut config, "plugins.enabled", "jira, github").unwrap();
+        assert!(!config.git.worktree_enabled);
+        assert_eq!(config.git.worktree_prefix, "../{project}-wt-");
+        assert!(config.git.worktree_cleanup_on_close);
+        assert_eq!(config.mcp.write_coalesce_ms, 250);
+        assert_eq!(config.ui.statuses["doing"].emoji.as_deref(), Some("🚧"));
+        assert_eq!(config.plugins.enabled, vec!["jira", "github"]);
+
+        // Strings stay strings, optional keys are unset by an empty value
+        set_config_value(&mut config, "git.commit_template", "true").unwrap();
+        assert_eq!(config.git.commit_template.as_deref(), Some("true"));
+        set_config_value(&mut config, "git.commit_template", "").unwrap();
+        assert!(config.git.commit_template.is_none());
+
+        assert!(set_config_value(&mut config, "git.worktree_enabled", "yes").is_err());
+        assert!(set_config_value(&mut config, "ui.page_size", "-1").is_err());
+        assert!(set_config_value(&mut config, "git.no_such_key", "x").is_err());
+        assert!(set_config_value(&mut config, "git.branch_prefix.nested", "x").is_err());
+        assert!(set_config_value(&mut config, "nope", "").is_err());
     }
 }
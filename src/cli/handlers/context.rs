@@ -0,0 +1,145 @@
+//! Handler for the `context` command
+//!
+//! `context pack` prints a compact snapshot of the project (see
+//! [`ContextPack`]) to paste into an AI session. The MCP tool
+//! `vibe-ticket_context_pack` returns the same snapshot.
+
+use std::path::Path;
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::core::context_pack::{ContextPack, ContextSources, parse_budget};
+use crate::error::{ErrorContext, Result};
+use crate::specs::{SpecManager, SpecMetadata, SpecPhase};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+/// Handler for the `context pack` subcommand
+///
+/// # Arguments
+///
+/// * `budget` - Token budget, such as `8000`, `8k` or `8000-tokens`
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the budget is invalid or the project cannot be read
+pub fn handle_context_pack(
+    budget: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let budget = parse_budget(budget)?;
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let pack = build_context_pack(&project_root, &storage, budget)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!(pack))?;
+    } else {
+        // Plain Markdown, ready to paste
+        print!("{}", pack.markdown);
+    }
+
+    Ok(())
+}
+
+/// Assembles the context pack of the project at `project_root`
+///
+/// The current spec is the active spec, or else the most recently updated
+/// spec that is not completed.
+///
+/// # Errors
+///
+/// Returns an error if the project state, the tickets, the specs or
+/// `CLAUDE.md` cannot be read
+pub fn build_context_pack(
+    project_root: &Path,
+    storage: &FileStorage,
+    budget: usize,
+) -> Result<ContextPack> {
+    let state = storage.load_state()?;
+    let tickets = storage.load_all()?;
+    let active = storage.get_active()?;
+    let spec = current_spec(&SpecManager::new(
+        project_root.join(".vibe-ticket").join("specs"),
+    ))?;
+
+    let claude_path = project_root.join("CLAUDE.md");
+    let claude_md = if claude_path.is_file() {
+        Some(
+            std::fs::read_to_string(&claude_path)
+                .with_context(|| format!("Failed to read {}", claude_path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ContextPack::build(
+        &ContextSources {
+            project: &state.name,
+            description: state.description.as_deref(),
+            tickets: &tickets,
+            active: active.as_ref(),
+            spec: spec.as_ref(),
+            claude_md: claude_md.as_deref(),
+        },
+        budget,
+    ))
+}
+
+/// The active spec, or the most recently updated unfinished spec
+fn current_spec(manager: &SpecManager) -> Result<Option<SpecMetadata>> {
+    let specs = manager.list_specs()?;
+    if let Some(active) = manager.get_active_spec()? {
+        if let Some(spec) = specs.iter().find(|spec| spec.id == active) {
+            return Ok(Some(spec.clone()));
+        }
+    }
+    Ok(specs
+        .into_iter()
+        .filter(|spec| spec.progress.current_phase != SpecPhase::Completed)
+        .max_by_key(|spec| spec.updated_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::storage::ProjectState;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_build_context_pack() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        storage
+            .save_state(&ProjectState {
+                name: "demo".to_string(),
+                description: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                ticket_count: 0,
+            })
+            .unwrap();
+        let ticket = Ticket::new("fix-login", "Fix login");
+        storage.save(&ticket).unwrap();
+        storage.set_active(&ticket.id).unwrap();
+        let manager = SpecManager::new(temp_dir.path().join(".vibe-ticket/specs"));
+        manager
+            .create_spec("Auth".to_string(), "Auth rework".to_string())
+            .unwrap();
+        std::fs::write(
+            temp_dir.path().join("CLAUDE.md"),
+            "## Conventions\n\nKeep commits small\n",
+        )
+        .unwrap();
+
+        let pack = build_context_pack(temp_dir.path(), &storage, 4000).unwrap();
+        assert!(pack.markdown.starts_with("# Project: demo\n"));
+        assert!(pack.markdown.contains("**Fix login**"));
+        assert!(pack.markdown.contains("## Current spec\n\n**Auth**"));
+        assert!(pack.markdown.contains("Keep commits small"));
+    }
+}
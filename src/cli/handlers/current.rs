@@ -0,0 +1,119 @@
+//! Handler for the `current` command
+//!
+//! Infers the ticket of the checked out git branch (see
+//! [`crate::core::branch`]). The same inference backs the `@branch` ticket
+//! reference accepted wherever a ticket ID or slug is.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::core::Ticket;
+use crate::core::branch::{self, BranchMatch};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Ticket inferred from a branch
+#[derive(Debug)]
+pub struct BranchTicket {
+    /// The checked out branch
+    pub branch: String,
+    /// The ticket the branch was created for
+    pub ticket: Ticket,
+    /// How the branch name was matched to the ticket
+    pub matched: BranchMatch,
+}
+
+/// Handler for the `current` command
+///
+/// # Arguments
+///
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, no branch is checked
+/// out, or no ticket matches the branch
+pub fn handle_current_command(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let current = branch_ticket(&storage, Path::new(project_dir.unwrap_or(".")))?;
+    let ticket = &current.ticket;
+    let active = storage.get_active_ticket()?.as_ref() == Some(&ticket.id);
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "branch": current.branch,
+            "matched_by": current.matched.describe(),
+            "active": active,
+            "ticket": {
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+                "title": ticket.title,
+                "status": ticket.status.to_string(),
+                "priority": ticket.priority.to_string(),
+                "assignee": ticket.assignee,
+            },
+        }))?;
+    } else {
+        output.success(&format!(
+            "{} {} ({})",
+            ticket.display_id(),
+            ticket.slug,
+            ticket.title
+        ));
+        output.info(&format!(
+            "Branch: {} (matched by {})",
+            current.branch,
+            current.matched.describe()
+        ));
+        output.info(&format!("Status: {}", ticket.status));
+        if !active {
+            output.info("Not the active ticket");
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the ticket of the branch checked out in `dir`
+///
+/// # Errors
+///
+/// Returns an error if `dir` is not on a git branch, the tickets cannot be
+/// loaded, or no ticket matches the branch
+pub fn branch_ticket(storage: &FileStorage, dir: &Path) -> Result<BranchTicket> {
+    let branch = current_branch(dir)
+        .ok_or_else(|| VibeTicketError::custom("Not on a git branch; cannot infer the ticket"))?;
+    let config_path = storage.base_dir().join("config.yaml");
+    let config = if config_path.exists() {
+        Config::load_from_path(&config_path)?
+    } else {
+        Config::default()
+    };
+
+    let tickets = storage.load_all()?;
+    let (ticket, matched) = branch::ticket_for_branch(&branch, &config.git.branch_prefix, &tickets)
+        .ok_or_else(|| VibeTicketError::custom(format!("No ticket matches branch '{branch}'")))?;
+
+    Ok(BranchTicket {
+        ticket: ticket.clone(),
+        branch,
+        matched,
+    })
+}
+
+/// Name of the branch checked out in `dir`, if any
+///
+/// Returns `None` outside a repository and with a detached HEAD.
+fn current_branch(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && branch != "HEAD" && !branch.is_empty()).then_some(branch)
+}
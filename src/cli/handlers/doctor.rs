@@ -0,0 +1,161 @@
+//! Handler for the `doctor` command
+//!
+//! This module checks the listing index against the ticket files (see
+//! [`FileStorage::index_status`]) and rebuilds it with `--rebuild-index`.
+//! Reads repair an outdated index on their own; rebuilding is for an index
+//! that was damaged or that missed a change made within the resolution of
+//! the file system's write times.
+//!
+//! It first finishes transactions interrupted by a crash (see
+//! [`crate::storage::Transaction`]), so that the check sees their tickets.
+//!
+//! `doctor locks` lists the lock files left in the storage directory (see
+//! [`crate::storage::HeldLock`]), to find the process a writer is waiting
+//! for.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::Result;
+use crate::storage::{FileStorage, INDEX_KEY};
+
+/// Handler for the `doctor` command
+///
+/// # Arguments
+///
+/// * `rebuild_index` - Whether to rebuild the listing index
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the ticket files
+/// cannot be read, or the index cannot be written
+pub fn handle_doctor_command(
+    rebuild_index: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    let recovered = storage.recover_transactions()?;
+    let status = storage.index_status()?;
+    let rebuilt = rebuild_index.then(|| storage.rebuild_index()).transpose()?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "index": status,
+            "index_current": status.is_current(),
+            "rebuilt": rebuilt,
+            "recovered_transactions": recovered,
+        }))?;
+        return Ok(());
+    }
+
+    if recovered > 0 {
+        output.warning(&format!(
+            "Finished {recovered} transaction(s) interrupted by a crash"
+        ));
+    }
+
+    if status.is_current() {
+        output.success(&format!(
+            "Listing index is up to date ({} tickets)",
+            status.tickets
+        ));
+    } else {
+        if status.readable {
+            output.warning(&format!("Listing index {INDEX_KEY} is out of date:"));
+        } else {
+            output.warning(&format!(
+                "Listing index {INDEX_KEY} is missing or unreadable"
+            ));
+        }
+        for (label, keys) in [
+            ("not indexed", &status.missing),
+            ("changed since indexed", &status.stale),
+            ("deleted", &status.removed),
+        ] {
+            if !keys.is_empty() {
+                output.info(&format!("  {} ticket file(s) {label}", keys.len()));
+            }
+        }
+    }
+
+    match rebuilt {
+        Some(count) => output.success(&format!("Rebuilt the listing index with {count} tickets")),
+        None if !status.is_current() => {
+            output.info("It is refreshed on the next read, or now with --rebuild-index.");
+        },
+        None => {},
+    }
+
+    Ok(())
+}
+
+/// Handler for the `doctor locks` command
+///
+/// # Arguments
+///
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized
+pub fn handle_doctor_locks(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    let settings = storage.lock_settings();
+    let locks = storage.held_locks();
+
+    if output.is_json() {
+        let locks: Vec<_> = locks
+            .iter()
+            .map(|lock| {
+                serde_json::json!({
+                    "path": lock.path.strip_prefix(&vibe_ticket_dir).unwrap_or(&lock.path),
+                    "pid": lock.pid,
+                    "operation": lock.operation,
+                    "age_secs": lock.age_secs,
+                    "stale": lock.is_stale(settings),
+                })
+            })
+            .collect();
+        return output.print_json(&serde_json::json!({
+            "locks": locks,
+            "retries": settings.retries,
+            "retry_delay_ms": settings.retry_delay.as_millis(),
+            "stale_after_secs": settings.stale_after.as_secs(),
+        }));
+    }
+
+    if locks.is_empty() {
+        output.success("No locks are held");
+    }
+    for lock in &locks {
+        let path = lock
+            .path
+            .strip_prefix(&vibe_ticket_dir)
+            .unwrap_or(&lock.path);
+        let line = format!(
+            "{} held by process {} for {} since {} s",
+            path.display(),
+            lock.pid,
+            lock.operation.as_deref().unwrap_or("unknown operation"),
+            lock.age_secs,
+        );
+        if lock.is_stale(settings) {
+            output.warning(&format!("{line}, stale: the next writer takes it over"));
+        } else {
+            output.info(&line);
+        }
+    }
+    output.info(&format!(
+        "Writers try {} times, {} ms apart; locks are stale after {} s",
+        settings.retries,
+        settings.retry_delay.as_millis(),
+        settings.stale_after.as_secs()
+    ));
+    Ok(())
+}
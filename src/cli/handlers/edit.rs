@@ -3,9 +3,14 @@
 //! This module implements the logic for editing ticket properties,
 //! including title, description, priority, status, and tags.
 
+use chrono::NaiveDate;
+
 use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
-use crate::core::{Priority, Status};
+use crate::config::Config;
+use crate::core::fields::set_fields;
+use crate::core::{Priority, Severity, Status, hierarchy};
 use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 
 /// Handler for the `edit` command
@@ -24,9 +29,12 @@ use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 /// * `title` - New title for the ticket
 /// * `description` - New description for the ticket
 /// * `priority` - New priority for the ticket
+/// * `severity` - New severity for the ticket (`none` clears it)
 /// * `status` - New status for the ticket
+/// * `due` - New due date (`YYYY-MM-DD`; `none` clears it)
 /// * `add_tags` - Tags to add (comma-separated)
 /// * `remove_tags` - Tags to remove (comma-separated)
+/// * `fields` - User-defined fields to set (`name=value`; empty value clears)
 /// * `editor` - Whether to open in the default editor
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
@@ -37,16 +45,20 @@ use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 /// - The project is not initialized
 /// - No ticket is specified and there's no active ticket
 /// - The ticket is not found
-/// - Invalid priority or status values are provided
+/// - Invalid priority, status or due date values are provided
 #[allow(clippy::too_many_arguments)]
 pub fn handle_edit_command(
     ticket_ref: Option<String>,
     title: Option<String>,
     description: Option<String>,
     priority: Option<String>,
+    severity: Option<String>,
     status: Option<String>,
+    due: Option<String>,
+    parent: Option<String>,
     add_tags: Option<String>,
     remove_tags: Option<String>,
+    fields: &[String],
     editor: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
@@ -70,6 +82,8 @@ pub fn handle_edit_command(
 
     // Load the ticket
     let mut ticket = storage.load(&ticket_id)?;
+    let old_status = ticket.status;
+    let was_done = old_status == Status::Done;
 
     // Track what was changed
     let mut changes = Vec::new();
@@ -77,6 +91,7 @@ pub fn handle_edit_command(
     // Open in editor if requested
     if editor {
         edit_in_editor(&mut ticket, &storage, output)?;
+        emit_cli_event(&project_root, &TicketEvent::Updated(ticket));
         return Ok(());
     }
 
@@ -91,6 +106,11 @@ pub fn handle_edit_command(
     if let Some(new_description) = description {
         ticket.description = new_description;
         changes.push("Description updated".to_string());
+
+        let config = Config::load_for_project(&project_root)?;
+        if storage.spill_description(&mut ticket, config.project.description_size_limit)? {
+            changes.push("Description moved to attachment".to_string());
+        }
     }
 
     // Update priority if provided
@@ -105,6 +125,22 @@ pub fn handle_edit_command(
         changes.push(format!("Priority: {old_priority} → {new_priority}"));
     }
 
+    // Update severity if provided
+    if let Some(severity_str) = severity {
+        let new_severity = if severity_str.eq_ignore_ascii_case("none") {
+            None
+        } else {
+            Some(Severity::try_from(severity_str.as_str()).map_err(VibeTicketError::InvalidInput)?)
+        };
+        let format = |s: Option<Severity>| s.map_or_else(|| "none".to_string(), |s| s.to_string());
+        changes.push(format!(
+            "Severity: {} → {}",
+            format(ticket.severity),
+            format(new_severity)
+        ));
+        ticket.severity = new_severity;
+    }
+
     // Update status if provided
     if let Some(status_str) = status {
         let new_status = Status::try_from(status_str.as_str())
@@ -121,10 +157,47 @@ pub fn handle_edit_command(
             (_, Status::Done) if old_status != Status::Done => {
                 ticket.closed_at = Some(chrono::Utc::now());
             },
+            (Status::Done, _) if new_status != Status::Done => {
+                ticket.closed_at = None;
+            },
             _ => {},
         }
     }
 
+    // Update due date if provided
+    if let Some(due_str) = due {
+        let new_due = if due_str.eq_ignore_ascii_case("none") {
+            None
+        } else {
+            Some(
+                NaiveDate::parse_from_str(&due_str, "%Y-%m-%d").map_err(|_| {
+                    VibeTicketError::InvalidInput(format!(
+                        "Invalid due date '{due_str}'. Expected YYYY-MM-DD or 'none'"
+                    ))
+                })?,
+            )
+        };
+        let format = |d: Option<NaiveDate>| d.map_or_else(|| "none".to_string(), |d| d.to_string());
+        changes.push(format!("Due: {} → {}", format(ticket.due), format(new_due)));
+        ticket.due = new_due;
+    }
+
+    // Update the parent (epic) if provided
+    if let Some(parent_ref) = parent {
+        let tickets = storage.load_all()?;
+        let new_parent = if parent_ref.eq_ignore_ascii_case("none") {
+            None
+        } else {
+            let id = resolve_ticket_ref(&storage, &parent_ref)?;
+            Some(storage.load(&id)?)
+        };
+        hierarchy::set_parent(&mut ticket, new_parent.as_ref(), &tickets)?;
+        changes.push(format!(
+            "Parent: {}",
+            new_parent.map_or_else(|| "none".to_string(), |p| p.slug)
+        ));
+    }
+
     // Add tags if provided
     if let Some(tags_str) = add_tags {
         let new_tags: Vec<String> = tags_str
@@ -153,6 +226,13 @@ pub fn handle_edit_command(
         changes.push("Tags removed".to_string());
     }
 
+    // Set user-defined fields if provided
+    if !fields.is_empty() {
+        let config = Config::load_for_project(&project_root)?;
+        set_fields(&mut ticket, &config.fields, fields)?;
+        changes.push(format!("Fields: {}", fields.join(", ")));
+    }
+
     // Check if any changes were made
     if changes.is_empty() {
         output.warning("No changes specified");
@@ -161,6 +241,19 @@ pub fn handle_edit_command(
 
     // Save the updated ticket
     storage.save(&ticket)?;
+    emit_cli_event(&project_root, &TicketEvent::Updated(ticket.clone()));
+    if ticket.status != old_status {
+        emit_cli_event(
+            &project_root,
+            &TicketEvent::StatusChanged(ticket.id.clone(), old_status, ticket.status),
+        );
+    }
+    if !was_done && ticket.status == Status::Done {
+        emit_cli_event(
+            &project_root,
+            &TicketEvent::Closed(ticket.id.clone(), String::new()),
+        );
+    }
 
     // Output results
     if output.is_json() {
@@ -174,6 +267,7 @@ pub fn handle_edit_command(
                 "status": ticket.status.to_string(),
                 "priority": ticket.priority.to_string(),
                 "tags": ticket.tags,
+                "due": ticket.due,
             },
             "changes": changes,
         }))?;
@@ -187,8 +281,17 @@ pub fn handle_edit_command(
         output.info("");
         output.info("Current state:");
         output.info(&format!("  Title: {}", ticket.title));
-        output.info(&format!("  Status: {}", ticket.status));
-        output.info(&format!("  Priority: {}", ticket.priority));
+        output.info(&format!(
+            "  Status: {}",
+            output.visuals().status_text(ticket.status)
+        ));
+        output.info(&format!(
+            "  Priority: {}",
+            output.visuals().priority_text(ticket.priority)
+        ));
+        if let Some(due) = ticket.due {
+            output.info(&format!("  Due: {due}"));
+        }
         if !ticket.tags.is_empty() {
             output.info(&format!("  Tags: {}", ticket.tags.join(", ")));
         }
@@ -204,7 +307,6 @@ fn edit_in_editor(
     output: &OutputFormatter,
 ) -> Result<()> {
     use std::io::Write as IoWrite;
-    use std::process::Command;
 
     // Create a temporary file with the ticket content
     let temp_dir = std::env::temp_dir();
@@ -220,12 +322,9 @@ fn edit_in_editor(
     file.write_all(yaml_content.as_bytes())
         .map_err(|e| VibeTicketError::io_error("write", &temp_file, e))?;
 
-    // Get editor from environment
-    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
-
-    // Open editor
-    let status = Command::new(&editor)
-        .arg(&temp_file)
+    // Open the editor from the environment
+    let editor = crate::platform::editor();
+    let status = crate::platform::editor_command(&editor, &temp_file)
         .status()
         .map_err(|e| VibeTicketError::custom(format!("Failed to launch editor: {e}")))?;
 
@@ -257,6 +356,9 @@ fn edit_in_editor(
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use tempfile::TempDir;
 
     #[test]
     fn test_tag_parsing() {
@@ -268,4 +370,46 @@ mod tests {
             .collect();
         assert_eq!(tags, vec!["bug", "ui", "urgent"]);
     }
+
+    #[test]
+    fn test_status_done_needs_closed_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        let dependency = Ticket::new("schema", "Schema");
+        let mut ticket = Ticket::new("migration", "Migration");
+        ticket.depends_on.push(dependency.id.clone());
+        storage.save(&dependency).unwrap();
+        storage.save(&ticket).unwrap();
+
+        let output = OutputFormatter::new(true, false);
+        let edit = |slug: &str| {
+            handle_edit_command(
+                Some(slug.to_string()),
+                None,
+                None,
+                None,
+                None,
+                Some("done".to_string()),
+                None,
+                None,
+                None,
+                None,
+                &[],
+                false,
+                temp.path().to_str(),
+                &output,
+            )
+        };
+        assert!(matches!(
+            edit("migration"),
+            Err(VibeTicketError::OpenDependencies { .. })
+        ));
+        assert_eq!(storage.load(&ticket.id).unwrap().status, Status::Todo);
+
+        edit("schema").unwrap();
+        edit("migration").unwrap();
+        let fresh = FileStorage::new(temp.path().join(".vibe-ticket"));
+        assert_eq!(fresh.load(&ticket.id).unwrap().status, Status::Done);
+    }
 }
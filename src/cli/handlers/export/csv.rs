@@ -1,38 +1,110 @@
 //! CSV export implementation
+//!
+//! The ticket rows only carry task counts. `export --include-tasks` adds a
+//! task section with one row per task: appended after a blank line on
+//! stdout, or written to `<name>.tasks.csv` next to an export file. `import`
+//! reads either back into the tickets' tasks.
 
-use super::Exporter;
+use std::path::{Path, PathBuf};
+
+use super::{ExportHeader, Exporter};
 use crate::core::Ticket;
+use crate::core::fields::field_text;
 use crate::error::{Result, VibeTicketError};
+use crate::storage::attachment_names;
 use csv::Writer;
 
+/// Columns of the task section
+pub const TASK_COLUMNS: [&str; 7] = [
+    "Ticket ID",
+    "Ticket Slug",
+    "Task ID",
+    "Title",
+    "Completed",
+    "Created At",
+    "Completed At",
+];
+
 /// CSV exporter implementation
 pub struct CsvExporter;
 
+impl CsvExporter {
+    /// Path of the task file written next to the CSV export at `path`
+    pub fn tasks_path_for(path: &Path) -> PathBuf {
+        path.with_extension("tasks.csv")
+    }
+
+    /// Exports the tasks of `tickets`, one row per task
+    pub fn export_tasks(&self, tickets: &[Ticket]) -> Result<String> {
+        let mut wtr = Writer::from_writer(vec![]);
+        wtr.write_record(TASK_COLUMNS)
+            .map_err(|e| VibeTicketError::serialization_error("CSV header", e))?;
+
+        for ticket in tickets {
+            for task in &ticket.tasks {
+                wtr.write_record(&[
+                    ticket.id.to_string(),
+                    ticket.slug.clone(),
+                    task.id.to_string(),
+                    task.title.clone(),
+                    task.completed.to_string(),
+                    task.created_at.to_rfc3339(),
+                    task.completed_at
+                        .map(|dt| dt.to_rfc3339())
+                        .unwrap_or_default(),
+                ])
+                .map_err(|e| VibeTicketError::serialization_error("CSV record", e))?;
+            }
+        }
+
+        let data = wtr
+            .into_inner()
+            .map_err(|e| VibeTicketError::serialization_error("CSV", e))?;
+        String::from_utf8(data).map_err(|e| VibeTicketError::serialization_error("CSV string", e))
+    }
+
+    /// Splits CSV content into its ticket rows and task section, if any
+    pub fn split_sections(content: &str) -> (&str, Option<&str>) {
+        let marker = format!("\n{}", TASK_COLUMNS.join(","));
+        content.find(&marker).map_or((content, None), |pos| {
+            (&content[..pos], Some(&content[pos + 1..]))
+        })
+    }
+}
+
 impl Exporter for CsvExporter {
-    fn export(&self, tickets: &[Ticket]) -> Result<String> {
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String> {
         let mut wtr = Writer::from_writer(vec![]);
 
         // Write header
-        wtr.write_record([
-            "ID",
-            "Slug",
-            "Title",
-            "Status",
-            "Priority",
-            "Assignee",
-            "Tags",
-            "Created At",
-            "Started At",
-            "Closed At",
-            "Tasks Total",
-            "Tasks Completed",
-            "Description",
-        ])
+        wtr.write_record(
+            [
+                "ID",
+                "Slug",
+                "Title",
+                "Status",
+                "Priority",
+                "Assignee",
+                "Tags",
+                "Created At",
+                "Started At",
+                "Closed At",
+                "Tasks Total",
+                "Tasks Completed",
+                "Description",
+                "Severity",
+                "Comments",
+                "Attachments",
+                "Relations",
+            ]
+            .into_iter()
+            .chain(header.fields.iter().map(String::as_str)),
+        )
         .map_err(|e| VibeTicketError::serialization_error("CSV header", e))?;
 
         // Write ticket records
         for ticket in tickets {
-            write_ticket_record(&mut wtr, ticket)?;
+            write_ticket_record(&mut wtr, ticket, &header.fields)?;
         }
 
         // Convert to string
@@ -48,37 +120,94 @@ impl Exporter for CsvExporter {
     }
 }
 
-/// Write a single ticket record to CSV
-fn write_ticket_record<W: std::io::Write>(wtr: &mut Writer<W>, ticket: &Ticket) -> Result<()> {
+/// Write a single ticket record to CSV, followed by the given user-defined fields
+fn write_ticket_record<W: std::io::Write>(
+    wtr: &mut Writer<W>,
+    ticket: &Ticket,
+    fields: &[String],
+) -> Result<()> {
     let tasks_total = ticket.tasks.len();
     let tasks_completed = ticket.tasks.iter().filter(|t| t.completed).count();
 
-    wtr.write_record(&[
-        ticket.id.to_string(),
-        ticket.slug.clone(),
-        ticket.title.clone(),
-        ticket.status.to_string(),
-        ticket.priority.to_string(),
-        ticket.assignee.clone().unwrap_or_default(),
-        ticket.tags.join(", "),
-        ticket.created_at.to_rfc3339(),
+    let fields = fields.iter().map(|name| {
         ticket
-            .started_at
-            .map(|dt| dt.to_rfc3339())
-            .unwrap_or_default(),
-        ticket
-            .closed_at
-            .map(|dt| dt.to_rfc3339())
-            .unwrap_or_default(),
-        tasks_total.to_string(),
-        tasks_completed.to_string(),
-        ticket.description.replace('\n', " "),
-    ])
+            .metadata
+            .get(name)
+            .map(field_text)
+            .unwrap_or_default()
+    });
+    wtr.write_record(
+        [
+            ticket.id.to_string(),
+            ticket.slug.clone(),
+            ticket.title.clone(),
+            ticket.status.to_string(),
+            ticket.priority.to_string(),
+            ticket.assignee.clone().unwrap_or_default(),
+            ticket.tags.join(", "),
+            ticket.created_at.to_rfc3339(),
+            ticket
+                .started_at
+                .map(|dt| dt.to_rfc3339())
+                .unwrap_or_default(),
+            ticket
+                .closed_at
+                .map(|dt| dt.to_rfc3339())
+                .unwrap_or_default(),
+            tasks_total.to_string(),
+            tasks_completed.to_string(),
+            ticket.description.replace('\n', " "),
+            ticket.severity.map(|s| s.to_string()).unwrap_or_default(),
+            ticket.comments.len().to_string(),
+            attachment_names(ticket).join(", "),
+            relations_text(ticket),
+        ]
+        .into_iter()
+        .chain(fields),
+    )
     .map_err(|e| VibeTicketError::serialization_error("CSV record", e))
 }
 
+/// Relations of a ticket as `kind:ticket-id` pairs, read back by `import`
+pub(super) fn relations_text(ticket: &Ticket) -> String {
+    ticket
+        .relations
+        .iter()
+        .map(|relation| format!("{}:{}", relation.kind, relation.ticket))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_section() {
+        let mut ticket = Ticket::new("with-tasks", "With tasks");
+        ticket.add_task("Write, then test");
+        let tickets = [ticket];
+
+        let content = CsvExporter.export(&tickets).unwrap();
+        let tasks = CsvExporter.export_tasks(&tickets).unwrap();
+        assert!(tasks.contains("with-tasks"));
+        assert!(tasks.contains("\"Write, then test\",false"));
+
+        let combined = format!("{content}\n{tasks}");
+        assert_eq!(
+            CsvExporter::split_sections(&combined),
+            (content.as_str(), Some(tasks.as_str()))
+        );
+        assert_eq!(
+            CsvExporter::split_sections(&content),
+            (content.as_str(), None)
+        );
+        assert_eq!(
+            CsvExporter::tasks_path_for(Path::new("out/tickets.csv")),
+            PathBuf::from("out/tickets.tasks.csv")
+        );
+    }
+
     #[test]
     fn test_csv_escaping() {
         let description = "This has\nnewlines and, commas";
@@ -0,0 +1,149 @@
+//! Graphviz DOT export implementation
+//!
+//! Exports the graph of tickets: one node per ticket, one edge per
+//! dependency and one per relation. Each relation is drawn once, from the
+//! ticket it was added on; `relates_to` edges have no direction.
+
+use super::{ExportHeader, Exporter};
+use crate::core::relations::{Relation, RelationKind};
+use crate::core::{Status, Ticket, TicketId};
+use crate::error::Result;
+use std::collections::HashSet;
+use std::fmt::Write as FmtWrite;
+
+/// DOT exporter implementation
+pub struct DotExporter;
+
+impl Exporter for DotExporter {
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String> {
+        let mut output = String::from("digraph tickets {\n");
+        if let Some(project) = &header.project {
+            writeln!(output, "  label={};", quote(project)).unwrap();
+        }
+        write!(
+            output,
+            "  // Exported by vibe-ticket {}",
+            env!("CARGO_PKG_VERSION")
+        )
+        .unwrap();
+        if let Some(exported_at) = header.exported_at {
+            write!(output, " at {}", exported_at.to_rfc3339()).unwrap();
+        }
+        output.push_str("\n  rankdir=LR;\n  node [shape=box];\n\n");
+
+        for ticket in tickets {
+            let label = format!("{}\n{}", ticket.slug, ticket.title);
+            write!(output, "  {} [label={}", node(&ticket.id), quote(&label)).unwrap();
+            if ticket.status == Status::Done {
+                output.push_str(", style=dashed");
+            }
+            output.push_str("];\n");
+        }
+
+        let exported: HashSet<&TicketId> = tickets.iter().map(|t| &t.id).collect();
+        let mut edges = String::new();
+        for ticket in tickets {
+            for dependency in ticket.depends_on.iter().filter(|id| exported.contains(id)) {
+                writeln!(
+                    edges,
+                    "  {} -> {} [label=\"depends on\"];",
+                    node(&ticket.id),
+                    node(dependency)
+                )
+                .unwrap();
+            }
+            for relation in ticket
+                .relations
+                .iter()
+                .filter(|r| exported.contains(&r.ticket) && is_drawn(&ticket.id, r))
+            {
+                let direction = if relation.kind == RelationKind::RelatesTo {
+                    ", dir=none"
+                } else {
+                    ""
+                };
+                writeln!(
+                    edges,
+                    "  {} -> {} [label=\"{}\", style=dotted{direction}];",
+                    node(&ticket.id),
+                    node(&relation.ticket),
+                    relation.kind
+                )
+                .unwrap();
+            }
+        }
+        if !edges.is_empty() {
+            output.push('\n');
+            output.push_str(&edges);
+        }
+
+        output.push_str("}\n");
+        Ok(output)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "DOT"
+    }
+}
+
+/// Whether the relation of the ticket `id` is drawn from that ticket
+///
+/// Inverse relations are drawn from the other ticket, and `relates_to`,
+/// recorded identically on both tickets, from the smaller ID.
+fn is_drawn(id: &TicketId, relation: &Relation) -> bool {
+    match relation.kind {
+        RelationKind::RelatesTo => id.to_string() < relation.ticket.to_string(),
+        kind => !kind.is_inverse(),
+    }
+}
+
+/// Node ID of a ticket
+fn node(id: &TicketId) -> String {
+    format!("\"{id}\"")
+}
+
+/// Quotes a string as a DOT string
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dependencies::add_dependency;
+    use crate::core::relations::add_relation;
+
+    #[test]
+    fn test_dot_export_draws_dependencies_and_relations() {
+        let mut crash = Ticket::new("crash", "Crash on \"save\"");
+        let mut refactor = Ticket::new("refactor", "Refactor storage");
+        let mut docs = Ticket::new("docs", "Docs");
+        add_dependency(&mut docs, &mut refactor, &[]).unwrap();
+        add_relation(&mut crash, &mut refactor, RelationKind::CausedBy, &[]).unwrap();
+        add_relation(&mut crash, &mut docs, RelationKind::RelatesTo, &[]).unwrap();
+
+        let dot = DotExporter
+            .export_with_header(
+                &[crash.clone(), refactor.clone(), docs.clone()],
+                &ExportHeader::reproducible(None),
+            )
+            .unwrap();
+
+        assert!(dot.starts_with("digraph tickets {"));
+        assert!(dot.contains(r#"[label="crash\nCrash on \"save\""]"#));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"depends on\"];",
+            docs.id, refactor.id
+        )));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"caused_by\", style=dotted];",
+            crash.id, refactor.id
+        )));
+        assert!(!dot.contains("label=\"causes\""));
+        assert_eq!(dot.matches("relates_to").count(), 1);
+    }
+}
@@ -0,0 +1,203 @@
+//! HTML kanban board export implementation
+//!
+//! Renders the tickets as a self-contained static page: one column per
+//! status, one card per ticket with its priority color and task progress.
+//! The page has no external resources, so it can be shared as a single file.
+
+use super::{ExportHeader, Exporter};
+use crate::core::{Priority, Status, Ticket};
+use crate::error::Result;
+use serde::Serialize;
+
+/// Columns of the board, left to right
+const COLUMNS: [Status; 5] = [
+    Status::Todo,
+    Status::Doing,
+    Status::Review,
+    Status::Blocked,
+    Status::Done,
+];
+
+/// HTML kanban board exporter implementation
+pub struct HtmlExporter;
+
+impl Exporter for HtmlExporter {
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String> {
+        let board = Board::new(tickets, header);
+        let context = tera::Context::from_serialize(&board)?;
+        Ok(tera::Tera::one_off(HTML_TEMPLATE, &context, true)?)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "HTML"
+    }
+}
+
+/// Data rendered by the template
+#[derive(Debug, Serialize)]
+struct Board {
+    title: String,
+    version: &'static str,
+    exported_at: Option<String>,
+    total: usize,
+    columns: Vec<Column>,
+}
+
+#[derive(Debug, Serialize)]
+struct Column {
+    status: String,
+    count: usize,
+    cards: Vec<Card>,
+}
+
+#[derive(Debug, Serialize)]
+struct Card {
+    id: String,
+    slug: String,
+    title: String,
+    priority: String,
+    color: &'static str,
+    assignee: Option<String>,
+    tags: Vec<String>,
+    tasks_done: usize,
+    tasks_total: usize,
+    progress: u32,
+}
+
+impl Board {
+    fn new(tickets: &[Ticket], header: &ExportHeader) -> Self {
+        let columns = COLUMNS
+            .iter()
+            .map(|status| {
+                let cards: Vec<Card> = tickets
+                    .iter()
+                    .filter(|t| t.status == *status)
+                    .map(Card::new)
+                    .collect();
+                Column {
+                    status: status.to_string(),
+                    count: cards.len(),
+                    cards,
+                }
+            })
+            .collect();
+
+        Self {
+            title: header
+                .project
+                .clone()
+                .unwrap_or_else(|| "Tickets".to_string()),
+            version: env!("CARGO_PKG_VERSION"),
+            exported_at: header
+                .exported_at
+                .map(|exported_at| header.format_date(exported_at)),
+            total: tickets.len(),
+            columns,
+        }
+    }
+}
+
+impl Card {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn new(ticket: &Ticket) -> Self {
+        Self {
+            id: ticket.id.short(),
+            slug: ticket.slug.clone(),
+            title: ticket.title.clone(),
+            priority: ticket.priority.to_string(),
+            color: priority_color(ticket.priority),
+            assignee: ticket.assignee.clone(),
+            tags: ticket.tags.clone(),
+            tasks_done: ticket.tasks.iter().filter(|t| t.completed).count(),
+            tasks_total: ticket.tasks.len(),
+            progress: ticket.completion_percentage().round() as u32,
+        }
+    }
+}
+
+/// Accent color of the cards of a priority
+const fn priority_color(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "#8c959f",
+        Priority::Medium => "#0969da",
+        Priority::High => "#bc4c00",
+        Priority::Critical => "#cf222e",
+    }
+}
+
+/// Template of the board page
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{ title }} – Kanban board</title>
+<style>
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 0; padding: 1.5em; color: #24292f; background: #f6f8fa; }
+header h1 { margin: 0; }
+header p { margin: .3em 0 1.2em; color: #57606a; }
+.board { display: flex; gap: 1em; align-items: flex-start; overflow-x: auto; }
+.column { flex: 1 0 16em; background: #eaeef2; border-radius: 8px; padding: .6em; }
+.column h2 { font-size: 1em; margin: .2em .3em .6em; }
+.column h2 span { color: #57606a; font-weight: normal; }
+.card { background: #fff; border-radius: 6px; border-left: 5px solid; padding: .6em .7em; margin-bottom: .6em; box-shadow: 0 1px 2px rgba(31, 35, 40, .15); }
+.card .title { font-weight: 600; margin-bottom: .3em; }
+.card .meta { font-size: .8em; color: #57606a; }
+.card code { background: #f6f8fa; padding: 0 .3em; border-radius: 4px; }
+.tag { display: inline-block; background: #ddf4ff; color: #0969da; border-radius: 1em; padding: 0 .5em; margin: .3em .2em 0 0; font-size: .75em; }
+.progress { height: 6px; background: #eaeef2; border-radius: 3px; margin-top: .5em; overflow: hidden; }
+.progress div { height: 100%; background: #1a7f37; }
+.empty { color: #8c959f; font-size: .9em; margin: .3em; }
+footer { margin-top: 1.5em; color: #57606a; font-size: .8em; }
+</style>
+</head>
+<body>
+<header>
+<h1>{{ title }}</h1>
+<p>{{ total }} tickets{% if exported_at %} · exported {{ exported_at }}{% endif %}</p>
+</header>
+<main class="board">
+{% for column in columns %}<section class="column">
+<h2>{{ column.status }} <span>{{ column.count }}</span></h2>
+{% for card in column.cards %}<article class="card" style="border-left-color: {{ card.color }}">
+<div class="title">{{ card.title }}</div>
+<div class="meta"><code>{{ card.id }}</code> {{ card.slug }} · <span style="color: {{ card.color }}">{{ card.priority }}</span>{% if card.assignee %} · @{{ card.assignee }}{% endif %}</div>
+{% if card.tags %}<div>{% for tag in card.tags %}<span class="tag">{{ tag }}</span>{% endfor %}</div>
+{% endif %}{% if card.tasks_total > 0 %}<div class="progress" title="{{ card.tasks_done }}/{{ card.tasks_total }} tasks"><div style="width: {{ card.progress }}%"></div></div>
+<div class="meta">{{ card.tasks_done }}/{{ card.tasks_total }} tasks</div>
+{% endif %}</article>
+{% else %}<p class="empty">No tickets</p>
+{% endfor %}</section>
+{% endfor %}</main>
+<footer>Generated by vibe-ticket {{ version }}</footer>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Task;
+
+    #[test]
+    fn test_html_board() {
+        let mut ticket = Ticket::new("board-me", "Ship <it>");
+        ticket.status = Status::Doing;
+        ticket.priority = Priority::Critical;
+        ticket.tasks = vec![Task::new("one".to_string()), Task::new("two".to_string())];
+        ticket.tasks[0].completed = true;
+        let tickets = vec![ticket, Ticket::new("idle", "Idle")];
+
+        let html = HtmlExporter
+            .export_with_header(&tickets, &ExportHeader::reproducible(Some("demo".into())))
+            .unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>demo</h1>"));
+        assert!(html.contains("Ship &lt;it&gt;"));
+        assert!(!html.contains("<it>"));
+        assert!(html.contains("border-left-color: #cf222e"));
+        assert!(html.contains("width: 50%"));
+        assert_eq!(html.matches("class=\"column\"").count(), COLUMNS.len());
+        assert_eq!(html.matches("No tickets").count(), 3);
+        assert!(!html.contains("exported"));
+    }
+}
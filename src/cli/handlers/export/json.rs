@@ -1,6 +1,6 @@
 //! JSON export implementation
 
-use super::{ExportMetadata, Exporter};
+use super::{ExportHeader, ExportMetadata, Exporter};
 use crate::core::Ticket;
 use crate::error::{Result, VibeTicketError};
 
@@ -8,8 +8,8 @@ use crate::error::{Result, VibeTicketError};
 pub struct JsonExporter;
 
 impl Exporter for JsonExporter {
-    fn export(&self, tickets: &[Ticket]) -> Result<String> {
-        let metadata = ExportMetadata::new(tickets.to_vec());
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String> {
+        let metadata = ExportMetadata::with_header(tickets.to_vec(), header);
 
         serde_json::to_string_pretty(&metadata)
             .map_err(|e| VibeTicketError::serialization_error("JSON", e))
@@ -63,6 +63,7 @@ mod tests {
         ticket.status = Status::Doing;
         ticket.tags = vec!["tag1".to_string(), "tag2".to_string()];
         ticket.assignee = Some("user@example.com".to_string());
+        ticket.add_comment("alice", "First comment");
 
         let tickets = vec![ticket];
         let result = exporter.export(&tickets);
@@ -70,6 +71,7 @@ mod tests {
 
         let json_str = result.unwrap();
         assert!(json_str.contains("Complex Ticket"));
+        assert!(json_str.contains("\"body\": \"First comment\""));
         assert!(json_str.contains("\"priority\": \"high\""));
         assert!(json_str.contains("\"status\": \"doing\""));
     }
@@ -0,0 +1,237 @@
+//! Checksum manifests written alongside export files
+//!
+//! An export to `tickets.json` is accompanied by `tickets.json.manifest.json`
+//! holding the SHA-256 of the whole file, its size, the number of tickets and
+//! a hash of each ticket. `import --verify` checks the file against the
+//! manifest before any ticket is written, so truncated or edited exports are
+//! rejected; the ticket hashes name the tickets that differ.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::Ticket;
+use crate::error::{Result, VibeTicketError};
+
+/// Version of the manifest layout
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Checksums of an export file and the tickets in it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// Version of the manifest layout
+    pub manifest_version: u32,
+    /// Version of vibe-ticket that wrote the export
+    pub vibe_ticket_version: String,
+    /// Export format
+    pub format: String,
+    /// File name of the export
+    pub file: String,
+    /// Size of the export in bytes
+    pub size: u64,
+    /// SHA-256 of the export
+    pub sha256: String,
+    /// Number of exported tickets
+    pub ticket_count: usize,
+    /// Hash of each exported ticket, in export order
+    pub tickets: Vec<TicketChecksum>,
+}
+
+/// Hash of one exported ticket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketChecksum {
+    /// Ticket ID
+    pub id: String,
+    /// Ticket slug
+    pub slug: String,
+    /// SHA-256 of the ticket serialized as JSON
+    pub sha256: String,
+}
+
+impl TicketChecksum {
+    /// Hashes a ticket
+    pub fn of(ticket: &Ticket) -> Result<Self> {
+        let json = serde_json::to_vec(ticket)
+            .map_err(|e| VibeTicketError::serialization_error("JSON", e))?;
+        Ok(Self {
+            id: ticket.id.to_string(),
+            slug: ticket.slug.clone(),
+            sha256: sha256(&json),
+        })
+    }
+}
+
+impl ExportManifest {
+    /// Builds the manifest of an export
+    pub fn new(format: &str, path: &Path, content: &[u8], tickets: &[Ticket]) -> Result<Self> {
+        Ok(Self {
+            manifest_version: MANIFEST_VERSION,
+            vibe_ticket_version: env!("CARGO_PKG_VERSION").to_string(),
+            format: format.to_lowercase(),
+            file: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            size: content.len() as u64,
+            sha256: sha256(content),
+            ticket_count: tickets.len(),
+            tickets: tickets
+                .iter()
+                .map(TicketChecksum::of)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Path of the manifest of the export at `path`
+    pub fn path_for(path: &Path) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".manifest.json");
+        PathBuf::from(name)
+    }
+
+    /// Reads the manifest of the export at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is missing, unreadable, or of a newer
+    /// layout version
+    pub fn load_for(path: &Path) -> Result<Self> {
+        let manifest_path = Self::path_for(path);
+        let content = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| VibeTicketError::io_error("read manifest", &manifest_path, e))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .map_err(|e| VibeTicketError::deserialization_error("manifest", e))?;
+        if manifest.manifest_version > MANIFEST_VERSION {
+            return Err(VibeTicketError::custom(format!(
+                "Manifest version {} is not supported (newest: {MANIFEST_VERSION})",
+                manifest.manifest_version
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Writes the manifest next to the export at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be written
+    pub fn save_for(&self, path: &Path) -> Result<PathBuf> {
+        let manifest_path = Self::path_for(path);
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| VibeTicketError::serialization_error("JSON", e))?;
+        std::fs::write(&manifest_path, json)
+            .map_err(|e| VibeTicketError::io_error("write", &manifest_path, e))?;
+        Ok(manifest_path)
+    }
+
+    /// Checks an export file against the manifest
+    ///
+    /// `tickets` are the tickets parsed from `content`, if it parsed; they
+    /// are compared one by one to name the tickets that differ.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing the mismatches if the file does not match
+    pub fn verify(&self, content: &str, tickets: Option<&[Ticket]>) -> Result<()> {
+        if content.len() as u64 == self.size && sha256(content.as_bytes()) == self.sha256 {
+            return Ok(());
+        }
+
+        let mut problems = Vec::new();
+        if (content.len() as u64) < self.size {
+            problems.push(format!(
+                "file is truncated ({} of {} bytes)",
+                content.len(),
+                self.size
+            ));
+        } else {
+            problems.push("file checksum does not match".to_string());
+        }
+        if let Some(tickets) = tickets {
+            problems.extend(self.ticket_mismatches(tickets));
+        }
+
+        Err(VibeTicketError::custom(format!(
+            "Export '{}' failed verification: {}",
+            self.file,
+            problems.join("; ")
+        )))
+    }
+
+    /// Describes the tickets that differ from the manifest
+    fn ticket_mismatches(&self, tickets: &[Ticket]) -> Vec<String> {
+        let mut problems = Vec::new();
+        if tickets.len() != self.ticket_count {
+            problems.push(format!(
+                "{} tickets instead of {}",
+                tickets.len(),
+                self.ticket_count
+            ));
+        }
+        for expected in &self.tickets {
+            match tickets.iter().find(|t| t.id.to_string() == expected.id) {
+                None => problems.push(format!("ticket '{}' is missing", expected.slug)),
+                Some(ticket) => {
+                    if TicketChecksum::of(ticket).map(|c| c.sha256).ok()
+                        != Some(expected.sha256.clone())
+                    {
+                        problems.push(format!("ticket '{}' was modified", expected.slug));
+                    }
+                },
+            }
+        }
+        for ticket in tickets {
+            if !self.tickets.iter().any(|c| c.id == ticket.id.to_string()) {
+                problems.push(format!("ticket '{}' was added", ticket.slug));
+            }
+        }
+        problems
+    }
+}
+
+/// Hex-encoded SHA-256 of `bytes`
+fn sha256(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::handlers::export::{ExportHeader, Exporter, JsonExporter};
+
+    #[test]
+    fn test_manifest_verification() {
+        let tickets = vec![Ticket::new("one", "One"), Ticket::new("two", "Two")];
+        let path = Path::new("/tmp/out/tickets.json");
+        let content = JsonExporter
+            .export_with_header(&tickets, &ExportHeader::reproducible(None))
+            .unwrap();
+        let manifest = ExportManifest::new("JSON", path, content.as_bytes(), &tickets).unwrap();
+        assert_eq!(manifest.file, "tickets.json");
+        assert_eq!(manifest.format, "json");
+        assert_eq!(
+            ExportManifest::path_for(path),
+            Path::new("/tmp/out/tickets.json.manifest.json")
+        );
+
+        manifest.verify(&content, Some(&tickets)).unwrap();
+
+        let truncated = &content[..content.len() / 2];
+        let err = manifest.verify(truncated, None).unwrap_err().to_string();
+        assert!(err.contains("truncated"), "{err}");
+
+        let mut tampered = tickets.clone();
+        tampered[1].title = "Changed".to_string();
+        tampered.push(Ticket::new("three", "Three"));
+        tampered.remove(0);
+        let tampered_content = content.replace("\"Two\"", "\"Changed\"");
+        let err = manifest
+            .verify(&tampered_content, Some(&tampered))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ticket 'one' is missing"), "{err}");
+        assert!(err.contains("ticket 'two' was modified"), "{err}");
+        assert!(err.contains("ticket 'three' was added"), "{err}");
+    }
+}
@@ -1,26 +1,40 @@
 //! Markdown export implementation
 
-use super::Exporter;
+use super::{ExportHeader, Exporter};
+use crate::cli::VisualRegistry;
+use crate::core::fields::field_text;
 use crate::core::{Status, Ticket};
 use crate::error::Result;
-use std::collections::HashMap;
+use crate::storage::attachment_names;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write as FmtWrite;
 
 /// Markdown exporter implementation
-pub struct MarkdownExporter;
+#[derive(Default)]
+pub struct MarkdownExporter {
+    /// Visuals used for the status section headings
+    visuals: VisualRegistry,
+}
+
+impl MarkdownExporter {
+    /// Creates an exporter rendering statuses with the given visuals
+    pub const fn new(visuals: VisualRegistry) -> Self {
+        Self { visuals }
+    }
+}
 
 impl Exporter for MarkdownExporter {
-    fn export(&self, tickets: &[Ticket]) -> Result<String> {
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String> {
         let mut output = String::new();
 
         // Write header
-        write_header(&mut output, tickets.len());
+        write_header(&mut output, tickets.len(), header);
 
         // Write summary
         write_summary(&mut output, tickets);
 
         // Write tickets grouped by status
-        write_tickets_by_status(&mut output, tickets);
+        write_tickets_by_status(&mut output, tickets, &self.visuals, header);
 
         Ok(output)
     }
@@ -31,14 +45,20 @@ impl Exporter for MarkdownExporter {
 }
 
 /// Write the document header
-fn write_header(output: &mut String, ticket_count: usize) {
+fn write_header(output: &mut String, ticket_count: usize, header: &ExportHeader) {
     writeln!(output, "# Ticket Export\n").unwrap();
-    writeln!(
-        output,
-        "**Exported at**: {}",
-        chrono::Local::now().format("%Y-%m-%d %H:%M")
-    )
-    .unwrap();
+    if let Some(project) = &header.project {
+        writeln!(output, "**Project**: {project}").unwrap();
+    }
+    writeln!(output, "**Version**: {}", env!("CARGO_PKG_VERSION")).unwrap();
+    if let Some(exported_at) = header.exported_at {
+        writeln!(
+            output,
+            "**Exported at**: {}",
+            header.format_date(exported_at)
+        )
+        .unwrap();
+    }
     writeln!(output, "**Total tickets**: {ticket_count}\n").unwrap();
 }
 
@@ -57,8 +77,8 @@ fn write_summary(output: &mut String, tickets: &[Ticket]) {
 }
 
 /// Count tickets by status
-fn count_by_status(tickets: &[Ticket]) -> HashMap<String, usize> {
-    let mut counts = HashMap::new();
+fn count_by_status(tickets: &[Ticket]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
     for ticket in tickets {
         *counts.entry(ticket.status.to_string()).or_insert(0) += 1;
     }
@@ -66,27 +86,24 @@ fn count_by_status(tickets: &[Ticket]) -> HashMap<String, usize> {
 }
 
 /// Write tickets grouped by status
-fn write_tickets_by_status(output: &mut String, tickets: &[Ticket]) {
+fn write_tickets_by_status(
+    output: &mut String,
+    tickets: &[Ticket],
+    visuals: &VisualRegistry,
+    header: &ExportHeader,
+) {
     let groups = group_by_status(tickets);
 
-    if let Some(tickets) = groups.get(&Status::Todo) {
-        write_status_section(output, "📋 Todo", tickets);
-    }
-
-    if let Some(tickets) = groups.get(&Status::Doing) {
-        write_status_section(output, "🔄 In Progress", tickets);
-    }
-
-    if let Some(tickets) = groups.get(&Status::Review) {
-        write_status_section(output, "👀 In Review", tickets);
-    }
-
-    if let Some(tickets) = groups.get(&Status::Blocked) {
-        write_status_section(output, "🚫 Blocked", tickets);
-    }
-
-    if let Some(tickets) = groups.get(&Status::Done) {
-        write_status_section(output, "✅ Done", tickets);
+    for status in [
+        Status::Todo,
+        Status::Doing,
+        Status::Review,
+        Status::Blocked,
+        Status::Done,
+    ] {
+        if let Some(tickets) = groups.get(&status) {
+            write_status_section(output, &visuals.status_text(status), tickets, header);
+        }
     }
 }
 
@@ -102,18 +119,33 @@ fn group_by_status(tickets: &[Ticket]) -> HashMap<Status, Vec<&Ticket>> {
 }
 
 /// Write a section for a specific status
-fn write_status_section(output: &mut String, title: &str, tickets: &[&Ticket]) {
+fn write_status_section(
+    output: &mut String,
+    title: &str,
+    tickets: &[&Ticket],
+    header: &ExportHeader,
+) {
     writeln!(output, "### {title}\n").unwrap();
     for ticket in tickets {
-        write_ticket(output, ticket);
+        write_ticket(output, ticket, header);
     }
 }
 
-/// Write a single ticket in Markdown format
-fn write_ticket(output: &mut String, ticket: &Ticket) {
+/// Write a single ticket in Markdown format, with the user-defined fields of
+/// the header
+fn write_ticket(output: &mut String, ticket: &Ticket, header: &ExportHeader) {
     writeln!(output, "#### {} - {}\n", ticket.slug, ticket.title).unwrap();
     writeln!(output, "- **Priority**: {}", ticket.priority).unwrap();
 
+    if let Some(severity) = ticket.severity {
+        writeln!(
+            output,
+            "- **Severity**: {severity} ({})",
+            severity.description()
+        )
+        .unwrap();
+    }
+
     if let Some(assignee) = &ticket.assignee {
         writeln!(output, "- **Assignee**: {assignee}").unwrap();
     }
@@ -127,10 +159,21 @@ fn write_ticket(output: &mut String, ticket: &Ticket) {
         writeln!(output, "- **Tasks**: {}/{}", completed, ticket.tasks.len()).unwrap();
     }
 
+    for name in &header.fields {
+        if let Some(value) = ticket.metadata.get(name) {
+            writeln!(output, "- **{name}**: {}", field_text(value)).unwrap();
+        }
+    }
+
+    let attachments = attachment_names(ticket);
+    if !attachments.is_empty() {
+        writeln!(output, "- **Attachments**: {}", attachments.join(", ")).unwrap();
+    }
+
     writeln!(
         output,
         "- **Created**: {}",
-        ticket.created_at.format("%Y-%m-%d")
+        header.format_date(ticket.created_at)
     )
     .unwrap();
 
@@ -139,6 +182,21 @@ fn write_ticket(output: &mut String, ticket: &Ticket) {
         writeln!(output, "{}", ticket.description).unwrap();
     }
 
+    if !ticket.comments.is_empty() {
+        writeln!(output, "\n##### Comments\n").unwrap();
+        for comment in &ticket.comments {
+            let edited = if comment.edited { " (edited)" } else { "" };
+            writeln!(
+                output,
+                "- **{}** ({}){edited}: {}",
+                comment.author,
+                header.format_date(comment.created_at),
+                comment.body.replace('\n', " ")
+            )
+            .unwrap();
+        }
+    }
+
     writeln!(output, "\n---\n").unwrap();
 }
 
@@ -149,7 +207,7 @@ mod tests {
 
     #[test]
     fn test_markdown_export() {
-        let exporter = MarkdownExporter;
+        let exporter = MarkdownExporter::default();
         let tickets = vec![
             Ticket::new("test-1".to_string(), "Test Ticket 1".to_string()),
             Ticket::new("test-2".to_string(), "Test Ticket 2".to_string()),
@@ -167,7 +225,7 @@ mod tests {
 
     #[test]
     fn test_markdown_export_empty() {
-        let exporter = MarkdownExporter;
+        let exporter = MarkdownExporter::default();
         let tickets: Vec<Ticket> = vec![];
 
         let result = exporter.export(&tickets);
@@ -179,7 +237,7 @@ mod tests {
 
     #[test]
     fn test_markdown_export_with_different_statuses() {
-        let exporter = MarkdownExporter;
+        let exporter = MarkdownExporter::default();
 
         let mut todo_ticket = Ticket::new("todo".to_string(), "Todo Ticket".to_string());
         todo_ticket.status = Status::Todo;
@@ -196,13 +254,13 @@ mod tests {
 
         let markdown = result.unwrap();
         assert!(markdown.contains("### 📋 Todo"));
-        assert!(markdown.contains("### 🔄 In Progress"));
+        assert!(markdown.contains("### 🔧 Doing"));
         assert!(markdown.contains("### ✅ Done"));
     }
 
     #[test]
     fn test_markdown_export_with_rich_ticket() {
-        let exporter = MarkdownExporter;
+        let exporter = MarkdownExporter::default();
 
         let mut ticket = Ticket::new("rich".to_string(), "Rich Ticket".to_string());
         ticket.description = "This is a detailed description\nwith multiple lines".to_string();
@@ -214,6 +272,7 @@ mod tests {
             Task::new("Task 2".to_string()),
         ];
         ticket.tasks[0].completed = true;
+        ticket.add_comment("alice", "Looks good");
 
         let tickets = vec![ticket];
         let result = exporter.export(&tickets);
@@ -225,11 +284,14 @@ mod tests {
         assert!(markdown.contains("- **Tags**: feature, urgent"));
         assert!(markdown.contains("- **Tasks**: 1/2"));
         assert!(markdown.contains("This is a detailed description"));
+        assert!(markdown.contains("##### Comments"));
+        assert!(markdown.contains("- **alice** ("));
+        assert!(markdown.contains("): Looks good"));
     }
 
     #[test]
     fn test_format_name() {
-        let exporter = MarkdownExporter;
+        let exporter = MarkdownExporter::default();
         assert_eq!(exporter.format_name(), "Markdown");
     }
 
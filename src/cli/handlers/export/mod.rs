@@ -4,46 +4,172 @@
 //! to various formats using the Exporter trait.
 
 mod csv;
+mod dot;
+mod html;
 mod json;
+mod manifest;
 mod markdown;
+#[cfg(feature = "xlsx")]
+mod xlsx;
 mod yaml;
 
-use crate::cli::{OutputFormatter, find_project_root};
+use crate::cli::{DateFormat, OutputFormatter, find_project_root};
+use crate::config::Config;
 use crate::core::Ticket;
 use crate::error::{Result, VibeTicketError};
 use crate::storage::{FileStorage, TicketRepository};
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
-pub use self::csv::CsvExporter;
+pub use self::csv::{CsvExporter, TASK_COLUMNS};
+pub use self::dot::DotExporter;
+pub use self::html::HtmlExporter;
 pub use self::json::JsonExporter;
+pub use self::manifest::ExportManifest;
 pub use self::markdown::MarkdownExporter;
+#[cfg(feature = "xlsx")]
+pub use self::xlsx::XlsxExporter;
 pub use self::yaml::YamlExporter;
 
 /// Common metadata structure for JSON and YAML exports
 #[derive(Debug, Serialize)]
 pub struct ExportMetadata {
+    pub schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub version: String,
     pub tickets: Vec<Ticket>,
-    pub exported_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
     pub total: usize,
 }
 
 impl ExportMetadata {
     /// Create new export metadata with the given tickets
     pub fn new(tickets: Vec<Ticket>) -> Self {
+        Self::with_header(tickets, &ExportHeader::default())
+    }
+
+    /// Create export metadata with the given tickets and header
+    pub fn with_header(tickets: Vec<Ticket>, header: &ExportHeader) -> Self {
         let total = tickets.len();
         Self {
+            schema_version: crate::cli::schema::SCHEMA_VERSION,
+            project: header.project.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
             tickets,
-            exported_at: Utc::now(),
+            exported_at: header.exported_at,
             total,
         }
     }
 }
 
+/// Header information written at the top of an export
+#[derive(Debug, Clone)]
+pub struct ExportHeader {
+    /// Name of the exported project
+    pub project: Option<String>,
+
+    /// Time of the export; `None` for reproducible exports
+    pub exported_at: Option<DateTime<Utc>>,
+
+    /// Names of the user-defined fields, exported as their own columns or
+    /// lines by the formats that do not carry the metadata
+    pub fields: Vec<String>,
+
+    /// Format of the dates of the document formats (Markdown, HTML)
+    pub dates: DateFormat,
+}
+
+impl Default for ExportHeader {
+    fn default() -> Self {
+        Self {
+            project: None,
+            exported_at: Some(Utc::now()),
+            fields: Vec::new(),
+            dates: DateFormat::default(),
+        }
+    }
+}
+
+impl ExportHeader {
+    /// Header without a timestamp, so identical tickets export byte-for-byte identically
+    pub fn reproducible(project: Option<String>) -> Self {
+        Self {
+            project,
+            exported_at: None,
+            fields: Vec::new(),
+            dates: DateFormat::default(),
+        }
+    }
+
+    /// Formats a date of a document export with the configured pattern
+    ///
+    /// Dates are in local time, or in UTC for reproducible exports so they
+    /// do not depend on the timezone of the machine.
+    pub fn format_date(&self, at: DateTime<Utc>) -> String {
+        if self.exported_at.is_some() {
+            self.dates.format_absolute(at)
+        } else {
+            self.dates.format_utc(at)
+        }
+    }
+}
+
+/// Ordering of exported tickets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportSort {
+    /// By creation date, ties broken by ID
+    Created,
+    /// By ticket ID
+    Id,
+    /// By slug
+    Slug,
+}
+
+impl std::str::FromStr for ExportSort {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "created" | "created_at" => Ok(Self::Created),
+            "id" => Ok(Self::Id),
+            "slug" => Ok(Self::Slug),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Invalid sort field: {s}. Valid fields: created, id, slug"
+            ))),
+        }
+    }
+}
+
+impl ExportSort {
+    /// Sort tickets in place; the order is total, so exports are deterministic
+    pub fn sort(self, tickets: &mut [Ticket]) {
+        match self {
+            Self::Created => tickets.sort_by(|a, b| {
+                a.created_at
+                    .cmp(&b.created_at)
+                    .then_with(|| a.id.to_string().cmp(&b.id.to_string()))
+            }),
+            Self::Id => tickets.sort_by_key(|t| t.id.to_string()),
+            Self::Slug => tickets.sort_by(|a, b| {
+                a.slug
+                    .cmp(&b.slug)
+                    .then_with(|| a.id.to_string().cmp(&b.id.to_string()))
+            }),
+        }
+    }
+}
+
 /// Trait for ticket exporters
 pub trait Exporter {
     /// Export tickets to the target format
-    fn export(&self, tickets: &[Ticket]) -> Result<String>;
+    fn export(&self, tickets: &[Ticket]) -> Result<String> {
+        self.export_with_header(tickets, &ExportHeader::default())
+    }
+
+    /// Export tickets to the target format with the given header
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String>;
 
     /// Get the format name for display
     fn format_name(&self) -> &'static str;
@@ -51,49 +177,168 @@ pub trait Exporter {
 
 /// Handler for the `export` command
 ///
-/// Exports tickets to various formats using the appropriate exporter
+/// Exports tickets to various formats using the appropriate exporter.
+/// With `reproducible` set, the export timestamp is omitted so that exporting
+/// unchanged tickets produces identical files. With `include_tasks`, a CSV
+/// export also writes one row per task (see [`CsvExporter::export_tasks`]).
+/// XLSX workbooks are binary and are only written to `output_path`.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_export_command(
     format: &str,
     output_path: Option<String>,
     include_archived: bool,
+    sort_by: &str,
+    reproducible: bool,
+    include_tasks: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
-    // Get exporter for the format
-    let exporter: Box<dyn Exporter> = match format.to_lowercase().as_str() {
-        "json" => Box::new(JsonExporter),
-        "yaml" => Box::new(YamlExporter),
-        "csv" => Box::new(CsvExporter),
-        "markdown" | "md" => Box::new(MarkdownExporter),
-        _ => {
-            return Err(VibeTicketError::custom(format!(
-                "Unsupported export format: {format}. Supported formats: json, yaml, csv, markdown"
-            )));
-        },
-    };
+    let sort: ExportSort = sort_by.parse()?;
+    let is_csv = format.eq_ignore_ascii_case("csv");
+    if include_tasks && !is_csv {
+        return Err(VibeTicketError::InvalidInput(
+            "--include-tasks only applies to csv exports; the other formats include tasks already"
+                .to_string(),
+        ));
+    }
 
-    // Load and filter tickets
-    let tickets = load_tickets(project_dir, include_archived)?;
+    if format.eq_ignore_ascii_case("xlsx") {
+        let path = output_path.ok_or_else(|| {
+            VibeTicketError::InvalidInput(
+                "XLSX exports are binary; write them to a file with --output".to_string(),
+            )
+        })?;
+        let (tickets, header) = load_export(project_dir, include_archived, sort, reproducible)?;
+        let content = export_xlsx(&tickets, &header)?;
+        let manifest =
+            ExportManifest::new(format, std::path::Path::new(&path), &content, &tickets)?;
+        return output_results(
+            &content,
+            Some(path),
+            Some(&manifest),
+            tickets.len(),
+            "XLSX",
+            include_archived,
+            output,
+        );
+    }
+
+    // Get exporter for the format
+    let exporter = exporter_for(format, output)?;
+    let (tickets, header) = load_export(project_dir, include_archived, sort, reproducible)?;
 
     // Export using the appropriate exporter
-    let content = exporter.export(&tickets)?;
+    let mut content = exporter.export_with_header(&tickets, &header)?;
+    let tasks = include_tasks
+        .then(|| CsvExporter.export_tasks(&tickets))
+        .transpose()?;
+    let tasks_path = output_path
+        .as_deref()
+        .map(|path| CsvExporter::tasks_path_for(std::path::Path::new(path)));
+    if let (Some(tasks), None) = (&tasks, &tasks_path) {
+        content.push('\n');
+        content.push_str(tasks);
+    }
+
+    // Checksums written next to an export file
+    let manifest = output_path
+        .as_deref()
+        .map(|path| {
+            ExportManifest::new(
+                format,
+                std::path::Path::new(path),
+                content.as_bytes(),
+                &tickets,
+            )
+        })
+        .transpose()?;
 
     // Output results
     output_results(
-        content,
+        content.as_bytes(),
         output_path,
+        manifest.as_ref(),
         tickets.len(),
         exporter.format_name(),
         include_archived,
         output,
-    )
+    )?;
+
+    if let (Some(tasks), Some(path)) = (tasks, tasks_path) {
+        std::fs::write(&path, tasks).map_err(|e| VibeTicketError::io_error("write", &path, e))?;
+        output.info(&format!("Tasks: {}", path.display()));
+    }
+
+    Ok(())
 }
 
-/// Load tickets from storage
-fn load_tickets(project_dir: Option<&str>, include_archived: bool) -> Result<Vec<Ticket>> {
+/// Get the exporter of a text format
+fn exporter_for(format: &str, output: &OutputFormatter) -> Result<Box<dyn Exporter>> {
+    Ok(match format.to_lowercase().as_str() {
+        "json" => Box::new(JsonExporter),
+        "yaml" => Box::new(YamlExporter),
+        "csv" => Box::new(CsvExporter),
+        "markdown" | "md" => Box::new(MarkdownExporter::new(output.visuals().clone())),
+        "html" => Box::new(HtmlExporter),
+        "dot" => Box::new(DotExporter),
+        _ => {
+            return Err(VibeTicketError::custom(format!(
+                "Unsupported export format: {format}. Supported formats: json, yaml, csv, markdown, html, dot, xlsx"
+            )));
+        },
+    })
+}
+
+/// Export tickets to an XLSX workbook
+#[cfg(feature = "xlsx")]
+fn export_xlsx(tickets: &[Ticket], header: &ExportHeader) -> Result<Vec<u8>> {
+    XlsxExporter::export(tickets, header)
+}
+
+/// Export tickets to an XLSX workbook
+#[cfg(not(feature = "xlsx"))]
+fn export_xlsx(_tickets: &[Ticket], _header: &ExportHeader) -> Result<Vec<u8>> {
+    Err(VibeTicketError::custom(
+        "XLSX export is not available in this build; rebuild vibe-ticket with --features xlsx",
+    ))
+}
+
+/// Load the tickets to export and build the export header
+fn load_export(
+    project_dir: Option<&str>,
+    include_archived: bool,
+    sort: ExportSort,
+    reproducible: bool,
+) -> Result<(Vec<Ticket>, ExportHeader)> {
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
-    let storage = FileStorage::new(&vibe_ticket_dir);
+    let tickets = load_tickets(&vibe_ticket_dir, include_archived, sort)?;
+
+    let config = Config::load_from_path(vibe_ticket_dir.join("config.yaml")).ok();
+    let project = config.as_ref().map(|config| config.project.name.clone());
+    let mut header = if reproducible {
+        ExportHeader::reproducible(project)
+    } else {
+        ExportHeader {
+            project,
+            ..ExportHeader::default()
+        }
+    };
+    if let Some(config) = config {
+        header.dates = DateFormat::from_config(&config.ui);
+        header.fields = config.fields.into_keys().collect();
+    }
+
+    Ok((tickets, header))
+}
+
+/// Load tickets from storage
+fn load_tickets(
+    vibe_ticket_dir: &std::path::Path,
+    include_archived: bool,
+    sort: ExportSort,
+) -> Result<Vec<Ticket>> {
+    let storage = FileStorage::new(vibe_ticket_dir);
 
     let mut tickets = storage.load_all()?;
 
@@ -107,16 +352,16 @@ fn load_tickets(project_dir: Option<&str>, include_archived: bool) -> Result<Vec
         });
     }
 
-    // Sort tickets by creation date
-    tickets.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    sort.sort(&mut tickets);
 
     Ok(tickets)
 }
 
 /// Output export results
 fn output_results(
-    content: String,
+    content: &[u8],
     output_path: Option<String>,
+    manifest: Option<&ExportManifest>,
     ticket_count: usize,
     format_name: &str,
     include_archived: bool,
@@ -128,6 +373,10 @@ fn output_results(
 
         output.success(&format!("Exported {ticket_count} tickets to {path}"));
         output.info(&format!("Format: {format_name}"));
+        if let Some(manifest) = manifest {
+            let manifest_path = manifest.save_for(std::path::Path::new(&path))?;
+            output.info(&format!("Manifest: {}", manifest_path.display()));
+        }
         if !include_archived {
             output.info(
                 "Note: Archived tickets were excluded. Use --include-archived to include them.",
@@ -135,7 +384,7 @@ fn output_results(
         }
     } else {
         // Output to stdout
-        println!("{content}");
+        println!("{}", String::from_utf8_lossy(content));
     }
 
     Ok(())
@@ -150,14 +399,26 @@ mod tests {
     fn create_test_ticket() -> Ticket {
         Ticket {
             id: TicketId::new(),
+            short_id: None,
             slug: "test-ticket".to_string(),
             title: "Test Ticket".to_string(),
             description: "Test description".to_string(),
             status: Status::Todo,
             priority: Priority::Medium,
+            severity: None,
             tags: vec!["test".to_string()],
             assignee: None,
             tasks: vec![],
+            work_log: Vec::new(),
+            comments: Vec::new(),
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: Vec::new(),
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: Default::default(),
             created_at: Utc::now(),
             started_at: None,
@@ -187,5 +448,82 @@ mod tests {
     test_exporter!(test_json_exporter, JsonExporter, "\"total\": 1");
     test_exporter!(test_csv_exporter, CsvExporter, "test-ticket");
     test_exporter!(test_yaml_exporter, YamlExporter, "total: 1");
-    test_exporter!(test_markdown_exporter, MarkdownExporter, "# Ticket Export");
+    test_exporter!(
+        test_markdown_exporter,
+        MarkdownExporter::default(),
+        "# Ticket Export"
+    );
+
+    #[test]
+    fn test_reproducible_exports() {
+        let mut ticket = create_test_ticket();
+        for key in ["zeta", "alpha", "mid", "beta"] {
+            ticket
+                .metadata
+                .insert(key.to_string(), serde_json::json!(key));
+        }
+        let tickets = vec![ticket];
+        let header = ExportHeader::reproducible(Some("demo".to_string()));
+
+        let exporters: Vec<Box<dyn Exporter>> = vec![
+            Box::new(JsonExporter),
+            Box::new(YamlExporter),
+            Box::new(CsvExporter),
+            Box::new(MarkdownExporter::default()),
+            Box::new(HtmlExporter),
+        ];
+        for exporter in exporters {
+            let first = exporter.export_with_header(&tickets, &header).unwrap();
+            let second = exporter.export_with_header(&tickets, &header).unwrap();
+            assert_eq!(
+                first,
+                second,
+                "{} export is not stable",
+                exporter.format_name()
+            );
+            assert!(!first.contains("xported at"));
+            assert!(!first.contains("exported_at"));
+        }
+
+        let json = JsonExporter.export_with_header(&tickets, &header).unwrap();
+        assert!(json.contains("\"project\": \"demo\""));
+        assert!(json.contains(&format!("\"version\": \"{}\"", env!("CARGO_PKG_VERSION"))));
+    }
+
+    #[cfg(feature = "xlsx")]
+    #[test]
+    fn test_xlsx_export() {
+        let mut ticket = create_test_ticket();
+        ticket.tasks.push(crate::core::Task::new("Write tests"));
+        let tickets = vec![ticket];
+        let header = ExportHeader::reproducible(Some("demo".to_string()));
+
+        let first = XlsxExporter::export(&tickets, &header).unwrap();
+        let second = XlsxExporter::export(&tickets, &header).unwrap();
+        assert!(first.starts_with(b"PK"), "XLSX files are zip archives");
+        assert_eq!(first, second, "XLSX export is not stable");
+    }
+
+    #[test]
+    fn test_export_sort() {
+        let mut tickets = vec![
+            create_test_ticket(),
+            create_test_ticket(),
+            create_test_ticket(),
+        ];
+        tickets[0].slug = "c".to_string();
+        tickets[2].slug = "a".to_string();
+
+        ExportSort::Id.sort(&mut tickets);
+        let ids: Vec<_> = tickets.iter().map(|t| t.id.to_string()).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+
+        ExportSort::Slug.sort(&mut tickets);
+        assert_eq!(tickets[0].slug, "a");
+        assert_eq!(tickets[2].slug, "test-ticket");
+
+        assert!("priority".parse::<ExportSort>().is_err());
+    }
 }
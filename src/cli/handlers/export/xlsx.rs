@@ -0,0 +1,307 @@
+//! Excel (XLSX) export implementation
+//!
+//! Available with the `xlsx` feature. The workbook has a `Tickets` sheet
+//! with the columns of the CSV export, a `Tasks` sheet with one row per task
+//! and a `Summary` sheet counting tickets by status, priority and assignee.
+//! Dates are Excel dates in UTC, counts are numbers, and the ticket and task
+//! sheets have auto-filters below a frozen header row.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use rust_xlsxwriter::{DocProperties, Format, Workbook, Worksheet, XlsxError};
+
+use super::csv::relations_text;
+use super::{ExportHeader, TASK_COLUMNS};
+use crate::core::fields::field_text;
+use crate::core::{Priority, Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::attachment_names;
+
+/// Columns of the ticket sheet, before the user-defined fields
+const TICKET_COLUMNS: [&str; 17] = [
+    "ID",
+    "Slug",
+    "Title",
+    "Status",
+    "Priority",
+    "Assignee",
+    "Tags",
+    "Created At",
+    "Started At",
+    "Closed At",
+    "Tasks Total",
+    "Tasks Completed",
+    "Description",
+    "Severity",
+    "Comments",
+    "Attachments",
+    "Relations",
+];
+
+/// XLSX exporter implementation
+///
+/// Spreadsheets are binary, so this exporter returns bytes instead of
+/// implementing [`Exporter`](super::Exporter).
+pub struct XlsxExporter;
+
+impl XlsxExporter {
+    /// Exports `tickets` to an XLSX workbook
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workbook cannot be built.
+    pub fn export(tickets: &[Ticket], header: &ExportHeader) -> Result<Vec<u8>> {
+        workbook(tickets, header).map_err(|e| VibeTicketError::serialization_error("XLSX", e))
+    }
+}
+
+/// Cell formats shared by the sheets
+struct Formats {
+    header: Format,
+    date: Format,
+}
+
+/// Builds the workbook and returns its bytes
+fn workbook(tickets: &[Ticket], header: &ExportHeader) -> std::result::Result<Vec<u8>, XlsxError> {
+    let formats = Formats {
+        header: Format::new().set_bold(),
+        date: Format::new().set_num_format("yyyy-mm-dd hh:mm"),
+    };
+
+    let mut workbook = Workbook::new();
+    // A fixed creation time keeps reproducible exports identical
+    let created = header.exported_at.unwrap_or(DateTime::UNIX_EPOCH);
+    workbook.set_properties(&DocProperties::new().set_creation_datetime(&created));
+
+    write_tickets(
+        workbook.add_worksheet().set_name("Tickets")?,
+        tickets,
+        &header.fields,
+        &formats,
+    )?;
+    write_tasks(
+        workbook.add_worksheet().set_name("Tasks")?,
+        tickets,
+        &formats,
+    )?;
+    write_summary(
+        workbook.add_worksheet().set_name("Summary")?,
+        tickets,
+        header,
+        &formats,
+    )?;
+
+    workbook.save_to_buffer()
+}
+
+/// Writes the bold header row of a sheet, freezes it and filters below it
+fn write_header<'a>(
+    sheet: &mut Worksheet,
+    columns: impl IntoIterator<Item = &'a str>,
+    rows: usize,
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    let mut last_col = 0;
+    for (col, name) in columns.into_iter().enumerate() {
+        last_col = col_num(col);
+        sheet.write_string_with_format(0, last_col, name, &formats.header)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(0, 0, row_num(rows), last_col)?;
+    Ok(())
+}
+
+/// Writes one row per ticket, with the user-defined fields last
+fn write_tickets(
+    sheet: &mut Worksheet,
+    tickets: &[Ticket],
+    fields: &[String],
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    write_header(
+        sheet,
+        TICKET_COLUMNS
+            .into_iter()
+            .chain(fields.iter().map(String::as_str)),
+        tickets.len(),
+        formats,
+    )?;
+
+    for (index, ticket) in tickets.iter().enumerate() {
+        let row = row_num(index + 1);
+        sheet.write_string(row, 0, ticket.id.to_string())?;
+        sheet.write_string(row, 1, &ticket.slug)?;
+        sheet.write_string(row, 2, &ticket.title)?;
+        sheet.write_string(row, 3, ticket.status.to_string())?;
+        sheet.write_string(row, 4, ticket.priority.to_string())?;
+        sheet.write_string(row, 5, ticket.assignee.clone().unwrap_or_default())?;
+        sheet.write_string(row, 6, ticket.tags.join(", "))?;
+        write_date(sheet, row, 7, Some(ticket.created_at), formats)?;
+        write_date(sheet, row, 8, ticket.started_at, formats)?;
+        write_date(sheet, row, 9, ticket.closed_at, formats)?;
+        sheet.write_number(row, 10, count(ticket.tasks.len()))?;
+        sheet.write_number(
+            row,
+            11,
+            count(ticket.tasks.iter().filter(|t| t.completed).count()),
+        )?;
+        sheet.write_string(row, 12, &ticket.description)?;
+        sheet.write_string(
+            row,
+            13,
+            ticket.severity.map(|s| s.to_string()).unwrap_or_default(),
+        )?;
+        sheet.write_number(row, 14, count(ticket.comments.len()))?;
+        sheet.write_string(row, 15, attachment_names(ticket).join(", "))?;
+        sheet.write_string(row, 16, relations_text(ticket))?;
+
+        for (offset, name) in fields.iter().enumerate() {
+            let col = col_num(TICKET_COLUMNS.len() + offset);
+            match ticket.metadata.get(name) {
+                Some(value) if value.is_number() => {
+                    sheet.write_number(row, col, value.as_f64().unwrap_or_default())?;
+                },
+                Some(value) => {
+                    sheet.write_string(row, col, field_text(value))?;
+                },
+                None => {},
+            }
+        }
+    }
+
+    sheet.autofit();
+    Ok(())
+}
+
+/// Writes one row per task
+fn write_tasks(
+    sheet: &mut Worksheet,
+    tickets: &[Ticket],
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    let tasks = tickets
+        .iter()
+        .flat_map(|ticket| ticket.tasks.iter().map(move |task| (ticket, task)));
+    write_header(sheet, TASK_COLUMNS, tasks.clone().count(), formats)?;
+
+    for (index, (ticket, task)) in tasks.enumerate() {
+        let row = row_num(index + 1);
+        sheet.write_string(row, 0, ticket.id.to_string())?;
+        sheet.write_string(row, 1, &ticket.slug)?;
+        sheet.write_string(row, 2, task.id.to_string())?;
+        sheet.write_string(row, 3, &task.title)?;
+        sheet.write_boolean(row, 4, task.completed)?;
+        write_date(sheet, row, 5, Some(task.created_at), formats)?;
+        write_date(sheet, row, 6, task.completed_at, formats)?;
+    }
+
+    sheet.autofit();
+    Ok(())
+}
+
+/// Writes the project, the ticket and task totals, and the number of
+/// tickets by status, priority and assignee
+fn write_summary(
+    sheet: &mut Worksheet,
+    tickets: &[Ticket],
+    header: &ExportHeader,
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    let mut row = 0;
+    if let Some(project) = &header.project {
+        sheet.write_string(row, 0, "Project")?;
+        sheet.write_string(row, 1, project)?;
+        row += 1;
+    }
+    if let Some(exported_at) = header.exported_at {
+        sheet.write_string(row, 0, "Exported At")?;
+        write_date(sheet, row, 1, Some(exported_at), formats)?;
+        row += 1;
+    }
+    let tasks: Vec<_> = tickets.iter().flat_map(|ticket| &ticket.tasks).collect();
+    for (label, n) in [
+        ("Tickets", tickets.len()),
+        ("Tasks", tasks.len()),
+        (
+            "Tasks Completed",
+            tasks.iter().filter(|task| task.completed).count(),
+        ),
+    ] {
+        sheet.write_string(row, 0, label)?;
+        sheet.write_number(row, 1, count(n))?;
+        row += 1;
+    }
+
+    let by_status = Status::all().into_iter().map(|status| {
+        let n = tickets.iter().filter(|t| t.status == status).count();
+        (status.to_string(), n)
+    });
+    row = write_counts(sheet, row + 1, "Status", by_status, formats)?;
+
+    let by_priority = Priority::all().into_iter().map(|priority| {
+        let n = tickets.iter().filter(|t| t.priority == priority).count();
+        (priority.to_string(), n)
+    });
+    row = write_counts(sheet, row + 1, "Priority", by_priority, formats)?;
+
+    let mut by_assignee = BTreeMap::new();
+    for ticket in tickets {
+        let assignee = ticket.assignee.as_deref().unwrap_or("(unassigned)");
+        *by_assignee.entry(assignee.to_string()).or_insert(0) += 1;
+    }
+    write_counts(sheet, row + 1, "Assignee", by_assignee, formats)?;
+
+    sheet.autofit();
+    Ok(())
+}
+
+/// Writes a table of ticket counts starting at `row`, returning the row
+/// after it
+fn write_counts(
+    sheet: &mut Worksheet,
+    row: u32,
+    title: &str,
+    counts: impl IntoIterator<Item = (String, usize)>,
+    formats: &Formats,
+) -> std::result::Result<u32, XlsxError> {
+    sheet.write_string_with_format(row, 0, title, &formats.header)?;
+    sheet.write_string_with_format(row, 1, "Tickets", &formats.header)?;
+    let mut row = row + 1;
+    for (label, n) in counts {
+        sheet.write_string(row, 0, label)?;
+        sheet.write_number(row, 1, count(n))?;
+        row += 1;
+    }
+    Ok(row)
+}
+
+/// Writes a UTC timestamp as an Excel date, or leaves the cell empty
+fn write_date(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    at: Option<DateTime<Utc>>,
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    if let Some(at) = at {
+        sheet.write_datetime_with_format(row, col, at.naive_utc(), &formats.date)?;
+    }
+    Ok(())
+}
+
+/// Converts a count to a cell number
+#[allow(clippy::cast_precision_loss)]
+const fn count(n: usize) -> f64 {
+    n as f64
+}
+
+/// Converts a row index to a row number, saturating at the sheet limit
+fn row_num(index: usize) -> u32 {
+    u32::try_from(index).unwrap_or(u32::MAX)
+}
+
+/// Converts a column index to a column number, saturating at the sheet limit
+fn col_num(index: usize) -> u16 {
+    u16::try_from(index).unwrap_or(u16::MAX)
+}
@@ -1,6 +1,6 @@
 //! YAML export implementation
 
-use super::{ExportMetadata, Exporter};
+use super::{ExportHeader, ExportMetadata, Exporter};
 use crate::core::Ticket;
 use crate::error::{Result, VibeTicketError};
 
@@ -8,8 +8,8 @@ use crate::error::{Result, VibeTicketError};
 pub struct YamlExporter;
 
 impl Exporter for YamlExporter {
-    fn export(&self, tickets: &[Ticket]) -> Result<String> {
-        let metadata = ExportMetadata::new(tickets.to_vec());
+    fn export_with_header(&self, tickets: &[Ticket], header: &ExportHeader) -> Result<String> {
+        let metadata = ExportMetadata::with_header(tickets.to_vec(), header);
 
         serde_yaml::to_string(&metadata)
             .map_err(|e| VibeTicketError::serialization_error("YAML", e))
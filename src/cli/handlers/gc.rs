@@ -0,0 +1,143 @@
+//! Handler for the `gc` command
+//!
+//! This module reports data whose ticket or spec no longer exists (see
+//! [`FileStorage::find_orphans`]) and deletes it with `--prune`.
+
+use std::io::{BufRead, IsTerminal, Write as IoWrite};
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{ErrorContext, Result};
+use crate::storage::FileStorage;
+
+/// Handler for the `gc` command
+///
+/// `--prune` asks for confirmation on a terminal; `--force` skips it. When
+/// there is no terminal to ask on, nothing is deleted without `--force`.
+///
+/// # Arguments
+///
+/// * `prune` - Whether to delete the orphaned data
+/// * `force` - Skip the confirmation
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the data cannot be
+/// read or deleted
+pub fn handle_gc_command(
+    prune: bool,
+    force: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let vibe_dir = project_root.join(".vibe-ticket");
+    let storage = FileStorage::new(&vibe_dir);
+
+    let orphans = storage.find_orphans()?;
+    let reclaimable: u64 = orphans.iter().map(|o| o.bytes).sum();
+
+    if !output.is_json() {
+        if orphans.is_empty() {
+            output.success("No orphaned data found");
+            return Ok(());
+        }
+        output.info(&format!("Found {} orphaned item(s):", orphans.len()));
+        for orphan in &orphans {
+            let path = orphan.path.strip_prefix(&vibe_dir).unwrap_or(&orphan.path);
+            output.info(&format!(
+                "  {:<15} {:>10}  {}",
+                orphan.kind.to_string(),
+                format_bytes(orphan.bytes),
+                path.display()
+            ));
+        }
+        output.info(&format!("Reclaimable: {}", format_bytes(reclaimable)));
+    }
+
+    let pruned = prune
+        && !orphans.is_empty()
+        && (force || (!output.is_json() && confirm(orphans.len(), reclaimable)?));
+    if pruned {
+        for orphan in &orphans {
+            storage.remove_orphan(orphan)?;
+        }
+    }
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "orphans": orphans,
+            "reclaimable_bytes": reclaimable,
+            "pruned": pruned,
+        }))?;
+    } else if pruned {
+        output.success(&format!(
+            "Deleted {} orphaned item(s), freed {}",
+            orphans.len(),
+            format_bytes(reclaimable)
+        ));
+    } else if prune {
+        output.warning("Nothing was deleted. Use --force to delete without confirmation.");
+    } else {
+        output.info("Run with --prune to delete them.");
+    }
+
+    Ok(())
+}
+
+/// Asks on the terminal whether to delete the orphaned data
+///
+/// Returns `false` without asking when standard input is not a terminal.
+fn confirm(count: usize, bytes: u64) -> Result<bool> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        return Ok(false);
+    }
+
+    let mut stderr = std::io::stderr();
+    write!(
+        stderr,
+        "Delete {count} orphaned item(s) ({})? [y/N] ",
+        format_bytes(bytes)
+    )
+    .and_then(|()| stderr.flush())
+    .context("Failed to write confirmation prompt")?;
+
+    let mut answer = String::new();
+    stdin
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Formats a byte count for display (e.g., `1.5 KiB`)
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
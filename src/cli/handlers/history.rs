@@ -0,0 +1,54 @@
+//! Handler for the `history` command
+//!
+//! This module prints the full history of a ticket, built from its audit log
+//! (see [`crate::core::audit`]), work-log notes and comments.
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::sections;
+use crate::error::Result;
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Handler for the `history` command
+///
+/// Unlike `show --history`, the history is never collapsed.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the ticket is not
+/// found.
+pub fn handle_history_command(
+    ticket_ref: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+    let ticket = storage.load(&ticket_id)?;
+    let log = storage.load_history(&ticket_id)?;
+    let events = sections::history(&ticket, &log);
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "events": events,
+        }))?;
+        return Ok(());
+    }
+
+    output.info(&format!("History of ticket '{}':", ticket.slug));
+    for event in &events {
+        output.info(&format!("  {} {event}", output.dates().format(event.at)));
+    }
+
+    Ok(())
+}
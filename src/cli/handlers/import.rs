@@ -1,10 +1,15 @@
 //! Handler for the `import` command
 //!
 //! This module implements the logic for importing tickets
-//! from various formats (JSON, YAML, CSV).
-
-use crate::cli::{OutputFormatter, find_project_root};
-use crate::core::{Priority, Status, Ticket, TicketId};
+//! from various formats (JSON, YAML, CSV, markdown plans).
+
+use super::export::{CsvExporter, ExportManifest, TASK_COLUMNS};
+use crate::cli::{OutputFormatter, find_project_root, slugify};
+use crate::config::Config;
+use crate::core::fields::{FieldDefinition, FieldSchema, validate_fields};
+use crate::core::plan;
+use crate::core::relations::{Relation, RelationKind};
+use crate::core::{Priority, Severity, Status, Task, TaskId, Ticket, TicketId};
 use crate::error::{Result, VibeTicketError};
 use crate::storage::{FileStorage, TicketRepository};
 use std::collections::HashMap;
@@ -14,7 +19,10 @@ use std::collections::HashMap;
 /// Imports tickets from various formats:
 /// 1. JSON - Full structured data
 /// 2. YAML - Human-readable structured data
-/// 3. CSV - Spreadsheet format
+/// 3. CSV - Spreadsheet format; tasks are read from a task section after the
+///    tickets, or from `<file>.tasks.csv` next to the file
+/// 4. Markdown plan - A document such as a PRD, one ticket per work item of
+///    [`plan::parse`], with an epic for the whole plan if `epic` is set
 ///
 /// # Arguments
 ///
@@ -22,13 +30,18 @@ use std::collections::HashMap;
 /// * `format` - Optional format (auto-detected if not specified)
 /// * `skip_validation` - Whether to skip validation
 /// * `dry_run` - Whether to perform a dry run (don't actually import)
+/// * `verify` - Whether to check the file against its export manifest first
+/// * `epic` - Whether to group the tickets of a markdown plan under an epic
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
+#[allow(clippy::too_many_arguments)]
 pub fn handle_import_command(
     file_path: &str,
     format: Option<&str>,
     skip_validation: bool,
     dry_run: bool,
+    verify: bool,
+    epic: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -50,21 +63,37 @@ pub fn handle_import_command(
         detect_format(file_path, &content)?
     };
 
+    if epic && !format.eq_ignore_ascii_case("markdown-plan") {
+        return Err(VibeTicketError::custom(
+            "--epic is only supported with the markdown-plan format",
+        ));
+    }
+
     // Parse tickets based on format
-    let tickets = match format.to_lowercase().as_str() {
-        "json" => import_json(&content)?,
-        "yaml" => import_yaml(&content)?,
-        "csv" => import_csv(&content)?,
+    let schema = Config::load_for_project(&project_root)?.fields;
+    let parsed = match format.to_lowercase().as_str() {
+        "json" => import_json(&content),
+        "yaml" => import_yaml(&content),
+        "csv" => read_tasks_file(file_path)
+            .and_then(|tasks| import_csv(&content, tasks.as_deref(), &schema)),
+        "markdown-plan" => Ok(import_markdown_plan(&content, file_path, epic)),
         _ => {
             return Err(VibeTicketError::custom(format!(
-                "Unsupported import format: {format}. Supported formats: json, yaml, csv"
+                "Unsupported import format: {format}. Supported formats: json, yaml, csv, markdown-plan"
             )));
         },
     };
 
+    // Check the file against its manifest before anything is written; a
+    // truncated file may not even parse
+    if verify {
+        verify_export(file_path, &content, parsed.as_deref().ok(), output)?;
+    }
+    let tickets = parsed?;
+
     // Validate tickets
     if !skip_validation {
-        validate_tickets(&tickets, &storage)?;
+        validate_tickets(&tickets, &storage, &schema)?;
     }
 
     // Show what will be imported
@@ -101,51 +130,66 @@ pub fn handle_import_command(
 
     // Perform the import if not dry run
     if !dry_run {
-        let mut imported = 0;
-        let mut skipped = 0;
-        let mut errors = Vec::new();
-
-        for ticket in tickets {
-            // Check if ticket with same slug already exists
-            if storage.find_ticket_by_slug(&ticket.slug)?.is_some() {
-                skipped += 1;
-                if !output.is_json() {
-                    output.warning(&format!(
-                        "Skipping '{}': ticket with this slug already exists",
-                        ticket.slug
-                    ));
-                }
-                continue;
-            }
+        import_tickets(&storage, tickets, output)?;
+    }
+
+    Ok(())
+}
 
-            // Save the ticket
-            match storage.save(&ticket) {
-                Ok(()) => imported += 1,
-                Err(e) => {
-                    errors.push(format!("Failed to import '{}': {}", ticket.slug, e));
-                },
+/// Saves the tickets whose slug is not taken yet and reports the results
+fn import_tickets(
+    storage: &FileStorage,
+    tickets: Vec<Ticket>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let mut skipped = 0;
+    let mut errors = Vec::new();
+    let mut to_import = Vec::new();
+
+    for ticket in tickets {
+        // Check if ticket with same slug already exists
+        if storage.find_ticket_by_slug(&ticket.slug)?.is_some() {
+            skipped += 1;
+            if !output.is_json() {
+                output.warning(&format!(
+                    "Skipping '{}': ticket with this slug already exists",
+                    ticket.slug
+                ));
             }
+            continue;
         }
+        to_import.push(ticket);
+    }
 
-        // Report results
-        if output.is_json() {
-            output.print_json(&serde_json::json!({
-                "status": "completed",
-                "imported": imported,
-                "skipped": skipped,
-                "errors": errors,
-            }))?;
-        } else {
-            output.info("");
-            output.success(&format!(
-                "Import completed: {imported} imported, {skipped} skipped"
-            ));
+    // Save the tickets in one transaction so they can refer to each other;
+    // none is imported if one of them breaks an integrity constraint or the
+    // import is interrupted
+    let imported = match storage.save_all(&to_import) {
+        Ok(()) => to_import.len(),
+        Err(e) => {
+            errors.push(format!("Failed to import tickets: {e}"));
+            0
+        },
+    };
 
-            if !errors.is_empty() {
-                output.error("Errors occurred during import:");
-                for error in errors {
-                    output.error(&format!("  • {error}"));
-                }
+    // Report results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "completed",
+            "imported": imported,
+            "skipped": skipped,
+            "errors": errors,
+        }))?;
+    } else {
+        output.info("");
+        output.success(&format!(
+            "Import completed: {imported} imported, {skipped} skipped"
+        ));
+
+        if !errors.is_empty() {
+            output.error("Errors occurred during import:");
+            for error in errors {
+                output.error(&format!("  • {error}"));
             }
         }
     }
@@ -153,6 +197,26 @@ pub fn handle_import_command(
     Ok(())
 }
 
+/// Checks an import file against the manifest written by `export`
+fn verify_export(
+    file_path: &str,
+    content: &str,
+    tickets: Option<&[Ticket]>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let path = std::path::Path::new(file_path);
+    let manifest = ExportManifest::load_for(path)?;
+    manifest.verify(content, tickets)?;
+    if !output.is_json() {
+        output.success(&format!(
+            "Verified {} tickets against {}",
+            manifest.ticket_count,
+            ExportManifest::path_for(path).display()
+        ));
+    }
+    Ok(())
+}
+
 /// Detect format from file extension or content
 fn detect_format(file_path: &str, content: &str) -> Result<String> {
     // Try to detect from file extension
@@ -161,6 +225,7 @@ fn detect_format(file_path: &str, content: &str) -> Result<String> {
             "json" => return Ok("json".to_string()),
             "yaml" | "yml" => return Ok("yaml".to_string()),
             "csv" => return Ok("csv".to_string()),
+            "md" | "markdown" => return Ok("markdown-plan".to_string()),
             _ => {},
         }
     }
@@ -225,11 +290,141 @@ fn import_yaml(content: &str) -> Result<Vec<Ticket>> {
     ))
 }
 
+/// Creates tickets for the work items of a markdown plan
+///
+/// Items become open tickets with the item's checkboxes as tasks and the
+/// headings it is nested under as tags. With `epic`, a ticket for the plan
+/// itself, titled after its level 1 heading or else the file name, becomes
+/// the parent of the others. Slugs are prefixed with the current time like
+/// those of `new`, and numbered when two items share a title.
+fn import_markdown_plan(content: &str, file_path: &str, epic: bool) -> Vec<Ticket> {
+    let plan = plan::parse(content);
+    let slug_prefix = chrono::Local::now().format("%Y%m%d%H%M").to_string();
+    let mut slugs = std::collections::HashSet::new();
+    let mut unique_slug = |title: &str, index: usize| {
+        let mut base = slugify(title);
+        if base.is_empty() {
+            base = format!("item-{}", index + 1);
+        }
+        let mut slug = format!("{slug_prefix}-{base}");
+        let mut suffix = 1;
+        while !slugs.insert(slug.clone()) {
+            suffix += 1;
+            slug = format!("{slug_prefix}-{base}-{suffix}");
+        }
+        slug
+    };
+
+    let mut tickets = Vec::with_capacity(plan.items.len() + 1);
+    let parent = epic.then(|| {
+        let title = plan.title.clone().unwrap_or_else(|| {
+            std::path::Path::new(file_path).file_stem().map_or_else(
+                || "Plan".to_string(),
+                |stem| stem.to_string_lossy().into_owned(),
+            )
+        });
+        let mut ticket = Ticket::new(unique_slug(&title, 0), title);
+        ticket.description.clone_from(&plan.description);
+        ticket.tags.push("epic".to_string());
+        let id = ticket.id.clone();
+        tickets.push(ticket);
+        id
+    });
+
+    for (index, item) in plan.items.into_iter().enumerate() {
+        let mut ticket = Ticket::new(unique_slug(&item.title, index), item.title);
+        ticket.description = item.description;
+        ticket.tags = item.tags;
+        ticket.parent.clone_from(&parent);
+        for plan_task in item.tasks {
+            let mut task = Task::new(plan_task.title);
+            if plan_task.done {
+                task.complete();
+            }
+            ticket.tasks.push(task);
+        }
+        tickets.push(ticket);
+    }
+    tickets
+}
+
+/// Reads the task file written next to a CSV export, if there is one
+fn read_tasks_file(file_path: &str) -> Result<Option<String>> {
+    let path = CsvExporter::tasks_path_for(std::path::Path::new(file_path));
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| VibeTicketError::io_error("read", &path, e))
+}
+
 /// Import tickets from CSV
-fn import_csv(content: &str) -> Result<Vec<Ticket>> {
+///
+/// A task section in `content` takes precedence over `tasks_file`. Columns
+/// named after a field of `schema` are read into the tickets' fields.
+fn import_csv(
+    content: &str,
+    tasks_file: Option<&str>,
+    schema: &FieldSchema,
+) -> Result<Vec<Ticket>> {
+    let (content, task_section) = CsvExporter::split_sections(content);
+    let mut tickets = import_csv_tickets(content, schema)?;
+    if let Some(tasks) = task_section.or(tasks_file) {
+        import_csv_tasks(tasks, &mut tickets)?;
+    }
+    Ok(tickets)
+}
+
+/// Parses an optional RFC 3339 timestamp column, empty meaning none
+fn parse_optional_date(value: &str, column: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|date| Some(date.with_timezone(&chrono::Utc)))
+        .map_err(|e| VibeTicketError::custom(format!("Invalid {column} date: {e}")))
+}
+
+/// Parses the `kind:ticket-id` pairs of the Relations column
+fn parse_relations(value: Option<&str>) -> Result<Vec<Relation>> {
+    value
+        .unwrap_or_default()
+        .split(", ")
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let invalid = || VibeTicketError::custom(format!("Invalid relation: {pair}"));
+            let (kind, ticket) = pair.split_once(':').ok_or_else(invalid)?;
+            Ok(Relation {
+                kind: RelationKind::try_from(kind).map_err(|_| invalid())?,
+                ticket: TicketId::parse_str(ticket).map_err(|_| invalid())?,
+            })
+        })
+        .collect()
+}
+
+/// Parses the ticket rows of a CSV export
+fn import_csv_tickets(content: &str, schema: &FieldSchema) -> Result<Vec<Ticket>> {
     let mut rdr = csv::Reader::from_reader(content.as_bytes());
     let mut tickets = Vec::new();
 
+    // Columns holding user-defined fields; invalid values are kept as text
+    // for validation to report
+    let headers = rdr
+        .headers()
+        .map_err(|e| VibeTicketError::deserialization_error("CSV header", e))?
+        .clone();
+    let relations_column = headers.iter().position(|header| header == "Relations");
+    let field_columns: Vec<(usize, &str, &FieldDefinition)> = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, header)| {
+            schema
+                .get_key_value(header)
+                .map(|(name, definition)| (index, name.as_str(), definition))
+        })
+        .collect();
+
     for result in rdr.records() {
         let record = result.map_err(|e| VibeTicketError::deserialization_error("CSV record", e))?;
 
@@ -266,41 +461,53 @@ fn import_csv(content: &str) -> Result<Vec<Ticket>> {
             .map_err(|e| VibeTicketError::custom(format!("Invalid created_at date: {e}")))?
             .with_timezone(&chrono::Utc);
 
-        let started_at = if record[8].is_empty() {
-            None
-        } else {
-            Some(
-                chrono::DateTime::parse_from_rfc3339(&record[8])
-                    .map_err(|e| VibeTicketError::custom(format!("Invalid started_at date: {e}")))?
-                    .with_timezone(&chrono::Utc),
-            )
-        };
+        let started_at = parse_optional_date(&record[8], "started_at")?;
+        let closed_at = parse_optional_date(&record[9], "closed_at")?;
 
-        let closed_at = if record[9].is_empty() {
-            None
-        } else {
-            Some(
-                chrono::DateTime::parse_from_rfc3339(&record[9])
-                    .map_err(|e| VibeTicketError::custom(format!("Invalid closed_at date: {e}")))?
-                    .with_timezone(&chrono::Utc),
-            )
+        // Severity is an optional trailing column added after the original 13
+        let severity = match record.get(13) {
+            Some(value) if !value.is_empty() => Some(
+                Severity::try_from(value)
+                    .map_err(|_| VibeTicketError::custom(format!("Invalid severity: {value}")))?,
+            ),
+            _ => None,
         };
 
-        let ticket = Ticket {
+        let mut ticket = Ticket {
             id,
+            short_id: None,
             slug: record[1].to_string(),
             title: record[2].to_string(),
             description: record[12].to_string(),
             priority,
+            severity,
             status,
             tags,
             created_at,
             started_at,
             closed_at,
             assignee,
-            tasks: Vec::new(), // CSV doesn't include task details
+            tasks: Vec::new(), // Filled from the task section, if any
+            work_log: Vec::new(),
+            comments: Vec::new(), // CSV only carries the comment count
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: parse_relations(relations_column.and_then(|index| record.get(index)))?,
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: HashMap::new(),
         };
+        for (index, name, definition) in &field_columns {
+            if let Some(raw) = record.get(*index).filter(|raw| !raw.is_empty()) {
+                let value = definition
+                    .parse(name, raw)
+                    .unwrap_or_else(|_| serde_json::Value::from(raw));
+                ticket.metadata.insert((*name).to_string(), value);
+            }
+        }
 
         tickets.push(ticket);
     }
@@ -308,10 +515,68 @@ fn import_csv(content: &str) -> Result<Vec<Ticket>> {
     Ok(tickets)
 }
 
+/// Adds the tasks of a CSV task section to their tickets
+///
+/// Tasks are matched to tickets by ticket ID, falling back to the slug.
+fn import_csv_tasks(content: &str, tickets: &mut [Ticket]) -> Result<()> {
+    let parse_date = |value: &str, field: &str| {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .map_err(|e| VibeTicketError::custom(format!("Invalid task {field} date: {e}")))
+    };
+
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
+    for result in rdr.records() {
+        let record = result.map_err(|e| VibeTicketError::deserialization_error("CSV task", e))?;
+        if record.len() < TASK_COLUMNS.len() {
+            return Err(VibeTicketError::custom(format!(
+                "CSV task rows must have {} columns",
+                TASK_COLUMNS.len()
+            )));
+        }
+
+        let ticket = tickets
+            .iter_mut()
+            .find(|t| t.id.to_string() == record[0] || t.slug == record[1])
+            .ok_or_else(|| {
+                VibeTicketError::custom(format!(
+                    "Task '{}' belongs to ticket '{}', which is not in the import",
+                    &record[3], &record[1]
+                ))
+            })?;
+
+        let id = if record[2].is_empty() {
+            TaskId::new()
+        } else {
+            TaskId::parse_str(&record[2])
+                .map_err(|_| VibeTicketError::custom(format!("Invalid task ID: {}", &record[2])))?
+        };
+        let mut task = Task::with_id(id, &record[3]);
+        task.completed = record[4].parse().map_err(|_| {
+            VibeTicketError::custom(format!("Invalid task completion: {}", &record[4]))
+        })?;
+        task.created_at = parse_date(&record[5], "created_at")?;
+        if !record[6].is_empty() {
+            task.completed_at = Some(parse_date(&record[6], "completed_at")?);
+        }
+        ticket.tasks.push(task);
+    }
+
+    Ok(())
+}
+
 /// Validate tickets before import
-fn validate_tickets(tickets: &[Ticket], storage: &FileStorage) -> Result<()> {
+fn validate_tickets(tickets: &[Ticket], storage: &FileStorage, schema: &FieldSchema) -> Result<()> {
     let mut errors = Vec::new();
 
+    // Check the values of the user-defined fields
+    errors.extend(
+        tickets
+            .iter()
+            .filter_map(|ticket| validate_fields(ticket, schema).err())
+            .map(|e| e.to_string()),
+    );
+
     // Check for duplicate slugs within import
     let mut seen_slugs = std::collections::HashSet::new();
     for ticket in tickets {
@@ -349,9 +614,52 @@ mod tests {
         assert_eq!(detect_format("data.json", "{}").unwrap(), "json");
         assert_eq!(detect_format("data.yaml", "tickets:").unwrap(), "yaml");
         assert_eq!(detect_format("data.csv", "a,b,c").unwrap(), "csv");
+        assert_eq!(detect_format("prd.md", "# Plan").unwrap(), "markdown-plan");
 
         // Test content-based detection
         assert_eq!(detect_format("unknown", "[{\"test\": 1}]").unwrap(), "json");
         assert_eq!(detect_format("unknown", "---\ntickets:").unwrap(), "yaml");
     }
+
+    #[test]
+    fn test_csv_relations_round_trip() {
+        use super::super::export::Exporter;
+        use crate::core::relations::add_relation;
+
+        let mut a = Ticket::new("a", "A");
+        let mut b = Ticket::new("b", "B");
+        add_relation(&mut a, &mut b, RelationKind::Supersedes, &[]).unwrap();
+        add_relation(&mut a, &mut b, RelationKind::RelatesTo, &[]).unwrap();
+
+        let content = CsvExporter.export(&[a.clone(), b.clone()]).unwrap();
+        let tickets = import_csv_tickets(&content, &FieldSchema::default()).unwrap();
+        assert_eq!(tickets[0].relations, a.relations);
+        assert_eq!(tickets[1].relations, b.relations);
+
+        assert!(parse_relations(Some("blocks:x")).is_err());
+        assert!(parse_relations(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_markdown_plan_tickets() {
+        let content = "# Search\n\nFind tickets fast.\n\n## Backend\n### Index\n- [ ] Build\n- [x] Design\n### Index\n";
+        let tickets = import_markdown_plan(content, "prd.md", true);
+        assert_eq!(tickets.len(), 3);
+
+        let epic = &tickets[0];
+        assert_eq!(epic.title, "Search");
+        assert_eq!(epic.description, "Find tickets fast.");
+        assert!(epic.slug.ends_with("-search"));
+
+        let index = &tickets[1];
+        assert_eq!(index.parent.as_ref(), Some(&epic.id));
+        assert_eq!(index.tags, vec!["backend"]);
+        assert_eq!(index.total_tasks_count(), 2);
+        assert_eq!(index.completed_tasks_count(), 1);
+        assert!(tickets[2].slug.ends_with("-index-2"));
+
+        let tickets = import_markdown_plan(content, "prd.md", false);
+        assert_eq!(tickets.len(), 2);
+        assert!(tickets.iter().all(|ticket| ticket.parent.is_none()));
+    }
 }
@@ -0,0 +1,374 @@
+//! Handlers for the `in` command and its subcommands
+//!
+//! `in "text"` captures a thought into the inbox (see
+//! [`crate::core::InboxEntry`]) without asking for a slug. `in process`
+//! goes through the entries, oldest first, and turns each into a ticket or
+//! discards it; entries that are skipped stay in the inbox.
+
+use std::io::{BufRead, Write as IoWrite};
+use std::path::Path;
+
+use crate::cli::{OutputFormatter, find_project_root, slugify};
+use crate::core::InboxEntry;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::FileStorage;
+
+use super::new::{NewTicketInput, save_new_ticket};
+
+/// Longest slug suggested for an inbox entry
+const MAX_SUGGESTED_SLUG_LEN: usize = 50;
+
+/// Handler for `in <text>`
+///
+/// # Arguments
+///
+/// * `text` - Text to capture
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the text is empty or the inbox cannot be written
+pub fn handle_inbox_capture(
+    text: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let storage = open_storage(project_dir)?.1;
+    let entry = InboxEntry::new(text);
+    if entry.text.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Nothing to capture; give the text of the entry".to_string(),
+        ));
+    }
+    let count = storage.capture(entry.clone())?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "entry": entry,
+            "inbox": count,
+        }))?;
+    } else {
+        output.success(&format!("Captured into the inbox ({count} entries)"));
+    }
+
+    Ok(())
+}
+
+/// Handler for `in` without text and `in list`
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the inbox cannot
+/// be read
+pub fn handle_inbox_list(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let entries = open_storage(project_dir)?.1.load_inbox()?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({ "entries": entries }))?;
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        output.info("The inbox is empty");
+        return Ok(());
+    }
+    for (number, entry) in entries.iter().enumerate() {
+        output.info(&format!(
+            "{:>3}. {} ({})",
+            number + 1,
+            entry.title(),
+            output.dates().format(entry.captured_at)
+        ));
+    }
+    output.info(&format!(
+        "{} entries; turn them into tickets with: vibe-ticket in process",
+        entries.len()
+    ));
+
+    Ok(())
+}
+
+/// Handler for `in process`
+///
+/// Prompts on standard error and reads the answers from standard input.
+///
+/// # Errors
+///
+/// Returns an error if the inbox cannot be read or written, or a prompt
+/// fails
+pub fn handle_inbox_process(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let (project_root, storage) = open_storage(project_dir)?;
+    let entries = storage.load_inbox()?;
+    if entries.is_empty() {
+        output.info("The inbox is empty");
+        if output.is_json() {
+            output.print_json(&ProcessOutcome::default())?;
+        }
+        return Ok(());
+    }
+
+    let stdin = std::io::stdin();
+    let outcome = process_entries(
+        &entries,
+        &project_root,
+        &storage,
+        &mut stdin.lock(),
+        &mut std::io::stderr(),
+    )?;
+
+    if output.is_json() {
+        output.print_json(&outcome)?;
+    } else {
+        output.success(&format!(
+            "Created {} ticket(s), discarded {} entries; {} left in the inbox",
+            outcome.created.len(),
+            outcome.discarded,
+            outcome.remaining
+        ));
+    }
+
+    Ok(())
+}
+
+/// What processing the inbox did
+#[derive(Debug, Default, serde::Serialize)]
+struct ProcessOutcome {
+    /// Slugs of the created tickets
+    created: Vec<String>,
+
+    /// Number of discarded entries
+    discarded: usize,
+
+    /// Number of entries left in the inbox
+    remaining: usize,
+}
+
+/// Answer to the prompt for an inbox entry
+enum Action {
+    Ticket,
+    Discard,
+    Skip,
+    Quit,
+}
+
+/// Asks what to do with each of `entries` and does it
+///
+/// Each entry is removed from the inbox as soon as it is turned into a
+/// ticket or discarded, so quitting halfway loses nothing. A ticket that
+/// cannot be created, e.g. for an invalid slug, is reported and the entry
+/// asked about again.
+fn process_entries<R: BufRead, W: IoWrite>(
+    entries: &[InboxEntry],
+    project_root: &Path,
+    storage: &FileStorage,
+    input: &mut R,
+    out: &mut W,
+) -> Result<ProcessOutcome> {
+    let mut outcome = ProcessOutcome {
+        remaining: entries.len(),
+        ..ProcessOutcome::default()
+    };
+    // Messages of the ticket creation are left out of the prompts
+    let quiet = OutputFormatter::new(true, false);
+
+    'entries: for (number, entry) in entries.iter().enumerate() {
+        writeln!(out, "\n[{}/{}] {}", number + 1, entries.len(), entry.text)
+            .context("Failed to write prompt")?;
+        loop {
+            let slug = match read_action(input, out)? {
+                Action::Ticket => read_slug(input, out, entry)?,
+                Action::Discard => {
+                    outcome.remaining = storage.remove_from_inbox(entry)?;
+                    outcome.discarded += 1;
+                    continue 'entries;
+                },
+                Action::Skip => continue 'entries,
+                Action::Quit => None,
+            };
+            let Some(slug) = slug else {
+                break 'entries;
+            };
+
+            let input = NewTicketInput {
+                slug: Some(slug),
+                title: Some(entry.title().to_string()),
+                description: entry.description().map(ToString::to_string),
+                ..NewTicketInput::default()
+            };
+            match save_new_ticket(input, false, project_root, storage, &quiet) {
+                Ok(ticket) => {
+                    writeln!(
+                        out,
+                        "Created ticket '{}' (ID: {})",
+                        ticket.slug,
+                        ticket.display_id()
+                    )
+                    .context("Failed to write prompt")?;
+                    outcome.remaining = storage.remove_from_inbox(entry)?;
+                    outcome.created.push(ticket.slug);
+                    continue 'entries;
+                },
+                Err(e) => writeln!(out, "Error: {e}").context("Failed to write prompt")?,
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Reads what to do with an entry, asking again until the answer is valid
+///
+/// An empty answer makes a ticket; the end of the input quits.
+fn read_action<R: BufRead, W: IoWrite>(input: &mut R, out: &mut W) -> Result<Action> {
+    loop {
+        let Some(answer) = prompt(
+            input,
+            out,
+            "Make a [t]icket, [d]iscard, [s]kip or [q]uit? [t] ",
+        )?
+        else {
+            return Ok(Action::Quit);
+        };
+        match answer.to_lowercase().as_str() {
+            "" | "t" | "ticket" => return Ok(Action::Ticket),
+            "d" | "discard" => return Ok(Action::Discard),
+            "s" | "skip" => return Ok(Action::Skip),
+            "q" | "quit" => return Ok(Action::Quit),
+            _ => writeln!(out, "Answer t, d, s or q").context("Failed to write prompt")?,
+        }
+    }
+}
+
+/// Reads the slug of the ticket to make of `entry`, suggesting one from its
+/// title
+///
+/// Returns `None` at the end of the input.
+fn read_slug<R: BufRead, W: IoWrite>(
+    input: &mut R,
+    out: &mut W,
+    entry: &InboxEntry,
+) -> Result<Option<String>> {
+    let suggested = suggested_slug(entry);
+    let Some(slug) = prompt(input, out, &format!("Slug [{suggested}]: "))? else {
+        return Ok(None);
+    };
+    Ok(Some(if slug.is_empty() { suggested } else { slug }))
+}
+
+/// Writes `question` and reads the trimmed answer, or `None` at the end of
+/// the input
+fn prompt<R: BufRead, W: IoWrite>(
+    input: &mut R,
+    out: &mut W,
+    question: &str,
+) -> Result<Option<String>> {
+    write!(out, "{question}")
+        .and_then(|()| out.flush())
+        .context("Failed to write prompt")?;
+    let mut line = String::new();
+    if input
+        .read_line(&mut line)
+        .context("Failed to read answer")?
+        == 0
+    {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// Slug suggested for a ticket made of `entry`, e.g. `random-idea-about-caching`
+fn suggested_slug(entry: &InboxEntry) -> String {
+    let slug = slugify(entry.title());
+    if slug.is_empty() {
+        return "inbox-entry".to_string();
+    }
+    let mut end = slug.len().min(MAX_SUGGESTED_SLUG_LEN);
+    while !slug.is_char_boundary(end) {
+        end -= 1;
+    }
+    slug[..end].trim_end_matches('-').to_string()
+}
+
+/// Opens the storage of the project
+fn open_storage(project_dir: Option<&str>) -> Result<(std::path::PathBuf, FileStorage)> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    Ok((project_root, storage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_process_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join(".vibe-ticket/tickets")).unwrap();
+        let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+        for text in [
+            "Random idea about caching",
+            "Nah",
+            "Later",
+            "Call Bob\nAbout the demo",
+        ] {
+            storage.capture(InboxEntry::new(text)).unwrap();
+        }
+        let entries = storage.load_inbox().unwrap();
+
+        // Ticket with the suggested slug, discard, skip, ticket with a
+        // rejected slug, then the end of the input
+        let answers = "\n\nd\ns\nt\nBad Slug\n";
+        let mut out = Vec::new();
+        let outcome = process_entries(
+            &entries,
+            project_root,
+            &storage,
+            &mut answers.as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(outcome.created.len(), 1);
+        assert!(outcome.created[0].ends_with("-random-idea-about-caching"));
+        assert_eq!(outcome.discarded, 1);
+        assert_eq!(outcome.remaining, 2);
+        assert!(String::from_utf8(out).unwrap().contains("Error: "));
+
+        let remaining = storage.load_inbox().unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].text, "Later");
+
+        let answers = "s\nt\ncall-bob\n";
+        let outcome = process_entries(
+            &remaining,
+            project_root,
+            &storage,
+            &mut answers.as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(outcome.remaining, 1);
+        let tickets = storage.load_all().unwrap();
+        let ticket = tickets
+            .iter()
+            .find(|t| t.slug.ends_with("-call-bob"))
+            .unwrap();
+        assert_eq!(ticket.title, "Call Bob");
+        assert_eq!(ticket.description, "About the demo");
+    }
+
+    #[test]
+    fn test_suggested_slug() {
+        assert_eq!(
+            suggested_slug(&InboxEntry::new("Random idea: caching?")),
+            "random-idea-caching"
+        );
+        assert_eq!(suggested_slug(&InboxEntry::new("!!!")), "inbox-entry");
+        let long = suggested_slug(&InboxEntry::new(&"word ".repeat(20)));
+        assert!(long.len() <= MAX_SUGGESTED_SLUG_LEN && !long.ends_with('-'));
+    }
+}
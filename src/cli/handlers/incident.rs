@@ -0,0 +1,277 @@
+//! Handler for the `incident` command and its subcommands
+//!
+//! This module records the timeline of incident tickets (see
+//! [`crate::core::incident`]) and writes postmortems pre-filled from the
+//! timeline and the work log.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::Ticket;
+use crate::core::incident::{
+    self, IncidentTimeline, Phase, TimelineEntry, format_duration, is_incident,
+};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+use super::list::parse_date_filter;
+
+/// Handler for the `incident timeline add` subcommand
+///
+/// # Arguments
+///
+/// * `message` - What happened
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `phase` - Optional stage the entry marks (detected, mitigated, resolved)
+/// * `at` - Optional time of the event (defaults to now)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not an incident, or the message, phase
+/// or time is invalid
+pub fn handle_timeline_add(
+    message: &str,
+    ticket_ref: Option<&str>,
+    phase: Option<&str>,
+    at: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    if message.trim().is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Timeline entry cannot be empty".to_string(),
+        ));
+    }
+    let phase = phase.map(str::parse::<Phase>).transpose()?;
+    let at = at.map_or_else(|| Ok(Utc::now()), parse_time)?;
+
+    let (storage, mut ticket) = load_incident(ticket_ref, project_dir)?;
+    let entry = TimelineEntry {
+        at,
+        message: message.trim().to_string(),
+        phase,
+        author: Some(storage.identity().name.clone()),
+    };
+    ticket
+        .incident
+        .get_or_insert_with(IncidentTimeline::default)
+        .add(entry.clone());
+    storage.save(&ticket)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "entry": entry,
+            "timeline": ticket.incident,
+        }))?;
+    } else {
+        let marks = phase.map_or_else(String::new, |phase| format!(" ({phase})"));
+        output.success(&format!(
+            "Added timeline entry{marks} to incident '{}'",
+            ticket.slug
+        ));
+        if phase == Some(Phase::Resolved) && ticket.closed_at.is_none() {
+            output.info(&format!(
+                "Close the ticket with: vibe-ticket close {}",
+                ticket.slug
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Handler for the `incident timeline list` subcommand
+///
+/// # Errors
+///
+/// Returns an error if the ticket cannot be found or is not an incident
+pub fn handle_timeline_list(
+    ticket_ref: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (_, ticket) = load_incident(ticket_ref, project_dir)?;
+    let timeline = ticket.incident.clone().unwrap_or_default();
+    let to_mitigate = incident::time_to_mitigate(&ticket);
+    let to_resolve = incident::time_to_resolve(&ticket);
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "detected_at": incident::detected_at(&ticket),
+            "mitigated_at": timeline.mitigated_at,
+            "resolved_at": incident::resolved_at(&ticket),
+            "time_to_mitigate_minutes": to_mitigate.map(|d| d.num_minutes()),
+            "time_to_resolve_minutes": to_resolve.map(|d| d.num_minutes()),
+            "entries": timeline.entries,
+        }));
+    }
+
+    output.info(&format!("Timeline of incident '{}':", ticket.slug));
+    if timeline.entries.is_empty() {
+        output.info("  No entries yet");
+    }
+    for entry in &timeline.entries {
+        let phase = entry
+            .phase
+            .map_or_else(String::new, |phase| format!("[{phase}] "));
+        output.info(&format!(
+            "  {} {phase}{}",
+            output.dates().format(entry.at),
+            entry.message
+        ));
+    }
+    output.info("");
+    output.info(&format!(
+        "Time to mitigate: {}",
+        to_mitigate.map_or_else(|| "-".to_string(), format_duration)
+    ));
+    output.info(&format!(
+        "Time to resolve: {}",
+        to_resolve.map_or_else(|| "-".to_string(), format_duration)
+    ));
+    Ok(())
+}
+
+/// Handler for the `incident postmortem` subcommand
+///
+/// Prints the postmortem, or writes it to `output_path`.
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not an incident or the file cannot be
+/// written
+pub fn handle_postmortem(
+    ticket_ref: Option<&str>,
+    output_path: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (_, ticket) = load_incident(ticket_ref, project_dir)?;
+    let markdown = incident::postmortem(&ticket);
+
+    match output_path {
+        Some(path) => {
+            std::fs::write(path, &markdown)
+                .with_context(|| format!("Failed to write postmortem to {path}"))?;
+            if output.is_json() {
+                output.print_json(&serde_json::json!({
+                    "status": "success",
+                    "ticket_slug": ticket.slug,
+                    "path": path,
+                }))?;
+            } else {
+                output.success(&format!("Wrote postmortem of '{}' to {path}", ticket.slug));
+            }
+        },
+        None if output.is_json() => output.print_json(&serde_json::json!({
+            "ticket_slug": ticket.slug,
+            "markdown": markdown,
+        }))?,
+        None => print!("{markdown}"),
+    }
+    Ok(())
+}
+
+/// Loads the incident ticket `ticket_ref`, or the active ticket
+fn load_incident(
+    ticket_ref: Option<&str>,
+    project_dir: Option<&str>,
+) -> Result<(FileStorage, Ticket)> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let ticket_id = match ticket_ref {
+        Some(ticket_ref) => resolve_ticket_ref(&storage, ticket_ref)?,
+        None => storage
+            .get_active()?
+            .ok_or(VibeTicketError::NoActiveTicket)?,
+    };
+    let ticket = storage.load(&ticket_id)?;
+    if !is_incident(&ticket) {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Ticket '{}' is not an incident; create incidents with --template incident, \
+             or tag the ticket '{}'",
+            ticket.slug,
+            incident::INCIDENT_TAG
+        )));
+    }
+    Ok((storage, ticket))
+}
+
+/// Parses the time of a timeline entry
+///
+/// Accepts `now`, `HH:MM` (today), `YYYY-MM-DD HH:MM` in local time, RFC 3339
+/// timestamps, `N minutes ago` and the dates accepted by
+/// [`parse_date_filter`], such as `2 hours ago`.
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    let local = |naive: NaiveDateTime| {
+        naive
+            .and_local_timezone(Local)
+            .earliest()
+            .map(|at| at.with_timezone(&Utc))
+    };
+
+    if value.eq_ignore_ascii_case("now") {
+        return Ok(Utc::now());
+    }
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Some(at) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M")
+        .ok()
+        .and_then(local)
+    {
+        return Ok(at);
+    }
+    if let Some(at) = NaiveTime::parse_from_str(value, "%H:%M")
+        .ok()
+        .and_then(|time| local(Local::now().date_naive().and_time(time)))
+    {
+        return Ok(at);
+    }
+    if let Some(minutes) = value
+        .strip_suffix(" ago")
+        .and_then(|rest| {
+            rest.strip_suffix(" minutes")
+                .or_else(|| rest.strip_suffix(" minute"))
+        })
+        .and_then(|minutes| minutes.trim().parse::<i64>().ok())
+    {
+        return Ok(Utc::now() - chrono::Duration::minutes(minutes));
+    }
+    if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok() || value.ends_with(" ago") {
+        return parse_date_filter(value);
+    }
+    Err(VibeTicketError::InvalidInput(format!(
+        "Invalid time: '{value}'. Use formats like 'now', '14:05', '2025-07-20 14:05', \
+         '30 minutes ago' or '2 hours ago'"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Timelike};
+
+    #[test]
+    fn test_parse_time() {
+        let at = parse_time("2025-07-20T14:05:00Z").unwrap();
+        assert_eq!(at.to_rfc3339(), "2025-07-20T14:05:00+00:00");
+
+        let at = parse_time("09:30").unwrap().with_timezone(&Local);
+        assert_eq!((at.hour(), at.minute()), (9, 30));
+        assert_eq!(at.date_naive(), Local::now().date_naive());
+
+        let ago = Utc::now() - parse_time("30 minutes ago").unwrap();
+        assert!((ago - Duration::minutes(30)).num_seconds().abs() < 5);
+        assert!(parse_time("2 hours ago").is_ok());
+        assert!(parse_time("2025-07-20 14:05").is_ok());
+        assert!(parse_time("teatime").is_err());
+    }
+}
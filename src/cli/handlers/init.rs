@@ -230,13 +230,20 @@ Closes #{{ ticket_id }} - {{ ticket_title }}
 /// Create or update .gitignore file
 ///
 /// Adds vibe-ticket specific entries to .gitignore
-fn create_gitignore(project_dir: &Path) -> Result<()> {
+pub(super) fn create_gitignore(project_dir: &Path) -> Result<()> {
     let gitignore_path = project_dir.join(".gitignore");
     let vibe_entries = [
         "# vibe-ticket",
         ".vibe-ticket/backups/",
         ".vibe-ticket/tmp/",
         ".vibe-ticket/*.log",
+        ".vibe-ticket/logs/",
+        ".vibe-ticket/index.json",
+        ".vibe-ticket/transactions/",
+        ".vibe-ticket/presence/",
+        ".vibe-ticket/journal/",
+        ".vibe-ticket/run/",
+        ".vibe-ticket/outbox/",
         "",
         "# vibe-ticket worktree directories",
         "*-vibeticket-*/",
@@ -273,8 +280,9 @@ fn generate_claude_md_for_init(
     project_name: &str,
     description: Option<&str>,
 ) -> Result<()> {
+    let examples = crate::cli::examples::render_markdown();
     let claude_content = format!(
-        r#"# vibe-ticket Project: {}
+        r"# vibe-ticket Project: {}
 
 {}
 
@@ -284,65 +292,7 @@ This project uses vibe-ticket for ticket management. This document provides guid
 
 ## Common vibe-ticket Commands
 
-### Getting Started
-```bash
-# Create your first ticket
-vibe-ticket new fix-bug --title "Fix login issue" --priority high
-
-# List all tickets
-vibe-ticket list
-
-# Start working on a ticket
-vibe-ticket start fix-bug
-
-# Show current status
-vibe-ticket check
-```
-
-### Working with Tickets
-```bash
-# Show ticket details
-vibe-ticket show <ticket>
-
-# Update ticket
-vibe-ticket edit <ticket> --status review
-
-# Add tasks to ticket
-vibe-ticket task add "Write unit tests"
-vibe-ticket task add "Update documentation"
-
-# Complete tasks
-vibe-ticket task complete 1
-
-# Close ticket
-vibe-ticket close <ticket> --message "Fixed the login issue"
-```
-
-### Search and Filter
-```bash
-# Search tickets
-vibe-ticket search "login"
-
-# Filter by status
-vibe-ticket list --status doing
-
-# Filter by priority
-vibe-ticket list --priority high
-```
-
-### Configuration
-```bash
-# View configuration
-vibe-ticket config show
-
-# Set configuration values
-vibe-ticket config set project.default_priority medium
-vibe-ticket config set git.auto_branch true
-
-# Generate this file
-vibe-ticket config claude
-```
-
+{examples}
 ## Project Configuration
 
 The project has been initialized with default settings. You can customize them using the config commands above.
@@ -374,7 +324,7 @@ When helping with this project:
 
 ---
 Generated on: {}
-"#,
+",
         project_name,
         description.unwrap_or("A vibe-ticket managed project"),
         chrono::Local::now().format("%Y-%m-%d")
@@ -453,6 +403,10 @@ mod tests {
         let content = fs::read_to_string(&gitignore_path).unwrap();
         assert!(content.contains("# vibe-ticket"));
         assert!(content.contains(".vibe-ticket/backups/"));
+        assert!(content.contains(".vibe-ticket/presence/"));
+        assert!(content.contains(".vibe-ticket/journal/"));
+        assert!(content.contains(".vibe-ticket/run/"));
+        assert!(content.contains(".vibe-ticket/outbox/"));
     }
 
     #[test]
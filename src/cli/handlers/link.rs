@@ -0,0 +1,247 @@
+//! Handlers for the `link`, `unlink`, `relate` and `unrelate` commands
+//!
+//! This module records dependencies (see [`crate::core::dependencies`]) and
+//! relations (see [`crate::core::relations`]) between tickets. `unlink` and
+//! `unrelate` remove them again.
+
+use std::collections::BTreeSet;
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::relations::{self, RelationKind};
+use crate::core::{Ticket, TicketId, dependencies};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Tickets to link a ticket to
+#[derive(Debug, Clone, Default)]
+pub struct LinkTargets {
+    /// Tickets the ticket depends on
+    pub depends_on: Vec<String>,
+    /// Tickets that depend on the ticket
+    pub blocks: Vec<String>,
+    /// Tickets the ticket has a relation to, with the kind of relation
+    pub relations: Vec<(RelationKind, String)>,
+}
+
+impl LinkTargets {
+    /// Adds a relation of `kind` to each of `references`
+    #[must_use]
+    pub fn with_relations(mut self, kind: RelationKind, references: Vec<String>) -> Self {
+        self.relations
+            .extend(references.into_iter().map(|reference| (kind, reference)));
+        self
+    }
+}
+
+/// A link between two tickets, by position in the loaded tickets
+enum Link {
+    /// `ticket` depends on `other`
+    Dependency { ticket: usize, other: usize },
+    /// `ticket` has a relation of `kind` to `other`
+    Relation {
+        ticket: usize,
+        other: usize,
+        kind: RelationKind,
+    },
+}
+
+/// Handler for the `link` and `unlink` commands
+///
+/// Makes the ticket depend on every ticket in `depends_on`, every ticket in
+/// `blocks` depend on the ticket, and records the `relations`, or removes
+/// those links if `remove` is set. Both sides of each link are updated.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `targets` - Tickets to link to, by kind of link
+/// * `remove` - Whether to remove the links instead of adding them
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, a ticket is not
+/// found, or a link would relate a ticket to itself, or create a cycle of
+/// dependencies or of hierarchical relations.
+pub fn handle_link_command(
+    ticket_ref: &str,
+    targets: &LinkTargets,
+    remove: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+    let mut tickets = storage.load_all()?;
+    let ticket = position(&tickets, &ticket_id)?;
+    let links = resolve_links(&storage, &tickets, ticket, targets)?;
+
+    let mut modified = BTreeSet::new();
+    let mut results = Vec::new();
+
+    for link in links {
+        let (ticket, other) = match link {
+            Link::Dependency { ticket, other } | Link::Relation { ticket, other, .. } => {
+                (ticket, other)
+            },
+        };
+        let mut a = tickets[ticket].clone();
+        let mut b = tickets[other].clone();
+        let (changed, phrase, missing) = match link {
+            Link::Dependency { .. } => {
+                let changed = if remove {
+                    dependencies::remove_dependency(&mut a, &mut b)
+                } else {
+                    dependencies::add_dependency(&mut a, &mut b, &tickets)?
+                };
+                (changed, "depends on", "did not depend on".to_string())
+            },
+            Link::Relation { kind, .. } => {
+                let changed = if remove {
+                    relations::remove_relation(&mut a, &mut b, kind)
+                } else {
+                    relations::add_relation(&mut a, &mut b, kind, &tickets)?
+                };
+                (changed, kind.phrase(), format!("had no {kind} relation to"))
+            },
+        };
+
+        let change = match (changed, remove) {
+            (false, false) => format!("'{}' already {phrase} '{}'", a.slug, b.slug),
+            (false, true) => format!("'{}' {missing} '{}'", a.slug, b.slug),
+            (true, false) => format!("'{}' now {phrase} '{}'", a.slug, b.slug),
+            (true, true) => format!("'{}' no longer {phrase} '{}'", a.slug, b.slug),
+        };
+        if changed {
+            modified.extend([ticket, other]);
+        }
+        results.push((changed, change));
+
+        tickets[ticket] = a;
+        tickets[other] = b;
+    }
+
+    // Both ends of a link are written together
+    let mut transaction = storage.transaction();
+    for &index in &modified {
+        transaction.save(tickets[index].clone());
+    }
+    transaction.commit()?;
+
+    // Output results
+    let ticket = &tickets[ticket];
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "depends_on": slugs(&ticket.depends_on, &tickets),
+            "blocks": slugs(&ticket.blocks, &tickets),
+            "relations": ticket
+                .relations
+                .iter()
+                .map(|relation| serde_json::json!({
+                    "kind": relation.kind,
+                    "ticket": slug(&relation.ticket, &tickets),
+                }))
+                .collect::<Vec<_>>(),
+            "changes": results
+                .iter()
+                .filter(|(changed, _)| *changed)
+                .map(|(_, change)| change)
+                .collect::<Vec<_>>(),
+        }))?;
+        return Ok(());
+    }
+
+    for (changed, change) in &results {
+        if *changed {
+            output.success(change);
+        } else {
+            output.info(change);
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `relate` and `unrelate` commands
+///
+/// Records a relation of `kind` from the ticket to each of `others`, or
+/// removes it if `remove` is set. See [`handle_link_command`].
+///
+/// # Errors
+///
+/// Returns an error if `kind` is not a relation kind, or for the reasons
+/// [`handle_link_command`] fails.
+pub fn handle_relate_command(
+    ticket_ref: &str,
+    kind: &str,
+    others: &[String],
+    remove: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let kind = RelationKind::try_from(kind).map_err(VibeTicketError::InvalidInput)?;
+    let targets = LinkTargets::default().with_relations(kind, others.to_vec());
+    handle_link_command(ticket_ref, &targets, remove, project_dir, output)
+}
+
+/// Resolves the target references into links from the ticket at `ticket`
+fn resolve_links(
+    storage: &FileStorage,
+    tickets: &[Ticket],
+    ticket: usize,
+    targets: &LinkTargets,
+) -> Result<Vec<Link>> {
+    let resolve = |reference: &String| -> Result<usize> {
+        position(tickets, &resolve_ticket_ref(storage, reference)?)
+    };
+
+    let mut links = Vec::new();
+    for reference in &targets.depends_on {
+        links.push(Link::Dependency {
+            ticket,
+            other: resolve(reference)?,
+        });
+    }
+    for reference in &targets.blocks {
+        links.push(Link::Dependency {
+            ticket: resolve(reference)?,
+            other: ticket,
+        });
+    }
+    for (kind, reference) in &targets.relations {
+        links.push(Link::Relation {
+            ticket,
+            other: resolve(reference)?,
+            kind: *kind,
+        });
+    }
+    Ok(links)
+}
+
+/// Index of the ticket with `id` in `tickets`
+fn position(tickets: &[Ticket], id: &TicketId) -> Result<usize> {
+    tickets
+        .iter()
+        .position(|t| &t.id == id)
+        .ok_or_else(|| VibeTicketError::TicketNotFound { id: id.to_string() })
+}
+
+/// Slugs of the tickets with the given IDs
+fn slugs(ids: &[TicketId], tickets: &[Ticket]) -> Vec<String> {
+    ids.iter().map(|id| slug(id, tickets)).collect()
+}
+
+/// Slug of the ticket with the given ID, falling back to the short ID
+fn slug(id: &TicketId, tickets: &[Ticket]) -> String {
+    tickets
+        .iter()
+        .find(|t| &t.id == id)
+        .map_or_else(|| id.short(), |t| t.slug.clone())
+}
@@ -0,0 +1,211 @@
+//! Count conditions for `list --fail-if`
+//!
+//! A condition compares the number of tickets matching a filter with a
+//! threshold, for example `count(open,critical) > 0`. The filter is a
+//! comma-separated list of terms that must all match:
+//!
+//! - `open`: any status but done
+//! - a status, priority or severity (`blocked`, `critical`, `s1`, ...)
+//! - `status=`, `priority=`, `severity=` or `assignee=` followed by a value
+//!
+//! `count()` counts every ticket. Supported comparisons are `>`, `>=`, `<`,
+//! `<=`, `==` (or `=`) and `!=`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::{Priority, Severity, Status};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::TicketSummary;
+
+/// Condition on the number of tickets matching a filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountCondition {
+    terms: Vec<Term>,
+    comparison: Comparison,
+    threshold: usize,
+    source: String,
+}
+
+/// Term of a count filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Open,
+    Status(Status),
+    Priority(Priority),
+    Severity(Severity),
+    Assignee(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl CountCondition {
+    /// Number of tickets matching the filter
+    pub fn count(&self, tickets: &[TicketSummary]) -> usize {
+        tickets
+            .iter()
+            .filter(|ticket| self.terms.iter().all(|term| term.matches(ticket)))
+            .count()
+    }
+
+    /// Returns true if `count` satisfies the comparison
+    pub const fn holds(&self, count: usize) -> bool {
+        match self.comparison {
+            Comparison::Greater => count > self.threshold,
+            Comparison::GreaterOrEqual => count >= self.threshold,
+            Comparison::Less => count < self.threshold,
+            Comparison::LessOrEqual => count <= self.threshold,
+            Comparison::Equal => count == self.threshold,
+            Comparison::NotEqual => count != self.threshold,
+        }
+    }
+}
+
+impl fmt::Display for CountCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for CountCondition {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let source = s.trim();
+        let invalid = |reason: &str| {
+            VibeTicketError::InvalidInput(format!(
+                "Invalid condition '{source}': {reason}. Expected e.g. \"count(open,critical) > 0\""
+            ))
+        };
+
+        let rest = source
+            .strip_prefix("count(")
+            .ok_or_else(|| invalid("must start with count("))?;
+        let (filter, rest) = rest
+            .split_once(')')
+            .ok_or_else(|| invalid("missing closing parenthesis"))?;
+
+        let terms = filter
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(|term| Term::parse(term).ok_or_else(|| invalid(&format!("unknown term '{term}'"))))
+            .collect::<Result<Vec<_>>>()?;
+
+        let rest = rest.trim();
+        let (comparison, threshold) = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+            ("=", Comparison::Equal),
+        ]
+        .into_iter()
+        .find_map(|(op, comparison)| rest.strip_prefix(op).map(|value| (comparison, value)))
+        .ok_or_else(|| invalid("missing comparison"))?;
+        let threshold = threshold
+            .trim()
+            .parse()
+            .map_err(|_| invalid("threshold must be a non-negative number"))?;
+
+        Ok(Self {
+            terms,
+            comparison,
+            threshold,
+            source: source.to_string(),
+        })
+    }
+}
+
+impl Term {
+    fn parse(term: &str) -> Option<Self> {
+        if let Some((key, value)) = term.split_once('=') {
+            let value = value.trim();
+            return match key.trim() {
+                "status" => Status::try_from(value).ok().map(Self::Status),
+                "priority" => Priority::try_from(value).ok().map(Self::Priority),
+                "severity" => Severity::try_from(value).ok().map(Self::Severity),
+                "assignee" if !value.is_empty() => Some(Self::Assignee(value.to_string())),
+                _ => None,
+            };
+        }
+
+        if term.eq_ignore_ascii_case("open") {
+            return Some(Self::Open);
+        }
+        Status::try_from(term)
+            .map(Self::Status)
+            .or_else(|_| Priority::try_from(term).map(Self::Priority))
+            .or_else(|_| Severity::try_from(term).map(Self::Severity))
+            .ok()
+    }
+
+    fn matches(&self, ticket: &TicketSummary) -> bool {
+        match self {
+            Self::Open => ticket.status != Status::Done,
+            Self::Status(status) => ticket.status == *status,
+            Self::Priority(priority) => ticket.priority == *priority,
+            Self::Severity(severity) => ticket.severity == Some(*severity),
+            Self::Assignee(assignee) => ticket.assignee.as_deref() == Some(assignee.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+
+    fn summary(status: Status, priority: Priority) -> TicketSummary {
+        let mut ticket = Ticket::new("t", "T");
+        ticket.status = status;
+        ticket.priority = priority;
+        serde_yaml::from_str(&serde_yaml::to_string(&ticket).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_count_condition() {
+        let tickets = vec![
+            summary(Status::Todo, Priority::Critical),
+            summary(Status::Blocked, Priority::Critical),
+            summary(Status::Done, Priority::Critical),
+            summary(Status::Doing, Priority::Low),
+        ];
+
+        let condition: CountCondition = "count(open, critical) > 0".parse().unwrap();
+        assert_eq!(condition.count(&tickets), 2);
+        assert!(condition.holds(2));
+        assert!(!condition.holds(0));
+
+        let condition: CountCondition = "count(priority=critical)>=3".parse().unwrap();
+        assert!(condition.holds(condition.count(&tickets)));
+
+        let condition: CountCondition = "count() != 4".parse().unwrap();
+        assert!(!condition.holds(condition.count(&tickets)));
+        assert_eq!(condition.to_string(), "count() != 4");
+    }
+
+    #[test]
+    fn test_invalid_conditions() {
+        for source in [
+            "open > 0",
+            "count(open > 0",
+            "count(open)",
+            "count(bogus) > 0",
+            "count(open) > -1",
+            "count(assignee=) > 0",
+        ] {
+            assert!(source.parse::<CountCondition>().is_err(), "{source}");
+        }
+    }
+}
@@ -0,0 +1,644 @@
+use crate::cli::handlers::{load_named_filter, parse_query};
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::core::audit::{self, TransitionKind};
+use crate::core::hierarchy::{self, Rollup};
+use crate::core::{Priority, Severity, Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository, TicketSummary};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, Utc, Weekday};
+use std::path::Path;
+
+mod gate;
+mod projects;
+
+pub use gate::CountCondition;
+
+/// Handler for the `list` command
+///
+/// Prints the matching tickets followed by their counts per status. Every
+/// `fail_if` condition is evaluated against all non-archived tickets,
+/// regardless of the listing filters; the command fails if any holds.
+///
+/// `filter` names a saved query and `where_clause` is a query expression
+/// (see [`crate::core::query`]); both apply on top of the other filters, and
+/// done tickets are then only hidden if a query says so.
+///
+/// With `entered_status` or `left_status`, tickets are selected by when
+/// their audit log records them entering or leaving that status: `since`
+/// and `until` bound the time of the transition instead of the creation,
+/// and done tickets are included unless `status` says otherwise.
+///
+/// With `tree`, children follow their parent (epic), indented, and epics
+/// show the roll-up progress of all their descendants. With `all_projects`,
+/// the tickets of all registered projects are listed together.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_list_command(
+    status: Option<String>,
+    priority: Option<String>,
+    severity: Option<String>,
+    assignee: Option<String>,
+    milestone: Option<String>,
+    sort: &str,
+    reverse: bool,
+    limit: Option<usize>,
+    archived: bool,
+    open: bool,
+    since: Option<String>,
+    until: Option<String>,
+    entered_status: Option<&str>,
+    left_status: Option<&str>,
+    include_done: bool,
+    filter: Option<&str>,
+    where_clause: Option<&str>,
+    fail_if: &[String],
+    tree: bool,
+    all_projects: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let conditions = fail_if
+        .iter()
+        .map(|condition| condition.parse::<CountCondition>())
+        .collect::<Result<Vec<_>>>()?;
+
+    // Parse date filters
+    let since_date = since.map(|s| output.dates().parse(&s)).transpose()?;
+    let until_date = until.map(|s| output.dates().parse(&s)).transpose()?;
+    let transition = transition_kind(entered_status, left_status)?;
+
+    // A milestone filter must name an existing milestone of the listed project
+    let required_milestone = milestone.clone().filter(|name| name != "none");
+    let select = selector(
+        status,
+        priority,
+        severity,
+        assignee,
+        milestone,
+        archived,
+        open,
+        since_date,
+        until_date,
+        transition,
+        include_done,
+        filter,
+        where_clause,
+    );
+
+    if all_projects {
+        return projects::list_all_projects(select, sort, reverse, limit, output);
+    }
+
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+
+    // Initialize storage
+    let storage = FileStorage::new(&vibe_ticket_dir);
+
+    if let Some(name) = &required_milestone {
+        storage.load_milestone(name)?;
+    }
+
+    // Load ticket summaries; full tickets are only read for the final results
+    let summaries = storage.summaries()?;
+    let checks = check_conditions(&conditions, &summaries);
+    let mut summaries = select(&project_root, &storage, summaries)?;
+
+    // Sort tickets
+    sort_tickets(&mut summaries, sort, reverse);
+
+    // Apply limit
+    if let Some(limit) = limit {
+        summaries.truncate(limit);
+    }
+
+    let tickets = summaries
+        .iter()
+        .map(|summary| storage.load(&summary.id))
+        .collect::<Result<Vec<Ticket>>>()?;
+
+    // Output results
+    if output.is_json() {
+        let mut json = serde_json::json!({
+            "tickets": tickets,
+            "count": tickets.len(),
+            "counts": status_counts(&tickets)
+                .into_iter()
+                .map(|(status, count)| (status.to_string().to_lowercase(), count.into()))
+                .collect::<serde_json::Map<_, _>>(),
+        });
+        if !checks.is_empty() {
+            json["fail_if"] = checks
+                .iter()
+                .map(|(condition, count, failed)| {
+                    serde_json::json!({
+                        "condition": condition.to_string(),
+                        "count": count,
+                        "failed": failed,
+                    })
+                })
+                .collect();
+        }
+        if tree {
+            json["tree"] = tree_json(&tickets);
+        }
+        output.print_json(&json)?;
+    } else if tickets.is_empty() {
+        output.info("No tickets found matching the criteria.");
+    } else {
+        if tree {
+            print_tree(&tickets, &storage.load_all()?, output);
+        } else {
+            let fields = Config::load_for_project(&project_root)?.fields;
+            output.print_tickets_with_fields(&tickets, &fields.into_keys().collect::<Vec<_>>())?;
+        }
+        output.info(&format!("By status: {}", status_summary(&tickets)));
+    }
+
+    let failed: Vec<String> = checks
+        .iter()
+        .filter(|(_, _, failed)| *failed)
+        .map(|(condition, count, _)| format!("{condition} (count is {count})"))
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::custom(format!(
+            "Failed condition: {}",
+            failed.join("; ")
+        )))
+    }
+}
+
+/// Builds the filter that narrows down the ticket summaries of one project
+///
+/// The filter loads the named filter and parses the query against the
+/// project it is applied to, so it can be reused for several projects.
+/// With a `transition`, the dates bound its time rather than the creation.
+#[allow(clippy::too_many_arguments)]
+fn selector<'a>(
+    status: Option<String>,
+    priority: Option<String>,
+    severity: Option<String>,
+    assignee: Option<String>,
+    milestone: Option<String>,
+    archived: bool,
+    open: bool,
+    since_date: Option<DateTime<Utc>>,
+    until_date: Option<DateTime<Utc>>,
+    transition: Option<TransitionKind>,
+    include_done: bool,
+    filter: Option<&'a str>,
+    where_clause: Option<&'a str>,
+) -> impl Fn(&Path, &FileStorage, Vec<TicketSummary>) -> Result<Vec<TicketSummary>> + 'a {
+    move |project_root: &Path, storage: &FileStorage, summaries: Vec<TicketSummary>| {
+        let mut queries = Vec::new();
+        if let Some(name) = filter {
+            queries.push(load_named_filter(project_root, name)?);
+        }
+        if let Some(source) = where_clause {
+            queries.push(parse_query(project_root, source)?);
+        }
+
+        let mut summaries = filter_tickets(
+            summaries,
+            status.clone(),
+            priority.clone(),
+            severity.clone(),
+            assignee
+                .as_deref()
+                .map(|name| storage.identity().expand(name)),
+            milestone.clone(),
+            archived,
+            open,
+            since_date.filter(|_| transition.is_none()),
+            until_date.filter(|_| transition.is_none()),
+            include_done || transition.is_some() || !queries.is_empty(),
+        )?;
+
+        // Select by the status transitions in the audit logs
+        if let Some(kind) = transition {
+            summaries.retain(|summary| {
+                storage.load_history(&summary.id).is_ok_and(|log| {
+                    audit::transitioned_between(&log, summary.status, kind, since_date, until_date)
+                })
+            });
+        }
+
+        // Apply the queries to the full tickets
+        if !queries.is_empty() {
+            summaries.retain(|summary| {
+                storage
+                    .load(&summary.id)
+                    .is_ok_and(|ticket| queries.iter().all(|query| query.matches(&ticket)))
+            });
+        }
+        Ok(summaries)
+    }
+}
+
+/// Builds the transition filter of `--entered-status` or `--left-status`
+fn transition_kind(
+    entered_status: Option<&str>,
+    left_status: Option<&str>,
+) -> Result<Option<TransitionKind>> {
+    let status = |name: &str| {
+        Status::try_from(name).map_err(|_| VibeTicketError::InvalidStatus {
+            status: name.to_string(),
+        })
+    };
+    Ok(match (entered_status, left_status) {
+        (Some(name), _) => Some(TransitionKind::Entered(status(name)?)),
+        (None, Some(name)) => Some(TransitionKind::Left(status(name)?)),
+        (None, None) => None,
+    })
+}
+
+/// Evaluates the conditions against the non-archived tickets
+///
+/// Returns each condition with its count and whether it holds.
+fn check_conditions<'a>(
+    conditions: &'a [CountCondition],
+    tickets: &[TicketSummary],
+) -> Vec<(&'a CountCondition, usize, bool)> {
+    let active: Vec<TicketSummary> = tickets
+        .iter()
+        .filter(|t| !t.is_archived())
+        .cloned()
+        .collect();
+    conditions
+        .iter()
+        .map(|condition| {
+            let count = condition.count(&active);
+            (condition, count, condition.holds(count))
+        })
+        .collect()
+}
+
+/// Lists the slugs of `tickets` in tree order with their depth
+fn tree_json(tickets: &[Ticket]) -> serde_json::Value {
+    hierarchy::forest(tickets)
+        .into_iter()
+        .map(|(depth, ticket)| serde_json::json!({ "slug": ticket.slug, "depth": depth }))
+        .collect()
+}
+
+/// Prints `tickets` as a tree, with the roll-up progress of the epics
+/// computed over `all` tickets
+fn print_tree(tickets: &[Ticket], all: &[Ticket], output: &OutputFormatter) {
+    for (depth, ticket) in hierarchy::forest(tickets) {
+        let rollup = Rollup::of(ticket, all);
+        let progress = if rollup.total > 0 {
+            format!(" — {rollup}")
+        } else {
+            String::new()
+        };
+        output.info(&format!(
+            "{}{} {} [{}] {}{progress}",
+            "  ".repeat(depth),
+            ticket.display_id(),
+            ticket.slug,
+            output.visuals().status_text(ticket.status),
+            ticket.title,
+        ));
+    }
+}
+
+/// Number of tickets per status, omitting statuses without tickets
+fn status_counts(tickets: &[Ticket]) -> Vec<(Status, usize)> {
+    Status::all()
+        .into_iter()
+        .map(|status| {
+            (
+                status,
+                tickets.iter().filter(|t| t.status == status).count(),
+            )
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+/// Number of tickets per status as text, e.g. `todo: 2, done: 1`
+fn status_summary(tickets: &[Ticket]) -> String {
+    status_counts(tickets)
+        .into_iter()
+        .map(|(status, count)| format!("{}: {count}", status.to_string().to_lowercase()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse date filter strings
+///
+/// Accepts `today`, `yesterday`, `tomorrow`, weekday names such as `monday`
+/// (the latest such day, today included), `N hours ago`, `N days ago`,
+/// `N weeks ago`, `N months ago` and `YYYY-MM-DD`.
+///
+/// # Errors
+///
+/// Returns an error if the string is not in one of these formats
+///
+/// # Panics
+///
+/// Panics if local midnight does not exist on the resulting day
+pub fn parse_date_filter(date_str: &str) -> Result<DateTime<Utc>> {
+    let date_str = date_str.trim().to_lowercase();
+
+    // Handle relative dates
+    if date_str == "today" {
+        return Ok(Local::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .with_timezone(&Utc));
+    } else if date_str == "yesterday" {
+        return Ok((Local::now() - Duration::days(1))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .with_timezone(&Utc));
+    } else if date_str == "tomorrow" {
+        return Ok((Local::now() + Duration::days(1))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .with_timezone(&Utc));
+    }
+
+    // Handle weekday names
+    if let Ok(weekday) = date_str.parse::<Weekday>() {
+        return Ok(latest_weekday(weekday));
+    }
+
+    // Handle "X hours ago" format
+    if let Some(captures) = regex::Regex::new(r"^(\d+)\s+hours?\s+ago$")
+        .unwrap()
+        .captures(&date_str)
+    {
+        if let Some(hours_str) = captures.get(1) {
+            if let Ok(hours) = hours_str.as_str().parse::<i64>() {
+                return Ok((Local::now() - Duration::hours(hours)).with_timezone(&Utc));
+            }
+        }
+    }
+
+    // Handle "X days ago" format
+    if let Some(captures) = regex::Regex::new(r"^(\d+)\s+days?\s+ago$")
+        .unwrap()
+        .captures(&date_str)
+    {
+        if let Some(days_str) = captures.get(1) {
+            if let Ok(days) = days_str.as_str().parse::<i64>() {
+                return Ok((Local::now() - Duration::days(days))
+                    .date_naive()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_local_timezone(Local)
+                    .unwrap()
+                    .with_timezone(&Utc));
+            }
+        }
+    }
+
+    // Handle "X weeks ago" format
+    if let Some(captures) = regex::Regex::new(r"^(\d+)\s+weeks?\s+ago$")
+        .unwrap()
+        .captures(&date_str)
+    {
+        if let Some(weeks_str) = captures.get(1) {
+            if let Ok(weeks) = weeks_str.as_str().parse::<i64>() {
+                return Ok((Local::now() - Duration::weeks(weeks))
+                    .date_naive()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_local_timezone(Local)
+                    .unwrap()
+                    .with_timezone(&Utc));
+            }
+        }
+    }
+
+    // Handle "X months ago" format
+    if let Some(captures) = regex::Regex::new(r"^(\d+)\s+months?\s+ago$")
+        .unwrap()
+        .captures(&date_str)
+    {
+        if let Some(months) = captures
+            .get(1)
+            .and_then(|months| months.as_str().parse::<u32>().ok())
+            .and_then(|months| Local::now().checked_sub_months(Months::new(months)))
+        {
+            return Ok(months
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_local_timezone(Local)
+                .unwrap()
+                .with_timezone(&Utc));
+        }
+    }
+
+    // Try parsing as ISO date (YYYY-MM-DD)
+    if let Ok(date) = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+
+    Err(VibeTicketError::custom(format!(
+        "Invalid date format: '{date_str}'. Use formats like 'yesterday', 'monday', '2 hours ago', '3 days ago', '3 months ago', or 'YYYY-MM-DD'"
+    )))
+}
+
+/// Local midnight of the latest `weekday`, today included
+///
+/// # Panics
+///
+/// Panics if local midnight does not exist on that day
+fn latest_weekday(weekday: Weekday) -> DateTime<Utc> {
+    let today = Local::now().date_naive();
+    let days_back =
+        (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    (today - Duration::days(i64::from(days_back)))
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_local_timezone(Local)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// Filter tickets based on criteria
+#[allow(clippy::too_many_arguments)]
+fn filter_tickets(
+    tickets: Vec<TicketSummary>,
+    status: Option<String>,
+    priority: Option<String>,
+    severity: Option<String>,
+    assignee: Option<String>,
+    milestone: Option<String>,
+    archived: bool,
+    open: bool,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    include_done: bool,
+) -> Result<Vec<TicketSummary>> {
+    let mut filtered = tickets;
+
+    // Filter by status
+    if let Some(status_str) = status {
+        let status = Status::try_from(status_str.as_str())
+            .map_err(|_| VibeTicketError::InvalidStatus { status: status_str })?;
+        filtered.retain(|t| t.status == status);
+    } else if !include_done {
+        // If no status filter is provided and include_done is false, exclude DONE tickets
+        filtered.retain(|t| t.status != Status::Done);
+    }
+
+    // Filter by priority
+    if let Some(priority_str) = priority {
+        let priority = Priority::try_from(priority_str.as_str()).map_err(|_| {
+            VibeTicketError::InvalidPriority {
+                priority: priority_str,
+            }
+        })?;
+        filtered.retain(|t| t.priority == priority);
+    }
+
+    // Filter by severity
+    if let Some(severity_str) = severity {
+        let severity =
+            Severity::try_from(severity_str.as_str()).map_err(VibeTicketError::InvalidInput)?;
+        filtered.retain(|t| t.severity == Some(severity));
+    }
+
+    // Filter by assignee
+    if let Some(assignee) = assignee {
+        filtered.retain(|t| t.assignee.as_ref() == Some(&assignee));
+    }
+
+    // Filter by milestone (`none` selects tickets without milestone)
+    if let Some(milestone) = milestone {
+        let milestone = Some(milestone).filter(|name| name != "none");
+        filtered.retain(|t| t.milestone == milestone);
+    }
+
+    // Filter by archived status
+    if !archived {
+        // Filter out archived tickets
+        filtered.retain(|t| !t.is_archived());
+    }
+
+    // Filter by open status (todo, doing)
+    if open {
+        filtered.retain(|t| matches!(t.status, Status::Todo | Status::Doing));
+    }
+
+    // Filter by date range
+    if let Some(since) = since {
+        filtered.retain(|t| t.created_at >= since);
+    }
+
+    if let Some(until) = until {
+        filtered.retain(|t| t.created_at <= until);
+    }
+
+    Ok(filtered)
+}
+
+/// Sort tickets based on the specified field
+fn sort_tickets(tickets: &mut [TicketSummary], sort_by: &str, reverse: bool) {
+    match sort_by {
+        "created" => {
+            tickets.sort_by_key(|t| t.created_at);
+        },
+        "updated" => {
+            // For now, sort by created_at as we don't have updated_at
+            tickets.sort_by_key(|t| t.created_at);
+        },
+        "priority" => {
+            tickets.sort_by_key(|t| t.priority);
+        },
+        "severity" => {
+            // Most severe first; tickets without a severity go last
+            tickets.sort_by_key(|t| std::cmp::Reverse(t.severity.map_or(0, |s| s.value())));
+        },
+        "status" => {
+            tickets.sort_by(|a, b| {
+                // Custom sort order for status
+                let order = |s: &Status| match s {
+                    Status::Doing => 0,
+                    Status::Review => 1,
+                    Status::Blocked => 2,
+                    Status::Todo => 3,
+                    Status::Done => 4,
+                };
+                order(&a.status).cmp(&order(&b.status))
+            });
+        },
+        "slug" => {
+            // Default to slug sort (which will be chronological due to timestamp prefix)
+            tickets.sort_by(|a, b| a.slug.cmp(&b.slug));
+        },
+        _ => {
+            tickets.sort_by(|a, b| a.slug.cmp(&b.slug));
+        },
+    }
+
+    if reverse {
+        tickets.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_filter() {
+        // Test "yesterday" - should be some time yesterday
+        let yesterday_result = parse_date_filter("yesterday").unwrap();
+        let now = Utc::now();
+        let diff = now.signed_duration_since(yesterday_result);
+        // Should be between 1 and 2 days ago
+        assert!(
+            diff.num_hours() >= 24 && diff.num_hours() <= 48,
+            "Expected yesterday to be 24-48 hours ago, got {} hours",
+            diff.num_hours()
+        );
+
+        // Test "X days ago" - should be X days ago at midnight
+        let three_days_ago = parse_date_filter("3 days ago").unwrap();
+        let diff = now.signed_duration_since(three_days_ago);
+        // Should be between 3 and 4 days ago (72-96 hours)
+        assert!(
+            diff.num_hours() >= 72 && diff.num_hours() <= 96,
+            "Expected 3 days ago to be 72-96 hours ago, got {} hours",
+            diff.num_hours()
+        );
+
+        // Test "X months ago"
+        let three_months_ago = parse_date_filter("3 months ago").unwrap();
+        let diff = now.signed_duration_since(three_months_ago);
+        assert!(
+            (89..=93).contains(&diff.num_days()),
+            "Expected 3 months ago to be 89-93 days ago, got {} days",
+            diff.num_days()
+        );
+
+        // Test weekday names: the latest such day, today included
+        let monday = parse_date_filter("Monday").unwrap().with_timezone(&Local);
+        assert_eq!(monday.weekday(), Weekday::Mon);
+        assert!((0..7).contains(&(Local::now().date_naive() - monday.date_naive()).num_days()));
+
+        // Test ISO date
+        let iso_date = parse_date_filter("2025-07-15").unwrap();
+        assert_eq!(iso_date.format("%Y-%m-%d").to_string(), "2025-07-15");
+
+        // Test invalid format
+        assert!(parse_date_filter("invalid").is_err());
+    }
+}
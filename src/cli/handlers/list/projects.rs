@@ -0,0 +1,111 @@
+//! Listing across projects for `list --all-projects`
+//!
+//! The tickets of every project in the workspace registry (see
+//! [`WorkspaceRegistry`]) are filtered per project, then sorted and limited
+//! together and printed with the name of their project.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::{sort_tickets, status_counts, status_summary};
+use crate::cli::OutputFormatter;
+use crate::config::WorkspaceRegistry;
+use crate::core::Ticket;
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository, TicketSummary};
+
+/// Lists the tickets of all registered projects
+///
+/// `select` narrows down the ticket summaries of one project. Registered
+/// projects whose directory no longer holds a project are skipped with a
+/// warning.
+///
+/// # Errors
+///
+/// Returns an error if no project is registered or a project cannot be read
+pub(super) fn list_all_projects<F>(
+    select: F,
+    sort: &str,
+    reverse: bool,
+    limit: Option<usize>,
+    output: &OutputFormatter,
+) -> Result<()>
+where
+    F: Fn(&Path, &FileStorage, Vec<TicketSummary>) -> Result<Vec<TicketSummary>>,
+{
+    let registry = WorkspaceRegistry::load()?;
+    if registry.projects.is_empty() {
+        return Err(VibeTicketError::custom(
+            "No projects registered. Add them with `vibe-ticket workspace add`",
+        ));
+    }
+
+    let mut storages = HashMap::new();
+    let mut owners = HashMap::new();
+    let mut summaries = Vec::new();
+    let mut skipped = Vec::new();
+    for project in &registry.projects {
+        let vibe_ticket_dir = project.path.join(".vibe-ticket");
+        if !vibe_ticket_dir.is_dir() {
+            skipped.push(project.name.as_str());
+            continue;
+        }
+        let storage = FileStorage::new(vibe_ticket_dir);
+        let selected = select(&project.path, &storage, storage.summaries()?)?;
+        for summary in &selected {
+            owners.insert(summary.id.clone(), project.name.as_str());
+        }
+        summaries.extend(selected);
+        storages.insert(project.name.as_str(), storage);
+    }
+
+    sort_tickets(&mut summaries, sort, reverse);
+    if let Some(limit) = limit {
+        summaries.truncate(limit);
+    }
+
+    let mut projects = Vec::new();
+    let mut tickets: Vec<Ticket> = Vec::new();
+    for summary in &summaries {
+        let project = owners[&summary.id];
+        tickets.push(storages[project].load(&summary.id)?);
+        projects.push(project);
+    }
+
+    if output.is_json() {
+        let rows: Vec<serde_json::Value> = projects
+            .iter()
+            .zip(&tickets)
+            .map(|(project, ticket)| {
+                let mut row = serde_json::json!(ticket);
+                row["project"] = serde_json::json!(project);
+                row
+            })
+            .collect();
+        output.print_json(&serde_json::json!({
+            "projects": storages.keys().collect::<std::collections::BTreeSet<_>>(),
+            "skipped": skipped,
+            "tickets": rows,
+            "count": tickets.len(),
+            "counts": status_counts(&tickets)
+                .into_iter()
+                .map(|(status, count)| (status.to_string().to_lowercase(), count.into()))
+                .collect::<serde_json::Map<_, _>>(),
+        }))?;
+        return Ok(());
+    }
+
+    for name in &skipped {
+        output.warning(&format!(
+            "Skipped project '{name}': no project found at its path"
+        ));
+    }
+    if tickets.is_empty() {
+        output.info("No tickets found matching the criteria.");
+    } else {
+        output.print_project_tickets(&projects, &tickets);
+        output.info(&format!("By status: {}", status_summary(&tickets)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! Live re-rendering for `--watch`
+//!
+//! `list --watch` and `check --watch` re-render whenever the files under
+//! `.vibe-ticket/tickets/` (or the active ticket) change, including changes
+//! made by other processes and worktrees. Changes made through vibe-ticket
+//! are read from the storage event log (see [`crate::storage::StorageEvent`])
+//! on every poll; the file sizes and modification times are compared less
+//! often, to catch changes made without it such as a `git pull`. Changes
+//! are summarized by diffing the tickets against the previous load with
+//! [`TicketCache::refresh_all_tickets`].
+
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::cache::{TicketCache, TicketChanges};
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::Result;
+use crate::storage::FileStorage;
+
+/// Time between two checks of the ticket files
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of polls between two scans of the ticket files
+const SCAN_EVERY: u32 = 10;
+
+/// Size and modification time of each watched file, by name
+type Fingerprint = Vec<(String, u64, Option<SystemTime>)>;
+
+/// Runs `render` now and again whenever the tickets change, until interrupted
+///
+/// Errors of later renders, for example from a ticket file caught half
+/// written, are reported without stopping the watch.
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the first render
+/// fails
+pub fn watch_and_render<F>(
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+    mut render: F,
+) -> Result<()>
+where
+    F: FnMut() -> Result<()>,
+{
+    let vibe_ticket_dir = find_project_root(project_dir)?.join(".vibe-ticket");
+    let cache = TicketCache::with_default_ttl();
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut cursor = storage.event_cursor().unwrap_or_default();
+    let mut last: Option<Fingerprint> = None;
+    let mut polls: u32 = 0;
+
+    loop {
+        let logged = storage.poll_events(&cursor).is_ok_and(|poll| {
+            cursor = poll.cursor;
+            poll.reset || !poll.events.is_empty()
+        });
+        let modified = if last.is_none() || logged || polls % SCAN_EVERY == 0 {
+            let current = fingerprint(&vibe_ticket_dir);
+            let modified = logged || last.as_ref() != Some(&current);
+            if modified {
+                last = Some(current);
+            }
+            modified
+        } else {
+            false
+        };
+
+        if modified {
+            let first = polls == 0;
+            let changes = FileStorage::new(&vibe_ticket_dir)
+                .load_all_tickets()
+                .map(|tickets| cache.refresh_all_tickets(&tickets))
+                .unwrap_or_default();
+
+            if !output.is_json() {
+                // Clear the screen and move the cursor home
+                print!("\x1b[2J\x1b[H");
+            }
+            match render() {
+                Ok(()) => {},
+                Err(e) if first => return Err(e),
+                Err(e) => output.error(&e.to_string()),
+            }
+            if !output.is_json() {
+                if !first {
+                    print_changes(&changes, output);
+                }
+                output.info("Watching for ticket changes (Ctrl+C to stop)");
+            }
+        }
+        polls = polls.wrapping_add(1).max(1);
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Lists the watched files with their sizes and modification times
+fn fingerprint(vibe_ticket_dir: &Path) -> Fingerprint {
+    let mut files: Fingerprint = fs::read_dir(vibe_ticket_dir.join("tickets"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .chain(std::iter::once(vibe_ticket_dir.join("active_ticket")))
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            Some((
+                path.file_name()?.to_string_lossy().into_owned(),
+                metadata.len(),
+                metadata.modified().ok(),
+            ))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Prints one line per changed ticket
+fn print_changes(changes: &TicketChanges, output: &OutputFormatter) {
+    if changes.is_empty() {
+        return;
+    }
+    output.info("");
+    output.info("Changes:");
+    for ticket in &changes.added {
+        output.info(&format!("  + {} created", ticket.slug));
+    }
+    for (ticket, ticket_changes) in &changes.updated {
+        let summary: Vec<String> = ticket_changes.iter().map(ToString::to_string).collect();
+        output.info(&format!("  ~ {}: {}", ticket.slug, summary.join(", ")));
+    }
+    for ticket in &changes.removed {
+        output.info(&format!("  - {} deleted", ticket.slug));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fingerprint_tracks_ticket_files() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        storage.ensure_directories().unwrap();
+        let empty = fingerprint(temp.path());
+        assert!(empty.is_empty());
+
+        let mut ticket = Ticket::new("watched", "Watched");
+        storage.save(&ticket).unwrap();
+        let saved = fingerprint(temp.path());
+        assert_eq!(saved.len(), 1);
+
+        ticket.title = "Watched closely".to_string();
+        storage.save(&ticket).unwrap();
+        assert_ne!(fingerprint(temp.path()), saved);
+
+        storage.set_active_ticket(&ticket.id).unwrap();
+        assert_eq!(fingerprint(temp.path()).len(), 2);
+    }
+}
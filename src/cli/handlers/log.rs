@@ -0,0 +1,202 @@
+//! Handler for the `log` command
+//!
+//! This module implements work-log notes: short, timestamped entries recorded
+//! against a ticket while working on it, and the standup report built from
+//! them.
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::{Ticket, WorkLogEntry};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+use chrono::{DateTime, Utc};
+
+/// Handler for the `log` command
+///
+/// Records a work-log note on a ticket.
+///
+/// # Arguments
+///
+/// * `message` - Note describing the work done
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_log_command(
+    message: &str,
+    ticket_ref: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    if message.trim().is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Work-log message cannot be empty".to_string(),
+        ));
+    }
+
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+
+    // Initialize storage
+    let storage = FileStorage::new(&vibe_ticket_dir);
+
+    // Get the active ticket if no ticket specified
+    let ticket_id = if let Some(ref_str) = ticket_ref {
+        resolve_ticket_ref(&storage, &ref_str)?
+    } else {
+        storage
+            .get_active()?
+            .ok_or(VibeTicketError::NoActiveTicket)?
+    };
+
+    // Record the entry
+    let mut ticket = storage.load(&ticket_id)?;
+    ticket.log_work(message.trim());
+    storage.save(&ticket)?;
+
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "entry": ticket.work_log.last(),
+            "total_entries": ticket.work_log.len(),
+        }))?;
+    } else {
+        output.success(&format!("Logged work on ticket '{}'", ticket.slug));
+        output.info(&format!("Total entries: {}", ticket.work_log.len()));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `log --standup` command
+///
+/// Prints the work-log entries recorded since the given date, grouped by
+/// ticket and in chronological order.
+///
+/// # Arguments
+///
+/// * `since` - Start of the report window (e.g., "yesterday", "2025-07-18")
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_standup_report(
+    since: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+
+    // Initialize storage
+    let storage = FileStorage::new(&vibe_ticket_dir);
+
+    let since = output.dates().parse(since)?;
+    let report = collect_standup(storage.load_all()?, since);
+
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "since": since,
+            "tickets": report.iter().map(|(ticket, entries)| serde_json::json!({
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+                "title": ticket.title,
+                "status": ticket.status.to_string(),
+                "entries": entries,
+            })).collect::<Vec<_>>(),
+        }))?;
+    } else if report.is_empty() {
+        output.info("No work logged in this period.");
+    } else {
+        output.success(&format!(
+            "Standup report since {}",
+            output.dates().format(since)
+        ));
+        for (ticket, entries) in &report {
+            output.info("");
+            output.info(&format!(
+                "{} - {} ({})",
+                ticket.slug,
+                ticket.title,
+                output.visuals().status_text(ticket.status)
+            ));
+            for entry in entries {
+                output.info(&format!(
+                    "  {} {}",
+                    output.dates().format(entry.logged_at),
+                    entry.message
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect the work-log entries logged since `since`, grouped by ticket
+///
+/// Tickets are ordered by their earliest entry in the window.
+fn collect_standup(tickets: Vec<Ticket>, since: DateTime<Utc>) -> Vec<(Ticket, Vec<WorkLogEntry>)> {
+    let mut report: Vec<_> = tickets
+        .into_iter()
+        .filter_map(|ticket| {
+            let mut entries: Vec<_> = ticket
+                .work_log
+                .iter()
+                .filter(|e| e.logged_at >= since)
+                .cloned()
+                .collect();
+            if entries.is_empty() {
+                return None;
+            }
+            entries.sort_by_key(|e| e.logged_at);
+            Some((ticket, entries))
+        })
+        .collect();
+
+    report.sort_by_key(|(_, entries)| entries[0].logged_at);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_collect_standup() {
+        let now = Utc::now();
+
+        let mut first = Ticket::new("flaky-test", "Fix flaky test");
+        first.work_log.push(WorkLogEntry {
+            logged_at: now - Duration::days(3),
+            message: "too old".to_string(),
+        });
+        first.work_log.push(WorkLogEntry {
+            logged_at: now - Duration::hours(2),
+            message: "suspect race".to_string(),
+        });
+        first.work_log.push(WorkLogEntry {
+            logged_at: now - Duration::hours(5),
+            message: "investigated flaky test".to_string(),
+        });
+
+        let mut second = Ticket::new("docs", "Update docs");
+        second.work_log.push(WorkLogEntry {
+            logged_at: now - Duration::hours(10),
+            message: "drafted outline".to_string(),
+        });
+
+        let idle = Ticket::new("idle", "No work logged");
+
+        let report = collect_standup(vec![first, second, idle], now - Duration::days(1));
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].0.slug, "docs");
+        assert_eq!(report[1].0.slug, "flaky-test");
+
+        let messages: Vec<_> = report[1].1.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["investigated flaky test", "suspect race"]);
+    }
+}
@@ -0,0 +1,150 @@
+//! Handler for the `maintain` command
+//!
+//! This module runs the periodic upkeep of a project. It currently applies
+//! the aging policy configured under `aging` (see [`crate::core::aging`]):
+//! untouched tickets are tagged stale and their watchers notified, stale
+//! tickets are closed after the grace period, and stale tickets with new
+//! activity are revived. Stale tickets that depend on open tickets stay
+//! stale rather than closing. The command is meant to be run on a schedule, for
+//! example from cron or CI.
+
+use chrono::Utc;
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::core::aging::{self, AgingAction};
+use crate::core::{FieldDelta, Ticket, dependencies};
+use crate::error::Result;
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+/// Handler for the `maintain` command
+///
+/// # Arguments
+///
+/// * `dry_run` - Report what would change without saving anything
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or tickets cannot be
+/// read or saved
+pub fn handle_maintain_command(
+    dry_run: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let config = Config::load_for_project(&project_root)?;
+    // Closing stale tickets must not fail on the close requirements
+    let storage = FileStorage::new(project_root.join(".vibe-ticket")).with_forced_close(true);
+
+    if config.aging.stale_after_days.is_none() {
+        if output.is_json() {
+            output.print_json(&serde_json::json!({
+                "aging_enabled": false,
+                "dry_run": dry_run,
+                "actions": [],
+            }))?;
+        } else {
+            output.info("Aging is disabled. Set aging.stale_after_days to enable it.");
+        }
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let tickets = storage.load_all()?;
+    let mut actions = Vec::new();
+    for ticket in &tickets {
+        let history = storage.load_history(&ticket.id)?;
+        match aging::evaluate(&config.aging, ticket, &history, now) {
+            Some(AgingAction::Close { .. })
+                if !dependencies::open_dependencies(ticket, &tickets).is_empty() => {},
+            Some(action) => actions.push((ticket.clone(), action)),
+            None => {},
+        }
+    }
+
+    if !dry_run {
+        for (ticket, action) in &mut actions {
+            let before = ticket.clone();
+            *ticket = storage.update_ticket(&ticket.id, |t| aging::apply(*action, t, now))?;
+            notify(&project_root, &storage, &before, ticket, *action)?;
+        }
+    }
+
+    if output.is_json() {
+        let actions: Vec<_> = actions
+            .iter()
+            .map(|(ticket, action)| {
+                let mut entry = serde_json::json!({
+                    "ticket_id": ticket.id.to_string(),
+                    "ticket_slug": ticket.slug,
+                });
+                if let (Some(entry), Ok(serde_json::Value::Object(action))) =
+                    (entry.as_object_mut(), serde_json::to_value(action))
+                {
+                    entry.extend(action);
+                }
+                entry
+            })
+            .collect();
+        output.print_json(&serde_json::json!({
+            "aging_enabled": true,
+            "dry_run": dry_run,
+            "actions": actions,
+        }))?;
+    } else if actions.is_empty() {
+        output.success("Nothing to do");
+    } else {
+        let prefix = if dry_run { "Would update" } else { "Updated" };
+        output.success(&format!("{prefix} {} ticket(s):", actions.len()));
+        for (ticket, action) in &actions {
+            output.info(&format!("  {}: {}", ticket.slug, action.describe()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Tells watchers and plugins about an applied aging action
+fn notify(
+    project_root: &std::path::Path,
+    storage: &FileStorage,
+    before: &Ticket,
+    after: &Ticket,
+    action: AgingAction,
+) -> Result<()> {
+    match action {
+        // Status changes reach watchers through the audit log; stale marks
+        // are tag changes, which are not watched, so they are sent here
+        AgingAction::MarkStale { .. } => {
+            let delta = FieldDelta {
+                field: "tags".to_string(),
+                from: Some(before.tags.join(", ")),
+                to: Some(after.tags.join(", ")),
+            };
+            storage.notify_watchers(after, vec![delta])?;
+            emit_cli_event(project_root, &TicketEvent::Updated(after.clone()));
+        },
+        AgingAction::Revive => {
+            emit_cli_event(project_root, &TicketEvent::Updated(after.clone()));
+        },
+        AgingAction::Close { .. } => {
+            if storage.get_active()?.as_ref() == Some(&after.id) {
+                storage.clear_active()?;
+            }
+            let message = after
+                .metadata
+                .get("close_message")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default();
+            emit_cli_event(
+                project_root,
+                &TicketEvent::Closed(after.id.clone(), message.to_string()),
+            );
+        },
+    }
+    Ok(())
+}
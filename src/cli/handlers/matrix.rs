@@ -0,0 +1,244 @@
+//! Handler for the `matrix` command
+//!
+//! Renders the open tickets as a priority matrix (see
+//! [`crate::core::matrix`]): urgency from the due date on one axis,
+//! importance from the priority on the other.
+
+use chrono::{Local, NaiveDate};
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::core::Status;
+use crate::core::matrix::Quadrant;
+use crate::error::Result;
+use crate::storage::{FileStorage, TicketSummary};
+
+/// Width of a cell of the grid, in characters
+const CELL_WIDTH: usize = 38;
+
+/// Width of the row labels of the grid, in characters
+const LABEL_WIDTH: usize = 14;
+
+/// Handler for the `matrix` command
+///
+/// # Arguments
+///
+/// * `urgent_days` - Tickets due within this many days (or overdue) are urgent
+/// * `limit` - Most tickets listed per quadrant in the grid
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or cannot be read
+pub fn handle_matrix_command(
+    urgent_days: i64,
+    limit: usize,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let today = Local::now().date_naive();
+    let quadrants = build_matrix(storage.summaries()?, today, urgent_days);
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "today": today,
+            "urgent_days": urgent_days,
+            "total": quadrants.iter().map(|(_, tickets)| tickets.len()).sum::<usize>(),
+            "quadrants": quadrants
+                .iter()
+                .map(|(quadrant, tickets)| {
+                    serde_json::json!({
+                        "quadrant": quadrant,
+                        "label": quadrant.label(),
+                        "urgent": quadrant.is_urgent(),
+                        "important": quadrant.is_important(),
+                        "count": tickets.len(),
+                        "tickets": tickets
+                            .iter()
+                            .map(|t| {
+                                serde_json::json!({
+                                    "id": t.id.to_string(),
+                                    "slug": t.slug,
+                                    "title": t.title,
+                                    "priority": t.priority.to_string(),
+                                    "status": t.status.to_string(),
+                                    "due": t.due,
+                                    "assignee": t.assignee,
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        }))?;
+    } else if quadrants.iter().all(|(_, tickets)| tickets.is_empty()) {
+        output.info("No open tickets.");
+    } else {
+        for line in render_grid(&quadrants, urgent_days, limit) {
+            println!("{line}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Sorts the open, non-archived tickets into the quadrants
+///
+/// Within a quadrant, tickets are ordered by due date (tickets without one
+/// last), then by priority, highest first.
+fn build_matrix(
+    tickets: Vec<TicketSummary>,
+    today: NaiveDate,
+    urgent_days: i64,
+) -> Vec<(Quadrant, Vec<TicketSummary>)> {
+    let mut quadrants: Vec<(Quadrant, Vec<TicketSummary>)> =
+        Quadrant::ALL.iter().map(|q| (*q, Vec::new())).collect();
+
+    for ticket in tickets
+        .into_iter()
+        .filter(|t| t.status != Status::Done && !t.is_archived())
+    {
+        let quadrant = Quadrant::classify(ticket.priority, ticket.due, today, urgent_days);
+        if let Some((_, cell)) = quadrants.iter_mut().find(|(q, _)| *q == quadrant) {
+            cell.push(ticket);
+        }
+    }
+
+    for (_, cell) in &mut quadrants {
+        cell.sort_by(|a, b| {
+            (a.due.is_none(), a.due, b.priority, &a.slug).cmp(&(
+                b.due.is_none(),
+                b.due,
+                a.priority,
+                &b.slug,
+            ))
+        });
+    }
+    quadrants
+}
+
+/// Renders the quadrants as a 2×2 grid
+fn render_grid(
+    quadrants: &[(Quadrant, Vec<TicketSummary>)],
+    urgent_days: i64,
+    limit: usize,
+) -> Vec<String> {
+    let rule = |cross: char, end: char| {
+        format!(
+            "{}{cross}{}{cross}{}{end}",
+            "─".repeat(LABEL_WIDTH),
+            "─".repeat(CELL_WIDTH),
+            "─".repeat(CELL_WIDTH)
+        )
+    };
+    let mut lines = vec![
+        format!(
+            "{}│{}│{}│",
+            fit("", LABEL_WIDTH),
+            fit(&format!(" Urgent (due ≤ {urgent_days} days)"), CELL_WIDTH),
+            fit(" Not urgent", CELL_WIDTH)
+        ),
+        rule('┼', '┤'),
+    ];
+
+    for (label, row) in [
+        ("Important", &quadrants[..2]),
+        ("Not important", &quadrants[2..]),
+    ] {
+        let cells: Vec<Vec<String>> = row
+            .iter()
+            .map(|(quadrant, tickets)| cell_lines(*quadrant, tickets, limit))
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for i in 0..height {
+            let text = |cell: &Vec<String>| fit(cell.get(i).map_or("", String::as_str), CELL_WIDTH);
+            lines.push(format!(
+                "{}│{}│{}│",
+                fit(if i == 0 { label } else { "" }, LABEL_WIDTH),
+                text(&cells[0]),
+                text(&cells[1])
+            ));
+        }
+        lines.push(rule('┼', '┤'));
+    }
+    lines.pop();
+    lines.push(rule('┴', '┘'));
+
+    lines
+}
+
+/// Lines of a cell: the quadrant with its count, then its first tickets
+fn cell_lines(quadrant: Quadrant, tickets: &[TicketSummary], limit: usize) -> Vec<String> {
+    let mut lines = vec![format!(" {} ({})", quadrant.label(), tickets.len())];
+    for ticket in tickets.iter().take(limit) {
+        let due = ticket
+            .due
+            .map(|due| format!(" [due {}]", due.format("%m-%d")))
+            .unwrap_or_default();
+        lines.push(format!("  {} {}{due}", ticket.display_id(), ticket.title));
+    }
+    if tickets.len() > limit {
+        lines.push(format!("  … and {} more", tickets.len() - limit));
+    }
+    lines
+}
+
+/// Pads or truncates `text` to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        let mut truncated: String = text.chars().take(width - 1).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        format!("{text:<width$}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Priority, Ticket};
+    use chrono::Duration;
+
+    fn summary(slug: &str, priority: Priority, due: Option<NaiveDate>) -> TicketSummary {
+        let mut ticket = Ticket::new(slug, slug);
+        ticket.priority = priority;
+        ticket.due = due;
+        serde_yaml::from_str(&serde_yaml::to_string(&ticket).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_build_and_render_matrix() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 20).unwrap();
+        let mut done = summary("done", Priority::Critical, Some(today));
+        done.status = Status::Done;
+        let tickets = vec![
+            summary("later", Priority::High, None),
+            summary(
+                "urgent",
+                Priority::Critical,
+                Some(today + Duration::days(5)),
+            ),
+            summary("overdue", Priority::High, Some(today - Duration::days(1))),
+            summary("chore", Priority::Low, None),
+            done,
+        ];
+
+        let quadrants = build_matrix(tickets, today, 7);
+        let slugs =
+            |i: usize| -> Vec<&str> { quadrants[i].1.iter().map(|t| t.slug.as_str()).collect() };
+        assert_eq!(quadrants[0].0, Quadrant::DoFirst);
+        assert_eq!(slugs(0), vec!["overdue", "urgent"]);
+        assert_eq!(slugs(1), vec!["later"]);
+        assert!(slugs(2).is_empty());
+        assert_eq!(slugs(3), vec!["chore"]);
+
+        let lines = render_grid(&quadrants, 7, 1);
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+        assert!(lines.iter().any(|line| line.contains("Do first (2)")));
+        assert!(lines.iter().any(|line| line.contains("… and 1 more")));
+    }
+}
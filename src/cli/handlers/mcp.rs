@@ -1,15 +1,26 @@
 //! MCP server command handler
 
-use crate::cli::output::OutputFormatter;
-use crate::config::Config;
+use crate::cli::{find_project_root, output::OutputFormatter};
+use crate::config::{Config, TokenScope};
+use crate::error::{Result, VibeTicketError};
+use crate::mcp::access_log::{AccessEntry, AccessLog, AccessStatus};
+use crate::mcp::auth;
+use crate::mcp::config::TransportType;
+use crate::mcp::daemon::{Daemon, DaemonState};
 use crate::mcp::{McpConfig, McpServer};
 use crate::storage::FileStorage;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// Time the background server gets to shut down after being asked to stop
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub fn handle_mcp_serve(
-    _config: Config,
+    config: Config,
     host: Option<String>,
     port: Option<u16>,
+    transport: &str,
     daemon: bool,
     project_path: Option<&str>,
     formatter: &OutputFormatter,
@@ -18,6 +29,9 @@ pub fn handle_mcp_serve(
 
     // Create MCP configuration
     let mut mcp_config = McpConfig::default();
+    mcp_config.server.transport = transport
+        .parse::<TransportType>()
+        .map_err(|e| anyhow::anyhow!(e))?;
 
     if let Some(host) = host {
         mcp_config.server.host = host;
@@ -36,30 +50,375 @@ pub fn handle_mcp_serve(
 
     mcp_config.storage_path = storage_path.clone();
 
-    // Create storage
-    let storage = FileStorage::new(storage_path);
-
-    // Create and start server
-    let server = McpServer::new(mcp_config.clone(), storage);
-
     if daemon {
-        formatter.info("Starting MCP server in daemon mode...");
-        // TODO: Implement daemon mode
-        return Err(anyhow::anyhow!("Daemon mode not yet implemented"));
+        let state = start_daemon(&mcp_config, project_path)?;
+        print_state(&state, "MCP server started in the background", formatter)?;
+        return Ok(());
     }
 
+    // Coalesce the bursts of saves agents make, if configured, as the
+    // server runs for long
+    let config = match project_path {
+        Some(path) => Config::load_for_project(Path::new(path))?,
+        None => config,
+    };
+    let storage = FileStorage::new(storage_path)
+        .with_write_coalescing(Duration::from_millis(config.mcp.write_coalesce_ms));
+
+    // Create and start server
+    let server = McpServer::new(mcp_config.clone(), storage.clone());
+
     formatter.info(&format!(
         "Starting MCP server on {}:{}",
         mcp_config.server.host, mcp_config.server.port
     ));
 
-    // Run server
+    // Run server until it fails or the process is asked to stop
     let runtime = tokio::runtime::Runtime::new()?;
-    runtime.block_on(async {
-        if let Err(e) = server.start().await {
-            error!("MCP server error: {}", e);
-            return Err(anyhow::anyhow!("MCP server error: {}", e));
+    let result = runtime.block_on(async {
+        tokio::select! {
+            result = server.start() => {
+                if let Err(e) = result {
+                    error!("MCP server error: {}", e);
+                    return Err(anyhow::anyhow!("MCP server error: {}", e));
+                }
+            },
+            () = shutdown_signal() => {},
         }
         Ok(())
-    })
+    });
+
+    // Write out the saves still held back; connections still open are
+    // dropped rather than waited for
+    let flushed = storage.flush();
+    runtime.shutdown_background();
+    flushed?;
+    result
+}
+
+/// Completes when the process receives Ctrl-C or, on unix, SIGTERM
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = Box::pin(tokio::signal::ctrl_c()) => {},
+                _ = terminate.recv() => {},
+            }
+            return;
+        }
+    }
+    let _ = Box::pin(tokio::signal::ctrl_c()).await;
+}
+
+/// Re-runs this binary as a detached server for the project
+///
+/// The server speaks the selected transport, except stdio, which a detached
+/// process has no terminal for and which falls back to TCP.
+fn start_daemon(mcp_config: &McpConfig, project_path: Option<&str>) -> Result<DaemonState> {
+    let project_root = find_project_root(project_path)?;
+    let config = Config::load_for_project(&project_root)?;
+    let log_file = project_root.join(&config.mcp.log_file);
+
+    let transport = match mcp_config.server.transport {
+        TransportType::Stdio | TransportType::Tcp => "tcp",
+        TransportType::Http => "http",
+        TransportType::WebSocket => "websocket",
+    };
+    let host = &mcp_config.server.host;
+    let port = mcp_config.server.port;
+    let mut command = Command::new(std::env::current_exe()?);
+    command.current_dir(&project_root).args([
+        "--project",
+        &project_root.to_string_lossy(),
+        "mcp",
+        "serve",
+        "--transport",
+        transport,
+        "--host",
+        host,
+        "--port",
+        &port.to_string(),
+    ]);
+
+    daemon_for(&project_root).start(command, transport, host, port, &log_file)
+}
+
+/// Handler for `mcp status`
+///
+/// Reports whether a background server runs for the project.
+pub fn handle_mcp_status(project_path: Option<&str>, formatter: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_path)?;
+    daemon_for(&project_root).status()?.map_or_else(
+        || print_stopped(formatter, "MCP server is not running"),
+        |state| print_state(&state, "MCP server is running", formatter),
+    )
+}
+
+/// Handler for `mcp stop`
+///
+/// Stops the background server of the project, if one runs.
+pub fn handle_mcp_stop(project_path: Option<&str>, formatter: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_path)?;
+    match daemon_for(&project_root).stop(STOP_TIMEOUT)? {
+        Some(state) => {
+            if formatter.is_json() {
+                formatter.print_json(&serde_json::json!({
+                    "running": false,
+                    "stopped_pid": state.pid,
+                }))?;
+            } else {
+                formatter.success(&format!("Stopped MCP server (pid {})", state.pid));
+            }
+            Ok(())
+        },
+        None => print_stopped(formatter, "No MCP server is running"),
+    }
+}
+
+/// Handler for `mcp token create`
+///
+/// Stores the hash of a new API token and prints the token, which cannot be
+/// shown again.
+pub fn handle_mcp_token_create(
+    name: &str,
+    scope: &str,
+    project_path: Option<&str>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Token name cannot be empty".to_string(),
+        ));
+    }
+    let scope = scope.parse::<TokenScope>()?;
+    let project_root = find_project_root(project_path)?;
+    let mut config = Config::load_for_project(&project_root)?;
+    if config.mcp.tokens.iter().any(|token| token.name == name) {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "A token named '{name}' already exists"
+        )));
+    }
+
+    let (token, entry) = auth::create_token(name, scope);
+    config.mcp.tokens.push(entry);
+    config.save_to_path(project_root.join(".vibe-ticket/config.yaml"))?;
+
+    if formatter.is_json() {
+        formatter.print_json(&serde_json::json!({
+            "status": "success",
+            "name": name,
+            "scope": scope,
+            "token": token,
+        }))?;
+    } else {
+        formatter.success(&format!("Created {scope} token '{name}'"));
+        formatter.info(&format!("  Token: {token}"));
+        formatter.info("  Store it now; it cannot be shown again.");
+    }
+    Ok(())
+}
+
+/// Handler for `mcp token list`
+pub fn handle_mcp_token_list(
+    project_path: Option<&str>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_path)?;
+    let tokens = Config::load_for_project(&project_root)?.mcp.tokens;
+
+    if formatter.is_json() {
+        let tokens: Vec<_> = tokens
+            .iter()
+            .map(|token| {
+                serde_json::json!({
+                    "name": token.name,
+                    "scope": token.scope,
+                    "created_at": token.created_at,
+                })
+            })
+            .collect();
+        formatter.print_json(&serde_json::json!({ "tokens": tokens }))?;
+        return Ok(());
+    }
+
+    if tokens.is_empty() {
+        formatter.info("No API tokens; the HTTP transport accepts any request");
+        return Ok(());
+    }
+    for token in &tokens {
+        formatter.info(&format!(
+            "{} ({}) created {}",
+            token.name,
+            token.scope,
+            formatter.dates().format(token.created_at)
+        ));
+    }
+    Ok(())
+}
+
+/// Handler for `mcp token revoke`
+///
+/// Running servers reject the token from their next request on.
+pub fn handle_mcp_token_revoke(
+    name: &str,
+    project_path: Option<&str>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_path)?;
+    let mut config = Config::load_for_project(&project_root)?;
+    let before = config.mcp.tokens.len();
+    config.mcp.tokens.retain(|token| token.name != name);
+    if config.mcp.tokens.len() == before {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "No token named '{name}'"
+        )));
+    }
+    config.save_to_path(project_root.join(".vibe-ticket/config.yaml"))?;
+
+    if formatter.is_json() {
+        formatter.print_json(&serde_json::json!({
+            "status": "success",
+            "revoked": name,
+        }))?;
+    } else {
+        formatter.success(&format!("Revoked token '{name}'"));
+    }
+    Ok(())
+}
+
+/// Filters of `mcp audit`
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// Name of the tool
+    pub tool: Option<String>,
+    /// Name of the API token
+    pub token: Option<String>,
+    /// Outcome of the calls (ok, error, denied)
+    pub status: Option<String>,
+    /// Earliest date of the calls
+    pub since: Option<String>,
+    /// Number of most recent calls to show
+    pub limit: usize,
+}
+
+/// Handler for `mcp audit`
+///
+/// Shows the most recent matching calls of the access log, oldest first.
+pub fn handle_mcp_audit(
+    filter: &AuditFilter,
+    project_path: Option<&str>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_path)?;
+    let status = filter
+        .status
+        .as_deref()
+        .map(|name| {
+            AccessStatus::parse(name).ok_or_else(|| {
+                VibeTicketError::InvalidInput(format!(
+                    "Unknown status '{name}'. Use ok, error or denied"
+                ))
+            })
+        })
+        .transpose()?;
+    let since = filter
+        .since
+        .as_deref()
+        .map(|since| formatter.dates().parse(since))
+        .transpose()?;
+
+    let mut entries: Vec<AccessEntry> = AccessLog::new(&project_root.join(".vibe-ticket"))
+        .load()?
+        .into_iter()
+        .filter(|entry| filter.tool.as_ref().is_none_or(|tool| &entry.tool == tool))
+        .filter(|entry| {
+            filter
+                .token
+                .as_ref()
+                .is_none_or(|token| entry.token.as_ref() == Some(token))
+        })
+        .filter(|entry| status.is_none_or(|status| entry.status == status))
+        .filter(|entry| since.is_none_or(|since| entry.at >= since))
+        .collect();
+    let total = entries.len();
+    entries.drain(..total.saturating_sub(filter.limit));
+
+    if formatter.is_json() {
+        formatter.print_json(&serde_json::json!({
+            "total": total,
+            "entries": entries,
+        }))?;
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        formatter.info("No matching MCP tool calls");
+        return Ok(());
+    }
+    for entry in &entries {
+        let caller = match (&entry.token, &entry.client) {
+            (Some(token), Some(client)) => format!("{token} via {client}"),
+            (Some(who), None) | (None, Some(who)) => who.clone(),
+            (None, None) => "-".to_string(),
+        };
+        formatter.info(&format!(
+            "{} {:<6} {} {caller} {}ms params {}",
+            formatter.dates().format(entry.at),
+            entry.status,
+            entry.tool,
+            entry.duration_ms,
+            &entry.params_hash[..12.min(entry.params_hash.len())],
+        ));
+        if let Some(error) = &entry.error {
+            formatter.info(&format!("    {error}"));
+        }
+    }
+    if total > entries.len() {
+        formatter.info(&format!(
+            "Showing the last {} of {total} calls (use --limit to show more)",
+            entries.len()
+        ));
+    }
+    Ok(())
+}
+
+fn daemon_for(project_root: &Path) -> Daemon {
+    Daemon::new(&project_root.join(".vibe-ticket"))
+}
+
+fn print_state(state: &DaemonState, message: &str, formatter: &OutputFormatter) -> Result<()> {
+    if formatter.is_json() {
+        formatter.print_json(&serde_json::json!({
+            "running": true,
+            "pid": state.pid,
+            "transport": state.transport,
+            "address": state.address(),
+            "log_file": state.log_file,
+            "started_at": state.started_at,
+        }))?;
+        return Ok(());
+    }
+
+    formatter.success(message);
+    formatter.info(&format!("  PID: {}", state.pid));
+    formatter.info(&format!("  Transport: {}", state.transport));
+    formatter.info(&format!("  Address: {}", state.address()));
+    formatter.info(&format!("  Log file: {}", state.log_file.display()));
+    formatter.info(&format!(
+        "  Started: {}",
+        formatter.dates().format(state.started_at)
+    ));
+    Ok(())
+}
+
+fn print_stopped(formatter: &OutputFormatter, message: &str) -> Result<()> {
+    if formatter.is_json() {
+        formatter.print_json(&serde_json::json!({ "running": false }))?;
+    } else {
+        formatter.info(message);
+    }
+    Ok(())
 }
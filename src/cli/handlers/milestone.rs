@@ -0,0 +1,393 @@
+//! Handler for the `milestone` command and its subcommands
+//!
+//! This module manages milestones (see [`crate::core::Milestone`]):
+//! creating, listing, editing and deleting them, and adding tickets to them
+//! or removing them again. Progress is shown as the share of done tickets
+//! and a burndown of the open tickets over time.
+
+use chrono::{Local, NaiveDate};
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::{Milestone, MilestoneProgress, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+/// Handler for the `milestone create` subcommand
+///
+/// # Arguments
+///
+/// * `name` - Unique name of the milestone
+/// * `due` - Optional due date (`YYYY-MM-DD`)
+/// * `description` - Optional description
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the name is empty or taken, or the due date is invalid
+pub fn handle_milestone_create(
+    name: &str,
+    due: Option<&str>,
+    description: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let storage = open_storage(project_dir)?;
+    let name = name.trim();
+    if name.is_empty() || name.eq_ignore_ascii_case("none") {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Invalid milestone name '{name}'"
+        )));
+    }
+
+    let mut milestones = storage.load_milestones()?;
+    if milestones.iter().any(|m| m.name == name) {
+        return Err(VibeTicketError::custom(format!(
+            "Milestone '{name}' already exists"
+        )));
+    }
+
+    let mut milestone = Milestone::new(name);
+    milestone.due = due.map(parse_due).transpose()?.flatten();
+    milestone.description = description.filter(|d| !d.trim().is_empty());
+    milestones.push(milestone.clone());
+    storage.save_milestones(&milestones)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "milestone": milestone,
+        }))?;
+    } else {
+        output.success(&format!("Created milestone '{name}'"));
+        if let Some(due) = milestone.due {
+            output.info(&format!("Due: {due}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `milestone list` subcommand
+///
+/// Prints every milestone with a one-line progress summary.
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or cannot be read
+pub fn handle_milestone_list(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let storage = open_storage(project_dir)?;
+    let milestones = storage.load_milestones()?;
+    let tickets = storage.load_all()?;
+    let today = Local::now().date_naive();
+    let progress: Vec<MilestoneProgress> = milestones
+        .iter()
+        .map(|milestone| MilestoneProgress::compute(milestone, &tickets, today))
+        .collect();
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "milestones": milestones,
+            "progress": progress,
+        }))?;
+    } else if milestones.is_empty() {
+        output.info("No milestones. Create one with 'vibe-ticket milestone create <name>'.");
+    } else {
+        for progress in &progress {
+            output.info(&format!("{}: {}", progress.name, progress.summary()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `milestone show` subcommand
+///
+/// Prints the milestone, its progress and burndown, and its tickets.
+///
+/// # Errors
+///
+/// Returns an error if the milestone does not exist
+pub fn handle_milestone_show(
+    name: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let storage = open_storage(project_dir)?;
+    let milestone = storage.load_milestone(name)?;
+    let mut tickets = storage.load_all()?;
+    tickets.retain(|t| t.milestone.as_deref() == Some(name));
+    tickets.sort_by(|a, b| a.slug.cmp(&b.slug));
+    let progress = MilestoneProgress::compute(&milestone, &tickets, Local::now().date_naive());
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "milestone": milestone,
+            "progress": progress,
+            "tickets": tickets,
+        }))?;
+        return Ok(());
+    }
+
+    output.success(&format!("Milestone: {}", milestone.name));
+    if let Some(description) = &milestone.description {
+        output.info(&format!("Description: {description}"));
+    }
+    output.info(&format!("Progress: {}", progress.summary()));
+    output.info(&format!("Burndown: {}", progress.burndown_line()));
+    output.info("");
+    if tickets.is_empty() {
+        output.info("No tickets in this milestone.");
+    } else {
+        output.print_tickets(&tickets)?;
+    }
+
+    Ok(())
+}
+
+/// Handler for the `milestone edit` subcommand
+///
+/// # Arguments
+///
+/// * `name` - Milestone name
+/// * `due` - New due date (`YYYY-MM-DD`; `none` clears it)
+/// * `description` - New description (an empty string clears it)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the milestone does not exist or the due date is invalid
+pub fn handle_milestone_edit(
+    name: &str,
+    due: Option<&str>,
+    description: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    if due.is_none() && description.is_none() {
+        return Err(VibeTicketError::custom(
+            "Nothing to change. Use --due or --description",
+        ));
+    }
+
+    let storage = open_storage(project_dir)?;
+    let mut milestones = storage.load_milestones()?;
+    let milestone = milestones
+        .iter_mut()
+        .find(|m| m.name == name)
+        .ok_or_else(|| VibeTicketError::custom(format!("Milestone '{name}' not found")))?;
+    if let Some(due) = due {
+        milestone.due = parse_due(due)?;
+    }
+    if let Some(description) = description {
+        milestone.description = Some(description).filter(|d| !d.trim().is_empty());
+    }
+    let milestone = milestone.clone();
+    storage.save_milestones(&milestones)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "milestone": milestone,
+        }))?;
+    } else {
+        output.success(&format!("Updated milestone '{name}'"));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `milestone delete` subcommand
+///
+/// Removes the milestone and clears it from the tickets that had it.
+///
+/// # Errors
+///
+/// Returns an error if the milestone does not exist or a ticket cannot be saved
+pub fn handle_milestone_delete(
+    name: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let storage = open_storage(project_dir)?;
+    let mut milestones = storage.load_milestones()?;
+    let before = milestones.len();
+    milestones.retain(|m| m.name != name);
+    if milestones.len() == before {
+        return Err(VibeTicketError::custom(format!(
+            "Milestone '{name}' not found"
+        )));
+    }
+
+    let mut cleared = Vec::new();
+    for ticket in storage.load_all()? {
+        if ticket.milestone.as_deref() == Some(name) {
+            storage.update_ticket(&ticket.id, |t| t.milestone = None)?;
+            cleared.push(ticket.slug);
+        }
+    }
+    storage.save_milestones(&milestones)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "milestone": name,
+            "cleared_tickets": cleared,
+        }))?;
+    } else {
+        output.success(&format!("Deleted milestone '{name}'"));
+        if !cleared.is_empty() {
+            output.info(&format!("Removed from {} ticket(s)", cleared.len()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `milestone assign` subcommand
+///
+/// Adds tickets to a milestone, moving them out of any other milestone.
+///
+/// # Errors
+///
+/// Returns an error if the milestone or a ticket does not exist
+pub fn handle_milestone_assign(
+    name: &str,
+    ticket_refs: &[String],
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let storage = open_storage(project_dir)?;
+    let milestone = storage.load_milestone(name)?;
+    let tickets = set_milestone(&storage, ticket_refs, Some(&milestone.name))?;
+
+    report_tickets(
+        &tickets,
+        &format!("Added to milestone '{name}'"),
+        Some(name),
+        output,
+    )
+}
+
+/// Handler for the `milestone unassign` subcommand
+///
+/// Removes tickets from their milestone.
+///
+/// # Errors
+///
+/// Returns an error if a ticket does not exist
+pub fn handle_milestone_unassign(
+    ticket_refs: &[String],
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let storage = open_storage(project_dir)?;
+    let tickets = set_milestone(&storage, ticket_refs, None)?;
+
+    report_tickets(&tickets, "Removed from milestone", None, output)
+}
+
+/// Opens the storage of the project
+fn open_storage(project_dir: Option<&str>) -> Result<FileStorage> {
+    let project_root = find_project_root(project_dir)?;
+    Ok(FileStorage::new(project_root.join(".vibe-ticket")))
+}
+
+/// Parses a due date, where `none` means no due date
+fn parse_due(due: &str) -> Result<Option<NaiveDate>> {
+    if due.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(due, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| {
+            VibeTicketError::InvalidInput(format!(
+                "Invalid due date '{due}'. Expected YYYY-MM-DD or 'none'"
+            ))
+        })
+}
+
+/// Sets the milestone of the referenced tickets (the active ticket if none)
+fn set_milestone(
+    storage: &FileStorage,
+    ticket_refs: &[String],
+    milestone: Option<&str>,
+) -> Result<Vec<Ticket>> {
+    let ids = if ticket_refs.is_empty() {
+        vec![
+            storage
+                .get_active()?
+                .ok_or(VibeTicketError::NoActiveTicket)?,
+        ]
+    } else {
+        ticket_refs
+            .iter()
+            .map(|r| resolve_ticket_ref(storage, r))
+            .collect::<Result<Vec<TicketId>>>()?
+    };
+
+    ids.iter()
+        .map(|id| storage.update_ticket(id, |t| t.milestone = milestone.map(ToString::to_string)))
+        .collect()
+}
+
+/// Prints the tickets whose milestone changed
+fn report_tickets(
+    tickets: &[Ticket],
+    message: &str,
+    milestone: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "milestone": milestone,
+            "tickets": tickets.iter().map(|t| &t.slug).collect::<Vec<_>>(),
+        }))?;
+    } else {
+        output.success(&format!("{message}: {} ticket(s)", tickets.len()));
+        for ticket in tickets {
+            output.info(&format!("  {}", ticket.slug));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_milestone_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().to_str().unwrap();
+        let output = OutputFormatter::new(false, false);
+
+        // The handlers use their own storage, so every check opens a fresh
+        // one instead of reading through a stale cache
+        let storage = || FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        storage().ensure_directories().unwrap();
+        let ticket = Ticket::new("login", "Fix login");
+        storage().save(&ticket).unwrap();
+
+        handle_milestone_create("v1.0", Some("2030-01-31"), None, Some(project), &output).unwrap();
+        assert!(
+            handle_milestone_create("v1.0", None, None, Some(project), &output).is_err(),
+            "duplicate name"
+        );
+        handle_milestone_assign("v1.0", &["login".to_string()], Some(project), &output).unwrap();
+        assert_eq!(
+            storage().load(&ticket.id).unwrap().milestone.as_deref(),
+            Some("v1.0")
+        );
+
+        handle_milestone_edit("v1.0", Some("none"), None, Some(project), &output).unwrap();
+        assert_eq!(storage().load_milestone("v1.0").unwrap().due, None);
+
+        handle_milestone_delete("v1.0", Some(project), &output).unwrap();
+        assert!(storage().load_milestones().unwrap().is_empty());
+        assert_eq!(storage().load(&ticket.id).unwrap().milestone, None);
+    }
+}
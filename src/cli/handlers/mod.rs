@@ -18,49 +18,140 @@
 //! specific commands like `init`, `new`, `list`, etc.
 
 mod archive;
+mod assign;
+mod attach;
 mod check;
+mod ci;
 mod close;
+mod comment;
+mod completions;
 mod config;
+mod context;
+mod current;
+mod doctor;
 mod edit;
 mod export;
+mod gc;
+mod history;
 mod import;
+mod inbox;
+mod incident;
 mod init;
+mod link;
 mod list;
+mod live;
+mod log;
+mod maintain;
+mod matrix;
 #[cfg(feature = "mcp")]
 mod mcp;
+mod milestone;
+mod move_ticket;
 mod new;
+mod notify;
+mod outbox;
+mod plugin;
+mod project;
+mod reopen;
+mod schema;
 mod search;
+mod share;
 mod show;
 mod spec;
+mod spec_comment;
 mod start;
+mod stats;
 mod task;
+mod tasks;
+mod trash;
+mod undo;
+mod watch;
+mod webhook;
+mod workspace;
 mod worktree;
 
 // Re-export handlers
-pub use archive::handle_archive_command;
+pub use archive::{ArchiveFilter, handle_archive_command, handle_bulk_archive_command};
+pub use assign::handle_suggest_assignee;
+pub use attach::{handle_attach_add, handle_attach_list, handle_attach_remove};
 pub use check::handle_check_command;
+pub use ci::handle_ci_report;
 pub use close::handle_close_command;
+pub use comment::{
+    handle_comment_add, handle_comment_delete, handle_comment_edit, handle_comment_list,
+};
+pub use completions::{handle_complete_tickets, handle_completions_command};
 pub use config::handle_config_command;
+pub use context::{build_context_pack, handle_context_pack};
+pub use current::{BranchTicket, branch_ticket, handle_current_command};
+pub use doctor::{handle_doctor_command, handle_doctor_locks};
 pub use edit::handle_edit_command;
-pub use export::handle_export_command;
+pub use export::{
+    CsvExporter, ExportHeader, ExportManifest, ExportMetadata, ExportSort, Exporter, HtmlExporter,
+    JsonExporter, MarkdownExporter, YamlExporter, handle_export_command,
+};
+pub use gc::handle_gc_command;
+pub use history::handle_history_command;
 pub use import::handle_import_command;
+pub use inbox::{handle_inbox_capture, handle_inbox_list, handle_inbox_process};
+pub use incident::{handle_postmortem, handle_timeline_add, handle_timeline_list};
 pub use init::handle_init;
-pub use list::handle_list_command;
+pub use link::{LinkTargets, handle_link_command, handle_relate_command};
+pub use list::{handle_list_command, parse_date_filter};
+pub use live::watch_and_render;
+pub use log::{handle_log_command, handle_standup_report};
+pub use maintain::handle_maintain_command;
+pub use matrix::handle_matrix_command;
 #[cfg(feature = "mcp")]
-pub use mcp::handle_mcp_serve;
-pub use new::handle_new_command;
+pub use mcp::{
+    AuditFilter, handle_mcp_audit, handle_mcp_serve, handle_mcp_status, handle_mcp_stop,
+    handle_mcp_token_create, handle_mcp_token_list, handle_mcp_token_revoke,
+};
+pub use milestone::{
+    handle_milestone_assign, handle_milestone_create, handle_milestone_delete,
+    handle_milestone_edit, handle_milestone_list, handle_milestone_show, handle_milestone_unassign,
+};
+pub use move_ticket::handle_move_command;
+pub use new::{NewTicketInput, handle_new_command, handle_new_from_json};
+pub use notify::handle_notify_test;
+pub use outbox::{
+    OutboxRetry, begin_outbox_retry, handle_outbox_flush, handle_outbox_list, handle_outbox_log,
+};
+pub use plugin::handle_plugin_command;
+pub use project::{handle_project_relocate, handle_project_rename};
+pub use reopen::handle_reopen_command;
+pub use schema::handle_schema_dump;
 pub use search::handle_search_command;
+pub use share::{ShareTargets, handle_share_command};
 pub use show::handle_show_command;
 pub use spec::{
-    handle_spec_activate, handle_spec_approve, handle_spec_delete, handle_spec_design,
-    handle_spec_init, handle_spec_list, handle_spec_requirements, handle_spec_show,
-    handle_spec_status, handle_spec_tasks,
+    handle_spec_activate, handle_spec_approve, handle_spec_check_refs, handle_spec_delete,
+    handle_spec_design, handle_spec_diff, handle_spec_estimate, handle_spec_history,
+    handle_spec_init, handle_spec_link, handle_spec_lint, handle_spec_list,
+    handle_spec_requirements, handle_spec_set, handle_spec_show, handle_spec_status,
+    handle_spec_tasks, handle_spec_template_list,
+};
+pub use spec_comment::{
+    handle_spec_comment_add, handle_spec_comment_list, handle_spec_comment_resolve,
 };
 pub use start::handle_start_command;
+pub use stats::handle_stats_command;
 pub use task::{
     handle_task_add, handle_task_complete, handle_task_list, handle_task_remove,
     handle_task_uncomplete,
 };
+pub use tasks::handle_tasks_command;
+pub use trash::{
+    handle_delete_command, handle_trash_empty, handle_trash_list, handle_trash_restore,
+};
+pub use undo::handle_undo_command;
+pub use watch::{dispatch_notifications, handle_watch_command};
+pub use webhook::{
+    handle_webhook_add, handle_webhook_list, handle_webhook_remove, handle_webhook_test,
+};
+pub use workspace::{
+    handle_workspace_add, handle_workspace_list, handle_workspace_remove, handle_workspace_switch,
+};
 pub use worktree::{handle_worktree_list, handle_worktree_prune, handle_worktree_remove};
 
 use crate::cli::output::OutputFormatter;
@@ -123,7 +214,8 @@ pub fn get_active_ticket() -> Result<String> {
 /// Helper function to resolve a ticket identifier
 ///
 /// Takes a ticket ID or slug and returns the actual ticket ID.
-/// If None is provided, returns the active ticket ID.
+/// If None is provided, returns the active ticket ID; `@branch` refers to the
+/// ticket of the current git branch.
 ///
 /// # Arguments
 ///
@@ -142,6 +234,13 @@ pub fn resolve_ticket_id(ticket_ref: Option<String>) -> Result<String> {
             ensure_project_initialized()?;
             let storage = FileStorage::new(".vibe-ticket");
 
+            if ref_str == crate::core::branch::BRANCH_REF {
+                return Ok(branch_ticket(&storage, std::path::Path::new("."))?
+                    .ticket
+                    .id
+                    .to_string());
+            }
+
             // First try to parse as ticket ID
             if let Ok(ticket_id) = TicketId::parse_str(&ref_str) {
                 // Try to load the ticket to verify it exists
@@ -182,17 +281,57 @@ pub fn parse_tags(tags_str: Option<String>) -> Vec<String> {
         .collect()
 }
 
-/// Resolve a ticket reference (ID, partial ID, or slug) to a ticket ID
+/// Parses a query expression (see [`crate::core::Query`])
+///
+/// `assignee:me` in the query refers to the current user of the project at
+/// `project_root` (see [`crate::config::Identity`]).
+///
+/// # Errors
+///
+/// Returns an error if the query is invalid
+pub fn parse_query(project_root: &std::path::Path, source: &str) -> Result<crate::core::Query> {
+    Ok(with_current_user(project_root, source.parse()?))
+}
+
+/// Loads a saved filter from the project configuration
+///
+/// `assignee:me` in the filter refers to the current user.
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be loaded, no filter has
+/// that name, or its query is invalid
+pub fn load_named_filter(project_root: &std::path::Path, name: &str) -> Result<crate::core::Query> {
+    let query = crate::config::Config::load_for_project(project_root)?.filter(name)?;
+    Ok(with_current_user(project_root, query))
+}
+
+/// Binds `assignee:me` in a query to the current user
+fn with_current_user(
+    project_root: &std::path::Path,
+    query: crate::core::Query,
+) -> crate::core::Query {
+    if query.uses_current_user() {
+        query.with_current_user(crate::config::Identity::for_project(project_root).name)
+    } else {
+        query
+    }
+}
+
+/// Resolve a ticket reference (ID, short ID, partial ID, or slug) to a ticket ID
 ///
 /// This function attempts to find a ticket by:
 /// 1. Full UUID match
-/// 2. Exact slug match
-/// 3. Partial UUID match (prefix)
+/// 2. Exact short ID match
+/// 3. Exact slug match
+/// 4. Partial UUID match (prefix)
+///
+/// `@branch` refers to the ticket of the current git branch.
 ///
 /// # Arguments
 ///
 /// * `storage` - The storage instance to use
-/// * `ticket_ref` - The ticket reference (UUID, partial UUID, or slug)
+/// * `ticket_ref` - The ticket reference (UUID, short ID, partial UUID, or slug)
 ///
 /// # Errors
 ///
@@ -202,11 +341,31 @@ pub fn parse_tags(tags_str: Option<String>) -> Vec<String> {
 pub fn resolve_ticket_ref(
     storage: &crate::storage::FileStorage,
     ticket_ref: &str,
+) -> Result<crate::core::TicketId> {
+    resolve_ticket_ref_in(storage, ticket_ref, std::path::Path::new("."))
+}
+
+/// Resolve a ticket reference like [`resolve_ticket_ref`], taking `@branch`
+/// to be the branch checked out in `dir`
+///
+/// Used by the MCP server, whose sessions have their own working directory.
+///
+/// # Errors
+///
+/// Returns an error as [`resolve_ticket_ref`] does
+pub fn resolve_ticket_ref_in(
+    storage: &crate::storage::FileStorage,
+    ticket_ref: &str,
+    dir: &std::path::Path,
 ) -> Result<crate::core::TicketId> {
     use crate::core::TicketId;
     use crate::error::VibeTicketError;
     use crate::storage::TicketRepository;
 
+    if ticket_ref == crate::core::branch::BRANCH_REF {
+        return Ok(branch_ticket(storage, dir)?.ticket.id);
+    }
+
     // First try to parse as full ticket ID
     if let Ok(ticket_id) = TicketId::parse_str(ticket_ref) {
         // Verify the ticket exists
@@ -215,10 +374,16 @@ pub fn resolve_ticket_ref(
         }
     }
 
-    // Try to find by partial ID or slug
-    let all_tickets = storage.load_all()?;
-    let mut matches = Vec::new();
+    // Try to find by short ID, slug or partial ID, from the listing index
+    let all_tickets = storage.summaries()?;
+    if let Some(ticket) = all_tickets
+        .iter()
+        .find(|ticket| ticket.short_id.as_deref() == Some(ticket_ref))
+    {
+        return Ok(ticket.id.clone());
+    }
 
+    let mut matches = Vec::new();
     for ticket in all_tickets {
         // Check if it matches the slug exactly
         if ticket.slug == ticket_ref {
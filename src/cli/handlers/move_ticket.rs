@@ -0,0 +1,369 @@
+//! Handler for the `move` command
+//!
+//! This module implements moving a ticket to another vibe-ticket project.
+//! The ticket is copied into the target project together with its tasks,
+//! work log, attachments and audit log, and the source copy is closed as a tombstone
+//! that forwards to the new location.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::config::WorkspaceRegistry;
+use crate::core::{Status, Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::platform;
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+use chrono::Utc;
+
+/// Metadata key on the moved ticket pointing back to its origin
+pub const MOVED_FROM_KEY: &str = "moved_from";
+
+/// Metadata key on the source tombstone pointing to the new location
+pub const MOVED_TO_KEY: &str = "moved_to";
+
+/// Handler for the `move` command
+///
+/// Moves a ticket to another project.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug in the current project
+/// * `to` - Path of the target project or name of a registered project
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if either project is not initialized, `to` is neither a
+/// directory nor a registered project, the ticket is not found, or the target project already has a ticket with the same ID or slug.
+/// A failure part-way through rolls back the changes made to the target.
+pub fn handle_move_command(
+    ticket_ref: &str,
+    to: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure both projects are initialized
+    let source_root = find_project_root(project_dir)?;
+    let target_root = target_project_root(to)?;
+
+    let source_root = source_root
+        .canonicalize()
+        .map_or(source_root, platform::strip_verbatim);
+    let target_root = target_root
+        .canonicalize()
+        .map_or(target_root, platform::strip_verbatim);
+    if source_root == target_root {
+        return Err(VibeTicketError::custom(
+            "Source and target projects are the same",
+        ));
+    }
+
+    // Initialize storage
+    // The tombstone closes the ticket whatever the close requirements
+    let source = FileStorage::new(source_root.join(".vibe-ticket")).with_forced_close(true);
+    let target = FileStorage::new(target_root.join(".vibe-ticket"));
+
+    let ticket_id = resolve_ticket_ref(&source, ticket_ref)?;
+    let moved = move_ticket(&source, &target, &ticket_id, &source_root, &target_root)?;
+
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": moved.id.to_string(),
+            "ticket_slug": moved.slug,
+            "from": source_root,
+            "to": target_root,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Moved ticket '{}' to {}",
+            moved.slug,
+            target_root.display()
+        ));
+        output.info("The source ticket was closed with a forwarding reference.");
+    }
+
+    Ok(())
+}
+
+/// Root of the project `to` names: a directory in it, or else the name of
+/// a project registered with `workspace add`
+fn target_project_root(to: &str) -> Result<PathBuf> {
+    if Path::new(to).is_dir() {
+        return find_project_root(Some(to));
+    }
+    let registry = WorkspaceRegistry::load()?;
+    let Some(project) = registry.get(to) else {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Unknown project '{to}': it is neither a directory nor a registered project (see `vibe-ticket workspace list`)"
+        )));
+    };
+    if !project.path.join(".vibe-ticket").is_dir() {
+        return Err(VibeTicketError::custom(format!(
+            "Registered project '{to}' no longer exists at {}",
+            project.path.display()
+        )));
+    }
+    Ok(project.path.clone())
+}
+
+/// Move a ticket from `source` to `target`
+///
+/// Copies attachments, imports the ticket and its audit log into the target
+/// and finally closes the source ticket as a tombstone. If a later step
+/// fails, the earlier steps are undone without leaving traces in the target,
+/// so that the ticket lives in exactly one project.
+fn move_ticket(
+    source: &FileStorage,
+    target: &FileStorage,
+    id: &TicketId,
+    source_root: &Path,
+    target_root: &Path,
+) -> Result<Ticket> {
+    let ticket = source.load(id)?;
+
+    if target.exists(id)? {
+        return Err(VibeTicketError::custom(format!(
+            "Target project already has a ticket with ID {id}"
+        )));
+    }
+    if target.ticket_exists_with_slug(&ticket.slug)? {
+        return Err(VibeTicketError::DuplicateTicket { slug: ticket.slug });
+    }
+
+    // References to tickets the target does not have cannot be kept
+    let index = target.ticket_index()?;
+    let mut moved = ticket.clone();
+    moved.parent = moved.parent.filter(|parent| index.contains(parent));
+    moved.depends_on.retain(|id| index.contains(id));
+    moved.blocks.retain(|id| index.contains(id));
+    moved
+        .relations
+        .retain(|relation| index.contains(&relation.ticket));
+    moved.metadata.insert(
+        MOVED_FROM_KEY.to_string(),
+        serde_json::json!({
+            "project": source_root,
+            "moved_at": Utc::now(),
+        }),
+    );
+
+    // Step 1: copy attachments
+    let source_attachments = source.attachments_dir(id);
+    let target_attachments = target.attachments_dir(id);
+    if source_attachments.exists() {
+        if let Err(e) = copy_dir(&source_attachments, &target_attachments) {
+            let _ = fs::remove_dir_all(&target_attachments);
+            return Err(e);
+        }
+    }
+
+    // Step 2: import into the target with the audit log, and tombstone the
+    // source before the target ticket is unlocked
+    let mut tombstone = ticket;
+    if tombstone.status != Status::Done {
+        tombstone.status = Status::Done;
+        tombstone.closed_at = Some(Utc::now());
+    }
+    tombstone.metadata.insert(
+        MOVED_TO_KEY.to_string(),
+        serde_json::json!({ "project": target_root }),
+    );
+    tombstone.metadata.insert(
+        crate::core::closing::CLOSE_MESSAGE_KEY.to_string(),
+        serde_json::json!(format!("Moved to {}", target_root.display())),
+    );
+
+    let history = source.load_history(id)?;
+    let moved = match target.import_ticket(&moved, &history, || source.save(&tombstone)) {
+        Ok(moved) => moved,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&target_attachments);
+            return Err(e);
+        },
+    };
+
+    if source.get_active()?.as_ref() == Some(id) {
+        source.clear_active()?;
+    }
+
+    Ok(moved)
+}
+
+/// Recursively copy a directory
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))? {
+        let path = entry.context("Failed to read directory entry")?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let dest = to.join(name);
+
+        if path.is_dir() {
+            copy_dir(&path, &dest)?;
+        } else {
+            fs::copy(&path, &dest).with_context(|| format!("Failed to copy {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_storage(dir: &TempDir) -> FileStorage {
+        let storage = FileStorage::new(dir.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_move_ticket() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+        let source = create_storage(&source_dir);
+        let target = create_storage(&target_dir);
+
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.start();
+        ticket.add_task("Reproduce");
+        ticket.log_work("suspect session cookie");
+        source.save(&ticket).unwrap();
+        source.set_active(&ticket.id).unwrap();
+
+        let attachments = source.attachments_dir(&ticket.id);
+        fs::create_dir_all(&attachments).unwrap();
+        fs::write(attachments.join("trace.log"), "stack trace").unwrap();
+
+        let moved = move_ticket(
+            &source,
+            &target,
+            &ticket.id,
+            source_dir.path(),
+            target_dir.path(),
+        )
+        .unwrap();
+        assert!(moved.metadata.contains_key(MOVED_FROM_KEY));
+
+        let imported = target.load(&ticket.id).unwrap();
+        assert_eq!(imported.status, Status::Doing);
+        assert_eq!(imported.tasks.len(), 1);
+        assert_eq!(imported.work_log.len(), 1);
+        assert_eq!(
+            fs::read_to_string(target.attachments_dir(&ticket.id).join("trace.log")).unwrap(),
+            "stack trace"
+        );
+
+        let tombstone = source.load(&ticket.id).unwrap();
+        assert_eq!(tombstone.status, Status::Done);
+        assert!(tombstone.metadata.contains_key(MOVED_TO_KEY));
+        assert!(source.get_active().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_move_ticket_keeps_history() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+        let source = create_storage(&source_dir);
+        let target = create_storage(&target_dir);
+
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        source.save(&ticket).unwrap();
+        ticket.start();
+        source.save(&ticket).unwrap();
+        let history = source.load_history(&ticket.id).unwrap();
+
+        move_ticket(
+            &source,
+            &target,
+            &ticket.id,
+            source_dir.path(),
+            target_dir.path(),
+        )
+        .unwrap();
+
+        assert_eq!(target.load_history(&ticket.id).unwrap(), history);
+        assert!(target.load_journal().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_move_ticket_rolls_back_without_traces() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+        // A closed ticket cannot be tombstoned in a project where closed
+        // tickets are read-only
+        let source = create_storage(&source_dir).with_immutable_closed(true);
+        let target = create_storage(&target_dir);
+
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.close();
+        source.save(&ticket).unwrap();
+        let attachments = source.attachments_dir(&ticket.id);
+        fs::create_dir_all(&attachments).unwrap();
+        fs::write(attachments.join("trace.log"), "stack trace").unwrap();
+
+        let result = move_ticket(
+            &source,
+            &target,
+            &ticket.id,
+            source_dir.path(),
+            target_dir.path(),
+        );
+        assert!(result.is_err());
+
+        assert!(!target.exists(&ticket.id).unwrap());
+        assert!(target.load_history(&ticket.id).unwrap().is_empty());
+        assert!(target.load_journal().unwrap().is_empty());
+        assert!(!target.attachments_dir(&ticket.id).exists());
+        assert!(
+            source
+                .load(&ticket.id)
+                .unwrap()
+                .metadata
+                .get(MOVED_TO_KEY)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_target_project_root() {
+        let target_dir = TempDir::new().unwrap();
+        create_storage(&target_dir);
+        let target = target_dir.path().to_str().unwrap();
+        assert_eq!(target_project_root(target).unwrap(), target_dir.path());
+
+        let error = target_project_root("no-such-project-registered").unwrap_err();
+        assert!(matches!(error, VibeTicketError::InvalidInput(_)));
+        assert!(error.to_string().contains("no-such-project-registered"));
+    }
+
+    #[test]
+    fn test_move_ticket_rejects_slug_conflict() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+        let source = create_storage(&source_dir);
+        let target = create_storage(&target_dir);
+
+        let ticket = Ticket::new("fix-login", "Fix login");
+        source.save(&ticket).unwrap();
+        target.save(&Ticket::new("fix-login", "Other")).unwrap();
+
+        let result = move_ticket(
+            &source,
+            &target,
+            &ticket.id,
+            source_dir.path(),
+            target_dir.path(),
+        );
+        assert!(result.is_err());
+        assert_eq!(source.load(&ticket.id).unwrap().status, Status::Todo);
+    }
+}
@@ -1,19 +1,149 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Deserialize;
+
 use crate::cli::{OutputFormatter, find_project_root, validate_slug};
-use crate::core::{Priority, Ticket};
-use crate::error::{Result, VibeTicketError};
+use crate::config::Config;
+use crate::core::fields::{field_text, set_fields};
+use crate::core::{Priority, Severity, Ticket, TicketTemplate, assignment};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::integration::issues::{IssueRef, ORIGIN_KEY, RemoteIssue};
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 
-use super::parse_tags;
+use super::{parse_tags, resolve_ticket_ref};
+
+/// Fields of a ticket to create
+///
+/// Built from the flags of `new`, or read as JSON by `new --from-json`.
+/// Unknown keys are rejected, so that a misspelled key fails instead of
+/// being dropped; `fields` maps field names to their values.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewTicketInput {
+    /// Slug without the timestamp prefix
+    pub slug: Option<String>,
+
+    /// Issue or pull request URL to prefill the ticket from
+    pub from_url: Option<String>,
+
+    /// Title; defaults to the issue's or one derived from the slug
+    pub title: Option<String>,
+
+    /// Description
+    pub description: Option<String>,
+
+    /// Priority (low, medium, high, critical); defaults to medium
+    pub priority: Option<String>,
+
+    /// Incident severity (S1, S2, S3, S4)
+    pub severity: Option<String>,
+
+    /// Ticket template (incident, bug)
+    pub template: Option<String>,
+
+    /// Tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Parent ticket (epic)
+    pub parent: Option<String>,
+
+    /// Assignee
+    pub assignee: Option<String>,
+
+    /// User-defined fields as `name=value` assignments
+    #[serde(default, deserialize_with = "field_assignments")]
+    pub fields: Vec<String>,
+
+    /// Titles of the tasks to add
+    #[serde(default)]
+    pub tasks: Vec<String>,
+}
 
 /// Handler for the `new` command
+///
+/// With `from_url`, the issue or pull request behind the URL is fetched and
+/// its title, description and labels fill in whatever is not given; the slug
+/// defaults to the issue number and title. A description of `-` is read from
+/// stdin.
 #[allow(clippy::too_many_arguments)]
 pub fn handle_new_command(
-    slug: &str,
+    slug: Option<&str>,
+    from_url: Option<&str>,
     title: Option<String>,
     description: Option<String>,
     priority: &str,
+    severity: Option<&str>,
+    template: Option<&str>,
     tags: Option<String>,
+    parent: Option<&str>,
     start: bool,
+    auto_assign: bool,
+    fields: &[String],
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let description = match description {
+        Some(description) if description == "-" => Some(read_stdin("description")?),
+        description => description,
+    };
+    let input = NewTicketInput {
+        slug: slug.map(ToString::to_string),
+        from_url: from_url.map(ToString::to_string),
+        title,
+        description,
+        priority: Some(priority.to_string()),
+        severity: severity.map(ToString::to_string),
+        template: template.map(ToString::to_string),
+        tags: parse_tags(tags),
+        parent: parent.map(ToString::to_string),
+        assignee: None,
+        fields: fields.to_vec(),
+        tasks: Vec::new(),
+    };
+    create_ticket(input, start, auto_assign, project_dir, output)
+}
+
+/// Handler for `new --from-json`
+///
+/// Reads a [`NewTicketInput`] as JSON from the file `source`, or from stdin
+/// if `source` is `-`, and creates the ticket. A `slug` given on the command
+/// line replaces the one in the JSON.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or is not a valid ticket,
+/// or if the ticket cannot be created.
+pub fn handle_new_from_json(
+    source: &str,
+    slug: Option<&str>,
+    start: bool,
+    auto_assign: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let json = if source == "-" {
+        read_stdin("ticket JSON")?
+    } else {
+        std::fs::read_to_string(source)
+            .map_err(|e| VibeTicketError::io_error("read", Path::new(source), e))?
+    };
+    let mut input: NewTicketInput = serde_json::from_str(&json)
+        .map_err(|e| VibeTicketError::InvalidInput(format!("Invalid ticket JSON: {e}")))?;
+    if let Some(slug) = slug {
+        input.slug = Some(slug.to_string());
+    }
+    create_ticket(input, start, auto_assign, project_dir, output)
+}
+
+/// Creates and saves the ticket described by `input`
+fn create_ticket(
+    input: NewTicketInput,
+    start: bool,
+    auto_assign: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -23,13 +153,52 @@ pub fn handle_new_command(
 
     // Initialize storage
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut ticket = save_new_ticket(input, auto_assign, &project_root, &storage, output)?;
 
+    // If --start flag is provided, start working on the ticket immediately
+    if start {
+        let old_status = ticket.status;
+        ticket.start();
+        storage.save(&ticket)?;
+        emit_cli_event(
+            &project_root,
+            &TicketEvent::StatusChanged(ticket.id.clone(), old_status, ticket.status),
+        );
+        storage.set_active(&ticket.id)?;
+        print_started(&ticket, output)
+    } else {
+        print_created(&ticket, output)
+    }
+}
+
+/// Builds the ticket described by `input` and saves it in `storage`
+///
+/// Shared by `new` and `in process`, which turns inbox entries into tickets.
+pub(super) fn save_new_ticket(
+    input: NewTicketInput,
+    auto_assign: bool,
+    project_root: &Path,
+    storage: &FileStorage,
+    output: &OutputFormatter,
+) -> Result<Ticket> {
     // Generate timestamp prefix for the slug
     let now = chrono::Local::now();
     let timestamp_prefix = now.format("%Y%m%d%H%M").to_string();
 
+    // Fetch the issue to prefill the ticket from
+    let origin = input.from_url.as_deref().map(fetch_issue).transpose()?;
+
     // Validate and normalize the slug
-    let base_slug = slug.trim();
+    let base_slug = match (input.slug, &origin) {
+        (Some(slug), _) => slug.trim().to_string(),
+        (None, Some((issue_ref, issue))) => issue.slug(issue_ref.number),
+        (None, None) => {
+            return Err(VibeTicketError::InvalidInput(
+                "A slug is required unless --from-url is given".to_string(),
+            ));
+        },
+    };
+    let base_slug = base_slug.as_str();
     validate_slug(base_slug)?;
 
     // Combine timestamp and slug
@@ -41,88 +210,208 @@ pub fn handle_new_command(
     }
 
     // Parse priority
+    let priority = input.priority.as_deref().unwrap_or("medium");
     let priority = Priority::try_from(priority).map_err(|_| VibeTicketError::InvalidPriority {
         priority: priority.to_string(),
     })?;
 
+    // Parse severity
+    let severity = input
+        .severity
+        .as_deref()
+        .map(|s| Severity::try_from(s).map_err(VibeTicketError::InvalidInput))
+        .transpose()?;
+
+    // Resolve the ticket template and check its required fields
+    let template = input
+        .template
+        .as_deref()
+        .map(|name| resolve_template(name, severity))
+        .transpose()?;
+
+    // Resolve the epic the ticket belongs to
+    let parent = input
+        .parent
+        .as_deref()
+        .map(|r| resolve_ticket_ref(storage, r))
+        .transpose()?;
+
     // Parse tags
-    let tags = tags.map(|t| parse_tags(Some(t))).unwrap_or_default();
-
-    // Create title from base slug if not provided
-    let title = title.unwrap_or_else(|| {
-        base_slug
-            .split('-')
-            .map(|word| {
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(" ")
-    });
+    let mut tags = input.tags;
+    let extra_tags = template
+        .iter()
+        .flat_map(|template| template.tags.iter().map(ToString::to_string))
+        .chain(origin.iter().flat_map(|(_, issue)| issue.tags()));
+    for tag in extra_tags {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    // Take the title from the issue or the base slug if not provided
+    let title = input
+        .title
+        .or_else(|| origin.as_ref().map(|(_, issue)| issue.title.clone()))
+        .unwrap_or_else(|| title_from_slug(base_slug));
 
     // Create the ticket
     let mut ticket = Ticket::new(&slug, &title);
-    ticket.description = description.unwrap_or_default();
+    ticket.description = input
+        .description
+        .or_else(|| origin.as_ref().map(|(_, issue)| issue.body.clone()))
+        .or_else(|| template.map(|t| t.description.to_string()))
+        .unwrap_or_default();
     ticket.priority = priority;
+    ticket.severity = severity;
     ticket.tags = tags;
+    ticket.parent = parent;
+    ticket.assignee = input.assignee;
+    for task in input.tasks {
+        ticket.add_task(task);
+    }
+    if let Some((issue_ref, issue)) = &origin {
+        ticket
+            .metadata
+            .insert(ORIGIN_KEY.to_string(), issue_ref.origin(issue));
+    }
+
+    // Move oversized descriptions (e.g. pasted logs) into an attachment
+    let config = Config::load_for_project(project_root)?;
+    set_fields(&mut ticket, &config.fields, &input.fields)?;
+    if storage.spill_description(&mut ticket, config.project.description_size_limit)? {
+        output.info("Description exceeds the size limit; full text stored as an attachment");
+    }
 
-    // Save the ticket
+    // Pick an assignee based on team capacity
+    if auto_assign {
+        match assignment::suggest_assignee(&ticket, &storage.load_all()?, &config.team) {
+            Some(candidate) => ticket.assignee = Some(candidate.name),
+            None => output.warning("No qualified team member has capacity; left unassigned"),
+        }
+    }
+
+    // Save the ticket, with its short ID assigned up front for the output
+    storage.assign_short_id(&mut ticket)?;
     storage.save(&ticket)?;
+    emit_cli_event(project_root, &TicketEvent::Created(ticket.clone()));
+    Ok(ticket)
+}
 
-    // If --start flag is provided, start working on the ticket immediately
-    if start {
-        ticket.start();
-        storage.save(&ticket)?;
-        storage.set_active(&ticket.id)?;
+/// Reads all of stdin as the `what` of a ticket, without trailing whitespace
+fn read_stdin(what: &str) -> Result<String> {
+    let mut content = String::new();
+    std::io::stdin()
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to read the {what} from stdin"))?;
+    Ok(content.trim_end().to_string())
+}
 
-        if output.is_json() {
-            output.print_json(&serde_json::json!({
-                "success": true,
-                "message": "Created and started ticket",
-                "ticket": ticket,
-            }))?;
-        } else {
-            output.success(&format!(
-                "Created ticket '{}' (ID: {})",
-                ticket.slug,
-                ticket.id.short()
-            ));
-            output.info(&format!("Started working on ticket '{}'", ticket.slug));
+/// Reads the `fields` object of a ticket JSON as `name=value` assignments
+fn field_assignments<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let fields = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    Ok(fields
+        .into_iter()
+        .map(|(name, value)| format!("{name}={}", field_text(&value)))
+        .collect())
+}
 
-            // TODO: Create Git branch when Git integration is implemented
-            output.info("Note: Git branch creation will be available in future version");
-        }
-    } else if output.is_json() {
-        output.print_json(&serde_json::json!({
+/// Print a ticket that was created and started
+fn print_started(ticket: &Ticket, output: &OutputFormatter) -> Result<()> {
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "success": true,
+            "message": "Created and started ticket",
+            "ticket": ticket,
+        }));
+    }
+
+    output.success(&format!(
+        "Created ticket '{}' (ID: {})",
+        ticket.slug,
+        ticket.display_id()
+    ));
+    output.info(&format!("Started working on ticket '{}'", ticket.slug));
+
+    // TODO: Create Git branch when Git integration is implemented
+    output.info("Note: Git branch creation will be available in future version");
+    Ok(())
+}
+
+/// Print the details of a ticket that was created without starting it
+fn print_created(ticket: &Ticket, output: &OutputFormatter) -> Result<()> {
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
             "success": true,
             "message": "Created ticket",
             "ticket": ticket,
-        }))?;
-    } else {
-        output.success(&format!(
-            "Created ticket '{}' (ID: {})",
-            ticket.slug,
-            ticket.id.short()
-        ));
-        output.info(&format!("Title: {}", ticket.title));
-        output.info(&format!("Priority: {}", ticket.priority));
-        if !ticket.tags.is_empty() {
-            output.info(&format!("Tags: {}", ticket.tags.join(", ")));
-        }
-        output.info("");
-        output.info("To start working on this ticket:");
-        output.info(&format!("  vibe-ticket start {}", ticket.slug));
+        }));
     }
 
+    output.success(&format!(
+        "Created ticket '{}' (ID: {})",
+        ticket.slug,
+        ticket.display_id()
+    ));
+    output.info(&format!("Title: {}", ticket.title));
+    output.info(&format!("Priority: {}", ticket.priority));
+    if let Some(assignee) = &ticket.assignee {
+        output.info(&format!("Assignee: {assignee}"));
+    }
+    if let Some(severity) = ticket.severity {
+        output.info(&format!("Severity: {severity}"));
+    }
+    if !ticket.tags.is_empty() {
+        output.info(&format!("Tags: {}", ticket.tags.join(", ")));
+    }
+    output.info("");
+    output.info("To start working on this ticket:");
+    output.info(&format!("  vibe-ticket start {}", ticket.slug));
+
     Ok(())
 }
 
+/// Fetches the issue or pull request behind `url`
+fn fetch_issue(url: &str) -> Result<(IssueRef, RemoteIssue)> {
+    let issue_ref = IssueRef::parse(url)?;
+    let issue = issue_ref.fetch()?;
+    Ok((issue_ref, issue))
+}
+
+/// Derives a title from a slug, e.g. `fix-login-bug` becomes `Fix Login Bug`
+fn title_from_slug(slug: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Looks up a ticket template and validates the fields it requires
+fn resolve_template(name: &str, severity: Option<Severity>) -> Result<&'static TicketTemplate> {
+    let template = TicketTemplate::find(name).ok_or_else(|| {
+        VibeTicketError::InvalidInput(format!(
+            "Unknown template '{name}'. Available templates: {}",
+            TicketTemplate::names().join(", ")
+        ))
+    })?;
+    template
+        .validate_severity(severity)
+        .map_err(VibeTicketError::InvalidInput)?;
+    Ok(template)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::fields::FieldDefinition;
     use tempfile::TempDir;
 
     #[test]
@@ -149,12 +438,18 @@ mod tests {
 
         // Test creating a ticket
         let result = handle_new_command(
-            "fix-login-bug",
+            Some("fix-login-bug"),
+            None,
             None,
             Some("Users cannot login".to_string()),
             "high",
+            None,
+            None,
             Some("bug,auth".to_string()),
+            None,
+            false,
             false,
+            &[],
             Some(temp_dir.path().to_str().unwrap()),
             &output,
         );
@@ -178,4 +473,180 @@ mod tests {
         assert_eq!(ticket.priority, Priority::High);
         assert_eq!(ticket.tags, vec!["bug", "auth"]);
     }
+
+    #[test]
+    fn test_incident_template_requires_severity() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        let output = OutputFormatter::new(false, false);
+        let project = Some(temp_dir.path().to_str().unwrap());
+
+        let result = handle_new_command(
+            Some("db-outage"),
+            None,
+            None,
+            None,
+            "high",
+            None,
+            Some("incident"),
+            None,
+            None,
+            false,
+            false,
+            &[],
+            project,
+            &output,
+        );
+        assert!(result.is_err());
+
+        handle_new_command(
+            Some("db-outage"),
+            None,
+            None,
+            None,
+            "high",
+            Some("S1"),
+            Some("incident"),
+            None,
+            None,
+            false,
+            false,
+            &[],
+            project,
+            &output,
+        )
+        .unwrap();
+
+        let tickets = storage.load_all().unwrap();
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].severity, Some(Severity::S1));
+        assert!(tickets[0].tags.contains(&"incident".to_string()));
+        assert!(tickets[0].description.contains("## Impact"));
+    }
+
+    #[test]
+    fn test_new_ticket_with_parent() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = || FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        storage().ensure_directories().unwrap();
+        let epic = Ticket::new("epic", "Epic");
+        storage().save(&epic).unwrap();
+        let output = OutputFormatter::new(false, false);
+
+        handle_new_command(
+            Some("child"),
+            None,
+            None,
+            None,
+            "medium",
+            None,
+            None,
+            None,
+            Some("epic"),
+            false,
+            false,
+            &[],
+            Some(temp_dir.path().to_str().unwrap()),
+            &output,
+        )
+        .unwrap();
+
+        let tickets = storage().load_all().unwrap();
+        let child = tickets.iter().find(|t| t.slug.ends_with("-child")).unwrap();
+        assert_eq!(child.parent, Some(epic.id));
+    }
+    #[test]
+    fn test_new_ticket_with_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+        let storage = FileStorage::new(&vibe_ticket_dir);
+        storage.ensure_directories().unwrap();
+        let mut config = Config::default();
+        config
+            .fields
+            .insert("estimate".to_string(), FieldDefinition::Number);
+        config
+            .save_to_path(vibe_ticket_dir.join("config.yaml"))
+            .unwrap();
+        let output = OutputFormatter::new(false, false);
+        let project = Some(temp_dir.path().to_str().unwrap());
+        let new = |fields: &[String]| {
+            handle_new_command(
+                Some("sized"),
+                None,
+                None,
+                None,
+                "medium",
+                None,
+                None,
+                None,
+                None,
+                false,
+                false,
+                fields,
+                project,
+                &output,
+            )
+        };
+
+        assert!(new(&["estimate=big".to_string()]).is_err());
+        assert!(new(&["owner=me".to_string()]).is_err());
+        new(&["estimate=5".to_string()]).unwrap();
+
+        let tickets = storage.load_all().unwrap();
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].metadata["estimate"], serde_json::json!(5));
+    }
+
+    #[test]
+    fn test_new_ticket_from_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+        let storage = FileStorage::new(&vibe_ticket_dir);
+        storage.ensure_directories().unwrap();
+        let mut config = Config::default();
+        config
+            .fields
+            .insert("estimate".to_string(), FieldDefinition::Number);
+        config
+            .save_to_path(vibe_ticket_dir.join("config.yaml"))
+            .unwrap();
+        let output = OutputFormatter::new(false, false);
+        let project = Some(temp_dir.path().to_str().unwrap());
+
+        let path = temp_dir.path().join("ticket.json");
+        std::fs::write(
+            &path,
+            r#"{"slug": "ignored", "title": "It's \"quoted\"", "description": "Line 1\n`$HOME` stays", "priority": "high", "tags": ["api"], "assignee": "alice", "fields": {"estimate": 3}, "tasks": ["Write tests"]}"#,
+        )
+        .unwrap();
+        handle_new_from_json(
+            path.to_str().unwrap(),
+            Some("from-json"),
+            false,
+            false,
+            project,
+            &output,
+        )
+        .unwrap();
+
+        let tickets = storage.load_all().unwrap();
+        assert_eq!(tickets.len(), 1);
+        let ticket = &tickets[0];
+        assert!(ticket.slug.ends_with("-from-json"));
+        assert_eq!(ticket.title, "It's \"quoted\"");
+        assert_eq!(ticket.description, "Line 1\n`$HOME` stays");
+        assert_eq!(ticket.priority, Priority::High);
+        assert_eq!(ticket.tags, vec!["api"]);
+        assert_eq!(ticket.assignee.as_deref(), Some("alice"));
+        assert_eq!(ticket.metadata["estimate"], serde_json::json!(3));
+        assert_eq!(ticket.tasks[0].title, "Write tests");
+
+        std::fs::write(&path, r#"{"slug": "typo", "titel": "Typo"}"#).unwrap();
+        let err =
+            handle_new_from_json(path.to_str().unwrap(), None, false, false, project, &output)
+                .unwrap_err();
+        assert!(err.to_string().contains("unknown field `titel`"));
+    }
 }
@@ -0,0 +1,58 @@
+//! Handlers for the `notify` command
+//!
+//! This module implements checking the configuration of the built-in Slack
+//! plugin (see [`crate::plugins::slack`]) by sending it a test message.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::core::Priority;
+use crate::error::{Result, VibeTicketError};
+use crate::integration::outbox::{Outbox, http_deliver};
+use crate::plugins::slack;
+
+/// Handler for the `notify test` subcommand
+///
+/// Posts a test message along the route of `priority`, or the default route.
+/// The attempt is recorded in the delivery log but not queued if it fails.
+///
+/// # Errors
+///
+/// Returns an error if Slack is not configured, the priority is invalid or
+/// the message cannot be posted
+pub fn handle_notify_test(
+    priority: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let priority = priority
+        .map(Priority::try_from)
+        .transpose()
+        .map_err(VibeTicketError::InvalidInput)?;
+    let project_root = find_project_root(project_dir)?;
+    let config = Config::load_for_project(&project_root)?;
+    let slack_config = config.plugins.slack.as_ref().ok_or_else(|| {
+        VibeTicketError::InvalidInput(
+            "Slack is not configured; set plugins.slack.webhook_url".to_string(),
+        )
+    })?;
+
+    let text = format!(
+        ":wave: Test notification from vibe-ticket project *{}*",
+        config.project.name
+    );
+    let route = slack::route(slack_config, priority)?;
+    let entry = slack::entry(slack_config, priority, "test", &text)?;
+    Outbox::new(project_root.join(".vibe-ticket")).attempt(&entry, &http_deliver)?;
+
+    let destination = route.channel.unwrap_or("the webhook's default channel");
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "priority": priority,
+            "channel": route.channel,
+        }))?;
+    } else {
+        output.success(&format!("Sent a test message to {destination}"));
+    }
+    Ok(())
+}
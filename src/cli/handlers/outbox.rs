@@ -0,0 +1,189 @@
+//! Handlers for the `outbox` command
+//!
+//! This module implements listing and flushing integration deliveries that
+//! could not be sent (e.g. while offline) and were queued in
+//! `.vibe-ticket/outbox/`.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::Result;
+use crate::integration::outbox::{Outbox, http_deliver};
+use crate::storage::{EventCursor, FileStorage};
+
+/// Handler for the `outbox list` command
+///
+/// Shows every queued delivery with its target, attempt count and last error.
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the outbox cannot be read
+pub fn handle_outbox_list(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let outbox = Outbox::new(project_root.join(".vibe-ticket"));
+    let entries = outbox.pending()?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "entries": entries,
+            "count": entries.len(),
+        }))?;
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        output.info("Outbox is empty");
+        return Ok(());
+    }
+
+    output.info(&format!("{} queued deliveries:", entries.len()));
+    for entry in &entries {
+        output.info(&format!(
+            "  {} {} → {} (attempts: {}, queued: {})",
+            &entry.id[..8],
+            entry.event,
+            entry.target,
+            entry.attempts,
+            output.dates().format(entry.created_at)
+        ));
+        if let Some(error) = &entry.last_error {
+            output.info(&format!("    last error: {error}"));
+        }
+        if let Some(at) = entry.next_attempt_at {
+            output.info(&format!("    next retry: {}", output.dates().format(at)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `outbox flush` command
+///
+/// Retries every queued delivery. Delivered entries are removed; failed
+/// entries stay in the outbox for the next attempt.
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the outbox cannot be updated
+pub fn handle_outbox_flush(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let outbox = Outbox::new(project_root.join(".vibe-ticket"));
+    let report = outbox.flush(http_deliver)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "delivered": report.delivered,
+            "failed": report.failed,
+        }))?;
+    } else if report.delivered == 0 && report.failed == 0 {
+        output.info("Outbox is empty");
+    } else {
+        output.success(&format!("Delivered {} queued events", report.delivered));
+        if report.failed > 0 {
+            output.warning(&format!(
+                "{} deliveries failed and remain queued",
+                report.failed
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handler for the `outbox log` command
+///
+/// Shows the most recent delivery attempts, successful or not.
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the log cannot be read
+pub fn handle_outbox_log(
+    limit: usize,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let records = Outbox::new(project_root.join(".vibe-ticket")).log(limit)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "deliveries": records,
+            "count": records.len(),
+        }))?;
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        output.info("No deliveries yet");
+        return Ok(());
+    }
+
+    for record in &records {
+        let result = record
+            .error
+            .as_ref()
+            .map_or_else(|| "delivered".to_string(), |e| format!("failed: {e}"));
+        output.info(&format!(
+            "{} {} → {} (attempt {}) {result}",
+            record
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            record.event,
+            record.target,
+            record.attempt
+        ));
+    }
+
+    Ok(())
+}
+
+/// Queued deliveries to retry once a regular command run is done
+///
+/// Created by [`begin_outbox_retry`] before the command runs.
+pub struct OutboxRetry {
+    outbox: Outbox,
+    storage: FileStorage,
+    /// End of the event log when the command started
+    cursor: EventCursor,
+}
+
+/// Prepares retrying queued deliveries after a regular command run
+///
+/// Returns `None` outside of a project or with an empty outbox.
+pub fn begin_outbox_retry(project_dir: Option<&str>) -> Option<OutboxRetry> {
+    let project_root = find_project_root(project_dir).ok()?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let outbox = Outbox::new(&vibe_ticket_dir);
+    if outbox.is_empty() {
+        return None;
+    }
+    let storage = FileStorage::new(vibe_ticket_dir);
+    let cursor = storage.event_cursor().ok()?;
+    Some(OutboxRetry {
+        outbox,
+        storage,
+        cursor,
+    })
+}
+
+impl OutboxRetry {
+    /// Retries the queued deliveries if the command changed tickets
+    ///
+    /// Commands that only read skip the retry, so that unreachable
+    /// endpoints do not slow them down. Only entries whose backoff delay
+    /// has passed are retried. Failures are ignored: entries simply stay
+    /// queued until a later run.
+    pub fn finish(self) {
+        let pid = std::process::id();
+        let wrote = self
+            .storage
+            .poll_events(&self.cursor)
+            .is_ok_and(|poll| poll.events.iter().any(|event| event.pid == pid));
+        if !wrote {
+            return;
+        }
+
+        if let Err(e) = self.outbox.flush_due(http_deliver) {
+            tracing::debug!("Failed to flush outbox: {e}");
+        }
+    }
+}
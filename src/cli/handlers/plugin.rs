@@ -0,0 +1,108 @@
+//! Handler for the `x` command
+//!
+//! This module lists and runs the commands contributed by plugins (see
+//! [`crate::plugins`]).
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::error::{Result, VibeTicketError};
+use crate::plugins::{self, LoadedPlugin};
+
+/// Handler for the `x` command
+///
+/// Without a plugin, lists every plugin and its commands; without a command,
+/// lists the commands of the plugin. Otherwise the arguments are validated
+/// against the manifest and the plugin program is run.
+///
+/// # Arguments
+///
+/// * `plugin` - Optional plugin name
+/// * `args` - Command of the plugin followed by its raw arguments
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, a manifest is invalid,
+/// the plugin or command does not exist, the arguments do not match the
+/// manifest, or the plugin program fails.
+pub fn handle_plugin_command(
+    plugin: Option<&str>,
+    args: &[String],
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Ensure project is initialized
+    let project_root = find_project_root(project_dir)?;
+    let config = Config::load_for_project(&project_root)?;
+    let installed = plugins::discover_all(
+        &plugins::search_dirs(&project_root, &config.plugins),
+        &config.plugins.enabled,
+    )?;
+
+    let Some(name) = plugin else {
+        return list_plugins(&installed, output);
+    };
+    let loaded = installed
+        .iter()
+        .find(|p| p.manifest.name == name)
+        .ok_or_else(|| {
+            VibeTicketError::InvalidInput(format!(
+                "Unknown plugin '{name}'. Run `vibe-ticket x` to list installed plugins"
+            ))
+        })?;
+
+    let Some((command_name, args)) = args.split_first() else {
+        return list_plugins(std::slice::from_ref(loaded), output);
+    };
+    let spec = loaded.manifest.command(command_name).ok_or_else(|| {
+        VibeTicketError::InvalidInput(format!(
+            "Plugin '{name}' has no command '{command_name}'. Run `vibe-ticket x {name}` to list its commands"
+        ))
+    })?;
+
+    let values = spec.parse_args(args)?;
+    let status = spec.execute(&loaded.dir, &project_root, &values)?;
+    if !status.success() {
+        return Err(VibeTicketError::custom(format!(
+            "Plugin command '{name} {command_name}' failed ({status})"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Prints plugins and their commands
+fn list_plugins(installed: &[LoadedPlugin], output: &OutputFormatter) -> Result<()> {
+    if output.is_json() {
+        let manifests: Vec<_> = installed.iter().map(|p| &p.manifest).collect();
+        output.print_json(&serde_json::json!({ "plugins": manifests }))?;
+        return Ok(());
+    }
+
+    if installed.is_empty() {
+        output.info("No plugins installed");
+        return Ok(());
+    }
+
+    for plugin in installed {
+        let manifest = &plugin.manifest;
+        output.info(&format!(
+            "{} {}{}",
+            manifest.name,
+            manifest.version,
+            manifest
+                .description
+                .as_ref()
+                .map_or_else(String::new, |d| format!(" - {d}"))
+        ));
+        for command in &manifest.commands {
+            output.info(&format!("  {}", command.usage()));
+            if let Some(description) = &command.description {
+                output.info(&format!("      {description}"));
+            }
+        }
+    }
+
+    Ok(())
+}
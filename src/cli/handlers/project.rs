@@ -0,0 +1,270 @@
+//! Handlers for the `project` command and its subcommands
+//!
+//! `project rename` changes `project.name`, from which the directory names
+//! of ticket worktrees are derived through the `{project}` placeholder of
+//! `git.worktree_prefix`. `project relocate` moves `.vibe-ticket` to
+//! another root directory, from which worktree locations are derived.
+//! Both update the workspace registry (see [`WorkspaceRegistry`]) and
+//! list the existing ticket worktrees whose derived path changes; with
+//! `--move-worktrees` they move them with `git worktree move`. Branch
+//! names are derived from ticket slugs only and stay as they are.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::init::create_gitignore;
+use super::worktree::{extract_ticket_slug, list_git_worktrees, move_git_worktree};
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::{Config, WorkspaceRegistry};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::FileStorage;
+
+/// Ticket worktree whose derived path changes
+#[derive(Debug, Serialize)]
+struct WorktreeMove {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+/// Handler for the `project rename` subcommand
+///
+/// # Arguments
+///
+/// * `name` - New name of the project
+/// * `move_worktrees` - Whether to move the ticket worktrees to their new names
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the name is empty, the project is not initialized,
+/// or the configuration, the registry or a worktree cannot be updated
+pub fn handle_project_rename(
+    name: &str,
+    move_worktrees: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Project name must not be empty".to_string(),
+        ));
+    }
+    let root = canonical_root(project_dir)?;
+    let config_path = root.join(".vibe-ticket").join("config.yaml");
+    let old = Config::load_from_path(&config_path)?;
+    let old_name = old.project.name.clone();
+    if old_name == name {
+        output.info(&format!("The project is already named '{name}'"));
+        return Ok(());
+    }
+
+    let mut config = old.clone();
+    config.project.name = name.to_string();
+    let relocations = plan_worktree_moves(&root, &old, &root, &config);
+    config.save_to_path(&config_path)?;
+
+    // A registration under the old project name follows the rename
+    let mut registry = WorkspaceRegistry::load()?;
+    let registered = registry
+        .get(&old_name)
+        .is_some_and(|project| project.path == root)
+        && registry.get(name).is_none();
+    if registered {
+        registry.rename(&old_name, name)?;
+        registry.save()?;
+    }
+
+    if move_worktrees {
+        apply_worktree_moves(&root, &relocations)?;
+    }
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "status": "success",
+            "old_name": old_name,
+            "name": name,
+            "registry_updated": registered,
+            "worktrees": relocations,
+            "worktrees_moved": move_worktrees,
+        }));
+    }
+
+    output.success(&format!("Renamed project '{old_name}' to '{name}'"));
+    if registered {
+        output.info("Updated the workspace registry");
+    }
+    report_worktree_moves(&relocations, move_worktrees, output);
+    Ok(())
+}
+
+/// Handler for the `project relocate` subcommand
+///
+/// # Arguments
+///
+/// * `path` - Directory to move `.vibe-ticket` into, the new project root
+/// * `move_worktrees` - Whether to move the ticket worktrees along
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the target directory does not exist or already has
+/// a project, other processes hold locks in the project, or the project,
+/// the registry or a worktree cannot be moved
+pub fn handle_project_relocate(
+    path: &str,
+    move_worktrees: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let old_root = canonical_root(project_dir)?;
+    let new_root = Path::new(path)
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {path}"))?;
+    let old_dir = old_root.join(".vibe-ticket");
+    let new_dir = new_root.join(".vibe-ticket");
+    if new_dir.exists() || new_root.starts_with(&old_dir) {
+        return Err(VibeTicketError::custom(format!(
+            "Cannot move the project to {}: it already has a .vibe-ticket directory or is inside this one",
+            new_root.display()
+        )));
+    }
+    let locks = FileStorage::new(&old_dir).held_locks();
+    if !locks.is_empty() {
+        return Err(VibeTicketError::custom(format!(
+            "{} lock(s) are held by other processes. Retry once they are done; `vibe-ticket doctor locks` lists them",
+            locks.len()
+        )));
+    }
+
+    let old = Config::load_from_path(old_dir.join("config.yaml"))?;
+    let mut config = old.clone();
+    config.relocate_paths(&old_root, &new_root);
+    let relocations = plan_worktree_moves(&old_root, &old, &new_root, &config);
+
+    fs::rename(&old_dir, &new_dir).with_context(|| {
+        format!(
+            "Failed to move {} to {}",
+            old_dir.display(),
+            new_dir.display()
+        )
+    })?;
+    config.save_to_path(new_dir.join("config.yaml"))?;
+    create_gitignore(&new_root)?;
+
+    let mut registry = WorkspaceRegistry::load()?;
+    let registered = registry.relocate(&old_root, &new_root);
+    if registered > 0 {
+        registry.save()?;
+    }
+
+    if move_worktrees {
+        apply_worktree_moves(&old_root, &relocations)?;
+    }
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "status": "success",
+            "old_path": old_root,
+            "path": new_root,
+            "registry_updated": registered > 0,
+            "worktrees": relocations,
+            "worktrees_moved": move_worktrees,
+        }));
+    }
+
+    output.success(&format!(
+        "Moved the project from {} to {}",
+        old_root.display(),
+        new_root.display()
+    ));
+    if registered > 0 {
+        output.info("Updated the workspace registry");
+    }
+    report_worktree_moves(&relocations, move_worktrees, output);
+    output.info("Commit the move of .vibe-ticket if its tickets are tracked by Git.");
+    Ok(())
+}
+
+/// Root directory of the project, with symbolic links resolved
+///
+/// A relative `--project` is found again from the directory it names, as
+/// the empty path.
+fn canonical_root(project_dir: Option<&str>) -> Result<PathBuf> {
+    let mut root = find_project_root(project_dir)?;
+    if root.as_os_str().is_empty() {
+        root = PathBuf::from(".");
+    }
+    root.canonicalize()
+        .with_context(|| format!("Failed to resolve {}", root.display()))
+}
+
+/// Lists the ticket worktrees of the project at `old_root` configured as
+/// `old` whose path differs once it is at `new_root` configured as `new`
+///
+/// Projects without Git have no worktrees.
+fn plan_worktree_moves(
+    old_root: &Path,
+    old: &Config,
+    new_root: &Path,
+    new: &Config,
+) -> Vec<WorktreeMove> {
+    list_git_worktrees(old_root)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|worktree| worktree.path != old_root)
+        .filter_map(|worktree| {
+            let slug = extract_ticket_slug(&worktree.path, old).ok()??;
+            let to = new.worktree_path(new_root, &slug)?;
+            (to != worktree.path).then_some(WorktreeMove {
+                from: worktree.path,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Moves the worktrees with Git, run in the repository at `repo_dir`
+fn apply_worktree_moves(repo_dir: &Path, relocations: &[WorktreeMove]) -> Result<()> {
+    for worktree in relocations {
+        move_git_worktree(repo_dir, &worktree.from, &worktree.to)?;
+    }
+    Ok(())
+}
+
+/// Prints the worktree moves, or the commands to make them if they were
+/// not made
+///
+/// Worktrees left behind no longer match the configuration, so running the
+/// command again cannot find them; they have to be moved by hand.
+fn report_worktree_moves(relocations: &[WorktreeMove], moved: bool, output: &OutputFormatter) {
+    if relocations.is_empty() {
+        return;
+    }
+    if moved {
+        output.success(&format!("Moved {} worktree(s):", relocations.len()));
+        for worktree in relocations {
+            output.info(&format!(
+                "  {} -> {}",
+                worktree.from.display(),
+                worktree.to.display()
+            ));
+        }
+        return;
+    }
+    output.warning(&format!(
+        "{} ticket worktree(s) no longer match the worktree prefix. Move them with:",
+        relocations.len()
+    ));
+    for worktree in relocations {
+        output.info(&format!(
+            "  git worktree move {} {}",
+            worktree.from.display(),
+            worktree.to.display()
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! Handler for the `reopen` command
+//!
+//! Reopening is the explicit way back from a closed ticket; while
+//! `project.immutable_closed` is set, storage rejects any other change to a
+//! closed ticket.
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::core::Status;
+use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::storage::FileStorage;
+
+/// Handler for the `reopen` command
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `status` - Status the ticket is moved to
+/// * `reason` - Optional reason, kept in the ticket metadata
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not found or not closed, or the status
+/// is invalid or `done`
+pub fn handle_reopen_command(
+    ticket_ref: &str,
+    status: &str,
+    reason: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let status = Status::try_from(status).map_err(|_| VibeTicketError::InvalidStatus {
+        status: status.to_string(),
+    })?;
+    if status == Status::Done {
+        return Err(VibeTicketError::InvalidInput(
+            "A reopened ticket cannot be done".to_string(),
+        ));
+    }
+
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+
+    let ticket = storage.reopen_ticket(&ticket_id, status, |ticket| {
+        if let Some(reason) = &reason {
+            ticket
+                .metadata
+                .insert("reopen_reason".to_string(), reason.clone().into());
+        }
+    })?;
+    emit_cli_event(
+        &project_root,
+        &TicketEvent::StatusChanged(ticket_id, Status::Done, status),
+    );
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket": {
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+                "status": ticket.status.to_string(),
+            },
+            "reason": reason,
+        }))?;
+    } else {
+        output.success(&format!("Reopened ticket '{}'", ticket.slug));
+        output.info(&format!("Status: {}", ticket.status));
+        if let Some(reason) = reason {
+            output.info(&format!("Reason: {reason}"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reopen_closed_ticket() {
+        let temp = TempDir::new().unwrap();
+        let storage =
+            FileStorage::new(temp.path().join(".vibe-ticket")).with_immutable_closed(true);
+        storage.ensure_directories().unwrap();
+        let mut ticket = Ticket::new("closed", "Closed");
+        ticket.status = Status::Done;
+        ticket.closed_at = Some(chrono::Utc::now());
+        storage.save(&ticket).unwrap();
+
+        ticket.title = "Edited".to_string();
+        assert!(matches!(
+            storage.save(&ticket),
+            Err(VibeTicketError::TicketClosed { .. })
+        ));
+        let archived = storage
+            .update_ticket(&ticket.id, |t| {
+                t.metadata.insert("archived".to_string(), true.into());
+            })
+            .unwrap();
+        assert_eq!(archived.title, "Closed");
+
+        let output = OutputFormatter::new(true, false);
+        let project = temp.path().to_str();
+        handle_reopen_command("closed", "done", None, project, &output).unwrap_err();
+        handle_reopen_command(
+            "closed",
+            "doing",
+            Some("regressed".into()),
+            project,
+            &output,
+        )
+        .unwrap();
+        handle_reopen_command("closed", "todo", None, project, &output).unwrap_err();
+
+        let reopened = storage.load(&ticket.id).unwrap();
+        assert_eq!(reopened.status, Status::Doing);
+        assert_eq!(reopened.closed_at, None);
+        assert_eq!(reopened.metadata["reopen_reason"], "regressed");
+        storage.save(&ticket).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+//! Handler for the `schema` command
+//!
+//! Writes out the JSON Schemas of the JSON output documents (see
+//! [`crate::cli::schema`]) so downstream tools can validate against them.
+
+use std::fs;
+use std::path::Path;
+
+use crate::cli::OutputFormatter;
+use crate::cli::schema::{SCHEMA_VERSION, schemas};
+use crate::error::{ErrorContext, Result};
+
+/// Handler for the `schema dump` subcommand
+///
+/// Writes one `<name>.schema.json` file per document into `output_dir`, or
+/// prints all schemas keyed by file name when no directory is given.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a file cannot be written
+pub fn handle_schema_dump(output_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let schemas = schemas();
+
+    let Some(dir) = output_dir else {
+        let all: serde_json::Map<_, _> = schemas
+            .into_iter()
+            .map(|(name, schema)| (name.to_string(), schema))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&all)?);
+        return Ok(());
+    };
+
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut files = Vec::new();
+    for (name, schema) in &schemas {
+        let path = dir.join(name);
+        fs::write(&path, serde_json::to_string_pretty(schema)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        files.push(path);
+    }
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "files": files,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Wrote {} schemas (version {SCHEMA_VERSION}) to {}",
+            files.len(),
+            dir.display()
+        ));
+    }
+
+    Ok(())
+}
@@ -2,12 +2,17 @@
 //!
 //! This module implements the logic for searching tickets
 //! by title, description, tags, or using regex patterns.
+//! Tickets are searched in parallel and matches are shown as they are found.
 
+use crate::cli::handlers::{load_named_filter, parse_query};
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::core::Ticket;
-use crate::error::Result;
-use crate::storage::{FileStorage, TicketRepository};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketSummary};
 use regex::Regex;
+use std::num::NonZeroUsize;
+use std::thread;
+use std::time::Instant;
 
 /// Handler for the `search` command
 ///
@@ -17,25 +22,46 @@ use regex::Regex;
 /// 3. Description-only search
 /// 4. Tags-only search
 /// 5. Regex pattern matching
+/// 6. Query expressions (see [`crate::core::query`]) narrowing the tickets
+///
+/// Tickets are searched on `jobs` threads (see
+/// [`FileStorage::scan_tickets`]). Plain output shows each match as soon
+/// as it is found, followed by the number of tickets scanned and the time
+/// taken; JSON output lists the matches newest first.
 ///
 /// # Arguments
 ///
-/// * `query` - Search query or regex pattern
+/// * `query` - Optional search text or regex pattern
+/// * `expression` - Optional query expression the tickets must match
 /// * `title_only` - Search only in titles
 /// * `description_only` - Search only in descriptions
 /// * `tags_only` - Search only in tags
 /// * `use_regex` - Treat query as a regex pattern
+/// * `filter` - Optional saved filter the tickets must match
+/// * `limit` - Optional number of matches to stop the search after
+/// * `jobs` - Optional number of threads, defaulting to the available cores
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
+#[allow(clippy::too_many_arguments)]
 pub fn handle_search_command(
-    query: &str,
+    query: Option<&str>,
+    expression: Option<&str>,
     title_only: bool,
     description_only: bool,
     tags_only: bool,
     use_regex: bool,
+    filter: Option<&str>,
+    limit: Option<usize>,
+    jobs: Option<usize>,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
+    if limit == Some(0) || jobs == Some(0) {
+        return Err(VibeTicketError::InvalidInput(
+            "--limit and --jobs must be at least 1".to_string(),
+        ));
+    }
+
     // Ensure project is initialized
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
@@ -43,101 +69,79 @@ pub fn handle_search_command(
     // Initialize storage
     let storage = FileStorage::new(&vibe_ticket_dir);
 
-    // Load all tickets
-    let tickets = storage.load_all()?;
+    // Tickets are narrowed down by the saved filter and the expression
+    let mut narrowing = Vec::new();
+    if let Some(name) = filter {
+        narrowing.push(load_named_filter(&project_root, name)?);
+    }
+    if let Some(expression) = expression {
+        narrowing.push(parse_query(&project_root, expression)?);
+    }
 
     // Compile regex if needed
-    let regex = if use_regex {
-        Some(Regex::new(query).map_err(|e| {
-            crate::error::VibeTicketError::custom(format!("Invalid regex pattern: {e}"))
-        })?)
-    } else {
-        None
+    let regex = match query {
+        Some(query) if use_regex => Some(
+            Regex::new(query)
+                .map_err(|e| VibeTicketError::custom(format!("Invalid regex pattern: {e}")))?,
+        ),
+        _ => None,
     };
+    let label = query.or(expression).unwrap_or_default();
 
-    // Search tickets
-    let mut matches: Vec<(Ticket, Vec<String>)> = Vec::new();
-
-    for ticket in tickets {
-        let mut match_locations = Vec::new();
-
-        if use_regex {
-            // Regex search
-            let regex = regex.as_ref().unwrap();
-
-            if !title_only && !description_only && !tags_only {
-                // Search all fields
-                if regex.is_match(&ticket.title) {
-                    match_locations.push("title".to_string());
-                }
-                if regex.is_match(&ticket.description) {
-                    match_locations.push("description".to_string());
-                }
-                if ticket.tags.iter().any(|tag| regex.is_match(tag)) {
-                    match_locations.push("tags".to_string());
-                }
-            } else {
-                // Search specific fields
-                if title_only && regex.is_match(&ticket.title) {
-                    match_locations.push("title".to_string());
-                }
-                if description_only && regex.is_match(&ticket.description) {
-                    match_locations.push("description".to_string());
-                }
-                if tags_only && ticket.tags.iter().any(|tag| regex.is_match(tag)) {
-                    match_locations.push("tags".to_string());
-                }
-            }
-        } else {
-            // Case-insensitive substring search
-            let query_lower = query.to_lowercase();
-
-            if !title_only && !description_only && !tags_only {
-                // Search all fields
-                if ticket.title.to_lowercase().contains(&query_lower) {
-                    match_locations.push("title".to_string());
-                }
-                if ticket.description.to_lowercase().contains(&query_lower) {
-                    match_locations.push("description".to_string());
-                }
-                if ticket
-                    .tags
-                    .iter()
-                    .any(|tag| tag.to_lowercase().contains(&query_lower))
-                {
-                    match_locations.push("tags".to_string());
-                }
-            } else {
-                // Search specific fields
-                if title_only && ticket.title.to_lowercase().contains(&query_lower) {
-                    match_locations.push("title".to_string());
-                }
-                if description_only && ticket.description.to_lowercase().contains(&query_lower) {
-                    match_locations.push("description".to_string());
-                }
-                if tags_only
-                    && ticket
-                        .tags
-                        .iter()
-                        .any(|tag| tag.to_lowercase().contains(&query_lower))
-                {
-                    match_locations.push("tags".to_string());
-                }
-            }
+    // Search tickets, showing matches as they are found
+    let started = Instant::now();
+    let select = |ticket: Ticket| {
+        if !narrowing.iter().all(|filter| filter.matches(&ticket)) {
+            return None;
         }
-
-        if !match_locations.is_empty() {
-            matches.push((ticket, match_locations));
+        let locations = query.map_or_else(Vec::new, |query| {
+            match_locations(
+                &ticket,
+                query,
+                title_only,
+                description_only,
+                tags_only,
+                regex.as_ref(),
+            )
+        });
+        (query.is_none() || !locations.is_empty()).then_some((ticket, locations))
+    };
+    let mut matches: Vec<(Ticket, Vec<String>)> = Vec::new();
+    let jobs = jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get));
+    let found = |found: (Ticket, Vec<String>)| {
+        if !output.is_json() {
+            print_match(
+                &found.0,
+                &found.1,
+                query,
+                description_only,
+                tags_only,
+                regex.as_ref(),
+                output,
+            );
         }
-    }
-
-    // Sort matches by creation date (newest first)
-    matches.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
+        matches.push(found);
+        limit.is_none_or(|limit| matches.len() < limit)
+    };
+    let scanned = match index_filter(
+        query,
+        title_only,
+        description_only,
+        tags_only,
+        regex.as_ref(),
+    ) {
+        Some(candidate) => storage.scan_indexed_tickets(jobs, candidate, select, found)?,
+        None => storage.scan_tickets(jobs, select, found)?,
+    };
+    let elapsed = started.elapsed();
 
     // Output results
     if output.is_json() {
+        // Sort matches by creation date (newest first)
+        matches.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
         output.print_json(&serde_json::json!({
             "query": query,
+            "expression": expression,
             "regex": use_regex,
             "search_fields": {
                 "title": title_only || !description_only && !tags_only,
@@ -153,57 +157,185 @@ pub fn handle_search_command(
                 "matched_in": locations,
             })).collect::<Vec<_>>(),
             "total": matches.len(),
+            "limit": limit,
+            "scanned": scanned,
+            "elapsed_ms": elapsed.as_millis(),
         }))?;
-    } else if matches.is_empty() {
-        output.info(&format!("No tickets found matching '{query}'"));
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        output.info(&format!("No tickets found matching '{label}'"));
     } else {
+        let limited = if limit == Some(matches.len()) {
+            " (limit reached)"
+        } else {
+            ""
+        };
         output.success(&format!(
-            "Found {} ticket{} matching '{}'",
+            "Found {} ticket{} matching '{}'{limited}",
             matches.len(),
             if matches.len() == 1 { "" } else { "s" },
-            query
+            label
         ));
-        output.info("");
+    }
+    output.info(&format!(
+        "Scanned {scanned} ticket{} in {}ms",
+        if scanned == 1 { "" } else { "s" },
+        elapsed.as_millis()
+    ));
 
-        for (ticket, locations) in &matches {
-            let status_emoji = match ticket.status {
-                crate::core::Status::Todo => "📋",
-                crate::core::Status::Doing => "🔄",
-                crate::core::Status::Review => "👀",
-                crate::core::Status::Blocked => "🚫",
-                crate::core::Status::Done => "✅",
-            };
+    Ok(())
+}
 
-            output.info(&format!(
-                "{} {} - {}",
-                status_emoji, ticket.slug, ticket.title
-            ));
-            output.info(&format!(
-                "   Priority: {} | Status: {} | Matched in: {}",
-                ticket.priority,
-                ticket.status,
-                locations.join(", ")
-            ));
+/// Filter on the listing index for searches of titles and tags only, which
+/// then parse just the tickets whose indexed title or tags match
+fn index_filter<'a>(
+    query: Option<&str>,
+    title_only: bool,
+    description_only: bool,
+    tags_only: bool,
+    regex: Option<&'a Regex>,
+) -> Option<impl Fn(&TicketSummary) -> bool + 'a> {
+    let query_lower = query
+        .filter(|_| (title_only || tags_only) && !description_only)?
+        .to_lowercase();
+    let text_matches = move |text: &str| {
+        regex.map_or_else(
+            || text.to_lowercase().contains(&query_lower),
+            |regex| regex.is_match(text),
+        )
+    };
+    Some(move |summary: &TicketSummary| {
+        (title_only && text_matches(&summary.title))
+            || (tags_only && summary.tags.iter().any(|tag| text_matches(tag)))
+    })
+}
 
-            // Show matching context for description
-            if locations.contains(&"description".to_string()) && !description_only {
-                let excerpt =
-                    get_match_excerpt(&ticket.description, query, use_regex, regex.as_ref());
-                if let Some(excerpt) = excerpt {
-                    output.info(&format!("   Description: ...{excerpt}..."));
-                }
-            }
+/// Prints a matching ticket with the fields it matched in
+fn print_match(
+    ticket: &Ticket,
+    locations: &[String],
+    query: Option<&str>,
+    description_only: bool,
+    tags_only: bool,
+    regex: Option<&Regex>,
+    output: &OutputFormatter,
+) {
+    let visuals = output.visuals();
+    output.info(&format!(
+        "{}{} - {}",
+        visuals.status_prefix(ticket.status),
+        ticket.slug,
+        ticket.title
+    ));
+    let matched_in = if locations.is_empty() {
+        String::new()
+    } else {
+        format!(" | Matched in: {}", locations.join(", "))
+    };
+    output.info(&format!(
+        "   Priority: {} | Status: {}{matched_in}",
+        visuals.priority(ticket.priority).label,
+        visuals.status(ticket.status).label,
+    ));
+
+    // Show matching context for description
+    if let Some(query) = query
+        && locations.iter().any(|location| location == "description")
+        && !description_only
+    {
+        let excerpt = get_match_excerpt(&ticket.description, query, regex.is_some(), regex);
+        if let Some(excerpt) = excerpt {
+            output.info(&format!("   Description: ...{excerpt}..."));
+        }
+    }
+
+    // Show matching tags
+    if locations.iter().any(|location| location == "tags") && !tags_only && !ticket.tags.is_empty()
+    {
+        output.info(&format!("   Tags: {}", ticket.tags.join(", ")));
+    }
+
+    output.info("");
+}
+
+/// Returns the fields of a ticket that contain the search text
+fn match_locations(
+    ticket: &Ticket,
+    query: &str,
+    title_only: bool,
+    description_only: bool,
+    tags_only: bool,
+    regex: Option<&Regex>,
+) -> Vec<String> {
+    let mut match_locations = Vec::new();
+
+    if let Some(regex) = regex {
+        // Regex search
 
-            // Show matching tags
-            if locations.contains(&"tags".to_string()) && !tags_only && !ticket.tags.is_empty() {
-                output.info(&format!("   Tags: {}", ticket.tags.join(", ")));
+        if !title_only && !description_only && !tags_only {
+            // Search all fields
+            if regex.is_match(&ticket.title) {
+                match_locations.push("title".to_string());
             }
+            if regex.is_match(&ticket.description) {
+                match_locations.push("description".to_string());
+            }
+            if ticket.tags.iter().any(|tag| regex.is_match(tag)) {
+                match_locations.push("tags".to_string());
+            }
+        } else {
+            // Search specific fields
+            if title_only && regex.is_match(&ticket.title) {
+                match_locations.push("title".to_string());
+            }
+            if description_only && regex.is_match(&ticket.description) {
+                match_locations.push("description".to_string());
+            }
+            if tags_only && ticket.tags.iter().any(|tag| regex.is_match(tag)) {
+                match_locations.push("tags".to_string());
+            }
+        }
+    } else {
+        // Case-insensitive substring search
+        let query_lower = query.to_lowercase();
 
-            output.info("");
+        if !title_only && !description_only && !tags_only {
+            // Search all fields
+            if ticket.title.to_lowercase().contains(&query_lower) {
+                match_locations.push("title".to_string());
+            }
+            if ticket.description.to_lowercase().contains(&query_lower) {
+                match_locations.push("description".to_string());
+            }
+            if ticket
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&query_lower))
+            {
+                match_locations.push("tags".to_string());
+            }
+        } else {
+            // Search specific fields
+            if title_only && ticket.title.to_lowercase().contains(&query_lower) {
+                match_locations.push("title".to_string());
+            }
+            if description_only && ticket.description.to_lowercase().contains(&query_lower) {
+                match_locations.push("description".to_string());
+            }
+            if tags_only
+                && ticket
+                    .tags
+                    .iter()
+                    .any(|tag| tag.to_lowercase().contains(&query_lower))
+            {
+                match_locations.push("tags".to_string());
+            }
         }
     }
 
-    Ok(())
+    match_locations
 }
 
 /// Extract a short excerpt around the match
@@ -0,0 +1,254 @@
+//! Handler for the `share` command
+//!
+//! This module renders a ticket as a single self-contained markdown or HTML
+//! document and prints it, writes it to a file, copies it to the clipboard
+//! or publishes it as a GitHub gist.
+
+mod render;
+
+use render::{ShareDocument, ShareFormat};
+
+use std::io::Write as IoWrite;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Endpoint of the GitHub gist API
+const GIST_API_URL: &str = "https://api.github.com/gists";
+
+/// Timeout of the gist upload
+const GIST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where the shared document goes
+#[derive(Debug, Clone, Default)]
+pub struct ShareTargets {
+    /// File to write the document to
+    pub file: Option<String>,
+    /// Copy the document to the clipboard
+    pub clipboard: bool,
+    /// Publish the document as a gist
+    pub gist: bool,
+    /// Make the gist public instead of secret
+    pub public: bool,
+}
+
+/// Handler for the `share` command
+///
+/// Without a target, the document is printed to standard output.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `format` - Output format (`markdown` or `html`)
+/// * `targets` - File, clipboard and gist targets
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, the ticket is not
+/// found, the format is unknown, or a target cannot be written
+pub fn handle_share_command(
+    ticket_ref: &str,
+    format: &str,
+    targets: &ShareTargets,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let format = ShareFormat::parse(format).ok_or_else(|| {
+        VibeTicketError::InvalidInput(format!(
+            "Unknown share format '{format}'. Use markdown or html"
+        ))
+    })?;
+
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let ticket = storage.load(&resolve_ticket_ref(&storage, ticket_ref)?)?;
+    let content =
+        ShareDocument::collect(&storage, &project_root, &ticket, output.dates())?.render(format)?;
+
+    if let Some(file) = &targets.file {
+        std::fs::write(file, &content).with_context(|| format!("Failed to write {file}"))?;
+    }
+    if targets.clipboard {
+        copy_to_clipboard(&content)?;
+    }
+    let gist_url = if targets.gist {
+        let file_name = format!("{}.{}", ticket.slug, format.extension());
+        Some(create_gist(
+            &file_name,
+            &ticket.title,
+            &content,
+            targets.public,
+        )?)
+    } else {
+        None
+    };
+    let printed = targets.file.is_none() && !targets.clipboard && !targets.gist;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "format": format.extension(),
+            "file": targets.file,
+            "clipboard": targets.clipboard,
+            "gist_url": gist_url,
+            "content": printed.then_some(&content),
+        }))?;
+        return Ok(());
+    }
+
+    if printed {
+        print!("{content}");
+        return Ok(());
+    }
+    if let Some(file) = &targets.file {
+        output.success(&format!("Wrote '{}' to {file}", ticket.slug));
+    }
+    if targets.clipboard {
+        output.success(&format!("Copied '{}' to the clipboard", ticket.slug));
+    }
+    if let Some(url) = gist_url {
+        output.success(&format!("Published '{}' as a gist: {url}", ticket.slug));
+    }
+
+    Ok(())
+}
+
+/// Copies text to the system clipboard using the platform's clipboard tool
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    let candidates: &[&[&str]] = if cfg!(target_os = "macos") {
+        &[&["pbcopy"]]
+    } else if cfg!(windows) {
+        &[&["clip"]]
+    } else {
+        &[
+            &["wl-copy"],
+            &["xclip", "-selection", "clipboard"],
+            &["xsel", "--clipboard", "--input"],
+        ]
+    };
+
+    for candidate in candidates {
+        let Ok(mut child) = Command::new(candidate[0])
+            .args(&candidate[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .context("Failed to write to the clipboard")?;
+        }
+        if child
+            .wait()
+            .context("Failed to wait for the clipboard tool")?
+            .success()
+        {
+            return Ok(());
+        }
+    }
+
+    Err(VibeTicketError::custom(format!(
+        "No clipboard tool found (tried {})",
+        candidates
+            .iter()
+            .map(|c| c[0])
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
+
+/// Publishes a file as a GitHub gist and returns its URL
+///
+/// The token is read from `GITHUB_TOKEN` or `GH_TOKEN` and needs the `gist`
+/// scope.
+fn create_gist(file_name: &str, description: &str, content: &str, public: bool) -> Result<String> {
+    let token = std::env::var("GITHUB_TOKEN")
+        .or_else(|_| std::env::var("GH_TOKEN"))
+        .map_err(|_| VibeTicketError::custom("Set GITHUB_TOKEN (or GH_TOKEN) to publish gists"))?;
+
+    let response: serde_json::Value = ureq::post(GIST_API_URL)
+        .timeout(GIST_TIMEOUT)
+        .set("Authorization", &format!("Bearer {token}"))
+        .set("Accept", "application/vnd.github+json")
+        .set("User-Agent", "vibe-ticket")
+        .send_json(gist_request(file_name, description, content, public))
+        .map_err(|e| VibeTicketError::custom(format!("Failed to create gist: {e}")))?
+        .into_json()
+        .context("Failed to read gist response")?;
+
+    response["html_url"]
+        .as_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| VibeTicketError::custom("Gist response has no URL"))
+}
+
+/// Body of a gist creation request
+fn gist_request(
+    file_name: &str,
+    description: &str,
+    content: &str,
+    public: bool,
+) -> serde_json::Value {
+    serde_json::json!({
+        "description": description,
+        "public": public,
+        "files": { file_name: { "content": content } },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::DateFormat;
+    use crate::core::Ticket;
+    use crate::specs::{SpecManager, Specification};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collects_specs_and_attachments() {
+        let temp_dir = TempDir::new().unwrap();
+        let vibe_dir = temp_dir.path().join(".vibe-ticket");
+        let storage = FileStorage::new(&vibe_dir);
+
+        let mut ticket = Ticket::new("shared", "Shared");
+        ticket.description = "x".repeat(64);
+        storage.spill_description(&mut ticket, 16).unwrap();
+        storage.save(&ticket).unwrap();
+
+        let mut spec = Specification::new(
+            "Share spec".to_string(),
+            String::new(),
+            Some(ticket.id.to_string()),
+            Vec::new(),
+        );
+        spec.requirements = Some("Must share".to_string());
+        SpecManager::new(vibe_dir.join("specs"))
+            .save(&spec)
+            .unwrap();
+
+        let md = ShareDocument::collect(&storage, temp_dir.path(), &ticket, &DateFormat::default())
+            .unwrap()
+            .to_markdown();
+        assert!(md.contains(&"x".repeat(64)), "full description is inlined");
+        assert!(md.contains("## Spec: Share spec"));
+        assert!(md.contains("> Must share"));
+        assert!(md.contains("- `description.md` (64 bytes)"));
+    }
+
+    #[test]
+    fn test_gist_request() {
+        let body = gist_request("a.md", "A", "# A", false);
+        assert_eq!(body["public"], false);
+        assert_eq!(body["files"]["a.md"]["content"], "# A");
+    }
+}
@@ -0,0 +1,396 @@
+//! Rendering of a shareable ticket document
+//!
+//! [`ShareDocument`] gathers everything a reader outside the project needs:
+//! the ticket with its full description, tasks, history, excerpts of the
+//! linked specifications and a manifest of the attachments. It renders to
+//! markdown or to a standalone HTML page without external resources.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::cli::DateFormat;
+use crate::core::Ticket;
+use crate::core::sections::{self, HistoryEvent};
+use crate::error::Result;
+use crate::specs::{self, Specification};
+use crate::storage::{Attachment, FileStorage};
+
+/// Maximum number of lines kept from each spec document
+const EXCERPT_LINES: usize = 15;
+
+/// Output format of a shared ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareFormat {
+    /// Markdown document
+    Markdown,
+    /// Standalone HTML page
+    Html,
+}
+
+impl ShareFormat {
+    /// Parses a format name (`markdown`/`md` or `html`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "markdown" | "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    /// File extension of the format
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Everything included in a shared ticket
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareDocument {
+    /// Ticket title
+    pub title: String,
+    /// Ticket slug
+    pub slug: String,
+    /// Ticket ID
+    pub id: String,
+    /// Header fields as (label, value) pairs
+    pub fields: Vec<(String, String)>,
+    /// Full description
+    pub description: String,
+    /// Tasks as (completed, title) pairs
+    pub tasks: Vec<(bool, String)>,
+    /// Number of completed tasks
+    pub tasks_done: usize,
+    /// History entries as (time, event) pairs
+    pub history: Vec<(String, String)>,
+    /// Excerpts of the linked specifications
+    pub specs: Vec<SpecExcerpt>,
+    /// Files attached to the ticket
+    pub attachments: Vec<Attachment>,
+    /// When the document was generated
+    pub generated_at: String,
+}
+
+/// Excerpt of a specification linked to the ticket
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecExcerpt {
+    /// Spec title
+    pub title: String,
+    /// Current phase of the spec
+    pub phase: String,
+    /// Leading lines of each document as (document, text) pairs
+    pub documents: Vec<(String, String)>,
+}
+
+impl ShareDocument {
+    /// Collects the shareable content of a ticket, with dates in the given
+    /// format
+    pub fn collect(
+        storage: &FileStorage,
+        project_root: &Path,
+        ticket: &Ticket,
+        dates: &DateFormat,
+    ) -> Result<Self> {
+        let vibe_dir = project_root.join(".vibe-ticket");
+        let history = sections::load_history(project_root, ticket);
+        let specs = sections::linked_specs(&vibe_dir, ticket)
+            .into_iter()
+            .filter_map(|link| {
+                let spec = specs::load(&vibe_dir.join("specs"), &link.id).ok()?;
+                Some(SpecExcerpt {
+                    title: link.title,
+                    phase: link.phase,
+                    documents: excerpts(&spec),
+                })
+            })
+            .collect();
+
+        Ok(Self::new(
+            ticket,
+            storage.load_full_description(ticket)?,
+            &history,
+            specs,
+            storage.list_attachments(&ticket.id),
+            dates,
+        ))
+    }
+
+    /// Builds a document from already loaded parts
+    pub fn new(
+        ticket: &Ticket,
+        description: String,
+        history: &[HistoryEvent],
+        specs: Vec<SpecExcerpt>,
+        attachments: Vec<Attachment>,
+        dates: &DateFormat,
+    ) -> Self {
+        let mut fields = vec![
+            ("Status".to_string(), ticket.status.to_string()),
+            ("Priority".to_string(), ticket.priority.to_string()),
+        ];
+        if let Some(severity) = ticket.severity {
+            fields.push(("Severity".to_string(), severity.to_string()));
+        }
+        if let Some(assignee) = &ticket.assignee {
+            fields.push(("Assignee".to_string(), assignee.clone()));
+        }
+        if let Some(due) = ticket.due {
+            fields.push(("Due".to_string(), due.to_string()));
+        }
+        if !ticket.tags.is_empty() {
+            fields.push(("Tags".to_string(), ticket.tags.join(", ")));
+        }
+        fields.push((
+            "Created".to_string(),
+            dates.format_absolute(ticket.created_at),
+        ));
+        if let Some(closed_at) = ticket.closed_at {
+            fields.push(("Closed".to_string(), dates.format_absolute(closed_at)));
+        }
+
+        Self {
+            title: ticket.title.clone(),
+            slug: ticket.slug.clone(),
+            id: ticket.id.to_string(),
+            fields,
+            description,
+            tasks: ticket
+                .tasks
+                .iter()
+                .map(|task| (task.completed, task.title.clone()))
+                .collect(),
+            tasks_done: ticket.completed_tasks_count(),
+            history: history
+                .iter()
+                .map(|event| (dates.format_absolute(event.at), event.to_string()))
+                .collect(),
+            specs,
+            attachments,
+            generated_at: dates.format_absolute(Utc::now()),
+        }
+    }
+
+    /// Renders the document in the given format
+    pub fn render(&self, format: ShareFormat) -> Result<String> {
+        match format {
+            ShareFormat::Markdown => Ok(self.to_markdown()),
+            ShareFormat::Html => self.to_html(),
+        }
+    }
+
+    /// Renders the document as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "# {}\n", self.title);
+        let _ = writeln!(md, "- **ID**: `{}`", self.id);
+        let _ = writeln!(md, "- **Slug**: `{}`", self.slug);
+        for (label, value) in &self.fields {
+            let _ = writeln!(md, "- **{label}**: {value}");
+        }
+
+        if !self.description.trim().is_empty() {
+            let _ = writeln!(md, "\n## Description\n\n{}", self.description.trim_end());
+        }
+
+        if !self.tasks.is_empty() {
+            let _ = writeln!(
+                md,
+                "\n## Tasks ({}/{})\n",
+                self.tasks_done,
+                self.tasks.len()
+            );
+            for (done, title) in &self.tasks {
+                let _ = writeln!(md, "- [{}] {title}", if *done { "x" } else { " " });
+            }
+        }
+
+        if !self.history.is_empty() {
+            md.push_str("\n## History\n\n");
+            for (at, event) in &self.history {
+                let _ = writeln!(md, "- {at} {event}");
+            }
+        }
+
+        for spec in &self.specs {
+            let _ = writeln!(md, "\n## Spec: {} ({})", spec.title, spec.phase);
+            for (document, text) in &spec.documents {
+                let _ = writeln!(md, "\n### {document}\n");
+                for line in text.lines() {
+                    let _ = writeln!(md, "> {line}");
+                }
+            }
+        }
+
+        if !self.attachments.is_empty() {
+            md.push_str("\n## Attachments\n\n");
+            for attachment in &self.attachments {
+                let _ = writeln!(md, "- `{}` ({} bytes)", attachment.name, attachment.size);
+            }
+        }
+
+        let _ = writeln!(
+            md,
+            "\n---\n_Shared from vibe-ticket on {}_",
+            self.generated_at
+        );
+        md
+    }
+
+    /// Renders the document as a standalone HTML page
+    ///
+    /// All ticket content is HTML-escaped.
+    pub fn to_html(&self) -> Result<String> {
+        let context = tera::Context::from_serialize(self)?;
+        Ok(tera::Tera::one_off(HTML_TEMPLATE, &context, true)?)
+    }
+}
+
+/// Leading lines of the documents of a spec
+fn excerpts(spec: &Specification) -> Vec<(String, String)> {
+    [
+        ("Requirements", &spec.requirements),
+        ("Design", &spec.design),
+        ("Tasks", &spec.tasks),
+    ]
+    .into_iter()
+    .filter_map(|(name, content)| {
+        let content = content.as_deref()?.trim();
+        if content.is_empty() {
+            return None;
+        }
+        let mut excerpt: String = content
+            .lines()
+            .take(EXCERPT_LINES)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if content.lines().count() > EXCERPT_LINES {
+            excerpt.push_str("\n…");
+        }
+        Some((name.to_string(), excerpt))
+    })
+    .collect()
+}
+
+/// Template of the HTML page
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{ title }}</title>
+<style>
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; max-width: 50em; margin: 2em auto; padding: 0 1em; color: #24292f; line-height: 1.5; }
+h1 { border-bottom: 1px solid #d0d7de; padding-bottom: .3em; }
+h2 { border-bottom: 1px solid #eaeef2; padding-bottom: .2em; margin-top: 1.5em; }
+table.fields td:first-child { font-weight: 600; padding-right: 1em; }
+pre { white-space: pre-wrap; background: #f6f8fa; padding: 1em; border-radius: 6px; }
+blockquote { margin: 0; padding: 0 1em; color: #57606a; border-left: .25em solid #d0d7de; white-space: pre-wrap; }
+ul.tasks { list-style: none; padding-left: 0; }
+code { background: #f6f8fa; padding: .1em .3em; border-radius: 4px; }
+footer { margin-top: 2em; color: #57606a; font-size: .9em; }
+</style>
+</head>
+<body>
+<h1>{{ title }}</h1>
+<table class="fields">
+<tr><td>ID</td><td><code>{{ id }}</code></td></tr>
+<tr><td>Slug</td><td><code>{{ slug }}</code></td></tr>
+{% for field in fields %}<tr><td>{{ field.0 }}</td><td>{{ field.1 }}</td></tr>
+{% endfor %}</table>
+{% if description %}
+<h2>Description</h2>
+<pre>{{ description }}</pre>
+{% endif %}{% if tasks %}
+<h2>Tasks ({{ tasks_done }}/{{ tasks | length }})</h2>
+<ul class="tasks">
+{% for task in tasks %}<li>{% if task.0 %}&#9745;{% else %}&#9744;{% endif %} {{ task.1 }}</li>
+{% endfor %}</ul>
+{% endif %}{% if history %}
+<h2>History</h2>
+<ul>
+{% for entry in history %}<li><time>{{ entry.0 }}</time> {{ entry.1 }}</li>
+{% endfor %}</ul>
+{% endif %}{% for spec in specs %}
+<h2>Spec: {{ spec.title }} ({{ spec.phase }})</h2>
+{% for document in spec.documents %}<h3>{{ document.0 }}</h3>
+<blockquote>{{ document.1 }}</blockquote>
+{% endfor %}{% endfor %}{% if attachments %}
+<h2>Attachments</h2>
+<ul>
+{% for attachment in attachments %}<li><code>{{ attachment.name }}</code> ({{ attachment.size }} bytes)</li>
+{% endfor %}</ul>
+{% endif %}
+<footer>Shared from vibe-ticket on {{ generated_at }}</footer>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Task;
+
+    fn document() -> ShareDocument {
+        let mut ticket = Ticket::new("share-me", "Share <me>");
+        ticket.tags = vec!["ui".to_string()];
+        ticket.tasks.push(Task::new("Write docs"));
+        ticket.tasks.push(Task::new("Ship it"));
+        ticket.tasks[0].complete();
+        let history = sections::history(&ticket, &[]);
+        let specs = vec![SpecExcerpt {
+            title: "Sharing".to_string(),
+            phase: "Design".to_string(),
+            documents: vec![("Requirements".to_string(), "- R1\n- R2".to_string())],
+        }];
+        let attachments = vec![Attachment {
+            name: "description.md".to_string(),
+            size: 42,
+        }];
+        ShareDocument::new(
+            &ticket,
+            "Line one\n<script>alert(1)</script>".to_string(),
+            &history,
+            specs,
+            attachments,
+            &DateFormat::default(),
+        )
+    }
+
+    #[test]
+    fn test_markdown() {
+        let md = document().to_markdown();
+        assert!(md.starts_with("# Share <me>\n"));
+        assert!(md.contains("- **Tags**: ui"));
+        assert!(md.contains("## Tasks (1/2)"));
+        assert!(md.contains("- [x] Write docs\n- [ ] Ship it"));
+        assert!(md.contains("## History"));
+        assert!(md.contains("## Spec: Sharing (Design)\n\n### Requirements\n\n> - R1\n> - R2"));
+        assert!(md.contains("- `description.md` (42 bytes)"));
+    }
+
+    #[test]
+    fn test_html_is_escaped() {
+        let html = document().to_html().unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Share &lt;me&gt;</h1>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&#9745; Write docs"));
+        assert!(html.contains("<code>description.md</code> (42 bytes)"));
+    }
+
+    #[test]
+    fn test_excerpts_are_truncated() {
+        let mut spec = Specification::new("S".to_string(), String::new(), None, Vec::new());
+        spec.requirements = Some((1..=40).map(|i| format!("line {i}\n")).collect());
+        spec.design = Some("   ".to_string());
+
+        let documents = excerpts(&spec);
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].1.lines().count(), EXCERPT_LINES + 1);
+        assert!(documents[0].1.ends_with('…'));
+    }
+}
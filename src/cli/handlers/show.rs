@@ -1,31 +1,40 @@
 //! Handler for the `show` command
 //!
 //! This module implements the logic for displaying detailed information
-//! about a specific ticket, including tasks and history.
-
-use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+//! about a specific ticket. The ticket header is always shown; the
+//! description, tasks, links, history, spec and worktree are optional
+//! sections (see [`Section`]).
+
+use std::path::Path;
+
+use crate::cli::{DateFormat, OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::config::Config;
+use crate::core::fields::field_values;
+use crate::core::hierarchy::{self, Rollup};
+use crate::core::sections::{self, Section, SpecLink};
+use crate::core::{Status, Ticket, dependencies, relations};
 use crate::error::Result;
-use crate::storage::{FileStorage, TicketRepository};
-use chrono::{DateTime, Local, Utc};
+use crate::integration::presence::{Presence, Sighting};
+use crate::storage::{Attachment, FileStorage, TicketRepository};
+use chrono::Utc;
 
 /// Handler for the `show` command
 ///
-/// This function displays comprehensive information about a ticket:
-/// 1. Basic ticket information (ID, slug, title, etc.)
-/// 2. Full description
-/// 3. Status and priority
-/// 4. Timestamps (created, started, closed)
-/// 5. Tags
-/// 6. Tasks (if requested)
-/// 7. History (if available and requested)
-/// 8. Metadata
+/// This function displays information about a ticket:
+/// 1. Basic ticket information (ID, slug, title, status, priority, tags)
+/// 2. Timestamps (created, started, closed)
+/// 3. Metadata
+/// 4. Phases of the linked specs
+/// 5. MCP sessions recently working on the ticket
+/// 6. The selected sections (description, tasks, links, history, spec, worktree)
 ///
 /// # Arguments
 ///
 /// * `ticket_ref` - Ticket ID or slug to display
-/// * `show_tasks` - Whether to show task details
-/// * `show_history` - Whether to show ticket history
+/// * `sections` - Sections to display
+/// * `expand` - Whether to expand collapsed sections such as long histories
 /// * `markdown` - Whether to format output as markdown
+/// * `full_description` - Whether to inline a description moved to an attachment
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
 ///
@@ -36,9 +45,10 @@ use chrono::{DateTime, Local, Utc};
 /// - The ticket is not found
 pub fn handle_show_command(
     ticket_ref: &str,
-    show_tasks: bool,
-    show_history: bool,
+    sections: &[Section],
+    expand: bool,
     markdown: bool,
+    full_description: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -53,7 +63,17 @@ pub fn handle_show_command(
     let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
 
     // Load the ticket
-    let ticket = storage.load(&ticket_id)?;
+    let mut ticket = storage.load(&ticket_id)?;
+
+    // Inline the description attachment back if requested
+    if full_description {
+        ticket.description = storage.load_full_description(&ticket)?;
+    }
+
+    // The dependency graph, the parent and the children need the other tickets
+    let tickets = storage.load_all()?;
+    let children = hierarchy::children(&ticket, &tickets);
+    let extras = Extras::load(&storage, &project_root, &ticket)?;
 
     // Output results
     if output.is_json() {
@@ -65,63 +85,198 @@ pub fn handle_show_command(
                 "description": ticket.description,
                 "status": ticket.status.to_string(),
                 "priority": ticket.priority.to_string(),
+                "severity": ticket.severity.map(|s| s.to_string()),
                 "tags": ticket.tags,
                 "assignee": ticket.assignee,
+                "due": ticket.due,
+                "milestone": ticket.milestone,
+                "parent": parent_slug(&ticket, &tickets),
+                "watchers": ticket.watchers,
                 "created_at": ticket.created_at,
                 "started_at": ticket.started_at,
                 "closed_at": ticket.closed_at,
                 "metadata": ticket.metadata,
+                "specs": extras.specs,
             }
         });
 
-        if show_tasks {
+        if !children.is_empty() {
+            json_output["children"] = children
+                .iter()
+                .map(|child| {
+                    serde_json::json!({
+                        "id": child.id.to_string(),
+                        "slug": child.slug,
+                        "status": child.status.to_string(),
+                    })
+                })
+                .collect();
+            json_output["progress"] = serde_json::json!(Rollup::of(&ticket, &tickets));
+        }
+
+        if !extras.fields.is_empty() {
+            json_output["fields"] = extras
+                .fields
+                .iter()
+                .map(|(name, _)| (name.clone(), ticket.metadata[name].clone()))
+                .collect::<serde_json::Map<_, _>>()
+                .into();
+        }
+
+        if !extras.attachments.is_empty() {
+            json_output["attachments"] = serde_json::json!(extras.attachments);
+        }
+
+        if !extras.presence.is_empty() {
+            json_output["presence"] = serde_json::json!(extras.presence);
+        }
+
+        if sections.contains(&Section::Tasks) {
             json_output["tasks"] = serde_json::json!(ticket.tasks);
         }
 
+        if sections.contains(&Section::History) {
+            json_output["work_log"] = serde_json::json!(ticket.work_log);
+            json_output["comments"] = serde_json::json!(ticket.comments);
+        }
+
+        json_output["sections"] = sections::sections_json(&ticket, sections, expand, &project_root);
+
         output.print_json(&json_output)?;
     } else if markdown {
-        output_markdown(&ticket, show_tasks, output);
+        output_markdown(
+            &ticket,
+            sections,
+            expand,
+            &project_root,
+            &tickets,
+            &extras,
+            output,
+        );
     } else {
-        output_plain(&ticket, show_tasks, show_history, output);
+        output_plain(
+            &ticket,
+            sections,
+            expand,
+            &project_root,
+            &tickets,
+            &extras,
+            output,
+        );
     }
 
     Ok(())
 }
 
+/// Details shown besides the built-in ticket fields
+struct Extras {
+    /// Files attached to the ticket
+    attachments: Vec<Attachment>,
+    /// Declared user-defined fields set on the ticket, with their values
+    fields: Vec<(String, String)>,
+    /// MCP sessions recently working on the ticket
+    presence: Vec<Sighting>,
+    /// Specs written for the ticket or exported to it
+    specs: Vec<SpecLink>,
+}
+
+impl Extras {
+    /// Gathers the extras of `ticket`
+    fn load(storage: &FileStorage, project_root: &Path, ticket: &Ticket) -> Result<Self> {
+        let vibe_ticket_dir = project_root.join(".vibe-ticket");
+        let schema = Config::load_for_project(project_root)?.fields;
+        Ok(Self {
+            attachments: storage.list_attachments(&ticket.id),
+            fields: field_values(ticket, &schema)
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            presence: Presence::new(&vibe_ticket_dir).on_ticket(&ticket.id, None),
+            specs: sections::linked_specs(&vibe_ticket_dir, ticket),
+        })
+    }
+}
+
 /// Output ticket information in plain text format
 fn output_plain(
-    ticket: &crate::core::Ticket,
-    show_tasks: bool,
-    show_history: bool,
+    ticket: &Ticket,
+    sections: &[Section],
+    expand: bool,
+    project_root: &Path,
+    tickets: &[Ticket],
+    extras: &Extras,
     output: &OutputFormatter,
 ) {
     // Header
     output.success(&format!("Ticket: {}", ticket.slug));
     output.info(&format!("ID: {}", ticket.id));
+    if let Some(short_id) = &ticket.short_id {
+        output.info(&format!("Short ID: {short_id}"));
+    }
     output.info(&format!("Title: {}", ticket.title));
-    output.info(&format!("Status: {}", ticket.status));
-    output.info(&format!("Priority: {}", ticket.priority));
+    output.info(&format!(
+        "Status: {}",
+        output.visuals().status_text(ticket.status)
+    ));
+    output.info(&format!(
+        "Priority: {}",
+        output.visuals().priority_text(ticket.priority)
+    ));
+    if let Some(severity) = ticket.severity {
+        output.info(&format!(
+            "Severity: {severity} ({})",
+            severity.description()
+        ));
+    }
 
     // Assignee
     if let Some(assignee) = &ticket.assignee {
         output.info(&format!("Assignee: {assignee}"));
     }
 
+    // Due date and watchers
+    if let Some(due) = ticket.due {
+        output.info(&format!("Due: {due}"));
+    }
+    if let Some(milestone) = &ticket.milestone {
+        output.info(&format!("Milestone: {milestone}"));
+    }
+    if let Some(parent) = parent_slug(ticket, tickets) {
+        output.info(&format!("Parent: {parent}"));
+    }
+    if !ticket.watchers.is_empty() {
+        output.info(&format!("Watchers: {}", ticket.watchers.join(", ")));
+    }
+
     // Tags
     if !ticket.tags.is_empty() {
         output.info(&format!("Tags: {}", ticket.tags.join(", ")));
     }
 
+    // Phase of the specs, detailed in the spec section
+    for spec in &extras.specs {
+        output.info(&format!("Spec: {} [{}]", spec.title, spec.phase));
+    }
+
+    // Other people and agents working on the ticket
+    if !extras.presence.is_empty() {
+        output.info("");
+        output.info("Active now:");
+        for sighting in &extras.presence {
+            output.info(&format!("  {}", sighting.hint));
+        }
+    }
+
     // Timestamps
     output.info("");
     output.info("Timeline:");
     output.info(&format!(
         "  Created: {}",
-        format_datetime(ticket.created_at)
+        output.dates().format(ticket.created_at)
     ));
 
     if let Some(started_at) = ticket.started_at {
-        output.info(&format!("  Started: {}", format_datetime(started_at)));
+        output.info(&format!("  Started: {}", output.dates().format(started_at)));
 
         // Calculate time spent
         let end_time = ticket.closed_at.unwrap_or_else(Utc::now);
@@ -132,39 +287,153 @@ fn output_plain(
     }
 
     if let Some(closed_at) = ticket.closed_at {
-        output.info(&format!("  Closed: {}", format_datetime(closed_at)));
+        output.info(&format!("  Closed: {}", output.dates().format(closed_at)));
     }
 
-    // Description
-    output.info("");
-    output.info("Description:");
-    for line in ticket.description.lines() {
-        output.info(&format!("  {line}"));
+    // Metadata, user-defined fields and attached files
+    output_metadata(ticket, output);
+    if !extras.fields.is_empty() {
+        output.info("");
+        output.info("Fields:");
+        for (name, value) in &extras.fields {
+            output.info(&format!("  {name}: {value}"));
+        }
     }
-
-    // Tasks
-    if show_tasks && !ticket.tasks.is_empty() {
+    if !extras.attachments.is_empty() {
         output.info("");
-        output.info("Tasks:");
-        let completed = ticket.tasks.iter().filter(|t| t.completed).count();
-        output.info(&format!("  Progress: {}/{}", completed, ticket.tasks.len()));
+        output.info("Attachments:");
+        for attachment in &extras.attachments {
+            output.info(&format!(
+                "  {} ({} bytes)",
+                attachment.name, attachment.size
+            ));
+        }
+    }
+
+    // Children of an epic
+    let children = hierarchy::children(ticket, tickets);
+    if !children.is_empty() {
         output.info("");
+        output.info(&format!("Children: {}", Rollup::of(ticket, tickets)));
+        for child in children {
+            let checkbox = if child.status == Status::Done {
+                "✓"
+            } else {
+                "○"
+            };
+            output.info(&format!("  {checkbox} {} ({})", child.slug, child.status));
+        }
+    }
+
+    for &section in sections {
+        output_plain_section(ticket, section, expand, project_root, tickets, output);
+    }
+}
 
-        for task in &ticket.tasks {
-            let checkbox = if task.completed { "✓" } else { "○" };
-            output.info(&format!("  {} {}", checkbox, task.title));
-            if task.completed {
-                if let Some(completed_at) = task.completed_at {
+/// Output a single optional section in plain text format
+fn output_plain_section(
+    ticket: &Ticket,
+    section: Section,
+    expand: bool,
+    project_root: &Path,
+    tickets: &[Ticket],
+    output: &OutputFormatter,
+) {
+    match section {
+        Section::Description => {
+            output.info("");
+            output.info("Description:");
+            for line in ticket.description.lines() {
+                output.info(&format!("  {line}"));
+            }
+        },
+        Section::Tasks if !ticket.tasks.is_empty() => {
+            output.info("");
+            output.info("Tasks:");
+            output.info(&format!(
+                "  Progress: {}/{}",
+                ticket.completed_tasks_count(),
+                ticket.tasks.len()
+            ));
+            output.info("");
+
+            for task in &ticket.tasks {
+                let checkbox = if task.completed { "✓" } else { "○" };
+                output.info(&format!("  {} {}", checkbox, task.title));
+                if let Some(completed_at) = task.completed_at.filter(|_| task.completed) {
                     output.info(&format!(
                         "      Completed: {}",
-                        format_datetime(completed_at)
+                        output.dates().format(completed_at)
                     ));
                 }
             }
-        }
+        },
+        Section::Tasks => {},
+        Section::Links => {
+            output.info("");
+            output.info("Links:");
+            let links = sections::links(ticket);
+            if links.is_empty() {
+                output.info("  (none)");
+            }
+            for link in links.iter().filter(|l| !l.is_ticket_link()) {
+                output.info(&format!("  {}: {}", link.relation, link.target));
+            }
+            for line in relations::render_relations(ticket, tickets) {
+                output.info(&format!("  {line}"));
+            }
+            for line in dependencies::render_graph(ticket, tickets) {
+                output.info(&format!("  {line}"));
+            }
+        },
+        Section::History => {
+            output.info("");
+            output.info("History:");
+            let events = sections::load_history(project_root, ticket);
+            let (visible, hidden) = sections::visible_history(&events, expand);
+            if hidden > 0 {
+                output.info(&format!(
+                    "  ... {hidden} earlier events (use --expand to show all)"
+                ));
+            }
+            for event in visible {
+                output.info(&format!("  {} {event}", output.dates().format(event.at)));
+            }
+        },
+        Section::Spec => {
+            output.info("");
+            output.info("Specs:");
+            let specs = sections::linked_specs(&project_root.join(".vibe-ticket"), ticket);
+            if specs.is_empty() {
+                output.info("  (none)");
+            }
+            for spec in specs {
+                output.info(&format!("  {} {} [{}]", spec.id, spec.title, spec.phase));
+            }
+        },
+        Section::Worktree => {
+            output.info("");
+            match sections::ticket_worktree(project_root, ticket) {
+                Some(path) => output.info(&format!("Worktree: {}", path.display())),
+                None => output.info("Worktree: (none)"),
+            }
+        },
     }
+}
+
+/// Slug of the parent of a ticket, or its short ID if it no longer exists
+fn parent_slug(ticket: &Ticket, tickets: &[Ticket]) -> Option<String> {
+    let parent = ticket.parent.as_ref()?;
+    Some(
+        tickets
+            .iter()
+            .find(|t| &t.id == parent)
+            .map_or_else(|| parent.short(), |t| t.slug.clone()),
+    )
+}
 
-    // Metadata
+/// Output the well-known metadata entries of a ticket
+fn output_metadata(ticket: &Ticket, output: &OutputFormatter) {
     if !ticket.metadata.is_empty() {
         output.info("");
         output.info("Metadata:");
@@ -177,6 +446,21 @@ fn output_plain(
             output.info(&format!("  Close message: {msg}"));
         }
 
+        // Show forwarding reference of a moved ticket
+        if let Some(project) = ticket
+            .metadata
+            .get("moved_to")
+            .and_then(|v| v.get("project"))
+            .and_then(serde_json::Value::as_str)
+        {
+            output.info(&format!("  Moved to: {project}"));
+        }
+
+        // Show the issue the ticket was created from
+        if let Some(url) = crate::integration::issues::origin_url(ticket) {
+            output.info(&format!("  Origin: {url}"));
+        }
+
         // Show archived status if present
         if ticket
             .metadata
@@ -190,29 +474,51 @@ fn output_plain(
             }
         }
     }
-
-    // History (placeholder for future implementation)
-    if show_history {
-        output.info("");
-        output.info("History:");
-        output.info("  (History tracking not yet implemented)");
-    }
 }
 
 /// Output ticket information in markdown format
-fn output_markdown(ticket: &crate::core::Ticket, show_tasks: bool, _output: &OutputFormatter) {
+fn output_markdown(
+    ticket: &Ticket,
+    sections: &[Section],
+    expand: bool,
+    project_root: &Path,
+    tickets: &[Ticket],
+    extras: &Extras,
+    output: &OutputFormatter,
+) {
     // Title and metadata
     println!("# {}", ticket.title);
     println!();
     println!("**ID**: `{}`", ticket.id);
     println!("**Slug**: `{}`", ticket.slug);
-    println!("**Status**: {}", ticket.status);
-    println!("**Priority**: {}", ticket.priority);
+    println!(
+        "**Status**: {}",
+        output.visuals().status_text(ticket.status)
+    );
+    println!(
+        "**Priority**: {}",
+        output.visuals().priority_text(ticket.priority)
+    );
+    if let Some(severity) = ticket.severity {
+        println!("**Severity**: {severity}");
+    }
 
     if let Some(assignee) = &ticket.assignee {
         println!("**Assignee**: {assignee}");
     }
 
+    if let Some(due) = ticket.due {
+        println!("**Due**: {due}");
+    }
+
+    if let Some(milestone) = &ticket.milestone {
+        println!("**Milestone**: {milestone}");
+    }
+
+    if let Some(parent) = parent_slug(ticket, tickets) {
+        println!("**Parent**: `{parent}`");
+    }
+
     if !ticket.tags.is_empty() {
         println!(
             "**Tags**: {}",
@@ -225,15 +531,27 @@ fn output_markdown(ticket: &crate::core::Ticket, show_tasks: bool, _output: &Out
         );
     }
 
+    for spec in &extras.specs {
+        println!("**Spec**: {} ({})", spec.title, spec.phase);
+    }
+
+    for sighting in &extras.presence {
+        println!("**Active now**: {}", sighting.hint);
+    }
+
     println!();
 
     // Timeline
+    let dates = output.dates();
     println!("## Timeline");
     println!();
-    println!("- **Created**: {}", format_datetime(ticket.created_at));
+    println!(
+        "- **Created**: {}",
+        dates.format_absolute(ticket.created_at)
+    );
 
     if let Some(started_at) = ticket.started_at {
-        println!("- **Started**: {}", format_datetime(started_at));
+        println!("- **Started**: {}", dates.format_absolute(started_at));
 
         let end_time = ticket.closed_at.unwrap_or_else(Utc::now);
         let duration = end_time - started_at;
@@ -243,38 +561,134 @@ fn output_markdown(ticket: &crate::core::Ticket, show_tasks: bool, _output: &Out
     }
 
     if let Some(closed_at) = ticket.closed_at {
-        println!("- **Closed**: {}", format_datetime(closed_at));
+        println!("- **Closed**: {}", dates.format_absolute(closed_at));
     }
 
     println!();
 
-    // Description
-    println!("## Description");
-    println!();
-    println!("{}", ticket.description);
-    println!();
+    let children = hierarchy::children(ticket, tickets);
+    if !children.is_empty() {
+        println!("## Children");
+        println!();
+        println!("Progress: {}", Rollup::of(ticket, tickets));
+        println!();
+        for child in children {
+            let checkbox = if child.status == Status::Done {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            println!("- {checkbox} `{}` {}", child.slug, child.title);
+        }
+        println!();
+    }
 
-    // Tasks
-    if show_tasks && !ticket.tasks.is_empty() {
-        println!("## Tasks");
+    if !extras.fields.is_empty() {
+        println!("## Fields");
         println!();
-        let completed = ticket.tasks.iter().filter(|t| t.completed).count();
-        println!("Progress: {}/{}", completed, ticket.tasks.len());
+        for (name, value) in &extras.fields {
+            println!("- **{name}**: {value}");
+        }
         println!();
+    }
 
-        for task in &ticket.tasks {
-            let checkbox = if task.completed { "[x]" } else { "[ ]" };
-            println!("- {} {}", checkbox, task.title);
+    if !extras.attachments.is_empty() {
+        println!("## Attachments");
+        println!();
+        for attachment in &extras.attachments {
+            println!("- `{}` ({} bytes)", attachment.name, attachment.size);
         }
         println!();
     }
+
+    for &section in sections {
+        output_markdown_section(ticket, section, expand, project_root, tickets, dates);
+    }
 }
 
-/// Format datetime for display
-fn format_datetime(dt: DateTime<Utc>) -> String {
-    dt.with_timezone(&Local)
-        .format("%Y-%m-%d %H:%M")
-        .to_string()
+/// Output a single optional section in markdown format
+fn output_markdown_section(
+    ticket: &Ticket,
+    section: Section,
+    expand: bool,
+    project_root: &Path,
+    tickets: &[Ticket],
+    dates: &DateFormat,
+) {
+    match section {
+        Section::Description => {
+            println!("## Description");
+            println!();
+            println!("{}", ticket.description);
+        },
+        Section::Tasks if !ticket.tasks.is_empty() => {
+            println!("## Tasks");
+            println!();
+            println!(
+                "Progress: {}/{}",
+                ticket.completed_tasks_count(),
+                ticket.tasks.len()
+            );
+            println!();
+
+            for task in &ticket.tasks {
+                let checkbox = if task.completed { "[x]" } else { "[ ]" };
+                println!("- {} {}", checkbox, task.title);
+            }
+        },
+        Section::Tasks => return,
+        Section::Links => {
+            println!("## Links");
+            println!();
+            for link in sections::links(ticket)
+                .iter()
+                .filter(|l| !l.is_ticket_link())
+            {
+                println!("- **{}**: {}", link.relation, link.target);
+            }
+            for line in relations::render_relations(ticket, tickets) {
+                println!("- {line}");
+            }
+            let graph = dependencies::render_graph(ticket, tickets);
+            if !graph.is_empty() {
+                println!();
+                println!("```");
+                for line in graph {
+                    println!("{line}");
+                }
+                println!("```");
+            }
+        },
+        Section::History => {
+            println!("## History");
+            println!();
+            let events = sections::load_history(project_root, ticket);
+            let (visible, hidden) = sections::visible_history(&events, expand);
+            if hidden > 0 {
+                println!("_{hidden} earlier events hidden_");
+                println!();
+            }
+            for event in visible {
+                println!("- {} {event}", dates.format_absolute(event.at));
+            }
+        },
+        Section::Spec => {
+            println!("## Specs");
+            println!();
+            for spec in sections::linked_specs(&project_root.join(".vibe-ticket"), ticket) {
+                println!("- `{}` {} ({})", spec.id, spec.title, spec.phase);
+            }
+        },
+        Section::Worktree => {
+            println!("## Worktree");
+            println!();
+            match sections::ticket_worktree(project_root, ticket) {
+                Some(path) => println!("`{}`", path.display()),
+                None => println!("_none_"),
+            }
+        },
+    }
+    println!();
 }
 
 #[cfg(test)]
@@ -284,7 +698,7 @@ mod tests {
     #[test]
     fn test_format_datetime() {
         let dt = Utc::now();
-        let formatted = format_datetime(dt);
+        let formatted = DateFormat::default().format(dt);
         assert!(!formatted.is_empty());
         assert!(formatted.contains('-'));
         assert!(formatted.contains(':'));
@@ -3,22 +3,44 @@
 //! This module implements all handlers for specification management commands,
 //! supporting the three-phase spec-driven development workflow.
 
+use crate::cli::handlers::spec_comment::{check_review, print_comments};
 use crate::cli::output::OutputFormatter;
+use crate::cli::{find_project_root, slugify};
+use crate::config::{Config, Identity, SpecConfig};
+use crate::core::{Ticket, TicketId};
 use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::project::Project;
+use crate::specs::approval::{self, Approval, Decision, PhaseApproval};
+use crate::specs::estimate::{self, EstimateSummary, SpecTask, TicketEstimates};
 use crate::specs::{
-    SpecDocumentType, SpecManager, SpecPhase, SpecTemplate, Specification, TemplateEngine,
+    DataModelField, RequirementLink, SpecDocumentType, SpecManager, SpecMetadata, SpecProgress,
+    SpecTemplate, SpecVersion, Specification, TemplateEngine, delta, export, history,
+    lint::{self, LintDocument},
+    packs, refs,
+    requirement::{self, RequirementSummary},
 };
+use crate::storage::{FileStorage, TicketRepository};
 use chrono::Utc;
 use std::env;
 use std::fs;
+use std::io::{BufRead, Write as IoWrite};
 use std::path::Path;
 
 /// Handle spec init command
+///
+/// With `amends`, the spec is a delta spec of that completed spec (see
+/// [`crate::specs::delta`]): its requirements start as a copy of the amended
+/// requirements with change markers, and it inherits the tags unless given.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_spec_init(
     title: String,
     description: Option<String>,
     ticket: Option<String>,
     tags: Option<String>,
+    fields: &[String],
+    amends: Option<&str>,
+    template: Option<&str>,
     project: Option<String>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
@@ -37,21 +59,51 @@ pub fn handle_spec_init(
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
+    let base = amends
+        .map(|id| amended_spec(&spec_manager, id))
+        .transpose()?;
+
     // Parse tags
-    let tag_list: Vec<String> = tags
-        .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
-        .unwrap_or_default();
+    let tag_list: Vec<String> = tags.map_or_else(
+        || {
+            base.as_ref()
+                .map(|b| b.metadata.tags.clone())
+                .unwrap_or_default()
+        },
+        |t| t.split(',').map(|s| s.trim().to_string()).collect(),
+    );
 
     // Create new specification
-    let spec = Specification::new(
+    let mut spec = Specification::new(
         title.clone(),
         description.clone().unwrap_or_default(),
         ticket,
         tag_list,
     );
+    spec.metadata.fields.extend(parse_fields(fields)?);
+    spec.metadata.amends = base.as_ref().map(|b| b.metadata.id.clone());
+    spec.metadata.template = template
+        .map(|name| packs::find(&packs::templates_dir(&project_dir), name))
+        .transpose()?
+        .map(|pack| pack.name);
 
     // Save specification
     spec_manager.save(&spec)?;
+    emit_cli_event(
+        &current_dir,
+        &TicketEvent::SpecCreated(spec.metadata.clone()),
+    );
+    if let Some(base) = &base {
+        let requirements = delta::inherit(
+            &base.metadata,
+            base.requirements.as_deref().unwrap_or_default(),
+        );
+        spec_manager.update_document(
+            &spec.metadata.id,
+            SpecDocumentType::Requirements,
+            &requirements,
+        )?;
+    }
 
     formatter.success(&format!(
         "Created new specification '{}' with ID: {}",
@@ -66,15 +118,27 @@ pub fn handle_spec_init(
             "description": description,
             "ticket_id": spec.metadata.ticket_id,
             "tags": spec.metadata.tags,
+            "fields": spec.metadata.fields,
+            "amends": spec.metadata.amends,
+            "template": spec.metadata.template,
         }))?;
     } else {
         formatter.info(&format!("Specification ID: {}", spec.metadata.id));
+        if let Some(base) = &base {
+            formatter.info(&format!(
+                "Amends: {} ({}); requirements inherited with change markers",
+                base.metadata.title, base.metadata.id
+            ));
+        }
         if let Some(desc) = description {
             formatter.info(&format!("Description: {desc}"));
         }
         if let Some(ticket_id) = &spec.metadata.ticket_id {
             formatter.info(&format!("Associated ticket: {ticket_id}"));
         }
+        if let Some(template) = &spec.metadata.template {
+            formatter.info(&format!("Template: {template}"));
+        }
         formatter.info("\nNext steps:");
         formatter.info("  1. Define requirements: vibe-ticket spec requirements");
         formatter.info("  2. Create design: vibe-ticket spec design");
@@ -84,6 +148,39 @@ pub fn handle_spec_init(
     Ok(())
 }
 
+/// Handle spec template list command
+///
+/// Lists the template packs of the project (see [`crate::specs::packs`])
+/// after the built-in templates.
+pub fn handle_spec_template_list(project: Option<&str>, formatter: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project)?;
+    let dir = packs::templates_dir(&project_root.join(".vibe-ticket"));
+    let templates = packs::list(&dir)?;
+
+    if formatter.is_json() {
+        return formatter.json(&serde_json::json!({
+            "directory": dir,
+            "templates": templates,
+        }));
+    }
+    formatter.info("default (built-in): requirements, design, tasks");
+    for pack in &templates {
+        let documents: Vec<_> = pack
+            .documents
+            .iter()
+            .map(SpecDocumentType::as_str)
+            .collect();
+        formatter.info(&format!("{}: {}", pack.name, documents.join(", ")));
+    }
+    if templates.is_empty() {
+        formatter.info(&format!(
+            "Add Markdown templates to {} to use them with `spec init --template`",
+            dir.display()
+        ));
+    }
+    Ok(())
+}
+
 /// Handle spec requirements command
 pub fn handle_spec_requirements(
     spec: Option<String>,
@@ -118,9 +215,19 @@ pub fn handle_spec_requirements(
 
     if complete {
         // Mark requirements phase as complete
+        if let Some(changes) = requirement_changes(&spec_manager, &specification)? {
+            specification.metadata.superseded = delta::superseded(&changes);
+        }
+        let newly_completed = !specification.metadata.progress.requirements_completed;
         specification.metadata.progress.requirements_completed = true;
         specification.metadata.updated_at = Utc::now();
         spec_manager.save(&specification)?;
+        if newly_completed {
+            emit_cli_event(
+                &current_dir,
+                &TicketEvent::SpecPhaseCompleted(spec_id, SpecDocumentType::Requirements),
+            );
+        }
 
         formatter.success(&format!(
             "Marked requirements phase as complete for spec '{}'",
@@ -134,17 +241,19 @@ pub fn handle_spec_requirements(
 
     if !doc_path.exists() {
         // Create from template
-        let mut engine = TemplateEngine::new();
-        engine.set_variable("spec_id".to_string(), spec_id);
-
         let template = SpecTemplate::for_document_type(
             SpecDocumentType::Requirements,
             specification.metadata.title.clone(),
-            Some(specification.metadata.description),
+            Some(specification.metadata.description.clone()),
         );
 
-        let content = engine.generate(&template);
-        fs::write(&doc_path, content).context("Failed to create requirements document")?;
+        let content = generate_document(
+            &current_dir,
+            &specification.metadata,
+            SpecDocumentType::Requirements,
+            template,
+        )?;
+        spec_manager.update_document(&spec_id, SpecDocumentType::Requirements, &content)?;
 
         formatter.info(&format!(
             "Created requirements document: {}",
@@ -154,7 +263,7 @@ pub fn handle_spec_requirements(
 
     if editor {
         // Open in editor
-        open_in_editor(&doc_path)?;
+        edit_document(&spec_manager, &spec_id, SpecDocumentType::Requirements)?;
         formatter.success("Requirements document saved");
     } else {
         // Display content
@@ -203,11 +312,33 @@ pub fn handle_spec_design(
         formatter.warning("Requirements phase is not complete. Consider completing it first.");
     }
 
+    // Writing the design needs the required approvals of the requirements
+    if complete
+        || editor
+        || !spec_manager
+            .get_document_path(&spec_id, SpecDocumentType::Design)
+            .exists()
+    {
+        check_approved(
+            &current_dir,
+            &specification,
+            SpecDocumentType::Requirements,
+            "work on the design",
+        )?;
+    }
+
     if complete {
         // Mark design phase as complete
+        let newly_completed = !specification.metadata.progress.design_completed;
         specification.metadata.progress.design_completed = true;
         specification.metadata.updated_at = Utc::now();
         spec_manager.save(&specification)?;
+        if newly_completed {
+            emit_cli_event(
+                &current_dir,
+                &TicketEvent::SpecPhaseCompleted(spec_id, SpecDocumentType::Design),
+            );
+        }
 
         formatter.success(&format!(
             "Marked design phase as complete for spec '{}'",
@@ -223,31 +354,46 @@ pub fn handle_spec_design(
         // Create from template with requirements summary
         let requirements_path =
             spec_manager.get_document_path(&spec_id, SpecDocumentType::Requirements);
-        let requirements_summary = if requirements_path.exists() {
-            // Extract summary from requirements doc
-            "See requirements document for details."
+        let (requirements_summary, requirement_links) = if requirements_path.exists() {
+            // Link to the sections of the requirements doc
+            let requirements = fs::read_to_string(&requirements_path)
+                .context("Failed to read requirements document")?;
+            (
+                "See requirements document for details.",
+                RequirementLink::from_markdown(&requirements),
+            )
         } else {
-            "Requirements not yet defined."
+            ("Requirements not yet defined.", Vec::new())
         };
 
-        let mut engine = TemplateEngine::new();
-        engine.set_variable("spec_id".to_string(), spec_id);
+        let data_model = specification
+            .metadata
+            .ticket_id
+            .as_deref()
+            .map(|ticket_ref| linked_ticket_fields(&project_dir, ticket_ref))
+            .unwrap_or_default();
+
+        let template = SpecTemplate::Design {
+            title: specification.metadata.title.clone(),
+            requirements_summary: requirements_summary.to_string(),
+            data_model,
+            requirement_links,
+        };
 
-        let template = SpecTemplate::for_document_type(
+        let content = generate_document(
+            &current_dir,
+            &specification.metadata,
             SpecDocumentType::Design,
-            specification.metadata.title,
-            Some(requirements_summary.to_string()),
-        );
-
-        let content = engine.generate(&template);
-        fs::write(&doc_path, content).context("Failed to create design document")?;
+            template,
+        )?;
+        spec_manager.update_document(&spec_id, SpecDocumentType::Design, &content)?;
 
         formatter.info(&format!("Created design document: {}", doc_path.display()));
     }
 
     if editor {
         // Open in editor
-        open_in_editor(&doc_path)?;
+        edit_document(&spec_manager, &spec_id, SpecDocumentType::Design)?;
         formatter.success("Design document saved");
     } else {
         // Display content
@@ -258,6 +404,71 @@ pub fn handle_spec_design(
     Ok(())
 }
 
+/// Generates a spec document from the template pack of the spec, or from
+/// `builtin` when the spec has none or its pack lacks the document
+///
+/// The title, description, ticket, date, author and ID of the spec are
+/// available to templates as variables.
+fn generate_document(
+    project_root: &Path,
+    metadata: &SpecMetadata,
+    doc_type: SpecDocumentType,
+    builtin: SpecTemplate,
+) -> Result<String> {
+    let author = Identity::resolve(&Config::load_for_project(project_root)?.user).name;
+    let mut engine = TemplateEngine::new();
+    for (key, value) in [
+        ("spec_id", metadata.id.clone()),
+        ("title", metadata.title.clone()),
+        ("description", metadata.description.clone()),
+        ("ticket", metadata.ticket_id.clone().unwrap_or_default()),
+        ("author", author),
+    ] {
+        engine.set_variable(key.to_string(), value);
+    }
+
+    let pack = metadata
+        .template
+        .as_deref()
+        .map(|name| {
+            packs::find(
+                &packs::templates_dir(&project_root.join(".vibe-ticket")),
+                name,
+            )
+        })
+        .transpose()?;
+    let custom = pack
+        .map(|pack| pack.document(doc_type))
+        .transpose()?
+        .flatten();
+    Ok(custom.map_or_else(
+        || engine.generate(&builtin),
+        |template| engine.render(&template),
+    ))
+}
+
+/// Describe the custom fields of the ticket linked to a spec
+///
+/// A missing or unreadable ticket simply yields no fields, leaving the
+/// placeholder row in the generated data model table.
+fn linked_ticket_fields(project_dir: &Path, ticket_ref: &str) -> Vec<DataModelField> {
+    let storage = FileStorage::new(project_dir);
+    let Ok(ticket_id) = super::resolve_ticket_ref(&storage, ticket_ref) else {
+        return Vec::new();
+    };
+    let Ok(ticket) = storage.load(&ticket_id) else {
+        return Vec::new();
+    };
+
+    let mut fields: Vec<_> = ticket
+        .metadata
+        .iter()
+        .map(|(name, value)| DataModelField::from_value(name, value))
+        .collect();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    fields
+}
+
 /// Handle spec tasks command
 pub fn handle_spec_tasks(
     spec: Option<String>,
@@ -296,11 +507,33 @@ pub fn handle_spec_tasks(
         formatter.warning("Design phase is not complete. Consider completing it first.");
     }
 
+    // Writing the tasks needs the required approvals of the design
+    if complete
+        || editor
+        || !spec_manager
+            .get_document_path(&spec_id, SpecDocumentType::Tasks)
+            .exists()
+    {
+        check_approved(
+            &current_dir,
+            &specification,
+            SpecDocumentType::Design,
+            "work on the tasks",
+        )?;
+    }
+
     if complete {
         // Mark tasks phase as complete
+        let newly_completed = !specification.metadata.progress.tasks_completed;
         specification.metadata.progress.tasks_completed = true;
         specification.metadata.updated_at = Utc::now();
         spec_manager.save(&specification)?;
+        if newly_completed {
+            emit_cli_event(
+                &current_dir,
+                &TicketEvent::SpecPhaseCompleted(spec_id, SpecDocumentType::Tasks),
+            );
+        }
 
         formatter.success(&format!(
             "Marked tasks phase as complete for spec '{}'",
@@ -321,29 +554,36 @@ pub fn handle_spec_tasks(
             "Design not yet defined."
         };
 
-        let mut engine = TemplateEngine::new();
-        engine.set_variable("spec_id".to_string(), spec_id);
-
         let template = SpecTemplate::for_document_type(
             SpecDocumentType::Tasks,
-            specification.metadata.title,
+            specification.metadata.title.clone(),
             Some(design_summary.to_string()),
         );
 
-        let content = engine.generate(&template);
-        fs::write(&doc_path, content).context("Failed to create tasks document")?;
+        let content = generate_document(
+            &current_dir,
+            &specification.metadata,
+            SpecDocumentType::Tasks,
+            template,
+        )?;
+        spec_manager.update_document(&spec_id, SpecDocumentType::Tasks, &content)?;
 
         formatter.info(&format!("Created tasks document: {}", doc_path.display()));
     }
 
     if export_tickets {
-        // TODO: Implement task export to tickets
-        formatter.warning("Task export to tickets is not yet implemented");
+        return export_tasks(
+            &current_dir,
+            &spec_manager,
+            &mut specification,
+            &doc_path,
+            formatter,
+        );
     }
 
     if editor {
         // Open in editor
-        open_in_editor(&doc_path)?;
+        edit_document(&spec_manager, &spec_id, SpecDocumentType::Tasks)?;
         formatter.success("Tasks document saved");
     } else {
         // Display content
@@ -354,6 +594,467 @@ pub fn handle_spec_tasks(
     Ok(())
 }
 
+/// Exports the tasks of a spec to tickets and reports the created tickets
+///
+/// The tasks phase needs its required approvals first.
+fn export_tasks(
+    current_dir: &Path,
+    spec_manager: &SpecManager,
+    specification: &mut Specification,
+    doc_path: &Path,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    check_approved(
+        current_dir,
+        specification,
+        SpecDocumentType::Tasks,
+        "export the tasks",
+    )?;
+
+    let content = fs::read_to_string(doc_path).context("Failed to read tasks document")?;
+    let (created, existing) = export_task_tickets(
+        current_dir,
+        spec_manager,
+        &mut specification.metadata,
+        &content,
+    )?;
+
+    if formatter.is_json() {
+        return formatter.json(&serde_json::json!({
+            "status": "success",
+            "spec_id": specification.metadata.id,
+            "created": created.iter().map(|ticket| serde_json::json!({
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+                "title": ticket.title,
+                "parent": ticket.parent.as_ref().map(ToString::to_string),
+            })).collect::<Vec<_>>(),
+            "already_exported": existing,
+            "task_tickets": specification.metadata.task_tickets,
+        }));
+    }
+    formatter.success(&format!(
+        "Exported {} task(s) of spec '{}' to tickets",
+        created.len(),
+        specification.metadata.title
+    ));
+    for ticket in &created {
+        formatter.info(&format!("  {} - {}", ticket.slug, ticket.title));
+    }
+    if existing > 0 {
+        formatter.info(&format!("{existing} task(s) already had a ticket"));
+    }
+    Ok(())
+}
+
+/// Create tickets for the tasks of a spec that have none yet
+///
+/// Follows [`crate::specs::export`]: tickets are nested like the checklist
+/// items, and the mapping of tasks to tickets is saved in `spec.json` after
+/// each ticket, so that an interrupted export is resumed without duplicates.
+/// Returns the created tickets and the number of tasks that already had one.
+fn export_task_tickets(
+    project_root: &Path,
+    spec_manager: &SpecManager,
+    metadata: &mut SpecMetadata,
+    content: &str,
+) -> Result<(Vec<Ticket>, usize)> {
+    let project = Project::open(project_root)?;
+    let storage = project.storage();
+    let spec_ticket = metadata
+        .ticket_id
+        .as_deref()
+        .and_then(|ticket_ref| super::resolve_ticket_ref(storage, ticket_ref).ok());
+    let slug_prefix = chrono::Local::now().format("%Y%m%d%H%M").to_string();
+
+    let tasks = estimate::parse_tasks(content);
+    let mut ticket_ids: Vec<TicketId> = Vec::with_capacity(tasks.len());
+    let mut created = Vec::new();
+    let mut existing = 0;
+
+    for (index, node) in export::hierarchy(&tasks).iter().enumerate() {
+        let exported = metadata
+            .task_tickets
+            .get(&node.key)
+            .and_then(|id| TicketId::parse_str(id).ok())
+            .filter(|id| storage.exists(id).unwrap_or(false));
+        if let Some(id) = exported {
+            existing += 1;
+            ticket_ids.push(id);
+            continue;
+        }
+
+        let parent = node.parent.map_or_else(
+            || spec_ticket.clone(),
+            |parent| Some(ticket_ids[parent].clone()),
+        );
+        let mut base_slug = slugify(&node.task.title);
+        if base_slug.is_empty() {
+            base_slug = format!("task-{}", index + 1);
+        }
+        let mut slug = format!("{slug_prefix}-{base_slug}");
+        let mut suffix = 1;
+        while storage.ticket_exists_with_slug(&slug)? {
+            suffix += 1;
+            slug = format!("{slug_prefix}-{base_slug}-{suffix}");
+        }
+
+        let mut ticket = export::ticket(metadata, node.task, &slug, parent);
+        storage.assign_short_id(&mut ticket)?;
+        let ticket = project.create(ticket)?;
+        metadata
+            .task_tickets
+            .insert(node.key.clone(), ticket.id.to_string());
+        metadata.updated_at = Utc::now();
+        spec_manager.save_metadata(metadata)?;
+
+        ticket_ids.push(ticket.id.clone());
+        created.push(ticket);
+    }
+
+    Ok((created, existing))
+}
+
+/// Handle spec history command
+pub fn handle_spec_history(
+    spec: Option<String>,
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let (spec_manager, specification) = load_spec_for(spec, project)?;
+    let snapshots = spec_manager
+        .history(&specification.metadata.id)
+        .snapshots()?;
+
+    if formatter.is_json() {
+        return formatter.json(&serde_json::json!({
+            "spec_id": specification.metadata.id,
+            "version": specification.metadata.version.to_string(),
+            "history": snapshots.iter().map(|snapshot| serde_json::json!({
+                "version": snapshot.version.to_string(),
+                "documents": snapshot.documents,
+                "saved_at": snapshot.saved_at,
+            })).collect::<Vec<_>>(),
+        }));
+    }
+
+    formatter.info(&format!(
+        "History of '{}' (current version {}):",
+        specification.metadata.title, specification.metadata.version
+    ));
+    if snapshots.is_empty() {
+        formatter.info("  No saved versions yet");
+    }
+    for snapshot in &snapshots {
+        let saved_at = snapshot
+            .saved_at
+            .map_or_else(String::new, |at| formatter.dates().format(at));
+        let documents: Vec<&str> = snapshot
+            .documents
+            .iter()
+            .map(SpecDocumentType::file_name)
+            .collect();
+        formatter.info(&format!(
+            "  {:<8} {saved_at:<16} {}",
+            snapshot.version.to_string(),
+            documents.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Handle spec diff command
+///
+/// Compares the documents at `from` with those at `to`, or with the current
+/// documents without `to`. Without `from`, the latest saved version before
+/// `to` is used.
+pub fn handle_spec_diff(
+    spec: Option<String>,
+    from: Option<&str>,
+    to: Option<&str>,
+    document: Option<&str>,
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let (spec_manager, specification) = load_spec_for(spec, project)?;
+    let history = spec_manager.history(&specification.metadata.id);
+    let to = to.map(str::parse::<SpecVersion>).transpose()?;
+    let to_version = to
+        .clone()
+        .unwrap_or_else(|| specification.metadata.version.clone());
+    let from = match from {
+        Some(from) => from.parse::<SpecVersion>()?,
+        None => history
+            .snapshots()?
+            .into_iter()
+            .map(|snapshot| snapshot.version)
+            .rfind(|version| *version < to_version)
+            .ok_or_else(|| {
+                VibeTicketError::InvalidInput(format!(
+                    "No saved version of '{}' before {to_version}",
+                    specification.metadata.title
+                ))
+            })?,
+    };
+    let documents = match document {
+        Some(document) => vec![document.parse::<SpecDocumentType>()?],
+        None => history::DOCUMENTS.to_vec(),
+    };
+
+    let to_label = to
+        .as_ref()
+        .map_or_else(|| "current".to_string(), ToString::to_string);
+    let mut diffs = Vec::new();
+    for document in documents {
+        let old = history.content_at(document, &from)?.unwrap_or_default();
+        let new = match &to {
+            Some(to) => history.content_at(document, to)?.unwrap_or_default(),
+            None => spec_document(&specification, document)
+                .unwrap_or_default()
+                .to_string(),
+        };
+        let diff = history::diff(
+            &old,
+            &new,
+            &format!("{}@{from}", document.file_name()),
+            &format!("{}@{to_label}", document.file_name()),
+        );
+        if !diff.is_empty() {
+            diffs.push((document, diff));
+        }
+    }
+
+    if formatter.is_json() {
+        return formatter.json(&serde_json::json!({
+            "spec_id": specification.metadata.id,
+            "from": from.to_string(),
+            "to": to_label,
+            "documents": diffs.iter().map(|(document, diff)| serde_json::json!({
+                "document": document,
+                "diff": diff,
+            })).collect::<Vec<_>>(),
+        }));
+    }
+
+    if diffs.is_empty() {
+        formatter.info(&format!("No changes between {from} and {to_label}"));
+    }
+    for (_, diff) in &diffs {
+        print!("{diff}");
+    }
+    Ok(())
+}
+
+/// Load a spec by ID, or the active spec, with the manager of its project
+fn load_spec_for(
+    spec: Option<String>,
+    project: Option<String>,
+) -> Result<(SpecManager, Specification)> {
+    // Change to project directory if specified
+    if let Some(project_path) = project {
+        std::env::set_current_dir(&project_path)
+            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
+    }
+
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    let project_dir = current_dir.join(".vibe-ticket");
+
+    if !project_dir.exists() {
+        return Err(VibeTicketError::ProjectNotInitialized);
+    }
+
+    let spec_manager = SpecManager::new(project_dir.join("specs"));
+    let spec_id = match spec {
+        Some(id) => id,
+        None => get_active_spec(&project_dir)?,
+    };
+    let specification = spec_manager.load(&spec_id)?;
+    Ok((spec_manager, specification))
+}
+
+/// Content of a document of `specification`, if written
+fn spec_document(specification: &Specification, document: SpecDocumentType) -> Option<&str> {
+    match document {
+        SpecDocumentType::Requirements => specification.requirements.as_deref(),
+        SpecDocumentType::Design => specification.design.as_deref(),
+        SpecDocumentType::Tasks => specification.tasks.as_deref(),
+    }
+}
+
+/// Handle spec check-refs command
+///
+/// Lists the requirement reference problems of the design (see
+/// [`crate::specs::refs`]).
+///
+/// # Errors
+///
+/// Returns an error if the spec cannot be loaded or has reference problems.
+pub fn handle_spec_check_refs(
+    spec: Option<String>,
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    // Change to project directory if specified
+    if let Some(project_path) = project {
+        std::env::set_current_dir(&project_path)
+            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
+    }
+
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    let project_dir = current_dir.join(".vibe-ticket");
+
+    if !project_dir.exists() {
+        return Err(VibeTicketError::ProjectNotInitialized);
+    }
+
+    let spec_manager = SpecManager::new(project_dir.join("specs"));
+    let spec_id = match spec {
+        Some(id) => id,
+        None => get_active_spec(&project_dir)?,
+    };
+    let specification = spec_manager.load(&spec_id)?;
+    let requirements = specification.requirements.as_deref().unwrap_or_default();
+    let design = specification.design.as_deref().unwrap_or_default();
+    let defined = refs::requirement_ids(requirements);
+    let issues = refs::check(requirements, design);
+
+    if formatter.is_json() {
+        formatter.json(&serde_json::json!({
+            "spec_id": spec_id,
+            "requirements": defined,
+            "sections": refs::design_sections(design),
+            "issues": issues,
+        }))?;
+    } else if defined.is_empty() {
+        formatter.info(&format!(
+            "The requirements of '{}' define no IDs; number them like `### REQ-1 Login`",
+            specification.metadata.title
+        ));
+    } else if issues.is_empty() {
+        formatter.success(&format!(
+            "The design of '{}' references all {} requirement(s)",
+            specification.metadata.title,
+            defined.len()
+        ));
+    } else {
+        for issue in &issues {
+            formatter.warning(&issue.to_string());
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::InvalidInput(format!(
+            "{} requirement reference problem(s) in spec '{}'",
+            issues.len(),
+            specification.metadata.title
+        )))
+    }
+}
+
+/// Handle spec lint command
+///
+/// Checks the structure of the spec documents (see [`crate::specs::lint`])
+/// and counts the open tasks.
+///
+/// # Errors
+///
+/// Returns an error if the spec cannot be loaded or has problems, so that
+/// CI jobs fail on incomplete specs.
+pub fn handle_spec_lint(
+    spec: Option<String>,
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    let (_, specification) = load_spec_for(spec, project)?;
+    let sections = required_sections(&specification)?;
+    let report = lint::lint(std::array::from_fn(|index| LintDocument {
+        content: spec_document(&specification, history::DOCUMENTS[index]),
+        sections: &sections[index],
+    }));
+
+    if formatter.is_json() {
+        formatter.json(&serde_json::json!({
+            "spec_id": specification.metadata.id,
+            "passed": report.passed(),
+            "issues": report.issues,
+            "tasks": report.tasks,
+        }))?;
+    } else {
+        for issue in &report.issues {
+            formatter.warning(&issue.to_string());
+        }
+        formatter.info(&format!(
+            "Tasks: {} of {} done, {} open",
+            report.tasks.done, report.tasks.total, report.tasks.open
+        ));
+        if report.passed() {
+            formatter.success(&format!(
+                "Spec '{}' passed all checks",
+                specification.metadata.title
+            ));
+        }
+    }
+
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::InvalidInput(format!(
+            "{} problem(s) in spec '{}'",
+            report.issues.len(),
+            specification.metadata.title
+        )))
+    }
+}
+
+/// Sections required of each document of `specification`: those of its
+/// template pack, or the defaults
+fn required_sections(specification: &Specification) -> Result<[Vec<String>; 3]> {
+    let pack = specification
+        .metadata
+        .template
+        .as_deref()
+        .map(|name| {
+            let project_dir = env::current_dir()
+                .context("Failed to get current directory")?
+                .join(".vibe-ticket");
+            packs::find(&packs::templates_dir(&project_dir), name)
+        })
+        .transpose()?;
+    let mut sections: [Vec<String>; 3] = Default::default();
+    for (required, document) in sections.iter_mut().zip(history::DOCUMENTS) {
+        let template = pack
+            .as_ref()
+            .map(|pack| pack.document(document))
+            .transpose()?
+            .flatten();
+        *required = template.map_or_else(
+            || lint::default_sections(document),
+            |template| lint::sections(&template),
+        );
+    }
+    Ok(sections)
+}
+
+/// Checks that the design of `specification` references its requirements
+/// consistently, before the design is approved
+fn check_design_refs(specification: &Specification) -> Result<()> {
+    let issues = refs::check(
+        specification.requirements.as_deref().unwrap_or_default(),
+        specification.design.as_deref().unwrap_or_default(),
+    );
+    if issues.is_empty() {
+        return Ok(());
+    }
+    let list: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    Err(VibeTicketError::InvalidInput(format!(
+        "Cannot approve the design: {}. See `vibe-ticket spec check-refs {}`",
+        list.join("; "),
+        specification.metadata.id
+    )))
+}
+
 /// Handle spec status command
 pub fn handle_spec_status(
     spec: Option<String>,
@@ -385,6 +1086,28 @@ pub fn handle_spec_status(
     // Load specification
     let specification = spec_manager.load(&spec_id)?;
 
+    // Estimates of the tasks and, in detail, of the tickets implementing them
+    let estimates = EstimateSummary::of(&estimate::parse_tasks(
+        specification.tasks.as_deref().unwrap_or_default(),
+    ));
+    let ticket_estimates = if detailed {
+        let tickets = FileStorage::new(&project_dir).load_all()?;
+        Some(TicketEstimates::of(&specification.metadata, &tickets))
+    } else {
+        None
+    };
+    let config = Config::load_for_project(&current_dir)?;
+
+    // Structured requirements and where the design and tasks cite them
+    let requirements =
+        requirement::parse(specification.requirements.as_deref().unwrap_or_default());
+    let traces = requirement::trace(
+        &requirements,
+        specification.design.as_deref().unwrap_or_default(),
+        specification.tasks.as_deref().unwrap_or_default(),
+    );
+    let requirement_summary = RequirementSummary::of(&requirements, &traces);
+
     if formatter.is_json() {
         formatter.json(&serde_json::json!({
             "spec_id": specification.metadata.id,
@@ -395,7 +1118,12 @@ pub fn handle_spec_status(
                 "design": specification.metadata.progress.design_completed,
                 "tasks": specification.metadata.progress.tasks_completed,
             },
-            "approval": specification.metadata.progress.approval_status,
+            "approval": specification.metadata.progress.approvals_by_phase(&config.spec),
+            "approvals": specification.metadata.progress.approvals,
+            "estimates": estimates,
+            "ticket_estimates": ticket_estimates,
+            "requirements": requirement_summary,
+            "traceability": traces,
         }))?;
     } else {
         formatter.info(&format!(
@@ -407,45 +1135,300 @@ pub fn handle_spec_status(
             specification.metadata.progress.current_phase()
         ));
 
-        formatter.info("\nProgress:");
-        formatter.info(&format!(
-            "  Requirements: {}",
-            if specification.metadata.progress.requirements_completed {
-                "✓ Complete"
-            } else {
-                "○ In Progress"
+        print_progress(&specification.metadata.progress, formatter);
+        print_approvals(&specification, &config.spec, detailed, formatter);
+
+        if detailed {
+            formatter.info(&format!("\nCreated: {}", specification.metadata.created_at));
+            formatter.info(&format!("Updated: {}", specification.metadata.updated_at));
+            if let Some(ticket_id) = &specification.metadata.ticket_id {
+                formatter.info(&format!("Ticket: {ticket_id}"));
             }
-        ));
-        formatter.info(&format!(
-            "  Design: {}",
-            if specification.metadata.progress.design_completed {
-                "✓ Complete"
-            } else {
-                "○ Pending"
+            if !specification.metadata.tags.is_empty() {
+                formatter.info(&format!("Tags: {}", specification.metadata.tags.join(", ")));
             }
-        ));
+            if estimates.tasks > 0 {
+                formatter.info(&format!("\nEstimates: {estimates}"));
+            }
+            if let Some(totals) = ticket_estimates.filter(|totals| totals.tickets > 0) {
+                formatter.info(&format!(
+                    "Tickets: {} linked, {} estimated, {}pt ({})",
+                    totals.tickets,
+                    totals.estimated,
+                    totals.points,
+                    compare_points(totals.points, estimates.total_points)
+                ));
+            }
+            print_requirements(&requirement_summary, &traces, formatter);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print whether each phase is complete
+fn print_progress(progress: &SpecProgress, formatter: &OutputFormatter) {
+    formatter.info("\nProgress:");
+    for (phase, completed, incomplete) in [
+        (
+            "Requirements",
+            progress.requirements_completed,
+            "○ In Progress",
+        ),
+        ("Design", progress.design_completed, "○ Pending"),
+        ("Tasks", progress.tasks_completed, "○ Pending"),
+    ] {
         formatter.info(&format!(
-            "  Tasks: {}",
-            if specification.metadata.progress.tasks_completed {
+            "  {phase}: {}",
+            if completed {
                 "✓ Complete"
             } else {
-                "○ Pending"
+                incomplete
             }
         ));
+    }
+}
 
-        if detailed {
-            formatter.info(&format!("\nCreated: {}", specification.metadata.created_at));
-            formatter.info(&format!("Updated: {}", specification.metadata.updated_at));
-            if let Some(ticket_id) = &specification.metadata.ticket_id {
-                formatter.info(&format!("Ticket: {ticket_id}"));
+/// Print the counts of the structured requirements and where each is cited
+fn print_requirements(
+    summary: &RequirementSummary,
+    traces: &[requirement::Trace],
+    formatter: &OutputFormatter,
+) {
+    if summary.total == 0 {
+        return;
+    }
+    formatter.info(&format!(
+        "\nRequirements: {} ({} EARS, {} scenarios), {} in design, {} in tasks",
+        summary.total, summary.ears, summary.scenarios, summary.in_design, summary.in_tasks
+    ));
+    for trace in traces {
+        let design = if trace.design.is_empty() {
+            "not in design".to_string()
+        } else {
+            format!("design: {}", trace.design.join(", "))
+        };
+        let tasks = if trace.tasks.is_empty() {
+            "no tasks".to_string()
+        } else {
+            format!("{} task(s)", trace.tasks.len())
+        };
+        formatter.info(&format!("  {}: {design}; {tasks}", trace.id));
+    }
+}
+
+/// Handle spec estimate command
+///
+/// Writes story point estimates into the tasks document, either for a
+/// single task given with `task` and `points` or by prompting for each
+/// task. With `voters`, every task is estimated by planning poker.
+pub fn handle_spec_estimate(
+    spec: Option<String>,
+    all: bool,
+    voters: &[String],
+    task: Option<usize>,
+    points: Option<u32>,
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    // Change to project directory if specified
+    if let Some(project_path) = project {
+        std::env::set_current_dir(&project_path)
+            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
+    }
+
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    let project_dir = current_dir.join(".vibe-ticket");
+
+    if !project_dir.exists() {
+        return Err(VibeTicketError::ProjectNotInitialized);
+    }
+
+    let spec_manager = SpecManager::new(project_dir.join("specs"));
+    let spec_id = match spec {
+        Some(id) => id,
+        None => get_active_spec(&project_dir)?,
+    };
+    let content = spec_manager.load(&spec_id)?.tasks.ok_or_else(|| {
+        VibeTicketError::custom(format!(
+            "Spec '{spec_id}' has no tasks document. Create it with 'vibe-ticket spec tasks'"
+        ))
+    })?;
+    let tasks = estimate::parse_tasks(&content);
+
+    let assignments = if let (Some(number), Some(points)) = (task, points) {
+        let task = number
+            .checked_sub(1)
+            .and_then(|index| tasks.get(index))
+            .ok_or_else(|| {
+                VibeTicketError::InvalidInput(format!(
+                    "Task {number} does not exist; the spec has {} tasks",
+                    tasks.len()
+                ))
+            })?;
+        vec![(task.line, points)]
+    } else {
+        let stdin = std::io::stdin();
+        run_estimation(
+            &tasks,
+            all,
+            voters,
+            &mut stdin.lock(),
+            &mut std::io::stderr(),
+        )?
+    };
+
+    let updated = assignments.iter().fold(content, |content, (line, points)| {
+        estimate::set_points(&content, *line, Some(*points))
+    });
+    if !assignments.is_empty() {
+        spec_manager.update_document(&spec_id, SpecDocumentType::Tasks, &updated)?;
+    }
+    let summary = EstimateSummary::of(&estimate::parse_tasks(&updated));
+
+    if formatter.is_json() {
+        formatter.json(&serde_json::json!({
+            "spec_id": spec_id,
+            "estimated": assignments.len(),
+            "estimates": summary,
+        }))?;
+    } else {
+        formatter.success(&format!("Estimated {} task(s)", assignments.len()));
+        formatter.info(&format!("Estimates: {summary}"));
+    }
+
+    Ok(())
+}
+
+/// Answer to an estimation prompt
+enum Answer {
+    Points(u32),
+    Skip,
+    Quit,
+}
+
+/// Prompts for the points of each task to estimate
+///
+/// Tasks that are done or already estimated are only asked for with `all`.
+/// Without voters, the points are entered directly; with voters, each one
+/// picks a card and the agreed value is asked for unless all cards match.
+/// Returns the line of each estimated task with its points.
+fn run_estimation<R: BufRead, W: IoWrite>(
+    tasks: &[SpecTask],
+    all: bool,
+    voters: &[String],
+    input: &mut R,
+    out: &mut W,
+) -> Result<Vec<(usize, u32)>> {
+    let pending: Vec<&SpecTask> = tasks
+        .iter()
+        .filter(|task| all || (task.points.is_none() && !task.done))
+        .collect();
+    let deck: Vec<String> = estimate::DECK.iter().map(ToString::to_string).collect();
+    writeln!(
+        out,
+        "{} task(s) to estimate. Cards: {} ('-' skips, 'q' quits)",
+        pending.len(),
+        deck.join(" ")
+    )
+    .context("Failed to write prompt")?;
+
+    let mut assignments = Vec::new();
+    'tasks: for (i, task) in pending.iter().enumerate() {
+        let current = task
+            .points
+            .map(|points| format!(" (currently {points}pt)"))
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "\n[{}/{}] {}{current}",
+            i + 1,
+            pending.len(),
+            task.title
+        )
+        .context("Failed to write prompt")?;
+
+        let points = if voters.is_empty() {
+            match read_answer(input, out, "Points: ", None)? {
+                Answer::Points(points) => Some(points),
+                Answer::Skip => None,
+                Answer::Quit => break,
+            }
+        } else {
+            let mut ballots = Vec::new();
+            for voter in voters {
+                match read_answer(input, out, &format!("  {voter}: "), None)? {
+                    Answer::Points(points) => ballots.push((voter, points)),
+                    Answer::Skip => {},
+                    Answer::Quit => break 'tasks,
+                }
             }
-            if !specification.metadata.tags.is_empty() {
-                formatter.info(&format!("Tags: {}", specification.metadata.tags.join(", ")));
+            let cards: Vec<u32> = ballots.iter().map(|(_, points)| *points).collect();
+            let summary: Vec<String> = ballots
+                .iter()
+                .map(|(voter, points)| format!("{voter} {points}"))
+                .collect();
+            writeln!(out, "  Votes: {}", summary.join(", ")).context("Failed to write prompt")?;
+
+            if let Some(points) = estimate::consensus(&cards) {
+                writeln!(out, "  Consensus: {points}pt").context("Failed to write prompt")?;
+                Some(points)
+            } else {
+                let suggested = estimate::suggestion(&cards);
+                let prompt = suggested.map_or_else(
+                    || "  Agreed points: ".to_string(),
+                    |points| format!("  No consensus; agreed points [{points}]: "),
+                );
+                match read_answer(input, out, &prompt, suggested)? {
+                    Answer::Points(points) => Some(points),
+                    Answer::Skip => None,
+                    Answer::Quit => break,
+                }
             }
+        };
+
+        if let Some(points) = points {
+            assignments.push((task.line, points));
         }
     }
 
-    Ok(())
+    Ok(assignments)
+}
+
+/// Reads an answer, asking again until it is valid
+///
+/// An empty answer takes `default`, or skips without one; the end of the
+/// input quits.
+fn read_answer<R: BufRead, W: IoWrite>(
+    input: &mut R,
+    out: &mut W,
+    prompt: &str,
+    default: Option<u32>,
+) -> Result<Answer> {
+    loop {
+        write!(out, "{prompt}")
+            .and_then(|()| out.flush())
+            .context("Failed to write prompt")?;
+        let mut line = String::new();
+        if input
+            .read_line(&mut line)
+            .context("Failed to read answer")?
+            == 0
+        {
+            return Ok(Answer::Quit);
+        }
+
+        match (line.trim(), default) {
+            ("", Some(points)) => return Ok(Answer::Points(points)),
+            ("" | "-", _) => return Ok(Answer::Skip),
+            ("q" | "quit", _) => return Ok(Answer::Quit),
+            (answer, _) => match answer.parse() {
+                Ok(points) => return Ok(Answer::Points(points)),
+                Err(_) => writeln!(out, "  Enter a number of points, '-' or 'q'")
+                    .context("Failed to write prompt")?,
+            },
+        }
+    }
 }
 
 /// Handle spec list command
@@ -453,6 +1436,7 @@ pub fn handle_spec_list(
     status: Option<String>,
     phase: Option<String>,
     _archived: bool,
+    filters: &[String],
     project: Option<String>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
@@ -471,11 +1455,20 @@ pub fn handle_spec_list(
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
     let specs = spec_manager.list()?;
+    let field_filters = parse_fields(filters)?;
 
     // Filter specs
     let filtered_specs: Vec<_> = specs
         .into_iter()
         .filter(|spec| {
+            // Filter by custom fields
+            if !field_filters
+                .iter()
+                .all(|(key, value)| spec.fields.get(key) == Some(value))
+            {
+                return false;
+            }
+
             // Filter by status if provided
             if let Some(ref status_filter) = status {
                 let current_status = format!("{:?}", spec.progress.current_phase()).to_lowercase();
@@ -521,6 +1514,7 @@ pub fn handle_spec_list(
                     "phase": format!("{:?}", spec.progress.current_phase()),
                     "created_at": spec.created_at,
                     "updated_at": spec.updated_at,
+                    "fields": spec.fields,
                 })
             })
             .collect();
@@ -551,6 +1545,7 @@ pub fn handle_spec_show(
     spec: String,
     all: bool,
     markdown: bool,
+    comments: bool,
     project: Option<String>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
@@ -569,10 +1564,31 @@ pub fn handle_spec_show(
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
     let specification = spec_manager.load(&spec)?;
+    let (amended, amended_by) = amendment_chain(&spec_manager, &specification.metadata)?;
+    let changes = requirement_changes(&spec_manager, &specification)?;
+    let review_comments = if comments {
+        Some(spec_manager.load_comments(&spec)?)
+    } else {
+        None
+    };
 
     if formatter.is_json() {
-        formatter.json(&serde_json::json!(specification))?;
+        let mut json = serde_json::json!(specification);
+        json["amendments"] = serde_json::json!({
+            "amends": amended.iter().map(|s| &s.id).collect::<Vec<_>>(),
+            "amended_by": amended_by.iter().map(|s| &s.id).collect::<Vec<_>>(),
+            "changes": changes,
+        });
+        if let Some(review_comments) = &review_comments {
+            json["comments"] = serde_json::json!(review_comments);
+        }
+        formatter.json(&json)?;
     } else {
+        if markdown {
+            // Exported documents carry the spec metadata as YAML front matter
+            println!("{}", specification.metadata.front_matter());
+        }
+
         formatter.info(&format!(
             "# Specification: {}",
             specification.metadata.title
@@ -586,6 +1602,13 @@ pub fn handle_spec_show(
             "Phase: {:?}",
             specification.metadata.progress.current_phase()
         ));
+        if !specification.metadata.fields.is_empty() {
+            formatter.info("Fields:");
+            for (key, value) in &specification.metadata.fields {
+                formatter.info(&format!("  {key}: {value}"));
+            }
+        }
+        print_amendments(&amended, &amended_by, changes.as_deref(), formatter);
 
         if all || markdown {
             // Show all documents
@@ -605,11 +1628,261 @@ pub fn handle_spec_show(
                 }
             }
         }
+
+        if let Some(review_comments) = &review_comments {
+            formatter.info("");
+            print_comments(&specification, review_comments, formatter);
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the spec amended by a new delta spec, which must be completed
+fn amended_spec(spec_manager: &SpecManager, spec_id: &str) -> Result<Specification> {
+    let base = spec_manager.load(spec_id)?;
+    if !base.metadata.progress.is_completed() {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Spec '{}' is not completed; only shipped specs can be amended",
+            base.metadata.title
+        )));
+    }
+    Ok(base)
+}
+
+/// Specs amended by `spec`, nearest first, and the specs amending it
+fn amendment_chain(
+    spec_manager: &SpecManager,
+    spec: &SpecMetadata,
+) -> Result<(Vec<SpecMetadata>, Vec<SpecMetadata>)> {
+    let specs = spec_manager.list()?;
+
+    let mut amended: Vec<SpecMetadata> = Vec::new();
+    let mut next = spec.amends.as_deref();
+    while let Some(id) = next {
+        // Stop at missing specs and cycles from hand-edited metadata
+        let Some(base) = specs.iter().find(|s| s.id == id) else {
+            break;
+        };
+        if base.id == spec.id || amended.iter().any(|s| s.id == base.id) {
+            break;
+        }
+        amended.push(base.clone());
+        next = base.amends.as_deref();
+    }
+    let amended_by = specs
+        .into_iter()
+        .filter(|s| s.amends.as_deref() == Some(spec.id.as_str()))
+        .collect();
+
+    Ok((amended, amended_by))
+}
+
+/// Requirement changes of a delta spec against the spec it amends
+///
+/// Returns `None` for specs that amend nothing.
+fn requirement_changes(
+    spec_manager: &SpecManager,
+    spec: &Specification,
+) -> Result<Option<Vec<delta::RequirementChange>>> {
+    let Some(amends) = &spec.metadata.amends else {
+        return Ok(None);
+    };
+    let base = spec_manager.load(amends).ok().and_then(|b| b.requirements);
+    let changes = delta::changes(
+        spec.requirements.as_deref().unwrap_or_default(),
+        base.as_deref(),
+    )?;
+    Ok(Some(changes))
+}
+
+/// Prints the amendment chain and requirement changes of `spec show`
+fn print_amendments(
+    amended: &[SpecMetadata],
+    amended_by: &[SpecMetadata],
+    changes: Option<&[delta::RequirementChange]>,
+    formatter: &OutputFormatter,
+) {
+    if !amended.is_empty() {
+        formatter.info("Amends:");
+        for spec in amended {
+            formatter.info(&format!(
+                "  ← {} ({}) v{}",
+                spec.title, spec.id, spec.version
+            ));
+        }
+    }
+    if !amended_by.is_empty() {
+        formatter.info("Amended by:");
+        for spec in amended_by {
+            formatter.info(&format!("  → {} ({})", spec.title, spec.id));
+        }
+    }
+    let Some(changes) = changes else {
+        return;
+    };
+    let count = |kind| changes.iter().filter(|c| c.kind == kind).count();
+    formatter.info(&format!(
+        "Requirement changes: {} modified, {} removed, {} added, {} unchanged",
+        count(delta::ChangeKind::Modified),
+        count(delta::ChangeKind::Removed),
+        count(delta::ChangeKind::Added),
+        count(delta::ChangeKind::Unchanged)
+    ));
+    for change in changes
+        .iter()
+        .filter(|c| c.kind != delta::ChangeKind::Unchanged)
+    {
+        formatter.info(&format!(
+            "  [{}] {} ({})",
+            change.kind, change.title, change.id
+        ));
+    }
+}
+
+/// Handle spec set command
+///
+/// Sets and removes custom metadata fields stored in `spec.json`.
+pub fn handle_spec_set(
+    spec: &str,
+    fields: &[String],
+    unset: &[String],
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    // Change to project directory if specified
+    if let Some(project_path) = project {
+        std::env::set_current_dir(&project_path)
+            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
+    }
+
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    let project_dir = current_dir.join(".vibe-ticket");
+
+    if !project_dir.exists() {
+        return Err(VibeTicketError::ProjectNotInitialized);
+    }
+
+    if fields.is_empty() && unset.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Specify at least one KEY=VALUE field or --unset KEY".to_string(),
+        ));
+    }
+
+    let spec_manager = SpecManager::new(project_dir.join("specs"));
+    let mut specification = spec_manager.load(spec)?;
+
+    for key in unset {
+        specification.metadata.fields.remove(key);
+    }
+    specification.metadata.fields.extend(parse_fields(fields)?);
+    specification.metadata.updated_at = Utc::now();
+    spec_manager.save(&specification)?;
+
+    if formatter.is_json() {
+        formatter.json(&serde_json::json!({
+            "status": "success",
+            "spec_id": specification.metadata.id,
+            "fields": specification.metadata.fields,
+        }))?;
+    } else {
+        formatter.success(&format!(
+            "Updated fields of specification '{}'",
+            specification.metadata.title
+        ));
+        for (key, value) in &specification.metadata.fields {
+            formatter.info(&format!("  {key}: {value}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle spec link command
+///
+/// Links the spec to the ticket `ticket_ref`, stored by ID, or removes the
+/// link with `unlink`.
+pub fn handle_spec_link(
+    spec: &str,
+    ticket_ref: Option<&str>,
+    unlink: bool,
+    project: Option<String>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    // Change to project directory if specified
+    if let Some(project_path) = project {
+        std::env::set_current_dir(&project_path)
+            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
+    }
+
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    let project_dir = current_dir.join(".vibe-ticket");
+
+    if !project_dir.exists() {
+        return Err(VibeTicketError::ProjectNotInitialized);
+    }
+
+    let spec_manager = SpecManager::new(project_dir.join("specs"));
+    let mut metadata = spec_manager.load(spec)?.metadata;
+    let ticket = match ticket_ref {
+        Some(ticket_ref) if !unlink => {
+            let storage = FileStorage::new(&project_dir);
+            Some(storage.load(&super::resolve_ticket_ref(&storage, ticket_ref)?)?)
+        },
+        _ => None,
+    };
+
+    let previous = metadata.ticket_id.take();
+    metadata.ticket_id = ticket.as_ref().map(|ticket| ticket.id.to_string());
+    metadata.updated_at = Utc::now();
+    spec_manager.save_metadata(&metadata)?;
+
+    if formatter.is_json() {
+        formatter.json(&serde_json::json!({
+            "status": "success",
+            "spec_id": metadata.id,
+            "ticket_id": metadata.ticket_id,
+            "ticket_slug": ticket.as_ref().map(|ticket| &ticket.slug),
+            "previous_ticket_id": previous,
+        }))?;
+    } else if let Some(ticket) = &ticket {
+        formatter.success(&format!(
+            "Linked specification '{}' to ticket '{}'",
+            metadata.title, ticket.slug
+        ));
+    } else if previous.is_some() {
+        formatter.success(&format!(
+            "Removed the ticket link of specification '{}'",
+            metadata.title
+        ));
+    } else {
+        formatter.info(&format!(
+            "Specification '{}' is not linked to a ticket",
+            metadata.title
+        ));
     }
 
     Ok(())
 }
 
+/// Parse `KEY=VALUE` arguments into custom field pairs
+fn parse_fields(args: &[String]) -> Result<Vec<(String, String)>> {
+    args.iter()
+        .map(|arg| {
+            let (key, value) = arg.split_once('=').ok_or_else(|| {
+                VibeTicketError::InvalidInput(format!("Invalid field '{arg}'. Expected KEY=VALUE"))
+            })?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(VibeTicketError::InvalidInput(format!(
+                    "Invalid field '{arg}'. Key cannot be empty"
+                )));
+            }
+            Ok((key.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
 /// Handle spec delete command
 pub fn handle_spec_delete(
     spec: String,
@@ -649,10 +1922,16 @@ pub fn handle_spec_delete(
 }
 
 /// Handle spec approve command
+///
+/// Records the decision of `reviewer`, by default the current user, on a
+/// phase (see [`crate::specs::approval`]). Approving runs the checks of the
+/// phase; requesting changes does not.
 pub fn handle_spec_approve(
     spec: String,
     phase: String,
     message: Option<String>,
+    request_changes: bool,
+    reviewer: Option<String>,
     project: Option<String>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
@@ -671,46 +1950,182 @@ pub fn handle_spec_approve(
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
     let mut specification = spec_manager.load(&spec)?;
+    let config = Config::load_for_project(&current_dir)?;
+    let phase: SpecDocumentType = phase.parse()?;
 
-    // Parse phase
-    let phase_enum = match phase.to_lowercase().as_str() {
-        "requirements" => SpecPhase::Requirements,
-        "design" => SpecPhase::Design,
-        "tasks" => SpecPhase::Tasks,
-        _ => {
-            return Err(VibeTicketError::InvalidInput(
-                "Invalid phase. Must be one of: requirements, design, tasks".to_string(),
-            ));
-        },
-    };
+    let decision = if request_changes {
+        Decision::RequestChanges
+    } else {
+        // Strict review requires every comment on the document to be resolved
+        if config.spec.strict_review {
+            check_review(&spec_manager, &spec, phase)?;
+        }
 
-    // Update approval status
-    if specification.metadata.progress.approval_status.is_none() {
-        specification.metadata.progress.approval_status = Some(std::collections::HashMap::new());
-    }
+        // The design must cite every requirement, and only defined ones
+        if phase == SpecDocumentType::Design {
+            check_design_refs(&specification)?;
+        }
+        Decision::Approve
+    };
 
-    if let Some(ref mut approvals) = specification.metadata.progress.approval_status {
-        approvals.insert(
-            format!("{phase_enum:?}"),
-            serde_json::json!({
-                "approved": true,
-                "approved_at": Utc::now(),
-                "message": message,
-            }),
-        );
-    }
+    let reviewer = match reviewer.filter(|r| !r.trim().is_empty()) {
+        Some(reviewer) => reviewer.trim().to_string(),
+        None => Identity::resolve(&config.user).name,
+    };
+    let required = approval::required(&config.spec, phase);
+    let was_approved = specification
+        .metadata
+        .progress
+        .approval(phase, required)
+        .is_approved();
+    let state = specification.metadata.progress.record_approval(
+        Approval {
+            reviewer: reviewer.clone(),
+            phase,
+            decision,
+            message,
+            decided_at: Utc::now(),
+        },
+        required,
+    );
 
     specification.metadata.updated_at = Utc::now();
     spec_manager.save(&specification)?;
+    if state.is_approved() && !was_approved {
+        emit_cli_event(
+            &current_dir,
+            &TicketEvent::SpecPhaseApproved(spec.clone(), phase),
+        );
+    }
 
+    if formatter.is_json() {
+        return formatter.json(&serde_json::json!({
+            "status": "success",
+            "spec_id": spec,
+            "reviewer": reviewer,
+            "decision": decision,
+            "approval": state,
+            "approved": state.is_approved(),
+        }));
+    }
+    let decided = match decision {
+        Decision::Approve => "approved",
+        Decision::RequestChanges => "requested changes to",
+    };
     formatter.success(&format!(
-        "Approved {} phase for specification '{}'",
-        phase, specification.metadata.title
+        "{reviewer} {decided} the {} phase of specification '{}'",
+        phase.as_str(),
+        specification.metadata.title
     ));
+    print_phase_approval(&state, formatter);
 
     Ok(())
 }
 
+/// Prints the approval state of the phases that have decisions or need
+/// approvals, and in detail every decision
+fn print_approvals(
+    specification: &Specification,
+    config: &SpecConfig,
+    detailed: bool,
+    formatter: &OutputFormatter,
+) {
+    let progress = &specification.metadata.progress;
+    let reviewed: Vec<_> = progress
+        .approvals_by_phase(config)
+        .into_iter()
+        .filter(|state| {
+            state.required > 0
+                || !state.approved_by.is_empty()
+                || !state.changes_requested_by.is_empty()
+        })
+        .collect();
+    if reviewed.is_empty() {
+        return;
+    }
+
+    formatter.info("\nApprovals:");
+    for state in &reviewed {
+        formatter.info(&format!(
+            "  {}: {}",
+            state.phase.display_name(),
+            if state.is_approved() {
+                "✓ Approved"
+            } else {
+                "○ Pending"
+            }
+        ));
+        formatter.info(&format!("  {}", phase_approval_line(state)));
+        if !state.changes_requested_by.is_empty() {
+            formatter.info(&format!(
+                "    Changes requested by: {}",
+                state.changes_requested_by.join(", ")
+            ));
+        }
+    }
+
+    if detailed {
+        for approval in &progress.approvals {
+            formatter.info(&format!(
+                "  {} {}: {} {}{}",
+                formatter.dates().format(approval.decided_at),
+                approval.phase.as_str(),
+                approval.reviewer,
+                approval.decision,
+                approval
+                    .message
+                    .as_deref()
+                    .map(|message| format!(": {message}"))
+                    .unwrap_or_default()
+            ));
+        }
+    }
+}
+
+/// Describes the approvals of a phase, e.g. "Approvals: 1/2 (alice)"
+fn phase_approval_line(state: &PhaseApproval) -> String {
+    let required = if state.required > 0 {
+        format!("/{}", state.required)
+    } else {
+        String::new()
+    };
+    let reviewers = if state.approved_by.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", state.approved_by.join(", "))
+    };
+    format!(
+        "  Approvals: {}{required}{reviewers}",
+        state.approved_by.len()
+    )
+}
+
+/// Prints the approvals of a phase and the reviewers requesting changes
+fn print_phase_approval(state: &PhaseApproval, formatter: &OutputFormatter) {
+    formatter.info(&phase_approval_line(state));
+    if !state.changes_requested_by.is_empty() {
+        formatter.info(&format!(
+            "  Changes requested by: {}",
+            state.changes_requested_by.join(", ")
+        ));
+    }
+}
+
+/// Checks that `phase` of a spec has the approvals the project requires
+/// before `action`
+fn check_approved(
+    project_root: &Path,
+    specification: &Specification,
+    phase: SpecDocumentType,
+    action: &str,
+) -> Result<()> {
+    let config = Config::load_for_project(project_root)?;
+    specification
+        .metadata
+        .progress
+        .check_approved(&config.spec, phase, action)
+}
+
 /// Handle spec activate command
 pub fn handle_spec_activate(
     spec: String,
@@ -734,9 +2149,25 @@ pub fn handle_spec_activate(
     let spec_manager = SpecManager::new(project_dir.join("specs"));
     let specification = spec_manager.load(&spec)?;
 
+    // Completed phases need their required approvals
+    let progress = &specification.metadata.progress;
+    for (phase, completed) in [
+        (
+            SpecDocumentType::Requirements,
+            progress.requirements_completed,
+        ),
+        (SpecDocumentType::Design, progress.design_completed),
+        (SpecDocumentType::Tasks, progress.tasks_completed),
+    ] {
+        if completed {
+            check_approved(&current_dir, &specification, phase, "activate the spec")?;
+        }
+    }
+
     // Save active spec
     let active_spec_path = project_dir.join(".active_spec");
     fs::write(&active_spec_path, &spec).context("Failed to set active specification")?;
+    emit_cli_event(&current_dir, &TicketEvent::SpecActivated(spec.clone()));
 
     formatter.success(&format!(
         "Set active specification to '{}' ({})",
@@ -746,6 +2177,15 @@ pub fn handle_spec_activate(
     Ok(())
 }
 
+/// Describes how ticket estimates compare to the estimates of the tasks
+fn compare_points(tickets: u32, tasks: u32) -> String {
+    match tickets.cmp(&tasks) {
+        std::cmp::Ordering::Equal => "matches the tasks".to_string(),
+        std::cmp::Ordering::Less => format!("{}pt below the tasks", tasks - tickets),
+        std::cmp::Ordering::Greater => format!("{}pt above the tasks", tickets - tasks),
+    }
+}
+
 /// Get the active specification ID
 fn get_active_spec(project_dir: &Path) -> Result<String> {
     let active_spec_path = project_dir.join(".active_spec");
@@ -759,12 +2199,30 @@ fn get_active_spec(project_dir: &Path) -> Result<String> {
         .map(|s| s.trim().to_string())
 }
 
+/// Open a spec document in the default editor
+///
+/// Edited content is saved through the spec manager, which bumps the version
+/// and keeps a copy in the spec history.
+fn edit_document(
+    spec_manager: &SpecManager,
+    spec_id: &str,
+    doc_type: SpecDocumentType,
+) -> Result<()> {
+    let path = spec_manager.get_document_path(spec_id, doc_type);
+    let before = fs::read_to_string(&path).unwrap_or_default();
+    open_in_editor(&path)?;
+    let after = fs::read_to_string(&path).unwrap_or_default();
+    if after != before {
+        spec_manager.record_edit(spec_id, doc_type)?;
+    }
+    Ok(())
+}
+
 /// Open a file in the default editor
 fn open_in_editor(path: &Path) -> Result<()> {
-    let editor = env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let editor = crate::platform::editor();
 
-    std::process::Command::new(&editor)
-        .arg(path)
+    crate::platform::editor_command(&editor, path)
         .status()
         .with_context(|| format!("Failed to open editor: {editor}"))?;
 
@@ -794,6 +2252,9 @@ mod tests {
             Some("Test description".to_string()),
             None,
             Some("test,spec".to_string()),
+            &["team=core".to_string()],
+            None,
+            None,
             None,
             &formatter,
         );
@@ -812,13 +2273,139 @@ mod tests {
         assert!(!entries.is_empty());
     }
 
+    #[test]
+    fn test_generate_document_from_pack() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = packs::templates_dir(&temp_dir.path().join(".vibe-ticket"));
+        std::fs::create_dir_all(templates.join("rfc")).unwrap();
+        std::fs::write(
+            templates.join("rfc/requirements.md"),
+            "# RFC: {{title}}\nTicket: {{ ticket }}\nAuthor: {{author}}\n",
+        )
+        .unwrap();
+
+        let mut metadata = SpecMetadata::new("Auth".to_string(), String::new());
+        metadata.ticket_id = Some("fix-login".to_string());
+        metadata.template = Some("rfc".to_string());
+        let builtin =
+            |doc_type| SpecTemplate::for_document_type(doc_type, "Auth".to_string(), None);
+
+        let requirements = generate_document(
+            temp_dir.path(),
+            &metadata,
+            SpecDocumentType::Requirements,
+            builtin(SpecDocumentType::Requirements),
+        )
+        .unwrap();
+        assert!(requirements.starts_with("# RFC: Auth\nTicket: fix-login\nAuthor: "));
+        assert!(!requirements.contains("{{author}}"));
+
+        // Documents the pack lacks come from the built-in templates
+        let design = generate_document(
+            temp_dir.path(),
+            &metadata,
+            SpecDocumentType::Design,
+            builtin(SpecDocumentType::Design),
+        )
+        .unwrap();
+        assert!(design.contains("Technical Design"));
+
+        metadata.template = Some("prd".to_string());
+        assert!(
+            generate_document(
+                temp_dir.path(),
+                &metadata,
+                SpecDocumentType::Requirements,
+                builtin(SpecDocumentType::Requirements),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let fields = parse_fields(&["team=core".to_string(), "release = 2.1".to_string()]).unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                ("team".to_string(), "core".to_string()),
+                ("release".to_string(), "2.1".to_string()),
+            ]
+        );
+
+        assert!(parse_fields(&["team".to_string()]).is_err());
+        assert!(parse_fields(&["=core".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_check_design_refs() {
+        let mut specification =
+            Specification::new("Auth".to_string(), String::new(), None, Vec::new());
+        specification.requirements = Some("## Features\n- REQ-1: Login\n".to_string());
+        specification.design = Some("## Auth\nImplements REQ-2.\n".to_string());
+        let error = check_design_refs(&specification).unwrap_err().to_string();
+        assert!(error.contains("REQ-1 is not referenced in the design"));
+        assert!(error.contains("cites REQ-2"));
+
+        specification.design = Some("## Auth\nImplements REQ-1.\n".to_string());
+        assert!(check_design_refs(&specification).is_ok());
+
+        specification.requirements = Some("## Features\n- Login\n".to_string());
+        specification.design = None;
+        assert!(check_design_refs(&specification).is_ok());
+    }
+
+    #[test]
+    fn test_amendment_chain() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec_manager = SpecManager::new(temp_dir.path().join("specs"));
+        let mut base = Specification::new("Auth".to_string(), String::new(), None, vec![]);
+        base.requirements = Some("## 1. Login\n\n## 2. Logout\n".to_string());
+        spec_manager.save(&base).unwrap();
+        assert!(amended_spec(&spec_manager, &base.metadata.id).is_err());
+
+        base.design = Some(String::new());
+        base.tasks = Some(String::new());
+        spec_manager.save(&base).unwrap();
+        let loaded = amended_spec(&spec_manager, &base.metadata.id).unwrap();
+
+        let mut delta = Specification::new("Auth v2".to_string(), String::new(), None, vec![]);
+        delta.metadata.amends = Some(base.metadata.id.clone());
+        delta.requirements = Some(
+            delta::inherit(&loaded.metadata, loaded.requirements.as_deref().unwrap())
+                .replace("2-logout unchanged", "2-logout removed"),
+        );
+        spec_manager.save(&delta).unwrap();
+
+        let (amended, amended_by) = amendment_chain(&spec_manager, &delta.metadata).unwrap();
+        assert_eq!(amended[0].id, base.metadata.id);
+        assert!(amended_by.is_empty());
+        let (amended, amended_by) = amendment_chain(&spec_manager, &base.metadata).unwrap();
+        assert!(amended.is_empty());
+        assert_eq!(amended_by[0].id, delta.metadata.id);
+
+        let changes = requirement_changes(&spec_manager, &delta).unwrap().unwrap();
+        assert_eq!(delta::superseded(&changes), vec!["2-logout"]);
+        assert!(requirement_changes(&spec_manager, &base).unwrap().is_none());
+    }
+
     #[test]
     fn test_spec_init_no_project() {
         let temp_dir = TempDir::new().unwrap();
         std::env::set_current_dir(temp_dir.path()).unwrap();
 
         let formatter = create_test_formatter();
-        let result = handle_spec_init("Test Spec".to_string(), None, None, None, None, &formatter);
+        let result = handle_spec_init(
+            "Test Spec".to_string(),
+            None,
+            None,
+            None,
+            &[],
+            None,
+            None,
+            None,
+            &formatter,
+        );
 
         assert!(result.is_err());
         assert!(matches!(
@@ -861,13 +2448,16 @@ mod tests {
             Some("Testing spec lifecycle".to_string()),
             None,
             None,
+            &[],
+            None,
+            None,
             None,
             &formatter,
         );
         assert!(result.is_ok());
 
         // List specs
-        let list_result = handle_spec_list(None, None, false, None, &formatter);
+        let list_result = handle_spec_list(None, None, false, &[], None, &formatter);
         assert!(list_result.is_ok());
 
         // Test status command (should fail without active spec)
@@ -906,6 +2496,9 @@ mod tests {
             None,
             None,
             None,
+            &[],
+            None,
+            None,
             None,
             &formatter,
         )
@@ -916,10 +2509,31 @@ mod tests {
             "test-spec".to_string(),
             "invalid-phase".to_string(),
             None,
+            false,
+            None,
             None,
             &formatter,
         );
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_run_estimation() {
+        let tasks = estimate::parse_tasks("- [ ] api\n- [ ] ui (2pt)\n- [ ] docs\n- [ ] tests\n");
+        let mut out = Vec::new();
+
+        // Direct entry: invalid answers are asked again, '-' skips
+        let mut input = std::io::Cursor::new("abc\n5\n-\n3\n");
+        let result = run_estimation(&tasks, false, &[], &mut input, &mut out).unwrap();
+        assert_eq!(result, vec![(0, 5), (3, 3)]);
+
+        // Planning poker: matching cards agree, otherwise the suggestion
+        // is taken on an empty answer; the end of the input quits
+        let voters = vec!["ann".to_string(), "bob".to_string()];
+        let mut input = std::io::Cursor::new("3\n3\n2\n8\n\n5\n");
+        let result = run_estimation(&tasks, true, &voters, &mut input, &mut out).unwrap();
+        assert_eq!(result, vec![(0, 3), (1, 8)]);
+        assert!(String::from_utf8(out).unwrap().contains("Consensus: 3pt"));
+    }
 }
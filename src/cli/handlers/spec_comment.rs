@@ -0,0 +1,262 @@
+//! Handlers for the `spec comment` command and its subcommands
+//!
+//! This module implements review comments on specification documents:
+//! adding, listing and resolving them, and the review check run before a
+//! phase is approved.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::{Config, Identity};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::review::{self, ReviewComment};
+use crate::specs::{SpecDocumentType, SpecManager, Specification};
+
+/// Handler for the `spec comment add` subcommand
+///
+/// Adds a review comment to a spec document, optionally on one line.
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID
+/// * `body` - Text of the comment
+/// * `phase` - Document commented on (requirements, design, tasks)
+/// * `line` - Optional line of the document (1-based)
+/// * `author` - Optional author (defaults to the current user)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_comment_add(
+    spec: &str,
+    body: &str,
+    phase: &str,
+    line: Option<usize>,
+    author: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let phase = phase.parse::<SpecDocumentType>()?;
+    let project_root = find_project_root(project_dir)?;
+    let spec_manager = SpecManager::new(project_root.join(".vibe-ticket").join("specs"));
+    let specification = spec_manager.load(spec)?;
+
+    let author = match author.filter(|a| !a.trim().is_empty()) {
+        Some(author) => author.trim().to_string(),
+        None => Identity::resolve(&Config::load_for_project(&project_root)?.user).name,
+    };
+    let mut comments = spec_manager.load_comments(spec)?;
+    let comment = review::add_comment(
+        &mut comments,
+        phase,
+        document(&specification, phase),
+        line,
+        &author,
+        body,
+    )?;
+    spec_manager.save_comments(spec, &comments)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "spec_id": specification.metadata.id,
+            "comment": comment,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Added review comment #{} on {} of specification '{}'",
+            comment.id,
+            comment.location(),
+            specification.metadata.title
+        ));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `spec comment list` subcommand
+///
+/// Prints the review comments on a spec, optionally only those on one
+/// document or those still unresolved.
+pub fn handle_spec_comment_list(
+    spec: &str,
+    phase: Option<&str>,
+    unresolved: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let phase = phase.map(str::parse::<SpecDocumentType>).transpose()?;
+    let project_root = find_project_root(project_dir)?;
+    let spec_manager = SpecManager::new(project_root.join(".vibe-ticket").join("specs"));
+    let specification = spec_manager.load(spec)?;
+
+    let comments: Vec<ReviewComment> = spec_manager
+        .load_comments(spec)?
+        .into_iter()
+        .filter(|c| phase.is_none_or(|phase| c.phase == phase))
+        .filter(|c| !unresolved || !c.is_resolved())
+        .collect();
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "spec_id": specification.metadata.id,
+            "comments": comments,
+        }))?;
+        return Ok(());
+    }
+
+    print_comments(&specification, &comments, output);
+    Ok(())
+}
+
+/// Handler for the `spec comment resolve` subcommand
+///
+/// Marks a review comment as resolved by the current user, or reopens it.
+pub fn handle_spec_comment_resolve(
+    spec: &str,
+    id: u32,
+    reopen: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let spec_manager = SpecManager::new(project_root.join(".vibe-ticket").join("specs"));
+    let specification = spec_manager.load(spec)?;
+
+    let by = if reopen {
+        None
+    } else {
+        Some(Identity::resolve(&Config::load_for_project(&project_root)?.user).name)
+    };
+    let mut comments = spec_manager.load_comments(spec)?;
+    let comment = review::resolve_comment(&mut comments, id, by.as_deref())?;
+    spec_manager.save_comments(spec, &comments)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "spec_id": specification.metadata.id,
+            "comment": comment,
+        }))?;
+    } else {
+        let action = if reopen { "Reopened" } else { "Resolved" };
+        output.success(&format!(
+            "{action} review comment #{id} on specification '{}'",
+            specification.metadata.title
+        ));
+    }
+
+    Ok(())
+}
+
+/// Prints review comments, quoting the lines they refer to
+pub fn print_comments(
+    specification: &Specification,
+    comments: &[ReviewComment],
+    output: &OutputFormatter,
+) {
+    if comments.is_empty() {
+        output.info(&format!(
+            "No review comments on specification '{}'",
+            specification.metadata.title
+        ));
+        return;
+    }
+
+    output.info(&format!(
+        "Review comments on specification '{}':",
+        specification.metadata.title
+    ));
+    for comment in comments {
+        let state = comment
+            .resolved_by
+            .as_ref()
+            .map_or_else(|| "open".to_string(), |by| format!("resolved by {by}"));
+        output.info("");
+        output.info(&format!(
+            "#{} {} [{state}] {} - {}",
+            comment.id,
+            comment.location(),
+            comment.author,
+            output.dates().format(comment.created_at)
+        ));
+        let quoted = comment.line.and_then(|line| {
+            document(specification, comment.phase)?
+                .lines()
+                .nth(line - 1)
+        });
+        if let Some(quoted) = quoted.map(str::trim).filter(|q| !q.is_empty()) {
+            output.info(&format!("  > {quoted}"));
+        }
+        for line in comment.body.lines() {
+            output.info(&format!("  {line}"));
+        }
+    }
+}
+
+/// Checks that the document of `phase` has no unresolved review comments
+///
+/// # Errors
+///
+/// Returns [`VibeTicketError::InvalidInput`] listing the unresolved comments.
+pub fn check_review(spec_manager: &SpecManager, spec: &str, phase: SpecDocumentType) -> Result<()> {
+    let comments = spec_manager.load_comments(spec)?;
+    let open: Vec<String> = review::unresolved(&comments, phase)
+        .map(|c| format!("#{}", c.id))
+        .collect();
+    if open.is_empty() {
+        return Ok(());
+    }
+    Err(VibeTicketError::InvalidInput(format!(
+        "Cannot approve {}: {} unresolved review comment(s) ({}). \
+         Resolve them with `vibe-ticket spec comment resolve {spec} <id>`",
+        phase.display_name(),
+        open.len(),
+        open.join(", ")
+    )))
+}
+
+/// Content of the document of `phase`, if written
+fn document(specification: &Specification, phase: SpecDocumentType) -> Option<&str> {
+    match phase {
+        SpecDocumentType::Requirements => specification.requirements.as_deref(),
+        SpecDocumentType::Design => specification.design.as_deref(),
+        SpecDocumentType::Tasks => specification.tasks.as_deref(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_review_blocks_unresolved_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec_manager = SpecManager::new(temp_dir.path().join("specs"));
+        let metadata = spec_manager
+            .create_spec("Queue".to_string(), String::new())
+            .unwrap();
+        spec_manager
+            .save_document(&metadata.id, SpecDocumentType::Design, "# Design\n")
+            .unwrap();
+
+        let mut comments = Vec::new();
+        review::add_comment(
+            &mut comments,
+            SpecDocumentType::Design,
+            Some("# Design\n"),
+            Some(1),
+            "alice",
+            "why not use a queue?",
+        )
+        .unwrap();
+        spec_manager.save_comments(&metadata.id, &comments).unwrap();
+
+        let err = check_review(&spec_manager, &metadata.id, SpecDocumentType::Design)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("#1"));
+        check_review(&spec_manager, &metadata.id, SpecDocumentType::Tasks).unwrap();
+
+        review::resolve_comment(&mut comments, 1, Some("bob")).unwrap();
+        spec_manager.save_comments(&metadata.id, &comments).unwrap();
+        check_review(&spec_manager, &metadata.id, SpecDocumentType::Design).unwrap();
+    }
+}
@@ -7,8 +7,11 @@ use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_re
 use crate::config::Config;
 use crate::core::Status;
 use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::platform::{self, PrefixBase};
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 use chrono::Utc;
+use std::path::{Path, PathBuf};
 
 /// Handler for the `start` command
 ///
@@ -64,11 +67,17 @@ pub fn handle_start_command(
     }
 
     // Update ticket status and start time
+    let old_status = ticket.status;
     ticket.status = Status::Doing;
     ticket.started_at = Some(Utc::now());
+    ticket.closed_at = None;
 
     // Save the updated ticket
     storage.save(&ticket)?;
+    emit_cli_event(
+        &project_root,
+        &TicketEvent::StatusChanged(ticket_id.clone(), old_status, Status::Doing),
+    );
 
     // Set as active ticket
     storage.set_active(&ticket_id)?;
@@ -114,15 +123,9 @@ pub fn handle_start_command(
 
         if let Some(branch) = branch_name_final {
             if worktree_created {
-                let worktree_prefix = config
-                    .git
-                    .worktree_prefix
-                    .replace("{project}", &config.project.name);
-                output.info(&format!(
-                    "Git worktree created: ../{}{}",
-                    worktree_prefix.trim_start_matches("../"),
-                    ticket.slug
-                ));
+                if let Some(path) = config.worktree_path(&project_root, &ticket.slug) {
+                    output.info(&format!("Git worktree created: {}", path.display()));
+                }
                 output.info(&format!("Branch: {branch}"));
             } else {
                 output.info(&format!("Git branch created: {branch}"));
@@ -216,35 +219,9 @@ fn create_git_worktree(
     }
 
     // Construct the worktree path using config settings
-    let project_name = config.project.name.as_str();
-    let worktree_prefix = config
-        .git
-        .worktree_prefix
-        .replace("{project}", project_name);
-
-    // Determine base directory based on prefix
-    let (base_dir, clean_prefix) = if worktree_prefix.starts_with("../") {
-        // Parent directory
-        let parent = project_root
-            .parent()
-            .ok_or_else(|| VibeTicketError::custom("Cannot find parent directory for worktree"))?;
-        (
-            parent.to_path_buf(),
-            worktree_prefix.trim_start_matches("../"),
-        )
-    } else if worktree_prefix.starts_with("./") {
-        // Current directory
-        (
-            project_root.to_path_buf(),
-            worktree_prefix.trim_start_matches("./"),
-        )
-    } else {
-        // Default to current directory
-        (project_root.to_path_buf(), worktree_prefix.as_str())
-    };
-
-    let worktree_dir_name = format!("{}{}", clean_prefix.trim_end_matches('-'), ticket_slug);
-    let worktree_path = base_dir.join(&worktree_dir_name);
+    let worktree_path = config
+        .worktree_path(project_root, ticket_slug)
+        .ok_or_else(|| VibeTicketError::custom("Cannot find parent directory for worktree"))?;
 
     // Check if worktree directory already exists
     if worktree_path.exists() {
@@ -293,15 +270,15 @@ fn create_git_worktree(
         worktree_path.display()
     ));
 
-    // Show appropriate cd command based on location
-    let cd_path = if worktree_prefix.starts_with("../") {
-        format!("../{}", worktree_dir_name)
-    } else if worktree_prefix.starts_with("./") {
-        format!("./{}", worktree_dir_name)
-    } else {
-        worktree_dir_name.clone()
+    // Show the cd command relative to the project root, with the
+    // platform's separator
+    let (base, _) = platform::split_prefix(&config.git.worktree_prefix);
+    let worktree_dir_name = worktree_path.file_name().unwrap_or_default();
+    let cd_path = match base {
+        PrefixBase::Parent => Path::new("..").join(worktree_dir_name),
+        PrefixBase::Project => PathBuf::from(worktree_dir_name),
     };
-    output.info(&format!("You can now cd to '{}'", cd_path));
+    output.info(&format!("You can now cd to '{}'", cd_path.display()));
 
     Ok(())
 }
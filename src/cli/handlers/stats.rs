@@ -0,0 +1,163 @@
+//! Handler for the `stats` command
+//!
+//! Counts the changes recorded in the ticket audit logs (see
+//! [`crate::core::heatmap`]) to show how activity is distributed over days
+//! and hours, for the whole project or for the tickets of one assignee.
+//! Incidents are summarized by their mean time to mitigate and to resolve
+//! (see [`crate::core::incident`]).
+//!
+//! With `--closed-between`, only tickets whose audit log records them
+//! entering `done` within the range are counted (see
+//! [`crate::core::audit::status_transitions`]).
+
+use std::collections::HashSet;
+
+use chrono::{Local, Weekday};
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::core::Status;
+use crate::core::audit::{self, TransitionKind};
+use crate::core::heatmap::Heatmap;
+use crate::core::incident::{IncidentStats, format_duration};
+use crate::error::Result;
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Handler for the `stats` command
+///
+/// # Arguments
+///
+/// * `heatmap` - Whether to render the activity heatmap
+/// * `by_hour` - Whether the heatmap shows hours of the week instead of days
+/// * `weeks` - Number of weeks covered, ending today
+/// * `assignee` - Only count tickets assigned to this user (`me` for the current user)
+/// * `closed_between` - Only count tickets closed within this date range (`START..END`)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or cannot be read,
+/// or the date range is invalid
+pub fn handle_stats_command(
+    heatmap: bool,
+    by_hour: bool,
+    weeks: u32,
+    assignee: Option<&str>,
+    closed_between: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let assignee = assignee.map(|name| storage.identity().expand(name));
+    let closed_between = closed_between
+        .map(|range| output.dates().parse_range(range))
+        .transpose()?;
+
+    let mut activity = Heatmap::new(Local::now().date_naive(), weeks);
+    let mut counted = HashSet::new();
+    for ticket in storage.summaries()? {
+        if assignee.is_some() && ticket.assignee != assignee {
+            continue;
+        }
+        let log = storage.load_history(&ticket.id)?;
+        if let Some(range) = closed_between {
+            let closed = TransitionKind::Entered(Status::Done);
+            if !audit::transitioned_between(&log, ticket.status, closed, range.start, range.end) {
+                continue;
+            }
+        }
+        for event in &log {
+            activity.add(event.at.with_timezone(&Local));
+        }
+        counted.insert(ticket.id);
+    }
+
+    let tickets = storage.load_all()?;
+    let incidents = IncidentStats::of(tickets.iter().filter(|ticket| counted.contains(&ticket.id)));
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "assignee": assignee,
+            "closed_between": closed_between.map(|range| serde_json::json!({
+                "start": range.start,
+                "end": range.end,
+                "tickets": counted.len(),
+            })),
+            "weeks": activity.mondays().len(),
+            "active_days": activity.days.len(),
+            "heatmap": activity,
+            "incidents": incidents,
+        }));
+    }
+
+    let mut scope = assignee.map_or_else(String::new, |name| format!(" on tickets of {name}"));
+    if let Some(range) = closed_between {
+        let bound = |at: Option<_>| {
+            at.map_or_else(|| "-".to_string(), |at| output.dates().format_absolute(at))
+        };
+        scope = format!(
+            "{scope} on {} ticket(s) closed between {} and {}",
+            counted.len(),
+            bound(range.start),
+            bound(range.end)
+        );
+    }
+    output.info(&format!(
+        "Activity{scope} from {} to {}:",
+        activity.start, activity.end
+    ));
+    output.info(&format!("  Changes: {}", activity.total));
+    output.info(&format!("  Active days: {}", activity.days.len()));
+    if let Some((date, count)) = activity.busiest_day() {
+        output.info(&format!("  Busiest day: {date} ({count} changes)"));
+    }
+    if let Some((day, hour, count)) = activity.busiest_hour() {
+        output.info(&format!(
+            "  Busiest hour: {} {hour:02}:00 ({count} changes)",
+            weekday_name(day)
+        ));
+    }
+
+    if incidents.total > 0 {
+        let mean = |minutes: Option<i64>| {
+            minutes.map_or_else(
+                || "-".to_string(),
+                |minutes| format_duration(chrono::Duration::minutes(minutes)),
+            )
+        };
+        output.info(&format!(
+            "  Incidents: {} ({} open)",
+            incidents.total, incidents.open
+        ));
+        output.info(&format!("  MTTM: {}", mean(incidents.mttm_minutes)));
+        output.info(&format!("  MTTR: {}", mean(incidents.mttr_minutes)));
+    }
+
+    if heatmap {
+        let lines = if by_hour {
+            activity.render_hours()
+        } else {
+            activity.render_days()
+        };
+        println!();
+        for line in lines {
+            println!("{line}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Full English name of a weekday
+const fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
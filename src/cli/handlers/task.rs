@@ -6,6 +6,7 @@
 use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
 use crate::core::{Task, TaskId};
 use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 use chrono::Utc;
 
@@ -51,6 +52,10 @@ pub fn handle_task_add(
 
     // Save the updated ticket
     storage.save(&ticket)?;
+    emit_cli_event(
+        &project_root,
+        &TicketEvent::TaskAdded(ticket.id.clone(), task.clone()),
+    );
 
     // Output results
     if output.is_json() {
@@ -137,6 +142,10 @@ pub fn handle_task_complete(
 
     // Save the updated ticket
     storage.save(&ticket)?;
+    emit_cli_event(
+        &project_root,
+        &TicketEvent::TaskCompleted(ticket.id.clone(), task_id.to_string()),
+    );
 
     // Calculate completion stats
     let completed_count = ticket.tasks.iter().filter(|t| t.completed).count();
@@ -231,6 +240,7 @@ pub fn handle_task_uncomplete(
 
     // Save the updated ticket
     storage.save(&ticket)?;
+    emit_cli_event(&project_root, &TicketEvent::Updated(ticket.clone()));
 
     // Calculate completion stats
     let completed_count = ticket.tasks.iter().filter(|t| t.completed).count();
@@ -425,6 +435,10 @@ pub fn handle_task_remove(
 
     // Save the updated ticket
     storage.save(&ticket)?;
+    emit_cli_event(
+        &project_root,
+        &TicketEvent::TaskRemoved(ticket.id.clone(), removed_task.id.to_string()),
+    );
 
     // Output results
     if output.is_json() {
@@ -789,6 +803,24 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_resolve_ticket_ref_prefers_short_id() {
+        let (_, storage, _) = setup_test_env();
+        let ticket = Ticket::new("first".to_string(), "First".to_string());
+        storage.save(&ticket).unwrap();
+        let short_id = storage.load(&ticket.id).unwrap().short_id.unwrap();
+
+        // A slug equal to the short ID does not shadow it
+        let mut other = Ticket::new(short_id.clone(), "Other".to_string());
+        other.short_id = Some("zzzzzz".to_string());
+        storage.save(&other).unwrap();
+
+        assert_eq!(resolve_ticket_ref(&storage, &short_id).unwrap(), ticket.id);
+        assert_eq!(resolve_ticket_ref(&storage, "zzzzzz").unwrap(), other.id);
+        let prefix = &ticket.id.to_string()[..13];
+        assert_eq!(resolve_ticket_ref(&storage, prefix).unwrap(), ticket.id);
+    }
+
     #[test]
     fn test_json_output_format() {
         let (temp_dir, storage, _json_formatter) = setup_test_env();
@@ -0,0 +1,212 @@
+//! Handler for the top-level `tasks` command
+//!
+//! Collects the incomplete tasks of every open ticket into one list, grouped
+//! by ticket, so the ticket store doubles as a personal todo list. Tasks have
+//! no assignee of their own; they belong to whoever the ticket is assigned to.
+
+use std::cmp::Reverse;
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::core::{Priority, Status, Task, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Filters of the `tasks` command
+#[derive(Debug, Default)]
+struct TaskFilter {
+    /// Only tickets assigned to this user
+    assignee: Option<String>,
+    /// Only tickets with this tag
+    tag: Option<String>,
+    /// Only tickets with this priority
+    priority: Option<Priority>,
+}
+
+impl TaskFilter {
+    /// Returns true if the ticket is open and matches the filter
+    fn matches(&self, ticket: &Ticket) -> bool {
+        let archived = ticket
+            .metadata
+            .get("archived")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        ticket.status != Status::Done
+            && !archived
+            && self
+                .assignee
+                .as_ref()
+                .is_none_or(|assignee| ticket.assignee.as_ref() == Some(assignee))
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| ticket.tags.contains(tag))
+            && self.priority.is_none_or(|p| ticket.priority == p)
+    }
+}
+
+/// Handler for the `tasks` command
+///
+/// # Arguments
+///
+/// * `assignee` - Only tickets assigned to this user (`me` for the current user)
+/// * `mine` - Only tickets assigned to the current user
+/// * `tag` - Only tickets with this tag
+/// * `priority` - Only tickets with this priority
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, the priority is
+/// invalid, or the tickets cannot be loaded
+pub fn handle_tasks_command(
+    assignee: Option<String>,
+    mine: bool,
+    tag: Option<String>,
+    priority: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let priority = priority
+        .map(|p| {
+            Priority::try_from(p).map_err(|_| VibeTicketError::InvalidPriority {
+                priority: p.to_string(),
+            })
+        })
+        .transpose()?;
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let filter = TaskFilter {
+        assignee: if mine {
+            Some(storage.identity().name.clone())
+        } else {
+            assignee.map(|name| storage.identity().expand(&name))
+        },
+        tag,
+        priority,
+    };
+    let tickets = storage.load_all()?;
+    let groups = open_tasks(&tickets, &filter);
+    let task_count: usize = groups.iter().map(|(_, tasks)| tasks.len()).sum();
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "assignee": filter.assignee,
+            "total": task_count,
+            "tickets": groups.iter().map(|(ticket, tasks)| serde_json::json!({
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+                "title": ticket.title,
+                "status": ticket.status.to_string(),
+                "priority": ticket.priority.to_string(),
+                "assignee": ticket.assignee,
+                "tasks": tasks.iter().map(|t| serde_json::json!({
+                    "id": t.id.to_string(),
+                    "title": t.title,
+                    "created_at": t.created_at,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+        }))?;
+        return Ok(());
+    }
+
+    if groups.is_empty() {
+        output.info("No open tasks found");
+        return Ok(());
+    }
+    for (ticket, tasks) in &groups {
+        let assignee = ticket
+            .assignee
+            .as_ref()
+            .map_or_else(String::new, |a| format!(", @{a}"));
+        output.info(&format!(
+            "{} ({}, {}{assignee})",
+            ticket.slug, ticket.priority, ticket.status
+        ));
+        for task in tasks {
+            output.info(&format!(
+                "  ○ [{}] {}",
+                &task.id.to_string()[..8],
+                task.title
+            ));
+        }
+    }
+    output.info(&format!(
+        "\n{task_count} open task(s) in {} ticket(s)",
+        groups.len()
+    ));
+
+    Ok(())
+}
+
+/// Incomplete tasks of the matching tickets, grouped by ticket
+///
+/// Tickets without incomplete tasks are left out. Groups are ordered by
+/// priority, highest first, then by creation time; tasks keep their order.
+fn open_tasks<'a>(tickets: &'a [Ticket], filter: &TaskFilter) -> Vec<(&'a Ticket, Vec<&'a Task>)> {
+    let mut groups: Vec<(&Ticket, Vec<&Task>)> = tickets
+        .iter()
+        .filter(|ticket| filter.matches(ticket))
+        .map(|ticket| {
+            (
+                ticket,
+                ticket.tasks.iter().filter(|t| !t.completed).collect(),
+            )
+        })
+        .filter(|(_, tasks): &(_, Vec<_>)| !tasks.is_empty())
+        .collect();
+    groups.sort_by_key(|(ticket, _)| (Reverse(ticket.priority), ticket.created_at));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(slug: &str, priority: Priority, tasks: &[(&str, bool)]) -> Ticket {
+        let mut ticket = Ticket::new(slug, slug);
+        ticket.priority = priority;
+        for (title, completed) in tasks {
+            let mut task = Task::new(*title);
+            if *completed {
+                task.complete();
+            }
+            ticket.tasks.push(task);
+        }
+        ticket
+    }
+
+    #[test]
+    fn test_open_tasks_grouped_by_ticket() {
+        let low = ticket("low", Priority::Low, &[("a", false), ("b", true)]);
+        let mut high = ticket("high", Priority::High, &[("c", false), ("d", false)]);
+        high.assignee = Some("alice".to_string());
+        high.tags.push("backend".to_string());
+        let mut done = ticket("done", Priority::Critical, &[("e", false)]);
+        done.status = Status::Done;
+        let finished = ticket("finished", Priority::Critical, &[("f", true)]);
+        let tickets = vec![low, high, done, finished];
+
+        let groups = open_tasks(&tickets, &TaskFilter::default());
+        let slugs: Vec<&str> = groups.iter().map(|(t, _)| t.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["high", "low"]);
+        let titles: Vec<&str> = groups[1].1.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["a"]);
+
+        let mine = TaskFilter {
+            assignee: Some("alice".to_string()),
+            ..TaskFilter::default()
+        };
+        assert_eq!(open_tasks(&tickets, &mine).len(), 1);
+        let tagged = TaskFilter {
+            tag: Some("frontend".to_string()),
+            ..TaskFilter::default()
+        };
+        assert!(open_tasks(&tickets, &tagged).is_empty());
+        let low_only = TaskFilter {
+            priority: Some(Priority::Low),
+            ..TaskFilter::default()
+        };
+        assert_eq!(open_tasks(&tickets, &low_only)[0].0.slug, "low");
+    }
+}
@@ -0,0 +1,176 @@
+//! Handlers for the `delete` and `trash` commands
+//!
+//! Deleted tickets are moved to `.vibe-ticket/trash/` (see
+//! [`crate::storage::TrashedTicket`]) and can be restored until they are
+//! emptied from the trash, or removed by `gc` after the retention period.
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::error::Result;
+use crate::storage::{ActiveTicketRepository, FileStorage, TrashedTicket};
+
+/// Handler for the `delete` command
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Ticket ID or slug
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the ticket is not found or cannot be moved
+pub fn handle_delete_command(
+    ticket_ref: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+
+    if storage.get_active()?.as_ref() == Some(&ticket_id) {
+        storage.clear_active()?;
+    }
+    let entry = storage.trash_ticket(&ticket_id)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket": entry_json(&entry),
+            "retention_days": storage.trash_retention_days(),
+        }))?;
+    } else {
+        output.success(&format!(
+            "Moved ticket '{}' to the trash",
+            entry.ticket.slug
+        ));
+        output.info(&format!(
+            "Restore it with: vibe-ticket trash restore {}",
+            entry.ticket.id.short()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `trash list` subcommand
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the trash cannot be
+/// read
+pub fn handle_trash_list(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let entries = storage.list_trash()?;
+    let retention_days = storage.trash_retention_days();
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "retention_days": retention_days,
+            "tickets": entries.iter().map(entry_json).collect::<Vec<_>>(),
+        }))?;
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        output.info("The trash is empty");
+        return Ok(());
+    }
+    let now = chrono::Utc::now();
+    for entry in &entries {
+        let expiry = if entry.is_expired(retention_days, now) {
+            " (expired)"
+        } else {
+            ""
+        };
+        output.info(&format!(
+            "{} {} deleted {}{expiry}",
+            entry.ticket.id.short(),
+            entry.ticket.slug,
+            output.dates().format(entry.deleted_at)
+        ));
+    }
+    output.info(&format!(
+        "{} ticket(s) in the trash; kept for {retention_days} days",
+        entries.len()
+    ));
+
+    Ok(())
+}
+
+/// Handler for the `trash restore` subcommand
+///
+/// # Errors
+///
+/// Returns an error if no trashed ticket matches, or the ticket exists again
+pub fn handle_trash_restore(
+    ticket_ref: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let entry = storage.find_in_trash(ticket_ref)?;
+    let ticket = storage.restore_from_trash(&entry.ticket.id)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket": {
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+            },
+        }))?;
+    } else {
+        output.success(&format!("Restored ticket '{}'", ticket.slug));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `trash empty` subcommand
+///
+/// # Arguments
+///
+/// * `expired` - Only remove tickets older than the retention period
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the trash cannot be emptied
+pub fn handle_trash_empty(
+    expired: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let removed = storage.empty_trash(expired.then(|| storage.trash_retention_days()))?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "removed": removed.iter().map(entry_json).collect::<Vec<_>>(),
+        }))?;
+    } else if removed.is_empty() {
+        output.info("Nothing to remove");
+    } else {
+        output.success(&format!(
+            "Removed {} ticket(s) from the trash for good",
+            removed.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn entry_json(entry: &TrashedTicket) -> serde_json::Value {
+    serde_json::json!({
+        "id": entry.ticket.id.to_string(),
+        "slug": entry.ticket.slug,
+        "title": entry.ticket.title,
+        "deleted_at": entry.deleted_at,
+    })
+}
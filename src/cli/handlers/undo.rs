@@ -0,0 +1,117 @@
+//! Handler for the `undo` command
+//!
+//! Reverts the most recent operations recorded in the storage journal (see
+//! [`crate::storage::Operation`]), such as an accidental close, edit, task
+//! removal or import.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, Operation};
+
+/// Handler for the `undo` command
+///
+/// # Arguments
+///
+/// * `steps` - Number of operations to revert, most recent first
+/// * `list` - Only list the operations that can be reverted
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized, `steps` is zero, or
+/// a ticket cannot be restored
+pub fn handle_undo_command(
+    steps: usize,
+    list: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    if list {
+        return print_journal(&storage.load_journal()?, output);
+    }
+    if steps == 0 {
+        return Err(VibeTicketError::InvalidInput(
+            "--steps must be at least 1".to_string(),
+        ));
+    }
+
+    let mut undone = Vec::new();
+    for _ in 0..steps {
+        match storage.undo_operation()? {
+            Some(operation) => undone.push(operation),
+            None => break,
+        }
+    }
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "undone": undone.iter().map(operation_json).collect::<Vec<_>>(),
+        }))?;
+    } else if undone.is_empty() {
+        output.info("Nothing to undo");
+    } else {
+        for operation in &undone {
+            output.success(&format!(
+                "Undid '{}' ({} ticket(s))",
+                operation.command(),
+                operation.ticket_ids().len()
+            ));
+            for entry in operation.entries.iter().rev() {
+                let action = entry.before.as_ref().map_or_else(
+                    || format!("removed {}", entry.ticket_id.short()),
+                    |ticket| format!("restored {}", ticket.slug),
+                );
+                output.info(&format!("  {action}"));
+            }
+        }
+        if undone.len() < steps {
+            output.warning("No more operations to undo");
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the journaled operations, most recent first
+fn print_journal(operations: &[Operation], output: &OutputFormatter) -> Result<()> {
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "operations": operations.iter().map(operation_json).collect::<Vec<_>>(),
+        }));
+    }
+    if operations.is_empty() {
+        output.info("Nothing to undo");
+        return Ok(());
+    }
+    for (step, operation) in operations.iter().enumerate() {
+        let at = operation
+            .entries
+            .first()
+            .map(|e| output.dates().format(e.at));
+        output.info(&format!(
+            "{:>3}. {} {} ({} ticket(s))",
+            step + 1,
+            at.unwrap_or_default(),
+            operation.command(),
+            operation.ticket_ids().len()
+        ));
+    }
+    Ok(())
+}
+
+fn operation_json(operation: &Operation) -> serde_json::Value {
+    serde_json::json!({
+        "id": operation.id,
+        "command": operation.command(),
+        "tickets": operation
+            .ticket_ids()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+    })
+}
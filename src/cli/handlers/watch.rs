@@ -0,0 +1,134 @@
+//! Handlers for ticket watchers
+//!
+//! This module implements the `watch` command and the dispatch of the
+//! notifications that storage queues when a watched ticket's status,
+//! priority or due date changes.
+
+use crate::cli::{OutputFormatter, find_project_root, handlers::resolve_ticket_ref};
+use crate::config::Config;
+use crate::core::WatchNotification;
+use crate::error::{Result, VibeTicketError};
+use crate::integration::outbox::{Outbox, OutboxEntry, http_deliver};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+/// Handler for the `watch` command
+///
+/// Adds a user to (or removes them from) the watchers of a ticket.
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Optional ticket ID or slug (defaults to active ticket)
+/// * `user` - Optional user (defaults to the current user)
+/// * `remove` - Whether to stop watching instead
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the project is not initialized or the ticket cannot
+/// be found or saved
+pub fn handle_watch_command(
+    ticket_ref: Option<String>,
+    user: Option<String>,
+    remove: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+
+    let ticket_id = if let Some(ref_str) = ticket_ref {
+        resolve_ticket_ref(&storage, &ref_str)?
+    } else {
+        storage
+            .get_active()?
+            .ok_or(VibeTicketError::NoActiveTicket)?
+    };
+    let mut ticket = storage.load(&ticket_id)?;
+
+    let user = user
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| storage.identity().name.clone());
+    let changed = if remove {
+        ticket.unwatch(&user)
+    } else {
+        ticket.watch(user.as_str())
+    };
+    if changed {
+        storage.save(&ticket)?;
+    }
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "user": user,
+            "changed": changed,
+            "watchers": ticket.watchers,
+        }))?;
+    } else {
+        match (remove, changed) {
+            (false, true) => output.success(&format!("{user} is now watching '{}'", ticket.slug)),
+            (false, false) => output.info(&format!("{user} already watches '{}'", ticket.slug)),
+            (true, true) => {
+                output.success(&format!("{user} stopped watching '{}'", ticket.slug));
+            },
+            (true, false) => output.info(&format!("{user} does not watch '{}'", ticket.slug)),
+        }
+        if !ticket.watchers.is_empty() {
+            output.info(&format!("Watchers: {}", ticket.watchers.join(", ")));
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends the watcher notifications queued by the last command
+///
+/// Each notification is posted to `notifications.webhook_url`; deliveries
+/// that fail are moved to the outbox and retried later. Without a webhook
+/// the notifications are dropped. Failures are ignored so that they never
+/// fail the command that caused them.
+pub fn dispatch_notifications(project_dir: Option<&str>) {
+    let Ok(project_root) = find_project_root(project_dir) else {
+        return;
+    };
+
+    let vibe_dir = project_root.join(".vibe-ticket");
+    let storage = FileStorage::new(&vibe_dir);
+    let pending = match storage.pending_notifications() {
+        Ok(pending) if !pending.is_empty() => pending,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::debug!("Failed to read watcher notifications: {e}");
+            return;
+        },
+    };
+
+    let webhook_url = Config::load_for_project(&project_root)
+        .ok()
+        .and_then(|config| config.notifications.webhook_url);
+    let outbox = Outbox::new(&vibe_dir);
+
+    for notification in pending {
+        if let Some(url) = &webhook_url {
+            if let Err(e) = deliver(&outbox, url, &notification) {
+                tracing::debug!("Failed to deliver watcher notification: {e}");
+                continue;
+            }
+        }
+        if let Err(e) = storage.remove_notification(&notification.id) {
+            tracing::debug!("Failed to remove watcher notification: {e}");
+        }
+    }
+}
+
+/// Posts a notification to the webhook, queueing it in the outbox on failure
+fn deliver(outbox: &Outbox, url: &str, notification: &WatchNotification) -> Result<()> {
+    let payload = serde_json::to_value(notification)?;
+    let entry = OutboxEntry::new(url, WatchNotification::EVENT, payload);
+    outbox.deliver_or_enqueue(entry, http_deliver)?;
+    Ok(())
+}
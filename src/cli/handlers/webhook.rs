@@ -0,0 +1,214 @@
+//! Handlers for the `webhook` command
+//!
+//! This module implements registering, listing, removing and testing the
+//! webhooks that receive ticket events (see [`crate::integration::webhooks`]).
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::{Config, WebhookConfig};
+use crate::error::{Result, VibeTicketError};
+use crate::events::TicketEvent;
+use crate::integration::outbox::{Outbox, http_deliver};
+use crate::integration::webhooks::{self, PING_EVENT};
+
+/// Handler for the `webhook add` subcommand
+///
+/// # Arguments
+///
+/// * `name` - Name of the webhook
+/// * `url` - URL the events are posted to
+/// * `secret` - Optional secret signing the payloads
+/// * `events` - Event kinds to post (all events if empty)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the name is taken, the URL is not HTTP(S) or an
+/// event kind is unknown
+pub fn handle_webhook_add(
+    name: &str,
+    url: &str,
+    secret: Option<String>,
+    events: &[String],
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Webhook name cannot be empty".to_string(),
+        ));
+    }
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Webhook URL must start with http:// or https://: {url}"
+        )));
+    }
+    let events: Vec<String> = events
+        .iter()
+        .map(|event| event.trim().to_string())
+        .filter(|event| !event.is_empty())
+        .collect();
+    if let Some(unknown) = events
+        .iter()
+        .find(|event| !TicketEvent::KINDS.contains(&event.as_str()))
+    {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Unknown event kind '{unknown}'. Valid kinds: {}",
+            TicketEvent::KINDS.join(", ")
+        )));
+    }
+
+    let project_root = find_project_root(project_dir)?;
+    let mut config = Config::load_for_project(&project_root)?;
+    if config.webhooks.iter().any(|webhook| webhook.name == name) {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "A webhook named '{name}' already exists"
+        )));
+    }
+    let webhook = WebhookConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        secret: secret.filter(|secret| !secret.is_empty()),
+        events,
+    };
+    config.webhooks.push(webhook.clone());
+    config.save_to_path(project_root.join(".vibe-ticket/config.yaml"))?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "webhook": summary(&webhook),
+        }))?;
+    } else {
+        output.success(&format!(
+            "Added webhook '{name}' posting {} to {url}",
+            describe_events(&webhook)
+        ));
+    }
+    Ok(())
+}
+
+/// Handler for the `webhook list` subcommand
+///
+/// Secrets are never printed, only whether a webhook has one.
+pub fn handle_webhook_list(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let webhooks = Config::load_for_project(&project_root)?.webhooks;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "webhooks": webhooks.iter().map(summary).collect::<Vec<_>>(),
+        }))?;
+        return Ok(());
+    }
+
+    if webhooks.is_empty() {
+        output.info("No webhooks configured");
+        return Ok(());
+    }
+    for webhook in &webhooks {
+        let signed = if webhook.secret.is_some() {
+            ", signed"
+        } else {
+            ""
+        };
+        output.info(&format!(
+            "{} → {} ({}{signed})",
+            webhook.name,
+            webhook.url,
+            describe_events(webhook)
+        ));
+    }
+    Ok(())
+}
+
+/// Handler for the `webhook remove` subcommand
+///
+/// Deliveries already queued for the webhook stay in the outbox.
+pub fn handle_webhook_remove(
+    name: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let mut config = Config::load_for_project(&project_root)?;
+    let before = config.webhooks.len();
+    config.webhooks.retain(|webhook| webhook.name != name);
+    if config.webhooks.len() == before {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "No webhook named '{name}'"
+        )));
+    }
+    config.save_to_path(project_root.join(".vibe-ticket/config.yaml"))?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "name": name,
+        }))?;
+    } else {
+        output.success(&format!("Removed webhook '{name}'"));
+    }
+    Ok(())
+}
+
+/// Handler for the `webhook test` subcommand
+///
+/// Posts a `ping` event to the webhook once. The attempt is recorded in the
+/// delivery log but not queued if it fails.
+///
+/// # Errors
+///
+/// Returns an error if there is no such webhook or the delivery fails
+pub fn handle_webhook_test(
+    name: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let config = Config::load_for_project(&project_root)?;
+    let webhook = config
+        .webhooks
+        .iter()
+        .find(|webhook| webhook.name == name)
+        .ok_or_else(|| VibeTicketError::InvalidInput(format!("No webhook named '{name}'")))?;
+
+    let payload = webhooks::payload(
+        PING_EVENT,
+        &config.project.name,
+        &serde_json::json!({"kind": PING_EVENT, "webhook": name}),
+    );
+    let entry = webhooks::entry(webhook, PING_EVENT, payload)?;
+    Outbox::new(project_root.join(".vibe-ticket")).attempt(&entry, &http_deliver)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "name": name,
+            "url": webhook.url,
+        }))?;
+    } else {
+        output.success(&format!("Delivered a test event to {}", webhook.url));
+    }
+    Ok(())
+}
+
+/// JSON description of a webhook, without its secret
+fn summary(webhook: &WebhookConfig) -> serde_json::Value {
+    serde_json::json!({
+        "name": webhook.name,
+        "url": webhook.url,
+        "events": webhook.events,
+        "signed": webhook.secret.is_some(),
+    })
+}
+
+/// Human-readable list of the events a webhook receives
+fn describe_events(webhook: &WebhookConfig) -> String {
+    if webhook.events.is_empty() {
+        "all events".to_string()
+    } else {
+        webhook.events.join(", ")
+    }
+}
@@ -0,0 +1,158 @@
+//! Handlers for the `workspace` command and its subcommands
+//!
+//! Manages the registry of projects (see [`WorkspaceRegistry`]) that
+//! `list --all-projects` aggregates.
+
+use std::path::Path;
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::WorkspaceRegistry;
+use crate::error::{ErrorContext, Result};
+
+/// Handler for the `workspace add` subcommand
+///
+/// # Arguments
+///
+/// * `path` - Directory inside the project to register (defaults to the current project)
+/// * `name` - Name of the project (defaults to the name of its root directory)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if the directory is not inside a project, or the name
+/// or the project is already registered
+pub fn handle_workspace_add(
+    path: Option<&str>,
+    name: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let root = find_project_root(path.or(project_dir))?;
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", root.display()))?;
+    let name = name.map_or_else(|| default_name(&root), str::to_string);
+
+    let mut registry = WorkspaceRegistry::load()?;
+    registry.add(&name, root.clone())?;
+    registry.save()?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "name": name,
+            "path": root,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Registered project '{name}' at {}",
+            root.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `workspace list` subcommand
+///
+/// # Errors
+///
+/// Returns an error if the registry cannot be read
+pub fn handle_workspace_list(output: &OutputFormatter) -> Result<()> {
+    let registry = WorkspaceRegistry::load()?;
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "current": registry.current,
+            "projects": registry.projects.iter().map(|project| serde_json::json!({
+                "name": project.name,
+                "path": project.path,
+                "available": project.path.join(".vibe-ticket").is_dir(),
+            })).collect::<Vec<_>>(),
+        }));
+    }
+
+    if registry.projects.is_empty() {
+        output.info("No projects registered. Add one with `vibe-ticket workspace add`");
+        return Ok(());
+    }
+    for project in &registry.projects {
+        let marker = if registry.current.as_deref() == Some(project.name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        let missing = if project.path.join(".vibe-ticket").is_dir() {
+            ""
+        } else {
+            " (missing)"
+        };
+        output.info(&format!(
+            "{marker} {:<16} {}{missing}",
+            project.name,
+            project.path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `workspace switch` subcommand
+///
+/// Commands run outside of any project use the project switched to.
+///
+/// # Errors
+///
+/// Returns an error if no project has that name
+pub fn handle_workspace_switch(name: &str, output: &OutputFormatter) -> Result<()> {
+    let mut registry = WorkspaceRegistry::load()?;
+    let path = registry.switch(name)?.path.clone();
+    registry.save()?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "current": name,
+            "path": path,
+        }))?;
+    } else {
+        output.success(&format!("Switched to project '{name}'"));
+        output.info(&format!("Path: {}", path.display()));
+    }
+
+    Ok(())
+}
+
+/// Handler for the `workspace remove` subcommand
+///
+/// Only unregisters the project; its files are left alone.
+///
+/// # Errors
+///
+/// Returns an error if no project has that name
+pub fn handle_workspace_remove(name: &str, output: &OutputFormatter) -> Result<()> {
+    let mut registry = WorkspaceRegistry::load()?;
+    let project = registry.remove(name)?;
+    registry.save()?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "removed": project.name,
+            "path": project.path,
+        }))?;
+    } else {
+        output.success(&format!("Unregistered project '{name}'"));
+    }
+
+    Ok(())
+}
+
+/// Name of the root directory of a project
+fn default_name(root: &Path) -> String {
+    root.file_name().map_or_else(
+        || root.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
@@ -6,6 +6,7 @@
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::config::Config;
 use crate::error::{Result, VibeTicketError};
+use crate::platform;
 use crate::storage::{FileStorage, TicketRepository};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -167,15 +168,15 @@ pub fn handle_worktree_prune(
 
 /// Worktree information
 #[derive(Debug, Clone, serde::Serialize)]
-struct WorktreeInfo {
-    path: PathBuf,
+pub(super) struct WorktreeInfo {
+    pub(super) path: PathBuf,
     branch: Option<String>,
     commit: String,
     status: String,
 }
 
 /// List all Git worktrees
-fn list_git_worktrees(project_root: &Path) -> Result<Vec<WorktreeInfo>> {
+pub(super) fn list_git_worktrees(project_root: &Path) -> Result<Vec<WorktreeInfo>> {
     let output = Command::new("git")
         .arg("worktree")
         .arg("list")
@@ -201,7 +202,7 @@ fn list_git_worktrees(project_root: &Path) -> Result<Vec<WorktreeInfo>> {
             if let Some(wt) = current_worktree.take() {
                 worktrees.push(wt);
             }
-            let path = PathBuf::from(line.strip_prefix("worktree ").unwrap());
+            let path = platform::path_from_git(line.strip_prefix("worktree ").unwrap());
             current_worktree = Some(WorktreeInfo {
                 path,
                 branch: None,
@@ -231,8 +232,7 @@ fn list_git_worktrees(project_root: &Path) -> Result<Vec<WorktreeInfo>> {
 }
 
 /// Extract ticket slug from worktree path
-fn extract_ticket_slug(path: &Path, config: &Config) -> Result<Option<String>> {
-    let _path_str = path.to_string_lossy();
+pub(super) fn extract_ticket_slug(path: &Path, config: &Config) -> Result<Option<String>> {
     let project_name = &config.project.name;
     let prefix = config
         .git
@@ -242,10 +242,8 @@ fn extract_ticket_slug(path: &Path, config: &Config) -> Result<Option<String>> {
     // Check if this follows our worktree naming pattern
     if let Some(file_name) = path.file_name() {
         let name = file_name.to_string_lossy();
-        let prefix_cleaned = prefix
-            .trim_start_matches("../")
-            .trim_start_matches("./")
-            .trim_end_matches('-');
+        let (_, prefix_cleaned) = platform::split_prefix(&prefix);
+        let prefix_cleaned = prefix_cleaned.trim_end_matches('-');
 
         if name.starts_with(prefix_cleaned) {
             let slug = name
@@ -325,19 +323,9 @@ fn resolve_worktree_path(
         .replace("{project}", project_name);
 
     // Determine base directory based on prefix
-    let (base_dir, clean_prefix) = if prefix.starts_with("../") {
-        // Parent directory
-        let parent = project_root
-            .parent()
-            .ok_or_else(|| VibeTicketError::custom("Cannot find parent directory"))?;
-        (parent.to_path_buf(), prefix.trim_start_matches("../"))
-    } else if prefix.starts_with("./") {
-        // Current directory
-        (project_root.to_path_buf(), prefix.trim_start_matches("./"))
-    } else {
-        // Default to current directory
-        (project_root.to_path_buf(), prefix.as_str())
-    };
+    let (base, clean_prefix) = platform::split_prefix(&prefix);
+    let base_dir = platform::prefix_base_dir(project_root, base)
+        .ok_or_else(|| VibeTicketError::custom("Cannot find parent directory"))?;
 
     let worktree_name = format!("{}{}", clean_prefix, worktree_ref);
     let worktree_path = base_dir.join(&worktree_name);
@@ -424,6 +412,28 @@ fn remove_git_worktree(project_root: &Path, worktree_path: &Path, force: bool) -
     Ok(())
 }
 
+/// Move a Git worktree
+pub(super) fn move_git_worktree(project_root: &Path, from: &Path, to: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .arg("worktree")
+        .arg("move")
+        .arg(from)
+        .arg(to)
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| VibeTicketError::custom(format!("Failed to move worktree: {e}")))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(VibeTicketError::custom(format!(
+            "Failed to move worktree {}: {error_msg}",
+            from.display()
+        )));
+    }
+
+    Ok(())
+}
+
 /// Remove a Git branch
 fn remove_git_branch(project_root: &Path, branch: &str) -> Result<()> {
     let output = Command::new("git")
@@ -472,12 +482,20 @@ mod tests {
                 description: None,
                 default_assignee: None,
                 default_priority: "medium".to_string(),
+                description_size_limit: 16 * 1024,
+                immutable_closed: false,
+                trash_retention_days: 30,
+                close_requirements: Vec::new(),
+                short_id_length: 6,
             },
             ui: crate::config::UiConfig {
                 theme: "auto".to_string(),
                 emoji: true,
                 page_size: 20,
                 date_format: "%Y-%m-%d %H:%M".to_string(),
+                relative_dates: false,
+                statuses: std::collections::HashMap::new(),
+                priorities: std::collections::HashMap::new(),
             },
             git: GitConfig {
                 enabled: true,
@@ -492,7 +510,19 @@ mod tests {
             plugins: crate::config::PluginsConfig {
                 enabled: vec![],
                 directory: ".vibe-ticket/plugins".to_string(),
+                search_paths: Vec::new(),
+                slack: None,
             },
+            team: crate::config::TeamConfig::default(),
+            mcp: crate::config::McpServerConfig::default(),
+            storage: crate::config::StorageConfig::default(),
+            user: crate::config::UserConfig::default(),
+            notifications: crate::config::NotificationsConfig::default(),
+            aging: crate::config::AgingConfig::default(),
+            spec: crate::config::SpecConfig::default(),
+            webhooks: Vec::new(),
+            filters: std::collections::BTreeMap::new(),
+            fields: std::collections::BTreeMap::new(),
         }
     }
 
@@ -518,6 +548,49 @@ mod tests {
         assert_eq!(slug, Some("feature".to_string()));
     }
 
+    #[test]
+    fn test_resolve_worktree_path_with_backslash_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().join("test-project");
+        let worktree = temp_dir.path().join("test-project-vibeticket-fix-bug");
+        std::fs::create_dir_all(&project_root).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+
+        let mut config = create_test_config();
+        config.git.worktree_prefix = r"..\{project}-vibeticket-".to_string();
+
+        let resolved = resolve_worktree_path("fix-bug", &project_root, &config).unwrap();
+        assert_eq!(resolved, worktree);
+        assert_eq!(
+            extract_ticket_slug(&resolved, &config).unwrap(),
+            Some("fix-bug".to_string())
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_worktree_paths() {
+        let mut config = create_test_config();
+        config.git.worktree_prefix = r"..\{project}-vibeticket-".to_string();
+
+        // `git worktree list --porcelain` prints forward slashes
+        let path = platform::path_from_git("C:/work/test-project-vibeticket-fix-bug");
+        assert_eq!(
+            path,
+            PathBuf::from(r"C:\work\test-project-vibeticket-fix-bug")
+        );
+        assert_eq!(
+            extract_ticket_slug(&path, &config).unwrap(),
+            Some("fix-bug".to_string())
+        );
+
+        let path = PathBuf::from(r"D:\repos\test-project-vibeticket-feature");
+        assert_eq!(
+            extract_ticket_slug(&path, &config).unwrap(),
+            Some("feature".to_string())
+        );
+    }
+
     #[test]
     fn test_determine_worktree_status() {
         let temp_dir = TempDir::new().unwrap();
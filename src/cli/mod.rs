@@ -27,12 +27,25 @@
 //! ```
 
 mod commands;
+pub mod completions;
+pub mod dates;
+pub mod examples;
 pub mod handlers;
 mod output;
+pub mod schema;
 mod utils;
+mod visual;
 
+pub use commands::{
+    AttachCommands, CiCommands, Cli, Commands, CommentCommands, ConfigCommands, ContextCommands,
+    DoctorCommands, InboxCommands, IncidentCommands, MilestoneCommands, NotifyCommands,
+    OutboxCommands, ProjectCommands, SchemaCommands, SpecCommands, SpecCommentCommands,
+    SpecTemplateCommands, TaskCommands, TimelineCommands, TrashCommands, WebhookCommands,
+    WorkspaceCommands, WorktreeCommands,
+};
 #[cfg(feature = "mcp")]
-pub use commands::McpCommands;
-pub use commands::{Cli, Commands, ConfigCommands, SpecCommands, TaskCommands, WorktreeCommands};
+pub use commands::{McpCommands, McpTokenCommands};
+pub use dates::{DateFormat, DateRange};
 pub use output::{OutputFormatter, ProgressBar};
 pub use utils::*;
+pub use visual::{Visual, VisualRegistry};
@@ -2,12 +2,16 @@ use colored::{ColoredString, Colorize};
 use serde::Serialize;
 use std::io::Write as IoWrite;
 
+use super::{DateFormat, VisualRegistry};
+use crate::core::fields::field_text;
 use crate::core::{Priority, Status, Ticket};
 use crate::error::Result;
 
 /// Output formatter for CLI commands
 pub struct OutputFormatter {
     json: bool,
+    visuals: VisualRegistry,
+    dates: DateFormat,
 }
 
 impl OutputFormatter {
@@ -16,7 +20,35 @@ impl OutputFormatter {
         if no_color {
             colored::control::set_override(false);
         }
-        Self { json }
+        Self {
+            json,
+            visuals: VisualRegistry::default(),
+            dates: DateFormat::default(),
+        }
+    }
+
+    /// Uses the given visuals to render statuses and priorities
+    #[must_use]
+    pub fn with_visuals(mut self, visuals: VisualRegistry) -> Self {
+        self.visuals = visuals;
+        self
+    }
+
+    /// Returns the visuals used to render statuses and priorities
+    pub const fn visuals(&self) -> &VisualRegistry {
+        &self.visuals
+    }
+
+    /// Uses the given format to show dates
+    #[must_use]
+    pub fn with_dates(mut self, dates: DateFormat) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    /// Returns the format used to show and read dates
+    pub const fn dates(&self) -> &DateFormat {
+        &self.dates
     }
 
     /// Check if JSON output is enabled
@@ -96,14 +128,27 @@ impl OutputFormatter {
         if self.json {
             self.print_json(tickets)?;
         } else {
-            self.print_tickets_table(tickets);
+            self.print_tickets_table(tickets, &[]);
+        }
+        Ok(())
+    }
+
+    /// Prints a list of tickets with a column per user-defined field
+    pub fn print_tickets_with_fields(&self, tickets: &[Ticket], fields: &[String]) -> Result<()> {
+        if self.json {
+            self.print_json(tickets)?;
+        } else {
+            self.print_tickets_table(tickets, fields);
         }
         Ok(())
     }
 
     /// Prints data as JSON
+    ///
+    /// Objects are tagged with the schema version (see [`super::schema`]).
     pub fn print_json<T: Serialize + ?Sized>(&self, data: &T) -> Result<()> {
-        let json = serde_json::to_string_pretty(data)?;
+        let json =
+            serde_json::to_string_pretty(&super::schema::versioned(serde_json::to_value(data)?))?;
         println!("{json}");
         Ok(())
     }
@@ -112,14 +157,14 @@ impl OutputFormatter {
     fn print_ticket_formatted(&self, ticket: &Ticket) {
         println!("{}", "─".repeat(80).bright_black());
         println!(
-            "{} {} {}",
-            ticket.status.emoji(),
+            "{}{} {}",
+            self.visuals.status_prefix(ticket.status),
             ticket.title.bold(),
             format!("({})", ticket.slug).bright_black()
         );
         println!("{}", "─".repeat(80).bright_black());
 
-        println!("{:<12} {}", "ID:".bright_black(), ticket.id.short());
+        println!("{:<12} {}", "ID:".bright_black(), ticket.display_id());
         println!(
             "{:<12} {}",
             "Status:".bright_black(),
@@ -131,6 +176,10 @@ impl OutputFormatter {
             self.format_priority(&ticket.priority)
         );
 
+        if let Some(severity) = ticket.severity {
+            println!("{:<12} {}", "Severity:".bright_black(), severity);
+        }
+
         if let Some(assignee) = &ticket.assignee {
             println!("{:<12} {}", "Assignee:".bright_black(), assignee);
         }
@@ -146,14 +195,14 @@ impl OutputFormatter {
         println!(
             "{:<12} {}",
             "Created:".bright_black(),
-            ticket.created_at.format("%Y-%m-%d %H:%M")
+            self.dates.format(ticket.created_at)
         );
 
         if let Some(started) = ticket.started_at {
             println!(
                 "{:<12} {}",
                 "Started:".bright_black(),
-                started.format("%Y-%m-%d %H:%M")
+                self.dates.format(started)
             );
         }
 
@@ -190,20 +239,28 @@ impl OutputFormatter {
     }
 
     /// Prints tickets in a table format
-    fn print_tickets_table(&self, tickets: &[Ticket]) {
+    fn print_tickets_table(&self, tickets: &[Ticket], fields: &[String]) {
         if tickets.is_empty() {
             println!("No tickets found.");
             return;
         }
 
-        // Header
+        // Header; user-defined fields follow the task count
+        let trailing = |first: String, rest: Vec<String>| {
+            std::iter::once(first)
+                .chain(rest.into_iter().map(|cell| format!("{cell:<12}")))
+                .collect::<Vec<_>>()
+                .join(" ")
+                .trim_end()
+                .to_string()
+        };
         println!(
             "{:<8} {:<10} {:<10} {:<40} {}",
             "ID".bold(),
             "Status".bold(),
             "Priority".bold(),
             "Title".bold(),
-            "Tasks".bold()
+            trailing("Tasks".to_string(), fields.to_vec()).bold()
         );
         println!("{}", "─".repeat(90).bright_black());
 
@@ -215,13 +272,20 @@ impl OutputFormatter {
                 ticket.total_tasks_count()
             );
 
+            let values = fields
+                .iter()
+                .map(|name| {
+                    let value = ticket.metadata.get(name).map(field_text);
+                    truncate(&value.unwrap_or_default(), 12)
+                })
+                .collect();
             println!(
                 "{:<8} {:<10} {:<10} {:<40} {}",
-                ticket.id.short(),
+                ticket.display_id(),
                 self.format_status(&ticket.status),
                 self.format_priority(&ticket.priority),
                 truncate(&ticket.title, 40),
-                tasks
+                trailing(format!("{tasks:<5}"), values)
             );
         }
 
@@ -229,25 +293,46 @@ impl OutputFormatter {
         println!("Total: {} tickets", tickets.len());
     }
 
+    /// Prints tickets of several projects as a table with a project column
+    ///
+    /// `projects[i]` is the name of the project of `tickets[i]`.
+    pub fn print_project_tickets(&self, projects: &[&str], tickets: &[Ticket]) {
+        println!(
+            "{:<14} {:<8} {:<10} {:<10} {:<40} {}",
+            "Project".bold(),
+            "ID".bold(),
+            "Status".bold(),
+            "Priority".bold(),
+            "Title".bold(),
+            "Tasks".bold()
+        );
+        println!("{}", "─".repeat(105).bright_black());
+
+        for (project, ticket) in projects.iter().zip(tickets) {
+            println!(
+                "{:<14} {:<8} {:<10} {:<10} {:<40} {}/{}",
+                truncate(project, 14),
+                ticket.display_id(),
+                self.format_status(&ticket.status),
+                self.format_priority(&ticket.priority),
+                truncate(&ticket.title, 40),
+                ticket.completed_tasks_count(),
+                ticket.total_tasks_count()
+            );
+        }
+
+        println!("{}", "─".repeat(105).bright_black());
+        println!("Total: {} tickets", tickets.len());
+    }
+
     /// Formats status with color
     fn format_status(&self, status: &Status) -> ColoredString {
-        match status {
-            Status::Todo => "Todo".blue(),
-            Status::Doing => "Doing".yellow(),
-            Status::Done => "Done".green(),
-            Status::Blocked => "Blocked".red(),
-            Status::Review => "Review".cyan(),
-        }
+        self.visuals.format_status(*status)
     }
 
     /// Formats priority with color
     fn format_priority(&self, priority: &Priority) -> ColoredString {
-        match priority {
-            Priority::Low => "Low".green(),
-            Priority::Medium => "Medium".yellow(),
-            Priority::High => "High".magenta(),
-            Priority::Critical => "Critical".red(),
-        }
+        self.visuals.format_priority(*priority)
     }
 }
 
@@ -0,0 +1,273 @@
+//! Versioned JSON output schemas
+//!
+//! Every JSON object printed with `--json`, written by the JSON export or
+//! returned by an MCP tool carries a `schema_version` field. The version
+//! only changes when a field is removed, renamed or changes its type; new
+//! fields are added without a bump, so scripts should ignore fields they do
+//! not know. The documents are described by JSON Schemas that
+//! `vibe-ticket schema dump` writes out and that are published in
+//! `docs/schemas`.
+
+use serde_json::{Value, json};
+
+/// Version of the JSON output documents
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Field carrying the version in every JSON output document
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Adds the schema version to a JSON output document
+///
+/// Objects get a `schema_version` field unless they already have one; other
+/// values are returned unchanged.
+pub fn versioned(mut value: Value) -> Value {
+    if let Value::Object(map) = &mut value {
+        map.entry(SCHEMA_VERSION_KEY)
+            .or_insert_with(|| json!(SCHEMA_VERSION));
+    }
+    value
+}
+
+/// JSON Schemas of the output documents, keyed by file name
+pub fn schemas() -> Vec<(&'static str, Value)> {
+    vec![
+        ("ticket.schema.json", ticket_schema()),
+        (
+            "list.schema.json",
+            document(
+                "list.schema.json",
+                "Output of `vibe-ticket list --json`",
+                &json!({
+                    "tickets": { "type": "array", "items": { "$ref": "ticket.schema.json" } },
+                    "count": { "type": "integer" },
+                    "counts": {
+                        "type": "object",
+                        "description": "Number of listed tickets per status",
+                        "additionalProperties": { "type": "integer" }
+                    },
+                    "fail_if": { "type": "array", "items": { "type": "object" } },
+                    "projects": {
+                        "type": "array",
+                        "description": "Projects listed by `--all-projects`; each ticket then has a `project`",
+                        "items": { "type": "string" }
+                    },
+                    "skipped": { "type": "array", "items": { "type": "string" } },
+                    "tree": { "type": "array", "items": { "type": "object" } }
+                }),
+                &["tickets", "count", "counts"],
+            ),
+        ),
+        (
+            "show.schema.json",
+            document(
+                "show.schema.json",
+                "Output of `vibe-ticket show --json`",
+                &json!({
+                    "ticket": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "string" },
+                            "slug": { "type": "string" },
+                            "title": { "type": "string" },
+                            "status": { "type": "string" },
+                            "priority": { "type": "string" }
+                        },
+                        "required": ["id", "slug", "title", "status", "priority"]
+                    },
+                    "fields": { "type": "object" },
+                    "attachments": { "type": "array", "items": { "type": "object" } },
+                    "tasks": { "type": "array", "items": { "type": "object" } },
+                    "sections": { "type": "object" }
+                }),
+                &["ticket"],
+            ),
+        ),
+        (
+            "export.schema.json",
+            document(
+                "export.schema.json",
+                "JSON written by `vibe-ticket export json`",
+                &json!({
+                    "project": { "type": "string" },
+                    "version": {
+                        "type": "string",
+                        "description": "Version of vibe-ticket that wrote the export"
+                    },
+                    "tickets": { "type": "array", "items": { "$ref": "ticket.schema.json" } },
+                    "exported_at": { "type": "string", "format": "date-time" },
+                    "total": { "type": "integer" }
+                }),
+                &["version", "tickets", "total"],
+            ),
+        ),
+        (
+            "check.schema.json",
+            document(
+                "check.schema.json",
+                "Output of `vibe-ticket check --json`",
+                &json!({
+                    "project": { "type": "object" },
+                    "active_ticket": { "type": ["object", "null"] },
+                    "git_branch": { "type": ["string", "null"] },
+                    "statistics": { "type": "object" },
+                    "recent_tickets": { "type": "array", "items": { "type": "object" } }
+                }),
+                &["project", "statistics"],
+            ),
+        ),
+        (
+            "mcp-result.schema.json",
+            document(
+                "mcp-result.schema.json",
+                "JSON text content returned by the MCP tools",
+                &json!({}),
+                &[],
+            ),
+        ),
+    ]
+}
+
+/// Schema of an output document with the schema version and `properties`
+fn document(id: &str, description: &str, properties: &Value, required: &[&str]) -> Value {
+    let mut properties = properties.clone();
+    properties[SCHEMA_VERSION_KEY] = json!({
+        "type": "integer",
+        "const": SCHEMA_VERSION,
+        "description": "Version of this document; bumped only for incompatible changes"
+    });
+    let mut required: Vec<&str> = required.to_vec();
+    required.insert(0, SCHEMA_VERSION_KEY);
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": id,
+        "title": id.trim_end_matches(".schema.json"),
+        "description": description,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": true
+    })
+}
+
+/// Schema of a ticket as listed and exported
+fn ticket_schema() -> Value {
+    let timestamp = json!({ "type": "string", "format": "date-time" });
+    let optional_timestamp = json!({ "type": ["string", "null"], "format": "date-time" });
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "ticket.schema.json",
+        "title": "ticket",
+        "description": "A ticket as listed and exported",
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" },
+            "short_id": {
+                "type": "string",
+                "description": "Short ID accepted wherever a ticket is referenced"
+            },
+            "slug": { "type": "string" },
+            "title": { "type": "string" },
+            "description": { "type": "string" },
+            "priority": { "enum": ["low", "medium", "high", "critical"] },
+            "severity": { "type": "string" },
+            "status": { "enum": ["todo", "doing", "done", "blocked", "review"] },
+            "tags": strings,
+            "created_at": timestamp,
+            "started_at": optional_timestamp,
+            "closed_at": optional_timestamp,
+            "assignee": { "type": ["string", "null"] },
+            "tasks": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "title": { "type": "string" },
+                        "completed": { "type": "boolean" },
+                        "created_at": timestamp,
+                        "completed_at": optional_timestamp
+                    },
+                    "required": ["id", "title", "completed", "created_at"]
+                }
+            },
+            "work_log": { "type": "array", "items": { "type": "object" } },
+            "comments": { "type": "array", "items": { "type": "object" } },
+            "depends_on": strings,
+            "blocks": strings,
+            "relations": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "kind": {
+                            "enum": [
+                                "duplicates", "duplicated_by", "relates_to",
+                                "supersedes", "superseded_by", "caused_by",
+                                "causes", "follows", "followed_by"
+                            ]
+                        },
+                        "ticket": { "type": "string" }
+                    },
+                    "required": ["kind", "ticket"]
+                }
+            },
+            "parent": { "type": "string" },
+            "due": { "type": "string", "format": "date" },
+            "milestone": { "type": "string" },
+            "watchers": strings,
+            "metadata": { "type": "object" }
+        },
+        "required": [
+            "id", "slug", "title", "description", "priority", "status", "tags",
+            "created_at", "started_at", "closed_at", "assignee", "tasks", "metadata"
+        ],
+        "additionalProperties": true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+
+    #[test]
+    fn test_versioned_objects_only() {
+        assert_eq!(
+            versioned(json!({ "count": 1 })),
+            json!({ "count": 1, "schema_version": SCHEMA_VERSION })
+        );
+        assert_eq!(
+            versioned(json!({ "schema_version": 0 })),
+            json!({ "schema_version": 0 })
+        );
+        assert_eq!(versioned(json!("text")), json!("text"));
+    }
+
+    #[test]
+    fn test_ticket_schema_matches_tickets() {
+        let ticket = serde_json::to_value(Ticket::new("schema", "Schema")).unwrap();
+        let schema = ticket_schema();
+        for field in schema["required"].as_array().unwrap() {
+            assert!(ticket.get(field.as_str().unwrap()).is_some(), "{field}");
+        }
+        for field in ticket.as_object().unwrap().keys() {
+            assert!(schema["properties"].get(field).is_some(), "{field}");
+        }
+    }
+
+    #[test]
+    fn test_published_schemas_are_current() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("docs/schemas");
+        for (name, schema) in schemas() {
+            let published = std::fs::read_to_string(dir.join(name)).unwrap();
+            let published: Value = serde_json::from_str(&published).unwrap();
+            assert_eq!(
+                published, schema,
+                "{name} is outdated; run `vibe-ticket schema dump --output docs/schemas`"
+            );
+        }
+    }
+}
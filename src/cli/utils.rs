@@ -1,12 +1,15 @@
 use std::env;
 use std::path::{Path, PathBuf};
 
+use crate::config::WorkspaceRegistry;
 use crate::error::{Result, VibeTicketError};
 
 /// Gets the project root directory
 ///
 /// This function searches for a .vibe-ticket directory in the current directory
-/// and its parents, similar to how Git finds the repository root.
+/// and its parents, similar to how Git finds the repository root. Without a
+/// start directory, the project selected with `workspace switch` is used
+/// when none is found.
 pub fn find_project_root(start_dir: Option<&str>) -> Result<PathBuf> {
     let start = if let Some(dir) = start_dir {
         PathBuf::from(dir)
@@ -28,6 +31,17 @@ pub fn find_project_root(start_dir: Option<&str>) -> Result<PathBuf> {
         }
     }
 
+    // Outside of any project, fall back to the project switched to
+    if start_dir.is_none() {
+        if let Some(project) = WorkspaceRegistry::load()
+            .ok()
+            .and_then(|registry| registry.current().cloned())
+            .filter(|project| project.path.join(".vibe-ticket").is_dir())
+        {
+            return Ok(project.path);
+        }
+    }
+
     Err(VibeTicketError::ProjectNotInitialized)
 }
 
@@ -112,29 +126,9 @@ pub fn format_duration(duration: chrono::Duration) -> String {
 
 /// Opens a URL in the default browser
 pub fn open_url(url: &str) -> Result<()> {
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .arg(url)
-            .spawn()
-            .map_err(VibeTicketError::Io)?;
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        std::process::Command::new("xdg-open")
-            .arg(url)
-            .spawn()
-            .map_err(VibeTicketError::Io)?;
-    }
-
-    #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new("cmd")
-            .args(&["/C", "start", url])
-            .spawn()
-            .map_err(VibeTicketError::Io)?;
-    }
+    crate::platform::open_command(url)
+        .spawn()
+        .map_err(VibeTicketError::Io)?;
 
     Ok(())
 }
@@ -0,0 +1,225 @@
+//! Visual rendering of statuses and priorities
+//!
+//! Terminal output renders statuses and priorities through a
+//! [`VisualRegistry`]. The registry starts from the built-in emoji, labels
+//! and colors, applies the `ui.statuses` / `ui.priorities` overrides from the
+//! project configuration and drops emoji entirely when `ui.emoji` is false.
+
+use std::collections::HashMap;
+
+use colored::{ColoredString, Colorize};
+
+use crate::cli::find_project_root;
+use crate::config::{Config, UiConfig, VisualOverride};
+use crate::core::{Priority, Status};
+
+/// Emoji, label and color used to render a status or priority
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visual {
+    /// Emoji shown before the label
+    pub emoji: String,
+
+    /// Human-readable label
+    pub label: String,
+
+    /// Terminal color name understood by `colored`
+    pub color: String,
+}
+
+impl Visual {
+    fn builtin(emoji: &str, label: String, color: &str) -> Self {
+        Self {
+            emoji: emoji.to_string(),
+            label,
+            color: color.to_string(),
+        }
+    }
+
+    fn apply(&mut self, custom: &VisualOverride) {
+        if let Some(emoji) = &custom.emoji {
+            self.emoji.clone_from(emoji);
+        }
+        if let Some(label) = &custom.label {
+            self.label.clone_from(label);
+        }
+        if let Some(color) = &custom.color {
+            self.color.clone_from(color);
+        }
+    }
+}
+
+/// Registry of the visuals used for every status and priority
+#[derive(Debug, Clone)]
+pub struct VisualRegistry {
+    emoji: bool,
+    statuses: HashMap<Status, Visual>,
+    priorities: HashMap<Priority, Visual>,
+}
+
+impl Default for VisualRegistry {
+    fn default() -> Self {
+        let statuses = Status::all()
+            .into_iter()
+            .map(|s| (s, Visual::builtin(s.emoji(), s.to_string(), s.color())))
+            .collect();
+        let priorities = Priority::all()
+            .into_iter()
+            .map(|p| (p, Visual::builtin(p.emoji(), p.to_string(), p.color())))
+            .collect();
+
+        Self {
+            emoji: true,
+            statuses,
+            priorities,
+        }
+    }
+}
+
+impl VisualRegistry {
+    /// Builds the registry from the UI configuration
+    ///
+    /// Override keys that do not name a status or priority are ignored.
+    pub fn from_config(ui: &UiConfig) -> Self {
+        let mut registry = Self {
+            emoji: ui.emoji,
+            ..Self::default()
+        };
+
+        for (key, custom) in &ui.statuses {
+            if let Some(visual) = Status::try_from(key.as_str())
+                .ok()
+                .and_then(|s| registry.statuses.get_mut(&s))
+            {
+                visual.apply(custom);
+            }
+        }
+
+        for (key, custom) in &ui.priorities {
+            if let Some(visual) = Priority::try_from(key.as_str())
+                .ok()
+                .and_then(|p| registry.priorities.get_mut(&p))
+            {
+                visual.apply(custom);
+            }
+        }
+
+        registry
+    }
+
+    /// Builds the registry from the configuration of the given project
+    ///
+    /// Falls back to the built-in visuals outside a project or when the
+    /// configuration cannot be read.
+    pub fn for_project(project_dir: Option<&str>) -> Self {
+        find_project_root(project_dir)
+            .ok()
+            .and_then(|root| Config::load_for_project(&root).ok())
+            .map_or_else(Self::default, |config| Self::from_config(&config.ui))
+    }
+
+    /// Returns whether emoji are rendered
+    pub const fn emoji_enabled(&self) -> bool {
+        self.emoji
+    }
+
+    /// Returns the visual for a status
+    pub fn status(&self, status: Status) -> &Visual {
+        &self.statuses[&status]
+    }
+
+    /// Returns the visual for a priority
+    pub fn priority(&self, priority: Priority) -> &Visual {
+        &self.priorities[&priority]
+    }
+
+    /// Returns the emoji followed by a space, or nothing when emoji are disabled
+    pub fn status_prefix(&self, status: Status) -> String {
+        self.prefix(self.status(status))
+    }
+
+    /// Returns the emoji followed by a space, or nothing when emoji are disabled
+    pub fn priority_prefix(&self, priority: Priority) -> String {
+        self.prefix(self.priority(priority))
+    }
+
+    /// Returns the status label with its emoji prefix
+    pub fn status_text(&self, status: Status) -> String {
+        format!(
+            "{}{}",
+            self.status_prefix(status),
+            self.status(status).label
+        )
+    }
+
+    /// Returns the priority label with its emoji prefix
+    pub fn priority_text(&self, priority: Priority) -> String {
+        format!(
+            "{}{}",
+            self.priority_prefix(priority),
+            self.priority(priority).label
+        )
+    }
+
+    /// Returns the colored status label
+    pub fn format_status(&self, status: Status) -> ColoredString {
+        let visual = self.status(status);
+        visual.label.as_str().color(visual.color.as_str())
+    }
+
+    /// Returns the colored priority label
+    pub fn format_priority(&self, priority: Priority) -> ColoredString {
+        let visual = self.priority(priority);
+        visual.label.as_str().color(visual.color.as_str())
+    }
+
+    fn prefix(&self, visual: &Visual) -> String {
+        if self.emoji && !visual.emoji.is_empty() {
+            format!("{} ", visual.emoji)
+        } else {
+            String::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_visuals() {
+        let registry = VisualRegistry::default();
+        assert_eq!(registry.status_text(Status::Todo), "📋 Todo");
+        assert_eq!(registry.priority_text(Priority::Critical), "🔴 Critical");
+        assert_eq!(registry.status(Status::Done).color, "green");
+    }
+
+    #[test]
+    fn test_config_overrides_and_emoji_switch() {
+        let mut ui = Config::default().ui;
+        ui.statuses.insert(
+            "doing".to_string(),
+            VisualOverride {
+                emoji: Some("🚧".to_string()),
+                label: Some("WIP".to_string()),
+                color: None,
+            },
+        );
+        ui.priorities.insert(
+            "unknown".to_string(),
+            VisualOverride {
+                label: Some("ignored".to_string()),
+                ..VisualOverride::default()
+            },
+        );
+
+        let registry = VisualRegistry::from_config(&ui);
+        assert_eq!(registry.status_text(Status::Doing), "🚧 WIP");
+        assert_eq!(registry.status(Status::Doing).color, "yellow");
+        assert_eq!(registry.priority_text(Priority::Low), "🟢 Low");
+
+        ui.emoji = false;
+        let registry = VisualRegistry::from_config(&ui);
+        assert_eq!(registry.status_text(Status::Doing), "WIP");
+        assert_eq!(registry.priority_prefix(Priority::High), "");
+    }
+}
@@ -0,0 +1,104 @@
+//! Identity of the user running vibe-ticket
+//!
+//! The name is taken from `user.name` of the project configuration, then
+//! from `git config user.name`, then from the login name; the email from
+//! `user.email`, then from `git config user.email`. The name is recorded as
+//! the actor of audit log entries, authors comments and is what `me` refers
+//! to in assignee filters.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use super::{Config, UserConfig};
+
+/// Name used when no source knows the user
+const UNKNOWN_USER: &str = "unknown";
+
+/// Resolved name and email of the current user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Identity {
+    /// User name
+    pub name: String,
+
+    /// Email address, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl Identity {
+    /// Resolves the identity from the `user` section of a configuration
+    pub fn resolve(user: &UserConfig) -> Self {
+        let name = non_empty(user.name.as_deref())
+            .or_else(|| git_config("user.name"))
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| UNKNOWN_USER.to_string());
+        let email = non_empty(user.email.as_deref()).or_else(|| git_config("user.email"));
+        Self { name, email }
+    }
+
+    /// Resolves the identity configured for the project at `project_root`
+    ///
+    /// A missing or unreadable configuration falls back to git and the
+    /// login name.
+    pub fn for_project(project_root: &Path) -> Self {
+        let user = Config::load_for_project(project_root)
+            .map(|config| config.user)
+            .unwrap_or_default();
+        Self::resolve(&user)
+    }
+
+    /// Resolves `me` to the user name; other names are returned unchanged
+    pub fn expand(&self, name: &str) -> String {
+        if name == "me" {
+            self.name.clone()
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+/// Returns a trimmed, non-empty value
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Reads a value from the git configuration
+fn git_config(key: &str) -> Option<String> {
+    Command::new("git")
+        .args(["config", key])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .and_then(|value| non_empty(Some(&value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_user_wins() {
+        let identity = Identity::resolve(&UserConfig {
+            name: Some(" Alice ".to_string()),
+            email: Some("alice@example.com".to_string()),
+        });
+        assert_eq!(identity.name, "Alice");
+        assert_eq!(identity.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(identity.expand("me"), "Alice");
+        assert_eq!(identity.expand("bob"), "bob");
+
+        let fallback = Identity::resolve(&UserConfig {
+            name: Some(String::new()),
+            email: None,
+        });
+        assert!(!fallback.name.is_empty());
+    }
+}
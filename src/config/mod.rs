@@ -53,9 +53,19 @@
 //! theme = "dark"
 //! ```
 
-use crate::error::{ErrorContext, Result};
+use crate::core::closing::CloseRequirement;
+use crate::core::fields::FieldSchema;
+use crate::core::{Priority, Query, Status};
+use crate::error::{ErrorContext, Result, VibeTicketError};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+mod identity;
+mod workspace;
+
+pub use identity::Identity;
+pub use workspace::{WORKSPACES_ENV, WorkspaceProject, WorkspaceRegistry};
 
 /// Main configuration structure for vibe-ticket
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +81,46 @@ pub struct Config {
 
     /// Plugin configuration
     pub plugins: PluginsConfig,
+
+    /// Team roster used for assignment suggestions
+    #[serde(default)]
+    pub team: TeamConfig,
+
+    /// MCP server configuration
+    #[serde(default)]
+    pub mcp: McpServerConfig,
+
+    /// Storage configuration
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// Identity of the user (see [`Identity`])
+    #[serde(default, skip_serializing_if = "UserConfig::is_empty")]
+    pub user: UserConfig,
+
+    /// Watcher notification configuration
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Aging policy for untouched tickets, applied by `vibe-ticket maintain`
+    #[serde(default)]
+    pub aging: AgingConfig,
+
+    /// Specification workflow configuration
+    #[serde(default)]
+    pub spec: SpecConfig,
+
+    /// Webhooks receiving ticket and spec events (see [`crate::integration::webhooks`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Named queries run with `list --filter <name>` (see [`Query`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub filters: BTreeMap<String, String>,
+
+    /// User-defined ticket fields (see [`crate::core::fields`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: FieldSchema,
 }
 
 /// Project-specific configuration
@@ -87,6 +137,41 @@ pub struct ProjectConfig {
 
     /// Default priority for new tickets
     pub default_priority: String,
+
+    /// Maximum description size in bytes before the bulk is moved to an attachment
+    #[serde(default = "default_description_size_limit")]
+    pub description_size_limit: usize,
+
+    /// Make closed tickets read-only until they are reopened
+    #[serde(default)]
+    pub immutable_closed: bool,
+
+    /// Days deleted tickets are kept in the trash before `gc` removes them
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+
+    /// Fields a ticket must have before it can be closed
+    #[serde(default)]
+    pub close_requirements: Vec<CloseRequirement>,
+
+    /// Length of the short IDs given to new tickets (6–8 characters)
+    #[serde(default = "default_short_id_length")]
+    pub short_id_length: usize,
+}
+
+/// Default description size limit (16 KiB)
+const fn default_description_size_limit() -> usize {
+    16 * 1024
+}
+
+/// Default trash retention period (30 days)
+pub(crate) const fn default_trash_retention_days() -> u32 {
+    30
+}
+
+/// Default short ID length (6 characters)
+pub(crate) const fn default_short_id_length() -> usize {
+    crate::core::MIN_SHORT_ID_LENGTH
 }
 
 /// UI configuration
@@ -101,8 +186,38 @@ pub struct UiConfig {
     /// Default page size for list commands
     pub page_size: usize,
 
-    /// Date format
+    /// Date format (strftime pattern)
     pub date_format: String,
+
+    /// Show dates relative to now (`3 days ago`) in terminal output
+    #[serde(default)]
+    pub relative_dates: bool,
+
+    /// Custom emoji, labels and colors per status (keyed by status, e.g. `doing`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub statuses: HashMap<String, VisualOverride>,
+
+    /// Custom emoji, labels and colors per priority (keyed by priority, e.g. `high`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub priorities: HashMap<String, VisualOverride>,
+}
+
+/// Custom rendering of a status or priority
+///
+/// Unset fields fall back to the built-in emoji, label and color.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VisualOverride {
+    /// Emoji shown before the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+
+    /// Label shown instead of the built-in name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+
+    /// Terminal color (e.g. `red`, `bright blue`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
 }
 
 /// Git integration configuration
@@ -134,6 +249,33 @@ pub struct GitConfig {
     pub worktree_cleanup_on_close: bool,
 }
 
+/// Team configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TeamConfig {
+    /// People tickets can be assigned to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<TeamMember>,
+
+    /// Skills required by tickets carrying a tag (keyed by tag)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tag_skills: HashMap<String, Vec<String>>,
+}
+
+/// Member of the team roster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamMember {
+    /// Name as used in the ticket assignee field
+    pub name: String,
+
+    /// Skills of the member
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+
+    /// Maximum estimate points of open tickets the member should carry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<u32>,
+}
+
 /// Plugin configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginsConfig {
@@ -142,6 +284,290 @@ pub struct PluginsConfig {
 
     /// Plugin directory
     pub directory: String,
+
+    /// Additional plugin directories, searched after the plugin directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub search_paths: Vec<String>,
+
+    /// Configuration of the built-in Slack plugin; the plugin is off if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack: Option<SlackConfig>,
+}
+
+/// Slack notifications sent by the built-in `slack` plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// Incoming webhook URL messages are posted to
+    pub webhook_url: String,
+
+    /// Channel overriding the webhook's default channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Destination of messages about tickets of a priority: a channel name,
+    /// or the incoming webhook URL of a channel
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channels: BTreeMap<Priority, String>,
+
+    /// Event kinds announced (see [`crate::events::TicketEvent::kind`])
+    #[serde(default = "default_slack_events")]
+    pub events: Vec<String>,
+}
+
+impl Default for SlackConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: String::new(),
+            channel: None,
+            channels: BTreeMap::new(),
+            events: default_slack_events(),
+        }
+    }
+}
+
+/// Events announced in Slack by default
+fn default_slack_events() -> Vec<String> {
+    ["created", "closed", "status_changed"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// MCP server configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    /// Log file of the server when it runs in the background
+    #[serde(default = "default_mcp_log_file")]
+    pub log_file: String,
+
+    /// API tokens accepted by the HTTP transport; any request is accepted if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<ApiToken>,
+
+    /// Milliseconds successive saves of a ticket are coalesced into one
+    /// write; 0, the default, writes every save through
+    #[serde(default = "default_write_coalesce_ms")]
+    pub write_coalesce_ms: u64,
+}
+
+impl Default for McpServerConfig {
+    fn default() -> Self {
+        Self {
+            log_file: default_mcp_log_file(),
+            tokens: Vec::new(),
+            write_coalesce_ms: default_write_coalesce_ms(),
+        }
+    }
+}
+
+/// Storage configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Locks serializing writes to a ticket between processes
+    #[serde(default)]
+    pub lock: LockConfig,
+}
+
+/// How long writers wait for the lock of a ticket held by another process
+///
+/// A writer tries `retries` times, `retry_delay_ms` apart, before giving
+/// up. A lock held for longer than `stale_after_secs` is taken to be left
+/// behind by a crashed process and taken over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockConfig {
+    /// Attempts to take a lock before giving up
+    #[serde(default = "default_lock_retries")]
+    pub retries: u32,
+
+    /// Milliseconds between attempts
+    #[serde(default = "default_lock_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+
+    /// Seconds after which a lock is considered stale
+    #[serde(default = "default_lock_stale_after_secs")]
+    pub stale_after_secs: u64,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            retries: default_lock_retries(),
+            retry_delay_ms: default_lock_retry_delay_ms(),
+            stale_after_secs: default_lock_stale_after_secs(),
+        }
+    }
+}
+
+/// API token of the MCP server
+///
+/// Only the SHA-256 hash of the token is stored; the token itself is shown
+/// once, when it is created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// Name identifying the token
+    pub name: String,
+
+    /// Hex-encoded SHA-256 hash of the token
+    pub hash: String,
+
+    /// What the token may do
+    pub scope: TokenScope,
+
+    /// Time the token was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Permission level of an API token, each including the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenScope {
+    /// Read tickets, specs and reports
+    ReadOnly,
+
+    /// Also create and change tickets, tasks and specs
+    Write,
+
+    /// Also change the configuration and remove worktrees
+    Admin,
+}
+
+impl std::fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::ReadOnly => "read-only",
+            Self::Write => "write",
+            Self::Admin => "admin",
+        })
+    }
+}
+
+impl std::str::FromStr for TokenScope {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "read-only" | "read_only" | "read" => Ok(Self::ReadOnly),
+            "write" => Ok(Self::Write),
+            "admin" => Ok(Self::Admin),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Invalid scope '{s}'. Must be one of: read-only, write, admin"
+            ))),
+        }
+    }
+}
+
+/// Identity of the user, overriding the git configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserConfig {
+    /// User name, recorded in the audit log and matched by `me`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Email address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl UserConfig {
+    /// Whether neither name nor email is configured
+    pub const fn is_empty(&self) -> bool {
+        self.name.is_none() && self.email.is_none()
+    }
+}
+
+/// Watcher notification configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Webhook receiving watcher notifications; notifications are dropped if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+/// Aging policy for untouched tickets
+///
+/// Open tickets without activity for `stale_after_days` are tagged `stale`
+/// and their watchers notified; stale tickets are closed after a further
+/// `close_after_days` unless activity resumes. Ticket kinds created from
+/// templates (e.g., `incident`) are exempted through their tags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgingConfig {
+    /// Days without activity after which a ticket becomes stale; aging is off if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_after_days: Option<u32>,
+
+    /// Days a stale ticket stays open before it is closed; never closed if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_after_days: Option<u32>,
+
+    /// Tickets with any of these tags are never aged
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exempt_tags: Vec<String>,
+
+    /// Tickets with these priorities are never aged
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exempt_priorities: Vec<Priority>,
+
+    /// Tickets in these statuses are never aged
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exempt_statuses: Vec<Status>,
+}
+
+/// Specification workflow configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpecConfig {
+    /// Refuse to approve a phase while its document has unresolved review comments
+    #[serde(default)]
+    pub strict_review: bool,
+
+    /// Approvals each phase needs before the next phase can start, by phase
+    /// name; phases not listed need none (see [`crate::specs::approval`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub required_approvals: BTreeMap<String, u32>,
+}
+
+/// A webhook receiving ticket and spec events
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Name identifying the webhook in the CLI
+    pub name: String,
+
+    /// URL the events are posted to
+    pub url: String,
+
+    /// Secret signing the payloads with HMAC-SHA256; payloads are unsigned if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+
+    /// Event kinds posted (e.g. `created`, `status_changed`); all events if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl WebhookConfig {
+    /// Whether the webhook receives events of `kind`
+    pub fn accepts(&self, kind: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == kind)
+    }
+}
+
+/// Default log file of the background MCP server
+fn default_mcp_log_file() -> String {
+    ".vibe-ticket/run/mcp.log".to_string()
+}
+
+const fn default_write_coalesce_ms() -> u64 {
+    0
+}
+
+const fn default_lock_retries() -> u32 {
+    10
+}
+
+const fn default_lock_retry_delay_ms() -> u64 {
+    100
+}
+
+const fn default_lock_stale_after_secs() -> u64 {
+    30
 }
 
 impl Default for Config {
@@ -152,12 +578,20 @@ impl Default for Config {
                 description: None,
                 default_assignee: None,
                 default_priority: "medium".to_string(),
+                description_size_limit: default_description_size_limit(),
+                immutable_closed: false,
+                trash_retention_days: default_trash_retention_days(),
+                close_requirements: Vec::new(),
+                short_id_length: default_short_id_length(),
             },
             ui: UiConfig {
                 theme: "auto".to_string(),
                 emoji: true,
                 page_size: 20,
                 date_format: "%Y-%m-%d %H:%M".to_string(),
+                relative_dates: false,
+                statuses: HashMap::new(),
+                priorities: HashMap::new(),
             },
             git: GitConfig {
                 enabled: true,
@@ -172,7 +606,19 @@ impl Default for Config {
             plugins: PluginsConfig {
                 enabled: vec![],
                 directory: ".vibe-ticket/plugins".to_string(),
+                search_paths: Vec::new(),
+                slack: None,
             },
+            team: TeamConfig::default(),
+            mcp: McpServerConfig::default(),
+            storage: StorageConfig::default(),
+            user: UserConfig::default(),
+            notifications: NotificationsConfig::default(),
+            aging: AgingConfig::default(),
+            spec: SpecConfig::default(),
+            webhooks: Vec::new(),
+            filters: BTreeMap::new(),
+            fields: FieldSchema::new(),
         }
     }
 }
@@ -210,6 +656,74 @@ impl Config {
         }
     }
 
+    /// Load the configuration of the project rooted at `project_root`
+    ///
+    /// Returns the default configuration if the project has no config file yet.
+    pub fn load_for_project(project_root: &Path) -> Result<Self> {
+        let path = project_root.join(".vibe-ticket/config.yaml");
+        if path.exists() {
+            Self::load_from_path(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Parses the named query `name` from `filters`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no filter has that name or its query is invalid
+    pub fn filter(&self, name: &str) -> Result<Query> {
+        let query = self.filters.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.filters.keys().map(String::as_str).collect();
+            VibeTicketError::InvalidInput(if known.is_empty() {
+                format!(
+                    "Unknown filter '{name}'. Define one with `config set filters.{name} <query>`"
+                )
+            } else {
+                format!(
+                    "Unknown filter '{name}'. Known filters: {}",
+                    known.join(", ")
+                )
+            })
+        })?;
+        query.parse()
+    }
+
+    /// Path of the worktree created for a ticket by `start --worktree`
+    ///
+    /// Returns `None` if the prefix points to the parent of a project root
+    /// that has no parent.
+    pub fn worktree_path(&self, project_root: &Path, ticket_slug: &str) -> Option<PathBuf> {
+        let prefix = self
+            .git
+            .worktree_prefix
+            .replace("{project}", &self.project.name);
+
+        let (base, clean_prefix) = crate::platform::split_prefix(&prefix);
+        let base_dir = crate::platform::prefix_base_dir(project_root, base)?;
+
+        Some(base_dir.join(format!(
+            "{}{ticket_slug}",
+            clean_prefix.trim_end_matches('-')
+        )))
+    }
+
+    /// Points the absolute paths under `from` to the same place under `to`,
+    /// for a project moved from `from` to `to`
+    ///
+    /// Relative paths are relative to the project root and stay valid.
+    pub fn relocate_paths(&mut self, from: &Path, to: &Path) {
+        let relocate = |path: &mut String| {
+            if let Ok(rest) = Path::new(path.as_str()).strip_prefix(from) {
+                *path = to.join(rest).to_string_lossy().into_owned();
+            }
+        };
+        relocate(&mut self.plugins.directory);
+        self.plugins.search_paths.iter_mut().for_each(relocate);
+        relocate(&mut self.mcp.log_file);
+    }
+
     /// Save configuration to the default location
     pub fn save(&self) -> Result<()> {
         self.save_to_path(".vibe-ticket/config.yaml")
@@ -240,6 +754,54 @@ mod tests {
         assert!(config.git.enabled);
     }
 
+    #[test]
+    fn test_worktree_path() {
+        let mut config = Config::default();
+        config.project.name = "app".to_string();
+        config.git.worktree_prefix = "../{project}-vibeticket-".to_string();
+
+        assert_eq!(
+            config.worktree_path(Path::new("/work/app"), "fix-login"),
+            Some(PathBuf::from("/work/app-vibeticketfix-login"))
+        );
+        assert!(config.worktree_path(Path::new("/"), "fix-login").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_relocate_paths() {
+        let mut config = Config::default();
+        config.plugins.search_paths =
+            vec!["/work/app/tools".to_string(), "/opt/plugins".to_string()];
+        config.mcp.log_file = "/work/app/.vibe-ticket/run/mcp.log".to_string();
+        config.relocate_paths(Path::new("/work/app"), Path::new("/src/app"));
+
+        assert_eq!(config.plugins.directory, ".vibe-ticket/plugins");
+        assert_eq!(
+            config.plugins.search_paths,
+            ["/src/app/tools", "/opt/plugins"]
+        );
+        assert_eq!(config.mcp.log_file, "/src/app/.vibe-ticket/run/mcp.log");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_worktree_path_windows() {
+        let mut config = Config::default();
+        config.project.name = "app".to_string();
+        config.git.worktree_prefix = r"..\{project}-vibeticket-".to_string();
+
+        assert_eq!(
+            config.worktree_path(Path::new(r"D:\work\app"), "fix-login"),
+            Some(PathBuf::from(r"D:\work\app-vibeticketfix-login"))
+        );
+        assert!(
+            config
+                .worktree_path(Path::new(r"D:\"), "fix-login")
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_save_and_load_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,268 @@
+//! Registry of the projects of a user
+//!
+//! Projects registered with `vibe-ticket workspace add` are kept in
+//! `~/.config/vibe-ticket/workspaces.yaml` (or the file named by
+//! `VIBE_TICKET_WORKSPACES`). `list --all-projects` aggregates their
+//! tickets, and the project selected with `workspace switch` is used when a
+//! command runs outside of any project.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Environment variable overriding the location of the registry
+pub const WORKSPACES_ENV: &str = "VIBE_TICKET_WORKSPACES";
+
+/// A registered project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceProject {
+    /// Name used on the command line and in the project column
+    pub name: String,
+
+    /// Root directory of the project
+    pub path: PathBuf,
+}
+
+/// The registered projects and the one switched to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceRegistry {
+    /// Name of the project used outside of any project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+
+    /// Registered projects, in the order they were added
+    #[serde(default)]
+    pub projects: Vec<WorkspaceProject>,
+}
+
+impl WorkspaceRegistry {
+    /// Location of the registry file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no configuration directory can be determined
+    pub fn path() -> Result<PathBuf> {
+        if let Some(path) = std::env::var_os(WORKSPACES_ENV).filter(|p| !p.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
+        directories::BaseDirs::new()
+            .map(|base| {
+                base.config_dir()
+                    .join("vibe-ticket")
+                    .join("workspaces.yaml")
+            })
+            .ok_or_else(|| VibeTicketError::custom("Cannot determine the configuration directory"))
+    }
+
+    /// Loads the registry, which is empty if it does not exist yet
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry cannot be read or parsed
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path()?)
+    }
+
+    /// Loads the registry from `path`, which is empty if it does not exist
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content).context("Failed to parse the workspace registry")
+    }
+
+    /// Saves the registry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry cannot be written
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    /// Saves the registry to `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = serde_yaml::to_string(self)?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Returns the project registered as `name`
+    pub fn get(&self, name: &str) -> Option<&WorkspaceProject> {
+        self.projects.iter().find(|project| project.name == name)
+    }
+
+    /// Returns the project switched to, if it is still registered
+    pub fn current(&self) -> Option<&WorkspaceProject> {
+        self.current.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Registers the project at `path` as `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name or the path is already registered
+    pub fn add(&mut self, name: &str, path: PathBuf) -> Result<()> {
+        if name.trim().is_empty() {
+            return Err(VibeTicketError::InvalidInput(
+                "Project name must not be empty".to_string(),
+            ));
+        }
+        if self.get(name).is_some() {
+            return Err(VibeTicketError::custom(format!(
+                "A project named '{name}' is already registered"
+            )));
+        }
+        if let Some(existing) = self.projects.iter().find(|p| p.path == path) {
+            return Err(VibeTicketError::custom(format!(
+                "{} is already registered as '{}'",
+                path.display(),
+                existing.name
+            )));
+        }
+        self.projects.push(WorkspaceProject {
+            name: name.to_string(),
+            path,
+        });
+        Ok(())
+    }
+
+    /// Unregisters the project `name`, also unselecting it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no project has that name
+    pub fn remove(&mut self, name: &str) -> Result<WorkspaceProject> {
+        let index = self.index(name)?;
+        if self.current.as_deref() == Some(name) {
+            self.current = None;
+        }
+        Ok(self.projects.remove(index))
+    }
+
+    /// Selects the project `name` for commands run outside of any project
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no project has that name
+    pub fn switch(&mut self, name: &str) -> Result<&WorkspaceProject> {
+        let index = self.index(name)?;
+        self.current = Some(name.to_string());
+        Ok(&self.projects[index])
+    }
+
+    /// Renames the project registered as `name`, keeping it selected if it
+    /// was
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no project has that name or another project
+    /// already has the new name
+    pub fn rename(&mut self, name: &str, new_name: &str) -> Result<()> {
+        let index = self.index(name)?;
+        if self.get(new_name).is_some() {
+            return Err(VibeTicketError::custom(format!(
+                "A project named '{new_name}' is already registered"
+            )));
+        }
+        self.projects[index].name = new_name.to_string();
+        if self.current.as_deref() == Some(name) {
+            self.current = Some(new_name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Points the projects registered at `from` to `to`
+    ///
+    /// Returns the number of projects changed.
+    pub fn relocate(&mut self, from: &Path, to: &Path) -> usize {
+        let mut changed = 0;
+        for project in self.projects.iter_mut().filter(|p| p.path == from) {
+            project.path = to.to_path_buf();
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Position of the project `name`
+    fn index(&self, name: &str) -> Result<usize> {
+        self.projects
+            .iter()
+            .position(|project| project.name == name)
+            .ok_or_else(|| {
+                VibeTicketError::custom(format!(
+                    "No project named '{name}' is registered. Run `vibe-ticket workspace list` to see them"
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_registry_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config/workspaces.yaml");
+        let mut registry = WorkspaceRegistry::load_from(&path).unwrap();
+        assert!(registry.projects.is_empty());
+
+        registry.add("api", PathBuf::from("/work/api")).unwrap();
+        registry.add("web", PathBuf::from("/work/web")).unwrap();
+        assert!(registry.add("api", PathBuf::from("/work/other")).is_err());
+        assert!(registry.add("api2", PathBuf::from("/work/api")).is_err());
+        assert!(registry.switch("mobile").is_err());
+        assert_eq!(
+            registry.switch("web").unwrap().path,
+            PathBuf::from("/work/web")
+        );
+        registry.save_to(&path).unwrap();
+
+        let mut registry = WorkspaceRegistry::load_from(&path).unwrap();
+        assert_eq!(registry.projects.len(), 2);
+        assert_eq!(registry.current().unwrap().name, "web");
+
+        registry.remove("web").unwrap();
+        assert!(registry.current().is_none());
+        assert!(registry.remove("web").is_err());
+    }
+
+    #[test]
+    fn test_rename_and_relocate() {
+        let mut registry = WorkspaceRegistry::default();
+        registry.add("api", PathBuf::from("/work/api")).unwrap();
+        registry.add("web", PathBuf::from("/work/web")).unwrap();
+        registry.switch("api").unwrap();
+
+        assert!(registry.rename("api", "web").is_err());
+        registry.rename("api", "backend").unwrap();
+        assert_eq!(registry.current().unwrap().path, Path::new("/work/api"));
+
+        assert_eq!(
+            registry.relocate(Path::new("/work/api"), Path::new("/src/api")),
+            1
+        );
+        assert_eq!(registry.get("backend").unwrap().path, Path::new("/src/api"));
+        assert_eq!(
+            registry.relocate(Path::new("/work/api"), Path::new("/src/api")),
+            0
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Aging of untouched tickets
+//!
+//! With an [`AgingConfig`] policy, open tickets nobody has touched for a
+//! while are tagged [`STALE_TAG`], and closed after a grace period unless
+//! activity resumes. The time a ticket became stale is kept in its
+//! [`STALE_SINCE_KEY`] metadata entry.
+//!
+//! Activity is the latest of the ticket's creation, start, work-log entries,
+//! comments and audit-log events. Tag changes made by aging itself are not
+//! activity, so marking a ticket stale does not revive it.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use super::Ticket;
+use super::audit::{AuditEvent, Change};
+use crate::config::AgingConfig;
+
+/// Tag of stale tickets
+pub const STALE_TAG: &str = "stale";
+
+/// Metadata key holding the time a ticket became stale (RFC 3339)
+pub const STALE_SINCE_KEY: &str = "stale_since";
+
+/// What aging does to a ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AgingAction {
+    /// Tag the ticket stale
+    MarkStale {
+        /// Days since the last activity
+        idle_days: i64,
+    },
+    /// Remove the stale mark after new activity or an exemption
+    Revive,
+    /// Close the ticket after the grace period
+    Close {
+        /// Days since the ticket became stale
+        stale_days: i64,
+    },
+}
+
+impl AgingAction {
+    /// Describes the action for display
+    pub fn describe(&self) -> String {
+        match self {
+            Self::MarkStale { idle_days } => format!("marked stale ({idle_days} days idle)"),
+            Self::Revive => "no longer stale".to_string(),
+            Self::Close { stale_days } => format!("closed ({stale_days} days stale)"),
+        }
+    }
+}
+
+/// Returns the time of the latest activity on a ticket
+///
+/// `history` is the ticket's audit log.
+pub fn last_activity(ticket: &Ticket, history: &[AuditEvent]) -> DateTime<Utc> {
+    let work_log = ticket.work_log.iter().map(|entry| entry.logged_at);
+    let comments = ticket
+        .comments
+        .iter()
+        .map(|comment| comment.edited_at.unwrap_or(comment.created_at));
+    let events = history
+        .iter()
+        .filter(|event| !is_aging_change(&event.change))
+        .map(|event| event.at);
+
+    std::iter::once(ticket.created_at)
+        .chain(ticket.started_at)
+        .chain(work_log)
+        .chain(comments)
+        .chain(events)
+        .max()
+        .unwrap_or(ticket.created_at)
+}
+
+/// Returns when the ticket became stale, if it is stale
+pub fn stale_since(ticket: &Ticket) -> Option<DateTime<Utc>> {
+    ticket
+        .metadata
+        .get(STALE_SINCE_KEY)?
+        .as_str()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Decides what aging does to a ticket at `now`
+///
+/// Returns `None` if aging is disabled, the ticket is closed, or nothing is
+/// due.
+pub fn evaluate(
+    policy: &AgingConfig,
+    ticket: &Ticket,
+    history: &[AuditEvent],
+    now: DateTime<Utc>,
+) -> Option<AgingAction> {
+    let stale_after = Duration::days(i64::from(policy.stale_after_days?));
+    if ticket.status == super::Status::Done {
+        return None;
+    }
+
+    let since = stale_since(ticket);
+    let tagged = ticket.tags.iter().any(|tag| tag == STALE_TAG);
+    if let Some(since) = since {
+        // Exempted since, untagged by hand, or touched again
+        if is_exempt(policy, ticket) || !tagged || last_activity(ticket, history) > since {
+            return Some(AgingAction::Revive);
+        }
+        let stale_days = (now - since).num_days();
+        return policy
+            .close_after_days
+            .filter(|grace| stale_days >= i64::from(*grace))
+            .map(|_| AgingAction::Close { stale_days });
+    }
+
+    if is_exempt(policy, ticket) {
+        return None;
+    }
+    let idle = now - last_activity(ticket, history);
+    (idle >= stale_after).then(|| AgingAction::MarkStale {
+        idle_days: idle.num_days(),
+    })
+}
+
+/// Applies an aging action to a ticket
+pub fn apply(action: AgingAction, ticket: &mut Ticket, now: DateTime<Utc>) {
+    match action {
+        AgingAction::MarkStale { .. } => {
+            if !ticket.tags.iter().any(|tag| tag == STALE_TAG) {
+                ticket.tags.push(STALE_TAG.to_string());
+            }
+            ticket
+                .metadata
+                .insert(STALE_SINCE_KEY.to_string(), now.to_rfc3339().into());
+        },
+        AgingAction::Revive => {
+            ticket.tags.retain(|tag| tag != STALE_TAG);
+            ticket.metadata.remove(STALE_SINCE_KEY);
+        },
+        AgingAction::Close { stale_days } => {
+            ticket.status = super::Status::Done;
+            ticket.closed_at = Some(now);
+            ticket.metadata.remove(STALE_SINCE_KEY);
+            ticket.metadata.insert(
+                super::closing::CLOSE_MESSAGE_KEY.to_string(),
+                format!("Closed automatically after {stale_days} days without activity").into(),
+            );
+        },
+    }
+}
+
+/// Returns true if the policy never ages the ticket
+fn is_exempt(policy: &AgingConfig, ticket: &Ticket) -> bool {
+    policy.exempt_statuses.contains(&ticket.status)
+        || policy.exempt_priorities.contains(&ticket.priority)
+        || ticket
+            .tags
+            .iter()
+            .any(|tag| policy.exempt_tags.contains(tag))
+}
+
+/// Returns true for a tag change that only adds or removes the stale tag
+fn is_aging_change(change: &Change) -> bool {
+    let Change::FieldChanged { field, from, to } = change else {
+        return false;
+    };
+    if field != "tags" {
+        return false;
+    }
+
+    let without_stale = |tags: &Option<String>| -> Vec<String> {
+        tags.as_deref()
+            .unwrap_or_default()
+            .split(", ")
+            .filter(|tag| !tag.is_empty() && *tag != STALE_TAG)
+            .map(ToString::to_string)
+            .collect()
+    };
+    without_stale(from) == without_stale(to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Priority, Status, audit};
+
+    fn policy() -> AgingConfig {
+        AgingConfig {
+            stale_after_days: Some(30),
+            close_after_days: Some(14),
+            exempt_priorities: vec![Priority::Critical],
+            ..AgingConfig::default()
+        }
+    }
+
+    fn idle_ticket(days: i64, now: DateTime<Utc>) -> Ticket {
+        let mut ticket = Ticket::new("proposal", "Proposal");
+        ticket.created_at = now - Duration::days(days);
+        ticket
+    }
+
+    #[test]
+    fn test_marks_closes_and_revives() {
+        let policy = policy();
+        let now = Utc::now();
+
+        assert_eq!(evaluate(&policy, &idle_ticket(10, now), &[], now), None);
+
+        let mut ticket = idle_ticket(40, now);
+        let action = evaluate(&policy, &ticket, &[], now).unwrap();
+        assert_eq!(action, AgingAction::MarkStale { idle_days: 40 });
+
+        // Marking the ticket stale is not activity
+        let before = ticket.clone();
+        apply(action, &mut ticket, now);
+        let history: Vec<AuditEvent> = audit::diff(Some(&before), &ticket)
+            .into_iter()
+            .map(|change| AuditEvent::now(change, None))
+            .collect();
+        assert_eq!(ticket.tags, vec![STALE_TAG]);
+        assert_eq!(evaluate(&policy, &ticket, &history, now), None);
+
+        let later = now + Duration::days(15);
+        let action = evaluate(&policy, &ticket, &history, later).unwrap();
+        assert_eq!(action, AgingAction::Close { stale_days: 15 });
+
+        // A comment before the grace period ends revives the ticket
+        ticket.add_comment("bob", "still needed");
+        assert_eq!(
+            evaluate(&policy, &ticket, &history, later),
+            Some(AgingAction::Revive)
+        );
+        apply(AgingAction::Revive, &mut ticket, later);
+        assert!(ticket.tags.is_empty());
+        assert!(stale_since(&ticket).is_none());
+
+        let mut closed = idle_ticket(40, now);
+        apply(AgingAction::Close { stale_days: 15 }, &mut closed, now);
+        assert_eq!(closed.status, Status::Done);
+        assert_eq!(evaluate(&policy, &closed, &[], now), None);
+    }
+
+    #[test]
+    fn test_exemptions_and_disabled_policy() {
+        let now = Utc::now();
+        let mut ticket = idle_ticket(100, now);
+        ticket.priority = Priority::Critical;
+        assert_eq!(evaluate(&policy(), &ticket, &[], now), None);
+
+        let ticket = idle_ticket(100, now);
+        assert_eq!(evaluate(&AgingConfig::default(), &ticket, &[], now), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! Assignment suggestions based on team capacity
+//!
+//! The load of a person is the sum of the estimates of their open tickets.
+//! Estimates are read from the `estimate` metadata entry, either as a number
+//! or as `{ "points": n }`; tickets without an estimate count as one point.
+//! A person is qualified for a ticket if they have every skill its tags
+//! require according to the `team.tag_skills` configuration.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::{Status, Ticket};
+use crate::config::TeamConfig;
+
+/// Metadata key holding a ticket's estimate
+pub const ESTIMATE_KEY: &str = "estimate";
+
+/// Load of a ticket without an estimate
+const DEFAULT_ESTIMATE: u32 = 1;
+
+/// Person considered for an assignment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Candidate {
+    /// Name as used in the assignee field
+    pub name: String,
+
+    /// Number of open tickets assigned to the person
+    pub open_tickets: usize,
+
+    /// Sum of the estimates of the open tickets
+    pub load: u32,
+
+    /// Configured capacity in estimate points
+    pub capacity: Option<u32>,
+
+    /// Required skills the person does not have
+    pub missing_skills: Vec<String>,
+}
+
+impl Candidate {
+    /// Returns true if the person has all required skills
+    pub fn is_qualified(&self) -> bool {
+        self.missing_skills.is_empty()
+    }
+
+    /// Returns true if taking a ticket of `estimate` points exceeds the capacity
+    pub fn is_over_capacity(&self, estimate: u32) -> bool {
+        self.capacity
+            .is_some_and(|capacity| self.load + estimate > capacity)
+    }
+}
+
+/// Estimate of a ticket in points
+pub fn estimate(ticket: &Ticket) -> u32 {
+    explicit_estimate(ticket).unwrap_or(DEFAULT_ESTIMATE)
+}
+
+/// Estimate recorded on a ticket, if any
+pub fn explicit_estimate(ticket: &Ticket) -> Option<u32> {
+    ticket
+        .metadata
+        .get(ESTIMATE_KEY)
+        .and_then(|value| value.as_u64().or_else(|| value.get("points")?.as_u64()))
+        .and_then(|points| u32::try_from(points).ok())
+}
+
+/// Skills required by a ticket's tags, sorted and deduplicated
+pub fn required_skills(ticket: &Ticket, team: &TeamConfig) -> Vec<String> {
+    let mut skills: Vec<String> = ticket
+        .tags
+        .iter()
+        .filter_map(|tag| team.tag_skills.get(tag))
+        .flatten()
+        .cloned()
+        .collect();
+    skills.sort();
+    skills.dedup();
+    skills
+}
+
+/// Rank everyone on the roster for `ticket`, best candidate first
+///
+/// The roster is the configured team; without one, everyone currently
+/// assigned a ticket is considered. Qualified people with spare capacity come
+/// first, ordered by load.
+pub fn rank_candidates(ticket: &Ticket, tickets: &[Ticket], team: &TeamConfig) -> Vec<Candidate> {
+    // Current load per assignee, ignoring the ticket being assigned
+    let mut loads: BTreeMap<&str, (usize, u32)> = BTreeMap::new();
+    for other in tickets.iter().filter(|t| t.id != ticket.id && is_open(t)) {
+        if let Some(assignee) = other.assignee.as_deref() {
+            let entry = loads.entry(assignee).or_default();
+            entry.0 += 1;
+            entry.1 += estimate(other);
+        }
+    }
+
+    let required = required_skills(ticket, team);
+    let mut candidates: Vec<Candidate> = if team.members.is_empty() {
+        loads
+            .iter()
+            .map(|(name, &(open_tickets, load))| Candidate {
+                name: (*name).to_string(),
+                open_tickets,
+                load,
+                capacity: None,
+                missing_skills: required.clone(),
+            })
+            .collect()
+    } else {
+        team.members
+            .iter()
+            .map(|member| {
+                let (open_tickets, load) =
+                    loads.get(member.name.as_str()).copied().unwrap_or_default();
+                Candidate {
+                    name: member.name.clone(),
+                    open_tickets,
+                    load,
+                    capacity: member.capacity,
+                    missing_skills: required
+                        .iter()
+                        .filter(|skill| !member.skills.contains(skill))
+                        .cloned()
+                        .collect(),
+                }
+            })
+            .collect()
+    };
+
+    let points = estimate(ticket);
+    candidates.sort_by(|a, b| {
+        (
+            !a.is_qualified(),
+            a.is_over_capacity(points),
+            a.load,
+            &a.name,
+        )
+            .cmp(&(
+                !b.is_qualified(),
+                b.is_over_capacity(points),
+                b.load,
+                &b.name,
+            ))
+    });
+    candidates
+}
+
+/// Pick the least-loaded qualified person with spare capacity
+pub fn suggest_assignee(
+    ticket: &Ticket,
+    tickets: &[Ticket],
+    team: &TeamConfig,
+) -> Option<Candidate> {
+    let points = estimate(ticket);
+    rank_candidates(ticket, tickets, team)
+        .into_iter()
+        .find(|c| c.is_qualified() && !c.is_over_capacity(points))
+}
+
+/// Returns true if a ticket still counts towards its assignee's load
+fn is_open(ticket: &Ticket) -> bool {
+    ticket.status != Status::Done
+        && !ticket
+            .metadata
+            .get("archived")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TeamMember;
+
+    fn assigned(slug: &str, assignee: &str, points: u64) -> Ticket {
+        let mut ticket = Ticket::new(slug, slug);
+        ticket.assignee = Some(assignee.to_string());
+        ticket.metadata.insert(
+            ESTIMATE_KEY.to_string(),
+            serde_json::json!({ "points": points }),
+        );
+        ticket
+    }
+
+    fn member(name: &str, skills: &[&str], capacity: Option<u32>) -> TeamMember {
+        TeamMember {
+            name: name.to_string(),
+            skills: skills.iter().map(ToString::to_string).collect(),
+            capacity,
+        }
+    }
+
+    #[test]
+    fn test_suggest_least_loaded_qualified() {
+        let mut team = TeamConfig::default();
+        team.members = vec![
+            member("alice", &["rust", "db"], Some(10)),
+            member("bob", &["rust"], Some(10)),
+            member("carol", &["db"], None),
+        ];
+        team.tag_skills
+            .insert("database".to_string(), vec!["db".to_string()]);
+
+        let tickets = vec![
+            assigned("a", "alice", 5),
+            assigned("b", "carol", 8),
+            assigned("c", "bob", 1),
+        ];
+
+        let mut ticket = Ticket::new("migrate", "Migrate schema");
+        ticket.tags = vec!["database".to_string()];
+
+        // bob is least loaded but lacks the db skill
+        let ranked = rank_candidates(&ticket, &tickets, &team);
+        let names: Vec<_> = ranked.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "carol", "bob"]);
+        assert!(!ranked[2].is_qualified());
+
+        // Done tickets do not count
+        let mut tickets = tickets;
+        tickets[1].status = Status::Done;
+        let suggestion = suggest_assignee(&ticket, &tickets, &team).unwrap();
+        assert_eq!(suggestion.name, "carol");
+        assert_eq!(suggestion.load, 0);
+    }
+
+    #[test]
+    fn test_suggest_respects_capacity() {
+        let mut team = TeamConfig::default();
+        team.members = vec![member("alice", &[], Some(3))];
+
+        let tickets = vec![assigned("a", "alice", 3)];
+        let ticket = Ticket::new("new", "New work");
+        assert!(suggest_assignee(&ticket, &tickets, &team).is_none());
+    }
+
+    #[test]
+    fn test_roster_from_assignees() {
+        let tickets = vec![assigned("a", "alice", 2), assigned("b", "bob", 1)];
+        let ticket = Ticket::new("new", "New work");
+
+        let suggestion = suggest_assignee(&ticket, &tickets, &TeamConfig::default()).unwrap();
+        assert_eq!(suggestion.name, "bob");
+        assert_eq!(estimate(&ticket), DEFAULT_ESTIMATE);
+    }
+}
@@ -0,0 +1,417 @@
+//! Audit log of ticket changes
+//!
+//! Every time a ticket is saved, storage compares it with the stored version
+//! and appends the differences to the ticket's audit log. The log is
+//! append-only: events are never rewritten, and the log outlives the ticket.
+//!
+//! The status changes of a log tell when a ticket entered and left each
+//! status (see [`status_transitions`]), which listings and statistics filter
+//! by.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{Status, Ticket, TicketId};
+
+/// Recorded change of a ticket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditEvent {
+    /// Time of the change
+    pub at: DateTime<Utc>,
+
+    /// User who made the change, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+
+    /// What changed
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// Kind of change recorded in the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Change {
+    /// The ticket was created
+    Created,
+    /// The status changed
+    StatusChanged { from: Status, to: Status },
+    /// The assignee changed
+    AssigneeChanged {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// Another field changed; long text fields are recorded without values
+    FieldChanged {
+        field: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        to: Option<String>,
+    },
+    /// A task was added
+    TaskAdded { task: String },
+    /// A task was completed
+    TaskCompleted { task: String },
+    /// A completed task was reopened
+    TaskReopened { task: String },
+    /// A task was removed
+    TaskRemoved { task: String },
+    /// The ticket was closed without meeting its close requirements
+    CloseRequirementsWaived { missing: Vec<String> },
+    /// The ticket was deleted
+    Deleted,
+}
+
+impl AuditEvent {
+    /// Creates an event that happened now
+    pub fn now(change: Change, actor: Option<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            actor,
+            change,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created => write!(f, "Created"),
+            Self::StatusChanged { from, to } => write!(f, "Status: {from} -> {to}"),
+            Self::AssigneeChanged { from, to } => write!(
+                f,
+                "Assignee: {} -> {}",
+                from.as_deref().unwrap_or("(none)"),
+                to.as_deref().unwrap_or("(none)")
+            ),
+            Self::FieldChanged { field, from, to } => match (from, to) {
+                (None, None) => write!(f, "Edited {field}"),
+                (from, to) => write!(
+                    f,
+                    "{}{}: {} -> {}",
+                    field[..1].to_uppercase(),
+                    &field[1..],
+                    from.as_deref().unwrap_or("(none)"),
+                    to.as_deref().unwrap_or("(none)")
+                ),
+            },
+            Self::TaskAdded { task } => write!(f, "Added task: {task}"),
+            Self::TaskCompleted { task } => write!(f, "Completed task: {task}"),
+            Self::TaskReopened { task } => write!(f, "Reopened task: {task}"),
+            Self::TaskRemoved { task } => write!(f, "Removed task: {task}"),
+            Self::CloseRequirementsWaived { missing } => {
+                write!(f, "Closed without: {}", missing.join(", "))
+            },
+            Self::Deleted => write!(f, "Deleted"),
+        }
+    }
+}
+
+/// Status change of a ticket, including the status it was created with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusTransition {
+    /// Time of the change
+    pub at: DateTime<Utc>,
+
+    /// Status left, `None` on creation
+    pub from: Option<Status>,
+
+    /// Status entered
+    pub to: Status,
+}
+
+/// Direction of a status transition a filter looks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// The ticket entered the status
+    Entered(Status),
+    /// The ticket left the status
+    Left(Status),
+}
+
+impl StatusTransition {
+    /// Whether this transition is of `kind`
+    pub fn is(&self, kind: TransitionKind) -> bool {
+        match kind {
+            TransitionKind::Entered(status) => self.to == status,
+            TransitionKind::Left(status) => self.from == Some(status),
+        }
+    }
+}
+
+/// Whether the audit log of a ticket whose status is now `current` records
+/// a transition of `kind` at or after `since` and at or before `until`
+pub fn transitioned_between(
+    log: &[AuditEvent],
+    current: Status,
+    kind: TransitionKind,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> bool {
+    status_transitions(log, current).iter().any(|transition| {
+        transition.is(kind)
+            && since.is_none_or(|since| transition.at >= since)
+            && until.is_none_or(|until| transition.at <= until)
+    })
+}
+
+/// Lists the status changes recorded in the audit log of a ticket whose
+/// status is now `current`, oldest first
+///
+/// Creation counts as entering the status the ticket was created with: the
+/// status the first recorded change left, or `current` if there is none.
+pub fn status_transitions(log: &[AuditEvent], current: Status) -> Vec<StatusTransition> {
+    let initial = log
+        .iter()
+        .find_map(|event| match event.change {
+            Change::StatusChanged { from, .. } => Some(from),
+            _ => None,
+        })
+        .unwrap_or(current);
+    log.iter()
+        .filter_map(|event| {
+            let (from, to) = match event.change {
+                Change::Created => (None, initial),
+                Change::StatusChanged { from, to } => (Some(from), to),
+                _ => return None,
+            };
+            Some(StatusTransition {
+                at: event.at,
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Lists the changes from `before` (the stored version, if any) to `after`
+pub fn diff(before: Option<&Ticket>, after: &Ticket) -> Vec<Change> {
+    let Some(before) = before else {
+        return vec![Change::Created];
+    };
+    let mut changes = Vec::new();
+
+    if before.status != after.status {
+        changes.push(Change::StatusChanged {
+            from: before.status,
+            to: after.status,
+        });
+    }
+    if before.assignee != after.assignee {
+        changes.push(Change::AssigneeChanged {
+            from: before.assignee.clone(),
+            to: after.assignee.clone(),
+        });
+    }
+
+    let fields = [
+        ("title", Some(&before.title), Some(&after.title)),
+        ("slug", Some(&before.slug), Some(&after.slug)),
+    ];
+    for (field, from, to) in fields {
+        if from != to {
+            changes.push(field_changed(field, from.cloned(), to.cloned()));
+        }
+    }
+    if before.priority != after.priority {
+        changes.push(field_changed(
+            "priority",
+            Some(before.priority.to_string()),
+            Some(after.priority.to_string()),
+        ));
+    }
+    if before.severity != after.severity {
+        changes.push(field_changed(
+            "severity",
+            before.severity.map(|s| s.to_string()),
+            after.severity.map(|s| s.to_string()),
+        ));
+    }
+    if before.due != after.due {
+        changes.push(field_changed(
+            "due",
+            before.due.map(|d| d.to_string()),
+            after.due.map(|d| d.to_string()),
+        ));
+    }
+    if before.milestone != after.milestone {
+        changes.push(field_changed(
+            "milestone",
+            before.milestone.clone(),
+            after.milestone.clone(),
+        ));
+    }
+    if before.parent != after.parent {
+        changes.push(field_changed(
+            "parent",
+            before.parent.as_ref().map(TicketId::short),
+            after.parent.as_ref().map(TicketId::short),
+        ));
+    }
+    if before.tags != after.tags {
+        changes.push(field_changed(
+            "tags",
+            Some(before.tags.join(", ")),
+            Some(after.tags.join(", ")),
+        ));
+    }
+    if before.description != after.description {
+        changes.push(field_changed("description", None, None));
+    }
+    if before.incident != after.incident {
+        changes.push(field_changed("timeline", None, None));
+    }
+
+    diff_tasks(before, after, &mut changes);
+    changes
+}
+
+fn field_changed(field: &str, from: Option<String>, to: Option<String>) -> Change {
+    Change::FieldChanged {
+        field: field.to_string(),
+        from,
+        to,
+    }
+}
+
+fn diff_tasks(before: &Ticket, after: &Ticket, changes: &mut Vec<Change>) {
+    for task in &after.tasks {
+        match before.tasks.iter().find(|t| t.id == task.id) {
+            None => changes.push(Change::TaskAdded {
+                task: task.title.clone(),
+            }),
+            Some(old) if !old.completed && task.completed => changes.push(Change::TaskCompleted {
+                task: task.title.clone(),
+            }),
+            Some(old) if old.completed && !task.completed => changes.push(Change::TaskReopened {
+                task: task.title.clone(),
+            }),
+            Some(_) => {},
+        }
+    }
+    for task in &before.tasks {
+        if !after.tasks.iter().any(|t| t.id == task.id) {
+            changes.push(Change::TaskRemoved {
+                task: task.title.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Priority, Task};
+
+    #[test]
+    fn test_diff() {
+        let mut before = Ticket::new("fix", "Fix it");
+        before.tasks.push(Task::new("first"));
+        before.tasks.push(Task::new("second"));
+        assert_eq!(diff(None, &before), vec![Change::Created]);
+        assert!(diff(Some(&before), &before).is_empty());
+
+        let mut after = before.clone();
+        after.start();
+        after.assignee = Some("alice".to_string());
+        after.priority = Priority::High;
+        after.description = "More detail".to_string();
+        after.tasks[0].complete();
+        after.tasks.remove(1);
+        after.tasks.push(Task::new("third"));
+
+        let changes: Vec<String> = diff(Some(&before), &after)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "Status: Todo -> Doing",
+                "Assignee: (none) -> alice",
+                "Priority: Medium -> High",
+                "Edited description",
+                "Completed task: first",
+                "Added task: third",
+                "Removed task: second",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        let event = |change| AuditEvent::now(change, None);
+        let log = [
+            event(Change::Created),
+            event(Change::AssigneeChanged {
+                from: None,
+                to: Some("alice".to_string()),
+            }),
+            event(Change::StatusChanged {
+                from: Status::Todo,
+                to: Status::Doing,
+            }),
+        ];
+        let transitions: Vec<_> = status_transitions(&log, Status::Doing)
+            .into_iter()
+            .map(|t| (t.from, t.to))
+            .collect();
+        assert_eq!(
+            transitions,
+            [(None, Status::Todo), (Some(Status::Todo), Status::Doing)]
+        );
+
+        let entered = TransitionKind::Entered(Status::Doing);
+        let at = log[2].at;
+        assert!(transitioned_between(
+            &log,
+            Status::Doing,
+            entered,
+            Some(at),
+            Some(at)
+        ));
+        assert!(!transitioned_between(
+            &log,
+            Status::Doing,
+            entered,
+            None,
+            Some(log[0].at - chrono::Duration::seconds(1))
+        ));
+        assert!(transitioned_between(
+            &log,
+            Status::Doing,
+            TransitionKind::Left(Status::Todo),
+            None,
+            None
+        ));
+        assert!(!transitioned_between(
+            &log,
+            Status::Doing,
+            TransitionKind::Left(Status::Doing),
+            None,
+            None
+        ));
+
+        let created = status_transitions(&log[..1], Status::Blocked);
+        assert_eq!(created[0].to, Status::Blocked);
+        assert_eq!(created[0].at, log[0].at);
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = AuditEvent::now(
+            Change::StatusChanged {
+                from: Status::Todo,
+                to: Status::Done,
+            },
+            Some("bob".to_string()),
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "status_changed");
+        assert_eq!(json["to"], "done");
+        let parsed: AuditEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
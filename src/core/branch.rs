@@ -0,0 +1,121 @@
+//! Tickets referenced by git branch names
+//!
+//! `start` names branches `{branch_prefix}{slug}`, and worktrees check out
+//! such a branch. [`ticket_for_branch`] reverses this naming to find the
+//! ticket a branch was created for, also accepting branches named by hand
+//! that contain the slug or the short ID of a ticket.
+
+use super::Ticket;
+
+/// Ticket reference meaning "the ticket of the current branch"
+pub const BRANCH_REF: &str = "@branch";
+
+/// How a branch name was matched to a ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchMatch {
+    /// `{branch_prefix}{slug}`, as created by `start`
+    Template,
+    /// The last path segment of the branch is the slug
+    Slug,
+    /// The branch contains the short ID of the ticket
+    ShortId,
+    /// The branch contains the slug between separators
+    Contains,
+}
+
+impl BranchMatch {
+    /// Human-readable description of the match
+    pub const fn describe(self) -> &'static str {
+        match self {
+            Self::Template => "branch naming template",
+            Self::Slug => "slug",
+            Self::ShortId => "short ID",
+            Self::Contains => "slug in branch name",
+        }
+    }
+}
+
+/// Finds the ticket a branch was created for
+///
+/// Tried in order: the branch naming template, the last path segment as a
+/// slug, a short ID in the branch, and finally the longest slug contained in
+/// the branch between `/`, `-` or `_` separators.
+pub fn ticket_for_branch<'a>(
+    branch: &str,
+    branch_prefix: &str,
+    tickets: &'a [Ticket],
+) -> Option<(&'a Ticket, BranchMatch)> {
+    if let Some(slug) = branch.strip_prefix(branch_prefix) {
+        if let Some(ticket) = tickets.iter().find(|t| t.slug == slug) {
+            return Some((ticket, BranchMatch::Template));
+        }
+    }
+
+    let last = branch.rsplit('/').next().unwrap_or(branch);
+    if let Some(ticket) = tickets.iter().find(|t| t.slug == last) {
+        return Some((ticket, BranchMatch::Slug));
+    }
+
+    let tokens: Vec<&str> = branch.split(is_separator).collect();
+    let mut by_id = tickets.iter().filter(|t| {
+        let short = t.id.short();
+        tokens.iter().any(|token| *token == short)
+    });
+    if let (Some(ticket), None) = (by_id.next(), by_id.next()) {
+        return Some((ticket, BranchMatch::ShortId));
+    }
+
+    tickets
+        .iter()
+        .filter(|t| contains_token(branch, &t.slug))
+        .max_by_key(|t| t.slug.len())
+        .map(|ticket| (ticket, BranchMatch::Contains))
+}
+
+const fn is_separator(c: char) -> bool {
+    matches!(c, '/' | '-' | '_')
+}
+
+/// Whether `needle` occurs in `haystack` bounded by separators or the ends
+fn contains_token(haystack: &str, needle: &str) -> bool {
+    !needle.is_empty()
+        && haystack.match_indices(needle).any(|(start, _)| {
+            let end = start + needle.len();
+            haystack[..start]
+                .chars()
+                .next_back()
+                .is_none_or(is_separator)
+                && haystack[end..].chars().next().is_none_or(is_separator)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_for_branch() {
+        let tickets = vec![
+            Ticket::new("fix-login", "Fix login"),
+            Ticket::new("fix", "Fix"),
+            Ticket::new("api", "API"),
+        ];
+        let find = |branch: &str| {
+            ticket_for_branch(branch, "ticket/", &tickets).map(|(t, how)| (t.slug.as_str(), how))
+        };
+
+        assert_eq!(
+            find("ticket/fix-login"),
+            Some(("fix-login", BranchMatch::Template))
+        );
+        assert_eq!(find("feature/api"), Some(("api", BranchMatch::Slug)));
+        let short = format!("wip/{}-oauth", tickets[1].id.short());
+        assert_eq!(find(&short), Some(("fix", BranchMatch::ShortId)));
+        assert_eq!(
+            find("user/fix-login-retry"),
+            Some(("fix-login", BranchMatch::Contains))
+        );
+        assert_eq!(find("main"), None);
+        assert_eq!(find("ticket/apiary"), None);
+    }
+}
@@ -0,0 +1,232 @@
+//! Builder for tickets
+//!
+//! Code embedding vibe-ticket creates tickets through [`TicketBuilder`]
+//! instead of filling in [`Ticket`] field by field:
+//!
+//! ```
+//! use vibe_ticket::core::{Priority, Status, Ticket};
+//!
+//! let ticket = Ticket::builder()
+//!     .slug("fix-login")
+//!     .title("Fix login")
+//!     .priority(Priority::High)
+//!     .tag("auth")
+//!     .task("write tests")
+//!     .status(Status::Doing)
+//!     .build()?;
+//!
+//! assert_eq!(ticket.tasks.len(), 1);
+//! assert!(ticket.started_at.is_some());
+//! # Ok::<(), vibe_ticket::VibeTicketError>(())
+//! ```
+
+use chrono::{NaiveDate, Utc};
+
+use super::{Priority, Severity, Status, Task, Ticket, TicketId};
+use crate::cli::{slugify, validate_slug};
+use crate::error::{Result, VibeTicketError};
+
+/// Builder returned by [`Ticket::builder`]
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct TicketBuilder {
+    id: Option<TicketId>,
+    slug: Option<String>,
+    title: Option<String>,
+    description: String,
+    priority: Priority,
+    severity: Option<Severity>,
+    status: Status,
+    tags: Vec<String>,
+    assignee: Option<String>,
+    tasks: Vec<Task>,
+    depends_on: Vec<TicketId>,
+    due: Option<NaiveDate>,
+    watchers: Vec<String>,
+    metadata: Vec<(String, serde_json::Value)>,
+}
+
+impl Ticket {
+    /// Starts building a ticket
+    pub fn builder() -> TicketBuilder {
+        TicketBuilder::default()
+    }
+}
+
+impl TicketBuilder {
+    /// Uses a specific ID instead of a new one
+    pub const fn id(mut self, id: TicketId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the slug (derived from the title if not set)
+    pub fn slug(mut self, slug: impl Into<String>) -> Self {
+        self.slug = Some(slug.into());
+        self
+    }
+
+    /// Sets the title (the slug if not set)
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the priority
+    pub const fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the incident severity
+    pub const fn severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    /// Sets the status
+    ///
+    /// `Doing` and `Done` also set the start and close timestamps.
+    pub const fn status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Adds a tag
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Adds several tags
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the assignee
+    pub fn assignee(mut self, assignee: impl Into<String>) -> Self {
+        self.assignee = Some(assignee.into());
+        self
+    }
+
+    /// Adds a task with the given title
+    pub fn task(mut self, title: impl Into<String>) -> Self {
+        self.tasks.push(Task::new(title));
+        self
+    }
+
+    /// Adds a ticket this ticket depends on
+    pub fn depends_on(mut self, id: TicketId) -> Self {
+        self.depends_on.push(id);
+        self
+    }
+
+    /// Sets the due date
+    pub const fn due(mut self, due: NaiveDate) -> Self {
+        self.due = Some(due);
+        self
+    }
+
+    /// Adds a watcher
+    pub fn watcher(mut self, user: impl Into<String>) -> Self {
+        self.watchers.push(user.into());
+        self
+    }
+
+    /// Sets a metadata entry
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Builds the ticket
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither a slug nor a title was given, or the slug
+    /// is not valid (lowercase alphanumeric with hyphens)
+    pub fn build(self) -> Result<Ticket> {
+        let (slug, title) = match (self.slug, self.title) {
+            (Some(slug), Some(title)) => (slug, title),
+            (Some(slug), None) => (slug.clone(), slug),
+            (None, Some(title)) => (slugify(&title), title),
+            (None, None) => {
+                return Err(VibeTicketError::InvalidInput(
+                    "A ticket needs a slug or a title".to_string(),
+                ));
+            },
+        };
+        validate_slug(&slug)?;
+
+        let mut ticket = match self.id {
+            Some(id) => Ticket::with_id(id, slug, title),
+            None => Ticket::new(slug, title),
+        };
+        ticket.description = self.description;
+        ticket.priority = self.priority;
+        ticket.severity = self.severity;
+        ticket.tags = self.tags;
+        ticket.assignee = self.assignee;
+        ticket.tasks = self.tasks;
+        ticket.depends_on = self.depends_on;
+        ticket.due = self.due;
+        ticket.metadata = self.metadata.into_iter().collect();
+        for watcher in self.watchers {
+            ticket.watch(watcher);
+        }
+
+        match self.status {
+            Status::Doing => ticket.start(),
+            Status::Done => {
+                ticket.started_at = Some(Utc::now());
+                ticket.close();
+            },
+            status => ticket.status = status,
+        }
+
+        Ok(ticket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults_and_derivations() {
+        let ticket = Ticket::builder().title("Fix Login Bug").build().unwrap();
+        assert_eq!(ticket.slug, "fix-login-bug");
+        assert_eq!(ticket.status, Status::Todo);
+        assert_eq!(ticket.priority, Priority::default());
+
+        let ticket = Ticket::builder()
+            .slug("release")
+            .status(Status::Done)
+            .watcher("alice")
+            .watcher("alice")
+            .metadata("estimate", 3)
+            .build()
+            .unwrap();
+        assert_eq!(ticket.title, "release");
+        assert!(ticket.closed_at.is_some());
+        assert_eq!(ticket.watchers, vec!["alice"]);
+        assert_eq!(ticket.metadata["estimate"], 3);
+    }
+
+    #[test]
+    fn test_builder_rejects_missing_or_invalid_slug() {
+        assert!(Ticket::builder().build().is_err());
+        assert!(Ticket::builder().slug("Not A Slug").build().is_err());
+    }
+}
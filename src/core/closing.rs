@@ -0,0 +1,133 @@
+//! Fields required before a ticket can be closed
+//!
+//! A project lists its requirements in `project.close_requirements`. Storage
+//! checks them whenever a ticket moves to `done` and refuses the change with
+//! the list of missing items, unless the close is forced; a forced close is
+//! recorded in the audit log together with what was missing.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::Ticket;
+use crate::error::{Result, VibeTicketError};
+
+/// Metadata key of the close message
+pub const CLOSE_MESSAGE_KEY: &str = "close_message";
+
+/// Metadata key of the linked pull request URL
+pub const PR_URL_KEY: &str = "pr_url";
+
+/// Something a ticket must have before it is closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseRequirement {
+    /// A non-empty close message
+    Message,
+    /// At least one tag
+    Tag,
+    /// A linked pull request URL
+    #[serde(rename = "pr")]
+    PullRequest,
+    /// At least one work-log entry
+    WorkLog,
+}
+
+impl CloseRequirement {
+    /// All requirements, in the order they are reported
+    pub const ALL: [Self; 4] = [Self::Message, Self::Tag, Self::PullRequest, Self::WorkLog];
+
+    /// Returns true if `ticket` satisfies the requirement
+    pub fn is_met(self, ticket: &Ticket) -> bool {
+        let metadata_set = |key: &str| {
+            ticket
+                .metadata
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .is_some_and(|value| !value.trim().is_empty())
+        };
+        match self {
+            Self::Message => metadata_set(CLOSE_MESSAGE_KEY),
+            Self::Tag => !ticket.tags.is_empty(),
+            Self::PullRequest => metadata_set(PR_URL_KEY),
+            Self::WorkLog => !ticket.work_log.is_empty(),
+        }
+    }
+
+    /// What is missing when the requirement is not met
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Message => "a close message (--message)",
+            Self::Tag => "at least one tag",
+            Self::PullRequest => "a linked pull request (--pr-url or --pr)",
+            Self::WorkLog => "at least one work-log entry (vibe-ticket log)",
+        }
+    }
+}
+
+impl fmt::Display for CloseRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Message => "message",
+            Self::Tag => "tag",
+            Self::PullRequest => "pr",
+            Self::WorkLog => "work_log",
+        })
+    }
+}
+
+impl FromStr for CloseRequirement {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|requirement| requirement.to_string() == s.trim().to_lowercase())
+            .ok_or_else(|| {
+                VibeTicketError::InvalidInput(format!(
+                    "Unknown close requirement: {s}. Expected message, tag, pr or work_log"
+                ))
+            })
+    }
+}
+
+/// Returns the requirements `ticket` does not meet
+pub fn missing(ticket: &Ticket, requirements: &[CloseRequirement]) -> Vec<CloseRequirement> {
+    requirements
+        .iter()
+        .copied()
+        .filter(|requirement| !requirement.is_met(ticket))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::WorkLogEntry;
+
+    #[test]
+    fn test_missing_requirements() {
+        let mut ticket = Ticket::new("close-me", "Close me");
+        assert_eq!(
+            missing(&ticket, &CloseRequirement::ALL),
+            CloseRequirement::ALL
+        );
+
+        ticket.tags.push("bug".to_string());
+        ticket
+            .metadata
+            .insert(CLOSE_MESSAGE_KEY.to_string(), serde_json::json!("  "));
+        ticket.work_log.push(WorkLogEntry::new("fixed"));
+        assert_eq!(
+            missing(&ticket, &CloseRequirement::ALL),
+            vec![CloseRequirement::Message, CloseRequirement::PullRequest]
+        );
+
+        assert_eq!(
+            "PR".parse::<CloseRequirement>().unwrap(),
+            CloseRequirement::PullRequest
+        );
+        assert!("reviewer".parse::<CloseRequirement>().is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Represents a comment in the discussion of a ticket
+///
+/// Comments are numbered per ticket, starting at 1. A comment keeps its
+/// number when other comments are deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Comment {
+    /// Number of the comment within its ticket
+    pub id: u32,
+
+    /// Author of the comment
+    pub author: String,
+
+    /// Timestamp when the comment was written
+    pub created_at: DateTime<Utc>,
+
+    /// Text of the comment
+    pub body: String,
+
+    /// Whether the comment was changed after it was written
+    #[serde(default)]
+    pub edited: bool,
+
+    /// Timestamp of the last edit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+impl Comment {
+    /// Creates a new comment written now
+    pub fn new(id: u32, author: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            id,
+            author: author.into(),
+            created_at: Utc::now(),
+            body: body.into(),
+            edited: false,
+            edited_at: None,
+        }
+    }
+
+    /// Replaces the text and marks the comment as edited
+    pub fn edit(&mut self, body: impl Into<String>) {
+        self.body = body.into();
+        self.edited = true;
+        self.edited_at = Some(Utc::now());
+    }
+}
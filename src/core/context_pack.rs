@@ -0,0 +1,389 @@
+//! Compact project snapshot for AI sessions
+//!
+//! A context pack is a Markdown document with what an assistant joining a
+//! project needs first: the active ticket, the most pressing open tickets,
+//! the phase of the current spec and the conventions from `CLAUDE.md`. It is
+//! assembled in that order of importance and cut to fit a token budget.
+
+use std::fmt::Write as FmtWrite;
+
+use serde::Serialize;
+
+use super::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::SpecMetadata;
+
+/// Token budget used when none is given
+pub const DEFAULT_BUDGET: usize = 4000;
+
+/// Open tickets listed at most
+pub const TOP_TICKETS: usize = 10;
+
+/// Bytes of UTF-8 counted per token when estimating the size of the pack
+///
+/// Counting bytes rather than characters keeps the estimate on the safe side
+/// for non-Latin text.
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Bytes of the active ticket's description included
+const DESCRIPTION_BYTES: usize = 800;
+
+/// Headings of the `CLAUDE.md` sections taken as conventions
+const CONVENTION_HEADINGS: [&str; 6] = [
+    "convention",
+    "guideline",
+    "rule",
+    "style",
+    "standard",
+    "workflow",
+];
+
+/// Everything a context pack is assembled from
+#[derive(Debug, Clone, Copy)]
+pub struct ContextSources<'a> {
+    /// Name of the project
+    pub project: &'a str,
+    /// Description of the project
+    pub description: Option<&'a str>,
+    /// All tickets of the project
+    pub tickets: &'a [Ticket],
+    /// ID of the active ticket
+    pub active: Option<&'a TicketId>,
+    /// The active spec, or the most recently updated unfinished one
+    pub spec: Option<&'a SpecMetadata>,
+    /// Content of `CLAUDE.md`
+    pub claude_md: Option<&'a str>,
+}
+
+/// A project snapshot cut to a token budget
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextPack {
+    /// The snapshot as Markdown
+    pub markdown: String,
+    /// Estimated size of the snapshot in tokens
+    pub tokens: usize,
+    /// Token budget the snapshot was cut to
+    pub budget: usize,
+    /// Open tickets listed
+    pub tickets: usize,
+    /// Open tickets left out for lack of room
+    pub omitted_tickets: usize,
+    /// Whether anything was cut to fit the budget
+    pub truncated: bool,
+}
+
+impl ContextPack {
+    /// Assembles a context pack of at most `budget` tokens
+    ///
+    /// The header, active ticket and spec are always included; open tickets
+    /// are added while they fit, and the conventions fill what is left.
+    pub fn build(sources: &ContextSources<'_>, budget: usize) -> Self {
+        let limit = budget * BYTES_PER_TOKEN;
+        let mut md = header(sources);
+        let mut truncated = false;
+
+        let active = sources
+            .active
+            .and_then(|id| sources.tickets.iter().find(|ticket| &ticket.id == id));
+        if let Some(ticket) = active {
+            md.push_str(&active_section(ticket));
+        }
+        if let Some(spec) = sources.spec {
+            md.push_str(&spec_section(spec));
+        }
+
+        let open = open_tickets(sources.tickets, sources.active);
+        let mut listed = 0;
+        if !open.is_empty() {
+            md.push_str("\n## Open tickets\n\n");
+            for ticket in open.iter().take(TOP_TICKETS) {
+                let line = ticket_line(ticket);
+                if md.len() + line.len() > limit {
+                    truncated = true;
+                    break;
+                }
+                md.push_str(&line);
+                listed += 1;
+            }
+            if listed < open.len() {
+                writeln!(md, "- ... and {} more", open.len() - listed).unwrap();
+            }
+        }
+
+        if let Some(conventions) = sources.claude_md.map(conventions) {
+            let heading = "\n## Conventions\n\n";
+            let room = limit.saturating_sub(md.len() + heading.len());
+            let (text, cut) = cut_lines(conventions.trim(), room);
+            truncated |= cut;
+            if !text.is_empty() {
+                md.push_str(heading);
+                md.push_str(text);
+                md.push('\n');
+            }
+        }
+
+        Self {
+            tokens: estimate_tokens(&md),
+            markdown: md,
+            budget,
+            tickets: listed,
+            omitted_tickets: open.len() - listed,
+            truncated,
+        }
+    }
+}
+
+/// Estimates the number of tokens of `text`
+pub const fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(BYTES_PER_TOKEN)
+}
+
+/// Parses a token budget such as `8000`, `8k` or `8000-tokens`
+///
+/// # Errors
+///
+/// Returns an error if the budget is not a positive number of tokens
+pub fn parse_budget(input: &str) -> Result<usize> {
+    let value = input.trim().to_lowercase();
+    let value = value
+        .strip_suffix("tokens")
+        .map_or(value.as_str(), |rest| rest.trim_end_matches(['-', ' ']));
+    let (number, scale) = value
+        .strip_suffix('k')
+        .map_or((value, 1), |number| (number, 1000));
+    number
+        .parse::<usize>()
+        .ok()
+        .map(|number| number * scale)
+        .filter(|budget| *budget > 0)
+        .ok_or_else(|| {
+            VibeTicketError::InvalidInput(format!(
+                "Invalid budget '{input}'; expected a number of tokens such as 8000 or 8k"
+            ))
+        })
+}
+
+/// Project name and description
+fn header(sources: &ContextSources<'_>) -> String {
+    let mut md = format!("# Project: {}\n", sources.project);
+    if let Some(description) = sources.description.filter(|d| !d.trim().is_empty()) {
+        writeln!(md, "\n{}", description.trim()).unwrap();
+    }
+    md
+}
+
+/// The active ticket with its description and open tasks
+fn active_section(ticket: &Ticket) -> String {
+    let mut md = String::from("\n## Active ticket\n\n");
+    writeln!(
+        md,
+        "**{}** `{}` — {}",
+        ticket.title,
+        ticket.display_id(),
+        ticket.slug
+    )
+    .unwrap();
+    write!(
+        md,
+        "Status: {} · Priority: {}",
+        ticket.status, ticket.priority
+    )
+    .unwrap();
+    if let Some(assignee) = &ticket.assignee {
+        write!(md, " · Assignee: {assignee}").unwrap();
+    }
+    if let Some(due) = ticket.due {
+        write!(md, " · Due: {due}").unwrap();
+    }
+    md.push('\n');
+
+    let description = ticket.description.trim();
+    if !description.is_empty() {
+        let (text, cut) = cut_lines(description, DESCRIPTION_BYTES);
+        writeln!(md, "\n{text}{}", if cut { "\n…" } else { "" }).unwrap();
+    }
+
+    let open_tasks: Vec<_> = ticket.tasks.iter().filter(|t| !t.completed).collect();
+    if !open_tasks.is_empty() {
+        writeln!(
+            md,
+            "\nOpen tasks ({} of {}):",
+            open_tasks.len(),
+            ticket.tasks.len()
+        )
+        .unwrap();
+        for task in open_tasks {
+            writeln!(md, "- [ ] {}", task.title).unwrap();
+        }
+    }
+    md
+}
+
+/// The current spec and its phase
+fn spec_section(spec: &SpecMetadata) -> String {
+    let progress = &spec.progress;
+    let approved: Vec<&str> = [
+        ("requirements", progress.requirements_approved),
+        ("design", progress.design_approved),
+        ("tasks", progress.tasks_approved),
+    ]
+    .into_iter()
+    .filter_map(|(phase, approved)| approved.then_some(phase))
+    .collect();
+
+    let mut md = String::from("\n## Current spec\n\n");
+    writeln!(md, "**{}** `{}`", spec.title, spec.id).unwrap();
+    write!(md, "Phase: {}", progress.current_phase).unwrap();
+    if !approved.is_empty() {
+        write!(md, " · Approved: {}", approved.join(", ")).unwrap();
+    }
+    md.push('\n');
+    md
+}
+
+/// One line of the open ticket list
+fn ticket_line(ticket: &Ticket) -> String {
+    format!(
+        "- `{}` [{}] {} — {}\n",
+        ticket.display_id(),
+        ticket.status,
+        ticket.priority,
+        ticket.title
+    )
+}
+
+/// Open, non-archived tickets other than the active one, most pressing first
+///
+/// Tickets in progress come first, then by priority, due date and age.
+fn open_tickets<'a>(tickets: &'a [Ticket], active: Option<&TicketId>) -> Vec<&'a Ticket> {
+    let mut open: Vec<&Ticket> = tickets
+        .iter()
+        .filter(|ticket| ticket.status != Status::Done && Some(&ticket.id) != active)
+        .filter(|ticket| {
+            !ticket
+                .metadata
+                .get("archived")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false)
+        })
+        .collect();
+    open.sort_by(|a, b| {
+        b.status
+            .is_active()
+            .cmp(&a.status.is_active())
+            .then(b.priority.cmp(&a.priority))
+            .then(a.due.is_none().cmp(&b.due.is_none()))
+            .then(a.due.cmp(&b.due))
+            .then(a.created_at.cmp(&b.created_at))
+    });
+    open
+}
+
+/// The convention sections of `CLAUDE.md`, or all of it if it has none
+fn conventions(claude_md: &str) -> String {
+    let mut selected = String::new();
+    let mut level = None;
+    for line in claude_md.lines() {
+        let heading = line.trim_start();
+        let depth = heading.chars().take_while(|c| *c == '#').count();
+        if depth > 0 && heading[depth..].starts_with(' ') {
+            let title = heading.to_lowercase();
+            if level.is_some_and(|level| depth <= level) {
+                level = None;
+            }
+            if level.is_none() && CONVENTION_HEADINGS.iter().any(|h| title.contains(h)) {
+                level = Some(depth);
+            }
+        }
+        if level.is_some() {
+            selected.push_str(line);
+            selected.push('\n');
+        }
+    }
+
+    if selected.trim().is_empty() {
+        claude_md.to_string()
+    } else {
+        selected
+    }
+}
+
+/// The whole lines of `text` that fit in `limit` bytes, and whether any
+/// were left out
+fn cut_lines(text: &str, limit: usize) -> (&str, bool) {
+    if text.len() <= limit {
+        return (text, false);
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let end = text[..end].rfind('\n').unwrap_or(0);
+    (text[..end].trim_end(), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Priority;
+
+    fn ticket(slug: &str, status: Status, priority: Priority) -> Ticket {
+        let mut ticket = Ticket::new(slug, slug);
+        ticket.status = status;
+        ticket.priority = priority;
+        ticket
+    }
+
+    #[test]
+    fn test_build_orders_and_fits_budget() {
+        let mut active = ticket("active", Status::Doing, Priority::High);
+        active.description = "Fix the login flow".to_string();
+        active
+            .tasks
+            .push(crate::core::Task::new("write tests".to_string()));
+        let tickets = vec![
+            active.clone(),
+            ticket("low", Status::Todo, Priority::Low),
+            ticket("critical", Status::Todo, Priority::Critical),
+            ticket("reviewing", Status::Review, Priority::Medium),
+            ticket("done", Status::Done, Priority::Critical),
+        ];
+        let claude_md =
+            "# Intro\n\nWelcome\n\n## Coding Conventions\n\n- Use rustfmt\n\n## Other\n\nSkip me\n";
+        let sources = ContextSources {
+            project: "demo",
+            description: Some("Demo project"),
+            tickets: &tickets,
+            active: Some(&active.id),
+            spec: None,
+            claude_md: Some(claude_md),
+        };
+
+        let pack = ContextPack::build(&sources, DEFAULT_BUDGET);
+        let md = &pack.markdown;
+        assert!(md.starts_with("# Project: demo\n\nDemo project\n"));
+        assert!(md.contains("## Active ticket") && md.contains("- [ ] write tests"));
+        let position = |slug: &str| md.find(&format!("— {slug}\n")).unwrap();
+        assert!(position("reviewing") < position("critical"));
+        assert!(position("critical") < position("low"));
+        assert!(!md.contains("— done\n"));
+        assert!(md.contains("- Use rustfmt") && !md.contains("Skip me"));
+        assert_eq!((pack.tickets, pack.omitted_tickets), (3, 0));
+        assert!(!pack.truncated);
+
+        let small = ContextPack::build(&sources, 60);
+        assert!(small.truncated);
+        assert!(small.omitted_tickets > 0);
+        assert!(small.markdown.contains("more\n"));
+    }
+
+    #[test]
+    fn test_parse_budget() {
+        assert_eq!(parse_budget("8000").unwrap(), 8000);
+        assert_eq!(parse_budget("8000-tokens").unwrap(), 8000);
+        assert_eq!(parse_budget("8k").unwrap(), 8000);
+        assert_eq!(parse_budget("2K tokens").unwrap(), 2000);
+        assert!(parse_budget("0").is_err());
+        assert!(parse_budget("lots").is_err());
+    }
+}
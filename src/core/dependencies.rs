@@ -0,0 +1,256 @@
+//! Dependencies between tickets
+//!
+//! A ticket lists the tickets it waits for in `depends_on` and the tickets
+//! waiting for it in `blocks`. The two lists are kept symmetric: linking `a`
+//! to depend on `b` records `b` in `a.depends_on` and `a` in `b.blocks`.
+//! Dependency cycles are rejected when a link is added.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+
+/// Records that `ticket` depends on `dependency`
+///
+/// `tickets` is used to detect cycles. Returns `false` if the link already
+/// existed.
+pub fn add_dependency(
+    ticket: &mut Ticket,
+    dependency: &mut Ticket,
+    tickets: &[Ticket],
+) -> Result<bool> {
+    if ticket.id == dependency.id {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Ticket '{}' cannot depend on itself",
+            ticket.slug
+        )));
+    }
+    if depends_transitively(tickets, &dependency.id, &ticket.id) {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "'{}' already depends on '{}'; linking them would create a cycle",
+            dependency.slug, ticket.slug
+        )));
+    }
+
+    let added = push_unique(&mut ticket.depends_on, &dependency.id);
+    push_unique(&mut dependency.blocks, &ticket.id);
+    Ok(added)
+}
+
+/// Removes the dependency of `ticket` on `dependency`
+///
+/// Returns `false` if there was no such link.
+pub fn remove_dependency(ticket: &mut Ticket, dependency: &mut Ticket) -> bool {
+    let before = ticket.depends_on.len();
+    ticket.depends_on.retain(|id| id != &dependency.id);
+    dependency.blocks.retain(|id| id != &ticket.id);
+    ticket.depends_on.len() != before
+}
+
+/// Returns the dependencies of `ticket` that are not closed yet
+///
+/// Dependencies that no longer exist are ignored.
+pub fn open_dependencies<'a>(ticket: &Ticket, tickets: &'a [Ticket]) -> Vec<&'a Ticket> {
+    ticket
+        .depends_on
+        .iter()
+        .filter_map(|id| tickets.iter().find(|t| &t.id == id))
+        .filter(|t| t.status != Status::Done)
+        .collect()
+}
+
+/// Fails if `ticket` still has open dependencies
+pub fn ensure_closable(ticket: &Ticket, tickets: &[Ticket]) -> Result<()> {
+    let open = open_dependencies(ticket, tickets);
+    if open.is_empty() {
+        return Ok(());
+    }
+
+    Err(VibeTicketError::OpenDependencies {
+        slug: ticket.slug.clone(),
+        open: open.iter().map(|t| t.slug.clone()).collect(),
+    })
+}
+
+/// Node of a dependency tree
+#[derive(Debug, Clone)]
+pub struct DependencyNode {
+    /// ID of the ticket
+    pub id: TicketId,
+
+    /// Slug of the ticket, or the ID if the ticket no longer exists
+    pub slug: String,
+
+    /// Status of the ticket, `None` if it no longer exists
+    pub status: Option<Status>,
+
+    /// Dependencies of the ticket
+    pub children: Vec<Self>,
+}
+
+/// Builds the tree of everything `ticket` transitively depends on
+pub fn dependency_tree(ticket: &Ticket, tickets: &[Ticket]) -> Vec<DependencyNode> {
+    let by_id: HashMap<&TicketId, &Ticket> = tickets.iter().map(|t| (&t.id, t)).collect();
+    let mut path = HashSet::from([ticket.id.clone()]);
+    children(&ticket.depends_on, &by_id, &mut path)
+}
+
+/// Builds the nodes for `ids`, stopping at tickets already on the current path
+fn children(
+    ids: &[TicketId],
+    by_id: &HashMap<&TicketId, &Ticket>,
+    path: &mut HashSet<TicketId>,
+) -> Vec<DependencyNode> {
+    let mut nodes = Vec::new();
+    for id in ids {
+        if path.contains(id) {
+            continue;
+        }
+        let Some(ticket) = by_id.get(id) else {
+            nodes.push(DependencyNode {
+                id: id.clone(),
+                slug: id.short(),
+                status: None,
+                children: Vec::new(),
+            });
+            continue;
+        };
+
+        path.insert(id.clone());
+        let children = children(&ticket.depends_on, by_id, path);
+        path.remove(id);
+        nodes.push(DependencyNode {
+            id: id.clone(),
+            slug: ticket.slug.clone(),
+            status: Some(ticket.status),
+            children,
+        });
+    }
+    nodes
+}
+
+/// Renders the dependency graph of a ticket as indented lines
+///
+/// Lists the dependency tree followed by the tickets the ticket blocks;
+/// returns no lines if the ticket has neither.
+pub fn render_graph(ticket: &Ticket, tickets: &[Ticket]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let tree = dependency_tree(ticket, tickets);
+    if !tree.is_empty() {
+        lines.push("Depends on:".to_string());
+        render_nodes(&tree, 1, &mut lines);
+    }
+
+    if !ticket.blocks.is_empty() {
+        lines.push("Blocks:".to_string());
+        for id in &ticket.blocks {
+            match tickets.iter().find(|t| &t.id == id) {
+                Some(blocked) => lines.push(format!("  {} ({})", blocked.slug, blocked.status)),
+                None => lines.push(format!("  {} (missing)", id.short())),
+            }
+        }
+    }
+
+    lines
+}
+
+fn render_nodes(nodes: &[DependencyNode], depth: usize, lines: &mut Vec<String>) {
+    for node in nodes {
+        let status = node
+            .status
+            .map_or_else(|| "missing".to_string(), |s| s.to_string());
+        lines.push(format!("{}{} ({status})", "  ".repeat(depth), node.slug));
+        render_nodes(&node.children, depth + 1, lines);
+    }
+}
+
+/// Returns true if `from` reaches `to` by following `depends_on`
+fn depends_transitively(tickets: &[Ticket], from: &TicketId, to: &TicketId) -> bool {
+    let by_id: HashMap<&TicketId, &Ticket> = tickets.iter().map(|t| (&t.id, t)).collect();
+    let mut stack = vec![from];
+    let mut seen = HashSet::new();
+
+    while let Some(id) = stack.pop() {
+        if id == to {
+            return true;
+        }
+        if !seen.insert(id) {
+            continue;
+        }
+        if let Some(ticket) = by_id.get(id) {
+            stack.extend(&ticket.depends_on);
+        }
+    }
+
+    false
+}
+
+/// Appends `id` unless it is already present, returning whether it was added
+fn push_unique(ids: &mut Vec<TicketId>, id: &TicketId) -> bool {
+    if ids.contains(id) {
+        false
+    } else {
+        ids.push(id.clone());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_dependency_is_symmetric_and_rejects_cycles() {
+        let mut a = Ticket::new("a", "A");
+        let mut b = Ticket::new("b", "B");
+        let mut c = Ticket::new("c", "C");
+
+        assert!(add_dependency(&mut a, &mut b, &[]).unwrap());
+        assert!(!add_dependency(&mut a, &mut b, &[]).unwrap());
+        assert_eq!(a.depends_on, vec![b.id.clone()]);
+        assert_eq!(b.blocks, vec![a.id.clone()]);
+
+        add_dependency(&mut b, &mut c, &[]).unwrap();
+        let tickets = vec![a.clone(), b.clone(), c.clone()];
+        assert!(add_dependency(&mut c, &mut a, &tickets).is_err());
+        let mut same = c.clone();
+        assert!(add_dependency(&mut c, &mut same, &tickets).is_err());
+
+        assert!(remove_dependency(&mut a, &mut b));
+        assert!(a.depends_on.is_empty() && b.blocks.is_empty());
+        assert!(!remove_dependency(&mut a, &mut b));
+    }
+
+    #[test]
+    fn test_open_dependencies_and_tree() {
+        let mut a = Ticket::new("a", "A");
+        let mut b = Ticket::new("b", "B");
+        let mut c = Ticket::new("c", "C");
+        add_dependency(&mut a, &mut b, &[]).unwrap();
+        add_dependency(&mut b, &mut c, &[]).unwrap();
+        add_dependency(&mut a, &mut c, &[]).unwrap();
+        c.status = Status::Done;
+
+        let tickets = vec![a.clone(), b, c];
+        let open: Vec<_> = open_dependencies(&a, &tickets)
+            .iter()
+            .map(|t| t.slug.as_str())
+            .collect();
+        assert_eq!(open, vec!["b"]);
+        assert!(ensure_closable(&a, &tickets).is_err());
+        assert!(ensure_closable(&tickets[1], &tickets).is_ok());
+
+        let tree = dependency_tree(&a, &tickets);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].slug, "b");
+        assert_eq!(tree[0].children[0].slug, "c");
+        assert_eq!(tree[1].status, Some(Status::Done));
+
+        assert_eq!(
+            render_graph(&a, &tickets),
+            vec!["Depends on:", "  b (Todo)", "    c (Done)", "  c (Done)"]
+        );
+        assert_eq!(render_graph(&tickets[2], &tickets)[0], "Blocks:");
+    }
+}
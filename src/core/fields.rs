@@ -0,0 +1,258 @@
+//! User-defined ticket fields
+//!
+//! A project declares typed fields under `fields` in its configuration, for
+//! example `estimate: { type: number }` or
+//! `team: { type: enum, values: [core, web] }`. Values are kept in the
+//! ticket's metadata under the field name, as a JSON number for number
+//! fields and as a string otherwise (`YYYY-MM-DD` for dates). They are set
+//! with `name=value` assignments and checked against the declaration.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Ticket;
+use crate::error::{Result, VibeTicketError};
+
+/// Declared fields by name
+pub type FieldSchema = BTreeMap<String, FieldDefinition>;
+
+/// Type of a user-defined field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FieldDefinition {
+    /// Free text
+    String,
+    /// Integer or decimal number
+    Number,
+    /// Calendar date (`YYYY-MM-DD`)
+    Date,
+    /// One of a fixed list of values
+    Enum { values: Vec<String> },
+}
+
+impl FieldDefinition {
+    /// Parses the text of a value of field `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a value of this type
+    pub fn parse(&self, name: &str, raw: &str) -> Result<Value> {
+        let raw = raw.trim();
+        let invalid = |expected: &str| {
+            VibeTicketError::InvalidInput(format!(
+                "Invalid value '{raw}' for field '{name}': expected {expected}"
+            ))
+        };
+        match self {
+            Self::String => Ok(Value::from(raw)),
+            Self::Number => raw.parse::<i64>().map(Value::from).or_else(|_| {
+                raw.parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| invalid("a number"))
+            }),
+            Self::Date => NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .map(|date| Value::from(date.to_string()))
+                .map_err(|_| invalid("a date (YYYY-MM-DD)")),
+            Self::Enum { values } => values
+                .iter()
+                .find(|value| value.as_str() == raw)
+                .map(|value| Value::from(value.as_str()))
+                .ok_or_else(|| invalid(&format!("one of {}", values.join(", ")))),
+        }
+    }
+
+    /// Checks a stored value of field `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not of this type
+    pub fn validate(&self, name: &str, value: &Value) -> Result<()> {
+        match (self, value) {
+            (Self::Number, Value::Number(_)) => Ok(()),
+            (Self::Number, _) => Err(VibeTicketError::InvalidInput(format!(
+                "Invalid value {value} for field '{name}': expected a number"
+            ))),
+            (_, Value::String(text)) => self.parse(name, text).map(|_| ()),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Invalid value {value} for field '{name}': expected {self}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for FieldDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String => f.write_str("string"),
+            Self::Number => f.write_str("number"),
+            Self::Date => f.write_str("date"),
+            Self::Enum { values } => write!(f, "enum:{}", values.join(",")),
+        }
+    }
+}
+
+impl FromStr for FieldDefinition {
+    type Err = VibeTicketError;
+
+    /// Parses `string`, `number`, `date` or `enum:<value>,<value>...`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(values) = s.strip_prefix("enum:") {
+            let values: Vec<String> = values
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect();
+            if values.is_empty() {
+                return Err(VibeTicketError::InvalidInput(
+                    "An enum field needs at least one value, as in enum:low,high".to_string(),
+                ));
+            }
+            return Ok(Self::Enum { values });
+        }
+        match s {
+            "string" => Ok(Self::String),
+            "number" => Ok(Self::Number),
+            "date" => Ok(Self::Date),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Unknown field type: {s}. Expected string, number, date or enum:<values>"
+            ))),
+        }
+    }
+}
+
+/// Applies `name=value` assignments to the fields of `ticket`
+///
+/// An empty value removes the field.
+///
+/// # Errors
+///
+/// Returns an error if an assignment is malformed, names an undeclared
+/// field or has an invalid value
+pub fn set_fields(ticket: &mut Ticket, schema: &FieldSchema, assignments: &[String]) -> Result<()> {
+    for assignment in assignments {
+        let (name, raw) = assignment.split_once('=').ok_or_else(|| {
+            VibeTicketError::InvalidInput(format!(
+                "Invalid field assignment '{assignment}': expected name=value"
+            ))
+        })?;
+        let name = name.trim();
+        let definition = definition(schema, name)?;
+        if raw.trim().is_empty() {
+            ticket.metadata.remove(name);
+        } else {
+            let value = definition.parse(name, raw)?;
+            ticket.metadata.insert(name.to_string(), value);
+        }
+    }
+    Ok(())
+}
+
+/// Checks the values of the declared fields of `ticket`
+///
+/// # Errors
+///
+/// Returns an error naming the ticket and the first invalid field
+pub fn validate_fields(ticket: &Ticket, schema: &FieldSchema) -> Result<()> {
+    for (name, definition) in schema {
+        if let Some(value) = ticket.metadata.get(name) {
+            definition.validate(name, value).map_err(|e| match e {
+                VibeTicketError::InvalidInput(message) => {
+                    VibeTicketError::InvalidInput(format!("Ticket '{}': {message}", ticket.slug))
+                },
+                e => e,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Declared fields set on `ticket`, with their values as text
+pub fn field_values<'a>(ticket: &Ticket, schema: &'a FieldSchema) -> Vec<(&'a str, String)> {
+    schema
+        .keys()
+        .filter_map(|name| {
+            ticket
+                .metadata
+                .get(name)
+                .map(|value| (name.as_str(), field_text(value)))
+        })
+        .collect()
+}
+
+/// Text of a stored field value
+pub fn field_text(value: &Value) -> String {
+    value
+        .as_str()
+        .map_or_else(|| value.to_string(), str::to_string)
+}
+
+/// Returns the declaration of field `name`
+fn definition<'a>(schema: &'a FieldSchema, name: &str) -> Result<&'a FieldDefinition> {
+    schema.get(name).ok_or_else(|| {
+        VibeTicketError::InvalidInput(if schema.is_empty() {
+            format!("Unknown field '{name}'. Declare it with `config set fields.{name} <type>`")
+        } else {
+            format!(
+                "Unknown field '{name}'. Declared fields: {}",
+                schema.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_validate_fields() {
+        let schema: FieldSchema = [
+            ("estimate".to_string(), FieldDefinition::Number),
+            ("review".to_string(), FieldDefinition::Date),
+            ("team".to_string(), "enum:core,web".parse().unwrap()),
+        ]
+        .into();
+        let mut ticket = Ticket::new("typed", "Typed");
+
+        set_fields(
+            &mut ticket,
+            &schema,
+            &[
+                "estimate=3".into(),
+                "team=web".into(),
+                "review=2026-11-02".into(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(ticket.metadata["estimate"], serde_json::json!(3));
+        assert_eq!(
+            field_values(&ticket, &schema),
+            vec![
+                ("estimate", "3".to_string()),
+                ("review", "2026-11-02".to_string()),
+                ("team", "web".to_string())
+            ]
+        );
+
+        assert!(set_fields(&mut ticket, &schema, &["team=mobile".into()]).is_err());
+        assert!(set_fields(&mut ticket, &schema, &["estimate=lots".into()]).is_err());
+        assert!(set_fields(&mut ticket, &schema, &["owner=me".into()]).is_err());
+        assert!(set_fields(&mut ticket, &schema, &["estimate".into()]).is_err());
+        set_fields(&mut ticket, &schema, &["estimate=".into()]).unwrap();
+        assert!(!ticket.metadata.contains_key("estimate"));
+
+        validate_fields(&ticket, &schema).unwrap();
+        ticket
+            .metadata
+            .insert("review".to_string(), serde_json::json!("soon"));
+        assert!(validate_fields(&ticket, &schema).is_err());
+    }
+}
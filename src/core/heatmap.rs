@@ -0,0 +1,217 @@
+//! Activity heatmap
+//!
+//! A [`Heatmap`] counts the changes recorded in the ticket audit logs over a
+//! window of whole weeks, per day and per hour of the week. The days render
+//! as a contribution graph (one column per week, one row per weekday) and
+//! the hours as a weekday by hour-of-day grid; both shade a cell by its
+//! count relative to the busiest cell.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Weekday};
+use serde::Serialize;
+
+/// Weeks shown when no window is given
+pub const DEFAULT_WEEKS: u32 = 26;
+
+/// Shades of a cell, from no activity to the busiest cell
+const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Weekdays in row order
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Changes per day and per hour of the week over a window of weeks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Heatmap {
+    /// First day of the window, a Monday
+    pub start: NaiveDate,
+    /// Last day of the window
+    pub end: NaiveDate,
+    /// Changes in the window
+    pub total: usize,
+    /// Changes per day, for the days with activity
+    pub days: BTreeMap<NaiveDate, usize>,
+    /// Changes per weekday (Monday first) and hour of the day
+    pub hours: Vec<[usize; 24]>,
+}
+
+impl Heatmap {
+    /// Creates an empty heatmap of `weeks` weeks ending on `end`
+    ///
+    /// The window starts on the Monday of the first week, so the last week
+    /// is the one containing `end`.
+    pub fn new(end: NaiveDate, weeks: u32) -> Self {
+        let monday = end - Duration::days(i64::from(end.weekday().num_days_from_monday()));
+        Self {
+            start: monday - Duration::weeks(i64::from(weeks.max(1)) - 1),
+            end,
+            total: 0,
+            days: BTreeMap::new(),
+            hours: vec![[0; 24]; 7],
+        }
+    }
+
+    /// Counts a change made at `at`, if it falls within the window
+    pub fn add(&mut self, at: DateTime<Local>) {
+        let date = at.date_naive();
+        if date < self.start || date > self.end {
+            return;
+        }
+        self.total += 1;
+        *self.days.entry(date).or_insert(0) += 1;
+        self.hours[at.weekday().num_days_from_monday() as usize][at.hour() as usize] += 1;
+    }
+
+    /// Mondays of the weeks in the window
+    pub fn mondays(&self) -> Vec<NaiveDate> {
+        self.start
+            .iter_weeks()
+            .take_while(|monday| *monday <= self.end)
+            .collect()
+    }
+
+    /// Day with the most changes, the earliest on a tie
+    pub fn busiest_day(&self) -> Option<(NaiveDate, usize)> {
+        self.days
+            .iter()
+            .rev()
+            .max_by_key(|(_, count)| **count)
+            .map(|(date, count)| (*date, *count))
+    }
+
+    /// Hour of the week with the most changes, the earliest on a tie
+    pub fn busiest_hour(&self) -> Option<(Weekday, u32, usize)> {
+        let mut busiest = None;
+        for (day, hours) in WEEKDAYS.iter().zip(&self.hours) {
+            for (hour, &count) in (0..).zip(hours) {
+                if count > busiest.map_or(0, |(_, _, max)| max) {
+                    busiest = Some((*day, hour, count));
+                }
+            }
+        }
+        busiest
+    }
+
+    /// Renders the days as a contribution graph, one column per week
+    pub fn render_days(&self) -> Vec<String> {
+        let mondays = self.mondays();
+        let max = self.days.values().copied().max().unwrap_or(0);
+
+        // Month labels above the first week of each month, where they fit
+        let mut header = vec![' '; mondays.len() * 2];
+        let mut free_from = 0;
+        for (week, monday) in mondays.iter().enumerate() {
+            let column = week * 2;
+            let new_month = week == 0 || mondays[week - 1].month() != monday.month();
+            if new_month && column >= free_from && column + 3 <= header.len() {
+                header.splice(column..column + 3, monday.format("%b").to_string().chars());
+                free_from = column + 4;
+            }
+        }
+        let mut lines = vec![
+            format!("    {}", header.iter().collect::<String>())
+                .trim_end()
+                .to_string(),
+        ];
+
+        for (offset, day) in (0..).zip(WEEKDAYS) {
+            let label = if matches!(day, Weekday::Mon | Weekday::Wed | Weekday::Fri) {
+                day.to_string()
+            } else {
+                String::new()
+            };
+            let mut line = format!("{label:<4}");
+            for date in mondays
+                .iter()
+                .map(|monday| *monday + Duration::days(offset))
+            {
+                if date > self.end {
+                    break;
+                }
+                line.push(shade(self.days.get(&date).copied().unwrap_or(0), max));
+                line.push(' ');
+            }
+            lines.push(line.trim_end().to_string());
+        }
+
+        lines.push(legend());
+        lines
+    }
+
+    /// Renders the hours as a grid, one row per weekday
+    pub fn render_hours(&self) -> Vec<String> {
+        let max = self.hours.iter().flatten().copied().max().unwrap_or(0);
+        let mut lines = vec![format!("    {:<12}{:<12}{:<12}{}", 0, 6, 12, 18)];
+        for (day, hours) in WEEKDAYS.iter().zip(&self.hours) {
+            let mut line = format!("{:<4}", day.to_string());
+            for &count in hours {
+                line.push(shade(count, max));
+                line.push(' ');
+            }
+            lines.push(line.trim_end().to_string());
+        }
+        lines.push(legend());
+        lines
+    }
+}
+
+/// Shade of a cell with `count` changes when the busiest cell has `max`
+fn shade(count: usize, max: usize) -> char {
+    if count == 0 || max == 0 {
+        return SHADES[0];
+    }
+    let levels = SHADES.len() - 1;
+    SHADES[(count * levels).div_ceil(max).clamp(1, levels)]
+}
+
+/// Line explaining the shades
+fn legend() -> String {
+    let shades: Vec<String> = SHADES.iter().map(char::to_string).collect();
+    format!("    Less {} More", shades.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_heatmap_counts_and_renders() {
+        // 2026-10-17 is a Saturday
+        let end = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let mut heatmap = Heatmap::new(end, 4);
+        assert_eq!(heatmap.start, NaiveDate::from_ymd_opt(2026, 9, 21).unwrap());
+        assert_eq!(heatmap.mondays().len(), 4);
+
+        let at = |d, h| Local.with_ymd_and_hms(2026, 10, d, h, 30, 0).unwrap();
+        heatmap.add(at(14, 9));
+        heatmap.add(at(14, 9));
+        heatmap.add(at(16, 15));
+        heatmap.add(Local.with_ymd_and_hms(2026, 9, 1, 9, 0, 0).unwrap());
+        assert_eq!(heatmap.total, 3);
+        assert_eq!(
+            heatmap.busiest_day(),
+            Some((NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(), 2))
+        );
+        assert_eq!(heatmap.busiest_hour(), Some((Weekday::Wed, 9, 2)));
+
+        let days = heatmap.render_days();
+        assert_eq!(days.len(), 9);
+        assert!(days[0].starts_with("    Sep Oct"));
+        assert_eq!(days[3], "Wed · · · █");
+        assert_eq!(days[5], "Fri · · · ▒");
+        // The window ends before Sunday of the last week
+        assert_eq!(days[7], "    · · ·");
+
+        let hours = heatmap.render_hours();
+        assert_eq!(hours[3].chars().nth(4 + 9 * 2), Some('█'));
+    }
+}
@@ -0,0 +1,230 @@
+//! Parent-child hierarchy of tickets
+//!
+//! A ticket may name a parent ticket in `parent`, which makes the parent an
+//! epic grouping its children. Only the child records the link; children are
+//! found by scanning the tickets. Cycles are rejected when a parent is set.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use super::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+
+/// Sets or clears the parent of `ticket`
+///
+/// `tickets` is used to detect cycles.
+pub fn set_parent(ticket: &mut Ticket, parent: Option<&Ticket>, tickets: &[Ticket]) -> Result<()> {
+    let Some(parent) = parent else {
+        ticket.parent = None;
+        return Ok(());
+    };
+
+    if parent.id == ticket.id {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Ticket '{}' cannot be its own parent",
+            ticket.slug
+        )));
+    }
+    if ancestors(parent, tickets).any(|ancestor| ancestor.id == ticket.id) {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "'{}' is a descendant of '{}'; making it the parent would create a cycle",
+            parent.slug, ticket.slug
+        )));
+    }
+
+    ticket.parent = Some(parent.id.clone());
+    Ok(())
+}
+
+/// Returns the direct children of `ticket`, ordered by slug
+pub fn children<'a>(ticket: &Ticket, tickets: &'a [Ticket]) -> Vec<&'a Ticket> {
+    let mut children: Vec<&Ticket> = tickets
+        .iter()
+        .filter(|t| t.parent.as_ref() == Some(&ticket.id))
+        .collect();
+    children.sort_by(|a, b| a.slug.cmp(&b.slug));
+    children
+}
+
+/// Returns all children of `ticket`, their children and so on
+pub fn descendants<'a>(ticket: &Ticket, tickets: &'a [Ticket]) -> Vec<&'a Ticket> {
+    let mut seen = HashSet::from([&ticket.id]);
+    let mut result = Vec::new();
+    let mut stack = children(ticket, tickets);
+    stack.reverse();
+
+    while let Some(child) = stack.pop() {
+        if !seen.insert(&child.id) {
+            continue;
+        }
+        result.push(child);
+        let mut grandchildren = children(child, tickets);
+        grandchildren.reverse();
+        stack.extend(grandchildren);
+    }
+    result
+}
+
+/// Iterates over the parent of `ticket`, its parent and so on
+///
+/// Parents that no longer exist end the iteration.
+pub fn ancestors<'a>(ticket: &Ticket, tickets: &'a [Ticket]) -> impl Iterator<Item = &'a Ticket> {
+    let mut seen: HashSet<TicketId> = HashSet::from([ticket.id.clone()]);
+    let mut next = ticket.parent.clone();
+    std::iter::from_fn(move || {
+        let id = next.take()?;
+        if !seen.insert(id.clone()) {
+            return None;
+        }
+        let parent = tickets.iter().find(|t| t.id == id)?;
+        next.clone_from(&parent.parent);
+        Some(parent)
+    })
+}
+
+/// Returns the descendants of `ticket` that are not done yet
+pub fn open_descendants<'a>(ticket: &Ticket, tickets: &'a [Ticket]) -> Vec<&'a Ticket> {
+    descendants(ticket, tickets)
+        .into_iter()
+        .filter(|t| t.status != Status::Done)
+        .collect()
+}
+
+/// Completion of an epic, counted over all its descendants
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Rollup {
+    /// Descendants that are done
+    pub done: usize,
+    /// All descendants
+    pub total: usize,
+}
+
+impl Rollup {
+    /// Computes the roll-up progress of `ticket`
+    pub fn of(ticket: &Ticket, tickets: &[Ticket]) -> Self {
+        let descendants = descendants(ticket, tickets);
+        Self {
+            done: descendants
+                .iter()
+                .filter(|t| t.status == Status::Done)
+                .count(),
+            total: descendants.len(),
+        }
+    }
+
+    /// Share of done descendants in percent (0 without descendants)
+    pub fn percent(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+impl std::fmt::Display for Rollup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} done ({}%)", self.done, self.total, self.percent())
+    }
+}
+
+/// Orders `tickets` as a forest, pairing each ticket with its depth
+///
+/// Tickets whose parent is not among `tickets` are roots and keep their
+/// relative order; children follow their parent, ordered by slug.
+pub fn forest(tickets: &[Ticket]) -> Vec<(usize, &Ticket)> {
+    let ids: HashSet<&TicketId> = tickets.iter().map(|t| &t.id).collect();
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+
+    for root in tickets
+        .iter()
+        .filter(|t| t.parent.as_ref().is_none_or(|parent| !ids.contains(parent)))
+    {
+        let mut stack = vec![(0, root)];
+        while let Some((depth, ticket)) = stack.pop() {
+            if !seen.insert(&ticket.id) {
+                continue;
+            }
+            result.push((depth, ticket));
+            stack.extend(
+                children(ticket, tickets)
+                    .into_iter()
+                    .rev()
+                    .map(|child| (depth + 1, child)),
+            );
+        }
+    }
+
+    // Tickets caught in a cycle have no root; list them at the top level
+    for ticket in tickets {
+        if seen.insert(&ticket.id) {
+            result.push((0, ticket));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_parent_rejects_cycles() {
+        let epic = Ticket::new("epic", "Epic");
+        let mut story = Ticket::new("story", "Story");
+        let mut task = Ticket::new("task", "Task");
+
+        set_parent(&mut story, Some(&epic), &[]).unwrap();
+        let tickets = vec![epic.clone(), story.clone()];
+        set_parent(&mut task, Some(&story), &tickets).unwrap();
+        assert_eq!(task.parent, Some(story.id.clone()));
+
+        let tickets = vec![epic.clone(), story.clone(), task.clone()];
+        let mut epic_copy = epic.clone();
+        assert!(set_parent(&mut epic_copy, Some(&task), &tickets).is_err());
+        assert!(set_parent(&mut epic_copy, Some(&epic), &tickets).is_err());
+        let slugs: Vec<_> = ancestors(&task, &tickets)
+            .map(|t| t.slug.as_str())
+            .collect();
+        assert_eq!(slugs, vec!["story", "epic"]);
+
+        set_parent(&mut task, None, &tickets).unwrap();
+        assert_eq!(task.parent, None);
+    }
+
+    #[test]
+    fn test_rollup_and_forest() {
+        let epic = Ticket::new("epic", "Epic");
+        let mut b = Ticket::new("b-story", "B");
+        let mut a = Ticket::new("a-story", "A");
+        let mut task = Ticket::new("task", "Task");
+        let other = Ticket::new("other", "Other");
+        b.parent = Some(epic.id.clone());
+        a.parent = Some(epic.id.clone());
+        a.status = Status::Done;
+        task.parent = Some(b.id.clone());
+
+        let tickets = vec![task.clone(), epic.clone(), other, b.clone(), a];
+        let rollup = Rollup::of(&epic, &tickets);
+        assert_eq!((rollup.done, rollup.total), (1, 3));
+        assert_eq!(rollup.to_string(), "1/3 done (33%)");
+        let open: Vec<_> = open_descendants(&epic, &tickets)
+            .iter()
+            .map(|t| t.slug.as_str())
+            .collect();
+        assert_eq!(open, vec!["b-story", "task"]);
+
+        let forest: Vec<_> = forest(&tickets)
+            .into_iter()
+            .map(|(depth, t)| (depth, t.slug.as_str()))
+            .collect();
+        assert_eq!(
+            forest,
+            vec![
+                (0, "epic"),
+                (1, "a-story"),
+                (1, "b-story"),
+                (2, "task"),
+                (0, "other"),
+            ]
+        );
+    }
+}
@@ -74,6 +74,55 @@ define_id_type! {
     TicketId
 }
 
+/// Shortest length of the short ID of a ticket
+pub const MIN_SHORT_ID_LENGTH: usize = 6;
+
+/// Longest length of the short ID of a ticket
+pub const MAX_SHORT_ID_LENGTH: usize = 8;
+
+/// Characters of short IDs: Crockford's base32 in lowercase, which leaves
+/// out the easily confused `i`, `l`, `o` and `u`
+const SHORT_ID_ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+impl TicketId {
+    /// Generates a short ID for the ticket that `is_taken` does not reject
+    ///
+    /// Candidates are derived from a hash of the UUID. A candidate of
+    /// `length` characters (clamped to 6–8) that is taken is lengthened up to
+    /// 8 characters, after which the UUID is hashed again with a counter.
+    pub fn generate_short_id(&self, length: usize, is_taken: impl Fn(&str) -> bool) -> String {
+        let length = length.clamp(MIN_SHORT_ID_LENGTH, MAX_SHORT_ID_LENGTH);
+        let mut attempt: u32 = 0;
+        loop {
+            let hash = short_id_hash(&self.0, attempt);
+            if let Some(short_id) = (length..=MAX_SHORT_ID_LENGTH)
+                .map(|len| encode_short_id(hash, len))
+                .find(|candidate| !is_taken(candidate))
+            {
+                return short_id;
+            }
+            attempt += 1;
+        }
+    }
+}
+
+/// FNV-1a hash of a UUID and an attempt counter
+fn short_id_hash(uuid: &Uuid, attempt: u32) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in uuid.as_bytes().iter().chain(&attempt.to_le_bytes()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Encodes the leading bits of `hash` as `length` base32 characters
+fn encode_short_id(hash: u64, length: usize) -> String {
+    (0..length)
+        .map(|i| char::from(SHORT_ID_ALPHABET[((hash >> (59 - 5 * i)) & 31) as usize]))
+        .collect()
+}
+
 define_id_type! {
     /// Unique identifier for a task
     ///
@@ -111,6 +160,26 @@ mod tests {
             assert!(id.to_string().starts_with(&short));
         }
 
+        #[test]
+        fn test_generate_short_id() {
+            let id = TicketId::new();
+            let short_id = id.generate_short_id(6, |_| false);
+            assert_eq!(short_id.len(), 6);
+            assert!(short_id.bytes().all(|b| SHORT_ID_ALPHABET.contains(&b)));
+            // Stable for the same ticket
+            assert_eq!(id.generate_short_id(6, |_| false), short_id);
+
+            // Collisions lengthen the ID, then rehash it
+            let longer = id.generate_short_id(6, |candidate| candidate == short_id);
+            assert_eq!(longer.len(), 7);
+            assert!(longer.starts_with(&short_id));
+            let rehashed = id.generate_short_id(6, |candidate| candidate.starts_with(&short_id));
+            assert_eq!(rehashed.len(), 6);
+            assert_ne!(rehashed, short_id);
+
+            assert_eq!(id.generate_short_id(12, |_| false).len(), 8);
+        }
+
         #[test]
         fn test_from_uuid() {
             let uuid = Uuid::new_v4();
@@ -0,0 +1,59 @@
+//! Quick-capture inbox
+//!
+//! `vibe-ticket in "idea"` records an [`InboxEntry`] without asking for a
+//! slug or anything else. `in process` later turns each entry into a ticket
+//! or discards it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A thought captured into the inbox
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InboxEntry {
+    /// Text as captured
+    pub text: String,
+
+    /// Timestamp when the entry was captured
+    pub captured_at: DateTime<Utc>,
+}
+
+impl InboxEntry {
+    /// Captures `text`, trimmed of surrounding whitespace
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.trim().to_string(),
+            captured_at: Utc::now(),
+        }
+    }
+
+    /// Title of a ticket made from the entry: its first line
+    pub fn title(&self) -> &str {
+        self.text.lines().next().unwrap_or_default().trim()
+    }
+
+    /// Description of a ticket made from the entry: the lines after the
+    /// first, if any
+    pub fn description(&self) -> Option<&str> {
+        self.text
+            .split_once('\n')
+            .map(|(_, rest)| rest.trim())
+            .filter(|rest| !rest.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_and_description() {
+        let entry = InboxEntry::new("  random idea about caching  ");
+        assert_eq!(entry.text, "random idea about caching");
+        assert_eq!(entry.title(), "random idea about caching");
+        assert_eq!(entry.description(), None);
+
+        let entry = InboxEntry::new("Cache misses\n\nSeen on the list page");
+        assert_eq!(entry.title(), "Cache misses");
+        assert_eq!(entry.description(), Some("Seen on the list page"));
+    }
+}
@@ -0,0 +1,422 @@
+//! Incident timelines and postmortems
+//!
+//! Incident tickets, created from the `incident` template or carrying a
+//! severity, keep an [`IncidentTimeline`]: timestamped entries describing
+//! what happened, some of which mark the incident as detected, mitigated or
+//! resolved. The timeline gives the time to mitigate and to resolve each
+//! incident, which `stats` averages into MTTR, and pre-fills the postmortem
+//! rendered by [`postmortem`].
+
+use std::fmt::{self, Write as FmtWrite};
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+
+/// Tag of incident tickets
+pub const INCIDENT_TAG: &str = "incident";
+
+/// Stage of an incident reached by a timeline entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// The incident was noticed
+    Detected,
+    /// The impact was stopped, for example by a rollback
+    Mitigated,
+    /// The underlying problem was fixed
+    Resolved,
+}
+
+impl Phase {
+    /// Names accepted by [`FromStr`]
+    pub const NAMES: [&str; 3] = ["detected", "mitigated", "resolved"];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Detected => "detected",
+            Self::Mitigated => "mitigated",
+            Self::Resolved => "resolved",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Phase {
+    type Err = VibeTicketError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "detected" | "detection" => Ok(Self::Detected),
+            "mitigated" | "mitigation" => Ok(Self::Mitigated),
+            "resolved" | "resolution" => Ok(Self::Resolved),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Invalid incident phase: '{value}'. Must be one of: {}",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// An event in the timeline of an incident
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimelineEntry {
+    /// When the event happened
+    pub at: DateTime<Utc>,
+
+    /// What happened
+    pub message: String,
+
+    /// Stage of the incident the event marks, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<Phase>,
+
+    /// Who recorded the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+/// Timeline of an incident ticket
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IncidentTimeline {
+    /// When the incident was detected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_at: Option<DateTime<Utc>>,
+
+    /// When the impact was mitigated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mitigated_at: Option<DateTime<Utc>>,
+
+    /// When the incident was resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+
+    /// Entries in chronological order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<TimelineEntry>,
+}
+
+impl IncidentTimeline {
+    /// Adds an entry in chronological order
+    ///
+    /// An entry marking a phase sets the time of the phase, replacing the
+    /// time set by an earlier entry.
+    pub fn add(&mut self, entry: TimelineEntry) {
+        match entry.phase {
+            Some(Phase::Detected) => self.detected_at = Some(entry.at),
+            Some(Phase::Mitigated) => self.mitigated_at = Some(entry.at),
+            Some(Phase::Resolved) => self.resolved_at = Some(entry.at),
+            None => {},
+        }
+        let index = self.entries.partition_point(|e| e.at <= entry.at);
+        self.entries.insert(index, entry);
+    }
+}
+
+/// Whether `ticket` is an incident
+pub fn is_incident(ticket: &Ticket) -> bool {
+    ticket.incident.is_some()
+        || ticket.severity.is_some()
+        || ticket.tags.iter().any(|tag| tag == INCIDENT_TAG)
+}
+
+/// When the incident was detected, or else when its ticket was created
+pub fn detected_at(ticket: &Ticket) -> DateTime<Utc> {
+    ticket
+        .incident
+        .as_ref()
+        .and_then(|timeline| timeline.detected_at)
+        .unwrap_or(ticket.created_at)
+}
+
+/// When the incident was resolved, or else when its ticket was closed
+pub fn resolved_at(ticket: &Ticket) -> Option<DateTime<Utc>> {
+    ticket
+        .incident
+        .as_ref()
+        .and_then(|timeline| timeline.resolved_at)
+        .or_else(|| ticket.closed_at.filter(|_| ticket.status == Status::Done))
+}
+
+/// Time from detection to mitigation; resolving also mitigates
+pub fn time_to_mitigate(ticket: &Ticket) -> Option<Duration> {
+    let mitigated_at = ticket
+        .incident
+        .as_ref()
+        .and_then(|timeline| timeline.mitigated_at)
+        .or_else(|| resolved_at(ticket))?;
+    Some(mitigated_at - detected_at(ticket))
+}
+
+/// Time from detection to resolution
+pub fn time_to_resolve(ticket: &Ticket) -> Option<Duration> {
+    Some(resolved_at(ticket)? - detected_at(ticket))
+}
+
+/// Incident metrics of a set of tickets
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IncidentStats {
+    /// Number of incidents
+    pub total: usize,
+
+    /// Number of incidents not resolved yet
+    pub open: usize,
+
+    /// Mean time to mitigate, in minutes
+    pub mttm_minutes: Option<i64>,
+
+    /// Mean time to resolve, in minutes
+    pub mttr_minutes: Option<i64>,
+}
+
+impl IncidentStats {
+    /// Computes the metrics of the incidents among `tickets`
+    pub fn of<'a>(tickets: impl IntoIterator<Item = &'a Ticket>) -> Self {
+        let incidents: Vec<&Ticket> = tickets.into_iter().filter(|t| is_incident(t)).collect();
+        let mean = |durations: Vec<Duration>| {
+            let count = i64::try_from(durations.len()).ok().filter(|&n| n > 0)?;
+            let total: i64 = durations.iter().map(Duration::num_minutes).sum();
+            Some(total / count)
+        };
+        Self {
+            total: incidents.len(),
+            open: incidents
+                .iter()
+                .filter(|t| resolved_at(t).is_none())
+                .count(),
+            mttm_minutes: mean(
+                incidents
+                    .iter()
+                    .filter_map(|t| time_to_mitigate(t))
+                    .collect(),
+            ),
+            mttr_minutes: mean(
+                incidents
+                    .iter()
+                    .filter_map(|t| time_to_resolve(t))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Human-readable duration such as "1h 05m"
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes < 60 {
+        format!("{minutes}m")
+    } else if minutes < 24 * 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}d {}h", minutes / (24 * 60), minutes % (24 * 60) / 60)
+    }
+}
+
+/// Local time of `at`, as shown in postmortems
+fn local_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M %:z")
+        .to_string()
+}
+
+/// Renders a postmortem of the incident `ticket` as markdown
+///
+/// The summary, timeline and effort are pre-filled from the ticket, its
+/// timeline and its work log; open tasks become action items. Sections that
+/// need a human, such as the root cause, are left for the author.
+pub fn postmortem(ticket: &Ticket) -> String {
+    let mut md = String::new();
+    let timeline = ticket.incident.clone().unwrap_or_default();
+
+    let _ = writeln!(md, "# Postmortem: {}\n", ticket.title);
+    let _ = writeln!(md, "- **Ticket**: `{}`", ticket.slug);
+    if let Some(severity) = ticket.severity {
+        let _ = writeln!(
+            md,
+            "- **Severity**: {severity} ({})",
+            severity.description()
+        );
+    }
+    if let Some(assignee) = &ticket.assignee {
+        let _ = writeln!(md, "- **Owner**: {assignee}");
+    }
+    let _ = writeln!(md, "- **Detected**: {}", local_time(detected_at(ticket)));
+    if let Some(mitigated_at) = timeline.mitigated_at {
+        let _ = writeln!(md, "- **Mitigated**: {}", local_time(mitigated_at));
+    }
+    match resolved_at(ticket) {
+        Some(resolved_at) => {
+            let _ = writeln!(md, "- **Resolved**: {}", local_time(resolved_at));
+        },
+        None => md.push_str("- **Resolved**: not yet\n"),
+    }
+    if let Some(duration) = time_to_mitigate(ticket) {
+        let _ = writeln!(md, "- **Time to mitigate**: {}", format_duration(duration));
+    }
+    if let Some(duration) = time_to_resolve(ticket) {
+        let _ = writeln!(md, "- **Time to resolve**: {}", format_duration(duration));
+    }
+
+    md.push_str("\n## Summary\n\n");
+    // A description holding only the headings of the incident template is
+    // unfilled; the headings of a filled one move below the summary's
+    let lines: Vec<&str> = ticket.description.trim().lines().collect();
+    if lines
+        .iter()
+        .all(|line| line.trim().is_empty() || line.starts_with('#'))
+    {
+        md.push_str("_What happened, and what was the impact?_\n");
+    } else {
+        for line in lines {
+            if line.starts_with('#') {
+                md.push('#');
+            }
+            let _ = writeln!(md, "{line}");
+        }
+    }
+
+    md.push_str("\n## Timeline\n\n");
+    if timeline.entries.is_empty() {
+        md.push_str("_No timeline entries recorded._\n");
+    } else {
+        md.push_str("| Time | Event |\n|------|-------|\n");
+        for entry in &timeline.entries {
+            let phase = entry
+                .phase
+                .map_or_else(String::new, |phase| format!("**{phase}** "));
+            let _ = writeln!(
+                md,
+                "| {} | {phase}{} |",
+                local_time(entry.at),
+                entry.message.replace('|', "\\|").replace('\n', " ")
+            );
+        }
+    }
+
+    md.push_str("\n## Effort\n\n");
+    if let Some(started_at) = ticket.started_at {
+        let end = ticket.closed_at.unwrap_or_else(Utc::now);
+        let _ = writeln!(md, "Time spent: {}\n", format_duration(end - started_at));
+    }
+    if ticket.work_log.is_empty() {
+        md.push_str("_No work logged._\n");
+    } else {
+        for entry in &ticket.work_log {
+            let _ = writeln!(md, "- {}: {}", local_time(entry.logged_at), entry.message);
+        }
+    }
+
+    md.push_str("\n## Root Cause\n\n_Why did it happen?_\n");
+    md.push_str("\n## Action Items\n\n");
+    let open_tasks: Vec<_> = ticket.tasks.iter().filter(|task| !task.completed).collect();
+    if open_tasks.is_empty() {
+        md.push_str("- [ ] _Follow-up work_\n");
+    } else {
+        for task in open_tasks {
+            let _ = writeln!(md, "- [ ] {}", task.title);
+        }
+    }
+    md.push_str("\n## Lessons Learned\n\n_What went well, and what could be improved?_\n");
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Severity, Task};
+
+    fn entry(at: DateTime<Utc>, message: &str, phase: Option<Phase>) -> TimelineEntry {
+        TimelineEntry {
+            at,
+            message: message.to_string(),
+            phase,
+            author: None,
+        }
+    }
+
+    fn incident() -> Ticket {
+        let start = Utc::now() - Duration::hours(5);
+        let mut ticket = Ticket::new("api-down", "API down");
+        ticket.severity = Some(Severity::S1);
+        let mut timeline = IncidentTimeline::default();
+        timeline.add(entry(
+            start + Duration::minutes(90),
+            "Root cause fixed",
+            Some(Phase::Resolved),
+        ));
+        timeline.add(entry(start, "Alerts fired", Some(Phase::Detected)));
+        timeline.add(entry(
+            start + Duration::minutes(20),
+            "Rolled back | v2",
+            Some(Phase::Mitigated),
+        ));
+        ticket.incident = Some(timeline);
+        ticket
+    }
+
+    #[test]
+    fn test_timeline_phases() {
+        let ticket = incident();
+        let timeline = ticket.incident.as_ref().unwrap();
+        let messages: Vec<_> = timeline
+            .entries
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            ["Alerts fired", "Rolled back | v2", "Root cause fixed"]
+        );
+        assert_eq!(time_to_mitigate(&ticket), Some(Duration::minutes(20)));
+        assert_eq!(time_to_resolve(&ticket), Some(Duration::minutes(90)));
+        assert!(is_incident(&ticket));
+        assert!(!is_incident(&Ticket::new("typo", "Fix typo")));
+        assert_eq!("Mitigation".parse::<Phase>().unwrap(), Phase::Mitigated);
+        assert!("fixed".parse::<Phase>().is_err());
+    }
+
+    #[test]
+    fn test_incident_stats() {
+        let mut open = Ticket::new("db-slow", "DB slow");
+        open.tags.push(INCIDENT_TAG.to_string());
+        let mut closed = Ticket::new("cdn", "CDN errors");
+        closed.tags.push(INCIDENT_TAG.to_string());
+        closed.close();
+        closed.closed_at = Some(closed.created_at + Duration::minutes(30));
+
+        let tickets = [incident(), open, closed, Ticket::new("typo", "Fix typo")];
+        let stats = IncidentStats::of(&tickets);
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.open, 1);
+        assert_eq!(stats.mttr_minutes, Some(60));
+        assert_eq!(stats.mttm_minutes, Some(25));
+        assert_eq!(IncidentStats::of(&[]).mttr_minutes, None);
+    }
+
+    #[test]
+    fn test_postmortem() {
+        let mut ticket = incident();
+        ticket.log_work("Paged the database team");
+        ticket
+            .tasks
+            .push(Task::new("Add a canary stage".to_string()));
+
+        let md = postmortem(&ticket);
+        assert!(md.starts_with("# Postmortem: API down\n"));
+        assert!(md.contains("## Summary\n\n_What happened"));
+        ticket.description = "## Impact\n\nCheckout failed".to_string();
+        let md = postmortem(&ticket);
+        assert!(md.contains("## Summary\n\n### Impact\n\nCheckout failed\n"));
+        assert!(md.contains("- **Severity**: S1 (Critical outage)"));
+        assert!(md.contains("- **Time to resolve**: 1h 30m"));
+        assert!(md.contains("| **mitigated** Rolled back \\| v2 |"));
+        assert!(md.contains(": Paged the database team"));
+        assert!(md.contains("- [ ] Add a canary stage"));
+        assert_eq!(format_duration(Duration::minutes(26 * 60)), "1d 2h");
+    }
+}
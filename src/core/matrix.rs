@@ -0,0 +1,131 @@
+//! Priority matrix (Eisenhower matrix)
+//!
+//! Open tickets are sorted into four [`Quadrant`]s by urgency and
+//! importance. A ticket is urgent when it is overdue or due within a number
+//! of days, and important when its priority is high or critical.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::Priority;
+
+/// Days before the due date from which a ticket counts as urgent
+pub const DEFAULT_URGENT_DAYS: i64 = 7;
+
+/// Quadrant of the priority matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Quadrant {
+    /// Urgent and important
+    DoFirst,
+    /// Important but not urgent
+    Schedule,
+    /// Urgent but not important
+    Delegate,
+    /// Neither urgent nor important
+    Eliminate,
+}
+
+impl Quadrant {
+    /// All quadrants, row by row: important first, urgent first
+    pub const ALL: [Self; 4] = [
+        Self::DoFirst,
+        Self::Schedule,
+        Self::Delegate,
+        Self::Eliminate,
+    ];
+
+    /// Sorts a ticket into its quadrant
+    pub fn classify(
+        priority: Priority,
+        due: Option<NaiveDate>,
+        today: NaiveDate,
+        urgent_days: i64,
+    ) -> Self {
+        match (is_urgent(due, today, urgent_days), is_important(priority)) {
+            (true, true) => Self::DoFirst,
+            (false, true) => Self::Schedule,
+            (true, false) => Self::Delegate,
+            (false, false) => Self::Eliminate,
+        }
+    }
+
+    /// Whether the tickets of this quadrant are urgent
+    pub const fn is_urgent(self) -> bool {
+        matches!(self, Self::DoFirst | Self::Delegate)
+    }
+
+    /// Whether the tickets of this quadrant are important
+    pub const fn is_important(self) -> bool {
+        matches!(self, Self::DoFirst | Self::Schedule)
+    }
+
+    /// Human-readable name of the quadrant
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::DoFirst => "Do first",
+            Self::Schedule => "Schedule",
+            Self::Delegate => "Delegate",
+            Self::Eliminate => "Eliminate",
+        }
+    }
+}
+
+/// Returns true if a ticket due on `due` is urgent on `today`
+///
+/// Tickets without a due date are never urgent.
+pub fn is_urgent(due: Option<NaiveDate>, today: NaiveDate, urgent_days: i64) -> bool {
+    due.is_some_and(|due| (due - today).num_days() <= urgent_days)
+}
+
+/// Returns true if a ticket with `priority` is important
+pub const fn is_important(priority: Priority) -> bool {
+    matches!(priority, Priority::High | Priority::Critical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_classify() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 20).unwrap();
+        let soon = Some(today + Duration::days(3));
+        let later = Some(today + Duration::days(30));
+        let overdue = Some(today - Duration::days(1));
+
+        assert_eq!(
+            Quadrant::classify(Priority::Critical, soon, today, 7),
+            Quadrant::DoFirst
+        );
+        assert_eq!(
+            Quadrant::classify(Priority::High, later, today, 7),
+            Quadrant::Schedule
+        );
+        assert_eq!(
+            Quadrant::classify(Priority::Low, overdue, today, 7),
+            Quadrant::Delegate
+        );
+        assert_eq!(
+            Quadrant::classify(Priority::Medium, None, today, 7),
+            Quadrant::Eliminate
+        );
+        assert_eq!(
+            Quadrant::classify(Priority::High, later, today, 30),
+            Quadrant::DoFirst
+        );
+        assert!(Quadrant::ALL.iter().all(|q| {
+            *q == Quadrant::classify(
+                if q.is_important() {
+                    Priority::High
+                } else {
+                    Priority::Low
+                },
+                q.is_urgent().then_some(today),
+                today,
+                0,
+            )
+        }));
+    }
+}
@@ -0,0 +1,237 @@
+//! Milestones grouping tickets
+//!
+//! A [`Milestone`] is a named goal, such as a release or a sprint, with an
+//! optional due date. Tickets join one through their `milestone` field;
+//! [`MilestoneProgress`] summarizes how many of them are done and how the
+//! number of open tickets went down over time.
+
+use std::fmt::Write as FmtWrite;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Status, Ticket};
+
+/// Most points in a burndown series
+const BURNDOWN_POINTS: i64 = 8;
+
+/// A named goal grouping tickets, such as a release or a sprint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Milestone {
+    /// Unique name, referenced by the tickets' `milestone` field
+    pub name: String,
+
+    /// Date the milestone is due
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+
+    /// What the milestone is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Timestamp when the milestone was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl Milestone {
+    /// Creates a milestone without due date or description
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            due: None,
+            description: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Number of open tickets of a milestone at the end of a day
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct BurndownPoint {
+    /// Day of the sample
+    pub date: NaiveDate,
+    /// Tickets created by then and not yet closed
+    pub remaining: usize,
+}
+
+/// Completion of a milestone
+#[derive(Debug, Clone, Serialize)]
+pub struct MilestoneProgress {
+    /// Name of the milestone
+    pub name: String,
+    /// Date the milestone is due
+    pub due: Option<NaiveDate>,
+    /// Tickets in the milestone
+    pub total: usize,
+    /// Done tickets in the milestone
+    pub done: usize,
+    /// Days until the due date, negative once overdue
+    pub days_left: Option<i64>,
+    /// Whether the share of done tickets keeps up with the time elapsed
+    /// towards the due date; unknown without a due date or tickets
+    pub on_track: Option<bool>,
+    /// Open tickets over time, from the start of the milestone to today
+    pub burndown: Vec<BurndownPoint>,
+}
+
+impl MilestoneProgress {
+    /// Computes the progress of `milestone` on `today`
+    ///
+    /// Tickets of other milestones are ignored. The milestone starts at its
+    /// creation or at the creation of its oldest ticket, whichever is
+    /// earlier. Tickets count towards the burndown from the day they were
+    /// created, as the day they joined the milestone is not recorded.
+    pub fn compute(milestone: &Milestone, tickets: &[Ticket], today: NaiveDate) -> Self {
+        let local = |at: DateTime<Utc>| at.with_timezone(&Local).date_naive();
+        let tickets: Vec<&Ticket> = tickets
+            .iter()
+            .filter(|t| t.milestone.as_deref() == Some(milestone.name.as_str()))
+            .collect();
+        let done = tickets.iter().filter(|t| t.status == Status::Done).count();
+
+        let start = tickets
+            .iter()
+            .map(|t| local(t.created_at))
+            .chain(std::iter::once(local(milestone.created_at)))
+            .min()
+            .unwrap_or(today)
+            .min(today);
+        let span = (today - start).num_days();
+        let step = (span / (BURNDOWN_POINTS - 1)).max(1);
+        let dates = (0..BURNDOWN_POINTS)
+            .map(|i| start + chrono::Duration::days(i * step))
+            .take_while(|date| *date < today)
+            .chain(std::iter::once(today));
+
+        let burndown = dates
+            .map(|date| BurndownPoint {
+                date,
+                remaining: tickets
+                    .iter()
+                    .filter(|t| local(t.created_at) <= date)
+                    .filter(|t| {
+                        t.status != Status::Done || t.closed_at.is_some_and(|at| local(at) > date)
+                    })
+                    .count(),
+            })
+            .collect();
+
+        let on_track = milestone.due.filter(|_| !tickets.is_empty()).map(|due| {
+            let planned = (due - start).num_days();
+            if today >= due || planned <= 0 {
+                return done == tickets.len();
+            }
+            // done / total >= elapsed / planned, without rounding
+            let elapsed = (today - start).num_days();
+            i64::try_from(done).unwrap_or(i64::MAX) * planned
+                >= elapsed * i64::try_from(tickets.len()).unwrap_or(i64::MAX)
+        });
+
+        Self {
+            name: milestone.name.clone(),
+            due: milestone.due,
+            total: tickets.len(),
+            done,
+            days_left: milestone.due.map(|due| (due - today).num_days()),
+            on_track,
+            burndown,
+        }
+    }
+
+    /// Share of done tickets in percent (0 without tickets)
+    pub fn percent(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+
+    /// One-line summary, e.g. `3/4 done (75%), due 2025-08-01 (5 days left), on track`
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{}/{} done ({}%)", self.done, self.total, self.percent());
+        if let (Some(due), Some(days_left)) = (self.due, self.days_left) {
+            let left = match days_left {
+                0 => "due today".to_string(),
+                d if d < 0 => format!("{} days overdue", -d),
+                d => format!("{d} days left"),
+            };
+            write!(summary, ", due {due} ({left})").unwrap();
+        }
+        match self.on_track {
+            Some(true) => summary.push_str(", on track"),
+            Some(false) => summary.push_str(", behind"),
+            None => {},
+        }
+        summary
+    }
+
+    /// Remaining tickets of the burndown, e.g. `4 → 3 → 1`
+    pub fn burndown_line(&self) -> String {
+        self.burndown
+            .iter()
+            .map(|point| point.remaining.to_string())
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn at(date: NaiveDate) -> DateTime<Utc> {
+        Local
+            .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_progress_and_burndown() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 20).unwrap();
+        let start = today - Duration::days(10);
+        let mut milestone = Milestone::new("v1.0");
+        milestone.created_at = at(start);
+        milestone.due = Some(today + Duration::days(10));
+
+        let mut tickets = Vec::new();
+        for (i, closed) in [Some(2), Some(8), None, None].into_iter().enumerate() {
+            let mut ticket = Ticket::new(format!("t{i}"), "Ticket");
+            ticket.milestone = Some("v1.0".to_string());
+            ticket.created_at = at(start);
+            if let Some(days) = closed {
+                ticket.status = Status::Done;
+                ticket.closed_at = Some(at(start + Duration::days(days)));
+            }
+            tickets.push(ticket);
+        }
+        tickets.push(Ticket::new("other", "Other milestone"));
+
+        let progress = MilestoneProgress::compute(&milestone, &tickets, today);
+        assert_eq!(
+            (progress.total, progress.done, progress.percent()),
+            (4, 2, 50)
+        );
+        assert_eq!(progress.days_left, Some(10));
+        // Half done at half time
+        assert_eq!(progress.on_track, Some(true));
+        assert_eq!(progress.burndown.first().unwrap().remaining, 4);
+        assert_eq!(progress.burndown.last().unwrap().date, today);
+        assert_eq!(progress.burndown.last().unwrap().remaining, 2);
+        assert_eq!(
+            progress.summary(),
+            "2/4 done (50%), due 2025-07-30 (10 days left), on track"
+        );
+
+        milestone.due = Some(today + Duration::days(2));
+        let progress = MilestoneProgress::compute(&milestone, &tickets, today);
+        assert_eq!(progress.on_track, Some(false));
+    }
+
+    #[test]
+    fn test_empty_milestone() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 20).unwrap();
+        let progress = MilestoneProgress::compute(&Milestone::new("next"), &[], today);
+        assert_eq!((progress.total, progress.percent()), (0, 0));
+        assert_eq!(progress.on_track, None);
+        assert_eq!(progress.burndown_line(), "0");
+    }
+}
@@ -28,14 +28,49 @@
 //! - All I/O operations should be abstracted through traits
 //! - Business rules should be enforced at this layer
 
+pub mod aging;
+pub mod assignment;
+pub mod audit;
+pub mod branch;
+mod builder;
+pub mod closing;
+mod comment;
+pub mod context_pack;
+pub mod dependencies;
+pub mod fields;
+pub mod heatmap;
+pub mod hierarchy;
 mod id;
+mod inbox;
+pub mod incident;
+pub mod matrix;
+mod milestone;
+pub mod plan;
 mod priority;
+pub mod query;
+pub mod relations;
+mod report;
+pub mod sections;
+mod severity;
 mod status;
 mod task;
+mod template;
 mod ticket;
+mod watch;
+mod work_log;
 
-pub use id::{TaskId, TicketId};
+pub use builder::TicketBuilder;
+pub use comment::Comment;
+pub use id::{MAX_SHORT_ID_LENGTH, MIN_SHORT_ID_LENGTH, TaskId, TicketId};
+pub use inbox::InboxEntry;
+pub use milestone::{BurndownPoint, Milestone, MilestoneProgress};
 pub use priority::Priority;
+pub use query::Query;
+pub use report::{Report, ReportEntry, ReportGroup, ReportGroupBy, ReportStats, ReportTemplate};
+pub use severity::Severity;
 pub use status::Status;
 pub use task::Task;
+pub use template::TicketTemplate;
 pub use ticket::Ticket;
+pub use watch::{FieldDelta, WATCHED_FIELDS, WatchNotification};
+pub use work_log::WorkLogEntry;
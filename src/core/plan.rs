@@ -0,0 +1,262 @@
+//! Markdown plans
+//!
+//! A plan is a markdown document, such as a PRD, broken down into work items
+//! (`import --format markdown-plan`):
+//!
+//! - the first level 1 heading is the title of the plan, and the text before
+//!   the next heading its description.
+//! - a heading with checkboxes, or without subheadings, is an item. Its text
+//!   is the item's description, its checkboxes (at any depth) its tasks, and
+//!   the headings it is nested under become its tags.
+//! - headings with subheadings and no checkboxes only group items.
+//! - top-level checkboxes before the first section heading are items of
+//!   their own, with the checkboxes nested under them as tasks.
+//!
+//! Headings inside fenced code blocks are ignored.
+
+use crate::cli::slugify;
+
+/// A checkbox of a plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanTask {
+    /// Text of the checkbox
+    pub title: String,
+    /// Whether the checkbox is checked
+    pub done: bool,
+}
+
+/// A work item of a plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanItem {
+    /// Heading or checkbox text
+    pub title: String,
+    /// Text under the heading, without the checkboxes
+    pub description: String,
+    /// Slugs of the headings the item is nested under, outermost first
+    pub tags: Vec<String>,
+    /// Checkboxes of the item
+    pub tasks: Vec<PlanTask>,
+}
+
+/// A parsed markdown plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    /// Text of the level 1 heading
+    pub title: Option<String>,
+    /// Text between the title and the first section
+    pub description: String,
+    /// Work items in document order
+    pub items: Vec<PlanItem>,
+}
+
+/// A heading and the lines up to the next one
+struct Section<'a> {
+    title: String,
+    tags: Vec<String>,
+    lines: Vec<&'a str>,
+    has_subsections: bool,
+}
+
+/// Parses a markdown plan
+pub fn parse(markdown: &str) -> Plan {
+    let mut plan = Plan::default();
+    let mut preamble = Vec::new();
+    let mut sections: Vec<Section<'_>> = Vec::new();
+    // Levels and indexes of the headings enclosing the current line
+    let mut enclosing: Vec<(usize, usize)> = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        match heading(line).filter(|_| !in_fence) {
+            Some((1, title)) if plan.title.is_none() && sections.is_empty() => {
+                plan.title = Some(title.to_string());
+            },
+            Some((level, title)) => {
+                while enclosing.last().is_some_and(|&(outer, _)| outer >= level) {
+                    enclosing.pop();
+                }
+                if let Some(&(_, parent)) = enclosing.last() {
+                    sections[parent].has_subsections = true;
+                }
+                let tags = enclosing
+                    .iter()
+                    .map(|&(_, index)| slugify(&sections[index].title))
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+                enclosing.push((level, sections.len()));
+                sections.push(Section {
+                    title: title.to_string(),
+                    tags,
+                    lines: Vec::new(),
+                    has_subsections: false,
+                });
+            },
+            None => match sections.last_mut() {
+                Some(section) => section.lines.push(line),
+                None => preamble.push(line),
+            },
+        }
+    }
+
+    let (description, preamble_items) = parse_preamble(&preamble);
+    plan.description = description;
+    plan.items = preamble_items;
+    for section in sections {
+        let (description, tasks) = split_checkboxes(&section.lines);
+        if tasks.is_empty() && section.has_subsections {
+            continue;
+        }
+        plan.items.push(PlanItem {
+            title: section.title,
+            description,
+            tags: section.tags,
+            tasks,
+        });
+    }
+    plan
+}
+
+/// Splits the lines before the first section into the plan's description
+/// and the items of its top-level checkboxes
+fn parse_preamble(lines: &[&str]) -> (String, Vec<PlanItem>) {
+    let top = lines
+        .iter()
+        .filter(|line| checkbox(line).is_some())
+        .map(|line| indent(line))
+        .min()
+        .unwrap_or_default();
+    let mut text = Vec::new();
+    let mut items: Vec<PlanItem> = Vec::new();
+    for line in lines {
+        match (checkbox(line), items.last_mut()) {
+            (Some(task), Some(item)) if indent(line) > top => item.tasks.push(task),
+            (Some(task), _) => items.push(PlanItem {
+                title: task.title,
+                ..PlanItem::default()
+            }),
+            (None, _) => text.push(*line),
+        }
+    }
+    (text.join("\n").trim().to_string(), items)
+}
+
+/// Splits the lines of a section into its text and its checkboxes
+fn split_checkboxes(lines: &[&str]) -> (String, Vec<PlanTask>) {
+    let (tasks, text): (Vec<&str>, Vec<&str>) =
+        lines.iter().partition(|line| checkbox(line).is_some());
+    (
+        text.join("\n").trim().to_string(),
+        tasks.into_iter().filter_map(checkbox).collect(),
+    )
+}
+
+/// Level and text of a markdown heading
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let title = line[level..]
+        .strip_prefix(' ')?
+        .trim()
+        .trim_end_matches('#');
+    ((1..=6).contains(&level) && !title.trim().is_empty()).then_some((level, title.trim()))
+}
+
+/// The checkbox of a `- [ ] text` or `* [x] text` line
+fn checkbox(line: &str) -> Option<PlanTask> {
+    let rest = line.trim_start();
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))?;
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let title = rest[3..].trim();
+    (!title.is_empty()).then(|| PlanTask {
+        title: title.to_string(),
+        done,
+    })
+}
+
+/// Indentation of a line
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "# Checkout Revamp
+
+Rework the checkout flow.
+
+## Payments
+
+### Card payments
+Accept Visa and Mastercard.
+
+- [ ] Integrate the gateway
+  - [x] Pick a provider
+- [ ] Handle 3-D Secure
+
+### Refunds
+
+## Launch
+- [ ] Announce
+
+```md
+## Not a heading
+```
+";
+
+    fn task(title: &str, done: bool) -> PlanTask {
+        PlanTask {
+            title: title.to_string(),
+            done,
+        }
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let plan = parse(PLAN);
+        assert_eq!(plan.title.as_deref(), Some("Checkout Revamp"));
+        assert_eq!(plan.description, "Rework the checkout flow.");
+
+        let titles: Vec<_> = plan.items.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, vec!["Card payments", "Refunds", "Launch"]);
+
+        let card = &plan.items[0];
+        assert_eq!(card.description, "Accept Visa and Mastercard.");
+        assert_eq!(card.tags, vec!["payments"]);
+        assert_eq!(
+            card.tasks,
+            vec![
+                task("Integrate the gateway", false),
+                task("Pick a provider", true),
+                task("Handle 3-D Secure", false),
+            ]
+        );
+        assert!(plan.items[1].tasks.is_empty());
+        assert!(plan.items[2].tags.is_empty());
+        assert!(plan.items[2].description.contains("## Not a heading"));
+    }
+
+    #[test]
+    fn test_parse_checklist() {
+        let plan = parse("Intro\n\n- [ ] Login\n  - [ ] Form\n  - [x] API\n- [x] Logout\n");
+        assert_eq!(plan.title, None);
+        assert_eq!(plan.description, "Intro");
+        assert_eq!(plan.items.len(), 2);
+        assert_eq!(plan.items[0].title, "Login");
+        assert_eq!(
+            plan.items[0].tasks,
+            vec![task("Form", false), task("API", true)]
+        );
+        assert_eq!(plan.items[1].title, "Logout");
+        assert!(plan.items[1].tasks.is_empty());
+    }
+}
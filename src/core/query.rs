@@ -0,0 +1,552 @@
+//! Query language for filtering tickets
+//!
+//! A query combines terms with `AND`, `OR` and `NOT` (or a leading `-`),
+//! grouped with parentheses, for example
+//! `status:doing AND (tag:backend OR priority:high) created:>2025-01-01`.
+//! Terms next to each other must all match, as if joined by `AND`; `AND`
+//! binds tighter than `OR`. Terms are:
+//!
+//! - `status:`, `priority:` and `severity:` followed by a value
+//! - `assignee:` followed by a user, `me` (the current user) or `none`
+//! - `tag:` followed by a tag
+//! - `milestone:` followed by a milestone name or `none`
+//! - `is:open` (any status but done) or `is:closed`
+//! - `created:`, `started:`, `closed:` and `due:` followed by a date
+//!   (`YYYY-MM-DD`), optionally prefixed by `>`, `>=`, `<` or `<=`
+//! - any other word, matched case-insensitively against the title,
+//!   description and tags
+//!
+//! Quoted text is taken literally: `assignee:"Jane Doe"`, `"AND"`. Queries
+//! are parsed into an [`Expr`] tree by [`Query::from_str`] and evaluated
+//! with [`Query::matches`]. They are used by `list --where`, `list
+//! --filter`, `search --query` and the MCP list and search tools; named
+//! queries are stored under `filters` in the project configuration.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use super::{Priority, Severity, Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+
+/// Parsed query expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    expr: Expr,
+    current_user: Option<String>,
+    source: String,
+}
+
+/// Syntax tree of a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A single condition
+    Term(Term),
+    /// Matches if the inner expression does not
+    Not(Box<Self>),
+    /// Matches if every expression matches (an empty list matches all)
+    And(Vec<Self>),
+    /// Matches if any expression matches
+    Or(Vec<Self>),
+}
+
+/// Condition on a single ticket field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    /// `status:<status>`
+    Status(Status),
+    /// `priority:<priority>`
+    Priority(Priority),
+    /// `severity:<severity>`
+    Severity(Severity),
+    /// `assignee:<user|me|none>`
+    Assignee(Assignee),
+    /// `tag:<tag>`, compared case-insensitively
+    Tag(String),
+    /// `milestone:<name|none>`; `None` matches tickets without milestone
+    Milestone(Option<String>),
+    /// `is:open` (true) or `is:closed` (false)
+    Open(bool),
+    /// Comparison of a date field, e.g. `created:>2025-01-01`
+    Date(DateField, Comparison, NaiveDate),
+    /// Free text searched in the title, description and tags
+    Text(String),
+}
+
+/// Value of an `assignee:` term
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assignee {
+    /// A named user
+    User(String),
+    /// The current user
+    Me,
+    /// No assignee
+    Nobody,
+}
+
+/// Date field of a ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    /// Day the ticket was created
+    Created,
+    /// Day work on the ticket started
+    Started,
+    /// Day the ticket was closed
+    Closed,
+    /// Day the ticket is due
+    Due,
+}
+
+/// Comparison of a date term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `>`
+    After,
+    /// `>=`
+    OnOrAfter,
+    /// `<`
+    Before,
+    /// `<=`
+    OnOrBefore,
+    /// No operator: the same day
+    On,
+}
+
+impl Query {
+    /// Sets the user `assignee:me` refers to
+    ///
+    /// Without a current user, `assignee:me` matches no ticket.
+    #[must_use]
+    pub fn with_current_user(mut self, user: impl Into<String>) -> Self {
+        self.current_user = Some(user.into());
+        self
+    }
+
+    /// Returns true if the query refers to the current user
+    pub fn uses_current_user(&self) -> bool {
+        self.expr
+            .any_term(&|term| matches!(term, Term::Assignee(Assignee::Me)))
+    }
+
+    /// Syntax tree of the query
+    pub const fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// Returns true if the ticket satisfies the query
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        self.expr.matches(ticket, self.current_user.as_deref())
+    }
+}
+
+impl Expr {
+    /// Evaluates the expression, with `me` as the current user
+    pub fn matches(&self, ticket: &Ticket, me: Option<&str>) -> bool {
+        match self {
+            Self::Term(term) => term.matches(ticket, me),
+            Self::Not(expr) => !expr.matches(ticket, me),
+            Self::And(exprs) => exprs.iter().all(|expr| expr.matches(ticket, me)),
+            Self::Or(exprs) => exprs.iter().any(|expr| expr.matches(ticket, me)),
+        }
+    }
+
+    /// Returns true if any term of the expression satisfies `predicate`
+    fn any_term(&self, predicate: &impl Fn(&Term) -> bool) -> bool {
+        match self {
+            Self::Term(term) => predicate(term),
+            Self::Not(expr) => expr.any_term(predicate),
+            Self::And(exprs) | Self::Or(exprs) => exprs.iter().any(|e| e.any_term(predicate)),
+        }
+    }
+}
+
+impl Term {
+    /// Evaluates the term, with `me` as the current user
+    pub fn matches(&self, ticket: &Ticket, me: Option<&str>) -> bool {
+        match self {
+            Self::Status(status) => ticket.status == *status,
+            Self::Priority(priority) => ticket.priority == *priority,
+            Self::Severity(severity) => ticket.severity == Some(*severity),
+            Self::Assignee(Assignee::User(user)) => ticket.assignee.as_ref() == Some(user),
+            Self::Assignee(Assignee::Me) => me.is_some() && ticket.assignee.as_deref() == me,
+            Self::Assignee(Assignee::Nobody) => ticket.assignee.is_none(),
+            Self::Tag(tag) => ticket.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            Self::Milestone(milestone) => ticket.milestone == *milestone,
+            Self::Open(open) => (ticket.status != Status::Done) == *open,
+            Self::Date(field, comparison, date) => field
+                .value(ticket)
+                .is_some_and(|value| comparison.holds(value, *date)),
+            Self::Text(text) => {
+                let text = text.to_lowercase();
+                ticket.title.to_lowercase().contains(&text)
+                    || ticket.description.to_lowercase().contains(&text)
+                    || ticket.tags.iter().any(|t| t.to_lowercase().contains(&text))
+            },
+        }
+    }
+
+    fn parse(token: &str) -> std::result::Result<Self, String> {
+        let Some((key, value)) = token.split_once(':') else {
+            return Ok(Self::Text(token.to_string()));
+        };
+        if value.is_empty() {
+            return Err(format!("missing value for '{key}'"));
+        }
+
+        match key.to_lowercase().as_str() {
+            "status" => Status::try_from(value).map(Self::Status),
+            "priority" => Priority::try_from(value).map(Self::Priority),
+            "severity" => Severity::try_from(value).map(Self::Severity),
+            "assignee" => Ok(Self::Assignee(match value {
+                "me" => Assignee::Me,
+                "none" => Assignee::Nobody,
+                user => Assignee::User(user.to_string()),
+            })),
+            "tag" => Ok(Self::Tag(value.to_string())),
+            "milestone" => Ok(Self::Milestone(
+                (value != "none").then(|| value.to_string()),
+            )),
+            "is" => match value.to_lowercase().as_str() {
+                "open" => Ok(Self::Open(true)),
+                "closed" => Ok(Self::Open(false)),
+                _ => Err(format!(
+                    "unknown state 'is:{value}'. Use is:open or is:closed"
+                )),
+            },
+            "created" => Self::parse_date(DateField::Created, value),
+            "started" => Self::parse_date(DateField::Started, value),
+            "closed" => Self::parse_date(DateField::Closed, value),
+            "due" => Self::parse_date(DateField::Due, value),
+            _ => Err(format!(
+                "unknown field '{key}'. Use status, priority, severity, assignee, tag, milestone, is, \
+                 created, started, closed or due"
+            )),
+        }
+    }
+
+    fn parse_date(field: DateField, value: &str) -> std::result::Result<Self, String> {
+        let (comparison, date) = [
+            (">=", Comparison::OnOrAfter),
+            ("<=", Comparison::OnOrBefore),
+            (">", Comparison::After),
+            ("<", Comparison::Before),
+        ]
+        .into_iter()
+        .find_map(|(op, comparison)| value.strip_prefix(op).map(|date| (comparison, date)))
+        .unwrap_or((Comparison::On, value));
+
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|date| Self::Date(field, comparison, date))
+            .map_err(|_| format!("invalid date '{date}'. Use YYYY-MM-DD"))
+    }
+}
+
+impl DateField {
+    /// Local date of the field, if set
+    fn value(self, ticket: &Ticket) -> Option<NaiveDate> {
+        let local = |at: DateTime<Utc>| at.with_timezone(&Local).date_naive();
+        match self {
+            Self::Created => Some(local(ticket.created_at)),
+            Self::Started => ticket.started_at.map(local),
+            Self::Closed => ticket.closed_at.map(local),
+            Self::Due => ticket.due,
+        }
+    }
+}
+
+impl Comparison {
+    /// Returns true if `value` compares to `date` as required
+    fn holds(self, value: NaiveDate, date: NaiveDate) -> bool {
+        match self {
+            Self::After => value > date,
+            Self::OnOrAfter => value >= date,
+            Self::Before => value < date,
+            Self::OnOrBefore => value <= date,
+            Self::On => value == date,
+        }
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for Query {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let source = s.trim();
+        let expr = tokenize(source)
+            .and_then(|tokens| Parser { tokens, pos: 0 }.parse())
+            .map_err(|reason| {
+                VibeTicketError::InvalidInput(format!("Invalid query '{source}': {reason}"))
+            })?;
+
+        Ok(Self {
+            expr,
+            current_user: None,
+            source: source.to_string(),
+        })
+    }
+}
+
+/// Token of a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    /// A word; `literal` is set if it started with a quote
+    Word {
+        text: String,
+        literal: bool,
+    },
+}
+
+impl Token {
+    /// Returns true if the token is the unquoted keyword `keyword`
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Word { text, literal: false } if text == keyword)
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("'('"),
+            Self::Close => f.write_str("')'"),
+            Self::Word { text, .. } => write!(f, "'{text}'"),
+        }
+    }
+}
+
+/// Splits a query into parentheses and words, keeping quoted parts together
+fn tokenize(source: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut current: Option<(String, bool)> = None;
+    let mut quoted = false;
+
+    let finish = |current: &mut Option<(String, bool)>, tokens: &mut Vec<Token>| {
+        if let Some((text, literal)) = current.take() {
+            tokens.push(Token::Word { text, literal });
+        }
+    };
+
+    for c in source.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.get_or_insert_with(|| (String::new(), true));
+            },
+            _ if quoted => current
+                .get_or_insert_with(|| (String::new(), false))
+                .0
+                .push(c),
+            '(' | ')' => {
+                finish(&mut current, &mut tokens);
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            },
+            c if c.is_whitespace() => finish(&mut current, &mut tokens),
+            c => current
+                .get_or_insert_with(|| (String::new(), false))
+                .0
+                .push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quote".to_string());
+    }
+    finish(&mut current, &mut tokens);
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the tokens of a query
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse(mut self) -> std::result::Result<Expr, String> {
+        if self.tokens.is_empty() {
+            return Ok(Expr::And(Vec::new()));
+        }
+        let expr = self.parse_or()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(Token::Close) => Err("unmatched ')'".to_string()),
+            Some(token) => Err(format!("unexpected {token}")),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// `and ("OR" and)*`
+    fn parse_or(&mut self) -> std::result::Result<Expr, String> {
+        let mut exprs = vec![self.parse_and()?];
+        while self.peek().is_some_and(|t| t.is_keyword("OR")) {
+            self.pos += 1;
+            exprs.push(self.parse_and()?);
+        }
+        Ok(collapse(exprs, Expr::Or))
+    }
+
+    /// `unary (["AND"] unary)*`
+    fn parse_and(&mut self) -> std::result::Result<Expr, String> {
+        let mut exprs = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                None | Some(Token::Close) => break,
+                Some(token) if token.is_keyword("OR") => break,
+                Some(token) if token.is_keyword("AND") => self.pos += 1,
+                Some(_) => {},
+            }
+            exprs.push(self.parse_unary()?);
+        }
+        Ok(collapse(exprs, Expr::And))
+    }
+
+    /// `"NOT" unary | "-"term | "(" or ")" | term`
+    fn parse_unary(&mut self) -> std::result::Result<Expr, String> {
+        match self.next() {
+            None => Err("unexpected end of query".to_string()),
+            Some(Token::Close) => Err("unexpected ')'".to_string()),
+            Some(Token::Open) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("missing ')'".to_string()),
+                }
+            },
+            Some(token) if token.is_keyword("NOT") => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Some(token) if token.is_keyword("AND") || token.is_keyword("OR") => {
+                Err(format!("unexpected {token}"))
+            },
+            Some(Token::Word {
+                text,
+                literal: true,
+            }) => Ok(Expr::Term(Term::Text(text))),
+            Some(Token::Word {
+                text,
+                literal: false,
+            }) => match text.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => {
+                    Ok(Expr::Not(Box::new(Expr::Term(Term::parse(rest)?))))
+                },
+                _ => Term::parse(&text).map(Expr::Term),
+            },
+        }
+    }
+}
+
+/// Combines expressions with `combine`, unless there is only one
+fn collapse(mut exprs: Vec<Expr>, combine: fn(Vec<Expr>) -> Expr) -> Expr {
+    if exprs.len() == 1 {
+        exprs.remove(0)
+    } else {
+        combine(exprs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ticket(status: Status, priority: Priority, assignee: Option<&str>) -> Ticket {
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.status = status;
+        ticket.priority = priority;
+        ticket.assignee = assignee.map(ToString::to_string);
+        ticket.tags = vec!["Backend".to_string()];
+        ticket
+    }
+
+    #[test]
+    fn test_query_matches_all_terms() {
+        let query: Query = "status:todo priority:high assignee:me".parse().unwrap();
+        let mine = ticket(Status::Todo, Priority::High, Some("alice"));
+        assert!(!query.matches(&mine), "me is unresolved");
+
+        let query = query.with_current_user("alice");
+        assert!(query.uses_current_user());
+        assert!(query.matches(&mine));
+        assert!(!query.matches(&ticket(Status::Todo, Priority::Low, Some("alice"))));
+        assert!(!query.matches(&ticket(Status::Todo, Priority::High, Some("bob"))));
+    }
+
+    #[test]
+    fn test_query_text_tags_and_state() {
+        let open = ticket(Status::Doing, Priority::Medium, None);
+        for query in [
+            "login tag:backend",
+            "is:open assignee:none",
+            "\"fix login\"",
+        ] {
+            assert!(query.parse::<Query>().unwrap().matches(&open), "{query}");
+        }
+        assert!(!"is:closed".parse::<Query>().unwrap().matches(&open));
+        assert!("".parse::<Query>().unwrap().matches(&open));
+    }
+
+    #[test]
+    fn test_invalid_queries() {
+        for query in [
+            "status:nope",
+            "owner:alice",
+            "priority:",
+            "is:stale",
+            "\"open",
+            "(status:todo",
+            "status:todo)",
+            "tag:a OR",
+            "NOT",
+            "created:>yesterday",
+        ] {
+            assert!(query.parse::<Query>().is_err(), "{query}");
+        }
+    }
+
+    #[test]
+    fn test_boolean_expressions() {
+        let mut doing = ticket(Status::Doing, Priority::High, Some("alice"));
+        doing.created_at = Local
+            .with_ymd_and_hms(2025, 3, 1, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let query = |source: &str| source.parse::<Query>().unwrap().matches(&doing);
+
+        assert!(query(
+            "status:doing AND (tag:frontend OR priority:high) created:>2025-01-01"
+        ));
+        assert!(!query("status:doing (tag:frontend OR priority:low)"));
+        assert!(query("status:todo OR status:doing"));
+        assert!(query("NOT assignee:bob -tag:frontend"));
+        assert!(!query("NOT (status:doing OR status:todo)"));
+        assert!(query(
+            "created:2025-03-01 created:<=2025-03-01 created:>=2025-03-01"
+        ));
+        assert!(!query("due:<2030-01-01"), "no due date");
+        assert!(query("milestone:none"));
+        doing.milestone = Some("v1.0".to_string());
+        let query = |source: &str| source.parse::<Query>().unwrap().matches(&doing);
+        assert!(query("milestone:v1.0 -milestone:none"));
+        assert!(query("\"OR\" OR status:doing"));
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        let query: Query = "status:todo tag:a OR tag:b".parse().unwrap();
+        assert!(matches!(query.expr(), Expr::Or(terms) if terms.len() == 2));
+    }
+}
@@ -0,0 +1,366 @@
+//! Typed relations between tickets
+//!
+//! Besides dependencies (see [`super::dependencies`]), a ticket can
+//! duplicate, supersede, be caused by, follow or simply relate to another
+//! ticket. Relations are recorded on both tickets: marking `a` as a duplicate
+//! of `b` records `duplicates b` on `a` and `duplicated_by a` on `b`.
+//! Cycles of hierarchical relations, such as `a` following `b` following
+//! `a`, are rejected when a relation is added.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+
+/// Kind of relation from one ticket to another
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    /// The ticket reports the same thing as the other ticket
+    Duplicates,
+
+    /// The other ticket duplicates this one
+    DuplicatedBy,
+
+    /// The tickets are related without further meaning
+    RelatesTo,
+
+    /// The ticket replaces the other ticket
+    Supersedes,
+
+    /// The other ticket replaces this one
+    SupersededBy,
+
+    /// The ticket was caused by the other ticket, such as a regression
+    CausedBy,
+
+    /// The ticket caused the other ticket
+    Causes,
+
+    /// The ticket is follow-up work of the other ticket
+    Follows,
+
+    /// The other ticket is follow-up work of this one
+    FollowedBy,
+}
+
+impl RelationKind {
+    /// Returns all relation kinds
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Duplicates,
+            Self::DuplicatedBy,
+            Self::RelatesTo,
+            Self::Supersedes,
+            Self::SupersededBy,
+            Self::CausedBy,
+            Self::Causes,
+            Self::Follows,
+            Self::FollowedBy,
+        ]
+    }
+
+    /// Kind recorded on the other ticket of the relation
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Duplicates => Self::DuplicatedBy,
+            Self::DuplicatedBy => Self::Duplicates,
+            Self::RelatesTo => Self::RelatesTo,
+            Self::Supersedes => Self::SupersededBy,
+            Self::SupersededBy => Self::Supersedes,
+            Self::CausedBy => Self::Causes,
+            Self::Causes => Self::CausedBy,
+            Self::Follows => Self::FollowedBy,
+            Self::FollowedBy => Self::Follows,
+        }
+    }
+
+    /// Whether the relation orders the tickets, so that chains of it must
+    /// not form a cycle
+    ///
+    /// Only `relates_to` is not hierarchical.
+    pub const fn is_hierarchical(self) -> bool {
+        !matches!(self, Self::RelatesTo)
+    }
+
+    /// Whether the relation is the inverse side of a pair, recorded on the
+    /// other ticket of the relation the user added
+    pub const fn is_inverse(self) -> bool {
+        matches!(
+            self,
+            Self::DuplicatedBy | Self::SupersededBy | Self::Causes | Self::FollowedBy
+        )
+    }
+
+    /// Phrase describing the relation, as in "a duplicates b"
+    pub const fn phrase(self) -> &'static str {
+        match self {
+            Self::Duplicates => "duplicates",
+            Self::DuplicatedBy => "is duplicated by",
+            Self::RelatesTo => "relates to",
+            Self::Supersedes => "supersedes",
+            Self::SupersededBy => "is superseded by",
+            Self::CausedBy => "is caused by",
+            Self::Causes => "causes",
+            Self::Follows => "follows",
+            Self::FollowedBy => "is followed by",
+        }
+    }
+}
+
+impl fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Duplicates => "duplicates",
+            Self::DuplicatedBy => "duplicated_by",
+            Self::RelatesTo => "relates_to",
+            Self::Supersedes => "supersedes",
+            Self::SupersededBy => "superseded_by",
+            Self::CausedBy => "caused_by",
+            Self::Causes => "causes",
+            Self::Follows => "follows",
+            Self::FollowedBy => "followed_by",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl TryFrom<&str> for RelationKind {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value.to_lowercase().replace('-', "_").as_str() {
+            "duplicates" => Ok(Self::Duplicates),
+            "duplicated_by" => Ok(Self::DuplicatedBy),
+            "relates_to" | "related" => Ok(Self::RelatesTo),
+            "supersedes" => Ok(Self::Supersedes),
+            "superseded_by" => Ok(Self::SupersededBy),
+            "caused_by" => Ok(Self::CausedBy),
+            "causes" => Ok(Self::Causes),
+            "follows" => Ok(Self::Follows),
+            "followed_by" => Ok(Self::FollowedBy),
+            _ => Err(format!(
+                "Invalid relation: {value}. Valid relations: {}",
+                Self::all()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+/// Relation from a ticket to another ticket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Relation {
+    /// Kind of relation
+    pub kind: RelationKind,
+
+    /// The other ticket
+    pub ticket: TicketId,
+}
+
+/// Records that `ticket` has a relation of `kind` to `other`, and the
+/// inverse relation on `other`
+///
+/// `tickets` is used to detect cycles of hierarchical relations. Returns
+/// `false` if the relation already existed.
+///
+/// # Errors
+///
+/// Returns an error if both are the same ticket, or if the relation is
+/// hierarchical and `other` already has it to `ticket`, directly (such as
+/// two tickets duplicating each other) or through other tickets.
+pub fn add_relation(
+    ticket: &mut Ticket,
+    other: &mut Ticket,
+    kind: RelationKind,
+    tickets: &[Ticket],
+) -> Result<bool> {
+    if ticket.id == other.id {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Ticket '{}' cannot be related to itself",
+            ticket.slug
+        )));
+    }
+    if kind.is_hierarchical() {
+        if has_relation(ticket, kind.inverse(), &other.id) {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "'{}' already {} '{}'",
+                other.slug,
+                kind.phrase(),
+                ticket.slug
+            )));
+        }
+        if relates_transitively(tickets, kind, &other.id, &ticket.id) {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "'{}' already {} '{}' through other tickets; relating them would create a cycle",
+                other.slug,
+                kind.phrase(),
+                ticket.slug
+            )));
+        }
+    }
+
+    let added = push_unique(ticket, kind, &other.id);
+    push_unique(other, kind.inverse(), &ticket.id);
+    Ok(added)
+}
+
+/// Removes the relation of `kind` from `ticket` to `other`, and its inverse
+///
+/// Returns `false` if there was no such relation.
+pub fn remove_relation(ticket: &mut Ticket, other: &mut Ticket, kind: RelationKind) -> bool {
+    let before = ticket.relations.len();
+    ticket
+        .relations
+        .retain(|r| !(r.kind == kind && r.ticket == other.id));
+    other
+        .relations
+        .retain(|r| !(r.kind == kind.inverse() && r.ticket == ticket.id));
+    ticket.relations.len() != before
+}
+
+/// Returns true if `ticket` has a relation of `kind` to the ticket `id`
+pub fn has_relation(ticket: &Ticket, kind: RelationKind, id: &TicketId) -> bool {
+    ticket
+        .relations
+        .iter()
+        .any(|r| r.kind == kind && &r.ticket == id)
+}
+
+/// Renders the relations of a ticket as lines such as
+/// `duplicates: fix-login (Done)`
+pub fn render_relations(ticket: &Ticket, tickets: &[Ticket]) -> Vec<String> {
+    let mut relations: Vec<&Relation> = ticket.relations.iter().collect();
+    relations.sort_by_key(|r| r.kind);
+    relations
+        .into_iter()
+        .map(|relation| {
+            tickets
+                .iter()
+                .find(|t| t.id == relation.ticket)
+                .map_or_else(
+                    || format!("{}: {} (missing)", relation.kind, relation.ticket.short()),
+                    |other| format!("{}: {} ({})", relation.kind, other.slug, other.status),
+                )
+        })
+        .collect()
+}
+
+/// Returns true if `from` reaches `to` by following relations of `kind`
+fn relates_transitively(
+    tickets: &[Ticket],
+    kind: RelationKind,
+    from: &TicketId,
+    to: &TicketId,
+) -> bool {
+    let by_id: HashMap<&TicketId, &Ticket> = tickets.iter().map(|t| (&t.id, t)).collect();
+    let mut stack = vec![from];
+    let mut seen = HashSet::new();
+
+    while let Some(id) = stack.pop() {
+        if id == to {
+            return true;
+        }
+        if !seen.insert(id) {
+            continue;
+        }
+        if let Some(ticket) = by_id.get(id) {
+            stack.extend(
+                ticket
+                    .relations
+                    .iter()
+                    .filter(|r| r.kind == kind)
+                    .map(|r| &r.ticket),
+            );
+        }
+    }
+
+    false
+}
+
+/// Appends the relation unless it is already present, returning whether it
+/// was added
+fn push_unique(ticket: &mut Ticket, kind: RelationKind, id: &TicketId) -> bool {
+    if has_relation(ticket, kind, id) {
+        false
+    } else {
+        ticket.relations.push(Relation {
+            kind,
+            ticket: id.clone(),
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relations_are_symmetric() {
+        let mut a = Ticket::new("a", "A");
+        let mut b = Ticket::new("b", "B");
+
+        assert!(add_relation(&mut a, &mut b, RelationKind::Duplicates, &[]).unwrap());
+        assert!(!add_relation(&mut a, &mut b, RelationKind::Duplicates, &[]).unwrap());
+        assert!(has_relation(&b, RelationKind::DuplicatedBy, &a.id));
+        assert!(add_relation(&mut b, &mut a, RelationKind::Duplicates, &[]).is_err());
+        let mut copy = a.clone();
+        assert!(add_relation(&mut a, &mut copy, RelationKind::RelatesTo, &[]).is_err());
+
+        assert!(add_relation(&mut a, &mut b, RelationKind::RelatesTo, &[]).unwrap());
+        assert!(has_relation(&b, RelationKind::RelatesTo, &a.id));
+        assert_eq!(
+            render_relations(&a, &[a.clone(), b.clone()]),
+            vec!["duplicates: b (Todo)", "relates_to: b (Todo)"]
+        );
+
+        assert!(remove_relation(&mut a, &mut b, RelationKind::Duplicates));
+        assert!(!remove_relation(&mut a, &mut b, RelationKind::Duplicates));
+        assert_eq!(b.relations.len(), 1);
+    }
+
+    #[test]
+    fn test_hierarchical_relations_reject_cycles() {
+        let mut a = Ticket::new("a", "A");
+        let mut b = Ticket::new("b", "B");
+        let mut c = Ticket::new("c", "C");
+
+        add_relation(&mut b, &mut a, RelationKind::Follows, &[]).unwrap();
+        let tickets = vec![a.clone(), b.clone(), c.clone()];
+        add_relation(&mut c, &mut b, RelationKind::Follows, &tickets).unwrap();
+        let tickets = vec![a.clone(), b.clone(), c.clone()];
+
+        // a <- b <- c, so a cannot follow c
+        let error = add_relation(&mut a, &mut c, RelationKind::Follows, &tickets).unwrap_err();
+        assert!(error.to_string().contains("cycle"));
+        // Other kinds are independent of each other
+        assert!(add_relation(&mut a, &mut c, RelationKind::CausedBy, &tickets).unwrap());
+        // relates_to is not hierarchical
+        assert!(add_relation(&mut c, &mut a, RelationKind::RelatesTo, &tickets).unwrap());
+        assert!(add_relation(&mut a, &mut c, RelationKind::RelatesTo, &tickets).is_ok());
+    }
+
+    #[test]
+    fn test_relation_kind_parsing() {
+        for kind in RelationKind::all() {
+            assert_eq!(RelationKind::try_from(kind.to_string().as_str()), Ok(kind));
+        }
+        assert_eq!(
+            RelationKind::try_from("relates-to"),
+            Ok(RelationKind::RelatesTo)
+        );
+        assert_eq!(
+            RelationKind::try_from("caused-by"),
+            Ok(RelationKind::CausedBy)
+        );
+        assert!(RelationKind::try_from("blocks").is_err());
+    }
+}
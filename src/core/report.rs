@@ -0,0 +1,403 @@
+//! Reports on ticket activity
+//!
+//! A [`Report`] lists the tickets with activity of one kind in a time window,
+//! such as the tickets closed last week, optionally grouped by status,
+//! priority, assignee or tag. It also counts the tickets created, started
+//! and closed in the window, and the open tickets by status. Reports are
+//! serializable for structured output and render themselves as markdown
+//! with [`Report::to_markdown`].
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as FmtWrite};
+use std::str::FromStr;
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+
+use super::{Priority, Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+
+/// Kind of activity a report lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportTemplate {
+    /// Tickets created, started or closed in the window
+    Summary,
+    /// Tickets closed in the window
+    Shipped,
+    /// Tickets created in the window
+    Created,
+    /// Tickets started in the window
+    Started,
+}
+
+impl ReportTemplate {
+    /// Names accepted by [`FromStr`]
+    pub const NAMES: [&str; 4] = ["summary", "shipped", "created", "started"];
+
+    /// Time of the ticket's activity of this kind within the window
+    fn activity(
+        self,
+        ticket: &Ticket,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let in_window = |at: Option<DateTime<Utc>>| at.filter(|at| *at >= since && *at < until);
+        match self {
+            Self::Summary => [Some(ticket.created_at), ticket.started_at, ticket.closed_at]
+                .into_iter()
+                .filter_map(in_window)
+                .max(),
+            Self::Shipped => in_window(ticket.closed_at),
+            Self::Created => in_window(Some(ticket.created_at)),
+            Self::Started => in_window(ticket.started_at),
+        }
+    }
+}
+
+impl fmt::Display for ReportTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Summary => "Summary",
+            Self::Shipped => "Shipped",
+            Self::Created => "Created",
+            Self::Started => "Started",
+        })
+    }
+}
+
+impl FromStr for ReportTemplate {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "summary" => Ok(Self::Summary),
+            "shipped" | "closed" | "done" => Ok(Self::Shipped),
+            "created" | "new" => Ok(Self::Created),
+            "started" => Ok(Self::Started),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Unknown report template '{s}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// Field the tickets of a report are grouped by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportGroupBy {
+    /// A single group with all tickets
+    #[default]
+    None,
+    /// Current status
+    Status,
+    /// Priority, most urgent first
+    Priority,
+    /// Assignee; unassigned tickets come last
+    Assignee,
+    /// Tag; tickets with several tags appear in each of their groups
+    Tag,
+}
+
+impl ReportGroupBy {
+    /// Names accepted by [`FromStr`]
+    pub const NAMES: [&str; 5] = ["none", "status", "priority", "assignee", "tag"];
+
+    /// Sort keys and labels of the groups a ticket belongs to
+    fn keys(self, ticket: &Ticket) -> Vec<((usize, String), String)> {
+        match self {
+            Self::None => vec![((0, String::new()), "All".to_string())],
+            Self::Status => {
+                let rank = Status::all()
+                    .iter()
+                    .position(|s| *s == ticket.status)
+                    .unwrap_or_default();
+                vec![((rank, String::new()), ticket.status.to_string())]
+            },
+            Self::Priority => {
+                let rank = usize::from(u8::MAX - ticket.priority.value());
+                vec![((rank, String::new()), ticket.priority.to_string())]
+            },
+            Self::Assignee => ticket.assignee.as_ref().map_or_else(
+                || vec![((1, String::new()), "Unassigned".to_string())],
+                |user| vec![((0, user.to_lowercase()), user.clone())],
+            ),
+            Self::Tag if ticket.tags.is_empty() => {
+                vec![((1, String::new()), "Untagged".to_string())]
+            },
+            Self::Tag => ticket
+                .tags
+                .iter()
+                .map(|tag| ((0, tag.to_lowercase()), tag.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for ReportGroupBy {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" | "" => Ok(Self::None),
+            "status" => Ok(Self::Status),
+            "priority" => Ok(Self::Priority),
+            "assignee" => Ok(Self::Assignee),
+            "tag" | "tags" => Ok(Self::Tag),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Cannot group by '{s}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// Ticket listed in a report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportEntry {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub status: Status,
+    pub priority: Priority,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Time of the activity the ticket is listed for
+    pub at: DateTime<Utc>,
+}
+
+/// Group of tickets in a report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportGroup {
+    pub key: String,
+    pub tickets: Vec<ReportEntry>,
+}
+
+/// Activity counts of a report's window
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReportStats {
+    /// Tickets created in the window
+    pub created: usize,
+    /// Tickets started in the window
+    pub started: usize,
+    /// Tickets closed in the window
+    pub shipped: usize,
+    /// Tickets not done, by status
+    pub open_by_status: BTreeMap<String, usize>,
+}
+
+/// Tickets with activity of one kind in a time window
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub template: ReportTemplate,
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub group_by: ReportGroupBy,
+    /// Number of distinct tickets listed
+    pub total: usize,
+    pub stats: ReportStats,
+    pub groups: Vec<ReportGroup>,
+}
+
+impl Report {
+    /// Builds a report over `tickets` for the window from `since` up to (but
+    /// not including) `until`
+    ///
+    /// Tickets within a group are listed by activity time, oldest first.
+    pub fn build(
+        template: ReportTemplate,
+        tickets: &[Ticket],
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        group_by: ReportGroupBy,
+    ) -> Self {
+        let mut stats = ReportStats::default();
+        let mut groups: BTreeMap<(usize, String), ReportGroup> = BTreeMap::new();
+        let mut total = 0;
+
+        for ticket in tickets {
+            let in_window =
+                |template: ReportTemplate| template.activity(ticket, since, until).is_some();
+            stats.created += usize::from(in_window(ReportTemplate::Created));
+            stats.started += usize::from(in_window(ReportTemplate::Started));
+            stats.shipped += usize::from(in_window(ReportTemplate::Shipped));
+            if ticket.status != Status::Done {
+                *stats
+                    .open_by_status
+                    .entry(ticket.status.to_string())
+                    .or_default() += 1;
+            }
+
+            let Some(at) = template.activity(ticket, since, until) else {
+                continue;
+            };
+            total += 1;
+            for (sort_key, key) in group_by.keys(ticket) {
+                groups
+                    .entry(sort_key)
+                    .or_insert_with(|| ReportGroup {
+                        key,
+                        tickets: Vec::new(),
+                    })
+                    .tickets
+                    .push(ReportEntry {
+                        id: ticket.id.to_string(),
+                        slug: ticket.slug.clone(),
+                        title: ticket.title.clone(),
+                        status: ticket.status,
+                        priority: ticket.priority,
+                        assignee: ticket.assignee.clone(),
+                        tags: ticket.tags.clone(),
+                        at,
+                    });
+            }
+        }
+
+        let mut groups: Vec<ReportGroup> = groups.into_values().collect();
+        for group in &mut groups {
+            group.tickets.sort_by_key(|entry| entry.at);
+        }
+
+        Self {
+            template,
+            since,
+            until,
+            group_by,
+            total,
+            stats,
+            groups,
+        }
+    }
+
+    /// Renders the report as markdown
+    pub fn to_markdown(&self) -> String {
+        let date = |at: DateTime<Utc>| at.with_timezone(&Local).format("%Y-%m-%d").to_string();
+        let mut md = String::new();
+
+        writeln!(
+            md,
+            "# {}: {} – {}\n",
+            self.template,
+            date(self.since),
+            date(self.until)
+        )
+        .unwrap();
+        writeln!(
+            md,
+            "{} ticket(s) · created {} · started {} · shipped {}",
+            self.total, self.stats.created, self.stats.started, self.stats.shipped
+        )
+        .unwrap();
+        if !self.stats.open_by_status.is_empty() {
+            let open: Vec<String> = self
+                .stats
+                .open_by_status
+                .iter()
+                .map(|(status, count)| format!("{status} {count}"))
+                .collect();
+            writeln!(md, "\nOpen: {}", open.join(" · ")).unwrap();
+        }
+
+        if self.groups.is_empty() {
+            writeln!(md, "\nNo tickets.").unwrap();
+        }
+        for group in &self.groups {
+            if self.group_by != ReportGroupBy::None {
+                writeln!(md, "\n## {} ({})", group.key, group.tickets.len()).unwrap();
+            }
+            md.push('\n');
+            for entry in &group.tickets {
+                write!(
+                    md,
+                    "- **{}** {} ({}, {})",
+                    entry.slug, entry.title, entry.status, entry.priority
+                )
+                .unwrap();
+                if let Some(assignee) = &entry.assignee {
+                    write!(md, " @{assignee}").unwrap();
+                }
+                writeln!(md, " — {}", date(entry.at)).unwrap();
+            }
+        }
+
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ticket(slug: &str, tags: &[&str], closed_days_ago: Option<i64>) -> Ticket {
+        let now = Utc::now();
+        let mut ticket = Ticket::new(slug, slug);
+        ticket.created_at = now - Duration::days(30);
+        ticket.tags = tags.iter().map(ToString::to_string).collect();
+        if let Some(days) = closed_days_ago {
+            ticket.status = Status::Done;
+            ticket.closed_at = Some(now - Duration::days(days));
+        }
+        ticket
+    }
+
+    #[test]
+    fn test_shipped_report_grouped_by_tag() {
+        let tickets = vec![
+            ticket("api", &["backend"], Some(2)),
+            ticket("both", &["backend", "ui"], Some(5)),
+            ticket("old", &["ui"], Some(20)),
+            ticket("open", &[], None),
+        ];
+        let until = Utc::now();
+        let report = Report::build(
+            ReportTemplate::Shipped,
+            &tickets,
+            until - Duration::days(7),
+            until,
+            ReportGroupBy::Tag,
+        );
+
+        assert_eq!(report.total, 2);
+        assert_eq!(report.stats.shipped, 2);
+        assert_eq!(report.stats.created, 0);
+        assert_eq!(report.stats.open_by_status.values().sum::<usize>(), 1);
+
+        let groups: Vec<(&str, Vec<&str>)> = report
+            .groups
+            .iter()
+            .map(|g| {
+                (
+                    g.key.as_str(),
+                    g.tickets.iter().map(|t| t.slug.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![("backend", vec!["both", "api"]), ("ui", vec!["both"])]
+        );
+
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Shipped: "));
+        assert!(markdown.contains("## backend (2)"));
+        assert!(!markdown.contains("old"));
+    }
+
+    #[test]
+    fn test_parse_template_and_group_by() {
+        assert_eq!(
+            "closed".parse::<ReportTemplate>().unwrap(),
+            ReportTemplate::Shipped
+        );
+        assert_eq!(
+            "Priority".parse::<ReportGroupBy>().unwrap(),
+            ReportGroupBy::Priority
+        );
+        assert!("velocity".parse::<ReportTemplate>().is_err());
+        assert!("owner".parse::<ReportGroupBy>().is_err());
+    }
+}
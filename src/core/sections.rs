@@ -0,0 +1,427 @@
+//! Sections of a ticket view
+//!
+//! `show` and the MCP show tool render a ticket as a fixed header followed by
+//! optional sections. This module defines the sections and builds their JSON
+//! representation, so every front end exposes the same structure.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::Ticket;
+use super::audit::{AuditEvent, Change};
+use super::relations::RelationKind;
+use crate::config::Config;
+use crate::error::{Result, VibeTicketError};
+use crate::storage::FileStorage;
+
+/// Number of history events shown before the history is collapsed
+pub const COLLAPSED_HISTORY_LEN: usize = 10;
+
+/// Optional section of a ticket view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Section {
+    /// Full description text
+    Description,
+    /// Tasks and progress
+    Tasks,
+    /// References to other tickets and projects
+    Links,
+    /// Chronological history
+    History,
+    /// Specifications linked to the ticket
+    Spec,
+    /// Git worktree of the ticket
+    Worktree,
+}
+
+impl Section {
+    /// All sections in display order
+    pub const ALL: [Self; 6] = [
+        Self::Description,
+        Self::Tasks,
+        Self::Links,
+        Self::History,
+        Self::Spec,
+        Self::Worktree,
+    ];
+
+    /// Name of the section as used on the command line and in JSON
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Description => "description",
+            Self::Tasks => "tasks",
+            Self::Links => "links",
+            Self::History => "history",
+            Self::Spec => "spec",
+            Self::Worktree => "worktree",
+        }
+    }
+
+    /// Resolve the sections to display
+    ///
+    /// An explicit `--sections` list wins; `all` selects every section.
+    /// Without a list, the description is shown and the legacy `--tasks` and
+    /// `--history` flags add their sections.
+    pub fn select(names: &[String], tasks: bool, history: bool) -> Result<Vec<Self>> {
+        let mut sections = if names.is_empty() {
+            let mut sections = vec![Self::Description];
+            if tasks {
+                sections.push(Self::Tasks);
+            }
+            if history {
+                sections.push(Self::History);
+            }
+            sections
+        } else if names.iter().any(|name| name.trim() == "all") {
+            Self::ALL.to_vec()
+        } else {
+            names
+                .iter()
+                .map(|name| name.parse())
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        sections.sort();
+        sections.dedup();
+        Ok(sections)
+    }
+}
+
+impl FromStr for Section {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| {
+                VibeTicketError::InvalidInput(format!(
+                    "Unknown section '{s}'. Valid sections: {}, all",
+                    Self::ALL.map(Self::as_str).join(", ")
+                ))
+            })
+    }
+}
+
+/// Entry of a ticket's history
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEvent {
+    /// Time of the event
+    pub at: DateTime<Utc>,
+
+    /// Description of the event
+    pub event: String,
+
+    /// User who caused the event, if recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+}
+
+impl fmt::Display for HistoryEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.actor {
+            Some(actor) => write!(f, "{} ({actor})", self.event),
+            None => f.write_str(&self.event),
+        }
+    }
+}
+
+impl HistoryEvent {
+    fn new(at: DateTime<Utc>, event: impl Into<String>) -> Self {
+        Self {
+            at,
+            event: event.into(),
+            actor: None,
+        }
+    }
+}
+
+/// Build the chronological history of a ticket
+///
+/// Changes come from the ticket's audit log `log`. Lifecycle timestamps
+/// and task completions that predate the log are derived from the ticket,
+/// so tickets created before the audit log existed still have a history.
+/// Work-log notes and comments are always included.
+pub fn history(ticket: &Ticket, log: &[AuditEvent]) -> Vec<HistoryEvent> {
+    let log_start = log.first().map(|event| event.at);
+    let predates_log = |at: &DateTime<Utc>| log_start.is_none_or(|start| *at < start);
+
+    let mut derived = vec![HistoryEvent::new(ticket.created_at, "Created")];
+    if let Some(at) = ticket.started_at {
+        derived.push(HistoryEvent::new(at, "Started"));
+    }
+    for task in &ticket.tasks {
+        if let Some(at) = task.completed_at {
+            derived.push(HistoryEvent::new(
+                at,
+                format!("Completed task: {}", task.title),
+            ));
+        }
+    }
+    if let Some(at) = ticket.closed_at {
+        derived.push(HistoryEvent::new(at, "Closed"));
+    }
+    let logs_creation = log.iter().any(|event| event.change == Change::Created);
+    derived.retain(|event| predates_log(&event.at) && !(logs_creation && event.event == "Created"));
+
+    let mut events = derived;
+    events.extend(log.iter().map(|event| HistoryEvent {
+        at: event.at,
+        event: event.change.to_string(),
+        actor: event.actor.clone(),
+    }));
+
+    for entry in &ticket.work_log {
+        events.push(HistoryEvent::new(
+            entry.logged_at,
+            format!("Log: {}", entry.message),
+        ));
+    }
+
+    for comment in &ticket.comments {
+        events.push(HistoryEvent {
+            at: comment.created_at,
+            event: format!("Comment #{}: {}", comment.id, comment.body),
+            actor: Some(comment.author.clone()),
+        });
+    }
+
+    events.sort_by_key(|e| e.at);
+    events
+}
+
+/// Build the history of a ticket with the audit log read from the project
+///
+/// A missing or unreadable audit log is treated as empty.
+pub fn load_history(project_root: &Path, ticket: &Ticket) -> Vec<HistoryEvent> {
+    let log = FileStorage::new(project_root.join(".vibe-ticket"))
+        .load_history(&ticket.id)
+        .unwrap_or_default();
+    history(ticket, &log)
+}
+
+/// Returns the events to display and the number of hidden earlier events
+///
+/// Long histories are collapsed to their most recent events unless `expand`
+/// is set.
+pub fn visible_history(events: &[HistoryEvent], expand: bool) -> (&[HistoryEvent], usize) {
+    if expand || events.len() <= COLLAPSED_HISTORY_LEN {
+        (events, 0)
+    } else {
+        let hidden = events.len() - COLLAPSED_HISTORY_LEN;
+        (&events[hidden..], hidden)
+    }
+}
+
+/// Reference from a ticket to another ticket or project
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketLink {
+    /// Kind of reference (e.g., `moved_to`)
+    pub relation: String,
+
+    /// Referenced ticket or project
+    pub target: String,
+}
+
+impl TicketLink {
+    /// Returns true if the link is a dependency or a relation between
+    /// tickets
+    pub fn is_ticket_link(&self) -> bool {
+        matches!(self.relation.as_str(), "depends_on" | "blocks")
+            || RelationKind::try_from(self.relation.as_str()).is_ok()
+    }
+}
+
+/// Collect the references recorded on a ticket
+///
+/// Dependencies and relations reference the other ticket by ID.
+pub fn links(ticket: &Ticket) -> Vec<TicketLink> {
+    let moves = ["moved_from", "moved_to"]
+        .into_iter()
+        .filter_map(|relation| {
+            let target = ticket.metadata.get(relation)?.get("project")?.as_str()?;
+            Some(TicketLink {
+                relation: relation.to_string(),
+                target: target.to_string(),
+            })
+        });
+    let dependencies = [
+        ("depends_on", &ticket.depends_on),
+        ("blocks", &ticket.blocks),
+    ]
+    .into_iter()
+    .flat_map(|(relation, ids)| {
+        ids.iter().map(move |id| TicketLink {
+            relation: relation.to_string(),
+            target: id.to_string(),
+        })
+    });
+
+    let relations = ticket.relations.iter().map(|relation| TicketLink {
+        relation: relation.kind.to_string(),
+        target: relation.ticket.to_string(),
+    });
+
+    moves.chain(dependencies).chain(relations).collect()
+}
+
+/// Specification linked to a ticket
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecLink {
+    /// Spec ID
+    pub id: String,
+
+    /// Spec title
+    pub title: String,
+
+    /// Current phase of the spec
+    pub phase: String,
+}
+
+/// Find the specifications written for a ticket or exported to it
+///
+/// See [`crate::specs::link`].
+pub fn linked_specs(vibe_ticket_dir: &Path, ticket: &Ticket) -> Vec<SpecLink> {
+    let specs_dir = vibe_ticket_dir.join("specs");
+    if !specs_dir.exists() {
+        return Vec::new();
+    }
+
+    let specs = crate::specs::list(&specs_dir).unwrap_or_default();
+    crate::specs::link::specs_of(&specs, ticket)
+        .into_iter()
+        .map(|spec| SpecLink {
+            id: spec.id.clone(),
+            title: spec.title.clone(),
+            phase: format!("{:?}", spec.progress.current_phase),
+        })
+        .collect()
+}
+
+/// Find the worktree created for a ticket, if it exists
+pub fn ticket_worktree(project_root: &Path, ticket: &Ticket) -> Option<PathBuf> {
+    let config = Config::load_for_project(project_root).ok()?;
+    config
+        .worktree_path(project_root, &ticket.slug)
+        .filter(|path| path.exists())
+}
+
+/// Build the JSON object of the selected sections
+///
+/// Keys are the section names; each section is present only if selected.
+pub fn sections_json(
+    ticket: &Ticket,
+    sections: &[Section],
+    expand: bool,
+    project_root: &Path,
+) -> Value {
+    let mut object = Map::new();
+
+    for &section in sections {
+        let value = match section {
+            Section::Description => json!(ticket.description),
+            Section::Tasks => json!({
+                "completed": ticket.completed_tasks_count(),
+                "total": ticket.tasks.len(),
+                "items": ticket.tasks,
+            }),
+            Section::Links => json!(links(ticket)),
+            Section::History => {
+                let events = load_history(project_root, ticket);
+                let (visible, hidden) = visible_history(&events, expand);
+                json!({
+                    "events": visible,
+                    "total": events.len(),
+                    "collapsed": hidden > 0,
+                })
+            },
+            Section::Spec => json!(linked_specs(&project_root.join(".vibe-ticket"), ticket)),
+            Section::Worktree => json!(ticket_worktree(project_root, ticket)),
+        };
+        object.insert(section.as_str().to_string(), value);
+    }
+
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_select_sections() {
+        assert_eq!(
+            Section::select(&[], true, false).unwrap(),
+            vec![Section::Description, Section::Tasks]
+        );
+
+        let names = vec!["worktree".to_string(), "Tasks".to_string()];
+        assert_eq!(
+            Section::select(&names, false, true).unwrap(),
+            vec![Section::Tasks, Section::Worktree]
+        );
+
+        assert_eq!(
+            Section::select(&["all".to_string()], false, false).unwrap(),
+            Section::ALL.to_vec()
+        );
+        assert!(Section::select(&["comments".to_string()], false, false).is_err());
+    }
+
+    #[test]
+    fn test_history_collapses() {
+        let mut ticket = Ticket::new("busy", "Busy ticket");
+        let start = Utc::now() - Duration::days(1);
+        for i in 0..15 {
+            ticket.work_log.push(crate::core::WorkLogEntry {
+                logged_at: start + Duration::minutes(i),
+                message: format!("entry {i}"),
+            });
+        }
+
+        let events = history(&ticket, &[]);
+        assert_eq!(events.len(), 16);
+
+        let (visible, hidden) = visible_history(&events, false);
+        assert_eq!(hidden, 6);
+        assert_eq!(visible.len(), COLLAPSED_HISTORY_LEN);
+        assert_eq!(visible.last().unwrap().event, "Created");
+
+        let (visible, hidden) = visible_history(&events, true);
+        assert_eq!((visible.len(), hidden), (16, 0));
+    }
+
+    #[test]
+    fn test_history_merges_audit_log() {
+        let mut ticket = Ticket::new("old", "Predates the log");
+        ticket.created_at = Utc::now() - Duration::days(2);
+        ticket.started_at = Some(Utc::now() - Duration::days(1));
+        ticket.add_comment("alice", "On it");
+
+        let log = vec![AuditEvent::now(
+            Change::AssigneeChanged {
+                from: None,
+                to: Some("alice".to_string()),
+            },
+            Some("bob".to_string()),
+        )];
+        let events: Vec<String> = history(&ticket, &log)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(events[..2], ["Created", "Started"]);
+        assert!(events.contains(&"Assignee: (none) -> alice (bob)".to_string()));
+        assert!(events.contains(&"Comment #1: On it (alice)".to_string()));
+
+        // Once the log records the creation, derived events are not repeated
+        let log = vec![AuditEvent::now(Change::Created, None)];
+        let mut fresh = Ticket::new("new", "New");
+        fresh.created_at = log[0].at;
+        assert_eq!(history(&fresh, &log).len(), 1);
+    }
+}
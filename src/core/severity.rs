@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Represents the severity of an incident ticket
+///
+/// Severity describes the impact of an incident and is tracked separately
+/// from priority, which describes scheduling urgency. `S1` is the most severe.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Critical outage affecting all users
+    S1,
+
+    /// Major degradation affecting many users
+    S2,
+
+    /// Partial degradation with a workaround available
+    S3,
+
+    /// Minor issue with little user impact
+    S4,
+}
+
+impl Severity {
+    /// Returns all possible severity values, most severe first
+    pub fn all() -> Vec<Self> {
+        vec![Self::S1, Self::S2, Self::S3, Self::S4]
+    }
+
+    /// Returns the numeric value for sorting (higher = more severe)
+    pub const fn value(&self) -> u8 {
+        match self {
+            Self::S1 => 4,
+            Self::S2 => 3,
+            Self::S3 => 2,
+            Self::S4 => 1,
+        }
+    }
+
+    /// Returns a short description of the impact this severity represents
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::S1 => "Critical outage",
+            Self::S2 => "Major degradation",
+            Self::S3 => "Partial degradation",
+            Self::S4 => "Minor issue",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::S1 => "S1",
+            Self::S2 => "S2",
+            Self::S3 => "S3",
+            Self::S4 => "S4",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl TryFrom<&str> for Severity {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "s1" | "sev1" | "1" => Ok(Self::S1),
+            "s2" | "sev2" | "2" => Ok(Self::S2),
+            "s3" | "sev3" | "3" => Ok(Self::S3),
+            "s4" | "sev4" | "4" => Ok(Self::S4),
+            _ => Err(format!("Invalid severity: {value}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_ordering() {
+        assert!(Severity::S1 < Severity::S2);
+        assert!(Severity::S1.value() > Severity::S4.value());
+    }
+
+    #[test]
+    fn test_severity_from_str() {
+        assert_eq!(Severity::try_from("S1").unwrap(), Severity::S1);
+        assert_eq!(Severity::try_from("sev2").unwrap(), Severity::S2);
+        assert_eq!(Severity::try_from("3").unwrap(), Severity::S3);
+        assert!(Severity::try_from("S5").is_err());
+    }
+
+    #[test]
+    fn test_severity_serialization() {
+        let yaml = serde_yaml::to_string(&Severity::S2).unwrap();
+        assert_eq!(yaml.trim(), "S2");
+        assert_eq!(Severity::S4.to_string(), "S4");
+    }
+}
@@ -23,6 +23,10 @@ pub struct Task {
 
     /// Timestamp when the task was completed
     pub completed_at: Option<DateTime<Utc>>,
+
+    /// Fingerprint of the CI finding this task was created from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
 }
 
 impl Task {
@@ -34,6 +38,7 @@ impl Task {
             completed: false,
             created_at: Utc::now(),
             completed_at: None,
+            fingerprint: None,
         }
     }
 
@@ -45,6 +50,7 @@ impl Task {
             completed: false,
             created_at: Utc::now(),
             completed_at: None,
+            fingerprint: None,
         }
     }
 
@@ -0,0 +1,77 @@
+use super::Severity;
+
+/// A built-in template applied when creating a ticket
+///
+/// Templates pre-fill tags and a description skeleton and may require
+/// fields such as severity that are optional for regular tickets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketTemplate {
+    /// Template name used on the command line
+    pub name: &'static str,
+
+    /// Tags added to tickets created from this template
+    pub tags: &'static [&'static str],
+
+    /// Description skeleton used when no description is given
+    pub description: &'static str,
+
+    /// Whether tickets created from this template must have a severity
+    pub requires_severity: bool,
+}
+
+/// Templates shipped with vibe-ticket
+const BUILTIN_TEMPLATES: &[TicketTemplate] = &[
+    TicketTemplate {
+        name: "incident",
+        tags: &["incident"],
+        description: "## Impact\n\n## Timeline\n\n## Mitigation\n\n## Root Cause\n",
+        requires_severity: true,
+    },
+    TicketTemplate {
+        name: "bug",
+        tags: &["bug"],
+        description: "## Steps to Reproduce\n\n## Expected Behavior\n\n## Actual Behavior\n",
+        requires_severity: false,
+    },
+];
+
+impl TicketTemplate {
+    /// Looks up a built-in template by name
+    pub fn find(name: &str) -> Option<&'static Self> {
+        BUILTIN_TEMPLATES
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the names of all built-in templates
+    pub fn names() -> Vec<&'static str> {
+        BUILTIN_TEMPLATES.iter().map(|t| t.name).collect()
+    }
+
+    /// Checks that the given severity satisfies this template
+    pub fn validate_severity(&self, severity: Option<Severity>) -> Result<(), String> {
+        if self.requires_severity && severity.is_none() {
+            return Err(format!(
+                "Template '{}' requires a severity (S1–S4)",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incident_requires_severity() {
+        let template = TicketTemplate::find("incident").unwrap();
+        assert!(template.validate_severity(None).is_err());
+        assert!(template.validate_severity(Some(Severity::S2)).is_ok());
+
+        let bug = TicketTemplate::find("BUG").unwrap();
+        assert!(bug.validate_severity(None).is_ok());
+        assert!(TicketTemplate::find("unknown").is_none());
+    }
+}
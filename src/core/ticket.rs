@@ -1,8 +1,10 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 
-use super::{Priority, Status, Task, TaskId, TicketId};
+use super::incident::IncidentTimeline;
+use super::relations::Relation;
+use super::{Comment, Priority, Severity, Status, Task, TaskId, TicketId, WorkLogEntry};
 
 /// Represents a ticket in the vibe-ticket system
 ///
@@ -13,6 +15,12 @@ pub struct Ticket {
     /// Unique identifier for the ticket
     pub id: TicketId,
 
+    /// Short ID shown in listings and accepted wherever a ticket is referenced
+    ///
+    /// Assigned, collision-checked, when the ticket is first saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_id: Option<String>,
+
     /// URL-friendly slug derived from the title
     pub slug: String,
 
@@ -25,6 +33,10 @@ pub struct Ticket {
     /// Priority level of the ticket
     pub priority: Priority,
 
+    /// Incident severity (S1–S4), independent of priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+
     /// Current status of the ticket
     pub status: Status,
 
@@ -48,8 +60,48 @@ pub struct Ticket {
     #[serde(default)]
     pub tasks: Vec<Task>,
 
+    /// Work-log notes recorded while working on the ticket
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work_log: Vec<WorkLogEntry>,
+
+    /// Discussion on the ticket
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+
+    /// Tickets that must be closed before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<TicketId>,
+
+    /// Tickets waiting for this one to be closed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<TicketId>,
+
+    /// Duplicate, supersede and related links to other tickets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<Relation>,
+
+    /// Epic this ticket belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<TicketId>,
+
+    /// Date the ticket is due
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+
+    /// Name of the milestone the ticket belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+
+    /// Users notified when the status, priority or due date changes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<String>,
+
+    /// Timeline of an incident ticket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incident: Option<IncidentTimeline>,
+
     /// Additional metadata for extensibility
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
@@ -58,10 +110,12 @@ impl Ticket {
     pub fn new(slug: impl Into<String>, title: impl Into<String>) -> Self {
         Self {
             id: TicketId::new(),
+            short_id: None,
             slug: slug.into(),
             title: title.into(),
             description: String::new(),
             priority: Priority::default(),
+            severity: None,
             status: Status::default(),
             tags: Vec::new(),
             created_at: Utc::now(),
@@ -69,6 +123,16 @@ impl Ticket {
             closed_at: None,
             assignee: None,
             tasks: Vec::new(),
+            work_log: Vec::new(),
+            comments: Vec::new(),
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: Vec::new(),
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: HashMap::new(),
         }
     }
@@ -77,10 +141,12 @@ impl Ticket {
     pub fn with_id(id: TicketId, slug: impl Into<String>, title: impl Into<String>) -> Self {
         Self {
             id,
+            short_id: None,
             slug: slug.into(),
             title: title.into(),
             description: String::new(),
             priority: Priority::default(),
+            severity: None,
             status: Status::default(),
             tags: Vec::new(),
             created_at: Utc::now(),
@@ -88,10 +154,26 @@ impl Ticket {
             closed_at: None,
             assignee: None,
             tasks: Vec::new(),
+            work_log: Vec::new(),
+            comments: Vec::new(),
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: Vec::new(),
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: HashMap::new(),
         }
     }
 
+    /// ID shown for the ticket: its short ID, or the UUID prefix for
+    /// tickets saved before short IDs were introduced
+    pub fn display_id(&self) -> String {
+        self.short_id.clone().unwrap_or_else(|| self.id.short())
+    }
+
     /// Starts work on the ticket, updating status and timestamp
     pub fn start(&mut self) {
         self.status = Status::Doing;
@@ -104,6 +186,48 @@ impl Ticket {
         self.closed_at = Some(Utc::now());
     }
 
+    /// Records a work-log note on the ticket
+    pub fn log_work(&mut self, message: impl Into<String>) {
+        self.work_log.push(WorkLogEntry::new(message));
+    }
+
+    /// Adds a comment to the ticket, returning its number
+    ///
+    /// The number follows the highest number in use.
+    pub fn add_comment(&mut self, author: impl Into<String>, body: impl Into<String>) -> u32 {
+        let id = self.comments.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        self.comments.push(Comment::new(id, author, body));
+        id
+    }
+
+    /// Finds a comment by number
+    pub fn comment_mut(&mut self, id: u32) -> Option<&mut Comment> {
+        self.comments.iter_mut().find(|c| c.id == id)
+    }
+
+    /// Removes a comment, returning it if it existed
+    pub fn remove_comment(&mut self, id: u32) -> Option<Comment> {
+        let index = self.comments.iter().position(|c| c.id == id)?;
+        Some(self.comments.remove(index))
+    }
+
+    /// Adds a watcher, returning false if the user already watches the ticket
+    pub fn watch(&mut self, user: impl Into<String>) -> bool {
+        let user = user.into();
+        if self.watchers.contains(&user) {
+            return false;
+        }
+        self.watchers.push(user);
+        true
+    }
+
+    /// Removes a watcher, returning false if the user did not watch the ticket
+    pub fn unwatch(&mut self, user: &str) -> bool {
+        let count = self.watchers.len();
+        self.watchers.retain(|watcher| watcher != user);
+        self.watchers.len() != count
+    }
+
     /// Adds a task to the ticket
     pub fn add_task(&mut self, title: impl Into<String>) -> TaskId {
         let task = Task::new(title);
@@ -156,6 +280,17 @@ impl Ticket {
     }
 }
 
+/// Serializes metadata with sorted keys so ticket files and exports are stable
+fn serialize_sorted<S: Serializer>(
+    metadata: &HashMap<String, serde_json::Value>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    metadata
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,6 +321,36 @@ mod tests {
         assert!(ticket.closed_at.is_some());
     }
 
+    #[test]
+    fn test_comments() {
+        let mut ticket = Ticket::new("test", "Test");
+        assert_eq!(ticket.add_comment("alice", "First"), 1);
+        assert_eq!(ticket.add_comment("bob", "Second"), 2);
+
+        ticket.comment_mut(1).unwrap().edit("First, revised");
+        assert!(ticket.comments[0].edited);
+        assert!(ticket.comments[0].edited_at.is_some());
+        assert_eq!(ticket.comments[0].body, "First, revised");
+
+        // Remaining comments keep their numbers
+        assert_eq!(ticket.remove_comment(2).unwrap().author, "bob");
+        assert!(ticket.remove_comment(2).is_none());
+        assert_eq!(ticket.add_comment("carol", "Third"), 2);
+        ticket.remove_comment(1);
+        assert_eq!(ticket.add_comment("dave", "Fourth"), 3);
+    }
+
+    #[test]
+    fn test_watchers() {
+        let mut ticket = Ticket::new("test", "Test");
+        assert!(ticket.watch("alice"));
+        assert!(!ticket.watch("alice"));
+        assert!(ticket.watch("bob"));
+        assert!(ticket.unwatch("alice"));
+        assert!(!ticket.unwatch("alice"));
+        assert_eq!(ticket.watchers, vec!["bob"]);
+    }
+
     #[test]
     fn test_task_management() {
         let mut ticket = Ticket::new("test", "Test");
@@ -0,0 +1,192 @@
+//! Notifications for ticket watchers
+//!
+//! Users listed in a ticket's `watchers` are notified when its status,
+//! priority or due date changes. The changes are taken from the events
+//! appended to the audit log, so a notification reports exactly what the
+//! history of the ticket records.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::audit::{AuditEvent, Change};
+use super::{Ticket, TicketId};
+
+/// Fields whose changes are reported to watchers
+pub const WATCHED_FIELDS: [&str; 3] = ["status", "priority", "due"];
+
+/// Change of a watched field, from its old to its new value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldDelta {
+    /// Name of the field
+    pub field: String,
+
+    /// Value before the change (`None` if unset)
+    pub from: Option<String>,
+
+    /// Value after the change (`None` if unset)
+    pub to: Option<String>,
+}
+
+/// Notification sent to the watchers of a ticket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WatchNotification {
+    /// Unique identifier of the notification
+    pub id: String,
+
+    /// Ticket that changed
+    pub ticket_id: TicketId,
+
+    /// Slug of the ticket
+    pub ticket_slug: String,
+
+    /// Title of the ticket
+    pub ticket_title: String,
+
+    /// Watchers to notify
+    pub recipients: Vec<String>,
+
+    /// User who made the change, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+
+    /// Time of the change
+    pub at: DateTime<Utc>,
+
+    /// Changed fields with their old and new values
+    pub changes: Vec<FieldDelta>,
+}
+
+impl WatchNotification {
+    /// Event name used when the notification is delivered to a webhook
+    pub const EVENT: &'static str = "ticket.watched_fields_changed";
+
+    /// Builds the notification for events just recorded on `ticket`
+    ///
+    /// Returns `None` if none of the events changed a watched field or
+    /// nobody but the actor watches the ticket.
+    pub fn from_events(ticket: &Ticket, events: &[AuditEvent]) -> Option<Self> {
+        let changes: Vec<FieldDelta> = events.iter().filter_map(watched_delta).collect();
+        let last = events.last()?;
+        Self::new(ticket, last.actor.clone(), last.at, changes)
+    }
+
+    /// Builds the notification of `changes` made to `ticket` by `actor`
+    ///
+    /// Returns `None` if there are no changes or nobody but the actor
+    /// watches the ticket.
+    pub fn new(
+        ticket: &Ticket,
+        actor: Option<String>,
+        at: DateTime<Utc>,
+        changes: Vec<FieldDelta>,
+    ) -> Option<Self> {
+        let recipients: Vec<String> = ticket
+            .watchers
+            .iter()
+            .filter(|watcher| actor.as_ref() != Some(*watcher))
+            .cloned()
+            .collect();
+        if changes.is_empty() || recipients.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            ticket_id: ticket.id.clone(),
+            ticket_slug: ticket.slug.clone(),
+            ticket_title: ticket.title.clone(),
+            recipients,
+            actor,
+            at,
+            changes,
+        })
+    }
+}
+
+/// Returns the delta of an event if it changed a watched field
+fn watched_delta(event: &AuditEvent) -> Option<FieldDelta> {
+    match &event.change {
+        Change::StatusChanged { from, to } => Some(FieldDelta {
+            field: "status".to_string(),
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+        }),
+        Change::FieldChanged { field, from, to } if WATCHED_FIELDS.contains(&field.as_str()) => {
+            Some(FieldDelta {
+                field: field.clone(),
+                from: from.clone(),
+                to: to.clone(),
+            })
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Priority;
+    use crate::core::audit;
+    use chrono::NaiveDate;
+
+    fn events(before: &Ticket, after: &Ticket, actor: &str) -> Vec<AuditEvent> {
+        audit::diff(Some(before), after)
+            .into_iter()
+            .map(|change| AuditEvent::now(change, Some(actor.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_notification_reports_watched_fields() {
+        let mut before = Ticket::new("fix", "Fix it");
+        before.watch("alice");
+        before.watch("bob");
+
+        let mut after = before.clone();
+        after.start();
+        after.priority = Priority::Critical;
+        after.due = NaiveDate::from_ymd_opt(2026, 11, 1);
+        after.title = "Fix it now".to_string();
+
+        let notification =
+            WatchNotification::from_events(&after, &events(&before, &after, "bob")).unwrap();
+        assert_eq!(notification.recipients, vec!["alice"]);
+        assert_eq!(notification.actor.as_deref(), Some("bob"));
+        assert_eq!(
+            notification.changes,
+            vec![
+                FieldDelta {
+                    field: "status".to_string(),
+                    from: Some("Todo".to_string()),
+                    to: Some("Doing".to_string()),
+                },
+                FieldDelta {
+                    field: "priority".to_string(),
+                    from: Some("Medium".to_string()),
+                    to: Some("Critical".to_string()),
+                },
+                FieldDelta {
+                    field: "due".to_string(),
+                    from: None,
+                    to: Some("2026-11-01".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_notification_without_watched_changes() {
+        let mut before = Ticket::new("fix", "Fix it");
+        before.watch("alice");
+
+        let mut after = before.clone();
+        after.title = "Renamed".to_string();
+        assert!(WatchNotification::from_events(&after, &events(&before, &after, "bob")).is_none());
+
+        // Own changes are not reported back
+        after.start();
+        assert!(
+            WatchNotification::from_events(&after, &events(&before, &after, "alice")).is_none()
+        );
+    }
+}
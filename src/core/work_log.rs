@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Represents a work-log note recorded against a ticket
+///
+/// Work-log entries are short, timestamped notes about the work done on a
+/// ticket ("investigated flaky test, suspect race"). Unlike tasks they are
+/// not tracked to completion; they document progress for standups.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkLogEntry {
+    /// Timestamp when the entry was logged
+    pub logged_at: DateTime<Utc>,
+
+    /// Free-form note describing the work
+    pub message: String,
+}
+
+impl WorkLogEntry {
+    /// Creates a new entry logged now
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            logged_at: Utc::now(),
+            message: message.into(),
+        }
+    }
+}
@@ -32,6 +32,18 @@ pub enum VibeTicketError {
     #[error("Ticket not found: {id}")]
     TicketNotFound { id: String },
 
+    /// Closed ticket changed while closed tickets are read-only
+    #[error("Ticket '{slug}' is closed and read-only")]
+    TicketClosed { slug: String },
+
+    /// Ticket closed without the fields the project requires
+    #[error("Ticket '{slug}' cannot be closed; missing: {}", missing.join(", "))]
+    CloseRequirementsNotMet { slug: String, missing: Vec<String> },
+
+    /// Ticket closed while it depends on open tickets
+    #[error("Ticket '{slug}' depends on open tickets: {}", open.join(", "))]
+    OpenDependencies { slug: String, open: Vec<String> },
+
     /// Task not found
     #[error("Task not found: {id}")]
     TaskNotFound { id: String },
@@ -68,6 +80,23 @@ pub enum VibeTicketError {
     #[error("Ticket with slug '{slug}' already exists")]
     DuplicateTicket { slug: String },
 
+    /// Reference to a missing ticket
+    #[error("Ticket '{slug}' refers to missing ticket {id} in {field}")]
+    DanglingReference {
+        slug: String,
+        field: String,
+        id: String,
+    },
+
+    /// Status change that leaves the close time inconsistent
+    #[error("Ticket '{slug}' cannot move from {from} to {to}: {reason}")]
+    InconsistentCloseTime {
+        slug: String,
+        from: String,
+        to: String,
+        reason: String,
+    },
+
     /// File operation error
     #[error("File operation failed for {}: {message}", path.display())]
     FileOperation { path: PathBuf, message: String },
@@ -194,10 +223,31 @@ impl VibeTicketError {
                 format!("Use a different slug or check existing ticket '{}'", slug),
                 "Run 'vibe-ticket list' to see all tickets".to_string(),
             ],
+            Self::DanglingReference { field, .. } => vec![
+                format!("Remove the reference from {field} or create the ticket first"),
+                "Run 'vibe-ticket list' to see all tickets".to_string(),
+            ],
+            Self::InconsistentCloseTime { .. } => vec![
+                "Run 'vibe-ticket close' to close a ticket".to_string(),
+                "Run 'vibe-ticket reopen' to reopen a closed ticket".to_string(),
+            ],
             Self::NoActiveSpec => vec![
                 "Run 'vibe-ticket spec list' to see available specifications".to_string(),
                 "Run 'vibe-ticket spec activate <id>' to set an active specification".to_string(),
             ],
+            Self::TicketClosed { slug } => vec![
+                format!("Run 'vibe-ticket reopen {slug}' to change the ticket"),
+                "Set 'project.immutable_closed' to false to allow editing closed tickets"
+                    .to_string(),
+            ],
+            Self::CloseRequirementsNotMet { slug, .. } => vec![
+                format!("Add the missing items and run 'vibe-ticket close {slug}' again"),
+                "Use --force to close anyway; the close is recorded in the audit log".to_string(),
+            ],
+            Self::OpenDependencies { slug, .. } => vec![
+                "Close the tickets it depends on first".to_string(),
+                format!("Run 'vibe-ticket close {slug} --force' to close it anyway"),
+            ],
             Self::SpecNotFound { id } => vec![
                 format!("Check if specification '{}' exists", id),
                 "Run 'vibe-ticket spec list' to see all specifications".to_string(),
@@ -1,13 +1,32 @@
 //! Event system for CLI-MCP synchronization
+//!
+//! CLI commands report the changes they make through [`emit_cli_event`],
+//! which runs the hooks of the project's plugins and posts the event to its
+//! webhooks. The tools of the MCP server report theirs through
+//! [`emit_event`], which notifies the clients subscribed to the changed
+//! resources and, through a handler registered at startup, the webhooks.
+//!
+//! Changes made by other processes reach the MCP server through the storage
+//! event log (see [`crate::storage::StorageEvent`]), which only tells which
+//! ticket changed. The server hands them to [`EventBus::emit_external_change`]
+//! so that subscribers are notified; they are not posted to webhooks again.
 
 use crate::core::{Status, Task, Ticket, TicketId};
 use crate::error::Result;
+use crate::plugins::PluginRegistry;
+use crate::specs::{SpecDocumentType, SpecMetadata};
+use serde_json::{Value, json};
+use std::path::Path;
 #[cfg(feature = "mcp")]
 use std::sync::Arc;
 #[cfg(feature = "mcp")]
 use tokio::sync::RwLock;
 
 /// Events that can be emitted by CLI operations
+///
+/// Besides changes to tickets, the lifecycle of specs is reported: a spec
+/// was created, one of its phases was completed or approved, or it became
+/// the active spec.
 #[derive(Debug, Clone)]
 pub enum TicketEvent {
     /// A new ticket was created
@@ -24,6 +43,133 @@ pub enum TicketEvent {
     TaskRemoved(TicketId, String), // ticket id, task id
     /// Ticket status changed
     StatusChanged(TicketId, Status, Status), // id, old status, new status
+    /// A new spec was created
+    SpecCreated(SpecMetadata),
+    /// A phase of a spec was marked as complete
+    SpecPhaseCompleted(String, SpecDocumentType), // spec id, phase
+    /// A phase of a spec received its required approvals
+    SpecPhaseApproved(String, SpecDocumentType), // spec id, phase
+    /// A spec became the active spec
+    SpecActivated(String), // spec id
+}
+
+impl TicketEvent {
+    /// ID of the ticket the event is about, or `None` for spec events
+    pub const fn ticket_id(&self) -> Option<&TicketId> {
+        match self {
+            Self::Created(ticket) | Self::Updated(ticket) => Some(&ticket.id),
+            Self::Closed(id, _)
+            | Self::TaskAdded(id, _)
+            | Self::TaskCompleted(id, _)
+            | Self::TaskRemoved(id, _)
+            | Self::StatusChanged(id, _, _) => Some(id),
+            Self::SpecCreated(_)
+            | Self::SpecPhaseCompleted(..)
+            | Self::SpecPhaseApproved(..)
+            | Self::SpecActivated(_) => None,
+        }
+    }
+
+    /// ID of the spec the event is about, or `None` for ticket events
+    pub fn spec_id(&self) -> Option<&str> {
+        match self {
+            Self::SpecCreated(metadata) => Some(&metadata.id),
+            Self::SpecPhaseCompleted(id, _)
+            | Self::SpecPhaseApproved(id, _)
+            | Self::SpecActivated(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Kinds of events, as named by [`TicketEvent::kind`]
+    pub const KINDS: [&str; 11] = [
+        "created",
+        "updated",
+        "closed",
+        "task_added",
+        "task_completed",
+        "task_removed",
+        "status_changed",
+        "spec_created",
+        "spec_phase_completed",
+        "spec_phase_approved",
+        "spec_activated",
+    ];
+
+    /// Kind of the event, as given in the `kind` field of [`TicketEvent::to_json`]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "created",
+            Self::Updated(_) => "updated",
+            Self::Closed(..) => "closed",
+            Self::TaskAdded(..) => "task_added",
+            Self::TaskCompleted(..) => "task_completed",
+            Self::TaskRemoved(..) => "task_removed",
+            Self::StatusChanged(..) => "status_changed",
+            Self::SpecCreated(_) => "spec_created",
+            Self::SpecPhaseCompleted(..) => "spec_phase_completed",
+            Self::SpecPhaseApproved(..) => "spec_phase_approved",
+            Self::SpecActivated(_) => "spec_activated",
+        }
+    }
+
+    /// JSON description of the event handed to plugin hooks
+    pub fn to_json(&self) -> Value {
+        let kind = self.kind();
+        if let Some(spec_id) = self.spec_id() {
+            return match self {
+                Self::SpecCreated(metadata) => {
+                    json!({"kind": kind, "spec_id": spec_id, "spec": metadata})
+                },
+                Self::SpecPhaseCompleted(_, phase) | Self::SpecPhaseApproved(_, phase) => {
+                    json!({"kind": kind, "spec_id": spec_id, "phase": phase})
+                },
+                _ => json!({"kind": kind, "spec_id": spec_id}),
+            };
+        }
+
+        let id = self.ticket_id().map(ToString::to_string);
+        match self {
+            Self::Created(ticket) | Self::Updated(ticket) => {
+                json!({"kind": kind, "ticket_id": id, "ticket": ticket})
+            },
+            Self::Closed(_, message) => {
+                json!({"kind": kind, "ticket_id": id, "message": message})
+            },
+            Self::TaskAdded(_, task) => json!({"kind": kind, "ticket_id": id, "task": task}),
+            Self::TaskCompleted(_, task_id) | Self::TaskRemoved(_, task_id) => {
+                json!({"kind": kind, "ticket_id": id, "task_id": task_id})
+            },
+            Self::StatusChanged(_, from, to) => json!({
+                "kind": kind,
+                "ticket_id": id,
+                "from": from,
+                "to": to,
+            }),
+            _ => json!({"kind": kind}),
+        }
+    }
+}
+
+/// Reports an event of a CLI command to the hooks of the project's plugins
+/// and to its webhooks
+///
+/// Hooks react to a change that has already been saved, so failures are
+/// printed as warnings and never fail the command.
+pub fn emit_cli_event(project_root: &Path, event: &TicketEvent) {
+    let registry = match PluginRegistry::load(project_root) {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("Warning: failed to load plugins: {e}");
+            return;
+        },
+    };
+
+    for (plugin, error) in registry.dispatch(event) {
+        eprintln!("Warning: plugin '{plugin}' hook failed: {error}");
+    }
+
+    crate::integration::webhooks::emit(project_root, event);
 }
 
 /// Trait for handling ticket events
@@ -32,6 +178,22 @@ pub enum TicketEvent {
 pub trait EventHandler: Send + Sync {
     /// Handle a ticket event
     async fn handle_event(&self, event: TicketEvent) -> Result<()>;
+
+    /// Handle a change another process made to a ticket
+    ///
+    /// Ignored by default: the process that made the change has already
+    /// reported it as a [`TicketEvent`].
+    async fn handle_external_change(&self, _ticket_id: TicketId) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether the handler still wants events
+    ///
+    /// Inactive handlers, such as those of closed MCP sessions, are dropped
+    /// by the event bus.
+    fn is_active(&self) -> bool {
+        true
+    }
 }
 
 /// Event bus for distributing events to handlers
@@ -57,7 +219,11 @@ impl EventBus {
 
     /// Emit an event to all registered handlers
     pub async fn emit(&self, event: TicketEvent) -> Result<()> {
-        let handlers = self.handlers.read().await;
+        let handlers = {
+            let mut handlers = self.handlers.write().await;
+            handlers.retain(|handler| handler.is_active());
+            handlers.clone()
+        };
 
         // Process events asynchronously but wait for all to complete
         let mut tasks = Vec::new();
@@ -81,6 +247,16 @@ impl EventBus {
 
         Ok(())
     }
+
+    /// Report a change another process made to a ticket to all handlers
+    pub async fn emit_external_change(&self, ticket_id: TicketId) {
+        let handlers = Box::pin(self.handlers.read()).await.clone();
+        for handler in handlers.iter().filter(|handler| handler.is_active()) {
+            if let Err(e) = handler.handle_external_change(ticket_id.clone()).await {
+                eprintln!("Event handler error: {e}");
+            }
+        }
+    }
 }
 
 #[cfg(feature = "mcp")]
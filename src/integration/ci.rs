@@ -0,0 +1,343 @@
+//! CI report ingestion
+//!
+//! Converts failing tests from `JUnit` XML reports and findings from SARIF
+//! reports into tasks on a ticket. Each finding carries a fingerprint that is
+//! stored on the task, so repeated reports do not create duplicates and
+//! findings that disappear from a later report complete their task.
+
+use crate::core::{Task, Ticket};
+use crate::error::{Result, VibeTicketError};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Supported report formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// `JUnit` XML test report
+    Junit,
+    /// SARIF static analysis report
+    Sarif,
+}
+
+impl ReportFormat {
+    /// Detects the format from a file name and its content
+    pub fn detect(path: &Path, content: &str) -> Result<Self> {
+        let name = path.to_string_lossy().to_lowercase();
+        if name.contains(".sarif") {
+            return Ok(Self::Sarif);
+        }
+        if Path::new(&name).extension().and_then(|e| e.to_str()) == Some("xml") {
+            return Ok(Self::Junit);
+        }
+
+        match content.trim_start().chars().next() {
+            Some('<') => Ok(Self::Junit),
+            Some('{') => Ok(Self::Sarif),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Cannot detect report format of {}, use --format",
+                path.display()
+            ))),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "junit" | "xml" => Ok(Self::Junit),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Unknown report format '{s}'. Valid formats: junit, sarif"
+            ))),
+        }
+    }
+}
+
+/// A failing test or analysis finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Stable identifier of the finding across reports
+    pub fingerprint: String,
+
+    /// Task title describing the finding
+    pub title: String,
+}
+
+/// Parsed CI report
+#[derive(Debug, Clone)]
+pub struct CiReport {
+    /// Fingerprint prefix shared by all findings of this report source
+    ///
+    /// Only tasks with this prefix are completed when their finding is gone,
+    /// so reports from different tools do not interfere.
+    pub source: String,
+
+    /// Findings contained in the report
+    pub findings: Vec<Finding>,
+}
+
+impl CiReport {
+    /// Parses a report in the given format
+    pub fn parse(format: ReportFormat, content: &str) -> Result<Self> {
+        match format {
+            ReportFormat::Junit => parse_junit(content),
+            ReportFormat::Sarif => parse_sarif(content),
+        }
+    }
+}
+
+/// Changes applied to a ticket by a report
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SyncSummary {
+    /// Titles of tasks created for new findings
+    pub added: Vec<String>,
+
+    /// Titles of completed tasks reopened because their finding came back
+    pub reopened: Vec<String>,
+
+    /// Titles of tasks completed because their finding is gone
+    pub resolved: Vec<String>,
+
+    /// Number of findings that already had an open task
+    pub unchanged: usize,
+}
+
+impl SyncSummary {
+    /// Returns true if the report changed the ticket
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.reopened.is_empty() && self.resolved.is_empty())
+    }
+}
+
+/// Synchronizes the ticket's CI tasks with a report
+pub fn apply_report(ticket: &mut Ticket, report: &CiReport) -> SyncSummary {
+    let mut summary = SyncSummary::default();
+    let mut seen = HashSet::new();
+
+    for finding in &report.findings {
+        if !seen.insert(finding.fingerprint.as_str()) {
+            continue;
+        }
+
+        let existing = ticket
+            .tasks
+            .iter_mut()
+            .find(|t| t.fingerprint.as_deref() == Some(finding.fingerprint.as_str()));
+        match existing {
+            Some(task) if task.completed => {
+                task.uncomplete();
+                summary.reopened.push(task.title.clone());
+            },
+            Some(_) => summary.unchanged += 1,
+            None => {
+                let mut task = Task::new(finding.title.clone());
+                task.fingerprint = Some(finding.fingerprint.clone());
+                ticket.tasks.push(task);
+                summary.added.push(finding.title.clone());
+            },
+        }
+    }
+
+    for task in &mut ticket.tasks {
+        let Some(fingerprint) = task.fingerprint.as_deref() else {
+            continue;
+        };
+        if !task.completed && fingerprint.starts_with(&report.source) && !seen.contains(fingerprint)
+        {
+            task.complete();
+            summary.resolved.push(task.title.clone());
+        }
+    }
+
+    summary
+}
+
+/// Parses failing and erroring test cases from a `JUnit` XML report
+fn parse_junit(content: &str) -> Result<CiReport> {
+    let document = roxmltree::Document::parse(content)
+        .map_err(|e| VibeTicketError::InvalidInput(format!("Invalid JUnit report: {e}")))?;
+
+    let findings = document
+        .descendants()
+        .filter(|node| node.has_tag_name("testcase"))
+        .filter(|case| {
+            case.children()
+                .any(|c| c.has_tag_name("failure") || c.has_tag_name("error"))
+        })
+        .map(|case| {
+            let name = case.attribute("name").unwrap_or("unnamed");
+            let path = match case.attribute("classname") {
+                Some(class) if !class.is_empty() => format!("{class}::{name}"),
+                _ => name.to_string(),
+            };
+            Finding {
+                fingerprint: format!("junit:{path}"),
+                title: format!("Fix failing test {path}"),
+            }
+        })
+        .collect();
+
+    Ok(CiReport {
+        source: "junit:".to_string(),
+        findings,
+    })
+}
+
+/// Parses results from the runs of a SARIF report
+fn parse_sarif(content: &str) -> Result<CiReport> {
+    let sarif: Value = serde_json::from_str(content)
+        .map_err(|e| VibeTicketError::InvalidInput(format!("Invalid SARIF report: {e}")))?;
+    let runs = sarif["runs"]
+        .as_array()
+        .ok_or_else(|| VibeTicketError::InvalidInput("SARIF report has no runs".to_string()))?;
+
+    let mut findings = Vec::new();
+    for run in runs {
+        let tool = run["tool"]["driver"]["name"].as_str().unwrap_or("sarif");
+        for result in run["results"].as_array().into_iter().flatten() {
+            if result["level"].as_str() == Some("none") {
+                continue;
+            }
+            findings.push(sarif_finding(tool, result));
+        }
+    }
+
+    Ok(CiReport {
+        source: "sarif:".to_string(),
+        findings,
+    })
+}
+
+/// Builds a finding from a single SARIF result
+fn sarif_finding(tool: &str, result: &Value) -> Finding {
+    let rule = result["ruleId"].as_str().unwrap_or("finding");
+    let message = result["message"]["text"]
+        .as_str()
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default();
+    let location = &result["locations"][0]["physicalLocation"];
+    let uri = location["artifactLocation"]["uri"].as_str();
+    let line = location["region"]["startLine"].as_u64();
+
+    let key = result["partialFingerprints"]
+        .as_object()
+        .and_then(|prints| prints.values().find_map(Value::as_str))
+        .map_or_else(
+            || {
+                format!(
+                    "{rule}:{}:{}",
+                    uri.unwrap_or_default(),
+                    line.unwrap_or_default()
+                )
+            },
+            ToString::to_string,
+        );
+
+    let location = match (uri, line) {
+        (Some(uri), Some(line)) => format!(" ({uri}:{line})"),
+        (Some(uri), None) => format!(" ({uri})"),
+        _ => String::new(),
+    };
+
+    Finding {
+        fingerprint: format!("sarif:{tool}:{key}"),
+        title: format!("[{tool}] {rule}: {message}{location}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUNIT: &str = r#"<?xml version="1.0"?>
+<testsuites>
+  <testsuite name="auth">
+    <testcase classname="auth::login" name="accepts_valid"/>
+    <testcase classname="auth::login" name="rejects_expired">
+      <failure message="assertion failed"/>
+    </testcase>
+    <testcase classname="auth::session" name="refresh">
+      <error message="panicked"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+    #[test]
+    fn test_parse_junit() {
+        let report = CiReport::parse(ReportFormat::Junit, JUNIT).unwrap();
+        let fingerprints: Vec<_> = report.findings.iter().map(|f| &f.fingerprint).collect();
+        assert_eq!(
+            fingerprints,
+            vec![
+                "junit:auth::login::rejects_expired",
+                "junit:auth::session::refresh"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sarif() {
+        let sarif = r#"{
+            "runs": [{
+                "tool": { "driver": { "name": "clippy" } },
+                "results": [{
+                    "ruleId": "unused_variable",
+                    "message": { "text": "unused variable `x`" },
+                    "locations": [{ "physicalLocation": {
+                        "artifactLocation": { "uri": "src/main.rs" },
+                        "region": { "startLine": 12 }
+                    }}]
+                }]
+            }]
+        }"#;
+
+        let report = CiReport::parse(ReportFormat::Sarif, sarif).unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(
+            report.findings[0].fingerprint,
+            "sarif:clippy:unused_variable:src/main.rs:12"
+        );
+        assert_eq!(
+            report.findings[0].title,
+            "[clippy] unused_variable: unused variable `x` (src/main.rs:12)"
+        );
+    }
+
+    #[test]
+    fn test_apply_report_dedups_and_resolves() {
+        let mut ticket = Ticket::new("ci", "CI failures");
+        ticket.add_task("Manual task");
+
+        let first = CiReport::parse(ReportFormat::Junit, JUNIT).unwrap();
+        let summary = apply_report(&mut ticket, &first);
+        assert_eq!(summary.added.len(), 2);
+        assert_eq!(ticket.tasks.len(), 3);
+
+        let again = apply_report(&mut ticket, &first);
+        assert_eq!(again.unchanged, 2);
+        assert!(!again.has_changes());
+
+        // Only one test still fails
+        let second = CiReport {
+            source: "junit:".to_string(),
+            findings: first.findings[..1].to_vec(),
+        };
+        let summary = apply_report(&mut ticket, &second);
+        assert_eq!(
+            summary.resolved,
+            vec!["Fix failing test auth::session::refresh"]
+        );
+        assert!(!ticket.tasks[0].completed);
+
+        // The fixed test regresses
+        let summary = apply_report(&mut ticket, &first);
+        assert_eq!(summary.reopened.len(), 1);
+        assert_eq!(ticket.completed_tasks_count(), 0);
+    }
+}
@@ -0,0 +1,377 @@
+//! Import of GitHub and GitLab issues
+//!
+//! `vibe-ticket new --from-url <URL>` prefills a ticket from an issue, pull
+//! request or merge request. The URL is parsed into an [`IssueRef`], the
+//! item is fetched from the host's REST API as a [`RemoteIssue`], and the
+//! link back to it is stored in the ticket's [`ORIGIN_KEY`] metadata entry
+//! so the ticket can be synchronized later.
+//!
+//! Supported URLs:
+//!
+//! - `https://github.com/<owner>/<repo>/issues/<n>` and `.../pull/<n>`
+//! - `https://<host>/<group>/<project>/-/issues/<n>` and
+//!   `.../-/merge_requests/<n>` on gitlab.com or a self-hosted GitLab
+//!
+//! Private repositories need a token in `GITHUB_TOKEN` (or `GH_TOKEN`) for
+//! GitHub and `GITLAB_TOKEN` for GitLab.
+
+use std::fmt;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::Ticket;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Metadata key holding the origin of an imported ticket
+pub const ORIGIN_KEY: &str = "origin";
+
+/// Timeout of API requests
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Longest slug derived from an issue title
+const MAX_SLUG_LEN: usize = 50;
+
+/// Service hosting an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueHost {
+    /// github.com
+    GitHub,
+    /// gitlab.com or a self-hosted GitLab
+    GitLab,
+}
+
+/// Kind of item an issue URL points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// An issue
+    Issue,
+    /// A GitHub pull request or GitLab merge request
+    PullRequest,
+}
+
+/// Issue, pull request or merge request identified by its URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    /// Hosting service
+    pub host: IssueHost,
+    /// Scheme and host name, e.g. `https://gitlab.example.com`
+    pub base_url: String,
+    /// Repository path, e.g. `org/repo` or `group/subgroup/project`
+    pub project: String,
+    /// Issue or pull request number
+    pub number: u64,
+    /// Issue or pull request
+    pub kind: IssueKind,
+}
+
+/// Issue data fetched from the hosting service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteIssue {
+    /// Title
+    pub title: String,
+    /// Description in Markdown
+    pub body: String,
+    /// Labels
+    pub labels: Vec<String>,
+    /// State as reported by the service (e.g. `open`, `closed`, `merged`)
+    pub state: String,
+}
+
+impl IssueRef {
+    /// Parses the web URL of an issue, pull request or merge request
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is not one of the supported forms
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid = || {
+            VibeTicketError::InvalidInput(format!(
+                "Unsupported issue URL '{url}'. Use a GitHub issue or pull request URL or a \
+                 GitLab issue or merge request URL"
+            ))
+        };
+
+        let trimmed = url.trim().trim_end_matches('/');
+        let (scheme, rest) = trimmed.split_once("://").ok_or_else(invalid)?;
+        if scheme != "https" && scheme != "http" {
+            return Err(invalid());
+        }
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (host, path) = rest.split_once('/').ok_or_else(invalid)?;
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let base_url = format!("{scheme}://{host}");
+
+        // GitLab marks the end of the project path with `-`
+        if let Some(dash) = segments.iter().position(|s| *s == "-") {
+            let (kind, number) = match &segments[dash + 1..] {
+                ["issues", number] => (IssueKind::Issue, number),
+                ["merge_requests", number] => (IssueKind::PullRequest, number),
+                _ => return Err(invalid()),
+            };
+            if dash < 2 {
+                return Err(invalid());
+            }
+            return Ok(Self {
+                host: IssueHost::GitLab,
+                base_url,
+                project: segments[..dash].join("/"),
+                number: number.parse().map_err(|_| invalid())?,
+                kind,
+            });
+        }
+
+        match segments.as_slice() {
+            [owner, repo, kind @ ("issues" | "pull"), number] if host == "github.com" => Ok(Self {
+                host: IssueHost::GitHub,
+                base_url,
+                project: format!("{owner}/{repo}"),
+                number: number.parse().map_err(|_| invalid())?,
+                kind: if *kind == "pull" {
+                    IssueKind::PullRequest
+                } else {
+                    IssueKind::Issue
+                },
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// REST API URL of the item
+    pub fn api_url(&self) -> String {
+        match self.host {
+            // The issues endpoint also returns pull requests, with labels
+            IssueHost::GitHub => format!(
+                "https://api.github.com/repos/{}/issues/{}",
+                self.project, self.number
+            ),
+            IssueHost::GitLab => format!(
+                "{}/api/v4/projects/{}/{}/{}",
+                self.base_url,
+                self.project.replace('/', "%2F"),
+                match self.kind {
+                    IssueKind::Issue => "issues",
+                    IssueKind::PullRequest => "merge_requests",
+                },
+                self.number
+            ),
+        }
+    }
+
+    /// Web URL of the item in canonical form
+    pub fn web_url(&self) -> String {
+        match (self.host, self.kind) {
+            (IssueHost::GitHub, IssueKind::Issue) => {
+                format!("{}/{}/issues/{}", self.base_url, self.project, self.number)
+            },
+            (IssueHost::GitHub, IssueKind::PullRequest) => {
+                format!("{}/{}/pull/{}", self.base_url, self.project, self.number)
+            },
+            (IssueHost::GitLab, IssueKind::Issue) => {
+                format!(
+                    "{}/{}/-/issues/{}",
+                    self.base_url, self.project, self.number
+                )
+            },
+            (IssueHost::GitLab, IssueKind::PullRequest) => format!(
+                "{}/{}/-/merge_requests/{}",
+                self.base_url, self.project, self.number
+            ),
+        }
+    }
+
+    /// Fetches the item from the hosting service
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be read
+    pub fn fetch(&self) -> Result<RemoteIssue> {
+        let mut request = ureq::get(&self.api_url())
+            .timeout(FETCH_TIMEOUT)
+            .set("User-Agent", "vibe-ticket");
+        match self.host {
+            IssueHost::GitHub => {
+                request = request.set("Accept", "application/vnd.github+json");
+                if let Ok(token) =
+                    std::env::var("GITHUB_TOKEN").or_else(|_| std::env::var("GH_TOKEN"))
+                {
+                    request = request.set("Authorization", &format!("Bearer {token}"));
+                }
+            },
+            IssueHost::GitLab => {
+                if let Ok(token) = std::env::var("GITLAB_TOKEN") {
+                    request = request.set("PRIVATE-TOKEN", &token);
+                }
+            },
+        }
+
+        let json: Value = request
+            .call()
+            .map_err(|e| VibeTicketError::custom(format!("Failed to fetch {self}: {e}")))?
+            .into_json()
+            .with_context(|| format!("Failed to read {self}"))?;
+        RemoteIssue::from_json(self.host, &json)
+    }
+
+    /// Metadata recording the origin of a ticket imported from this item
+    pub fn origin(&self, issue: &RemoteIssue) -> Value {
+        serde_json::json!({
+            "url": self.web_url(),
+            "host": self.host,
+            "project": self.project,
+            "kind": self.kind,
+            "number": self.number,
+            "state": issue.state,
+            "synced_at": Utc::now().to_rfc3339(),
+        })
+    }
+}
+
+impl fmt::Display for IssueRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match (self.host, self.kind) {
+            (IssueHost::GitLab, IssueKind::PullRequest) => '!',
+            _ => '#',
+        };
+        write!(f, "{}{marker}{}", self.project, self.number)
+    }
+}
+
+impl RemoteIssue {
+    /// Reads an issue from a GitHub or GitLab API response
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response has no title
+    pub fn from_json(host: IssueHost, json: &Value) -> Result<Self> {
+        let title = json["title"]
+            .as_str()
+            .ok_or_else(|| VibeTicketError::custom("Issue response has no title"))?;
+        let body_key = match host {
+            IssueHost::GitHub => "body",
+            IssueHost::GitLab => "description",
+        };
+        // GitHub labels are objects, GitLab labels are names
+        let labels = json["labels"]
+            .as_array()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label["name"].as_str().or_else(|| label.as_str()))
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let state = match json["pull_request"]["merged_at"].as_str() {
+            Some(_) => "merged",
+            None => json["state"].as_str().unwrap_or("open"),
+        };
+
+        Ok(Self {
+            title: title.trim().to_string(),
+            body: json[body_key].as_str().unwrap_or_default().to_string(),
+            labels,
+            state: state.to_string(),
+        })
+    }
+
+    /// Slug for a ticket created from the issue, e.g. `42-fix-login-crash`
+    pub fn slug(&self, number: u64) -> String {
+        let mut slug = format!("{number}-{}", crate::cli::slugify(&self.title));
+        if slug.len() > MAX_SLUG_LEN {
+            slug.truncate(MAX_SLUG_LEN);
+        }
+        slug.trim_end_matches('-').to_string()
+    }
+
+    /// Labels as ticket tags, lowercased with spaces replaced by dashes
+    pub fn tags(&self) -> Vec<String> {
+        self.labels
+            .iter()
+            .map(|label| label.trim().to_lowercase().replace(' ', "-"))
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+}
+
+/// Returns the URL of the item a ticket was imported from, if any
+pub fn origin_url(ticket: &Ticket) -> Option<&str> {
+    ticket.metadata.get(ORIGIN_KEY)?["url"].as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_urls() {
+        let github = IssueRef::parse("https://github.com/org/repo/issues/42").unwrap();
+        assert_eq!(github.host, IssueHost::GitHub);
+        assert_eq!(github.project, "org/repo");
+        assert_eq!(github.kind, IssueKind::Issue);
+        assert_eq!(
+            github.api_url(),
+            "https://api.github.com/repos/org/repo/issues/42"
+        );
+        assert_eq!(github.to_string(), "org/repo#42");
+
+        let pull = IssueRef::parse("https://github.com/org/repo/pull/7#issuecomment-1").unwrap();
+        assert_eq!(pull.kind, IssueKind::PullRequest);
+        assert_eq!(pull.web_url(), "https://github.com/org/repo/pull/7");
+
+        let gitlab =
+            IssueRef::parse("https://gitlab.example.com/group/sub/app/-/merge_requests/9/")
+                .unwrap();
+        assert_eq!(gitlab.host, IssueHost::GitLab);
+        assert_eq!(gitlab.project, "group/sub/app");
+        assert_eq!(gitlab.kind, IssueKind::PullRequest);
+        assert_eq!(
+            gitlab.api_url(),
+            "https://gitlab.example.com/api/v4/projects/group%2Fsub%2Fapp/merge_requests/9"
+        );
+        assert_eq!(gitlab.to_string(), "group/sub/app!9");
+
+        for url in [
+            "github.com/org/repo/issues/1",
+            "https://github.com/org/repo",
+            "https://github.com/org/repo/issues/abc",
+            "https://example.com/org/repo/issues/1",
+            "https://gitlab.com/app/-/issues/1",
+        ] {
+            assert!(IssueRef::parse(url).is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_read_responses() {
+        let github = serde_json::json!({
+            "title": " Login crashes on Safari ",
+            "body": "Steps to reproduce",
+            "state": "closed",
+            "labels": [{"name": "bug"}, {"name": "Good First Issue"}],
+            "pull_request": {"merged_at": "2025-07-01T00:00:00Z"},
+        });
+        let issue = RemoteIssue::from_json(IssueHost::GitHub, &github).unwrap();
+        assert_eq!(issue.title, "Login crashes on Safari");
+        assert_eq!(issue.state, "merged");
+        assert_eq!(issue.tags(), vec!["bug", "good-first-issue"]);
+        assert_eq!(issue.slug(42), "42-login-crashes-on-safari");
+
+        let gitlab = serde_json::json!({
+            "title": "Add dark mode",
+            "description": "As a user...",
+            "state": "opened",
+            "labels": ["ui", "feature"],
+        });
+        let issue = RemoteIssue::from_json(IssueHost::GitLab, &gitlab).unwrap();
+        assert_eq!(issue.body, "As a user...");
+        assert_eq!(issue.labels, vec!["ui", "feature"]);
+        assert_eq!(issue.state, "opened");
+
+        assert!(RemoteIssue::from_json(IssueHost::GitHub, &serde_json::json!({})).is_err());
+    }
+}
@@ -1,5 +1,11 @@
 //! CLI-MCP integration module
 
+pub mod ci;
+pub mod issues;
+pub mod outbox;
+pub mod presence;
+pub mod webhooks;
+
 use crate::core::{Status, Ticket, TicketId};
 use crate::storage::FileStorage;
 use std::sync::Arc;
@@ -0,0 +1,451 @@
+//! Persistent outbox for integration deliveries
+//!
+//! Webhook, Slack and GitHub sync calls can fail when the machine is offline.
+//! Instead of dropping the notification, the event is written to
+//! `.vibe-ticket/outbox/` and retried on the next command run or through
+//! `vibe-ticket outbox flush`. An entry is only removed after a successful
+//! delivery, which gives at-least-once delivery semantics.
+//!
+//! Automatic retries back off exponentially per entry, from
+//! [`RETRY_BASE_DELAY`] up to [`RETRY_MAX_DELAY`]. Every attempt, successful
+//! or not, is appended to `.vibe-ticket/deliveries.log` as a JSON line.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write as IoWrite;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Timeout applied to each HTTP delivery attempt
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the first automatic retry of a failed delivery
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+
+/// Longest delay between automatic retries
+pub const RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// File of the delivery log, in the `.vibe-ticket` directory
+pub const DELIVERY_LOG_FILE: &str = "deliveries.log";
+
+/// A pending integration delivery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// Unique identifier of the entry
+    pub id: String,
+
+    /// Destination URL of the delivery
+    pub target: String,
+
+    /// Event kind (e.g. `ticket.created`)
+    pub event: String,
+
+    /// JSON payload sent to the target
+    pub payload: Value,
+
+    /// Additional HTTP headers, such as a payload signature
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// When the event was first queued
+    pub created_at: DateTime<Utc>,
+
+    /// Number of failed delivery attempts
+    #[serde(default)]
+    pub attempts: u32,
+
+    /// Error reported by the last failed attempt
+    #[serde(default)]
+    pub last_error: Option<String>,
+
+    /// Earliest time of the next automatic retry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxEntry {
+    /// Creates a new entry for the given target, event and payload
+    pub fn new(target: impl Into<String>, event: impl Into<String>, payload: Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            target: target.into(),
+            event: event.into(),
+            payload,
+            headers: BTreeMap::new(),
+            created_at: Utc::now(),
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: None,
+        }
+    }
+
+    /// Adds an HTTP header sent with the payload
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Whether an automatic retry is due
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt_at.is_none_or(|at| at <= now)
+    }
+
+    /// Records a failed attempt and schedules the next retry
+    fn fail(&mut self, error: &VibeTicketError) {
+        self.attempts += 1;
+        self.last_error = Some(error.to_string());
+        let delay = chrono::Duration::from_std(retry_delay(self.attempts))
+            .unwrap_or_else(|_| chrono::Duration::hours(1));
+        self.next_attempt_at = Some(Utc::now() + delay);
+    }
+}
+
+/// Delay before the next automatic retry after `attempts` failed attempts
+///
+/// Doubles with every attempt, starting at [`RETRY_BASE_DELAY`] and capped
+/// at [`RETRY_MAX_DELAY`].
+pub fn retry_delay(attempts: u32) -> Duration {
+    let factor = 1u32 << attempts.saturating_sub(1).min(16);
+    RETRY_BASE_DELAY.saturating_mul(factor).min(RETRY_MAX_DELAY)
+}
+
+/// One delivery attempt, as recorded in the delivery log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    /// When the attempt was made
+    pub timestamp: DateTime<Utc>,
+
+    /// Outbox entry of the delivery
+    pub entry_id: String,
+
+    /// Event kind of the delivery
+    pub event: String,
+
+    /// Destination URL
+    pub target: String,
+
+    /// Number of the attempt, starting at 1
+    pub attempt: u32,
+
+    /// Error of a failed attempt; `None` if the delivery succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DeliveryRecord {
+    /// Whether the attempt succeeded
+    pub const fn delivered(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Summary of a flush run
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct FlushReport {
+    /// Number of entries delivered and removed from the outbox
+    pub delivered: usize,
+
+    /// Number of entries that failed again and remain queued
+    pub failed: usize,
+}
+
+/// File-based outbox stored under `.vibe-ticket/outbox/`
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    dir: PathBuf,
+    log_path: PathBuf,
+}
+
+impl Outbox {
+    /// Creates an outbox for the given `.vibe-ticket` directory
+    pub fn new(vibe_ticket_dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: vibe_ticket_dir.as_ref().join("outbox"),
+            log_path: vibe_ticket_dir.as_ref().join(DELIVERY_LOG_FILE),
+        }
+    }
+
+    /// Returns the directory holding queued entries
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// Persists an entry so it can be retried later
+    pub fn enqueue(&self, entry: &OutboxEntry) -> Result<()> {
+        fs::create_dir_all(&self.dir).context("Failed to create outbox directory")?;
+
+        let path = self.entry_path(&entry.id);
+        let json = serde_json::to_string_pretty(entry)?;
+        fs::write(&path, json)
+            .with_context(|| format!("Failed to write outbox entry to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Returns all queued entries, oldest first
+    pub fn pending(&self) -> Result<Vec<OutboxEntry>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir).context("Failed to read outbox directory")? {
+            let path = entry.context("Failed to read directory entry")?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            match serde_json::from_str::<OutboxEntry>(&content) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to load outbox entry from {}: {e}",
+                        path.display()
+                    );
+                },
+            }
+        }
+
+        entries.sort_by_key(|e| e.created_at);
+        Ok(entries)
+    }
+
+    /// Returns true if there are no queued entries
+    pub fn is_empty(&self) -> bool {
+        fs::read_dir(&self.dir).map_or(true, |mut entries| entries.next().is_none())
+    }
+
+    /// Removes a delivered entry from the outbox
+    pub fn remove(&self, id: &str) -> Result<()> {
+        let path = self.entry_path(id);
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove outbox entry {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Attempts a delivery and queues the entry if it fails
+    ///
+    /// Returns `true` if the entry was delivered immediately.
+    pub fn deliver_or_enqueue<F>(&self, mut entry: OutboxEntry, deliver: F) -> Result<bool>
+    where
+        F: Fn(&OutboxEntry) -> Result<()>,
+    {
+        match self.attempt(&entry, &deliver) {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                entry.fail(&e);
+                self.enqueue(&entry)?;
+                Ok(false)
+            },
+        }
+    }
+
+    /// Retries every queued entry, removing the ones that succeed
+    pub fn flush<F>(&self, deliver: F) -> Result<FlushReport>
+    where
+        F: Fn(&OutboxEntry) -> Result<()>,
+    {
+        self.flush_entries(self.pending()?, deliver)
+    }
+
+    /// Retries the queued entries whose backoff delay has passed
+    pub fn flush_due<F>(&self, deliver: F) -> Result<FlushReport>
+    where
+        F: Fn(&OutboxEntry) -> Result<()>,
+    {
+        let now = Utc::now();
+        let due = self
+            .pending()?
+            .into_iter()
+            .filter(|entry| entry.is_due(now))
+            .collect();
+        self.flush_entries(due, deliver)
+    }
+
+    fn flush_entries<F>(&self, entries: Vec<OutboxEntry>, deliver: F) -> Result<FlushReport>
+    where
+        F: Fn(&OutboxEntry) -> Result<()>,
+    {
+        let mut report = FlushReport::default();
+
+        for mut entry in entries {
+            match self.attempt(&entry, &deliver) {
+                Ok(()) => {
+                    self.remove(&entry.id)?;
+                    report.delivered += 1;
+                },
+                Err(e) => {
+                    entry.fail(&e);
+                    self.enqueue(&entry)?;
+                    report.failed += 1;
+                },
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Runs one delivery attempt and records it in the delivery log
+    ///
+    /// Unlike [`Outbox::deliver_or_enqueue`], a failed entry is not queued.
+    pub fn attempt<F>(&self, entry: &OutboxEntry, deliver: &F) -> Result<()>
+    where
+        F: Fn(&OutboxEntry) -> Result<()>,
+    {
+        let result = deliver(entry);
+        let record = DeliveryRecord {
+            timestamp: Utc::now(),
+            entry_id: entry.id.clone(),
+            event: entry.event.clone(),
+            target: entry.target.clone(),
+            attempt: entry.attempts + 1,
+            error: result.as_ref().err().map(ToString::to_string),
+        };
+        // The log is informational; failing to write it must not turn a
+        // delivery into a failure
+        if let Err(e) = self.append_log(&record) {
+            tracing::debug!("Failed to write delivery log: {e}");
+        }
+        result
+    }
+
+    fn append_log(&self, record: &DeliveryRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("Failed to append to {}", self.log_path.display()))?;
+        Ok(())
+    }
+
+    /// Returns the last `limit` records of the delivery log, oldest first
+    pub fn log(&self, limit: usize) -> Result<Vec<DeliveryRecord>> {
+        if !self.log_path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.log_path)
+            .with_context(|| format!("Failed to read {}", self.log_path.display()))?;
+        let records: Vec<DeliveryRecord> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let skip = records.len().saturating_sub(limit);
+        Ok(records.into_iter().skip(skip).collect())
+    }
+}
+
+/// Delivers an entry by sending its payload as a JSON `POST` to the target URL
+///
+/// The payload is sent in its compact serialization, the form the headers
+/// of the entry (e.g. signatures) were computed over.
+pub fn http_deliver(entry: &OutboxEntry) -> Result<()> {
+    let body = serde_json::to_string(&entry.payload)?;
+    let mut request = ureq::post(&entry.target)
+        .timeout(DELIVERY_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .set("X-Vibe-Ticket-Event", &entry.event);
+    for (name, value) in &entry.headers {
+        request = request.set(name, value);
+    }
+    request.send_string(&body).map_err(|e| {
+        VibeTicketError::custom(format!("Delivery to {} failed: {e}", entry.target))
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_failed_delivery_is_queued_and_flushed() {
+        let temp_dir = TempDir::new().unwrap();
+        let outbox = Outbox::new(temp_dir.path());
+        assert!(outbox.is_empty());
+
+        let entry = OutboxEntry::new("http://localhost/hook", "ticket.created", json!({"a": 1}));
+        let delivered = outbox
+            .deliver_or_enqueue(entry, |_| Err(VibeTicketError::custom("offline")))
+            .unwrap();
+        assert!(!delivered);
+
+        let pending = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].last_error.as_deref(), Some("offline"));
+        assert!(!pending[0].is_due(Utc::now()));
+
+        // Automatic retries wait for the backoff delay
+        let report = outbox.flush_due(|_| Ok(())).unwrap();
+        assert_eq!(report, FlushReport::default());
+
+        // Still offline: the entry stays queued
+        let report = outbox
+            .flush(|_| Err(VibeTicketError::custom("offline")))
+            .unwrap();
+        assert_eq!(
+            report,
+            FlushReport {
+                delivered: 0,
+                failed: 1
+            }
+        );
+        assert_eq!(outbox.pending().unwrap()[0].attempts, 2);
+
+        // Back online: the entry is delivered and removed
+        let report = outbox.flush(|_| Ok(())).unwrap();
+        assert_eq!(
+            report,
+            FlushReport {
+                delivered: 1,
+                failed: 0
+            }
+        );
+        assert!(outbox.pending().unwrap().is_empty());
+
+        let log = outbox.log(10).unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[0].attempt, 1);
+        assert!(!log[1].delivered());
+        assert!(log[2].delivered());
+        assert_eq!(log[2].attempt, 3);
+        assert_eq!(outbox.log(1).unwrap()[0].attempt, 3);
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(2), RETRY_BASE_DELAY * 2);
+        assert_eq!(retry_delay(3), RETRY_BASE_DELAY * 4);
+        assert_eq!(retry_delay(40), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_successful_delivery_is_not_queued() {
+        let temp_dir = TempDir::new().unwrap();
+        let outbox = Outbox::new(temp_dir.path());
+
+        let entry = OutboxEntry::new("http://localhost/hook", "ticket.closed", json!({}));
+        assert!(outbox.deliver_or_enqueue(entry, |_| Ok(())).unwrap());
+        assert!(outbox.pending().unwrap().is_empty());
+    }
+}
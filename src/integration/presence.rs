@@ -0,0 +1,295 @@
+//! Presence of MCP sessions on tickets
+//!
+//! While an MCP server runs, each of its sessions records which tickets its
+//! tool calls touched, and whether they only read them or changed them, in
+//! `.vibe-ticket/presence/<session>.json`. Commands such as `show` read these
+//! heartbeats to hint at who else is working on a ticket ("alice's agent
+//! (claude-code) edited this 2m ago"), so that several people and assistants
+//! sharing a repository can stay out of each other's way.
+//!
+//! Heartbeats are advisory: they are written on a best-effort basis, and
+//! sessions that stopped sending them are forgotten after [`PRESENCE_TTL`].
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::TicketId;
+use crate::error::{ErrorContext, Result};
+
+/// Directory of the session files, within `.vibe-ticket`
+pub const PRESENCE_DIR: &str = "presence";
+
+/// Time after its last heartbeat until a session is no longer shown
+pub const PRESENCE_TTL: Duration = Duration::minutes(15);
+
+/// What a session did with a ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Activity {
+    /// Read the ticket
+    Viewing,
+    /// Changed the ticket
+    Editing,
+}
+
+/// Latest activity of a session on a ticket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    /// When the session last read or changed the ticket
+    pub seen_at: DateTime<Utc>,
+
+    /// When the session last changed the ticket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+/// A session of an MCP server and the tickets it touched
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// Unique ID of the session
+    pub id: String,
+
+    /// User running the server
+    pub user: String,
+
+    /// Name of the MCP client, such as `claude-code`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+
+    /// Heartbeats by ticket ID
+    #[serde(default)]
+    pub tickets: BTreeMap<String, Heartbeat>,
+}
+
+impl Session {
+    /// Creates a session of `user` that has not touched any ticket yet
+    pub fn new(user: impl Into<String>, client: Option<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            user: user.into(),
+            client,
+            tickets: BTreeMap::new(),
+        }
+    }
+
+    /// Who the session acts for, as shown in presence hints
+    pub fn label(&self) -> String {
+        self.client.as_ref().map_or_else(
+            || format!("{}'s agent", self.user),
+            |client| format!("{}'s agent ({client})", self.user),
+        )
+    }
+
+    /// Records `activity` on `ticket` at `now`
+    pub fn touch(&mut self, ticket: &TicketId, activity: Activity, now: DateTime<Utc>) {
+        let heartbeat = self.tickets.entry(ticket.to_string()).or_insert(Heartbeat {
+            seen_at: now,
+            edited_at: None,
+        });
+        heartbeat.seen_at = now;
+        if activity == Activity::Editing {
+            heartbeat.edited_at = Some(now);
+        }
+    }
+
+    /// Time of the last heartbeat of the session
+    fn last_seen(&self) -> Option<DateTime<Utc>> {
+        self.tickets
+            .values()
+            .map(|heartbeat| heartbeat.seen_at)
+            .max()
+    }
+}
+
+/// Presence of another session on a ticket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sighting {
+    /// Session ID
+    pub session: String,
+    /// User running the session
+    pub user: String,
+    /// MCP client of the session
+    pub client: Option<String>,
+    /// Whether the last touch was a change
+    pub activity: Activity,
+    /// Time of the last touch
+    pub at: DateTime<Utc>,
+    /// Human-readable hint, such as "alice's agent edited this 2m ago"
+    pub hint: String,
+}
+
+impl Sighting {
+    fn new(session: &Session, heartbeat: &Heartbeat, now: DateTime<Utc>) -> Self {
+        let (activity, at) = match heartbeat.edited_at {
+            Some(edited_at) if edited_at >= heartbeat.seen_at - PRESENCE_TTL => {
+                (Activity::Editing, edited_at)
+            },
+            _ => (Activity::Viewing, heartbeat.seen_at),
+        };
+        let verb = match activity {
+            Activity::Editing => "edited",
+            Activity::Viewing => "viewed",
+        };
+        Self {
+            session: session.id.clone(),
+            user: session.user.clone(),
+            client: session.client.clone(),
+            activity,
+            at,
+            hint: format!("{} {verb} this {}", session.label(), elapsed(now - at)),
+        }
+    }
+}
+
+/// Session files of a project
+#[derive(Debug, Clone)]
+pub struct Presence {
+    dir: PathBuf,
+}
+
+impl Presence {
+    /// Presence of the project whose data lives in `vibe_dir`
+    pub fn new(vibe_dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: vibe_dir.as_ref().join(PRESENCE_DIR),
+        }
+    }
+
+    fn path(&self, session: &str) -> PathBuf {
+        self.dir.join(format!("{session}.json"))
+    }
+
+    /// Writes the heartbeats of `session`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session file cannot be written.
+    pub fn save(&self, session: &Session) -> Result<()> {
+        fs::create_dir_all(&self.dir).context("Failed to create presence directory")?;
+        let json = serde_json::to_string_pretty(session)?;
+        fs::write(self.path(&session.id), json).context("Failed to write presence file")
+    }
+
+    /// Removes the session file of `session`, at the end of the session
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be removed.
+    pub fn remove(&self, session: &str) -> Result<()> {
+        match fs::remove_file(self.path(session)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).context("Failed to remove presence file")
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Sessions with a heartbeat within [`PRESENCE_TTL`] of `now`
+    ///
+    /// Files of expired sessions are removed, and unreadable ones skipped.
+    pub fn sessions(&self, now: DateTime<Utc>) -> Vec<Session> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut sessions = Vec::new();
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(session) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<Session>(&json).ok())
+            else {
+                continue;
+            };
+            if session
+                .last_seen()
+                .is_none_or(|seen| now - seen > PRESENCE_TTL)
+            {
+                let _ = fs::remove_file(&path);
+            } else {
+                sessions.push(session);
+            }
+        }
+        sessions
+    }
+
+    /// Live sessions that touched `ticket`, most recent first
+    ///
+    /// The session `except`, usually the caller's own, is left out.
+    pub fn on_ticket(&self, ticket: &TicketId, except: Option<&str>) -> Vec<Sighting> {
+        let now = Utc::now();
+        let key = ticket.to_string();
+        let mut sightings: Vec<Sighting> = self
+            .sessions(now)
+            .iter()
+            .filter(|session| except != Some(session.id.as_str()))
+            .filter_map(|session| {
+                let heartbeat = session.tickets.get(&key)?;
+                (now - heartbeat.seen_at <= PRESENCE_TTL)
+                    .then(|| Sighting::new(session, heartbeat, now))
+            })
+            .collect();
+        sightings.sort_by_key(|sighting| std::cmp::Reverse(sighting.at));
+        sightings
+    }
+}
+
+/// Short description of an elapsed time, such as "2m ago"
+fn elapsed(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", seconds / 60),
+        _ => format!("{}h ago", seconds / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sightings() {
+        let temp_dir = TempDir::new().unwrap();
+        let presence = Presence::new(temp_dir.path());
+        let ticket = TicketId::new();
+        let now = Utc::now();
+
+        let mut alice = Session::new("alice", Some("claude-code".to_string()));
+        alice.touch(&ticket, Activity::Editing, now - Duration::minutes(3));
+        alice.touch(&ticket, Activity::Viewing, now - Duration::minutes(2));
+        presence.save(&alice).unwrap();
+
+        let mut bob = Session::new("bob", None);
+        bob.touch(&ticket, Activity::Viewing, now);
+        presence.save(&bob).unwrap();
+
+        let mut gone = Session::new("carol", None);
+        gone.touch(&ticket, Activity::Editing, now - Duration::hours(1));
+        presence.save(&gone).unwrap();
+
+        let sightings = presence.on_ticket(&ticket, None);
+        assert_eq!(sightings.len(), 2);
+        assert_eq!(sightings[0].hint, "bob's agent viewed this just now");
+        assert_eq!(sightings[1].activity, Activity::Editing);
+        assert_eq!(
+            sightings[1].hint,
+            "alice's agent (claude-code) edited this 3m ago"
+        );
+        // Expired sessions are cleaned up
+        assert!(!presence.path(&gone.id).exists());
+
+        assert_eq!(presence.on_ticket(&ticket, Some(&bob.id)).len(), 1);
+        assert!(presence.on_ticket(&TicketId::new(), None).is_empty());
+
+        presence.remove(&bob.id).unwrap();
+        presence.remove(&bob.id).unwrap();
+        assert_eq!(presence.on_ticket(&ticket, None).len(), 1);
+    }
+}
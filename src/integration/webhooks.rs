@@ -0,0 +1,177 @@
+//! Outgoing webhooks for ticket and spec events
+//!
+//! Each webhook in the `webhooks` section of the configuration receives the
+//! [`TicketEvent`]s it subscribes to, including the lifecycle events of
+//! specs, as JSON `POST` requests:
+//!
+//! ```json
+//! {
+//!   "id": "5f0c…",
+//!   "event": "status_changed",
+//!   "project": "my-project",
+//!   "timestamp": "2025-07-20T13:45:00Z",
+//!   "data": {"kind": "status_changed", "ticket_id": "…", "from": "todo", "to": "doing"}
+//! }
+//! ```
+//!
+//! With a secret, the compact JSON body is signed with HMAC-SHA256 and the
+//! signature sent as `X-Vibe-Ticket-Signature: sha256=<hex>`, so receivers
+//! can verify the sender. Deliveries go through the [`Outbox`]: failed posts
+//! are queued and retried with backoff, and every attempt is written to the
+//! delivery log.
+
+use std::path::Path;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha2::Sha256;
+
+use crate::config::{Config, WebhookConfig};
+use crate::error::Result;
+use crate::events::TicketEvent;
+use crate::integration::outbox::{Outbox, OutboxEntry, http_deliver};
+
+/// Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "X-Vibe-Ticket-Signature";
+
+/// Header carrying the ID of the delivery, unchanged across retries
+pub const DELIVERY_HEADER: &str = "X-Vibe-Ticket-Delivery";
+
+/// Event kind of the test delivery sent by `webhook test`
+pub const PING_EVENT: &str = "ping";
+
+/// Builds the payload posted for an event
+pub fn payload(event: &str, project: &str, data: &Value) -> Value {
+    json!({
+        "id": uuid::Uuid::new_v4().to_string(),
+        "event": event,
+        "project": project,
+        "timestamp": Utc::now(),
+        "data": data,
+    })
+}
+
+/// Signature of `body` with `secret`, as sent in [`SIGNATURE_HEADER`]
+///
+/// # Panics
+///
+/// Never panics: HMAC accepts keys of any length.
+pub fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+/// Builds the outbox entry delivering `payload` to `webhook`
+pub fn entry(webhook: &WebhookConfig, event: &str, payload: Value) -> Result<OutboxEntry> {
+    let delivery = payload["id"].as_str().unwrap_or_default().to_string();
+    let signature = match &webhook.secret {
+        Some(secret) => Some(sign(secret, &serde_json::to_string(&payload)?)),
+        None => None,
+    };
+    let mut entry =
+        OutboxEntry::new(&webhook.url, event, payload).with_header(DELIVERY_HEADER, delivery);
+    if let Some(signature) = signature {
+        entry = entry.with_header(SIGNATURE_HEADER, signature);
+    }
+    Ok(entry)
+}
+
+/// Posts an event to the webhooks of the project subscribed to it
+///
+/// Failed deliveries are queued in the outbox. Like plugin hooks, webhooks
+/// report a change that has already been saved, so errors are printed as
+/// warnings and never fail the command.
+pub fn emit(project_root: &Path, event: &TicketEvent) {
+    let Ok(config) = Config::load_for_project(project_root) else {
+        return;
+    };
+    let webhooks: Vec<&WebhookConfig> = config
+        .webhooks
+        .iter()
+        .filter(|webhook| webhook.accepts(event.kind()))
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let outbox = Outbox::new(project_root.join(".vibe-ticket"));
+    let payload = payload(event.kind(), &config.project.name, &event.to_json());
+    for webhook in webhooks {
+        let delivered = entry(webhook, event.kind(), payload.clone())
+            .and_then(|entry| outbox.deliver_or_enqueue(entry, http_deliver));
+        match delivered {
+            Ok(true) => {},
+            Ok(false) => eprintln!(
+                "Warning: webhook '{}' is unreachable; the event was queued for retry",
+                webhook.name
+            ),
+            Err(e) => eprintln!("Warning: webhook '{}' failed: {e}", webhook.name),
+        }
+    }
+}
+
+/// Event handler posting the events of MCP tools to the webhooks
+#[cfg(feature = "mcp")]
+#[derive(Debug, Clone)]
+pub struct WebhookHandler {
+    project_root: std::path::PathBuf,
+}
+
+#[cfg(feature = "mcp")]
+impl WebhookHandler {
+    /// Creates the handler of the project at `project_root`
+    pub const fn new(project_root: std::path::PathBuf) -> Self {
+        Self { project_root }
+    }
+}
+
+#[cfg(feature = "mcp")]
+#[async_trait::async_trait]
+impl crate::events::EventHandler for WebhookHandler {
+    async fn handle_event(&self, event: TicketEvent) -> Result<()> {
+        let project_root = self.project_root.clone();
+        // Deliveries block on HTTP requests
+        tokio::task::spawn_blocking(move || emit(&project_root, &event))
+            .await
+            .map_err(|e| crate::error::VibeTicketError::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_entry() {
+        let webhook = WebhookConfig {
+            name: "ci".to_string(),
+            url: "http://localhost/hook".to_string(),
+            secret: Some("s3cret".to_string()),
+            events: vec!["closed".to_string()],
+        };
+        assert!(webhook.accepts("closed"));
+        assert!(!webhook.accepts("created"));
+
+        let payload = payload("closed", "demo", &json!({"kind": "closed"}));
+        let entry = entry(&webhook, "closed", payload.clone()).unwrap();
+        assert_eq!(entry.target, webhook.url);
+        assert_eq!(entry.payload, payload);
+        assert_eq!(
+            entry.headers[SIGNATURE_HEADER],
+            sign("s3cret", &serde_json::to_string(&payload).unwrap())
+        );
+        assert_eq!(
+            entry.headers[DELIVERY_HEADER],
+            payload["id"].as_str().unwrap()
+        );
+
+        // Known HMAC-SHA256 test vector (RFC 4231, test case 2)
+        assert_eq!(
+            sign("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -39,7 +39,9 @@ pub mod core;
 pub mod error;
 pub mod events;
 pub mod integration;
+pub mod platform;
 pub mod plugins;
+pub mod project;
 pub mod specs;
 pub mod storage;
 
@@ -49,8 +51,12 @@ pub mod api;
 #[cfg(feature = "mcp")]
 pub mod mcp;
 
+#[cfg(feature = "chaos")]
+pub mod chaos;
+
 #[cfg(test)]
 pub mod test_utils;
 
 // Re-export commonly used types
 pub use error::{Result, VibeTicketError};
+pub use project::Project;
@@ -4,10 +4,14 @@
 //! It handles command-line argument parsing and dispatches to the appropriate
 //! command handlers.
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use std::process;
 use vibe_ticket::cli::{
-    Cli, Commands, OutputFormatter, SpecCommands, TaskCommands, WorktreeCommands,
+    AttachCommands, CiCommands, Cli, Commands, CommentCommands, ContextCommands, DateFormat,
+    DoctorCommands, InboxCommands, IncidentCommands, MilestoneCommands, NotifyCommands,
+    OutboxCommands, OutputFormatter, ProjectCommands, SchemaCommands, SpecCommands,
+    SpecCommentCommands, SpecTemplateCommands, TaskCommands, TimelineCommands, TrashCommands,
+    VisualRegistry, WebhookCommands, WorkspaceCommands, WorktreeCommands, examples,
     handlers::handle_init,
 };
 use vibe_ticket::error::Result;
@@ -17,11 +21,25 @@ use vibe_ticket::error::Result;
 /// Parses command-line arguments and executes the requested command.
 /// Handles errors gracefully and provides helpful error messages to users.
 fn main() {
+    // Print examples before parsing, which would fail on missing arguments
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(command) = examples::requested(&args) {
+        print!("{}", examples::render_text(&command));
+        return;
+    }
+
     // Parse command-line arguments
-    let cli = Cli::parse();
+    let matches = examples::with_examples(Cli::command()).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Configure output formatter based on flags
-    let formatter = OutputFormatter::new(cli.json, cli.no_color);
+    let mut dates = DateFormat::for_project(cli.project.as_deref());
+    if cli.absolute_dates {
+        dates = dates.absolute();
+    }
+    let formatter = OutputFormatter::new(cli.json, cli.no_color)
+        .with_visuals(VisualRegistry::for_project(cli.project.as_deref()))
+        .with_dates(dates);
 
     // Execute the command and handle errors
     if let Err(e) = run(cli, &formatter) {
@@ -49,13 +67,37 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
         tracing_subscriber::fmt().with_env_filter("debug").init();
     }
 
+    // Wait for the locks of other processes as long as requested
+    if let Some(secs) = cli.wait {
+        vibe_ticket::storage::override_lock_wait(std::time::Duration::from_secs(secs));
+    }
+
     // Change to project directory if specified
     if let Some(project_path) = &cli.project {
         std::env::set_current_dir(project_path).map_err(vibe_ticket::error::VibeTicketError::Io)?;
     }
 
+    // Retry integration deliveries queued by earlier runs once the command
+    // is done, if it changed anything
+    let outbox_retry = if matches!(
+        cli.command,
+        Commands::Init { .. }
+            | Commands::Outbox { .. }
+            | Commands::Schema { .. }
+            | Commands::Workspace { .. }
+            | Commands::Project { .. }
+            | Commands::Completions { .. }
+            | Commands::CompleteTickets
+    ) {
+        None
+    } else {
+        vibe_ticket::cli::handlers::begin_outbox_retry(cli.project.as_deref())
+    };
+
+    let project_dir = cli.project.clone();
+
     // Dispatch to command handler
-    match cli.command {
+    let result = match cli.command {
         Commands::Init {
             name,
             description,
@@ -71,20 +113,51 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
 
         Commands::New {
             slug,
+            from_json: Some(source),
+            start,
+            auto_assign,
+            ..
+        } => {
+            use vibe_ticket::cli::handlers::handle_new_from_json;
+            handle_new_from_json(
+                &source,
+                slug.as_deref(),
+                start,
+                auto_assign,
+                cli.project.as_deref(),
+                formatter,
+            )
+        },
+
+        Commands::New {
+            slug,
+            from_url,
+            from_json: None,
             title,
             description,
             priority,
+            severity,
+            template,
             tags,
+            parent,
             start,
+            auto_assign,
+            fields,
         } => {
             use vibe_ticket::cli::handlers::handle_new_command;
             handle_new_command(
-                &slug,
+                slug.as_deref(),
+                from_url.as_deref(),
                 title,
                 description,
                 &priority,
+                severity.as_deref(),
+                template.as_deref(),
                 tags,
+                parent.as_deref(),
                 start,
+                auto_assign,
+                &fields,
                 cli.project.as_deref(),
                 formatter,
             )
@@ -93,7 +166,9 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
         Commands::List {
             status,
             priority,
+            severity,
             assignee,
+            milestone,
             sort,
             reverse,
             limit,
@@ -101,24 +176,48 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             open,
             since,
             until,
+            entered_status,
+            left_status,
             include_done,
+            filter,
+            where_clause,
+            fail_if,
+            tree,
+            watch,
+            all_projects,
         } => {
-            use vibe_ticket::cli::handlers::handle_list_command;
-            handle_list_command(
-                status,
-                priority,
-                assignee,
-                &sort,
-                reverse,
-                limit,
-                archived,
-                open,
-                since,
-                until,
-                include_done,
-                cli.project.as_deref(),
-                formatter,
-            )
+            use vibe_ticket::cli::handlers::{handle_list_command, watch_and_render};
+            let list = || {
+                handle_list_command(
+                    status.clone(),
+                    priority.clone(),
+                    severity.clone(),
+                    assignee.clone(),
+                    milestone.clone(),
+                    &sort,
+                    reverse,
+                    limit,
+                    archived,
+                    open,
+                    since.clone(),
+                    until.clone(),
+                    entered_status.as_deref(),
+                    left_status.as_deref(),
+                    include_done,
+                    filter.as_deref(),
+                    where_clause.as_deref(),
+                    &fail_if,
+                    tree,
+                    all_projects,
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            };
+            if watch {
+                watch_and_render(cli.project.as_deref(), formatter, list)
+            } else {
+                list()
+            }
         },
 
         Commands::Open {
@@ -131,7 +230,9 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             handle_list_command(
                 None, // status
                 None, // priority
+                None, // severity
                 None, // assignee
+                None, // milestone
                 &sort,
                 reverse,
                 limit,
@@ -139,7 +240,14 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
                 true,  // open
                 None,  // since
                 None,  // until
+                None,  // entered_status
+                None,  // left_status
                 false, // include_done
+                None,  // filter
+                None,  // where_clause
+                &[],   // fail_if
+                false, // tree
+                false, // all_projects
                 cli.project.as_deref(),
                 formatter,
             )
@@ -170,21 +278,73 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             message,
             archive,
             pr,
+            pr_url,
+            force,
         } => {
             use vibe_ticket::cli::handlers::handle_close_command;
             handle_close_command(
                 ticket,
-                message,
+                message.as_deref(),
                 archive,
                 pr,
+                pr_url,
+                force,
                 cli.project.as_deref(),
                 formatter,
             )
         },
 
-        Commands::Check { detailed, stats } => {
-            use vibe_ticket::cli::handlers::handle_check_command;
-            handle_check_command(detailed, stats, cli.project.as_deref(), formatter)
+        Commands::Reopen {
+            ticket,
+            status,
+            reason,
+        } => {
+            use vibe_ticket::cli::handlers::handle_reopen_command;
+            handle_reopen_command(&ticket, &status, reason, cli.project.as_deref(), formatter)
+        },
+
+        Commands::Delete { ticket } => {
+            use vibe_ticket::cli::handlers::handle_delete_command;
+            handle_delete_command(&ticket, cli.project.as_deref(), formatter)
+        },
+
+        Commands::Trash { command } => match command {
+            TrashCommands::List => {
+                use vibe_ticket::cli::handlers::handle_trash_list;
+                handle_trash_list(cli.project.as_deref(), formatter)
+            },
+            TrashCommands::Restore { ticket } => {
+                use vibe_ticket::cli::handlers::handle_trash_restore;
+                handle_trash_restore(&ticket, cli.project.as_deref(), formatter)
+            },
+            TrashCommands::Empty { expired } => {
+                use vibe_ticket::cli::handlers::handle_trash_empty;
+                handle_trash_empty(expired, cli.project.as_deref(), formatter)
+            },
+        },
+
+        Commands::Undo { steps, list } => {
+            use vibe_ticket::cli::handlers::handle_undo_command;
+            handle_undo_command(steps, list, cli.project.as_deref(), formatter)
+        },
+
+        Commands::Check {
+            detailed,
+            stats,
+            watch,
+        } => {
+            use vibe_ticket::cli::handlers::{handle_check_command, watch_and_render};
+            let check = || handle_check_command(detailed, stats, cli.project.as_deref(), formatter);
+            if watch {
+                watch_and_render(cli.project.as_deref(), formatter, check)
+            } else {
+                check()
+            }
+        },
+
+        Commands::Current => {
+            use vibe_ticket::cli::handlers::handle_current_command;
+            handle_current_command(cli.project.as_deref(), formatter)
         },
 
         Commands::Edit {
@@ -192,9 +352,13 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             title,
             description,
             priority,
+            severity,
             status,
+            due,
+            parent,
             add_tags,
             remove_tags,
+            fields,
             editor,
         } => {
             use vibe_ticket::cli::handlers::handle_edit_command;
@@ -203,9 +367,13 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
                 title,
                 description,
                 priority,
+                severity,
                 status,
+                due,
+                parent,
                 add_tags,
                 remove_tags,
+                &fields,
                 editor,
                 cli.project.as_deref(),
                 formatter,
@@ -217,13 +385,37 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             tasks,
             history,
             markdown,
+            full_description,
+            sections,
+            expand,
         } => {
             use vibe_ticket::cli::handlers::handle_show_command;
-            handle_show_command(
-                &ticket,
-                tasks,
-                history,
-                markdown,
+            use vibe_ticket::core::sections::Section;
+            Section::select(&sections, tasks, history).and_then(|sections| {
+                handle_show_command(
+                    &ticket,
+                    &sections,
+                    expand,
+                    markdown,
+                    full_description,
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            })
+        },
+
+        Commands::Tasks {
+            assignee,
+            mine,
+            tag,
+            priority,
+        } => {
+            use vibe_ticket::cli::handlers::handle_tasks_command;
+            handle_tasks_command(
+                assignee,
+                mine,
+                tag,
+                priority.as_deref(),
                 cli.project.as_deref(),
                 formatter,
             )
@@ -260,25 +452,53 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             },
         },
 
-        Commands::Archive { ticket, unarchive } => {
-            use vibe_ticket::cli::handlers::handle_archive_command;
-            handle_archive_command(&ticket, unarchive, cli.project.as_deref(), formatter)
+        Commands::Archive {
+            ticket,
+            unarchive,
+            status,
+            until,
+            tag,
+            dry_run,
+        } => {
+            use vibe_ticket::cli::handlers::{
+                ArchiveFilter, handle_archive_command, handle_bulk_archive_command,
+            };
+            match ticket {
+                Some(ticket) => {
+                    handle_archive_command(&ticket, unarchive, cli.project.as_deref(), formatter)
+                },
+                None => handle_bulk_archive_command(
+                    &ArchiveFilter { status, until, tag },
+                    unarchive,
+                    dry_run,
+                    cli.project.as_deref(),
+                    formatter,
+                ),
+            }
         },
 
         Commands::Search {
             query,
+            expression,
             title,
             description,
             tags,
             regex,
+            filter,
+            limit,
+            jobs,
         } => {
             use vibe_ticket::cli::handlers::handle_search_command;
             handle_search_command(
-                &query,
+                query.as_deref(),
+                expression.as_deref(),
                 title,
                 description,
                 tags,
                 regex,
+                filter.as_deref(),
+                limit,
+                jobs,
                 cli.project.as_deref(),
                 formatter,
             )
@@ -288,12 +508,18 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             format,
             output,
             include_archived,
+            sort_by,
+            reproducible,
+            include_tasks,
         } => {
             use vibe_ticket::cli::handlers::handle_export_command;
             handle_export_command(
                 &format,
                 output,
                 include_archived,
+                &sort_by,
+                reproducible,
+                include_tasks,
                 cli.project.as_deref(),
                 formatter,
             )
@@ -304,6 +530,8 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
             format,
             skip_validation,
             dry_run,
+            verify,
+            epic,
         } => {
             use vibe_ticket::cli::handlers::handle_import_command;
             handle_import_command(
@@ -311,6 +539,8 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
                 format.as_deref(),
                 skip_validation,
                 dry_run,
+                verify,
+                epic,
                 cli.project.as_deref(),
                 formatter,
             )
@@ -327,9 +557,38 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
                 description,
                 ticket,
                 tags,
+                fields,
+                amends,
+                template,
             } => {
                 use vibe_ticket::cli::handlers::handle_spec_init;
-                handle_spec_init(title, description, ticket, tags, cli.project, formatter)
+                handle_spec_init(
+                    title,
+                    description,
+                    ticket,
+                    tags,
+                    &fields,
+                    amends.as_deref(),
+                    template.as_deref(),
+                    cli.project,
+                    formatter,
+                )
+            },
+            SpecCommands::Set {
+                spec,
+                fields,
+                unset,
+            } => {
+                use vibe_ticket::cli::handlers::handle_spec_set;
+                handle_spec_set(&spec, &fields, &unset, cli.project, formatter)
+            },
+            SpecCommands::Link {
+                spec,
+                ticket,
+                unlink,
+            } => {
+                use vibe_ticket::cli::handlers::handle_spec_link;
+                handle_spec_link(&spec, ticket.as_deref(), unlink, cli.project, formatter)
             },
             SpecCommands::Requirements {
                 spec,
@@ -363,25 +622,115 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
                     formatter,
                 )
             },
+            SpecCommands::History { spec } => {
+                use vibe_ticket::cli::handlers::handle_spec_history;
+                handle_spec_history(spec, cli.project, formatter)
+            },
+            SpecCommands::Diff {
+                spec,
+                from,
+                to,
+                document,
+            } => {
+                use vibe_ticket::cli::handlers::handle_spec_diff;
+                handle_spec_diff(
+                    spec,
+                    from.as_deref(),
+                    to.as_deref(),
+                    document.as_deref(),
+                    cli.project,
+                    formatter,
+                )
+            },
+            SpecCommands::CheckRefs { spec } => {
+                use vibe_ticket::cli::handlers::handle_spec_check_refs;
+                handle_spec_check_refs(spec, cli.project, formatter)
+            },
+            SpecCommands::Lint { spec } => {
+                use vibe_ticket::cli::handlers::handle_spec_lint;
+                handle_spec_lint(spec, cli.project, formatter)
+            },
             SpecCommands::Status { spec, detailed } => {
                 use vibe_ticket::cli::handlers::handle_spec_status;
                 handle_spec_status(spec, detailed, cli.project, formatter)
             },
+            SpecCommands::Estimate {
+                spec,
+                all,
+                voters,
+                task,
+                points,
+            } => {
+                use vibe_ticket::cli::handlers::handle_spec_estimate;
+                handle_spec_estimate(spec, all, &voters, task, points, cli.project, formatter)
+            },
             SpecCommands::List {
                 status,
                 phase,
                 archived,
+                filters,
             } => {
                 use vibe_ticket::cli::handlers::handle_spec_list;
-                handle_spec_list(status, phase, archived, cli.project, formatter)
+                handle_spec_list(status, phase, archived, &filters, cli.project, formatter)
             },
             SpecCommands::Show {
                 spec,
                 all,
                 markdown,
+                comments,
             } => {
                 use vibe_ticket::cli::handlers::handle_spec_show;
-                handle_spec_show(spec, all, markdown, cli.project, formatter)
+                handle_spec_show(spec, all, markdown, comments, cli.project, formatter)
+            },
+            SpecCommands::Comment { command } => match command {
+                SpecCommentCommands::Add {
+                    spec,
+                    body,
+                    phase,
+                    line,
+                    author,
+                } => {
+                    use vibe_ticket::cli::handlers::handle_spec_comment_add;
+                    handle_spec_comment_add(
+                        &spec,
+                        &body,
+                        &phase,
+                        line,
+                        author,
+                        cli.project.as_deref(),
+                        formatter,
+                    )
+                },
+                SpecCommentCommands::List {
+                    spec,
+                    phase,
+                    unresolved,
+                } => {
+                    use vibe_ticket::cli::handlers::handle_spec_comment_list;
+                    handle_spec_comment_list(
+                        &spec,
+                        phase.as_deref(),
+                        unresolved,
+                        cli.project.as_deref(),
+                        formatter,
+                    )
+                },
+                SpecCommentCommands::Resolve { spec, id, reopen } => {
+                    use vibe_ticket::cli::handlers::handle_spec_comment_resolve;
+                    handle_spec_comment_resolve(
+                        &spec,
+                        id,
+                        reopen,
+                        cli.project.as_deref(),
+                        formatter,
+                    )
+                },
+            },
+            SpecCommands::Template { command } => match command {
+                SpecTemplateCommands::List => {
+                    use vibe_ticket::cli::handlers::handle_spec_template_list;
+                    handle_spec_template_list(cli.project.as_deref(), formatter)
+                },
             },
             SpecCommands::Delete { spec, force } => {
                 use vibe_ticket::cli::handlers::handle_spec_delete;
@@ -391,9 +740,19 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
                 spec,
                 phase,
                 message,
+                request_changes,
+                reviewer,
             } => {
                 use vibe_ticket::cli::handlers::handle_spec_approve;
-                handle_spec_approve(spec, phase, message, cli.project, formatter)
+                handle_spec_approve(
+                    spec,
+                    phase,
+                    message,
+                    request_changes,
+                    reviewer,
+                    cli.project,
+                    formatter,
+                )
             },
             SpecCommands::Activate { spec } => {
                 use vibe_ticket::cli::handlers::handle_spec_activate;
@@ -428,21 +787,522 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
         },
         #[cfg(feature = "mcp")]
         Commands::Mcp { command } => match command {
-            vibe_ticket::cli::McpCommands::Serve { host, port, daemon } => {
+            vibe_ticket::cli::McpCommands::Serve {
+                host,
+                port,
+                transport,
+                daemon,
+            } => {
                 use vibe_ticket::cli::handlers::handle_mcp_serve;
                 let config = vibe_ticket::config::Config::load_or_default()?;
                 handle_mcp_serve(
                     config,
                     host,
                     port,
+                    &transport,
                     daemon,
                     cli.project.as_deref(),
                     formatter,
                 )
                 .map_err(|e| vibe_ticket::error::VibeTicketError::custom(e.to_string()))
             },
+            vibe_ticket::cli::McpCommands::Status => {
+                use vibe_ticket::cli::handlers::handle_mcp_status;
+                handle_mcp_status(cli.project.as_deref(), formatter)
+            },
+            vibe_ticket::cli::McpCommands::Stop => {
+                use vibe_ticket::cli::handlers::handle_mcp_stop;
+                handle_mcp_stop(cli.project.as_deref(), formatter)
+            },
+            vibe_ticket::cli::McpCommands::Audit {
+                tool,
+                token,
+                status,
+                since,
+                limit,
+            } => {
+                use vibe_ticket::cli::handlers::{AuditFilter, handle_mcp_audit};
+                let filter = AuditFilter {
+                    tool,
+                    token,
+                    status,
+                    since,
+                    limit,
+                };
+                handle_mcp_audit(&filter, cli.project.as_deref(), formatter)
+            },
+            vibe_ticket::cli::McpCommands::Token { command } => match command {
+                vibe_ticket::cli::McpTokenCommands::Create { name, scope } => {
+                    use vibe_ticket::cli::handlers::handle_mcp_token_create;
+                    handle_mcp_token_create(&name, &scope, cli.project.as_deref(), formatter)
+                },
+                vibe_ticket::cli::McpTokenCommands::List => {
+                    use vibe_ticket::cli::handlers::handle_mcp_token_list;
+                    handle_mcp_token_list(cli.project.as_deref(), formatter)
+                },
+                vibe_ticket::cli::McpTokenCommands::Revoke { name } => {
+                    use vibe_ticket::cli::handlers::handle_mcp_token_revoke;
+                    handle_mcp_token_revoke(&name, cli.project.as_deref(), formatter)
+                },
+            },
+        },
+        Commands::Outbox { command } => match command {
+            OutboxCommands::List => {
+                use vibe_ticket::cli::handlers::handle_outbox_list;
+                handle_outbox_list(cli.project.as_deref(), formatter)
+            },
+            OutboxCommands::Flush => {
+                use vibe_ticket::cli::handlers::handle_outbox_flush;
+                handle_outbox_flush(cli.project.as_deref(), formatter)
+            },
+            OutboxCommands::Log { limit } => {
+                use vibe_ticket::cli::handlers::handle_outbox_log;
+                handle_outbox_log(limit, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Notify { command } => match command {
+            NotifyCommands::Test { priority } => {
+                use vibe_ticket::cli::handlers::handle_notify_test;
+                handle_notify_test(priority.as_deref(), cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Webhook { command } => match command {
+            WebhookCommands::Add {
+                name,
+                url,
+                secret,
+                events,
+            } => {
+                use vibe_ticket::cli::handlers::handle_webhook_add;
+                handle_webhook_add(
+                    &name,
+                    &url,
+                    secret,
+                    &events,
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+            WebhookCommands::List => {
+                use vibe_ticket::cli::handlers::handle_webhook_list;
+                handle_webhook_list(cli.project.as_deref(), formatter)
+            },
+            WebhookCommands::Remove { name } => {
+                use vibe_ticket::cli::handlers::handle_webhook_remove;
+                handle_webhook_remove(&name, cli.project.as_deref(), formatter)
+            },
+            WebhookCommands::Test { name } => {
+                use vibe_ticket::cli::handlers::handle_webhook_test;
+                handle_webhook_test(&name, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Move { ticket, to } => {
+            use vibe_ticket::cli::handlers::handle_move_command;
+            handle_move_command(&ticket, &to, cli.project.as_deref(), formatter)
+        },
+        Commands::Log {
+            message,
+            ticket,
+            standup,
+            since,
+        } => {
+            use vibe_ticket::cli::handlers::{handle_log_command, handle_standup_report};
+            match message {
+                Some(message) if !standup => {
+                    handle_log_command(&message, ticket, cli.project.as_deref(), formatter)
+                },
+                _ => handle_standup_report(&since, cli.project.as_deref(), formatter),
+            }
+        },
+        Commands::History { ticket } => {
+            use vibe_ticket::cli::handlers::handle_history_command;
+            handle_history_command(&ticket, cli.project.as_deref(), formatter)
+        },
+        Commands::Comment { command } => match command {
+            CommentCommands::Add {
+                body,
+                ticket,
+                author,
+            } => {
+                use vibe_ticket::cli::handlers::handle_comment_add;
+                handle_comment_add(&body, ticket, author, cli.project.as_deref(), formatter)
+            },
+            CommentCommands::List { ticket } => {
+                use vibe_ticket::cli::handlers::handle_comment_list;
+                handle_comment_list(ticket, cli.project.as_deref(), formatter)
+            },
+            CommentCommands::Edit { id, body, ticket } => {
+                use vibe_ticket::cli::handlers::handle_comment_edit;
+                handle_comment_edit(id, &body, ticket, cli.project.as_deref(), formatter)
+            },
+            CommentCommands::Delete { id, ticket } => {
+                use vibe_ticket::cli::handlers::handle_comment_delete;
+                handle_comment_delete(id, ticket, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Attach { command } => match command {
+            AttachCommands::Add { ticket, file, name } => {
+                use vibe_ticket::cli::handlers::handle_attach_add;
+                handle_attach_add(
+                    &ticket,
+                    &file,
+                    name.as_deref(),
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+            AttachCommands::List { ticket } => {
+                use vibe_ticket::cli::handlers::handle_attach_list;
+                handle_attach_list(&ticket, cli.project.as_deref(), formatter)
+            },
+            AttachCommands::Remove { ticket, name } => {
+                use vibe_ticket::cli::handlers::handle_attach_remove;
+                handle_attach_remove(&ticket, &name, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Matrix { urgent_days, limit } => {
+            use vibe_ticket::cli::handlers::handle_matrix_command;
+            handle_matrix_command(urgent_days, limit, cli.project.as_deref(), formatter)
+        },
+        Commands::Stats {
+            heatmap,
+            by_hour,
+            weeks,
+            assignee,
+            closed_between,
+        } => {
+            use vibe_ticket::cli::handlers::handle_stats_command;
+            handle_stats_command(
+                heatmap,
+                by_hour,
+                weeks,
+                assignee.as_deref(),
+                closed_between.as_deref(),
+                cli.project.as_deref(),
+                formatter,
+            )
+        },
+
+        Commands::Incident { command } => match command {
+            IncidentCommands::Timeline {
+                command:
+                    TimelineCommands::Add {
+                        message,
+                        ticket,
+                        phase,
+                        at,
+                    },
+            } => {
+                use vibe_ticket::cli::handlers::handle_timeline_add;
+                handle_timeline_add(
+                    &message,
+                    ticket.as_deref(),
+                    phase.as_deref(),
+                    at.as_deref(),
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+            IncidentCommands::Timeline {
+                command: TimelineCommands::List { ticket },
+            } => {
+                use vibe_ticket::cli::handlers::handle_timeline_list;
+                handle_timeline_list(ticket.as_deref(), cli.project.as_deref(), formatter)
+            },
+            IncidentCommands::Postmortem { ticket, output } => {
+                use vibe_ticket::cli::handlers::handle_postmortem;
+                handle_postmortem(
+                    ticket.as_deref(),
+                    output.as_deref(),
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
         },
+        Commands::Inbox { command, text } => match command {
+            Some(InboxCommands::Process) => {
+                use vibe_ticket::cli::handlers::handle_inbox_process;
+                handle_inbox_process(cli.project.as_deref(), formatter)
+            },
+            Some(InboxCommands::List) => {
+                use vibe_ticket::cli::handlers::handle_inbox_list;
+                handle_inbox_list(cli.project.as_deref(), formatter)
+            },
+            None if text.is_empty() => {
+                use vibe_ticket::cli::handlers::handle_inbox_list;
+                handle_inbox_list(cli.project.as_deref(), formatter)
+            },
+            None => {
+                use vibe_ticket::cli::handlers::handle_inbox_capture;
+                handle_inbox_capture(&text.join(" "), cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Milestone { command } => match command {
+            MilestoneCommands::Create {
+                name,
+                due,
+                description,
+            } => {
+                use vibe_ticket::cli::handlers::handle_milestone_create;
+                handle_milestone_create(
+                    &name,
+                    due.as_deref(),
+                    description,
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+            MilestoneCommands::List => {
+                use vibe_ticket::cli::handlers::handle_milestone_list;
+                handle_milestone_list(cli.project.as_deref(), formatter)
+            },
+            MilestoneCommands::Show { name } => {
+                use vibe_ticket::cli::handlers::handle_milestone_show;
+                handle_milestone_show(&name, cli.project.as_deref(), formatter)
+            },
+            MilestoneCommands::Edit {
+                name,
+                due,
+                description,
+            } => {
+                use vibe_ticket::cli::handlers::handle_milestone_edit;
+                handle_milestone_edit(
+                    &name,
+                    due.as_deref(),
+                    description,
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+            MilestoneCommands::Delete { name } => {
+                use vibe_ticket::cli::handlers::handle_milestone_delete;
+                handle_milestone_delete(&name, cli.project.as_deref(), formatter)
+            },
+            MilestoneCommands::Assign { name, tickets } => {
+                use vibe_ticket::cli::handlers::handle_milestone_assign;
+                handle_milestone_assign(&name, &tickets, cli.project.as_deref(), formatter)
+            },
+            MilestoneCommands::Unassign { tickets } => {
+                use vibe_ticket::cli::handlers::handle_milestone_unassign;
+                handle_milestone_unassign(&tickets, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Gc { prune, force } => {
+            use vibe_ticket::cli::handlers::handle_gc_command;
+            handle_gc_command(prune, force, cli.project.as_deref(), formatter)
+        },
+        Commands::Doctor {
+            command,
+            rebuild_index,
+        } => match command {
+            Some(DoctorCommands::Locks) => {
+                use vibe_ticket::cli::handlers::handle_doctor_locks;
+                handle_doctor_locks(cli.project.as_deref(), formatter)
+            },
+            None => {
+                use vibe_ticket::cli::handlers::handle_doctor_command;
+                handle_doctor_command(rebuild_index, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Maintain { dry_run } => {
+            use vibe_ticket::cli::handlers::handle_maintain_command;
+            handle_maintain_command(dry_run, cli.project.as_deref(), formatter)
+        },
+        Commands::Share {
+            ticket,
+            format,
+            output,
+            clipboard,
+            gist,
+            public,
+        } => {
+            use vibe_ticket::cli::handlers::{ShareTargets, handle_share_command};
+            let targets = ShareTargets {
+                file: output,
+                clipboard,
+                gist,
+                public,
+            };
+            handle_share_command(
+                &ticket,
+                &format,
+                &targets,
+                cli.project.as_deref(),
+                formatter,
+            )
+        },
+        Commands::Watch {
+            ticket,
+            user,
+            remove,
+        } => {
+            use vibe_ticket::cli::handlers::handle_watch_command;
+            handle_watch_command(ticket, user, remove, cli.project.as_deref(), formatter)
+        },
+        Commands::Link {
+            ticket,
+            depends_on,
+            blocks,
+            duplicates,
+            relates_to,
+            supersedes,
+            remove,
+        } => {
+            use vibe_ticket::cli::handlers::{LinkTargets, handle_link_command};
+            use vibe_ticket::core::relations::RelationKind;
+            let targets = LinkTargets {
+                depends_on,
+                blocks,
+                ..LinkTargets::default()
+            }
+            .with_relations(RelationKind::Duplicates, duplicates)
+            .with_relations(RelationKind::RelatesTo, relates_to)
+            .with_relations(RelationKind::Supersedes, supersedes);
+            handle_link_command(&ticket, &targets, remove, cli.project.as_deref(), formatter)
+        },
+        Commands::Unlink {
+            ticket,
+            depends_on,
+            blocks,
+            duplicates,
+            relates_to,
+            supersedes,
+        } => {
+            use vibe_ticket::cli::handlers::{LinkTargets, handle_link_command};
+            use vibe_ticket::core::relations::RelationKind;
+            let targets = LinkTargets {
+                depends_on,
+                blocks,
+                ..LinkTargets::default()
+            }
+            .with_relations(RelationKind::Duplicates, duplicates)
+            .with_relations(RelationKind::RelatesTo, relates_to)
+            .with_relations(RelationKind::Supersedes, supersedes);
+            handle_link_command(&ticket, &targets, true, cli.project.as_deref(), formatter)
+        },
+        Commands::Relate {
+            ticket,
+            kind,
+            tickets,
+        } => {
+            use vibe_ticket::cli::handlers::handle_relate_command;
+            handle_relate_command(
+                &ticket,
+                &kind,
+                &tickets,
+                false,
+                cli.project.as_deref(),
+                formatter,
+            )
+        },
+        Commands::Unrelate {
+            ticket,
+            kind,
+            tickets,
+        } => {
+            use vibe_ticket::cli::handlers::handle_relate_command;
+            handle_relate_command(
+                &ticket,
+                &kind,
+                &tickets,
+                true,
+                cli.project.as_deref(),
+                formatter,
+            )
+        },
+        Commands::SuggestAssignee { ticket, assign } => {
+            use vibe_ticket::cli::handlers::handle_suggest_assignee;
+            handle_suggest_assignee(&ticket, assign, cli.project.as_deref(), formatter)
+        },
+        Commands::Plugin { plugin, args } => {
+            use vibe_ticket::cli::handlers::handle_plugin_command;
+            handle_plugin_command(plugin.as_deref(), &args, cli.project.as_deref(), formatter)
+        },
+        Commands::Ci { command } => match command {
+            CiCommands::Report {
+                ticket,
+                from,
+                format,
+            } => {
+                use vibe_ticket::cli::handlers::handle_ci_report;
+                handle_ci_report(
+                    &ticket,
+                    &from,
+                    format.as_deref(),
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+        },
+        Commands::Workspace { command } => match command {
+            WorkspaceCommands::Add { path, name } => {
+                use vibe_ticket::cli::handlers::handle_workspace_add;
+                handle_workspace_add(
+                    path.as_deref(),
+                    name.as_deref(),
+                    cli.project.as_deref(),
+                    formatter,
+                )
+            },
+            WorkspaceCommands::List => {
+                use vibe_ticket::cli::handlers::handle_workspace_list;
+                handle_workspace_list(formatter)
+            },
+            WorkspaceCommands::Switch { name } => {
+                use vibe_ticket::cli::handlers::handle_workspace_switch;
+                handle_workspace_switch(&name, formatter)
+            },
+            WorkspaceCommands::Remove { name } => {
+                use vibe_ticket::cli::handlers::handle_workspace_remove;
+                handle_workspace_remove(&name, formatter)
+            },
+        },
+        Commands::Project { command } => match command {
+            ProjectCommands::Rename {
+                name,
+                move_worktrees,
+            } => {
+                use vibe_ticket::cli::handlers::handle_project_rename;
+                handle_project_rename(&name, move_worktrees, cli.project.as_deref(), formatter)
+            },
+            ProjectCommands::Relocate {
+                path,
+                move_worktrees,
+            } => {
+                use vibe_ticket::cli::handlers::handle_project_relocate;
+                handle_project_relocate(&path, move_worktrees, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Context { command } => match command {
+            ContextCommands::Pack { budget } => {
+                use vibe_ticket::cli::handlers::handle_context_pack;
+                handle_context_pack(&budget, cli.project.as_deref(), formatter)
+            },
+        },
+        Commands::Schema { command } => match command {
+            SchemaCommands::Dump { output } => {
+                use vibe_ticket::cli::handlers::handle_schema_dump;
+                handle_schema_dump(output.as_deref(), formatter)
+            },
+        },
+        Commands::Completions { shell } => {
+            use vibe_ticket::cli::handlers::handle_completions_command;
+            handle_completions_command(&shell)
+        },
+        Commands::CompleteTickets => {
+            use vibe_ticket::cli::handlers::handle_complete_tickets;
+            handle_complete_tickets(cli.project.as_deref());
+            Ok(())
+        },
+    };
+
+    // Notify watchers of the changes made by the command
+    vibe_ticket::cli::handlers::dispatch_notifications(project_dir.as_deref());
+    if let Some(outbox_retry) = outbox_retry {
+        outbox_retry.finish();
     }
+
+    result
 }
 
 /// Handle errors and display them to the user
@@ -491,6 +1351,7 @@ fn handle_error(error: vibe_ticket::error::VibeTicketError, formatter: &OutputFo
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::Parser;
 
     #[test]
     fn test_cli_parsing() {
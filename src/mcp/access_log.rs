@@ -0,0 +1,249 @@
+//! Access log of the MCP server
+//!
+//! Every tool call is appended as one JSON line to
+//! `.vibe-ticket/logs/mcp-access.log`: when it happened, the API token and
+//! MCP client that made it, the tool, a SHA-256 hash of its arguments, how
+//! long it took and whether it succeeded, failed or was denied. Arguments
+//! are hashed rather than stored, so ticket content and secrets passed to
+//! tools stay out of the log while repeated calls remain recognizable.
+//!
+//! The log is rotated once it grows past [`MAX_LOG_BYTES`]: `mcp-access.log`
+//! becomes `mcp-access.log.1`, and so on up to [`ROTATED_LOGS`] files. It is
+//! read back by `vibe-ticket mcp audit`.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write as IoWrite;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::{ErrorContext, Result};
+
+/// Name of the current log file under `.vibe-ticket/logs/`
+pub const LOG_FILE: &str = "mcp-access.log";
+
+/// Size past which the log is rotated
+pub const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Number of rotated log files kept
+pub const ROTATED_LOGS: usize = 5;
+
+/// Outcome of a tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessStatus {
+    /// The tool succeeded
+    Ok,
+    /// The tool failed
+    Error,
+    /// The session's scope does not allow the tool
+    Denied,
+}
+
+impl AccessStatus {
+    /// Parses a status name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ok" => Some(Self::Ok),
+            "error" => Some(Self::Error),
+            "denied" => Some(Self::Denied),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AccessStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::Denied => "denied",
+        })
+    }
+}
+
+/// One tool call of the access log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessEntry {
+    /// When the call started
+    pub at: DateTime<Utc>,
+    /// Name of the API token of the session, if it authenticated with one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Name the MCP client gave when it connected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// Name of the tool
+    pub tool: String,
+    /// SHA-256 of the arguments (see [`params_hash`])
+    pub params_hash: String,
+    /// Duration of the call in milliseconds
+    pub duration_ms: u64,
+    /// Outcome of the call
+    pub status: AccessStatus,
+    /// Error message of failed and denied calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AccessEntry {
+    /// Describes a successful call of `tool` with the arguments hashed to
+    /// `params_hash`, started at `at` and lasting `duration`
+    pub fn new(tool: &str, params_hash: String, at: DateTime<Utc>, duration: Duration) -> Self {
+        Self {
+            at,
+            token: None,
+            client: None,
+            tool: tool.to_string(),
+            params_hash,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            status: AccessStatus::Ok,
+            error: None,
+        }
+    }
+}
+
+/// Hex-encoded SHA-256 of the JSON of tool arguments
+///
+/// Object keys are serialized in sorted order, so equal arguments have equal
+/// hashes.
+pub fn params_hash(arguments: &Value) -> String {
+    format!("{:x}", Sha256::digest(arguments.to_string().as_bytes()))
+}
+
+/// Rotating access log of a project
+#[derive(Debug)]
+pub struct AccessLog {
+    dir: PathBuf,
+    /// Serializes the appends and rotations of the calls of one server
+    lock: Mutex<()>,
+}
+
+impl AccessLog {
+    /// Access log stored in the `logs` directory of `vibe_dir`
+    pub fn new(vibe_dir: &Path) -> Self {
+        Self {
+            dir: vibe_dir.join("logs"),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the current log file
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(LOG_FILE)
+    }
+
+    /// Path of the `n`th rotated log file
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        self.dir.join(format!("{LOG_FILE}.{n}"))
+    }
+
+    /// Appends an entry, rotating the log first if it is full
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be written.
+    pub fn append(&self, entry: &AccessEntry) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        fs::create_dir_all(&self.dir).context("Failed to create the log directory")?;
+        let path = self.path();
+        if fs::metadata(&path).is_ok_and(|metadata| metadata.len() >= MAX_LOG_BYTES) {
+            self.rotate()?;
+        }
+
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .context("Failed to write the access log")
+    }
+
+    /// Shifts the log files by one, dropping the oldest
+    fn rotate(&self) -> Result<()> {
+        for n in (1..ROTATED_LOGS).rev() {
+            let from = self.rotated_path(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(n + 1))
+                    .context("Failed to rotate the access log")?;
+            }
+        }
+        fs::rename(self.path(), self.rotated_path(1)).context("Failed to rotate the access log")
+    }
+
+    /// Loads the entries of the current and rotated logs, oldest first
+    ///
+    /// Calls are appended when they end, so the entries are sorted by the
+    /// time they started. Lines that cannot be parsed are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an existing log file cannot be read.
+    pub fn load(&self) -> Result<Vec<AccessEntry>> {
+        let mut entries = Vec::new();
+        let files = (1..=ROTATED_LOGS)
+            .rev()
+            .map(|n| self.rotated_path(n))
+            .chain([self.path()]);
+        for file in files.filter(|file| file.exists()) {
+            let content = fs::read_to_string(&file).context("Failed to read the access log")?;
+            entries.extend(
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<AccessEntry>(line).ok()),
+            );
+        }
+        entries.sort_by_key(|entry| entry.at);
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_rotate_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AccessLog::new(temp_dir.path());
+        assert!(log.load().unwrap().is_empty());
+
+        let mut entry = AccessEntry::new(
+            "vibe-ticket_edit",
+            params_hash(&json!({"ticket": "a", "title": "b"})),
+            Utc::now(),
+            Duration::from_millis(12),
+        );
+        entry.token = Some("agent".to_string());
+        entry.status = AccessStatus::Denied;
+        log.append(&entry).unwrap();
+        assert_eq!(log.load().unwrap(), vec![entry.clone()]);
+
+        // A full log is rotated before the next append
+        fs::write(
+            log.path(),
+            "x".repeat(usize::try_from(MAX_LOG_BYTES).unwrap()),
+        )
+        .unwrap();
+        log.append(&entry).unwrap();
+        assert!(log.rotated_path(1).exists());
+        assert_eq!(log.load().unwrap(), vec![entry]);
+    }
+
+    #[test]
+    fn test_params_hash() {
+        let hash = params_hash(&json!({"b": 1, "a": [1, 2]}));
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, params_hash(&json!({"a": [1, 2], "b": 1})));
+        assert_ne!(hash, params_hash(&json!({"a": [2, 1], "b": 1})));
+    }
+}
@@ -1,32 +1,125 @@
-//! Authentication middleware for MCP server
+//! API token authentication for the MCP server
+//!
+//! Tokens are created with `mcp token create` and stored in the `mcp.tokens`
+//! section of the project configuration as SHA-256 hashes. Each token has a
+//! [`TokenScope`]; the tools a scope may call are listed in
+//! [`crate::mcp::handlers::required_scope`].
+//!
+//! Only the HTTP transport carries tokens, as `Authorization: Bearer`
+//! headers. The configuration is read on every request, so revoking a token
+//! takes effect on running servers. Without tokens the server accepts any
+//! request, and stdio sessions, started by the local user, always have the
+//! admin scope.
 
-use crate::mcp::{config::AuthConfig, error::McpError};
-use std::future::Future;
-use std::pin::Pin;
+use std::path::PathBuf;
 
-/// Authentication middleware
-pub struct AuthMiddleware {
-    config: AuthConfig,
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+use crate::config::{ApiToken, Config, TokenScope};
+use crate::mcp::error::McpError;
+
+/// Prefix of generated tokens, making them recognizable in secret scanners
+const TOKEN_PREFIX: &str = "vt_";
+
+/// Creates a token and the configuration entry holding its hash
+///
+/// Returns the token, which is not stored anywhere, and the entry.
+pub fn create_token(name: &str, scope: TokenScope) -> (String, ApiToken) {
+    let token = format!(
+        "{TOKEN_PREFIX}{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let entry = ApiToken {
+        name: name.to_string(),
+        hash: hash_token(&token),
+        scope,
+        created_at: Utc::now(),
+    };
+    (token, entry)
+}
+
+/// Hex-encoded SHA-256 hash of a token
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
 }
 
-impl AuthMiddleware {
-    /// Create new authentication middleware
-    pub fn new(config: AuthConfig) -> Self {
-        Self { config }
+/// Checks request tokens against the tokens of a project
+#[derive(Debug, Clone)]
+pub struct Authenticator {
+    project_root: PathBuf,
+}
+
+impl Authenticator {
+    /// Creates the authenticator of the project at `project_root`
+    pub const fn new(project_root: PathBuf) -> Self {
+        Self { project_root }
+    }
+
+    /// Tokens currently configured
+    fn tokens(&self) -> Result<Vec<ApiToken>, McpError> {
+        Ok(Config::load_for_project(&self.project_root)?.mcp.tokens)
     }
 
-    /// Authenticate a request
-    pub fn authenticate(
-        &self,
-        _api_key: Option<&str>,
-    ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
-        Box::pin(async move {
-            if !self.config.enabled {
-                return Ok(());
-            }
-
-            // TODO: Implement actual authentication
-            Ok(())
-        })
+    /// Whether requests must carry a token
+    pub fn enabled(&self) -> Result<bool, McpError> {
+        Ok(!self.tokens()?.is_empty())
+    }
+
+    /// Finds the configured token matching `token`
+    ///
+    /// Returns `None` if no tokens are configured and every request is
+    /// accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::AuthenticationFailed`] if tokens are configured
+    /// and `token` is missing or matches none of them.
+    pub fn authenticate(&self, token: Option<&str>) -> Result<Option<ApiToken>, McpError> {
+        let tokens = self.tokens()?;
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+        let token =
+            token.ok_or_else(|| McpError::AuthenticationFailed("missing API token".to_string()))?;
+        let hash = hash_token(token);
+        tokens
+            .into_iter()
+            .find(|entry| entry.hash == hash)
+            .map(Some)
+            .ok_or_else(|| McpError::AuthenticationFailed("invalid API token".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_authenticate_against_configured_tokens() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".vibe-ticket")).unwrap();
+        let authenticator = Authenticator::new(temp_dir.path().to_path_buf());
+
+        // Without tokens every request is accepted
+        assert!(!authenticator.enabled().unwrap());
+        assert!(authenticator.authenticate(None).unwrap().is_none());
+
+        let (token, entry) = create_token("agent", TokenScope::ReadOnly);
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_ne!(entry.hash, token);
+        let mut config = Config::default();
+        config.mcp.tokens.push(entry);
+        config
+            .save_to_path(temp_dir.path().join(".vibe-ticket/config.yaml"))
+            .unwrap();
+
+        let found = authenticator.authenticate(Some(&token)).unwrap().unwrap();
+        assert_eq!(found.name, "agent");
+        assert_eq!(found.scope, TokenScope::ReadOnly);
+        assert!(authenticator.authenticate(None).is_err());
+        assert!(authenticator.authenticate(Some("vt_wrong")).is_err());
     }
 }
@@ -9,9 +9,6 @@ pub struct McpConfig {
     /// Server configuration
     pub server: ServerConfig,
 
-    /// Authentication configuration
-    pub auth: AuthConfig,
-
     /// Ticket storage path
     pub storage_path: PathBuf,
 }
@@ -20,7 +17,6 @@ impl Default for McpConfig {
     fn default() -> Self {
         Self {
             server: ServerConfig::default(),
-            auth: AuthConfig::default(),
             storage_path: PathBuf::from(".vibe-ticket"),
         }
     }
@@ -35,7 +31,7 @@ pub struct ServerConfig {
     /// Port to listen on
     pub port: u16,
 
-    /// Transport type (stdio, tcp, websocket)
+    /// Transport type (stdio, tcp, http, websocket)
     pub transport: TransportType,
 }
 
@@ -59,16 +55,25 @@ pub enum TransportType {
     /// TCP socket
     Tcp,
 
+    /// Streamable HTTP with server-sent events
+    Http,
+
     /// WebSocket
     WebSocket,
 }
 
-/// Authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct AuthConfig {
-    /// Enable authentication
-    pub enabled: bool,
+impl std::str::FromStr for TransportType {
+    type Err = String;
 
-    /// API key for authentication
-    pub api_key: Option<String>,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "stdio" => Ok(Self::Stdio),
+            "tcp" => Ok(Self::Tcp),
+            "http" => Ok(Self::Http),
+            "websocket" | "ws" => Ok(Self::WebSocket),
+            _ => Err(format!(
+                "Unknown transport '{s}'. Valid transports: stdio, tcp, http, websocket"
+            )),
+        }
+    }
 }
@@ -0,0 +1,349 @@
+//! Background mode for the MCP server
+//!
+//! `mcp serve --daemon` starts the server as a detached child process that
+//! serves over TCP or streamable HTTP (a daemon has no terminal to speak
+//! stdio with, so stdio falls back to TCP). The
+//! child runs in its own process group with stdin closed and stdout and
+//! stderr appended to the configured log file.
+//!
+//! The state of the running daemon is kept in `.vibe-ticket/run/`:
+//! - `mcp.pid`: process ID of the server
+//! - `mcp.json`: transport, address, log file and start time
+//!
+//! Files left behind by a server that died are removed the next time the
+//! status is queried. Background mode is only available on unix.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Directory below `.vibe-ticket` holding runtime state
+pub const RUN_DIR: &str = "run";
+
+/// Time a freshly started server gets to fail before it counts as started
+const STARTUP_GRACE: Duration = Duration::from_millis(300);
+
+/// State of a server running in the background
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonState {
+    /// Process ID of the server
+    pub pid: u32,
+
+    /// Transport the server speaks, `tcp` or `http`
+    #[serde(default = "default_transport")]
+    pub transport: String,
+
+    /// Host the server listens on
+    pub host: String,
+
+    /// Port the server listens on
+    pub port: u16,
+
+    /// File receiving the server's output
+    pub log_file: PathBuf,
+
+    /// Time the server was started
+    pub started_at: DateTime<Utc>,
+}
+
+impl DaemonState {
+    /// Address clients connect to
+    pub fn address(&self) -> String {
+        if self.transport == "http" {
+            format!(
+                "http://{}:{}{}",
+                self.host,
+                self.port,
+                super::http::ENDPOINT
+            )
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Transport of state files written before the transport was recorded
+fn default_transport() -> String {
+    "tcp".to_string()
+}
+
+/// Runtime files of the background server of a project
+#[derive(Debug, Clone)]
+pub struct Daemon {
+    run_dir: PathBuf,
+}
+
+impl Daemon {
+    /// Creates the handle for the project whose data lives in `vibe_ticket_dir`
+    pub fn new(vibe_ticket_dir: &Path) -> Self {
+        Self {
+            run_dir: vibe_ticket_dir.join(RUN_DIR),
+        }
+    }
+
+    /// Path of the PID file
+    pub fn pid_path(&self) -> PathBuf {
+        self.run_dir.join("mcp.pid")
+    }
+
+    /// Path of the state file
+    fn state_path(&self) -> PathBuf {
+        self.run_dir.join("mcp.json")
+    }
+
+    /// Returns the state of the running server, if any
+    ///
+    /// Runtime files of a server that is no longer running are removed.
+    pub fn status(&self) -> Result<Option<DaemonState>> {
+        let Ok(content) = fs::read_to_string(self.pid_path()) else {
+            return Ok(None);
+        };
+
+        let pid = content.trim().parse::<u32>().ok();
+        if let Some(pid) = pid.filter(|&pid| is_running(pid)) {
+            let state = fs::read_to_string(self.state_path())
+                .ok()
+                .and_then(|json| serde_json::from_str::<DaemonState>(&json).ok())
+                .filter(|state| state.pid == pid);
+            if let Some(state) = state {
+                return Ok(Some(state));
+            }
+        }
+
+        self.clear()?;
+        Ok(None)
+    }
+
+    /// Starts `command` in the background and records it as the server
+    ///
+    /// # Errors
+    ///
+    /// Fails if a server is already running, the log file cannot be opened,
+    /// or the process exits right after starting.
+    pub fn start(
+        &self,
+        mut command: Command,
+        transport: &str,
+        host: &str,
+        port: u16,
+        log_file: &Path,
+    ) -> Result<DaemonState> {
+        if !cfg!(unix) {
+            return Err(VibeTicketError::custom(
+                "Running the MCP server in the background is only supported on unix",
+            ));
+        }
+        if let Some(state) = self.status()? {
+            return Err(VibeTicketError::custom(format!(
+                "MCP server is already running (pid {}, {})",
+                state.pid,
+                state.address()
+            )));
+        }
+
+        fs::create_dir_all(&self.run_dir).context("Failed to create run directory")?;
+        if let Some(parent) = log_file.parent() {
+            fs::create_dir_all(parent).context("Failed to create log directory")?;
+        }
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_file)
+            .with_context(|| format!("Failed to open log file {}", log_file.display()))?;
+        let log_err = log.try_clone().context("Failed to open log file")?;
+
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(log))
+            .stderr(Stdio::from(log_err));
+        detach(&mut command);
+
+        let mut child = command.spawn().context("Failed to start MCP server")?;
+        thread::sleep(STARTUP_GRACE);
+        if let Some(status) = child.try_wait().context("Failed to check MCP server")? {
+            return Err(VibeTicketError::custom(format!(
+                "MCP server exited right after starting ({status}); see {}",
+                log_file.display()
+            )));
+        }
+
+        let state = DaemonState {
+            pid: child.id(),
+            transport: transport.to_string(),
+            host: host.to_string(),
+            port,
+            log_file: log_file.to_path_buf(),
+            started_at: Utc::now(),
+        };
+        fs::write(self.state_path(), serde_json::to_string_pretty(&state)?)
+            .context("Failed to write MCP server state")?;
+        fs::write(self.pid_path(), format!("{}\n", state.pid))
+            .context("Failed to write PID file")?;
+
+        Ok(state)
+    }
+
+    /// Stops the running server, waiting up to `timeout` for it to exit
+    ///
+    /// Returns the state of the stopped server, or `None` if none was running.
+    pub fn stop(&self, timeout: Duration) -> Result<Option<DaemonState>> {
+        let Some(state) = self.status()? else {
+            return Ok(None);
+        };
+
+        terminate(state.pid)?;
+        let deadline = Instant::now() + timeout;
+        while is_running(state.pid) {
+            if Instant::now() >= deadline {
+                return Err(VibeTicketError::custom(format!(
+                    "MCP server (pid {}) did not stop within {}s",
+                    state.pid,
+                    timeout.as_secs()
+                )));
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        self.clear()?;
+        Ok(Some(state))
+    }
+
+    /// Removes the runtime files
+    fn clear(&self) -> Result<()> {
+        for path in [self.pid_path(), self.state_path()] {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Moves the child into its own process group, away from the terminal's signals
+#[cfg(unix)]
+fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+const fn detach(_command: &mut Command) {}
+
+/// Returns true if a live (non-zombie) process has the given ID
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    // On Linux, /proc also tells exited-but-unreaped processes apart
+    if let Ok(stat) = fs::read_to_string(format!("/proc/{pid}/stat")) {
+        return stat
+            .rsplit_once(')')
+            .is_some_and(|(_, rest)| !rest.trim_start().starts_with(['Z', 'X']));
+    }
+    if Path::new("/proc/self").exists() {
+        return false;
+    }
+
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(not(unix))]
+const fn is_running(_pid: u32) -> bool {
+    false
+}
+
+/// Asks the process to shut down
+fn terminate(pid: u32) -> Result<()> {
+    let status = Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .status()
+        .context("Failed to run kill")?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::custom(format!(
+            "Failed to stop MCP server (pid {pid})"
+        )))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_start_status_stop() {
+        let temp_dir = TempDir::new().unwrap();
+        let daemon = Daemon::new(temp_dir.path());
+        let log_file = temp_dir.path().join("run").join("mcp.log");
+        assert!(daemon.status().unwrap().is_none());
+
+        let mut sleeper = Command::new("sleep");
+        sleeper.arg("30");
+        let state = daemon
+            .start(sleeper, "tcp", "127.0.0.1", 3033, &log_file)
+            .unwrap();
+        assert!(log_file.exists());
+        assert_eq!(daemon.status().unwrap(), Some(state.clone()));
+
+        let mut second = Command::new("sleep");
+        second.arg("30");
+        assert!(
+            daemon
+                .start(second, "tcp", "127.0.0.1", 3033, &log_file)
+                .is_err()
+        );
+
+        let stopped = daemon.stop(Duration::from_secs(5)).unwrap();
+        assert_eq!(stopped.map(|s| s.pid), Some(state.pid));
+        assert!(!daemon.pid_path().exists());
+        assert!(daemon.stop(Duration::from_secs(1)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_state_without_transport_is_tcp() {
+        let state: DaemonState = serde_json::from_str(
+            r#"{"pid":1,"host":"127.0.0.1","port":3033,"log_file":"mcp.log","started_at":"2025-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(state.address(), "127.0.0.1:3033");
+
+        let http = DaemonState {
+            transport: "http".to_string(),
+            ..state
+        };
+        assert_eq!(http.address(), "http://127.0.0.1:3033/mcp");
+    }
+
+    #[test]
+    fn test_failed_start_and_stale_pid() {
+        let temp_dir = TempDir::new().unwrap();
+        let daemon = Daemon::new(temp_dir.path());
+        let log_file = temp_dir.path().join("mcp.log");
+
+        assert!(
+            daemon
+                .start(Command::new("false"), "tcp", "127.0.0.1", 1, &log_file)
+                .is_err()
+        );
+
+        // A PID file of a process that is gone is cleaned up
+        let mut done = Command::new("true").spawn().unwrap();
+        let pid = done.id();
+        done.wait().unwrap();
+        fs::create_dir_all(temp_dir.path().join(RUN_DIR)).unwrap();
+        fs::write(daemon.pid_path(), pid.to_string()).unwrap();
+        assert!(daemon.status().unwrap().is_none());
+        assert!(!daemon.pid_path().exists());
+    }
+}
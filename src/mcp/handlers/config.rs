@@ -104,6 +104,7 @@ pub fn handle_show(service: &VibeTicketService, arguments: Value) -> Result<Valu
             "git.worktree_cleanup_on_close" => json!(config.git.worktree_cleanup_on_close),
 
             "ui.date_format" => json!(config.ui.date_format),
+            "ui.relative_dates" => json!(config.ui.relative_dates),
 
             _ => return Err(format!("Unknown configuration key: {}", key)),
         };
@@ -132,6 +133,7 @@ pub fn handle_show(service: &VibeTicketService, arguments: Value) -> Result<Valu
             },
             "ui": {
                 "date_format": config.ui.date_format,
+                "relative_dates": config.ui.relative_dates,
             }
         }))
     }
@@ -212,11 +214,12 @@ pub fn handle_set(service: &VibeTicketService, arguments: Value) -> Result<Value
         },
 
         "ui.date_format" => {
-            config.ui.date_format = args
-                .value
-                .as_str()
-                .ok_or("Value must be a string")?
-                .to_string();
+            let pattern = args.value.as_str().ok_or("Value must be a string")?;
+            crate::cli::dates::validate(pattern).map_err(|e| e.to_string())?;
+            config.ui.date_format = pattern.to_string();
+        },
+        "ui.relative_dates" => {
+            config.ui.relative_dates = args.value.as_bool().ok_or("Value must be a boolean")?;
         },
 
         _ => return Err(format!("Unknown configuration key: {}", args.key)),
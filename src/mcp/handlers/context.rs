@@ -0,0 +1,153 @@
+//! Session context MCP tool handlers
+
+use crate::cli::handlers::build_context_pack;
+use crate::core::context_pack::{DEFAULT_BUDGET, parse_budget};
+use crate::mcp::handlers::schema_helper::json_to_schema;
+use crate::mcp::service::VibeTicketService;
+use crate::mcp::session::SessionContext;
+use crate::storage::TicketRepository;
+use rmcp::model::Tool;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Register all session context tools
+pub fn register_tools() -> Vec<Tool> {
+    vec![
+        // Set context tool
+        Tool {
+            name: Cow::Borrowed("vibe-ticket_context_set"),
+            description: Some(Cow::Borrowed(
+                "Set the session's working directory and/or current ticket. Tools that take \
+                 a ticket default to the ticket of the worktree containing the working directory",
+            )),
+            input_schema: Arc::new(json_to_schema(json!({
+                "type": "object",
+                "properties": {
+                    "working_dir": {
+                        "type": "string",
+                        "description": "Directory the assistant is currently editing in"
+                    },
+                    "ticket": {
+                        "type": "string",
+                        "description": "Ticket ID or slug to use as the session's ticket"
+                    }
+                }
+            }))),
+            annotations: None,
+        },
+        // Show context tool
+        Tool {
+            name: Cow::Borrowed("vibe-ticket_context_show"),
+            description: Some(Cow::Borrowed(
+                "Show the session context and the ticket tools will default to",
+            )),
+            input_schema: Arc::new(json_to_schema(json!({
+                "type": "object",
+                "properties": {}
+            }))),
+            annotations: None,
+        },
+        // Context pack tool
+        Tool {
+            name: Cow::Borrowed("vibe-ticket_context_pack"),
+            description: Some(Cow::Borrowed(
+                "Get a compact Markdown snapshot of the project to start a session with: the \
+                 active ticket, the most pressing open tickets, the current spec phase and the \
+                 conventions from CLAUDE.md, cut to fit a token budget",
+            )),
+            input_schema: Arc::new(json_to_schema(json!({
+                "type": "object",
+                "properties": {
+                    "budget": {
+                        "type": ["integer", "string"],
+                        "description": "Token budget, such as 8000 or \"8k\" (default 4000)"
+                    }
+                }
+            }))),
+            annotations: None,
+        },
+    ]
+}
+
+/// Handle updating the session context
+pub async fn handle_set(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+    #[derive(Deserialize)]
+    struct Args {
+        working_dir: Option<String>,
+        ticket: Option<String>,
+    }
+
+    let args: Args =
+        serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
+
+    if let Some(dir) = args.working_dir {
+        let mut dir = PathBuf::from(dir);
+        if dir.is_relative() {
+            dir = service.project_root.join(dir);
+        }
+        service.session().set_working_dir(dir, &service.storage);
+    }
+
+    if let Some(ticket_ref) = args.ticket {
+        let id = crate::mcp::handlers::tickets::resolve_ticket_ref(service, &ticket_ref).await?;
+        service.session().record_ticket(&id);
+    }
+
+    let context = service.session().clone();
+    Ok(describe(service, &context))
+}
+
+/// Handle showing the session context
+pub fn handle_show(service: &VibeTicketService, _arguments: Value) -> Result<Value, String> {
+    let context = service.session().clone();
+    Ok(describe(service, &context))
+}
+
+/// Handle building a context pack
+pub fn handle_pack(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+    #[derive(Deserialize)]
+    struct Args {
+        budget: Option<Value>,
+    }
+
+    let args: Args =
+        serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
+    let budget = match args.budget {
+        None => DEFAULT_BUDGET,
+        Some(Value::String(budget)) => parse_budget(&budget).map_err(|e| e.to_string())?,
+        Some(budget) => budget
+            .as_u64()
+            .and_then(|budget| usize::try_from(budget).ok())
+            .filter(|budget| *budget > 0)
+            .ok_or_else(|| format!("Invalid budget: {budget}"))?,
+    };
+
+    let pack = build_context_pack(&service.project_root, &service.storage, budget)
+        .map_err(|e| format!("Failed to build the context pack: {e}"))?;
+    Ok(json!(pack))
+}
+
+/// Build the JSON description of a session context
+fn describe(service: &VibeTicketService, context: &SessionContext) -> Value {
+    let default_ticket = context
+        .default_ticket()
+        .and_then(|id| service.storage.load(id).ok())
+        .map(|t| {
+            json!({
+                "id": t.id.to_string(),
+                "slug": t.slug,
+                "title": t.title,
+            })
+        });
+
+    json!({
+        "working_dir": context.working_dir,
+        "worktree": context.worktree,
+        "worktree_ticket": context.worktree_ticket.as_ref().map(ToString::to_string),
+        "last_ticket": context.last_ticket.as_ref().map(ToString::to_string),
+        "default_ticket": default_ticket,
+    })
+}
@@ -1,67 +1,77 @@
-//! MCP event handler for CLI operations
+//! MCP event handler notifying the clients subscribed to changed resources
 
 use crate::Result;
+use crate::core::TicketId;
 use crate::events::{EventHandler, TicketEvent};
-use crate::mcp::service::VibeTicketService;
-use std::sync::Arc;
+use crate::mcp::handlers::resources::Subscriptions;
+use rmcp::model::ResourceUpdatedNotificationParam;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
 
-/// MCP event handler that processes CLI events
-pub struct McpEventHandler {}
+/// Event handler of an MCP session
+///
+/// The handler only holds a weak reference to the session's subscriptions,
+/// so it becomes inactive once the session is closed.
+pub struct McpEventHandler {
+    subscriptions: Weak<Mutex<Subscriptions>>,
+}
 
 impl McpEventHandler {
-    /// Create a new MCP event handler
-    pub fn new(_service: Arc<VibeTicketService>) -> Self {
-        Self {}
+    /// Create a handler notifying the subscribers of a session
+    pub fn new(subscriptions: &Arc<Mutex<Subscriptions>>) -> Self {
+        Self {
+            subscriptions: Arc::downgrade(subscriptions),
+        }
+    }
+}
+
+impl McpEventHandler {
+    /// Notify the session of the updated resources
+    async fn notify(&self, uris: impl FnOnce(&Subscriptions) -> Vec<String>, list_changed: bool) {
+        let Some(subscriptions) = self.subscriptions.upgrade() else {
+            return;
+        };
+        let (peer, uris) = {
+            let subscriptions = subscriptions.lock().unwrap_or_else(PoisonError::into_inner);
+            (subscriptions.peer.clone(), uris(&subscriptions))
+        };
+        let Some(peer) = peer else {
+            return;
+        };
+
+        if list_changed {
+            if let Err(e) = Box::pin(peer.notify_resource_list_changed()).await {
+                tracing::debug!("Failed to notify resource list change: {e}");
+            }
+        }
+        for uri in uris {
+            let param = ResourceUpdatedNotificationParam { uri: uri.clone() };
+            if let Err(e) = Box::pin(peer.notify_resource_updated(param)).await {
+                tracing::debug!("Failed to notify update of {uri}: {e}");
+            }
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl EventHandler for McpEventHandler {
     async fn handle_event(&self, event: TicketEvent) -> Result<()> {
-        match event {
-            TicketEvent::Created(ticket) => {
-                // Clear any cached ticket lists
-                tracing::info!("MCP: Ticket created via CLI: {}", ticket.id);
-                // In the future, we could notify connected MCP clients here
-            },
-            TicketEvent::Updated(ticket) => {
-                tracing::info!("MCP: Ticket updated via CLI: {}", ticket.id);
-                // Update any internal caches if needed
-            },
-            TicketEvent::Closed(ticket_id, message) => {
-                tracing::info!("MCP: Ticket closed via CLI: {} - {}", ticket_id, message);
-            },
-            TicketEvent::TaskAdded(ticket_id, task) => {
-                tracing::info!(
-                    "MCP: Task added via CLI to ticket {}: {}",
-                    ticket_id,
-                    task.title
-                );
-            },
-            TicketEvent::TaskCompleted(ticket_id, task_id) => {
-                tracing::info!(
-                    "MCP: Task completed via CLI: {} in ticket {}",
-                    task_id,
-                    ticket_id
-                );
-            },
-            TicketEvent::TaskRemoved(ticket_id, task_id) => {
-                tracing::info!(
-                    "MCP: Task removed via CLI: {} from ticket {}",
-                    task_id,
-                    ticket_id
-                );
-            },
-            TicketEvent::StatusChanged(ticket_id, old_status, new_status) => {
-                tracing::info!(
-                    "MCP: Ticket {} status changed via CLI: {:?} -> {:?}",
-                    ticket_id,
-                    old_status,
-                    new_status
-                );
-            },
-        }
+        let list_changed = matches!(event, TicketEvent::Created(_) | TicketEvent::SpecCreated(_));
+        let uris = |subscriptions: &Subscriptions| match (event.ticket_id(), event.spec_id()) {
+            (Some(id), _) => subscriptions.ticket_uris(id),
+            (None, Some(id)) => subscriptions.spec_uris(id),
+            (None, None) => Vec::new(),
+        };
+        Box::pin(self.notify(uris, list_changed)).await;
+        Ok(())
+    }
 
+    async fn handle_external_change(&self, ticket_id: TicketId) -> Result<()> {
+        // The change may have created the ticket
+        Box::pin(self.notify(|subscriptions| subscriptions.ticket_uris(&ticket_id), true)).await;
         Ok(())
     }
+
+    fn is_active(&self) -> bool {
+        self.subscriptions.strong_count() > 0
+    }
 }
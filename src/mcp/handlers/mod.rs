@@ -1,10 +1,62 @@
 //! MCP tool handlers for vibe-ticket operations
 
+use crate::config::TokenScope;
+
 pub mod config;
+pub mod context;
 pub mod events;
+pub mod prompts;
+pub mod report;
+pub mod resources;
 pub mod schema_helper;
 pub mod search;
 pub mod spec;
 pub mod tasks;
 pub mod tickets;
 pub mod worktree;
+
+/// Scope an API token needs to call `tool`
+///
+/// Tools reading data need [`TokenScope::ReadOnly`], tools changing tickets,
+/// tasks and specs [`TokenScope::Write`], and tools changing the
+/// configuration or removing worktrees [`TokenScope::Admin`]. Unknown tools
+/// need the admin scope.
+pub fn required_scope(tool: &str) -> TokenScope {
+    match tool.strip_prefix("vibe-ticket_").unwrap_or(tool) {
+        "list" | "show" | "check" | "task_list" | "worktree_list" | "search" | "export"
+        | "report" | "config_show" | "spec_check" | "context_set" | "context_show"
+        | "context_pack" => TokenScope::ReadOnly,
+        "new" | "edit" | "bulk_update" | "close" | "start" | "task_add" | "task_complete"
+        | "task_remove" | "import" | "rebuild_index" | "spec_add" | "spec_update" => {
+            TokenScope::Write
+        },
+        _ => TokenScope::Admin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::service::VibeTicketService;
+
+    #[test]
+    fn test_only_configuration_and_worktree_removal_need_admin() {
+        let mut admin: Vec<String> = VibeTicketService::get_tools()
+            .iter()
+            .filter(|tool| required_scope(&tool.name) == TokenScope::Admin)
+            .map(|tool| tool.name.to_string())
+            .collect();
+        admin.sort();
+        assert_eq!(
+            admin,
+            [
+                "vibe-ticket_config_set",
+                "vibe-ticket_worktree_prune",
+                "vibe-ticket_worktree_remove"
+            ]
+        );
+        assert_eq!(required_scope("vibe-ticket_show"), TokenScope::ReadOnly);
+        assert_eq!(required_scope("vibe-ticket_edit"), TokenScope::Write);
+        assert_eq!(required_scope("vibe-ticket_unknown"), TokenScope::Admin);
+    }
+}
@@ -0,0 +1,234 @@
+//! Canned MCP prompts
+//!
+//! Each prompt embeds the ticket or spec it is about and tells the assistant
+//! which tools to apply its conclusions with.
+
+use crate::core::{Status, Ticket};
+use crate::mcp::service::VibeTicketService;
+use crate::specs::{SpecManager, Specification};
+use crate::storage::TicketRepository;
+use rmcp::model::{
+    GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+use std::fmt::Write as FmtWrite;
+
+/// Most open tickets listed for spotting duplicates
+const MAX_OPEN_TICKETS: usize = 30;
+
+/// Lists the available prompts
+pub fn list_prompts() -> Vec<Prompt> {
+    let ticket = || {
+        Some(vec![PromptArgument {
+            name: "ticket".to_string(),
+            description: Some(
+                "Ticket ID, short ID or slug (defaults to the session's ticket)".to_string(),
+            ),
+            required: Some(false),
+        }])
+    };
+    vec![
+        Prompt::new(
+            "triage-ticket",
+            Some("Triage a ticket: priority, severity, tags, assignee and duplicates"),
+            ticket(),
+        ),
+        Prompt::new(
+            "plan-ticket",
+            Some("Break a ticket down into tasks"),
+            ticket(),
+        ),
+        Prompt::new(
+            "review-spec",
+            Some("Review the requirements and design of a spec"),
+            Some(vec![PromptArgument {
+                name: "spec".to_string(),
+                description: Some(
+                    "Spec ID or part of its title (defaults to the active spec)".to_string(),
+                ),
+                required: Some(false),
+            }]),
+        ),
+    ]
+}
+
+/// Builds the prompt `name` with its `arguments`
+pub fn get_prompt(
+    service: &VibeTicketService,
+    name: &str,
+    arguments: Option<&JsonObject>,
+) -> Result<GetPromptResult, String> {
+    let argument = |key: &str| {
+        arguments
+            .and_then(|arguments| arguments.get(key))
+            .and_then(|value| value.as_str())
+    };
+
+    let (description, text) = match name {
+        "triage-ticket" => {
+            let ticket = load_ticket(service, argument("ticket"))?;
+            let tickets = service
+                .storage
+                .load_all()
+                .map_err(|e| format!("Failed to load tickets: {e}"))?;
+            (
+                format!("Triage ticket '{}'", ticket.slug),
+                triage_text(&ticket, &tickets),
+            )
+        },
+        "plan-ticket" => {
+            let ticket = load_ticket(service, argument("ticket"))?;
+            (format!("Plan ticket '{}'", ticket.slug), plan_text(&ticket))
+        },
+        "review-spec" => {
+            let spec = load_spec(service, argument("spec"))?;
+            (
+                format!("Review spec '{}'", spec.metadata.title),
+                review_text(&spec),
+            )
+        },
+        _ => return Err(format!("Unknown prompt: {name}")),
+    };
+
+    Ok(GetPromptResult {
+        description: Some(description),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+/// Loads the ticket a prompt is about
+fn load_ticket(service: &VibeTicketService, reference: Option<&str>) -> Result<Ticket, String> {
+    let id = super::tickets::resolve_ticket_arg(service, reference)?;
+    service
+        .storage
+        .load(&id)
+        .map_err(|e| format!("Failed to load ticket: {e}"))
+}
+
+/// Loads the spec a prompt is about: by ID, by title, or the active spec
+fn load_spec(
+    service: &VibeTicketService,
+    reference: Option<&str>,
+) -> Result<Specification, String> {
+    let manager = SpecManager::new(service.project_root.join(".vibe-ticket").join("specs"));
+    let spec_id = match reference {
+        Some(reference) if manager.load_spec(reference).is_ok() => reference.to_string(),
+        Some(reference) => {
+            manager
+                .find_spec_by_title(reference)
+                .map_err(|e| format!("Failed to list specs: {e}"))?
+                .ok_or_else(|| format!("Spec not found: {reference}"))?
+                .id
+        },
+        None => manager
+            .get_active_spec()
+            .map_err(|e| format!("Failed to get the active spec: {e}"))?
+            .ok_or_else(|| "No active spec. Please specify a spec.".to_string())?,
+    };
+    manager
+        .load_spec(&spec_id)
+        .map_err(|e| format!("Failed to load spec: {e}"))
+}
+
+/// Markdown summary of a ticket
+fn ticket_summary(ticket: &Ticket) -> String {
+    let mut text = format!("# {}\n\n", ticket.title);
+    writeln!(text, "- ID: {} ({})", ticket.display_id(), ticket.slug).unwrap();
+    writeln!(text, "- Status: {}", ticket.status).unwrap();
+    writeln!(text, "- Priority: {}", ticket.priority).unwrap();
+    if let Some(severity) = ticket.severity {
+        writeln!(text, "- Severity: {severity}").unwrap();
+    }
+    if !ticket.tags.is_empty() {
+        writeln!(text, "- Tags: {}", ticket.tags.join(", ")).unwrap();
+    }
+    if let Some(assignee) = &ticket.assignee {
+        writeln!(text, "- Assignee: {assignee}").unwrap();
+    }
+    if !ticket.description.is_empty() {
+        write!(text, "\n{}\n", ticket.description).unwrap();
+    }
+    if !ticket.tasks.is_empty() {
+        text.push_str("\n## Tasks\n\n");
+        for task in &ticket.tasks {
+            let checkbox = if task.completed { "[x]" } else { "[ ]" };
+            writeln!(text, "- {checkbox} {}", task.title).unwrap();
+        }
+    }
+    text
+}
+
+/// Text of the `triage-ticket` prompt
+fn triage_text(ticket: &Ticket, tickets: &[Ticket]) -> String {
+    let mut text = String::from(
+        "Triage the ticket below. Suggest its priority (low, medium, high, critical), its \
+         severity (S1-S4) if it is an incident, its tags and who should work on it, and say \
+         whether it duplicates one of the other open tickets. Apply the changes with \
+         `vibe-ticket_edit` once they are agreed on.\n\n",
+    );
+    text.push_str(&ticket_summary(ticket));
+
+    let open: Vec<&Ticket> = tickets
+        .iter()
+        .filter(|other| other.id != ticket.id && other.status != Status::Done)
+        .take(MAX_OPEN_TICKETS)
+        .collect();
+    if !open.is_empty() {
+        text.push_str("\n## Other open tickets\n\n");
+        for other in open {
+            writeln!(
+                text,
+                "- {} ({}): {}",
+                other.slug, other.priority, other.title
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+/// Text of the `plan-ticket` prompt
+fn plan_text(ticket: &Ticket) -> String {
+    let mut text = String::from(
+        "Break the ticket below down into small, verifiable tasks in the order they should \
+         be done. Leave out tasks it already has. Add them with `vibe-ticket_task_add` once \
+         they are agreed on.\n\n",
+    );
+    text.push_str(&ticket_summary(ticket));
+    text
+}
+
+/// Text of the `review-spec` prompt
+fn review_text(spec: &Specification) -> String {
+    let mut text = format!(
+        "Review the spec '{}' (phase: {}). Point out missing or ambiguous requirements, \
+         design decisions that do not cover them, and risks. Quote the parts you comment \
+         on.\n",
+        spec.metadata.title, spec.metadata.progress.current_phase
+    );
+    for (heading, document) in [
+        ("Requirements", &spec.requirements),
+        ("Design", &spec.design),
+    ] {
+        let content = document.as_deref().unwrap_or("(not written yet)");
+        write!(text, "\n## {heading}\n\n{content}\n").unwrap();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triage_text_lists_other_open_tickets() {
+        let ticket = Ticket::new("fix-login", "Fix login");
+        let mut closed = Ticket::new("old-login", "Old login");
+        closed.status = Status::Done;
+        let open = Ticket::new("login-error", "Login error");
+
+        let text = triage_text(&ticket, &[ticket.clone(), closed, open]);
+        assert!(text.contains("# Fix login"));
+        assert!(text.contains("- login-error (Medium): Login error"));
+        assert!(!text.contains("old-login"));
+    }
+}
@@ -0,0 +1,97 @@
+//! Report MCP tool handlers
+
+use crate::cli::handlers::parse_date_filter;
+use crate::core::{Report, ReportGroupBy, ReportTemplate};
+use crate::mcp::handlers::schema_helper::json_to_schema;
+use crate::mcp::service::VibeTicketService;
+use crate::storage::TicketRepository;
+use chrono::{Duration, Utc};
+use rmcp::model::Tool;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Register all report tools
+pub fn register_tools() -> Vec<Tool> {
+    vec![Tool {
+        name: Cow::Borrowed("vibe-ticket_report"),
+        description: Some(Cow::Borrowed(
+            "Report ticket activity in a time window (e.g. what was shipped last week), \
+             as markdown and structured data",
+        )),
+        input_schema: Arc::new(json_to_schema(json!({
+            "type": "object",
+            "properties": {
+                "template": {
+                    "type": "string",
+                    "enum": ReportTemplate::NAMES,
+                    "description": "Activity to report: tickets created, started or closed (summary), closed (shipped), created or started",
+                    "default": "summary"
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Start of the window (e.g. \"7 days ago\", \"yesterday\", \"2025-07-01\"); defaults to 7 days ago"
+                },
+                "until": {
+                    "type": "string",
+                    "description": "End of the window, exclusive; defaults to now"
+                },
+                "group_by": {
+                    "type": "string",
+                    "enum": ReportGroupBy::NAMES,
+                    "description": "Field to group the tickets by",
+                    "default": "none"
+                }
+            }
+        }))),
+        annotations: None,
+    }]
+}
+
+/// Handle building a report
+pub fn handle_report(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+    #[derive(Deserialize)]
+    struct Args {
+        template: Option<String>,
+        since: Option<String>,
+        until: Option<String>,
+        group_by: Option<String>,
+    }
+
+    let args: Args =
+        serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
+
+    let template = args
+        .template
+        .as_deref()
+        .map_or(Ok(ReportTemplate::Summary), str::parse)
+        .map_err(|e| e.to_string())?;
+    let group_by = args
+        .group_by
+        .as_deref()
+        .map_or(Ok(ReportGroupBy::None), str::parse)
+        .map_err(|e| e.to_string())?;
+    let since = match args.since.as_deref() {
+        Some(since) => parse_date_filter(since).map_err(|e| e.to_string())?,
+        None => Utc::now() - Duration::days(7),
+    };
+    let until = match args.until.as_deref() {
+        Some(until) => parse_date_filter(until).map_err(|e| e.to_string())?,
+        None => Utc::now(),
+    };
+    if since >= until {
+        return Err("'since' must be before 'until'".to_string());
+    }
+
+    let tickets = service
+        .storage
+        .load_all()
+        .map_err(|e| format!("Failed to list tickets: {e}"))?;
+    let report = Report::build(template, &tickets, since, until, group_by);
+
+    Ok(json!({
+        "markdown": report.to_markdown(),
+        "report": report,
+    }))
+}
@@ -0,0 +1,258 @@
+//! MCP resources: tickets and spec documents
+//!
+//! Tickets are exposed as `vibe-ticket://tickets/<id>` (JSON) and spec
+//! documents as `vibe-ticket://specs/<id>/<document>` (Markdown), where
+//! `<document>` is `requirements`, `design` or `tasks`. A ticket URI also
+//! accepts a short ID or a slug. Clients subscribed to a ticket are notified
+//! when it changes, and clients subscribed to a spec document when the spec
+//! goes through its lifecycle (see [`super::events::McpEventHandler`]).
+
+use crate::cli::schema::versioned;
+use crate::core::TicketId;
+use crate::mcp::service::VibeTicketService;
+use crate::specs::{SpecDocumentType, SpecManager};
+use crate::storage::TicketRepository;
+use rmcp::model::{
+    AnnotateAble, RawResource, RawResourceTemplate, ReadResourceResult, Resource, ResourceContents,
+    ResourceTemplate,
+};
+use rmcp::{Peer, RoleServer};
+use std::collections::HashMap;
+
+/// Prefix of the URIs of tickets
+const TICKETS_PREFIX: &str = "vibe-ticket://tickets/";
+
+/// Prefix of the URIs of spec documents
+const SPECS_PREFIX: &str = "vibe-ticket://specs/";
+
+/// Spec documents, by the name used in their URI
+const SPEC_DOCUMENTS: [(&str, SpecDocumentType); 3] = [
+    ("requirements", SpecDocumentType::Requirements),
+    ("design", SpecDocumentType::Design),
+    ("tasks", SpecDocumentType::Tasks),
+];
+
+/// Resource subscriptions of an MCP session
+#[derive(Default)]
+pub struct Subscriptions {
+    /// Client of the session, known once it is initialized
+    pub peer: Option<Peer<RoleServer>>,
+
+    /// Subscribed URIs, with the ticket or spec they refer to
+    pub uris: HashMap<String, SubscriptionTarget>,
+}
+
+/// Ticket or spec a subscribed URI refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionTarget {
+    /// A ticket, by ID
+    Ticket(TicketId),
+    /// A document of the spec with this ID
+    Spec(String),
+}
+
+impl Subscriptions {
+    /// Subscribed URIs of the ticket `id`
+    pub fn ticket_uris(&self, id: &TicketId) -> Vec<String> {
+        self.uris_of(|target| matches!(target, SubscriptionTarget::Ticket(t) if t == id))
+    }
+
+    /// Subscribed URIs of the documents of the spec `id`
+    pub fn spec_uris(&self, id: &str) -> Vec<String> {
+        self.uris_of(|target| matches!(target, SubscriptionTarget::Spec(s) if s == id))
+    }
+
+    fn uris_of(&self, matches: impl Fn(&SubscriptionTarget) -> bool) -> Vec<String> {
+        self.uris
+            .iter()
+            .filter(|(_, target)| matches(target))
+            .map(|(uri, _)| uri.clone())
+            .collect()
+    }
+}
+
+/// URI of a ticket
+pub fn ticket_uri(id: &TicketId) -> String {
+    format!("{TICKETS_PREFIX}{id}")
+}
+
+/// URI of a spec document
+fn spec_uri(spec_id: &str, document: &str) -> String {
+    format!("{SPECS_PREFIX}{spec_id}/{document}")
+}
+
+/// Resource a URI refers to
+enum ResourceRef<'a> {
+    /// Ticket, by ID, short ID or slug
+    Ticket(&'a str),
+    /// Document of a spec
+    Spec(&'a str, SpecDocumentType),
+}
+
+/// Parses a resource URI
+fn parse_uri(uri: &str) -> Result<ResourceRef<'_>, String> {
+    if let Some(reference) = uri.strip_prefix(TICKETS_PREFIX) {
+        if !reference.is_empty() && !reference.contains('/') {
+            return Ok(ResourceRef::Ticket(reference));
+        }
+    } else if let Some(rest) = uri.strip_prefix(SPECS_PREFIX) {
+        if let Some((spec_id, name)) = rest.split_once('/') {
+            if let Some((_, document)) = SPEC_DOCUMENTS.iter().find(|(n, _)| *n == name) {
+                return Ok(ResourceRef::Spec(spec_id, *document));
+            }
+        }
+    }
+    Err(format!("Unknown resource: {uri}"))
+}
+
+/// Spec manager of the project
+fn spec_manager(service: &VibeTicketService) -> SpecManager {
+    SpecManager::new(service.project_root.join(".vibe-ticket").join("specs"))
+}
+
+/// Lists the tickets and the existing spec documents
+pub fn list_resources(service: &VibeTicketService) -> Result<Vec<Resource>, String> {
+    let tickets = service
+        .storage
+        .load_all()
+        .map_err(|e| format!("Failed to load tickets: {e}"))?;
+    let mut resources: Vec<Resource> = tickets
+        .iter()
+        .map(|ticket| {
+            RawResource {
+                uri: ticket_uri(&ticket.id),
+                name: ticket.slug.clone(),
+                description: Some(ticket.title.clone()),
+                mime_type: Some("application/json".to_string()),
+                size: None,
+            }
+            .no_annotation()
+        })
+        .collect();
+
+    let manager = spec_manager(service);
+    let specs = manager
+        .list_specs()
+        .map_err(|e| format!("Failed to list specs: {e}"))?;
+    for spec in specs {
+        for (name, document) in SPEC_DOCUMENTS {
+            if !manager.get_document_path(&spec.id, document).is_file() {
+                continue;
+            }
+            resources.push(
+                RawResource {
+                    uri: spec_uri(&spec.id, name),
+                    name: format!("{} ({name})", spec.title),
+                    description: Some(document.display_name().to_string()),
+                    mime_type: Some("text/markdown".to_string()),
+                    size: None,
+                }
+                .no_annotation(),
+            );
+        }
+    }
+
+    Ok(resources)
+}
+
+/// Templates of the resource URIs
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        RawResourceTemplate {
+            uri_template: format!("{TICKETS_PREFIX}{{ticket}}"),
+            name: "ticket".to_string(),
+            description: Some("A ticket as JSON, by ID, short ID or slug".to_string()),
+            mime_type: Some("application/json".to_string()),
+        }
+        .no_annotation(),
+        RawResourceTemplate {
+            uri_template: format!("{SPECS_PREFIX}{{spec}}/{{document}}"),
+            name: "spec-document".to_string(),
+            description: Some(
+                "A spec document (requirements, design or tasks) as Markdown".to_string(),
+            ),
+            mime_type: Some("text/markdown".to_string()),
+        }
+        .no_annotation(),
+    ]
+}
+
+/// Reads the resource at `uri`
+pub async fn read_resource(
+    service: &VibeTicketService,
+    uri: &str,
+) -> Result<ReadResourceResult, String> {
+    let (mime_type, text) = match parse_uri(uri)? {
+        ResourceRef::Ticket(reference) => {
+            let id = super::tickets::resolve_ticket_ref(service, reference).await?;
+            let ticket = service
+                .storage
+                .load(&id)
+                .map_err(|e| format!("Failed to load ticket: {e}"))?;
+            let content = versioned(serde_json::json!(ticket));
+            (
+                "application/json",
+                serde_json::to_string_pretty(&content).map_err(|e| e.to_string())?,
+            )
+        },
+        ResourceRef::Spec(spec_id, document) => {
+            let spec = spec_manager(service)
+                .load_spec(spec_id)
+                .map_err(|e| format!("Failed to load spec: {e}"))?;
+            let content = match document {
+                SpecDocumentType::Requirements => spec.requirements,
+                SpecDocumentType::Design => spec.design,
+                SpecDocumentType::Tasks => spec.tasks,
+            };
+            let text = content.ok_or_else(|| {
+                format!(
+                    "Spec '{}' has no {} document",
+                    spec.metadata.title,
+                    document.display_name()
+                )
+            })?;
+            ("text/markdown", text)
+        },
+    };
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            text,
+        }],
+    })
+}
+
+/// Ticket or spec a subscribed URI refers to, failing for unknown resources
+pub async fn subscription_target(
+    service: &VibeTicketService,
+    uri: &str,
+) -> Result<SubscriptionTarget, String> {
+    match parse_uri(uri)? {
+        ResourceRef::Ticket(reference) => super::tickets::resolve_ticket_ref(service, reference)
+            .await
+            .map(SubscriptionTarget::Ticket),
+        ResourceRef::Spec(spec_id, _) => Ok(SubscriptionTarget::Spec(spec_id.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_uri() {
+        assert!(matches!(
+            parse_uri("vibe-ticket://tickets/fix-login"),
+            Ok(ResourceRef::Ticket("fix-login"))
+        ));
+        assert!(matches!(
+            parse_uri("vibe-ticket://specs/abc/design"),
+            Ok(ResourceRef::Spec("abc", SpecDocumentType::Design))
+        ));
+        assert!(parse_uri("vibe-ticket://specs/abc/notes").is_err());
+        assert!(parse_uri("vibe-ticket://tickets/").is_err());
+        assert!(parse_uri("file:///tmp/x").is_err());
+    }
+}
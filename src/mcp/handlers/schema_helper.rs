@@ -1,4 +1,13 @@
-//! Helper functions for creating MCP tool schemas
+//! Helper functions for creating MCP tool schemas and checking tool
+//! arguments against them
+//!
+//! Tool calls are validated against the `input_schema` of the tool before
+//! the handler runs (see [`validate`]). The supported subset of JSON Schema
+//! is the one the tool schemas use: `type` (a name or a list of names),
+//! `enum`, `required`, `properties`, `items` and `additionalProperties:
+//! false`. A `null` optional property counts as absent.
+
+use std::fmt;
 
 use serde_json::{Map, Value, json};
 
@@ -14,3 +23,303 @@ pub fn json_to_schema(value: Value) -> Map<String, Value> {
         },
     }
 }
+
+/// Why tool arguments do not match the input schema of the tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Path of the offending argument, such as `tags[1]`; empty for the
+    /// arguments as a whole
+    pub field: String,
+
+    /// What is wrong with it
+    pub reason: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "Invalid arguments: {}", self.reason)
+        } else {
+            write!(f, "Invalid argument '{}': {}", self.field, self.reason)
+        }
+    }
+}
+
+impl SchemaViolation {
+    fn new(field: &str, reason: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Top-level argument the violation is about, if any
+    pub fn argument(&self) -> Option<&str> {
+        let end = self.field.find(['.', '[']).unwrap_or(self.field.len());
+        Some(&self.field[..end]).filter(|argument| !argument.is_empty())
+    }
+}
+
+/// Checks tool arguments against the input schema of the tool
+///
+/// # Errors
+///
+/// Returns the first argument that does not match the schema
+pub fn validate(schema: &Map<String, Value>, arguments: &Value) -> Result<(), SchemaViolation> {
+    validate_at(schema, arguments, "")
+}
+
+fn validate_at(
+    schema: &Map<String, Value>,
+    value: &Value,
+    path: &str,
+) -> Result<(), SchemaViolation> {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+        return Err(SchemaViolation::new(
+            path,
+            format!("expected {}, got {}", types.join(" or "), type_name(value)),
+        ));
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            return Err(SchemaViolation::new(
+                path,
+                format!("must be one of {}, got {value}", options.join(", ")),
+            ));
+        }
+    }
+
+    match value {
+        Value::Object(arguments) => validate_object(schema, arguments, path),
+        Value::Array(items) => {
+            schema
+                .get("items")
+                .and_then(Value::as_object)
+                .map_or(Ok(()), |item_schema| {
+                    items.iter().enumerate().try_for_each(|(index, item)| {
+                        validate_at(item_schema, item, &format!("{path}[{index}]"))
+                    })
+                })
+        },
+        _ => Ok(()),
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    arguments: &Map<String, Value>,
+    path: &str,
+) -> Result<(), SchemaViolation> {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    for name in &required {
+        if arguments.get(*name).is_none_or(Value::is_null) {
+            return Err(SchemaViolation::new(&join(path, name), "is required"));
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+    for (name, value) in arguments {
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(Value::Object(property))
+                if !value.is_null() || required.contains(&name.as_str()) =>
+            {
+                validate_at(property, value, &join(path, name))?;
+            },
+            None if closed => {
+                let known: Vec<&String> =
+                    properties.map(|p| p.keys().collect()).unwrap_or_default();
+                return Err(SchemaViolation::new(
+                    &join(path, name),
+                    format!(
+                        "is not a known argument; known arguments are {}",
+                        known
+                            .iter()
+                            .map(|name| format!("'{name}'"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ));
+            },
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+/// Example of valid arguments for a tool
+///
+/// Has the required arguments and `argument`, if given, with their
+/// defaults, first allowed values or placeholders of their type.
+pub fn example(schema: &Map<String, Value>, argument: Option<&str>) -> Value {
+    let mut example = Map::new();
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Value::Object(example);
+    };
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    for name in required.chain(argument) {
+        if let Some(property) = properties.get(name) {
+            example.insert(name.to_string(), example_value(property));
+        }
+    }
+    Value::Object(example)
+}
+
+/// Example of a value matching `schema`
+fn example_value(schema: &Value) -> Value {
+    if let Some(default) = schema.get("default") {
+        return default.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|options| options.first())
+    {
+        return first.clone();
+    }
+    let name = match schema.get("type") {
+        Some(Value::Array(names)) => names.first().and_then(Value::as_str),
+        Some(name) => name.as_str(),
+        None => None,
+    };
+    match name {
+        Some("integer" | "number") => json!(1),
+        Some("boolean") => json!(true),
+        Some("array") => json!([schema
+            .get("items")
+            .map_or_else(|| json!("example"), example_value)]),
+        Some("object") => schema
+            .as_object()
+            .map_or_else(|| json!({}), |schema| example(schema, None)),
+        _ => json!("example"),
+    }
+}
+
+/// Whether `value` is of the JSON Schema type `name`
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// JSON Schema type name of `value`
+const fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Map<String, Value> {
+        json_to_schema(json!({
+            "type": "object",
+            "properties": {
+                "slug": {"type": "string"},
+                "priority": {"type": "string", "enum": ["low", "high"], "default": "low"},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "budget": {"type": ["integer", "string"]}
+            },
+            "required": ["slug"]
+        }))
+    }
+
+    #[test]
+    fn test_validate_reports_the_failing_argument() {
+        let schema = schema();
+        assert!(validate(&schema, &json!({"slug": "a", "budget": 8000})).is_ok());
+        assert!(validate(&schema, &json!({"slug": "a", "priority": null, "extra": 1})).is_ok());
+
+        let cases = [
+            (json!({}), "slug", "is required"),
+            (json!({"slug": 1}), "slug", "expected string, got number"),
+            (
+                json!({"slug": "a", "priority": "urgent"}),
+                "priority",
+                "must be one of \"low\", \"high\", got \"urgent\"",
+            ),
+            (
+                json!({"slug": "a", "tags": ["ok", 2]}),
+                "tags[1]",
+                "expected string, got number",
+            ),
+            (
+                json!({"slug": "a", "budget": 1.5}),
+                "budget",
+                "expected integer or string, got number",
+            ),
+        ];
+        for (arguments, field, reason) in cases {
+            let violation = validate(&schema, &arguments).unwrap_err();
+            assert_eq!(violation, SchemaViolation::new(field, reason));
+        }
+
+        let violation = validate(&schema, &json!([])).unwrap_err();
+        assert_eq!(
+            violation.to_string(),
+            "Invalid arguments: expected object, got array"
+        );
+        assert_eq!(violation.argument(), None);
+    }
+
+    #[test]
+    fn test_example_is_valid() {
+        let schema = schema();
+        let violation = validate(&schema, &json!({"slug": "a", "tags": [1]})).unwrap_err();
+        assert_eq!(violation.argument(), Some("tags"));
+        let example = example(&schema, violation.argument());
+        assert_eq!(example, json!({"slug": "example", "tags": ["example"]}));
+        assert!(validate(&schema, &example).is_ok());
+    }
+
+    #[test]
+    fn test_tool_examples_are_valid() {
+        for tool in crate::mcp::service::VibeTicketService::get_tools() {
+            let example = example(&tool.input_schema, None);
+            assert_eq!(
+                validate(&tool.input_schema, &example),
+                Ok(()),
+                "example of {}",
+                tool.name
+            );
+        }
+    }
+}
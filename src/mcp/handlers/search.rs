@@ -1,7 +1,10 @@
 //! Search and export MCP tool handlers
 
+use crate::cli::handlers::parse_query;
 use crate::core::Ticket;
+use crate::events::TicketEvent;
 use crate::mcp::handlers::schema_helper::json_to_schema;
+use crate::mcp::progress::Progress;
 use crate::mcp::service::VibeTicketService;
 use crate::storage::TicketRepository;
 use rmcp::model::Tool;
@@ -16,7 +19,9 @@ pub fn register_tools() -> Vec<Tool> {
         // Search tool
         Tool {
             name: Cow::Borrowed("vibe-ticket_search"),
-            description: Some(Cow::Borrowed("Search tickets by keyword")),
+            description: Some(Cow::Borrowed(
+                "Search tickets by keyword and/or query expression",
+            )),
             input_schema: Arc::new(json_to_schema(json!({
                 "type": "object",
                 "properties": {
@@ -24,6 +29,10 @@ pub fn register_tools() -> Vec<Tool> {
                         "type": "string",
                         "description": "Search query"
                     },
+                    "where": {
+                        "type": "string",
+                        "description": "Query expression the tickets must match, e.g. \"status:doing AND (tag:backend OR priority:high) created:>2025-01-01\""
+                    },
                     "in_title": {
                         "type": "boolean",
                         "description": "Search in titles only"
@@ -36,8 +45,7 @@ pub fn register_tools() -> Vec<Tool> {
                         "type": "boolean",
                         "description": "Search in tasks"
                     }
-                },
-                "required": ["query"]
+                }
             }))),
             annotations: None,
         },
@@ -65,7 +73,9 @@ pub fn register_tools() -> Vec<Tool> {
         // Import tool
         Tool {
             name: Cow::Borrowed("vibe-ticket_import"),
-            description: Some(Cow::Borrowed("Import tickets from JSON or YAML")),
+            description: Some(Cow::Borrowed(
+                "Import tickets from JSON or YAML all-or-nothing, reporting progress per ticket",
+            )),
             input_schema: Arc::new(json_to_schema(json!({
                 "type": "object",
                 "properties": {
@@ -84,6 +94,18 @@ pub fn register_tools() -> Vec<Tool> {
             }))),
             annotations: None,
         },
+        // Index rebuild tool
+        Tool {
+            name: Cow::Borrowed("vibe-ticket_rebuild_index"),
+            description: Some(Cow::Borrowed(
+                "Rebuild the listing index from the ticket files, reporting progress per file",
+            )),
+            input_schema: Arc::new(json_to_schema(json!({
+                "type": "object",
+                "properties": {}
+            }))),
+            annotations: None,
+        },
     ]
 }
 
@@ -91,7 +113,9 @@ pub fn register_tools() -> Vec<Tool> {
 pub fn handle_search(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        query: String,
+        query: Option<String>,
+        #[serde(rename = "where")]
+        where_clause: Option<String>,
         in_title: Option<bool>,
         in_description: Option<bool>,
         in_tasks: Option<bool>,
@@ -100,61 +124,74 @@ pub fn handle_search(service: &VibeTicketService, arguments: Value) -> Result<Va
     let args: Args =
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
-    let query = args.query.to_lowercase();
+    if args.query.is_none() && args.where_clause.is_none() {
+        return Err("Either 'query' or 'where' is required".to_string());
+    }
+    let expression = args
+        .where_clause
+        .as_deref()
+        .map(|source| parse_query(&service.project_root, source))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let query = args.query.as_deref().map(str::to_lowercase);
     let search_all = !args.in_title.unwrap_or(false)
         && !args.in_description.unwrap_or(false)
         && !args.in_tasks.unwrap_or(false);
 
-    let tickets = service
+    let mut tickets = service
         .storage
         .load_all()
         .map_err(|e| format!("Failed to list tickets: {}", e))?;
+    if let Some(expression) = &expression {
+        tickets.retain(|t| expression.matches(t));
+    }
 
     let mut results = Vec::new();
 
     for ticket in tickets {
         let mut matches = Vec::new();
+        if let Some(query) = &query {
+            // Search in title
+            if (search_all || args.in_title.unwrap_or(false))
+                && ticket.title.to_lowercase().contains(query.as_str())
+            {
+                matches.push("title");
+            }
 
-        // Search in title
-        if (search_all || args.in_title.unwrap_or(false))
-            && ticket.title.to_lowercase().contains(&query)
-        {
-            matches.push("title");
-        }
-
-        // Search in description
-        if (search_all || args.in_description.unwrap_or(false))
-            && ticket.description.to_lowercase().contains(&query)
-        {
-            matches.push("description");
-        }
+            // Search in description
+            if (search_all || args.in_description.unwrap_or(false))
+                && ticket.description.to_lowercase().contains(query.as_str())
+            {
+                matches.push("description");
+            }
 
-        // Search in tasks
-        if search_all || args.in_tasks.unwrap_or(false) {
-            for task in &ticket.tasks {
-                if task.title.to_lowercase().contains(&query) {
-                    matches.push("tasks");
-                    break;
-                }
+            // Search in tasks
+            if (search_all || args.in_tasks.unwrap_or(false))
+                && ticket
+                    .tasks
+                    .iter()
+                    .any(|task| task.title.to_lowercase().contains(query.as_str()))
+            {
+                matches.push("tasks");
             }
-        }
 
-        // Search in slug if searching all
-        if search_all && ticket.slug.to_lowercase().contains(&query) {
-            matches.push("slug");
-        }
+            // Search in slug if searching all
+            if search_all && ticket.slug.to_lowercase().contains(query.as_str()) {
+                matches.push("slug");
+            }
 
-        // Search in tags if searching all
-        if search_all {
-            for tag in &ticket.tags {
-                if tag.to_lowercase().contains(&query) {
-                    matches.push("tags");
-                    break;
-                }
+            // Search in tags if searching all
+            if search_all
+                && ticket
+                    .tags
+                    .iter()
+                    .any(|tag| tag.to_lowercase().contains(query.as_str()))
+            {
+                matches.push("tags");
             }
         }
 
-        if !matches.is_empty() {
+        if query.is_none() || !matches.is_empty() {
             results.push(json!({
                 "id": ticket.id.to_string(),
                 "slug": ticket.slug,
@@ -169,6 +206,7 @@ pub fn handle_search(service: &VibeTicketService, arguments: Value) -> Result<Va
 
     Ok(json!({
         "query": args.query,
+        "where": args.where_clause,
         "results": results,
         "count": results.len()
     }))
@@ -220,7 +258,16 @@ pub async fn handle_export(service: &VibeTicketService, arguments: Value) -> Res
 }
 
 /// Handle importing tickets
-pub fn handle_import(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+///
+/// The new tickets are saved together in a transaction, so either all of
+/// them are imported or none. Progress is reported per staged ticket; when
+/// the client cancels the request before the transaction is committed, it is
+/// rolled back and no ticket is imported.
+pub async fn handle_import(
+    service: &VibeTicketService,
+    arguments: Value,
+    progress: &Progress,
+) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
         data: String,
@@ -241,45 +288,69 @@ pub fn handle_import(service: &VibeTicketService, arguments: Value) -> Result<Va
             _ => return Err(format!("Invalid format: {}", format)),
         };
 
-    let mut imported_count = 0;
-    let mut skipped_count = 0;
-    let mut errors = Vec::new();
-
-    for ticket in tickets {
-        // Check if ticket already exists
-        if service
-            .storage
-            .load_all()
-            .map(|existing| {
-                existing
-                    .iter()
-                    .any(|t| t.id == ticket.id || t.slug == ticket.slug)
-            })
-            .unwrap_or(false)
-        {
-            skipped_count += 1;
-            continue;
-        }
-
-        match service.storage.save(&ticket) {
-            Ok(_) => imported_count += 1,
-            Err(e) => errors.push(format!("Failed to import '{}': {}", ticket.slug, e)),
+    // Tickets whose ID or slug is taken are skipped
+    let mut index = service
+        .storage
+        .ticket_index()
+        .map_err(|e| format!("Failed to load tickets: {e}"))?;
+    let (new, skipped): (Vec<Ticket>, Vec<Ticket>) = tickets
+        .into_iter()
+        .partition(|ticket| !index.contains(&ticket.id) && index.add(ticket).is_ok());
+
+    let total = new.len();
+    progress
+        .report(0, total, || format!("Importing {total} ticket(s)"))
+        .await;
+    let mut transaction = service.storage.transaction();
+    for ticket in &new {
+        transaction.save(ticket.clone());
+    }
+    let committed = transaction
+        .commit_with(progress.steps(|done, total| format!("Staged {done} of {total} ticket(s)")))
+        .map_err(|e| format!("No ticket was imported: {e}"))?;
+
+    let imported_count = if committed { new.len() } else { 0 };
+    if committed {
+        for ticket in new {
+            VibeTicketService::emit(TicketEvent::Created(ticket)).await;
         }
     }
 
     let mut response = json!({
         "imported": imported_count,
-        "skipped": skipped_count,
-        "total": imported_count + skipped_count
+        "skipped": skipped.len(),
+        "total": imported_count + skipped.len()
     });
 
-    if !errors.is_empty() {
-        response["errors"] = json!(errors);
+    if !committed {
+        response["cancelled"] = json!(true);
     }
 
     Ok(response)
 }
 
+/// Handle rebuilding the listing index
+///
+/// Reports progress per ticket file; when the client cancels the request,
+/// the index is left as it was.
+pub fn handle_rebuild_index(
+    service: &VibeTicketService,
+    _arguments: Value,
+    progress: &Progress,
+) -> Result<Value, String> {
+    let rebuilt = service
+        .storage
+        .rebuild_index_with(
+            progress.steps(|done, total| format!("Indexed {done} of {total} ticket file(s)")),
+        )
+        .map_err(|e| format!("Failed to rebuild the index: {e}"))?;
+
+    Ok(rebuilt.map_or_else(
+        || json!({ "indexed": 0, "cancelled": true }),
+        |indexed| json!({ "indexed": indexed }),
+    ))
+}
+
 /// Export tickets to CSV format
 fn export_to_csv(tickets: &[Ticket]) -> Result<String, String> {
     use std::io::Write as IoWrite;
@@ -390,3 +461,73 @@ fn escape_csv(field: &str) -> String {
         field.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{FileStorage, INDEX_KEY};
+    use tempfile::TempDir;
+    use tokio_util::sync::CancellationToken;
+
+    #[tokio::test]
+    async fn test_cancelled_import_is_rolled_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = VibeTicketService::new(
+            FileStorage::new(temp_dir.path().join(".vibe-ticket")),
+            temp_dir.path().to_path_buf(),
+        );
+        let parent = Ticket::new("first", "First");
+        let mut child = Ticket::new("second", "Second");
+        child.parent = Some(parent.id.clone());
+        let data = serde_json::to_string(&[child, parent]).unwrap();
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let progress = Progress::detached(cancellation);
+        let result = handle_import(&service, json!({ "data": data }), &progress)
+            .await
+            .unwrap();
+        assert_eq!(result["imported"], 0);
+        assert_eq!(result["cancelled"], true);
+        assert!(service.storage.load_all().unwrap().is_empty());
+
+        let progress = Progress::default();
+        let result = handle_import(&service, json!({ "data": data }), &progress)
+            .await
+            .unwrap();
+        assert_eq!(result["imported"], 2);
+        assert!(result.get("cancelled").is_none());
+
+        let result = handle_import(&service, json!({ "data": data }), &progress)
+            .await
+            .unwrap();
+        assert_eq!(result["imported"], 0);
+        assert_eq!(result["skipped"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_index_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = VibeTicketService::new(
+            FileStorage::new(temp_dir.path().join(".vibe-ticket")),
+            temp_dir.path().to_path_buf(),
+        );
+        service
+            .storage
+            .save(&Ticket::new("indexed", "Indexed"))
+            .unwrap();
+        let index = temp_dir.path().join(".vibe-ticket").join(INDEX_KEY);
+        assert!(!index.exists());
+
+        let cancellation = CancellationToken::new();
+        let progress = Progress::detached(cancellation.clone());
+        cancellation.cancel();
+        let result = handle_rebuild_index(&service, json!({}), &progress).unwrap();
+        assert_eq!(result["cancelled"], true);
+        assert!(!index.exists());
+
+        let result = handle_rebuild_index(&service, json!({}), &Progress::default()).unwrap();
+        assert_eq!(result["indexed"], 1);
+        assert!(index.exists());
+    }
+}
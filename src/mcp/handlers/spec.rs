@@ -1,5 +1,6 @@
 //! Spec-driven development MCP tool handlers
 
+use crate::events::TicketEvent;
 use crate::mcp::handlers::schema_helper::json_to_schema;
 use crate::mcp::service::VibeTicketService;
 use crate::storage::TicketRepository;
@@ -21,7 +22,7 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     },
                     "spec_type": {
                         "type": "string",
@@ -33,7 +34,7 @@ pub fn register_tools() -> Vec<Tool> {
                         "description": "Specification content"
                     }
                 },
-                "required": ["ticket", "spec_type", "content"]
+                "required": ["spec_type", "content"]
             }))),
             annotations: None,
         },
@@ -46,7 +47,7 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     },
                     "spec_type": {
                         "type": "string",
@@ -58,7 +59,7 @@ pub fn register_tools() -> Vec<Tool> {
                         "description": "Updated specification content"
                     }
                 },
-                "required": ["ticket", "spec_type", "content"]
+                "required": ["spec_type", "content"]
             }))),
             annotations: None,
         },
@@ -71,10 +72,9 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     }
-                },
-                "required": ["ticket"]
+                }
             }))),
             annotations: None,
         },
@@ -85,7 +85,7 @@ pub fn register_tools() -> Vec<Tool> {
 pub async fn handle_add(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        ticket: String,
+        ticket: Option<String>,
         spec_type: String,
         content: Value,
     }
@@ -94,7 +94,7 @@ pub async fn handle_add(service: &VibeTicketService, arguments: Value) -> Result
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
     let ticket_id =
-        crate::mcp::handlers::tickets::resolve_ticket_ref(service, &args.ticket).await?;
+        crate::mcp::handlers::tickets::resolve_ticket_arg(service, args.ticket.as_deref())?;
     let mut ticket = service
         .storage
         .load(&ticket_id)
@@ -119,6 +119,7 @@ pub async fn handle_add(service: &VibeTicketService, arguments: Value) -> Result
         .storage
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
+    VibeTicketService::emit(TicketEvent::Updated(ticket.clone())).await;
 
     Ok(json!({
         "status": "added",
@@ -131,21 +132,21 @@ pub async fn handle_add(service: &VibeTicketService, arguments: Value) -> Result
 /// Handle updating specifications
 pub async fn handle_update(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     // Update uses the same logic as add
-    handle_add(service, arguments).await
+    Box::pin(handle_add(service, arguments)).await
 }
 
 /// Handle checking specification status
 pub async fn handle_check(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        ticket: String,
+        ticket: Option<String>,
     }
 
     let args: Args =
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
     let ticket_id =
-        crate::mcp::handlers::tickets::resolve_ticket_ref(service, &args.ticket).await?;
+        crate::mcp::handlers::tickets::resolve_ticket_arg(service, args.ticket.as_deref())?;
     let ticket = service
         .storage
         .load(&ticket_id)
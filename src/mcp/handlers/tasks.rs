@@ -1,9 +1,10 @@
 //! Task management MCP tool handlers
 
 use crate::core::{Task, TaskId};
+use crate::events::TicketEvent;
 use crate::mcp::handlers::schema_helper::json_to_schema;
 use crate::mcp::service::VibeTicketService;
-use crate::storage::{ActiveTicketRepository, TicketRepository};
+use crate::storage::TicketRepository;
 use rmcp::model::Tool;
 use serde::Deserialize;
 use serde_json::{Value, json};
@@ -26,7 +27,7 @@ pub fn register_tools() -> Vec<Tool> {
                     },
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug (defaults to active ticket)"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     }
                 },
                 "required": ["title"]
@@ -46,7 +47,7 @@ pub fn register_tools() -> Vec<Tool> {
                     },
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug (defaults to active ticket)"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     }
                 },
                 "required": ["task_id"]
@@ -62,7 +63,7 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug (defaults to active ticket)"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     },
                     "completed_only": {
                         "type": "boolean",
@@ -89,7 +90,7 @@ pub fn register_tools() -> Vec<Tool> {
                     },
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug (defaults to active ticket)"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     }
                 },
                 "required": ["task_id"]
@@ -104,16 +105,7 @@ async fn resolve_ticket_ref(
     service: &VibeTicketService,
     ticket_ref: Option<&str>,
 ) -> Result<crate::core::TicketId, String> {
-    if let Some(ref_str) = ticket_ref {
-        crate::mcp::handlers::tickets::resolve_ticket_ref(service, ref_str).await
-    } else {
-        // Get active ticket
-        service
-            .storage
-            .get_active()
-            .map_err(|e| format!("Failed to get active ticket: {}", e))?
-            .ok_or_else(|| "No active ticket. Please specify a ticket ID or slug.".to_string())
-    }
+    crate::mcp::handlers::tickets::resolve_ticket_arg(service, ticket_ref)
 }
 
 /// Handle adding a task
@@ -136,12 +128,13 @@ pub async fn handle_add(service: &VibeTicketService, arguments: Value) -> Result
     let task = Task::new(args.title);
     let task_id = task.id.clone();
     let task_title = task.title.clone();
-    ticket.tasks.push(task);
+    ticket.tasks.push(task.clone());
 
     service
         .storage
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
+    VibeTicketService::emit(TicketEvent::TaskAdded(ticket.id.clone(), task)).await;
 
     Ok(json!({
         "status": "added",
@@ -203,6 +196,11 @@ pub async fn handle_complete(
         .storage
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
+    VibeTicketService::emit(TicketEvent::TaskCompleted(
+        ticket.id.clone(),
+        task_id.to_string(),
+    ))
+    .await;
 
     let completed_count = ticket.tasks.iter().filter(|t| t.completed).count();
     let total_count = ticket.tasks.len();
@@ -304,6 +302,11 @@ pub async fn handle_remove(service: &VibeTicketService, arguments: Value) -> Res
         .storage
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
+    VibeTicketService::emit(TicketEvent::TaskRemoved(
+        ticket.id.clone(),
+        task_id.to_string(),
+    ))
+    .await;
 
     Ok(json!({
         "status": "removed",
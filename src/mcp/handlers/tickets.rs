@@ -1,11 +1,19 @@
 //! Ticket management MCP tool handlers
 
-use crate::core::{Priority, Status, Ticket, TicketId};
+use crate::cli::handlers::{load_named_filter, parse_query};
+use crate::config::Config;
+use crate::core::closing::{CLOSE_MESSAGE_KEY, PR_URL_KEY};
+use crate::core::sections::{Section, sections_json};
+use crate::core::{Priority, Severity, Status, Ticket, TicketId, TicketTemplate};
+use crate::events::TicketEvent;
+use crate::integration::presence::Presence;
 use crate::mcp::handlers::schema_helper::json_to_schema;
+use crate::mcp::progress::Progress;
 use crate::mcp::service::VibeTicketService;
+use crate::specs::{SpecManager, link};
 use crate::storage::{ActiveTicketRepository, TicketRepository};
 use rmcp::model::Tool;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use serde_json::{Value, json};
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -38,6 +46,16 @@ pub fn register_tools() -> Vec<Tool> {
                         "description": "Priority level",
                         "default": "medium"
                     },
+                    "severity": {
+                        "type": "string",
+                        "enum": ["S1", "S2", "S3", "S4"],
+                        "description": "Incident severity (S1 is the most severe)"
+                    },
+                    "template": {
+                        "type": "string",
+                        "enum": TicketTemplate::names(),
+                        "description": "Ticket template adding its tags and, without a description, its description skeleton; incident requires a severity"
+                    },
                     "tags": {
                         "type": "array",
                         "items": {"type": "string"},
@@ -69,9 +87,14 @@ pub fn register_tools() -> Vec<Tool> {
                         "enum": ["low", "medium", "high", "critical"],
                         "description": "Filter by priority"
                     },
+                    "severity": {
+                        "type": "string",
+                        "enum": ["S1", "S2", "S3", "S4"],
+                        "description": "Filter by severity"
+                    },
                     "assignee": {
                         "type": "string",
-                        "description": "Filter by assignee"
+                        "description": "Filter by assignee (\"me\" for the current user)"
                     },
                     "open": {
                         "type": "boolean",
@@ -85,6 +108,14 @@ pub fn register_tools() -> Vec<Tool> {
                         "type": "array",
                         "items": {"type": "string"},
                         "description": "Filter by tags"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Query expression, e.g. \"status:todo priority:high assignee:me\""
+                    },
+                    "filter": {
+                        "type": "string",
+                        "description": "Name of a saved filter from the project configuration"
                     }
                 }
             }))),
@@ -99,10 +130,21 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
+                    },
+                    "sections": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": ["description", "tasks", "links", "history", "spec", "worktree", "all"]
+                        },
+                        "description": "Sections to include under 'sections', as in `show --sections`"
+                    },
+                    "expand": {
+                        "type": "boolean",
+                        "description": "Expand collapsed sections such as long histories"
                     }
-                },
-                "required": ["ticket"]
+                }
             }))),
             annotations: None,
         },
@@ -115,7 +157,7 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     },
                     "title": {
                         "type": "string",
@@ -135,6 +177,11 @@ pub fn register_tools() -> Vec<Tool> {
                         "enum": ["low", "medium", "high", "critical"],
                         "description": "New priority"
                     },
+                    "severity": {
+                        "type": "string",
+                        "enum": ["S1", "S2", "S3", "S4"],
+                        "description": "New severity; null clears it"
+                    },
                     "assignee": {
                         "type": "string",
                         "description": "New assignee"
@@ -143,9 +190,83 @@ pub fn register_tools() -> Vec<Tool> {
                         "type": "array",
                         "items": {"type": "string"},
                         "description": "New tags (replaces existing)"
+                    },
+                    "add_tags": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Tags to add"
+                    },
+                    "remove_tags": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Tags to remove"
+                    }
+                }
+            }))),
+            annotations: None,
+        },
+        // Bulk update tool
+        Tool {
+            name: Cow::Borrowed("vibe-ticket_bulk_update"),
+            description: Some(Cow::Borrowed(
+                "Apply the same edits to all selected tickets at once; either all tickets are updated or none",
+            )),
+            input_schema: Arc::new(json_to_schema(json!({
+                "type": "object",
+                "properties": {
+                    "tickets": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Ticket IDs or slugs to update"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Query expression selecting the tickets, e.g. \"status:todo tag:backend\""
+                    },
+                    "filter": {
+                        "type": "string",
+                        "description": "Name of a saved filter selecting the tickets"
+                    },
+                    "changes": {
+                        "type": "object",
+                        "properties": {
+                            "status": {
+                                "type": "string",
+                                "enum": ["todo", "doing", "done", "blocked", "review"]
+                            },
+                            "priority": {
+                                "type": "string",
+                                "enum": ["low", "medium", "high", "critical"]
+                            },
+                            "severity": {
+                                "type": "string",
+                                "enum": ["S1", "S2", "S3", "S4"],
+                                "description": "New severity; null clears it"
+                            },
+                            "assignee": {"type": "string"},
+                            "tags": {
+                                "type": "array",
+                                "items": {"type": "string"},
+                                "description": "New tags (replaces existing)"
+                            },
+                            "add_tags": {
+                                "type": "array",
+                                "items": {"type": "string"}
+                            },
+                            "remove_tags": {
+                                "type": "array",
+                                "items": {"type": "string"}
+                            }
+                        },
+                        "description": "Edits to apply, as in vibe-ticket_edit"
+                    },
+                    "dry_run": {
+                        "type": "boolean",
+                        "description": "Report what would change without saving",
+                        "default": false
                     }
                 },
-                "required": ["ticket"]
+                "required": ["changes"]
             }))),
             annotations: None,
         },
@@ -158,14 +279,21 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     },
                     "message": {
                         "type": "string",
                         "description": "Closing message"
+                    },
+                    "pr_url": {
+                        "type": "string",
+                        "description": "URL of a pull request to link to the ticket"
+                    },
+                    "force": {
+                        "type": "boolean",
+                        "description": "Close even if the ticket depends on open tickets or misses required fields"
                     }
-                },
-                "required": ["ticket"]
+                }
             }))),
             annotations: None,
         },
@@ -178,15 +306,14 @@ pub fn register_tools() -> Vec<Tool> {
                 "properties": {
                     "ticket": {
                         "type": "string",
-                        "description": "Ticket ID or slug"
+                        "description": "Ticket ID or slug (defaults to the session's ticket)"
                     },
                     "no_worktree": {
                         "type": "boolean",
                         "description": "Skip creating Git worktree",
                         "default": false
                     }
-                },
-                "required": ["ticket"]
+                }
             }))),
             annotations: None,
         },
@@ -208,34 +335,74 @@ pub async fn resolve_ticket_ref(
     service: &VibeTicketService,
     ticket_ref: &str,
 ) -> Result<TicketId, String> {
-    // Try parsing as ID first
-    if let Ok(id) = TicketId::parse_str(ticket_ref) {
-        return Ok(id);
-    }
+    let id = find_ticket_id(service, ticket_ref)?;
+    crate::mcp::session::touch(&id);
+    Ok(id)
+}
 
-    // Otherwise, search by slug
-    let tickets = service
-        .storage
-        .load_all()
-        .map_err(|e| format!("Failed to list tickets: {}", e))?;
+/// Look up a ticket ID from an ID, short ID, slug or ID prefix
+///
+/// Resolved like references given to the CLI (see
+/// [`crate::cli::handlers::resolve_ticket_ref`]), with `@branch` taken from
+/// the session's working directory.
+fn find_ticket_id(service: &VibeTicketService, ticket_ref: &str) -> Result<TicketId, String> {
+    let dir = service
+        .session()
+        .working_dir
+        .clone()
+        .unwrap_or_else(|| service.project_root.clone());
+    crate::cli::handlers::resolve_ticket_ref_in(&service.storage, ticket_ref, &dir)
+        .map_err(|e| e.to_string())
+}
 
-    for ticket in tickets {
-        if ticket.slug == ticket_ref {
-            return Ok(ticket.id);
-        }
+/// Resolve an optional `ticket` argument using the session context
+///
+/// An explicit reference is resolved and remembered as the last referenced
+/// ticket. Without one, the ticket of the session's worktree is used, then the
+/// last referenced ticket, and finally the project's active ticket.
+pub fn resolve_ticket_arg(
+    service: &VibeTicketService,
+    ticket_ref: Option<&str>,
+) -> Result<TicketId, String> {
+    if let Some(ticket_ref) = ticket_ref {
+        let id = find_ticket_id(service, ticket_ref)?;
+        service.session().record_ticket(&id);
+        return Ok(id);
     }
 
-    Err(format!("Ticket not found: {}", ticket_ref))
+    let default = service.session().default_ticket().cloned();
+    let id = match default {
+        Some(id) => id,
+        None => service
+            .storage
+            .get_active()
+            .map_err(|e| format!("Failed to get active ticket: {e}"))?
+            .ok_or_else(|| "No active ticket. Please specify a ticket ID or slug.".to_string())?,
+    };
+    crate::mcp::session::touch(&id);
+    Ok(id)
+}
+
+/// Move an oversized description into an attachment per the project config
+fn spill_description(service: &VibeTicketService, ticket: &mut Ticket) -> Result<bool, String> {
+    let config = Config::load_for_project(&service.project_root)
+        .map_err(|e| format!("Failed to load config: {e}"))?;
+    service
+        .storage
+        .spill_description(ticket, config.project.description_size_limit)
+        .map_err(|e| format!("Failed to store description attachment: {e}"))
 }
 
 /// Handle creating a new ticket
-pub fn handle_new(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+pub async fn handle_new(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
         slug: String,
         title: String,
         description: Option<String>,
         priority: Option<String>,
+        severity: Option<String>,
+        template: Option<String>,
         tags: Option<Vec<String>>,
         assignee: Option<String>,
     }
@@ -247,6 +414,7 @@ pub fn handle_new(service: &VibeTicketService, arguments: Value) -> Result<Value
 
     if let Some(desc) = args.description {
         ticket.description = desc;
+        spill_description(service, &mut ticket)?;
     }
 
     if let Some(priority_str) = args.priority {
@@ -259,6 +427,10 @@ pub fn handle_new(service: &VibeTicketService, arguments: Value) -> Result<Value
         };
     }
 
+    if let Some(severity_str) = args.severity {
+        ticket.severity = Some(Severity::try_from(severity_str.as_str())?);
+    }
+
     if let Some(tags) = args.tags {
         ticket.tags = tags;
     }
@@ -267,10 +439,31 @@ pub fn handle_new(service: &VibeTicketService, arguments: Value) -> Result<Value
         ticket.assignee = Some(assignee);
     }
 
+    // Templates apply as with `vibe-ticket new --template`
+    if let Some(name) = args.template {
+        let template = TicketTemplate::find(&name).ok_or_else(|| {
+            format!(
+                "Unknown template '{name}'. Available templates: {}",
+                TicketTemplate::names().join(", ")
+            )
+        })?;
+        template.validate_severity(ticket.severity)?;
+        for tag in template.tags {
+            if !ticket.tags.iter().any(|t| t == tag) {
+                ticket.tags.push((*tag).to_string());
+            }
+        }
+        if ticket.description.is_empty() {
+            template.description.clone_into(&mut ticket.description);
+        }
+    }
+
     service
         .storage
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
+    service.session().record_ticket(&ticket.id);
+    VibeTicketService::emit(TicketEvent::Created(ticket.clone())).await;
 
     Ok(json!({
         "status": "created",
@@ -279,6 +472,7 @@ pub fn handle_new(service: &VibeTicketService, arguments: Value) -> Result<Value
             "slug": ticket.slug,
             "title": ticket.title,
             "priority": format!("{:?}", ticket.priority).to_lowercase(),
+            "severity": ticket.severity.map(|s| s.to_string()),
             "status": format!("{:?}", ticket.status).to_lowercase(),
         }
     }))
@@ -290,10 +484,13 @@ pub fn handle_list(service: &VibeTicketService, arguments: Value) -> Result<Valu
     struct Args {
         status: Option<String>,
         priority: Option<String>,
+        severity: Option<String>,
         assignee: Option<String>,
         open: Option<bool>,
         closed: Option<bool>,
         tags: Option<Vec<String>>,
+        query: Option<String>,
+        filter: Option<String>,
     }
 
     let args: Args =
@@ -328,7 +525,13 @@ pub fn handle_list(service: &VibeTicketService, arguments: Value) -> Result<Valu
         tickets.retain(|t| t.priority == priority);
     }
 
+    if let Some(severity_str) = args.severity {
+        let severity = Severity::try_from(severity_str.as_str())?;
+        tickets.retain(|t| t.severity == Some(severity));
+    }
+
     if let Some(assignee) = args.assignee {
+        let assignee = service.storage.identity().expand(&assignee);
         tickets.retain(|t| t.assignee.as_ref() == Some(&assignee));
     }
 
@@ -344,6 +547,16 @@ pub fn handle_list(service: &VibeTicketService, arguments: Value) -> Result<Valu
         tickets.retain(|t| tags.iter().any(|tag| t.tags.contains(tag)));
     }
 
+    if let Some(query) = args.query {
+        let query = parse_query(&service.project_root, &query).map_err(|e| e.to_string())?;
+        tickets.retain(|t| query.matches(t));
+    }
+
+    if let Some(name) = args.filter {
+        let filter = load_named_filter(&service.project_root, &name).map_err(|e| e.to_string())?;
+        tickets.retain(|t| filter.matches(t));
+    }
+
     let ticket_list: Vec<Value> = tickets
         .into_iter()
         .map(|t| {
@@ -353,6 +566,7 @@ pub fn handle_list(service: &VibeTicketService, arguments: Value) -> Result<Valu
                 "title": t.title,
                 "status": format!("{:?}", t.status).to_lowercase(),
                 "priority": format!("{:?}", t.priority).to_lowercase(),
+                "severity": t.severity.map(|s| s.to_string()),
                 "assignee": t.assignee,
                 "tags": t.tags,
                 "created_at": t.created_at.to_rfc3339(),
@@ -371,18 +585,32 @@ pub fn handle_list(service: &VibeTicketService, arguments: Value) -> Result<Valu
 pub async fn handle_show(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        ticket: String,
+        ticket: Option<String>,
+        #[serde(default)]
+        sections: Vec<String>,
+        #[serde(default)]
+        expand: bool,
     }
 
     let args: Args =
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
-    let ticket_id = resolve_ticket_ref(service, &args.ticket).await?;
+    let ticket_id = resolve_ticket_arg(service, args.ticket.as_deref())?;
     let ticket = service
         .storage
         .load(&ticket_id)
         .map_err(|e| format!("Failed to load ticket: {}", e))?;
 
+    let sections = if args.sections.is_empty() {
+        Value::Null
+    } else {
+        let selected = Section::select(&args.sections, false, false).map_err(|e| e.to_string())?;
+        sections_json(&ticket, &selected, args.expand, &service.project_root)
+    };
+    // Other sessions working on the ticket
+    let presence = Presence::new(service.project_root.join(".vibe-ticket"))
+        .on_ticket(&ticket.id, service.presence_id().as_deref());
+
     Ok(json!({
         "id": ticket.id.to_string(),
         "slug": ticket.slug,
@@ -390,6 +618,7 @@ pub async fn handle_show(service: &VibeTicketService, arguments: Value) -> Resul
         "description": ticket.description,
         "status": format!("{:?}", ticket.status).to_lowercase(),
         "priority": format!("{:?}", ticket.priority).to_lowercase(),
+        "severity": ticket.severity.map(|s| s.to_string()),
         "assignee": ticket.assignee,
         "tags": ticket.tags,
         "tasks": ticket.tasks.iter().map(|t| json!({
@@ -403,26 +632,156 @@ pub async fn handle_show(service: &VibeTicketService, arguments: Value) -> Resul
         "started_at": ticket.started_at.map(|dt| dt.to_rfc3339()),
         "closed_at": ticket.closed_at.map(|dt| dt.to_rfc3339()),
         "metadata": ticket.metadata,
+        "sections": sections,
+        "presence": presence,
     }))
 }
 
+/// Field edits shared by `vibe-ticket_edit` and `vibe-ticket_bulk_update`
+#[derive(Debug, Default, Deserialize)]
+struct TicketEdits {
+    status: Option<String>,
+    priority: Option<String>,
+    #[serde(default, deserialize_with = "severity_edit")]
+    severity: Option<SeverityEdit>,
+    assignee: Option<String>,
+    tags: Option<Vec<String>>,
+    #[serde(default)]
+    add_tags: Vec<String>,
+    #[serde(default)]
+    remove_tags: Vec<String>,
+}
+
+impl TicketEdits {
+    /// Applies the edits to `ticket`, returning the changed fields
+    ///
+    /// Fields already holding the requested value are not reported.
+    fn apply(&self, ticket: &mut Ticket) -> Result<Vec<&'static str>, String> {
+        let mut changes = Vec::new();
+
+        if let Some(status_str) = &self.status {
+            let status = match status_str.as_str() {
+                "todo" => Status::Todo,
+                "doing" => Status::Doing,
+                "done" => Status::Done,
+                "blocked" => Status::Blocked,
+                "review" => Status::Review,
+                _ => return Err(format!("Invalid status: {status_str}")),
+            };
+
+            // Handle status transitions
+            if ticket.status == Status::Todo
+                && status == Status::Doing
+                && ticket.started_at.is_none()
+            {
+                ticket.started_at = Some(chrono::Utc::now());
+            }
+            if status == Status::Done && ticket.status != Status::Done {
+                ticket.closed_at = Some(chrono::Utc::now());
+            } else if status != Status::Done {
+                ticket.closed_at = None;
+            }
+
+            if ticket.status != status {
+                ticket.status = status;
+                changes.push("status");
+            }
+        }
+
+        if let Some(priority_str) = &self.priority {
+            let priority = match priority_str.as_str() {
+                "low" => Priority::Low,
+                "medium" => Priority::Medium,
+                "high" => Priority::High,
+                "critical" => Priority::Critical,
+                _ => return Err(format!("Invalid priority: {priority_str}")),
+            };
+            if ticket.priority != priority {
+                ticket.priority = priority;
+                changes.push("priority");
+            }
+        }
+
+        if let Some(edit) = &self.severity {
+            let severity = match edit {
+                SeverityEdit::Set(severity) => Some(Severity::try_from(severity.as_str())?),
+                SeverityEdit::Clear => None,
+            };
+            if ticket.severity != severity {
+                ticket.severity = severity;
+                changes.push("severity");
+            }
+        }
+
+        if let Some(assignee) = &self.assignee {
+            if ticket.assignee.as_ref() != Some(assignee) {
+                ticket.assignee = Some(assignee.clone());
+                changes.push("assignee");
+            }
+        }
+
+        let mut tags = self.tags.clone().unwrap_or_else(|| ticket.tags.clone());
+        for tag in &self.add_tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.retain(|tag| !self.remove_tags.contains(tag));
+        if tags != ticket.tags {
+            ticket.tags = tags;
+            changes.push("tags");
+        }
+
+        Ok(changes)
+    }
+
+    /// Whether no edit is requested
+    fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.priority.is_none()
+            && self.severity.is_none()
+            && self.assignee.is_none()
+            && self.tags.is_none()
+            && self.add_tags.is_empty()
+            && self.remove_tags.is_empty()
+    }
+}
+
+/// Requested change of the severity
+#[derive(Debug)]
+enum SeverityEdit {
+    /// Set the severity
+    Set(String),
+    /// Clear the severity, requested with an explicit null
+    Clear,
+}
+
+/// Deserializes a `severity` present in the arguments, even if null
+fn severity_edit<'de, D>(deserializer: D) -> Result<Option<SeverityEdit>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let severity = Option::<String>::deserialize(deserializer)?;
+    Ok(Some(
+        severity.map_or(SeverityEdit::Clear, SeverityEdit::Set),
+    ))
+}
+
 /// Handle editing a ticket
 pub async fn handle_edit(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        ticket: String,
+        ticket: Option<String>,
         title: Option<String>,
         description: Option<String>,
-        status: Option<String>,
-        priority: Option<String>,
-        assignee: Option<String>,
-        tags: Option<Vec<String>>,
+        #[serde(flatten)]
+        edits: TicketEdits,
     }
 
     let args: Args =
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
-    let ticket_id = resolve_ticket_ref(service, &args.ticket).await?;
+    let ticket_id = resolve_ticket_arg(service, args.ticket.as_deref())?;
     let mut ticket = service
         .storage
         .load(&ticket_id)
@@ -437,48 +796,11 @@ pub async fn handle_edit(service: &VibeTicketService, arguments: Value) -> Resul
 
     if let Some(description) = args.description {
         ticket.description = description;
+        spill_description(service, &mut ticket)?;
         changes.push("description");
     }
 
-    if let Some(status_str) = args.status {
-        let status = match status_str.as_str() {
-            "todo" => Status::Todo,
-            "doing" => Status::Doing,
-            "done" => Status::Done,
-            "blocked" => Status::Blocked,
-            "review" => Status::Review,
-            _ => return Err(format!("Invalid status: {}", status_str)),
-        };
-
-        // Handle status transitions
-        if ticket.status == Status::Todo && status == Status::Doing && ticket.started_at.is_none() {
-            ticket.started_at = Some(chrono::Utc::now());
-        }
-
-        ticket.status = status;
-        changes.push("status");
-    }
-
-    if let Some(priority_str) = args.priority {
-        ticket.priority = match priority_str.as_str() {
-            "low" => Priority::Low,
-            "medium" => Priority::Medium,
-            "high" => Priority::High,
-            "critical" => Priority::Critical,
-            _ => return Err(format!("Invalid priority: {}", priority_str)),
-        };
-        changes.push("priority");
-    }
-
-    if let Some(assignee) = args.assignee {
-        ticket.assignee = Some(assignee);
-        changes.push("assignee");
-    }
-
-    if let Some(tags) = args.tags {
-        ticket.tags = tags;
-        changes.push("tags");
-    }
+    changes.extend(args.edits.apply(&mut ticket)?);
 
     if changes.is_empty() {
         return Ok(json!({
@@ -491,6 +813,7 @@ pub async fn handle_edit(service: &VibeTicketService, arguments: Value) -> Resul
         .storage
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
+    VibeTicketService::emit(TicketEvent::Updated(ticket.clone())).await;
 
     Ok(json!({
         "status": "updated",
@@ -500,18 +823,118 @@ pub async fn handle_edit(service: &VibeTicketService, arguments: Value) -> Resul
     }))
 }
 
+/// Handle editing every ticket matching a selection at once
+///
+/// Tickets are selected by reference, query expression or saved filter; with
+/// several selectors, a ticket must match all of them. All changed tickets
+/// are saved together, so either all edits are applied or none. Progress is
+/// reported per staged ticket; when the client cancels the request before
+/// the tickets are committed, none is saved.
+pub async fn handle_bulk_update(
+    service: &VibeTicketService,
+    arguments: Value,
+    progress: &Progress,
+) -> Result<Value, String> {
+    #[derive(Deserialize)]
+    struct Args {
+        tickets: Option<Vec<String>>,
+        query: Option<String>,
+        filter: Option<String>,
+        #[serde(default)]
+        changes: TicketEdits,
+        #[serde(default)]
+        dry_run: bool,
+    }
+
+    let args: Args =
+        serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
+    if args.tickets.is_none() && args.query.is_none() && args.filter.is_none() {
+        return Err("Select tickets with 'tickets', 'query' or 'filter'".to_string());
+    }
+    if args.changes.is_empty() {
+        return Err("No changes specified".to_string());
+    }
+
+    let mut tickets = service
+        .storage
+        .load_all()
+        .map_err(|e| format!("Failed to list tickets: {e}"))?;
+    if let Some(refs) = &args.tickets {
+        let ids = refs
+            .iter()
+            .map(|reference| find_ticket_id(service, reference))
+            .collect::<Result<Vec<_>, _>>()?;
+        tickets.retain(|t| ids.contains(&t.id));
+    }
+    if let Some(query) = &args.query {
+        let query = parse_query(&service.project_root, query).map_err(|e| e.to_string())?;
+        tickets.retain(|t| query.matches(t));
+    }
+    if let Some(name) = &args.filter {
+        let filter = load_named_filter(&service.project_root, name).map_err(|e| e.to_string())?;
+        tickets.retain(|t| filter.matches(t));
+    }
+
+    let mut results = Vec::new();
+    let mut updated = Vec::new();
+    for mut ticket in tickets {
+        let changes = args.changes.apply(&mut ticket)?;
+        results.push(json!({
+            "ticket_id": ticket.id.to_string(),
+            "ticket_slug": ticket.slug,
+            "status": if changes.is_empty() { "unchanged" } else { "updated" },
+            "changes": changes,
+        }));
+        if !changes.is_empty() {
+            updated.push(ticket);
+        }
+    }
+
+    let mut cancelled = false;
+    if !args.dry_run {
+        let mut transaction = service.storage.transaction();
+        for ticket in &updated {
+            transaction.save(ticket.clone());
+        }
+        cancelled = !transaction
+            .commit_with(
+                progress.steps(|done, total| format!("Staged {done} of {total} ticket(s)")),
+            )
+            .map_err(|e| format!("No ticket was updated: {e}"))?;
+        if !cancelled {
+            for ticket in updated.iter().cloned() {
+                VibeTicketService::emit(TicketEvent::Updated(ticket)).await;
+            }
+        }
+    }
+
+    let mut response = json!({
+        "dry_run": args.dry_run,
+        "matched": results.len(),
+        "updated": if cancelled { 0 } else { updated.len() },
+        "results": results,
+    });
+    if cancelled {
+        response["cancelled"] = json!(true);
+    }
+    Ok(response)
+}
+
 /// Handle closing a ticket
 pub async fn handle_close(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        ticket: String,
+        ticket: Option<String>,
         message: Option<String>,
+        pr_url: Option<String>,
+        #[serde(default)]
+        force: bool,
     }
 
     let args: Args =
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
-    let ticket_id = resolve_ticket_ref(service, &args.ticket).await?;
+    let ticket_id = resolve_ticket_arg(service, args.ticket.as_deref())?;
     let mut ticket = service
         .storage
         .load(&ticket_id)
@@ -527,11 +950,19 @@ pub async fn handle_close(service: &VibeTicketService, arguments: Value) -> Resu
     if let Some(message) = args.message {
         ticket
             .metadata
-            .insert("closing_message".to_string(), Value::String(message));
+            .insert(CLOSE_MESSAGE_KEY.to_string(), Value::String(message));
+    }
+    if let Some(url) = args.pr_url {
+        ticket
+            .metadata
+            .insert(PR_URL_KEY.to_string(), Value::String(url));
     }
 
     service
         .storage
+        .as_ref()
+        .clone()
+        .with_forced_close(args.force)
         .save(&ticket)
         .map_err(|e| format!("Failed to save ticket: {}", e))?;
 
@@ -541,12 +972,24 @@ pub async fn handle_close(service: &VibeTicketService, arguments: Value) -> Resu
             let _ = service.storage.clear_active();
         }
     }
+    let message = ticket
+        .metadata
+        .get(CLOSE_MESSAGE_KEY)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    VibeTicketService::emit(TicketEvent::Closed(ticket.id.clone(), message)).await;
+
+    // Storage completed the spec if this was its last open exported ticket
+    let specs = SpecManager::new(service.project_root.join(".vibe-ticket").join("specs"));
+    let completed_spec = link::completed_spec(&specs, &ticket);
 
     Ok(json!({
         "status": "closed",
         "ticket_id": ticket.id.to_string(),
         "ticket_slug": ticket.slug,
-        "closed_at": ticket.closed_at.unwrap().to_rfc3339()
+        "closed_at": ticket.closed_at.unwrap().to_rfc3339(),
+        "completed_spec": completed_spec.map(|spec| spec.id),
     }))
 }
 
@@ -554,14 +997,14 @@ pub async fn handle_close(service: &VibeTicketService, arguments: Value) -> Resu
 pub async fn handle_start(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     #[derive(Deserialize)]
     struct Args {
-        ticket: String,
+        ticket: Option<String>,
         no_worktree: Option<bool>,
     }
 
     let args: Args =
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
 
-    let ticket_id = resolve_ticket_ref(service, &args.ticket).await?;
+    let ticket_id = resolve_ticket_arg(service, args.ticket.as_deref())?;
     let mut ticket = service
         .storage
         .load(&ticket_id)
@@ -577,6 +1020,12 @@ pub async fn handle_start(service: &VibeTicketService, arguments: Value) -> Resu
             .storage
             .save(&ticket)
             .map_err(|e| format!("Failed to save ticket: {}", e))?;
+        VibeTicketService::emit(TicketEvent::StatusChanged(
+            ticket.id.clone(),
+            Status::Todo,
+            Status::Doing,
+        ))
+        .await;
     }
 
     // Set as active ticket
@@ -635,3 +1084,188 @@ pub fn handle_check(service: &VibeTicketService, _arguments: Value) -> Result<Va
         }
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits_report_only_changed_fields() {
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.tags = vec!["auth".to_string(), "old".to_string()];
+        let edits: TicketEdits = serde_json::from_value(json!({
+            "status": "done",
+            "priority": "medium",
+            "add_tags": ["auth", "urgent"],
+            "remove_tags": ["old"]
+        }))
+        .unwrap();
+
+        assert_eq!(edits.apply(&mut ticket).unwrap(), vec!["status", "tags"]);
+        assert!(ticket.closed_at.is_some());
+        assert_eq!(ticket.tags, vec!["auth", "urgent"]);
+        assert!(edits.apply(&mut ticket).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_edits_clear_severity_with_null() {
+        let mut ticket = Ticket::new("outage", "Outage");
+        ticket.severity = Some(Severity::S2);
+
+        let edits: TicketEdits = serde_json::from_value(json!({ "priority": "high" })).unwrap();
+        edits.apply(&mut ticket).unwrap();
+        assert_eq!(ticket.severity, Some(Severity::S2));
+
+        let edits: TicketEdits = serde_json::from_value(json!({ "severity": null })).unwrap();
+        assert!(!edits.is_empty());
+        assert_eq!(edits.apply(&mut ticket).unwrap(), vec!["severity"]);
+        assert_eq!(ticket.severity, None);
+    }
+
+    #[tokio::test]
+    async fn test_new_applies_template_and_edit_clears_severity() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = crate::storage::FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        let service = VibeTicketService::new(storage, temp_dir.path().to_path_buf());
+
+        let error = handle_new(
+            &service,
+            json!({ "slug": "outage", "title": "Outage", "template": "incident" }),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("requires a severity"));
+
+        handle_new(
+            &service,
+            json!({
+                "slug": "outage",
+                "title": "Outage",
+                "template": "incident",
+                "severity": "S1",
+                "tags": ["db"]
+            }),
+        )
+        .await
+        .unwrap();
+        let ticket = service.storage.load_all().unwrap().pop().unwrap();
+        assert_eq!(ticket.tags, vec!["db", "incident"]);
+        assert!(ticket.description.starts_with("## Impact"));
+
+        handle_edit(&service, json!({ "ticket": "outage", "severity": null }))
+            .await
+            .unwrap();
+        assert_eq!(service.storage.load(&ticket.id).unwrap().severity, None);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_update_saves_nothing_when_cancelled() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = crate::storage::FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        for slug in ["first", "second"] {
+            storage.save(&Ticket::new(slug, slug)).unwrap();
+        }
+        let service = VibeTicketService::new(storage, temp_dir.path().to_path_buf());
+        let arguments = json!({
+            "tickets": ["first", "second"],
+            "changes": { "priority": "critical" },
+        });
+
+        let cancellation = tokio_util::sync::CancellationToken::new();
+        cancellation.cancel();
+        let result = handle_bulk_update(
+            &service,
+            arguments.clone(),
+            &Progress::detached(cancellation),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["cancelled"], true);
+        assert_eq!(result["updated"], 0);
+        let fresh = crate::storage::FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        assert!(
+            fresh
+                .load_all()
+                .unwrap()
+                .iter()
+                .all(|ticket| ticket.priority != Priority::Critical)
+        );
+
+        let result = handle_bulk_update(&service, arguments, &Progress::default())
+            .await
+            .unwrap();
+        assert!(result.get("cancelled").is_none());
+        assert_eq!(result["updated"], 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_calls_are_undone_separately() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = crate::storage::FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        let service = VibeTicketService::new(storage, temp_dir.path().to_path_buf());
+        let progress = Progress::default();
+        let slugs: Vec<String> = (0..8).map(|i| format!("ticket-{i}")).collect();
+        for slug in &slugs {
+            let (result, _) = service
+                .run_tool(
+                    "vibe-ticket_new",
+                    json!({ "slug": slug, "title": slug }),
+                    &progress,
+                )
+                .await;
+            result.unwrap();
+        }
+
+        let calls: Vec<_> = slugs
+            .iter()
+            .map(|slug| {
+                let service = service.clone();
+                let arguments = json!({ "ticket": slug, "priority": "critical" });
+                tokio::spawn(async move {
+                    let (result, _) = service
+                        .run_tool("vibe-ticket_edit", arguments, &Progress::default())
+                        .await;
+                    result.unwrap();
+                })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap();
+        }
+
+        // Each undo reverts exactly one of the concurrent edits
+        let critical = |service: &VibeTicketService| {
+            service
+                .storage
+                .load_all()
+                .unwrap()
+                .iter()
+                .filter(|ticket| ticket.priority == Priority::Critical)
+                .count()
+        };
+        for remaining in (0..slugs.len()).rev() {
+            let undone = service.storage.undo_operation().unwrap().unwrap();
+            assert_eq!(undone.command(), "mcp vibe-ticket_edit");
+            assert_eq!(undone.ticket_ids().len(), 1);
+            assert_eq!(critical(&service), remaining);
+        }
+        assert_eq!(service.storage.load_all().unwrap().len(), slugs.len());
+    }
+
+    #[test]
+    fn test_find_ticket_id_matches_cli() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = crate::storage::FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        let ticket = Ticket::new("fix-login", "Fix login");
+        storage.save(&ticket).unwrap();
+        let service = VibeTicketService::new(storage, temp_dir.path().to_path_buf());
+
+        let prefix = &ticket.id.to_string()[..8];
+        for reference in [prefix, "fix-login"] {
+            assert_eq!(find_ticket_id(&service, reference).unwrap(), ticket.id);
+        }
+        // IDs of tickets that do not exist are not passed on
+        let missing = TicketId::new().to_string();
+        assert!(find_ticket_id(&service, &missing).is_err());
+    }
+}
@@ -0,0 +1,412 @@
+//! Streamable HTTP transport for the MCP server
+//!
+//! Serves the streamable HTTP transport of the MCP specification on a single
+//! endpoint, [`ENDPOINT`]:
+//! - `POST` sends one JSON-RPC message or a batch. Requests are answered with
+//!   their responses as JSON; a body without requests gets `202 Accepted`.
+//! - `GET` opens a server-sent event stream carrying the messages the server
+//!   sends on its own, such as resource updates and progress notifications.
+//! - `DELETE` ends the session.
+//!
+//! An `initialize` request sent without a session starts one; the response
+//! carries its ID in the `Mcp-Session-Id` header, which later requests must
+//! send back. Each session runs its own [`VibeTicketService`], connected
+//! through an in-memory pipe speaking the same line-delimited JSON-RPC as
+//! the stdio transport.
+//!
+//! When API tokens are configured (see [`crate::mcp::auth`]), every request
+//! needs an `Authorization: Bearer` header. A session is bound to the token
+//! that started it and limited to the tools of that token's scope.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use futures::Stream;
+use rmcp::ServiceExt;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, ReadHalf, WriteHalf};
+use tokio::sync::{broadcast, oneshot};
+use tracing::{error, info};
+
+use crate::config::{ApiToken, TokenScope};
+use crate::mcp::auth::Authenticator;
+use crate::mcp::service::VibeTicketService;
+use crate::storage::FileStorage;
+
+/// Path of the MCP endpoint
+pub const ENDPOINT: &str = "/mcp";
+
+/// Header carrying the session ID
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Time a request gets to be answered
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Capacity of the pipe between a session and its service
+const PIPE_CAPACITY: usize = 64 * 1024;
+
+/// Messages kept for event streams that fall behind
+const EVENT_BUFFER: usize = 256;
+
+/// Builds the router serving [`ENDPOINT`] for a project
+pub fn router(storage: FileStorage, project_root: PathBuf) -> Router {
+    let state = Arc::new(HttpState {
+        storage,
+        authenticator: Authenticator::new(project_root.clone()),
+        project_root,
+        sessions: Mutex::default(),
+    });
+    Router::new()
+        .route(
+            ENDPOINT,
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .with_state(state)
+}
+
+/// State shared by all requests
+struct HttpState {
+    storage: FileStorage,
+    project_root: PathBuf,
+    authenticator: Authenticator,
+    sessions: Mutex<HashMap<String, Arc<Session>>>,
+}
+
+impl HttpState {
+    /// Authenticates the bearer token of a request
+    ///
+    /// Returns `None` if no tokens are configured.
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Option<ApiToken>, Box<Response>> {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim);
+        self.authenticator.authenticate(token).map_err(|e| {
+            let mut response = error_response(StatusCode::UNAUTHORIZED, &e.to_string());
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            Box::new(response)
+        })
+    }
+
+    /// The session a request names in its header, if its token started it
+    fn session(&self, headers: &HeaderMap) -> Result<Arc<Session>, Box<Response>> {
+        let token = self.authenticate(headers)?;
+        let session = headers
+            .get(SESSION_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .and_then(|id| {
+                self.sessions
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .get(id)
+                    .cloned()
+            })
+            .ok_or_else(|| Box::new(error_response(StatusCode::NOT_FOUND, "Unknown session")))?;
+        if token.map(|token| token.hash) != session.token {
+            return Err(Box::new(error_response(
+                StatusCode::FORBIDDEN,
+                "Session belongs to another token",
+            )));
+        }
+        Ok(session)
+    }
+}
+
+/// One client session and the service it talks to
+struct Session {
+    /// Writes messages to the service
+    input: tokio::sync::Mutex<WriteHalf<DuplexStream>>,
+
+    /// Requests waiting for their response, by JSON-RPC ID
+    pending: Mutex<HashMap<String, oneshot::Sender<Value>>>,
+
+    /// Messages the service sends on its own
+    events: broadcast::Sender<Value>,
+
+    /// Task running the service
+    service: tokio::task::JoinHandle<()>,
+
+    /// Hash of the token that started the session
+    token: Option<String>,
+}
+
+impl Session {
+    /// Starts `service` and a session talking to it, started with `token`
+    fn start(service: VibeTicketService, token: Option<String>) -> Arc<Self> {
+        let (client, server) = tokio::io::duplex(PIPE_CAPACITY);
+        let (output, input) = tokio::io::split(client);
+        let service = tokio::spawn(async move {
+            match Box::pin(service.serve(server)).await {
+                Ok(running) => {
+                    let _ = Box::pin(running.waiting()).await;
+                },
+                Err(e) => error!("MCP session failed to start: {e}"),
+            }
+        });
+        let session = Arc::new(Self {
+            input: tokio::sync::Mutex::new(input),
+            pending: Mutex::default(),
+            events: broadcast::channel(EVENT_BUFFER).0,
+            service,
+            token,
+        });
+        tokio::spawn(route_output(Arc::downgrade(&session), output));
+        session
+    }
+
+    /// Sends messages to the service and waits for the responses to the
+    /// requests among them
+    async fn exchange(&self, messages: &[Value]) -> Result<Vec<Value>, String> {
+        let (senders, responses): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .filter(|message| is_request(message))
+            .map(|message| {
+                let (sender, receiver) = oneshot::channel();
+                ((message["id"].to_string(), sender), receiver)
+            })
+            .unzip();
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(senders);
+
+        let mut input = Box::pin(self.input.lock()).await;
+        for message in messages {
+            let mut line = serde_json::to_vec(message).map_err(|e| e.to_string())?;
+            line.push(b'\n');
+            input
+                .write_all(&line)
+                .await
+                .map_err(|e| format!("Session closed: {e}"))?;
+        }
+        input
+            .flush()
+            .await
+            .map_err(|e| format!("Session closed: {e}"))?;
+        drop(input);
+
+        let mut results = Vec::with_capacity(responses.len());
+        for response in responses {
+            let response = Box::pin(tokio::time::timeout(RESPONSE_TIMEOUT, response))
+                .await
+                .map_err(|_| "Timed out waiting for the response".to_string())?
+                .map_err(|_| "Session closed".to_string())?;
+            results.push(response);
+        }
+        Ok(results)
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.service.abort();
+    }
+}
+
+/// Delivers the messages of the service: responses to their request, the
+/// rest to the event streams
+async fn route_output(session: Weak<Session>, output: ReadHalf<DuplexStream>) {
+    let mut lines = BufReader::new(output).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Some(session) = session.upgrade() else {
+            break;
+        };
+        let Ok(message) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if is_response(&message) {
+            let sender = session
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&message["id"].to_string());
+            if let Some(sender) = sender {
+                let _ = sender.send(message);
+            }
+        } else {
+            // Nobody may be listening
+            let _ = session.events.send(message);
+        }
+    }
+}
+
+/// Whether a message is a request, which gets a response
+fn is_request(message: &Value) -> bool {
+    message.get("method").is_some() && message.get("id").is_some()
+}
+
+/// Whether a message is a response to a request
+fn is_response(message: &Value) -> bool {
+    message.get("method").is_none() && message.get("id").is_some()
+}
+
+/// Handles `POST`: delivers messages and answers requests
+async fn handle_post(
+    State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Ok(body) = serde_json::from_slice::<Value>(&body) else {
+        return error_response(StatusCode::BAD_REQUEST, "Body is not JSON");
+    };
+    let batch = body.is_array();
+    let messages = match body {
+        Value::Array(messages) => messages,
+        message => vec![message],
+    };
+
+    let (session, new_session) = if headers.contains_key(SESSION_HEADER) {
+        match state.session(&headers) {
+            Ok(session) => (session, None),
+            Err(response) => return *response,
+        }
+    } else if messages
+        .iter()
+        .any(|message| message["method"] == "initialize")
+    {
+        let token = match state.authenticate(&headers) {
+            Ok(token) => token,
+            Err(response) => return *response,
+        };
+        let scope = token
+            .as_ref()
+            .map_or(TokenScope::Admin, |token| token.scope);
+        let id = uuid::Uuid::new_v4().to_string();
+        let service = VibeTicketService::new(state.storage.clone(), state.project_root.clone())
+            .with_scope(scope)
+            .with_token_name(token.as_ref().map(|token| token.name.clone()));
+        let session = Session::start(service, token.map(|token| token.hash));
+        state
+            .sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id.clone(), session.clone());
+        info!("MCP HTTP session {id} started");
+        (session, Some(id))
+    } else {
+        return error_response(StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header");
+    };
+
+    let mut responses = match Box::pin(session.exchange(&messages)).await {
+        Ok(responses) => responses,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e),
+    };
+    let mut response = if responses.is_empty() {
+        StatusCode::ACCEPTED.into_response()
+    } else if batch {
+        axum::Json(Value::Array(responses)).into_response()
+    } else {
+        axum::Json(responses.remove(0)).into_response()
+    };
+    if let Some(value) = new_session.and_then(|id| HeaderValue::from_str(&id).ok()) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+/// Handles `GET`: streams the messages the server sends on its own
+async fn handle_get(State(state): State<Arc<HttpState>>, headers: HeaderMap) -> Response {
+    let session = match state.session(&headers) {
+        Ok(session) => session,
+        Err(response) => return *response,
+    };
+    Sse::new(event_stream(session.events.subscribe()))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Server-sent events of the messages received on `events`
+fn event_stream(
+    events: broadcast::Receiver<Value>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    futures::stream::unfold(events, |mut events| async move {
+        loop {
+            match Box::pin(events.recv()).await {
+                Ok(message) => {
+                    let event = Event::default().event("message").data(message.to_string());
+                    return Some((Ok(event), events));
+                },
+                // Messages missed by a slow client are dropped
+                Err(broadcast::error::RecvError::Lagged(_)) => {},
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// Handles `DELETE`: ends the session
+async fn handle_delete(State(state): State<Arc<HttpState>>, headers: HeaderMap) -> Response {
+    if let Err(response) = state.session(&headers) {
+        return *response;
+    }
+    if let Some(id) = headers.get(SESSION_HEADER).and_then(|id| id.to_str().ok()) {
+        state
+            .sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+    }
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// JSON-RPC error response without a request ID
+fn error_response(status: StatusCode, message: &str) -> Response {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": { "code": -32600, "message": message },
+    });
+    (status, axum::Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_session_answers_requests_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        let session = Session::start(
+            VibeTicketService::new(storage, temp_dir.path().to_path_buf()),
+            None,
+        );
+
+        let initialize = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1" }
+            }
+        });
+        let responses = session.exchange(&[initialize]).await.unwrap();
+        assert_eq!(responses[0]["id"], 1);
+        assert!(responses[0]["result"]["capabilities"]["tools"].is_object());
+
+        let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        let list = json!({ "jsonrpc": "2.0", "id": "list", "method": "tools/list" });
+        let responses = session.exchange(&[initialized, list]).await.unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], "list");
+        assert!(responses[0]["result"]["tools"].as_array().is_some());
+    }
+}
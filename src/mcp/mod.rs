@@ -4,12 +4,17 @@
 //! operations through the Model Context Protocol, enabling AI assistants
 //! to interact with the ticket management system.
 
+pub mod access_log;
 pub mod auth;
 pub mod config;
+pub mod daemon;
 pub mod error;
 pub mod handlers;
+pub mod http;
+pub mod progress;
 pub mod server;
 pub mod service;
+pub mod session;
 
 pub use config::McpConfig;
 pub use error::{McpError, McpResult};
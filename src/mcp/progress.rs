@@ -0,0 +1,114 @@
+//! Progress reporting and cancellation for long-running MCP tools
+//!
+//! Tools that work through many tickets, such as imports, take an
+//! [`Progress`] built from the request context. When the client asked for
+//! progress by sending a `progressToken` in the request metadata, each step
+//! is reported as a `notifications/progress` message; when the client sends
+//! `notifications/cancelled` for the request, the tool stops at the next step;
+//! tools saving tickets in a transaction then roll it back.
+//!
+//! Synchronous storage operations, such as transactions and index rebuilds,
+//! take a step callback instead (see [`Progress::steps`]).
+
+use rmcp::{
+    Peer, RoleServer,
+    model::{ProgressNotificationParam, ProgressToken},
+    service::RequestContext,
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Most notifications sent for one operation
+const MAX_NOTIFICATIONS: usize = 100;
+
+/// Progress reporter and cancellation token of one tool call
+#[derive(Clone, Default)]
+pub struct Progress {
+    /// Client to notify and the token it asked progress for, if any
+    target: Option<(Peer<RoleServer>, ProgressToken)>,
+
+    /// Cancelled when the client cancels the request
+    cancellation: CancellationToken,
+}
+
+impl Progress {
+    /// Creates the reporter of a tool call
+    pub fn from_context(context: &RequestContext<RoleServer>) -> Self {
+        Self {
+            target: context
+                .meta
+                .get_progress_token()
+                .map(|token| (context.peer.clone(), token)),
+            cancellation: context.ct.clone(),
+        }
+    }
+
+    /// Creates a reporter that notifies no one, stopping when `cancellation` is cancelled
+    pub const fn detached(cancellation: CancellationToken) -> Self {
+        Self {
+            target: None,
+            cancellation,
+        }
+    }
+
+    /// Whether the client cancelled the request
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Reports that `done` of `total` steps are finished
+    ///
+    /// Large operations are reported in about [`MAX_NOTIFICATIONS`] steps;
+    /// the first and the last step are always reported. A client that went
+    /// away is not an error for the operation, so failed notifications are
+    /// only logged.
+    pub async fn report(&self, done: usize, total: usize, message: impl FnOnce() -> String) {
+        let Some((peer, token)) = &self.target else {
+            return;
+        };
+        let step = total.div_ceil(MAX_NOTIFICATIONS).max(1);
+        if done != 0 && done != total && done % step != 0 {
+            return;
+        }
+
+        let param = ProgressNotificationParam {
+            progress_token: token.clone(),
+            progress: u32::try_from(done).unwrap_or(u32::MAX),
+            total: u32::try_from(total).ok(),
+            message: Some(message()),
+        };
+        if let Err(e) = Box::pin(peer.notify_progress(param)).await {
+            tracing::debug!("Failed to send progress notification: {e}");
+        }
+    }
+
+    /// Step callback for synchronous operations, such as
+    /// `Transaction::commit_with` or `FileStorage::rebuild_index_with`
+    ///
+    /// The callback reports that `done` of `total` steps are finished, with
+    /// the message `message` makes of them, and returns false once the
+    /// client cancelled the request. The operation blocks the tool call, so
+    /// the notifications are sent in order by a task of their own.
+    pub fn steps<M>(&self, message: M) -> impl FnMut(usize, usize) -> bool + use<M>
+    where
+        M: Fn(usize, usize) -> String + Send + Sync + 'static,
+    {
+        let sender = self.target.is_some().then(|| {
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            let progress = self.clone();
+            tokio::spawn(async move {
+                while let Some((done, total)) = receiver.recv().await {
+                    Box::pin(progress.report(done, total, || message(done, total))).await;
+                }
+            });
+            sender
+        });
+        let cancellation = self.cancellation.clone();
+        move |done, total| {
+            if let Some(sender) = &sender {
+                let _ = sender.send((done, total));
+            }
+            !cancellation.is_cancelled()
+        }
+    }
+}
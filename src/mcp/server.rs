@@ -1,10 +1,23 @@
 //! MCP server implementation
 
-use crate::mcp::{config::McpConfig, error::McpResult, service::VibeTicketService};
-use crate::storage::FileStorage;
+use crate::core::TicketId;
+use crate::integration::webhooks::WebhookHandler;
+use crate::mcp::{
+    auth::Authenticator,
+    config::{McpConfig, TransportType},
+    error::{McpError, McpResult},
+    http,
+    service::VibeTicketService,
+};
+use crate::storage::{FileStorage, StorageEvent};
 use rmcp::ServiceExt;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tracing::info;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// Time between two polls of the storage event log
+const EVENT_LOG_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 /// MCP server for vibe-ticket
 pub struct McpServer {
@@ -30,9 +43,89 @@ impl McpServer {
 
         info!("Starting MCP server on {}", addr);
 
-        // For now, we'll use stdio transport
-        // TODO: Implement TCP transport
-        self.start_stdio().await
+        // Post the changes made by tools to the project's webhooks
+        let webhooks = WebhookHandler::new(self.project_root());
+        Box::pin(crate::events::event_bus().register_handler(Arc::new(webhooks))).await;
+
+        // Drop cached tickets that the CLI or other servers change
+        let _watcher = match self.storage.watch_tickets() {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                warn!("Cached tickets expire only after their TTL: {}", e);
+                None
+            },
+        };
+
+        // Notify subscribers of the changes other processes make
+        let follower = tokio::spawn(follow_event_log(Arc::clone(&self.storage)));
+
+        let result = match self.config.server.transport {
+            TransportType::Stdio => Box::pin(self.start_stdio()).await,
+            TransportType::Tcp => Box::pin(self.start_tcp()).await,
+            TransportType::Http => Box::pin(self.start_http()).await,
+            TransportType::WebSocket => Err(McpError::ConfigError(
+                "WebSocket transport is not supported yet".to_string(),
+            )),
+        };
+        follower.abort();
+        result
+    }
+
+    /// Start server with TCP transport
+    ///
+    /// Every connection gets its own session speaking the same line-delimited
+    /// JSON-RPC as the stdio transport. Runs until the process is stopped.
+    /// Refuses to start when API tokens are configured, as connections have
+    /// no way to present them.
+    pub async fn start_tcp(&self) -> McpResult<()> {
+        if Authenticator::new(self.project_root()).enabled()? {
+            return Err(McpError::ConfigError(
+                "API tokens are configured, but the TCP transport cannot carry them; \
+                 use --transport http"
+                    .to_string(),
+            ));
+        }
+
+        let addr = format!("{}:{}", self.config.server.host, self.config.server.port);
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        info!("MCP server listening on {}", addr);
+
+        let project_root = self.project_root();
+        loop {
+            let (stream, peer) = Box::pin(listener.accept()).await?;
+            info!("MCP client connected from {}", peer);
+
+            let service = VibeTicketService::new((*self.storage).clone(), project_root.clone());
+            tokio::spawn(async move {
+                if let Err(e) = Box::pin(serve_connection(service, stream)).await {
+                    error!("MCP session with {} failed: {}", peer, e);
+                }
+                info!("MCP client {} disconnected", peer);
+            });
+        }
+    }
+
+    /// Start server with streamable HTTP transport
+    ///
+    /// Serves the MCP endpoint described in [`crate::mcp::http`]. Runs until
+    /// the process is stopped.
+    pub async fn start_http(&self) -> McpResult<()> {
+        let addr = format!("{}:{}", self.config.server.host, self.config.server.port);
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        info!("MCP server listening on http://{}{}", addr, http::ENDPOINT);
+
+        let router = http::router((*self.storage).clone(), self.project_root());
+        axum::serve(listener, router).await?;
+        Ok(())
+    }
+
+    /// Project root, the parent of the storage directory
+    fn project_root(&self) -> PathBuf {
+        self.config
+            .storage_path
+            .parent()
+            .unwrap_or(&self.config.storage_path)
+            .to_path_buf()
     }
 
     /// Start server with stdio transport
@@ -40,12 +133,7 @@ impl McpServer {
         info!("Starting MCP server with stdio transport");
 
         // Get project root from storage path (parent of .vibe-ticket)
-        let project_root = self
-            .config
-            .storage_path
-            .parent()
-            .unwrap_or(&self.config.storage_path)
-            .to_path_buf();
+        let project_root = self.project_root();
 
         // Create service
         let service = VibeTicketService::new((*self.storage).clone(), project_root);
@@ -65,3 +153,66 @@ impl McpServer {
         Ok(())
     }
 }
+
+/// Follows the storage event log for changes made by other processes
+///
+/// Each changed ticket is dropped from the cache and reported to the event
+/// bus, which notifies the sessions subscribed to it. When the log was
+/// started anew, changes may have been missed and the whole cache is
+/// dropped.
+async fn follow_event_log(storage: Arc<FileStorage>) {
+    let mut cursor = match storage.event_cursor() {
+        Ok(cursor) => cursor,
+        Err(e) => {
+            warn!("Changes made by other processes are not followed: {}", e);
+            return;
+        },
+    };
+    let mut interval = tokio::time::interval(EVENT_LOG_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let polled = {
+            let storage = Arc::clone(&storage);
+            let since = cursor.clone();
+            tokio::task::spawn_blocking(move || storage.poll_events(&since)).await
+        };
+        let poll = match polled {
+            Ok(Ok(poll)) => poll,
+            Ok(Err(e)) => {
+                debug!("Failed to poll the event log: {}", e);
+                continue;
+            },
+            Err(e) => {
+                debug!("Failed to poll the event log: {}", e);
+                continue;
+            },
+        };
+        cursor = poll.cursor;
+        if poll.reset {
+            storage.cache.invalidate_all();
+        }
+
+        let mut changed: Vec<TicketId> = Vec::new();
+        for event in poll.events.into_iter().filter(StorageEvent::is_foreign) {
+            if let Some(id) = event.ticket_id {
+                if !changed.contains(&id) {
+                    changed.push(id);
+                }
+            }
+        }
+        for id in changed {
+            storage.cache.invalidate_ticket(&id);
+            Box::pin(crate::events::event_bus().emit_external_change(id)).await;
+        }
+    }
+}
+
+/// Runs one MCP session over a TCP connection until the client disconnects
+async fn serve_connection(
+    service: VibeTicketService,
+    stream: tokio::net::TcpStream,
+) -> McpResult<()> {
+    let server = Box::pin(service.serve(stream)).await?;
+    Box::pin(server.waiting()).await?;
+    Ok(())
+}
@@ -1,34 +1,272 @@
 //! MCP service implementation for vibe-ticket
 
+use crate::config::{Config, Identity, TokenScope};
+use crate::core::TicketId;
+use crate::events::TicketEvent;
+use crate::integration::presence::{self, Activity, Presence};
+use crate::mcp::access_log::{self, AccessEntry, AccessLog, AccessStatus};
+use crate::mcp::handlers::events::McpEventHandler;
+use crate::mcp::handlers::resources::Subscriptions;
+use crate::mcp::progress::Progress;
+use crate::mcp::session::SessionContext;
 use crate::storage::FileStorage;
 use rmcp::{
     ErrorData, RoleServer, ServerHandler,
-    model::{ServerCapabilities, ServerInfo, Tool},
-    service::RequestContext,
+    model::{
+        GetPromptRequestParam, GetPromptResult, ListPromptsResult, ListResourceTemplatesResult,
+        ListResourcesResult, PaginatedRequestParam, ReadResourceRequestParam, ReadResourceResult,
+        ServerCapabilities, ServerInfo, SubscribeRequestParam, Tool, UnsubscribeRequestParam,
+    },
+    service::{NotificationContext, RequestContext},
 };
 use serde_json::Value;
 use std::borrow::Cow;
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
 
 /// MCP service implementation
 #[derive(Clone)]
 pub struct VibeTicketService {
     pub storage: Arc<FileStorage>,
     pub project_root: PathBuf,
+    pub session: Arc<Mutex<SessionContext>>,
+    pub subscriptions: Arc<Mutex<Subscriptions>>,
+
+    /// Tools the session may call (see [`crate::mcp::auth`])
+    pub scope: TokenScope,
+
+    /// Name of the API token the session authenticated with
+    pub token: Option<String>,
+
+    /// Log of the tool calls (see [`crate::mcp::access_log`])
+    pub access_log: Arc<AccessLog>,
 }
 
 impl VibeTicketService {
     /// Create a new service instance
+    ///
+    /// The session starts in the server's working directory, so a server
+    /// launched from inside a ticket worktree defaults to that ticket.
     pub fn new(storage: FileStorage, project_root: PathBuf) -> Self {
+        let mut session = SessionContext::default();
+        if let Ok(cwd) = std::env::current_dir() {
+            session.set_working_dir(cwd, &storage);
+        }
+
+        let access_log = Arc::new(AccessLog::new(&project_root.join(".vibe-ticket")));
         Self {
             storage: Arc::new(storage),
             project_root,
+            session: Arc::new(Mutex::new(session)),
+            subscriptions: Arc::default(),
+            scope: TokenScope::Admin,
+            token: None,
+            access_log,
+        }
+    }
+
+    /// Restricts the session to the tools allowed for `scope`
+    #[must_use]
+    pub const fn with_scope(mut self, scope: TokenScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Records the name of the API token the session authenticated with in
+    /// the access log
+    #[must_use]
+    pub fn with_token_name(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Appends a tool call to the access log
+    ///
+    /// The log is advisory, so failures are only logged.
+    fn log_access(&self, mut entry: AccessEntry, client: Option<String>) {
+        entry.token.clone_from(&self.token);
+        entry.client = client;
+        if let Err(e) = self.access_log.append(&entry) {
+            tracing::warn!("Failed to write the access log: {e}");
+        }
+    }
+
+    /// Lock the session context
+    pub fn session(&self) -> MutexGuard<'_, SessionContext> {
+        self.session.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the resource subscriptions
+    pub fn subscriptions(&self) -> MutexGuard<'_, Subscriptions> {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Report a change made by a tool to the subscribed clients
+    ///
+    /// The future is boxed to keep the futures of the tools small.
+    pub fn emit(event: TicketEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            if let Err(e) = Box::pin(crate::events::emit_event(event)).await {
+                tracing::warn!("Failed to emit event: {e}");
+            }
+        })
+    }
+
+    /// Record that the tool call that just ended worked on `ticket`
+    ///
+    /// Tools needing more than the read-only `scope` count as edits. The
+    /// first heartbeat starts the presence of the session, named after the
+    /// project's user and the MCP `client`. Presence is advisory, so failures
+    /// are only logged.
+    pub fn record_presence(&self, ticket: &TicketId, scope: TokenScope, client: Option<String>) {
+        let activity = if scope == TokenScope::ReadOnly {
+            Activity::Viewing
+        } else {
+            Activity::Editing
+        };
+        let mut session = self.session();
+        let heartbeats = session.presence.get_or_insert_with(|| {
+            let config = Config::load_for_project(&self.project_root).unwrap_or_default();
+            presence::Session::new(Identity::resolve(&config.user).name, client)
+        });
+        heartbeats.touch(ticket, activity, chrono::Utc::now());
+        // Saving under the lock keeps concurrent calls from reverting the file
+        let saved = Presence::new(self.project_root.join(".vibe-ticket")).save(heartbeats);
+        drop(session);
+        if let Err(e) = saved {
+            tracing::warn!("Failed to record presence: {e}");
         }
     }
 
+    /// ID of the presence of the session, once it touched a ticket
+    pub fn presence_id(&self) -> Option<String> {
+        self.session()
+            .presence
+            .as_ref()
+            .map(|presence| presence.id.clone())
+    }
+
+    /// Run the tool `name` as an operation of its own, returning its output
+    /// and the ticket it worked on
+    ///
+    /// Each tool call is undone on its own, even while others run.
+    pub(crate) async fn run_tool(
+        &self,
+        name: &str,
+        arguments: Value,
+        progress: &Progress,
+    ) -> (Result<Value, String>, Option<TicketId>) {
+        Box::pin(crate::storage::in_operation(
+            format!("mcp {name}"),
+            crate::mcp::session::track_touched(self.dispatch(name, arguments, progress)),
+        ))
+        .await
+    }
+
+    /// Run the tool `name`
+    async fn dispatch(
+        &self,
+        name: &str,
+        arguments: Value,
+        progress: &Progress,
+    ) -> Result<Value, String> {
+        use crate::mcp::handlers;
+
+        match name {
+            // Ticket operations
+            "vibe-ticket_new" => Box::pin(handlers::tickets::handle_new(self, arguments)).await,
+            "vibe-ticket_list" => handlers::tickets::handle_list(self, arguments),
+            "vibe-ticket_show" => handlers::tickets::handle_show(self, arguments).await,
+            "vibe-ticket_edit" => Box::pin(handlers::tickets::handle_edit(self, arguments)).await,
+            "vibe-ticket_bulk_update" => {
+                Box::pin(handlers::tickets::handle_bulk_update(
+                    self, arguments, progress,
+                ))
+                .await
+            },
+            "vibe-ticket_close" => Box::pin(handlers::tickets::handle_close(self, arguments)).await,
+            "vibe-ticket_start" => Box::pin(handlers::tickets::handle_start(self, arguments)).await,
+            "vibe-ticket_check" => handlers::tickets::handle_check(self, arguments),
+
+            // Task operations
+            "vibe-ticket_task_add" => Box::pin(handlers::tasks::handle_add(self, arguments)).await,
+            "vibe-ticket_task_complete" => {
+                Box::pin(handlers::tasks::handle_complete(self, arguments)).await
+            },
+            "vibe-ticket_task_list" => handlers::tasks::handle_list(self, arguments).await,
+            "vibe-ticket_task_remove" => {
+                Box::pin(handlers::tasks::handle_remove(self, arguments)).await
+            },
+
+            // Worktree operations
+            "vibe-ticket_worktree_list" => handlers::worktree::handle_list(self, arguments),
+            "vibe-ticket_worktree_remove" => {
+                handlers::worktree::handle_remove(self, arguments).await
+            },
+            "vibe-ticket_worktree_prune" => handlers::worktree::handle_prune(self, arguments),
+
+            // Search and export
+            "vibe-ticket_search" => handlers::search::handle_search(self, arguments),
+            "vibe-ticket_export" => handlers::search::handle_export(self, arguments).await,
+            "vibe-ticket_import" => {
+                handlers::search::handle_import(self, arguments, progress).await
+            },
+            "vibe-ticket_rebuild_index" => {
+                handlers::search::handle_rebuild_index(self, arguments, progress)
+            },
+
+            // Reports
+            "vibe-ticket_report" => handlers::report::handle_report(self, arguments),
+
+            // Config operations
+            "vibe-ticket_config_show" => handlers::config::handle_show(self, arguments),
+            "vibe-ticket_config_set" => handlers::config::handle_set(self, arguments),
+
+            // Spec operations
+            "vibe-ticket_spec_add" => Box::pin(handlers::spec::handle_add(self, arguments)).await,
+            "vibe-ticket_spec_update" => {
+                Box::pin(handlers::spec::handle_update(self, arguments)).await
+            },
+            "vibe-ticket_spec_check" => handlers::spec::handle_check(self, arguments).await,
+
+            // Session context
+            "vibe-ticket_context_set" => handlers::context::handle_set(self, arguments).await,
+            "vibe-ticket_context_show" => handlers::context::handle_show(self, arguments),
+            "vibe-ticket_context_pack" => handlers::context::handle_pack(self, arguments),
+
+            _ => Err(format!("Unknown tool: {}", name)),
+        }
+    }
+
+    /// Checks the arguments of a tool call against the input schema of the
+    /// tool
+    ///
+    /// The error names the failing argument and carries an example of valid
+    /// arguments, so that clients can fix the call instead of guessing.
+    fn validate_arguments(name: &str, arguments: &Value) -> Result<(), ErrorData> {
+        use crate::mcp::handlers::schema_helper;
+
+        let Some(tool) = Self::get_tools().into_iter().find(|tool| tool.name == name) else {
+            return Ok(());
+        };
+        schema_helper::validate(&tool.input_schema, arguments).map_err(|violation| {
+            let example = schema_helper::example(&tool.input_schema, violation.argument());
+            ErrorData::invalid_params(
+                format!("{violation}. Example of valid arguments: {example}"),
+                Some(serde_json::json!({
+                    "field": violation.field,
+                    "reason": violation.reason,
+                    "example": example,
+                })),
+            )
+        })
+    }
+
     /// Get all available tools
     pub fn get_tools() -> Vec<Tool> {
         use crate::mcp::handlers;
@@ -39,8 +277,10 @@ impl VibeTicketService {
         tools.extend(handlers::tasks::register_tools());
         tools.extend(handlers::worktree::register_tools());
         tools.extend(handlers::search::register_tools());
+        tools.extend(handlers::report::register_tools());
         tools.extend(handlers::config::register_tools());
         tools.extend(handlers::spec::register_tools());
+        tools.extend(handlers::context::register_tools());
 
         tools
     }
@@ -53,21 +293,120 @@ impl ServerHandler for VibeTicketService {
         ServerInfo {
             instructions: Some(
                 "vibe-ticket MCP server provides comprehensive ticket management capabilities. \
-                 Use the available tools to create, manage, and track tickets, tasks, and worktrees."
+                 Use the available tools to create, manage, and track tickets, tasks, and worktrees. \
+                 Tickets and spec documents are also available as resources, and prompts such \
+                 as triage-ticket guide common workflows."
                     .into(),
             ),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_prompts()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_resources_list_changed()
+                .enable_tools()
+                .build(),
             ..Default::default()
         }
     }
 
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.subscriptions().peer = Some(context.peer);
+        let handler = Arc::new(McpEventHandler::new(&self.subscriptions));
+        Box::pin(crate::events::event_bus().register_handler(handler)).await;
+    }
+
+    async fn list_resources(
+        &self,
+        _pagination: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        let resources = crate::mcp::handlers::resources::list_resources(self)
+            .map_err(|e| ErrorData::internal_error(e, None))?;
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor: None,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _pagination: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: crate::mcp::handlers::resources::resource_templates(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        Box::pin(crate::mcp::handlers::resources::read_resource(
+            self,
+            &request.uri,
+        ))
+        .await
+        .map_err(|e| ErrorData::resource_not_found(e, None))
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        let target = Box::pin(crate::mcp::handlers::resources::subscription_target(
+            self,
+            &request.uri,
+        ))
+        .await
+        .map_err(|e| ErrorData::invalid_params(e, None))?;
+        self.subscriptions().uris.insert(request.uri, target);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.subscriptions().uris.remove(&request.uri);
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _pagination: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult {
+            prompts: crate::mcp::handlers::prompts::list_prompts(),
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        crate::mcp::handlers::prompts::get_prompt(self, &request.name, request.arguments.as_ref())
+            .map_err(|e| ErrorData::invalid_params(e, None))
+    }
+
     async fn list_tools(
         &self,
         _pagination: Option<rmcp::model::PaginatedRequestParam>,
         _ctx: RequestContext<RoleServer>,
     ) -> Result<rmcp::model::ListToolsResult, rmcp::ErrorData> {
+        let tools = Self::get_tools()
+            .into_iter()
+            .filter(|tool| crate::mcp::handlers::required_scope(&tool.name) <= self.scope)
+            .collect();
         Ok(rmcp::model::ListToolsResult {
-            tools: Self::get_tools(),
+            tools,
             next_cursor: None,
         })
     }
@@ -75,7 +414,7 @@ impl ServerHandler for VibeTicketService {
     fn call_tool(
         &self,
         request: rmcp::model::CallToolRequestParam,
-        _ctx: RequestContext<RoleServer>,
+        ctx: RequestContext<RoleServer>,
     ) -> Pin<
         Box<
             dyn Future<Output = Result<rmcp::model::CallToolResult, rmcp::ErrorData>>
@@ -83,92 +422,56 @@ impl ServerHandler for VibeTicketService {
                 + 'static,
         >,
     > {
+        use crate::mcp::handlers;
+
         let service = self.clone();
         let name = request.name.clone();
         let arguments = Value::Object(request.arguments.unwrap_or_default());
+        let progress = Progress::from_context(&ctx);
+        let client = ctx
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone());
+
+        let params_hash = access_log::params_hash(&arguments);
+        let started_at = chrono::Utc::now();
+        let started = Instant::now();
+        let required = handlers::required_scope(&name);
+        if required > service.scope {
+            let message = format!(
+                "Tool '{name}' needs the {required} scope; this session has {}",
+                service.scope
+            );
+            let mut entry = AccessEntry::new(&name, params_hash, started_at, started.elapsed());
+            entry.status = AccessStatus::Denied;
+            entry.error = Some(message.clone());
+            service.log_access(entry, client);
+            return Box::pin(async move { Err(ErrorData::invalid_request(message, None)) });
+        }
+        if let Err(error) = Self::validate_arguments(&name, &arguments) {
+            let mut entry = AccessEntry::new(&name, params_hash, started_at, started.elapsed());
+            entry.status = AccessStatus::Error;
+            entry.error = Some(error.message.to_string());
+            service.log_access(entry, client);
+            return Box::pin(async move { Err(error) });
+        }
 
         Box::pin(async move {
-            let result = match name.as_ref() {
-                // Ticket operations
-                "vibe-ticket_new" => crate::mcp::handlers::tickets::handle_new(&service, arguments),
-                "vibe-ticket_list" => {
-                    crate::mcp::handlers::tickets::handle_list(&service, arguments)
-                },
-                "vibe-ticket_show" => {
-                    crate::mcp::handlers::tickets::handle_show(&service, arguments).await
-                },
-                "vibe-ticket_edit" => {
-                    crate::mcp::handlers::tickets::handle_edit(&service, arguments).await
-                },
-                "vibe-ticket_close" => {
-                    crate::mcp::handlers::tickets::handle_close(&service, arguments).await
-                },
-                "vibe-ticket_start" => {
-                    crate::mcp::handlers::tickets::handle_start(&service, arguments).await
-                },
-                "vibe-ticket_check" => {
-                    crate::mcp::handlers::tickets::handle_check(&service, arguments)
-                },
-
-                // Task operations
-                "vibe-ticket_task_add" => {
-                    crate::mcp::handlers::tasks::handle_add(&service, arguments).await
-                },
-                "vibe-ticket_task_complete" => {
-                    crate::mcp::handlers::tasks::handle_complete(&service, arguments).await
-                },
-                "vibe-ticket_task_list" => {
-                    crate::mcp::handlers::tasks::handle_list(&service, arguments).await
-                },
-                "vibe-ticket_task_remove" => {
-                    crate::mcp::handlers::tasks::handle_remove(&service, arguments).await
-                },
-
-                // Worktree operations
-                "vibe-ticket_worktree_list" => {
-                    crate::mcp::handlers::worktree::handle_list(&service, arguments)
-                },
-                "vibe-ticket_worktree_remove" => {
-                    crate::mcp::handlers::worktree::handle_remove(&service, arguments).await
-                },
-                "vibe-ticket_worktree_prune" => {
-                    crate::mcp::handlers::worktree::handle_prune(&service, arguments)
-                },
-
-                // Search and export
-                "vibe-ticket_search" => {
-                    crate::mcp::handlers::search::handle_search(&service, arguments)
-                },
-                "vibe-ticket_export" => {
-                    crate::mcp::handlers::search::handle_export(&service, arguments).await
-                },
-                "vibe-ticket_import" => {
-                    crate::mcp::handlers::search::handle_import(&service, arguments)
-                },
-
-                // Config operations
-                "vibe-ticket_config_show" => {
-                    crate::mcp::handlers::config::handle_show(&service, arguments)
-                },
-                "vibe-ticket_config_set" => {
-                    crate::mcp::handlers::config::handle_set(&service, arguments)
-                },
-
-                // Spec operations
-                "vibe-ticket_spec_add" => {
-                    crate::mcp::handlers::spec::handle_add(&service, arguments).await
-                },
-                "vibe-ticket_spec_update" => {
-                    crate::mcp::handlers::spec::handle_update(&service, arguments).await
-                },
-                "vibe-ticket_spec_check" => {
-                    crate::mcp::handlers::spec::handle_check(&service, arguments).await
-                },
-
-                _ => Err(format!("Unknown tool: {}", name)),
-            };
-
-            match result {
+            let (result, touched) = Box::pin(service.run_tool(&name, arguments, &progress)).await;
+
+            let mut entry = AccessEntry::new(&name, params_hash, started_at, started.elapsed());
+            if let Err(e) = &result {
+                entry.status = AccessStatus::Error;
+                entry.error = Some(e.clone());
+            }
+            service.log_access(entry, client.clone());
+
+            if let (Ok(_), Some(ticket)) = (&result, touched) {
+                service.record_presence(&ticket, required, client);
+            }
+
+            // Results carry the schema version like the CLI JSON output
+            match result.map(crate::cli::schema::versioned) {
                 Ok(content) => Ok(rmcp::model::CallToolResult {
                     content: vec![rmcp::model::Content::text(
                         serde_json::to_string_pretty(&content)
@@ -0,0 +1,162 @@
+//! Per-session context for the MCP server
+//!
+//! AI assistants usually work inside a single ticket worktree. The session
+//! context remembers the working directory reported by the client, the
+//! worktree it belongs to and the last ticket a tool referenced, so that tools
+//! can default their `ticket` parameter instead of guessing. It also keeps
+//! the heartbeats that tell other sessions which tickets this one is working
+//! on (see [`crate::integration::presence`]).
+
+use crate::core::TicketId;
+use crate::integration::presence;
+use crate::storage::{FileStorage, TicketRepository};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// Marker used in worktree directory names (`{project}-vibeticket-{slug}`)
+const WORKTREE_MARKER: &str = "vibeticket-";
+
+/// Context tracked for the lifetime of an MCP session
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SessionContext {
+    /// Working directory reported by the client
+    pub working_dir: Option<PathBuf>,
+
+    /// Ticket worktree containing the working directory
+    pub worktree: Option<PathBuf>,
+
+    /// Ticket owning the current worktree
+    pub worktree_ticket: Option<TicketId>,
+
+    /// Ticket most recently referenced by a tool call
+    pub last_ticket: Option<TicketId>,
+
+    /// Presence of the session, once it touched a ticket
+    #[serde(skip)]
+    pub presence: Option<presence::Session>,
+}
+
+impl SessionContext {
+    /// Sets the working directory and detects the ticket worktree it belongs to
+    pub fn set_working_dir(&mut self, dir: PathBuf, storage: &FileStorage) {
+        self.worktree = None;
+        self.worktree_ticket = None;
+
+        if let Some((worktree, slug)) = find_ticket_worktree(&dir) {
+            self.worktree_ticket = storage
+                .load_all()
+                .ok()
+                .and_then(|tickets| tickets.into_iter().find(|t| t.slug == slug))
+                .map(|t| t.id);
+            self.worktree = Some(worktree);
+        }
+
+        self.working_dir = Some(dir);
+    }
+
+    /// Records a ticket explicitly referenced by a tool call
+    pub fn record_ticket(&mut self, id: &TicketId) {
+        self.last_ticket = Some(id.clone());
+        touch(id);
+    }
+
+    /// Returns the ticket a tool should default to, if any
+    ///
+    /// The worktree ticket wins over the last referenced ticket, because the
+    /// directory the assistant is editing in is the strongest signal.
+    pub fn default_ticket(&self) -> Option<&TicketId> {
+        self.worktree_ticket.as_ref().or(self.last_ticket.as_ref())
+    }
+}
+
+tokio::task_local! {
+    /// Ticket the tool call running on the task works on
+    static TOUCHED: RefCell<Option<TicketId>>;
+}
+
+/// Runs a tool call, returning its output and the ticket it worked on
+///
+/// Tool calls of a session may run concurrently, so the ticket is tracked
+/// per task rather than in the shared [`SessionContext`].
+pub async fn track_touched<F: Future>(call: F) -> (F::Output, Option<TicketId>) {
+    TOUCHED
+        .scope(RefCell::new(None), async {
+            let output = call.await;
+            (output, TOUCHED.with(RefCell::take))
+        })
+        .await
+}
+
+/// Records the ticket the current tool call works on
+///
+/// Outside of [`track_touched`], for example in tests, this does nothing.
+pub fn touch(id: &TicketId) {
+    let _ = TOUCHED.try_with(|touched| touched.replace(Some(id.clone())));
+}
+
+/// Finds the ticket worktree containing `dir` and the ticket slug it encodes
+fn find_ticket_worktree(dir: &Path) -> Option<(PathBuf, String)> {
+    dir.ancestors().find_map(|path| {
+        let name = path.file_name()?.to_str()?;
+        let (_, slug) = name.split_once(WORKTREE_MARKER)?;
+        (!slug.is_empty()).then(|| (path.to_path_buf(), slug.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_ticket_worktree() {
+        let (worktree, slug) =
+            find_ticket_worktree(Path::new("/work/app-vibeticket-202501011200-fix-login/src"))
+                .unwrap();
+        assert_eq!(
+            worktree,
+            PathBuf::from("/work/app-vibeticket-202501011200-fix-login")
+        );
+        assert_eq!(slug, "202501011200-fix-login");
+
+        assert!(find_ticket_worktree(Path::new("/work/app/src")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_track_touched() {
+        let id = TicketId::new();
+        let (output, touched) = track_touched(async {
+            touch(&TicketId::new());
+            touch(&id);
+            42
+        })
+        .await;
+        assert_eq!(output, 42);
+        assert_eq!(touched, Some(id));
+        assert_eq!(track_touched(async {}).await.1, None);
+    }
+
+    #[test]
+    fn test_default_ticket_prefers_worktree() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        let ticket = Ticket::new("fix-login", "Fix login");
+        storage.save(&ticket).unwrap();
+
+        let mut context = SessionContext::default();
+        assert!(context.default_ticket().is_none());
+
+        let other = TicketId::new();
+        context.record_ticket(&other);
+        assert_eq!(context.default_ticket(), Some(&other));
+
+        context.set_working_dir(
+            temp_dir.path().join("app-vibeticket-fix-login/src"),
+            &storage,
+        );
+        assert_eq!(context.default_ticket(), Some(&ticket.id));
+    }
+}
@@ -0,0 +1,214 @@
+//! Platform differences in paths and launched programs
+//!
+//! Worktree prefixes are written with `/` in configuration files but may use
+//! `\` on Windows, git prints paths with forward slashes even on Windows
+//! (`C:/work/app`), `canonicalize` returns verbatim paths (`\\?\C:\work`)
+//! that git and most editors reject, and the programs that open files and
+//! URLs differ per system. This module keeps those differences in one place
+//! so that command handlers can stay platform-agnostic.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directory a worktree prefix is relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixBase {
+    /// The parent of the project root (`../`)
+    Parent,
+    /// The project root itself (`./` or no leading component)
+    Project,
+}
+
+/// Splits a worktree prefix into its base directory and the name prefix
+///
+/// Both separators are accepted after the leading `..` or `.`, so
+/// `..\app-` and `../app-` mean the same on every platform.
+pub fn split_prefix(prefix: &str) -> (PrefixBase, &str) {
+    let strip = |lead: &str| {
+        prefix
+            .strip_prefix(lead)
+            .and_then(|rest| rest.strip_prefix(['/', '\\']))
+    };
+
+    strip("..")
+        .map(|rest| (PrefixBase::Parent, rest))
+        .or_else(|| strip(".").map(|rest| (PrefixBase::Project, rest)))
+        .unwrap_or((PrefixBase::Project, prefix))
+}
+
+/// Resolves the base directory of a worktree prefix
+///
+/// Relative project roots (e.g. `.`) are made absolute first, so that `../`
+/// still means the parent directory. Returns `None` if the prefix points
+/// to the parent of a root directory.
+pub fn prefix_base_dir(project_root: &Path, base: PrefixBase) -> Option<PathBuf> {
+    let root = absolute(project_root);
+    match base {
+        PrefixBase::Parent => root.parent().map(Path::to_path_buf),
+        PrefixBase::Project => Some(root),
+    }
+}
+
+/// Returns `path` as an absolute path without resolving symlinks
+///
+/// Unlike `canonicalize`, this never produces a verbatim `\\?\` path on
+/// Windows. Falls back to `path` unchanged if the current directory is
+/// unavailable.
+pub fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Removes the verbatim prefix (`\\?\C:\` or `\\?\UNC\server\`) that
+/// `canonicalize` adds on Windows
+///
+/// Other paths are returned unchanged.
+pub fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path;
+    };
+    let stripped = text
+        .strip_prefix(r"\\?\UNC\")
+        .map(|rest| PathBuf::from(format!(r"\\{rest}")))
+        .or_else(|| text.strip_prefix(r"\\?\").map(PathBuf::from));
+    stripped.unwrap_or(path)
+}
+
+/// Converts a path printed by git into a native path
+///
+/// git always prints `/`-separated paths; on Windows they are turned into
+/// `\`-separated ones so that comparisons with other native paths work.
+pub fn path_from_git(path: &str) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(path.replace('/', "\\"))
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+/// Returns the editor configured through `VISUAL` or `EDITOR`
+///
+/// Defaults to `notepad` on Windows and `vi` elsewhere.
+pub fn editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| default_editor().to_string())
+}
+
+const fn default_editor() -> &'static str {
+    if cfg!(windows) { "notepad" } else { "vi" }
+}
+
+/// Builds the command opening `path` in `editor`
+///
+/// The editor may carry arguments (`code --wait`). On Windows it is run
+/// through `cmd /C` so that batch wrappers such as `code.cmd` are found.
+pub fn editor_command(editor: &str, path: &Path) -> Command {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(default_editor());
+
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", program]);
+        command
+    } else {
+        Command::new(program)
+    };
+    command.args(parts).arg(path);
+    command
+}
+
+/// Builds the command opening a URL or file with the default application
+pub fn open_command(target: &str) -> Command {
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(target);
+        command
+    } else if cfg!(windows) {
+        // `cmd /C start` would split the URL at `&` and treat the first
+        // quoted argument as a window title
+        let mut command = Command::new("rundll32");
+        command.args(["url.dll,FileProtocolHandler", target]);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(target);
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_prefix_accepts_both_separators() {
+        assert_eq!(split_prefix("../app-"), (PrefixBase::Parent, "app-"));
+        assert_eq!(split_prefix(r"..\app-"), (PrefixBase::Parent, "app-"));
+        assert_eq!(split_prefix("./wt/app-"), (PrefixBase::Project, "wt/app-"));
+        assert_eq!(
+            split_prefix(r".\wt\app-"),
+            (PrefixBase::Project, r"wt\app-")
+        );
+        assert_eq!(split_prefix("app-"), (PrefixBase::Project, "app-"));
+        assert_eq!(split_prefix(".app-"), (PrefixBase::Project, ".app-"));
+    }
+
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(
+            strip_verbatim(PathBuf::from(r"\\?\C:\work\app")),
+            PathBuf::from(r"C:\work\app")
+        );
+        assert_eq!(
+            strip_verbatim(PathBuf::from(r"\\?\UNC\server\share\app")),
+            PathBuf::from(r"\\server\share\app")
+        );
+        assert_eq!(
+            strip_verbatim(PathBuf::from("/work/app")),
+            PathBuf::from("/work/app")
+        );
+    }
+
+    #[test]
+    fn test_editor_command_splits_arguments() {
+        let command = editor_command("code --wait", Path::new("notes.md"));
+        let args: Vec<_> = command.get_args().collect();
+        if cfg!(windows) {
+            assert_eq!(command.get_program(), "cmd");
+            assert_eq!(args, ["/C", "code", "--wait", "notes.md"]);
+        } else {
+            assert_eq!(command.get_program(), "code");
+            assert_eq!(args, ["--wait", "notes.md"]);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_paths() {
+        assert_eq!(
+            path_from_git("/work/app-vibeticket-fix"),
+            PathBuf::from("/work/app-vibeticket-fix")
+        );
+        assert_eq!(
+            prefix_base_dir(Path::new("/work/app"), PrefixBase::Parent),
+            Some(PathBuf::from("/work"))
+        );
+        assert_eq!(prefix_base_dir(Path::new("/"), PrefixBase::Parent), None);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_paths() {
+        assert_eq!(
+            path_from_git("C:/work/app-vibeticket-fix"),
+            PathBuf::from(r"C:\work\app-vibeticket-fix")
+        );
+        assert_eq!(
+            prefix_base_dir(Path::new(r"C:\work\app"), PrefixBase::Parent),
+            Some(PathBuf::from(r"C:\work"))
+        );
+        assert_eq!(prefix_base_dir(Path::new(r"C:\"), PrefixBase::Parent), None);
+    }
+}
@@ -0,0 +1,363 @@
+//! Plugin commands
+//!
+//! Commands contributed by plugins live in their own namespace,
+//! `vibe-ticket x <plugin> <command>`, so they can never shadow or conflict
+//! with built-in subcommands. Arguments are parsed against the schema of the
+//! manifest and handed to the plugin program as environment variables:
+//!
+//! - `VIBE_TICKET_ARGS`: all arguments as a JSON object
+//! - `VIBE_TICKET_ARG_<NAME>`: each argument, with the name upper-cased and
+//!   `-` replaced by `_`
+//! - `VIBE_TICKET_PROJECT_DIR` and `VIBE_TICKET_PLUGIN_DIR`
+//!
+//! The program runs in the project root with the terminal's standard streams.
+
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+use super::manifest::{ArgKind, CommandSpec, MANIFEST_FILE, PluginManifest};
+use crate::config::PluginsConfig;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Plugin directory shared by all users of the machine
+const SYSTEM_PLUGIN_DIR: &str = "/usr/local/share/vibe-ticket/plugins";
+
+/// Plugin found in the plugin directory
+#[derive(Debug, Clone)]
+pub struct LoadedPlugin {
+    /// Validated manifest
+    pub manifest: PluginManifest,
+
+    /// Directory the plugin is installed in
+    pub dir: PathBuf,
+}
+
+/// Loads the plugins installed in `plugins_dir`, sorted by name
+///
+/// Every subdirectory with a manifest is a plugin. When `enabled` is not
+/// empty, only the plugins it lists are loaded. Invalid manifests and
+/// duplicate plugin names are errors.
+pub fn discover(plugins_dir: &Path, enabled: &[String]) -> Result<Vec<LoadedPlugin>> {
+    let Ok(entries) = fs::read_dir(plugins_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut plugins = Vec::new();
+    for entry in entries {
+        let dir = entry
+            .context("Failed to read plugin directory entry")?
+            .path();
+        if !dir.join(MANIFEST_FILE).is_file() {
+            continue;
+        }
+
+        let manifest = PluginManifest::load(&dir)?;
+        if enabled.is_empty() || enabled.contains(&manifest.name) {
+            plugins.push(LoadedPlugin { manifest, dir });
+        }
+    }
+
+    plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    if let Some(pair) = plugins
+        .windows(2)
+        .find(|pair| pair[0].manifest.name == pair[1].manifest.name)
+    {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Plugin '{}' is installed twice: {} and {}",
+            pair[0].manifest.name,
+            pair[0].dir.display(),
+            pair[1].dir.display()
+        )));
+    }
+
+    Ok(plugins)
+}
+
+/// Directories searched for plugins, highest precedence first
+///
+/// The project plugin directory and the configured search paths (relative
+/// paths are resolved against the project root) come before the user
+/// (`~/.config/vibe-ticket/plugins`) and system directories.
+pub fn search_dirs(project_root: &Path, config: &PluginsConfig) -> Vec<PathBuf> {
+    let mut dirs = project_dirs(project_root, config);
+    dirs.extend(installed_dirs());
+    dirs
+}
+
+/// Plugin directories configured by the project: the plugin directory and
+/// the search paths
+///
+/// They come with the project, so cloning a repository brings its plugins.
+pub fn project_dirs(project_root: &Path, config: &PluginsConfig) -> Vec<PathBuf> {
+    let mut dirs = vec![project_root.join(&config.directory)];
+    dirs.extend(config.search_paths.iter().map(|p| project_root.join(p)));
+    dirs
+}
+
+/// Plugin directories of the user and the system
+pub(super) fn installed_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(base) = directories::BaseDirs::new() {
+        dirs.push(base.config_dir().join("vibe-ticket").join("plugins"));
+    }
+    if cfg!(unix) {
+        dirs.push(PathBuf::from(SYSTEM_PLUGIN_DIR));
+    }
+    dirs
+}
+
+/// Loads the plugins of several directories, see [`discover`]
+///
+/// A plugin found in an earlier directory hides plugins of the same name in
+/// later ones, so a project can override a user or system plugin.
+pub fn discover_all(dirs: &[PathBuf], enabled: &[String]) -> Result<Vec<LoadedPlugin>> {
+    let mut plugins: Vec<LoadedPlugin> = Vec::new();
+    for dir in dirs {
+        for plugin in discover(dir, enabled)? {
+            if !plugins
+                .iter()
+                .any(|p| p.manifest.name == plugin.manifest.name)
+            {
+                plugins.push(plugin);
+            }
+        }
+    }
+
+    plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    Ok(plugins)
+}
+
+impl CommandSpec {
+    /// Parses command-line arguments against the argument schema
+    ///
+    /// Accepts `--name value`, `--name=value` and bare `--name` for boolean
+    /// arguments. Missing optional arguments take their default.
+    pub fn parse_args(&self, raw: &[String]) -> Result<Map<String, Value>> {
+        let mut values = Map::new();
+        let mut raw = raw.iter();
+
+        while let Some(token) = raw.next() {
+            let Some(option) = token.strip_prefix("--") else {
+                return Err(self.usage_error(&format!("unexpected argument '{token}'")));
+            };
+            let (name, inline) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (option, None),
+            };
+            let spec = self
+                .args
+                .iter()
+                .find(|a| a.name == name)
+                .ok_or_else(|| self.usage_error(&format!("unknown argument '--{name}'")))?;
+
+            let text = match (inline, spec.kind) {
+                (Some(text), _) => text,
+                (None, ArgKind::Boolean) => "true",
+                (None, _) => raw
+                    .next()
+                    .ok_or_else(|| self.usage_error(&format!("'--{name}' needs a value")))?,
+            };
+            let value = spec.kind.parse(text).ok_or_else(|| {
+                self.usage_error(&format!(
+                    "'--{name}' expects a {:?}, got '{text}'",
+                    spec.kind
+                ))
+            })?;
+            values.insert(name.to_string(), value);
+        }
+
+        for spec in &self.args {
+            if values.contains_key(&spec.name) {
+                continue;
+            }
+            if spec.required {
+                return Err(self.usage_error(&format!("missing required '--{}'", spec.name)));
+            }
+            if let Some(default) = &spec.default {
+                values.insert(spec.name.clone(), default.clone());
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Runs the command of the plugin installed in `plugin_dir`
+    pub fn execute(
+        &self,
+        plugin_dir: &Path,
+        project_root: &Path,
+        args: &Map<String, Value>,
+    ) -> Result<ExitStatus> {
+        let program = self.program(plugin_dir);
+        let mut command = Command::new(&program);
+        command
+            .args(&self.run[1..])
+            .current_dir(project_root)
+            .env("VIBE_TICKET_ARGS", Value::Object(args.clone()).to_string())
+            .env("VIBE_TICKET_PROJECT_DIR", project_root)
+            .env("VIBE_TICKET_PLUGIN_DIR", plugin_dir);
+
+        for (name, value) in args {
+            let name = format!("VIBE_TICKET_ARG_{}", name.to_uppercase().replace('-', "_"));
+            let value = value
+                .as_str()
+                .map_or_else(|| value.to_string(), ToString::to_string);
+            command.env(name, value);
+        }
+
+        command
+            .status()
+            .with_context(|| format!("Failed to run plugin program {}", program.display()))
+    }
+
+    /// One-line usage of the command
+    pub fn usage(&self) -> String {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| {
+                let value = match arg.kind {
+                    ArgKind::Boolean => String::new(),
+                    kind => format!(" <{}>", format!("{kind:?}").to_lowercase()),
+                };
+                if arg.required {
+                    format!("--{}{value}", arg.name)
+                } else {
+                    format!("[--{}{value}]", arg.name)
+                }
+            })
+            .collect();
+        format!("{} {}", self.name, args.join(" "))
+            .trim_end()
+            .to_string()
+    }
+
+    fn usage_error(&self, message: &str) -> VibeTicketError {
+        VibeTicketError::InvalidInput(format!("{message}\nUsage: {}", self.usage()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_plugin(root: &Path, dir: &str, manifest: &str) {
+        let dir = root.join(dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+    }
+
+    fn greet_plugin() -> &'static str {
+        r#"
+name: hello
+version: 1.0.0
+commands:
+  - name: greet
+    run: ["sh", "-c", "test \"$VIBE_TICKET_ARG_NAME\" = world && test \"$VIBE_TICKET_ARG_LOUD\" = true"]
+    args:
+      - name: name
+        required: true
+      - name: times
+        type: integer
+        default: 1
+      - name: loud
+        type: boolean
+"#
+    }
+
+    #[test]
+    fn test_discover_plugins() {
+        let temp_dir = TempDir::new().unwrap();
+        write_plugin(temp_dir.path(), "hello", greet_plugin());
+        write_plugin(temp_dir.path(), "other", "name: other\nversion: 0.1.0\n");
+        fs::create_dir_all(temp_dir.path().join("not-a-plugin")).unwrap();
+
+        let plugins = discover(temp_dir.path(), &[]).unwrap();
+        let names: Vec<_> = plugins.iter().map(|p| p.manifest.name.as_str()).collect();
+        assert_eq!(names, vec!["hello", "other"]);
+
+        let plugins = discover(temp_dir.path(), &["other".to_string()]).unwrap();
+        assert_eq!(plugins.len(), 1);
+
+        write_plugin(temp_dir.path(), "copy", greet_plugin());
+        assert!(discover(temp_dir.path(), &[]).is_err());
+
+        write_plugin(temp_dir.path(), "copy", "name: Bad Name\nversion: 0.1.0\n");
+        assert!(discover(temp_dir.path(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_discover_all_prefers_earlier_directories() {
+        let project = TempDir::new().unwrap();
+        let user = TempDir::new().unwrap();
+        write_plugin(project.path(), "hello", greet_plugin());
+        write_plugin(user.path(), "hello", "name: hello\nversion: 9.0.0\n");
+        write_plugin(user.path(), "other", "name: other\nversion: 0.1.0\n");
+
+        let dirs = vec![project.path().to_path_buf(), user.path().to_path_buf()];
+        let plugins = discover_all(&dirs, &[]).unwrap();
+        let found: Vec<_> = plugins
+            .iter()
+            .map(|p| (p.manifest.name.as_str(), p.manifest.version.as_str()))
+            .collect();
+        assert_eq!(found, vec![("hello", "1.0.0"), ("other", "0.1.0")]);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let manifest: PluginManifest = serde_yaml::from_str(greet_plugin()).unwrap();
+        let greet = manifest.command("greet").unwrap();
+        let parse = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+            greet.parse_args(&args)
+        };
+
+        let values = parse(&["--name", "world", "--loud"]).unwrap();
+        assert_eq!(values["name"], "world");
+        assert_eq!(values["times"], 1);
+        assert_eq!(values["loud"], true);
+
+        let values = parse(&["--name=x", "--times=3", "--loud=false"]).unwrap();
+        assert_eq!(values["times"], 3);
+        assert_eq!(values["loud"], false);
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--name", "x", "--times", "many"]).is_err());
+        assert!(parse(&["--name", "x", "--color", "red"]).is_err());
+        assert!(parse(&["--name"]).is_err());
+        assert!(parse(&["--name", "x", "extra"]).is_err());
+        assert_eq!(
+            greet.usage(),
+            "greet --name <string> [--times <integer>] [--loud]"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_passes_arguments() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest: PluginManifest = serde_yaml::from_str(greet_plugin()).unwrap();
+        let greet = manifest.command("greet").unwrap();
+
+        let args = greet
+            .parse_args(&[
+                "--name".to_string(),
+                "world".to_string(),
+                "--loud".to_string(),
+            ])
+            .unwrap();
+        let status = greet
+            .execute(temp_dir.path(), temp_dir.path(), &args)
+            .unwrap();
+        assert!(status.success());
+
+        let args = greet.parse_args(&["--name=moon".to_string()]).unwrap();
+        let status = greet
+            .execute(temp_dir.path(), temp_dir.path(), &args)
+            .unwrap();
+        assert!(!status.success());
+    }
+}
@@ -0,0 +1,410 @@
+//! Plugin manifests
+//!
+//! A plugin is a directory below the plugin directory containing a
+//! `plugin.yaml` manifest. The manifest declares the commands the plugin
+//! contributes, the arguments each command accepts and the hooks run on
+//! ticket and spec events:
+//!
+//! ```yaml
+//! name: jira
+//! version: 0.1.0
+//! description: Jira synchronization
+//! commands:
+//!   - name: sync
+//!     description: Push open tickets to Jira
+//!     run: ["./sync.sh"]
+//!     args:
+//!       - name: project
+//!         type: string
+//!         required: true
+//!       - name: dry-run
+//!         type: boolean
+//! hooks:
+//!   - event: ticket.closed
+//!     run: ["./close-issue.sh"]
+//! ```
+//!
+//! Manifests are validated when they are loaded, so mistakes are reported
+//! before any plugin command runs.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// File name of a plugin manifest
+pub const MANIFEST_FILE: &str = "plugin.yaml";
+
+/// Manifest describing a plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Plugin name, used as the namespace of its commands
+    pub name: String,
+
+    /// Plugin version
+    pub version: String,
+
+    /// Short description of the plugin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Commands contributed by the plugin
+    #[serde(default)]
+    pub commands: Vec<CommandSpec>,
+
+    /// Programs run on ticket and spec events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookSpec>,
+}
+
+/// Command contributed by a plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandSpec {
+    /// Command name
+    pub name: String,
+
+    /// Short description shown in listings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Program and fixed arguments to execute
+    ///
+    /// A program starting with `./` is resolved relative to the plugin
+    /// directory; any other program is looked up on the `PATH`.
+    pub run: Vec<String>,
+
+    /// Arguments accepted by the command
+    #[serde(default)]
+    pub args: Vec<ArgSpec>,
+}
+
+/// Hook run when a ticket event occurs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookSpec {
+    /// Event the hook reacts to
+    pub event: HookEvent,
+
+    /// Program and fixed arguments to execute, resolved like command programs
+    pub run: Vec<String>,
+}
+
+/// Ticket or spec event a hook can react to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HookEvent {
+    /// A ticket was created
+    #[serde(rename = "ticket.created")]
+    Created,
+    /// A ticket or one of its tasks changed
+    #[serde(rename = "ticket.updated")]
+    Updated,
+    /// A ticket was closed
+    #[serde(rename = "ticket.closed")]
+    Closed,
+    /// A spec was created
+    #[serde(rename = "spec.created")]
+    SpecCreated,
+    /// A phase of a spec was completed
+    #[serde(rename = "spec.phase_completed")]
+    SpecPhaseCompleted,
+    /// A phase of a spec was approved
+    #[serde(rename = "spec.phase_approved")]
+    SpecPhaseApproved,
+    /// A spec became the active spec
+    #[serde(rename = "spec.activated")]
+    SpecActivated,
+}
+
+impl HookEvent {
+    /// Name of the event as written in manifests
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Created => "ticket.created",
+            Self::Updated => "ticket.updated",
+            Self::Closed => "ticket.closed",
+            Self::SpecCreated => "spec.created",
+            Self::SpecPhaseCompleted => "spec.phase_completed",
+            Self::SpecPhaseApproved => "spec.phase_approved",
+            Self::SpecActivated => "spec.activated",
+        }
+    }
+}
+
+/// Argument accepted by a plugin command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgSpec {
+    /// Argument name, passed on the command line as `--<name>`
+    pub name: String,
+
+    /// Type of the value
+    #[serde(rename = "type", default)]
+    pub kind: ArgKind,
+
+    /// Whether the argument must be given
+    #[serde(default)]
+    pub required: bool,
+
+    /// Value used when the argument is not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+
+    /// Help text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+/// Type of a plugin command argument
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgKind {
+    /// Any text
+    #[default]
+    String,
+    /// Signed integer
+    Integer,
+    /// Flag that takes no value on the command line
+    Boolean,
+}
+
+impl ArgKind {
+    /// Returns true if `value` has this type
+    pub fn accepts(self, value: &Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Integer => value.is_i64(),
+            Self::Boolean => value.is_boolean(),
+        }
+    }
+
+    /// Parses a command-line value of this type
+    pub fn parse(self, raw: &str) -> Option<Value> {
+        match self {
+            Self::String => Some(Value::String(raw.to_string())),
+            Self::Integer => raw.parse::<i64>().ok().map(Value::from),
+            Self::Boolean => raw.parse::<bool>().ok().map(Value::Bool),
+        }
+    }
+}
+
+impl PluginManifest {
+    /// Loads and validates the manifest of the plugin in `dir`
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        let yaml = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read plugin manifest {}", path.display()))?;
+        let manifest: Self = serde_yaml::from_str(&yaml).map_err(|e| {
+            VibeTicketError::InvalidInput(format!("plugin manifest {}: {e}", path.display()))
+        })?;
+        manifest.validate().map_err(|e| match e {
+            VibeTicketError::InvalidInput(message) => VibeTicketError::InvalidInput(format!(
+                "plugin manifest {}: {message}",
+                path.display()
+            )),
+            e => e,
+        })?;
+        Ok(manifest)
+    }
+
+    /// Checks that names are well-formed and unique and that defaults match their types
+    pub fn validate(&self) -> Result<()> {
+        check_name("plugin", &self.name)?;
+        if self.version.trim().is_empty() {
+            return Err(invalid(format!("plugin '{}' has no version", self.name)));
+        }
+
+        let mut commands = HashSet::new();
+        for command in &self.commands {
+            check_name("command", &command.name)?;
+            if !commands.insert(command.name.as_str()) {
+                return Err(invalid(format!("duplicate command '{}'", command.name)));
+            }
+            command.validate()?;
+        }
+
+        for hook in &self.hooks {
+            if hook
+                .run
+                .first()
+                .is_none_or(|program| program.trim().is_empty())
+            {
+                return Err(invalid(format!(
+                    "hook for '{}' has no program to run",
+                    hook.event.name()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Hooks reacting to `event`, in manifest order
+    pub fn hooks_for(&self, event: HookEvent) -> impl Iterator<Item = &HookSpec> {
+        self.hooks.iter().filter(move |hook| hook.event == event)
+    }
+
+    /// Finds a command by name
+    pub fn command(&self, name: &str) -> Option<&CommandSpec> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+}
+
+impl CommandSpec {
+    /// Checks the program and the argument schema of the command
+    fn validate(&self) -> Result<()> {
+        if self
+            .run
+            .first()
+            .is_none_or(|program| program.trim().is_empty())
+        {
+            return Err(invalid(format!(
+                "command '{}' has no program to run",
+                self.name
+            )));
+        }
+
+        let mut args = HashSet::new();
+        for arg in &self.args {
+            check_name("argument", &arg.name)?;
+            if !args.insert(arg.name.as_str()) {
+                return Err(invalid(format!(
+                    "duplicate argument '{}' in command '{}'",
+                    arg.name, self.name
+                )));
+            }
+            if arg.required && arg.default.is_some() {
+                return Err(invalid(format!(
+                    "argument '{}' of command '{}' is required and has a default",
+                    arg.name, self.name
+                )));
+            }
+            if arg.default.as_ref().is_some_and(|v| !arg.kind.accepts(v)) {
+                return Err(invalid(format!(
+                    "default of argument '{}' in command '{}' is not a {:?}",
+                    arg.name, self.name, arg.kind
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the program to execute for a plugin installed in `plugin_dir`
+    pub fn program(&self, plugin_dir: &Path) -> PathBuf {
+        resolve_program(&self.run[0], plugin_dir)
+    }
+}
+
+impl HookSpec {
+    /// Resolves the program to execute for a plugin installed in `plugin_dir`
+    pub fn program(&self, plugin_dir: &Path) -> PathBuf {
+        resolve_program(&self.run[0], plugin_dir)
+    }
+}
+
+/// Resolves `./` programs relative to the plugin directory
+fn resolve_program(program: &str, plugin_dir: &Path) -> PathBuf {
+    program
+        .strip_prefix("./")
+        .map_or_else(|| PathBuf::from(program), |rest| plugin_dir.join(rest))
+}
+
+/// Checks that `name` is a lowercase identifier usable on the command line
+fn check_name(kind: &str, name: &str) -> Result<()> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid(format!(
+            "invalid {kind} name '{name}': use lowercase letters, digits, '-' and '_'"
+        )))
+    }
+}
+
+/// Builds a manifest validation error
+const fn invalid(message: String) -> VibeTicketError {
+    VibeTicketError::InvalidInput(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+name: jira
+version: 0.1.0
+commands:
+  - name: sync
+    run: ["./sync.sh", "--quiet"]
+    args:
+      - name: project
+        required: true
+      - name: limit
+        type: integer
+        default: 10
+      - name: dry-run
+        type: boolean
+hooks:
+  - event: ticket.closed
+    run: ["./close-issue.sh"]
+"#;
+
+    #[test]
+    fn test_parse_and_validate_manifest() {
+        let manifest: PluginManifest = serde_yaml::from_str(MANIFEST).unwrap();
+        manifest.validate().unwrap();
+
+        let sync = manifest.command("sync").unwrap();
+        assert_eq!(sync.args[0].kind, ArgKind::String);
+        assert_eq!(sync.args[2].kind, ArgKind::Boolean);
+        assert_eq!(
+            sync.program(Path::new("/plugins/jira")),
+            PathBuf::from("/plugins/jira/sync.sh")
+        );
+
+        let hooks: Vec<_> = manifest.hooks_for(HookEvent::Closed).collect();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(
+            hooks[0].program(Path::new("/plugins/jira")),
+            PathBuf::from("/plugins/jira/close-issue.sh")
+        );
+        assert_eq!(manifest.hooks_for(HookEvent::Created).count(), 0);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_manifests() {
+        let base: PluginManifest = serde_yaml::from_str(MANIFEST).unwrap();
+
+        let mut manifest = base.clone();
+        manifest.name = "Jira Sync".to_string();
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = base.clone();
+        manifest.commands.push(manifest.commands[0].clone());
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = base.clone();
+        manifest.commands[0].run.clear();
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = base.clone();
+        manifest.commands[0].args[1].default = Some(Value::from("ten"));
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = base.clone();
+        manifest.hooks[0].run.clear();
+        assert!(manifest.validate().is_err());
+
+        let mut manifest = base;
+        manifest.commands[0].args[0].default = Some(Value::from("ABC"));
+        assert!(manifest.validate().is_err());
+
+        let unknown_event =
+            "name: x\nversion: '1'\nhooks:\n  - event: ticket.moved\n    run: [x]\n";
+        assert!(serde_yaml::from_str::<PluginManifest>(unknown_event).is_err());
+    }
+}
@@ -7,23 +7,43 @@
 //!
 //! The plugin system supports various extension points:
 //! - **Hooks**: React to ticket lifecycle events (create, update, close)
-//! - **Commands**: Add custom CLI commands
+//! - **Commands**: Add custom CLI commands under `vibe-ticket x <plugin> <command>`
 //! - **Validators**: Custom validation rules for tickets
 //! - **Formatters**: Custom output formats for displaying tickets
 //! - **Integrations**: Connect with external services (GitHub, Jira, Slack)
 //!
 //! # Plugin Discovery
 //!
-//! Plugins are discovered from:
-//! 1. Built-in plugins (compiled into the binary)
-//! 2. System plugin directory (`/usr/local/share/vibe-ticket/plugins/`)
+//! Plugins are discovered from (earlier directories take precedence):
+//! 1. Project plugin directory (`plugins.directory`, `.vibe-ticket/plugins/`)
+//! 2. Additional directories listed in `plugins.search_paths`
 //! 3. User plugin directory (`~/.config/vibe-ticket/plugins/`)
-//! 4. Project plugin directory (`.vibe-ticket/plugins/`)
+//! 4. System plugin directory (`/usr/local/share/vibe-ticket/plugins/`)
+//!
+//! The hooks of the first two only run once enabled (see [Trust Model](#trust-model)).
+//!
+//! Built-in plugins are registered in code with [`PluginRegistry::register`].
+//! The [`slack`] plugin is registered when `plugins.slack` is configured.
+//!
+//! # Command Plugins
+//!
+//! A plugin directory containing a `plugin.yaml` manifest (see
+//! [`PluginManifest`]) contributes external commands. Each command declares
+//! the program to run and a schema of its arguments; the manifest is
+//! validated when plugins are discovered.
+//!
+//! # Hooks
+//!
+//! The manifest can also declare hooks, programs run when a CLI command
+//! creates, updates or closes a ticket (see [`HookEvent`]). Commands report
+//! their changes through [`crate::events::emit_cli_event`], which delivers
+//! them to every plugin in the [`PluginRegistry`].
 //!
 //! # Plugin API
 //!
 //! Plugins implement the `Plugin` trait:
-//! ```ignore
+//! ```
+//! use vibe_ticket::events::TicketEvent;
 //! use vibe_ticket::plugins::{Plugin, PluginContext, PluginResult};
 //!
 //! pub struct MyPlugin;
@@ -37,8 +57,10 @@
 //!         "0.1.0"
 //!     }
 //!
-//!     fn initialize(&mut self, context: &PluginContext) -> PluginResult<()> {
-//!         // Plugin initialization logic
+//!     fn on_event(&self, event: &TicketEvent, context: &PluginContext) -> PluginResult<()> {
+//!         if let Some(id) = event.ticket_id() {
+//!             println!("{id} changed in {}", context.project_root.display());
+//!         }
 //!         Ok(())
 //!     }
 //! }
@@ -46,22 +68,37 @@
 //!
 //! # Configuration
 //!
-//! Plugins can be configured in the main configuration file:
-//! ```toml
-//! [[plugins]]
-//! name = "github-integration"
-//! enabled = true
-//!
-//! [plugins.config]
-//! token = "$GITHUB_TOKEN"
-//! repo = "owner/repo"
+//! Plugins are configured in the `plugins` section of the project
+//! configuration:
+//! ```yaml
+//! plugins:
+//!   enabled: [jira]          # empty: every user and system plugin
+//!   directory: .vibe-ticket/plugins
+//!   search_paths: [../shared-plugins]
 //! ```
 //!
-//! # Security
+//! # Trust Model
 //!
-//! Plugins run in a sandboxed environment with limited permissions:
-//! - No direct file system access outside designated directories
-//! - Network access only to whitelisted domains
-//! - Resource limits (CPU, memory, execution time)
+//! Plugins are not sandboxed. Hook and command programs are arbitrary
+//! executables run as the current user, with the user's permissions,
+//! environment and network access; installing a plugin means trusting it
+//! like any other program.
+//!
+//! Plugins of the project's directories (`plugins.directory` and
+//! `plugins.search_paths`) come with the repository, so their hooks only
+//! run once `plugins.enabled` names them: an empty list enables the user
+//! and system plugins only. Plugin commands run only when invoked with
+//! `vibe-ticket x`. As `plugins.enabled` is itself part of the project
+//! configuration, review it and the plugins it names before running
+//! commands in a repository you did not write.
+
+mod command;
+mod manifest;
+mod registry;
+pub mod slack;
 
-// TODO: Add submodules as they are implemented
+pub use command::{LoadedPlugin, discover, discover_all, project_dirs, search_dirs};
+pub use manifest::{
+    ArgKind, ArgSpec, CommandSpec, HookEvent, HookSpec, MANIFEST_FILE, PluginManifest,
+};
+pub use registry::{ExternalPlugin, Plugin, PluginContext, PluginRegistry, PluginResult};
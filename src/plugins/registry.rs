@@ -0,0 +1,385 @@
+//! Plugin trait and registry
+//!
+//! Every plugin implements [`Plugin`]. Built-in plugins are registered in
+//! code; plugins installed as directories with a manifest are wrapped in
+//! [`ExternalPlugin`], which runs the hook programs declared in the manifest.
+//! A hook program receives the event as JSON on standard input and the
+//! following environment variables:
+//!
+//! - `VIBE_TICKET_EVENT`: `ticket.created`, `ticket.updated` or `ticket.closed`
+//! - `VIBE_TICKET_TICKET_ID`: ID of the ticket
+//! - `VIBE_TICKET_PROJECT_DIR` and `VIBE_TICKET_PLUGIN_DIR`
+//!
+//! Its output is written to standard error so it never mixes with the
+//! command's (possibly JSON) output.
+
+use std::io::Write as IoWrite;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::command::{LoadedPlugin, discover_all, installed_dirs, search_dirs};
+use super::manifest::HookEvent;
+use super::slack::{self, SlackPlugin};
+use crate::config::Config;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::events::TicketEvent;
+
+/// Result type of plugin operations
+pub type PluginResult<T> = Result<T>;
+
+/// Information about the project a plugin runs in
+#[derive(Debug, Clone)]
+pub struct PluginContext {
+    /// Root directory of the project
+    pub project_root: PathBuf,
+}
+
+impl PluginContext {
+    /// Creates a context for the project in `project_root`
+    pub fn new(project_root: impl Into<PathBuf>) -> Self {
+        Self {
+            project_root: project_root.into(),
+        }
+    }
+
+    /// The project's `.vibe-ticket` directory
+    pub fn vibe_dir(&self) -> PathBuf {
+        self.project_root.join(".vibe-ticket")
+    }
+}
+
+/// Extension of vibe-ticket
+pub trait Plugin: Send + Sync {
+    /// Unique name of the plugin
+    fn name(&self) -> &str;
+
+    /// Version of the plugin
+    fn version(&self) -> &str;
+
+    /// Prepares the plugin before it receives events
+    fn initialize(&mut self, _context: &PluginContext) -> PluginResult<()> {
+        Ok(())
+    }
+
+    /// Reacts to a ticket being created, updated or closed
+    fn on_event(&self, _event: &TicketEvent, _context: &PluginContext) -> PluginResult<()> {
+        Ok(())
+    }
+}
+
+impl From<&TicketEvent> for HookEvent {
+    fn from(event: &TicketEvent) -> Self {
+        match event {
+            TicketEvent::Created(_) => Self::Created,
+            TicketEvent::Closed(..) => Self::Closed,
+            TicketEvent::Updated(_)
+            | TicketEvent::TaskAdded(..)
+            | TicketEvent::TaskCompleted(..)
+            | TicketEvent::TaskRemoved(..)
+            | TicketEvent::StatusChanged(..) => Self::Updated,
+            TicketEvent::SpecCreated(_) => Self::SpecCreated,
+            TicketEvent::SpecPhaseCompleted(..) => Self::SpecPhaseCompleted,
+            TicketEvent::SpecPhaseApproved(..) => Self::SpecPhaseApproved,
+            TicketEvent::SpecActivated(_) => Self::SpecActivated,
+        }
+    }
+}
+
+/// Plugin installed in a plugin directory
+#[derive(Debug, Clone)]
+pub struct ExternalPlugin {
+    loaded: LoadedPlugin,
+}
+
+impl ExternalPlugin {
+    /// Wraps a discovered plugin
+    pub const fn new(loaded: LoadedPlugin) -> Self {
+        Self { loaded }
+    }
+}
+
+impl Plugin for ExternalPlugin {
+    fn name(&self) -> &str {
+        &self.loaded.manifest.name
+    }
+
+    fn version(&self) -> &str {
+        &self.loaded.manifest.version
+    }
+
+    fn on_event(&self, event: &TicketEvent, context: &PluginContext) -> PluginResult<()> {
+        let hook_event = HookEvent::from(event);
+        let payload = serde_json::json!({
+            "event": hook_event.name(),
+            "data": event.to_json(),
+        })
+        .to_string();
+
+        for hook in self.loaded.manifest.hooks_for(hook_event) {
+            let program = hook.program(&self.loaded.dir);
+            let mut command = Command::new(&program);
+            if let Some(id) = event.ticket_id() {
+                command.env("VIBE_TICKET_TICKET_ID", id.to_string());
+            }
+            if let Some(id) = event.spec_id() {
+                command.env("VIBE_TICKET_SPEC_ID", id);
+            }
+            let mut child = command
+                .args(&hook.run[1..])
+                .current_dir(&context.project_root)
+                .env("VIBE_TICKET_EVENT", hook_event.name())
+                .env("VIBE_TICKET_PROJECT_DIR", &context.project_root)
+                .env("VIBE_TICKET_PLUGIN_DIR", &self.loaded.dir)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::from(std::io::stderr()))
+                .spawn()
+                .with_context(|| format!("Failed to run hook program {}", program.display()))?;
+
+            if let Some(mut stdin) = child.stdin.take() {
+                // A hook that does not read its input may exit before the write
+                let _ = stdin.write_all(payload.as_bytes());
+            }
+            let status = child.wait().with_context(|| {
+                format!("Failed to wait for hook program {}", program.display())
+            })?;
+            if !status.success() {
+                return Err(VibeTicketError::custom(format!(
+                    "hook for '{}' failed ({status})",
+                    hook_event.name()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Set of plugins loaded for a project
+pub struct PluginRegistry {
+    plugins: Vec<Box<dyn Plugin>>,
+    context: PluginContext,
+}
+
+impl PluginRegistry {
+    /// Creates an empty registry for the given project
+    pub fn new(context: PluginContext) -> Self {
+        Self {
+            plugins: Vec::new(),
+            context,
+        }
+    }
+
+    /// Loads the enabled plugins of the project in `project_root`
+    ///
+    /// Plugins are discovered in the directories returned by
+    /// [`search_dirs`](super::search_dirs); the built-in Slack plugin is
+    /// added if it is configured. Plugins of the project directories (see
+    /// [`project_dirs`](super::project_dirs)) are only loaded when
+    /// `plugins.enabled` names them, so that the hooks of a cloned
+    /// repository never run unless asked for.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let config = Config::load_for_project(project_root)?;
+        let enabled = &config.plugins.enabled;
+        // With an empty list every plugin is enabled, which only holds for
+        // the user's and the system's plugins
+        let dirs = if enabled.is_empty() {
+            installed_dirs()
+        } else {
+            search_dirs(project_root, &config.plugins)
+        };
+
+        let mut registry = Self::new(PluginContext::new(project_root));
+        for loaded in discover_all(&dirs, enabled)? {
+            registry.register(Box::new(ExternalPlugin::new(loaded)))?;
+        }
+        if let Some(slack) = &config.plugins.slack {
+            if enabled.is_empty() || enabled.iter().any(|name| name == slack::PLUGIN_NAME) {
+                registry.register(Box::new(SlackPlugin::new(slack.clone())))?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Initializes a plugin and adds it to the registry
+    pub fn register(&mut self, mut plugin: Box<dyn Plugin>) -> Result<()> {
+        if self.plugins.iter().any(|p| p.name() == plugin.name()) {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "Plugin '{}' is already registered",
+                plugin.name()
+            )));
+        }
+        plugin.initialize(&self.context)?;
+        self.plugins.push(plugin);
+        Ok(())
+    }
+
+    /// Registered plugins, in registration order
+    pub fn plugins(&self) -> impl Iterator<Item = &dyn Plugin> {
+        self.plugins.iter().map(AsRef::as_ref)
+    }
+
+    /// Delivers an event to every plugin
+    ///
+    /// A failing plugin does not stop delivery to the others; the failures
+    /// are returned with the name of the plugin.
+    pub fn dispatch(&self, event: &TicketEvent) -> Vec<(String, VibeTicketError)> {
+        self.plugins
+            .iter()
+            .filter_map(|plugin| {
+                plugin
+                    .on_event(event, &self.context)
+                    .err()
+                    .map(|e| (plugin.name().to_string(), e))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    struct Recorder {
+        seen: Arc<Mutex<Vec<HookEvent>>>,
+    }
+
+    impl Plugin for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+
+        fn on_event(&self, event: &TicketEvent, _context: &PluginContext) -> PluginResult<()> {
+            self.seen.lock().unwrap().push(HookEvent::from(event));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatches_to_plugins() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = PluginRegistry::new(PluginContext::new("/tmp"));
+        registry
+            .register(Box::new(Recorder {
+                seen: Arc::clone(&seen),
+            }))
+            .unwrap();
+        assert!(
+            registry
+                .register(Box::new(Recorder {
+                    seen: Arc::clone(&seen),
+                }))
+                .is_err()
+        );
+
+        let ticket = Ticket::new("hooked", "Hooked");
+        let events = [
+            TicketEvent::Created(ticket.clone()),
+            TicketEvent::TaskAdded(ticket.id.clone(), crate::core::Task::new("t")),
+            TicketEvent::Closed(ticket.id.clone(), "done".to_string()),
+            TicketEvent::SpecPhaseApproved(
+                "spec-1".to_string(),
+                crate::specs::SpecDocumentType::Design,
+            ),
+        ];
+        for event in &events {
+            assert!(registry.dispatch(event).is_empty());
+        }
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                HookEvent::Created,
+                HookEvent::Updated,
+                HookEvent::Closed,
+                HookEvent::SpecPhaseApproved
+            ]
+        );
+        assert_eq!(
+            events[3].to_json(),
+            serde_json::json!({
+                "kind": "spec_phase_approved",
+                "spec_id": "spec-1",
+                "phase": "design",
+            })
+        );
+    }
+
+    #[test]
+    fn test_project_plugins_need_opt_in() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_dir = temp_dir.path().join(".vibe-ticket/plugins/audit");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.yaml"),
+            "name: audit\nversion: 0.1.0\nhooks:\n  - event: ticket.closed\n    run: [\"true\"]\n",
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join(".vibe-ticket/config.yaml");
+        let mut config = Config::default();
+        config.save_to_path(&config_path).unwrap();
+
+        let registry = PluginRegistry::load(temp_dir.path()).unwrap();
+        assert!(registry.plugins().all(|p| p.name() != "audit"));
+
+        config.plugins.enabled = vec!["audit".to_string()];
+        config.save_to_path(&config_path).unwrap();
+        let registry = PluginRegistry::load(temp_dir.path()).unwrap();
+        assert!(registry.plugins().any(|p| p.name() == "audit"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_external_plugin_runs_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_dir = temp_dir.path().join("plugins").join("audit");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.yaml"),
+            r#"
+name: audit
+version: 0.1.0
+hooks:
+  - event: ticket.closed
+    run: ["sh", "-c", "cat > \"$VIBE_TICKET_PROJECT_DIR/closed.json\""]
+  - event: ticket.created
+    run: ["sh", "-c", "exit 3"]
+"#,
+        )
+        .unwrap();
+
+        let loaded = discover_all(&[temp_dir.path().join("plugins")], &[]).unwrap();
+        let mut registry = PluginRegistry::new(PluginContext::new(temp_dir.path()));
+        registry
+            .register(Box::new(ExternalPlugin::new(loaded[0].clone())))
+            .unwrap();
+
+        let ticket = Ticket::new("hooked", "Hooked");
+        let closed = TicketEvent::Closed(ticket.id.clone(), "shipped".to_string());
+        assert!(registry.dispatch(&closed).is_empty());
+        let payload: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.path().join("closed.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(payload["event"], "ticket.closed");
+        assert_eq!(payload["data"]["message"], "shipped");
+
+        let ticket_id = ticket.id.clone();
+        let failures = registry.dispatch(&TicketEvent::Created(ticket));
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "audit");
+
+        // No hook for updates
+        let updated = TicketEvent::StatusChanged(
+            ticket_id,
+            crate::core::Status::Todo,
+            crate::core::Status::Doing,
+        );
+        assert!(registry.dispatch(&updated).is_empty());
+    }
+}
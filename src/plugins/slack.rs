@@ -0,0 +1,234 @@
+//! Built-in Slack plugin
+//!
+//! Announces ticket events in Slack through incoming webhooks. The plugin is
+//! enabled by the `plugins.slack` section of the configuration:
+//!
+//! ```yaml
+//! plugins:
+//!   slack:
+//!     webhook_url: https://hooks.slack.com/services/T000/B000/XXXX
+//!     channel: "#tickets"            # optional override of the webhook's channel
+//!     channels:                      # optional routes per priority
+//!       critical: "#incidents"
+//!       high: https://hooks.slack.com/services/T000/B111/YYYY
+//!     events: [created, closed, status_changed]
+//! ```
+//!
+//! A route is either a channel name, sent as the message's `channel`, or the
+//! incoming webhook URL of another channel, for Slack apps whose webhooks
+//! cannot post elsewhere. Messages go through the [`Outbox`], so the ones
+//! that cannot be posted are retried like any webhook delivery.
+
+use serde_json::{Value, json};
+
+use super::registry::{Plugin, PluginContext, PluginResult};
+use crate::config::SlackConfig;
+use crate::core::{Priority, Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::events::TicketEvent;
+use crate::integration::outbox::{Outbox, OutboxEntry, http_deliver};
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Name of the plugin, as listed in `plugins.enabled`
+pub const PLUGIN_NAME: &str = "slack";
+
+/// Where a message is posted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Route<'a> {
+    /// Incoming webhook URL
+    pub webhook_url: &'a str,
+    /// Channel overriding the webhook's default channel
+    pub channel: Option<&'a str>,
+}
+
+/// Destination of messages about tickets of `priority`
+///
+/// Without a priority, or for priorities without a route of their own,
+/// messages go to `channel` through `webhook_url`.
+///
+/// # Errors
+///
+/// Returns [`VibeTicketError::InvalidInput`] if no webhook URL is configured.
+pub fn route(config: &SlackConfig, priority: Option<Priority>) -> Result<Route<'_>> {
+    let route = match priority
+        .and_then(|priority| config.channels.get(&priority))
+        .map(String::as_str)
+    {
+        Some(url) if url.starts_with("https://") || url.starts_with("http://") => Route {
+            webhook_url: url,
+            channel: None,
+        },
+        Some(channel) => Route {
+            webhook_url: &config.webhook_url,
+            channel: Some(channel),
+        },
+        None => Route {
+            webhook_url: &config.webhook_url,
+            channel: config.channel.as_deref(),
+        },
+    };
+    if route.webhook_url.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "No Slack webhook URL configured; set plugins.slack.webhook_url".to_string(),
+        ));
+    }
+    Ok(route)
+}
+
+/// Text announcing `event` about `ticket`, if the event is announced at all
+///
+/// Status changes to done are left to the `closed` event, which follows them.
+pub fn message(event: &TicketEvent, ticket: &Ticket) -> Option<String> {
+    let name = format!("*{}* (`{}`)", ticket.title, ticket.slug);
+    match event {
+        TicketEvent::Created(_) => Some(format!(
+            ":new: New {} priority ticket {name}",
+            ticket.priority.to_string().to_lowercase()
+        )),
+        TicketEvent::Closed(_, message) => {
+            let mut text = format!(":white_check_mark: Closed {name}");
+            if !message.trim().is_empty() {
+                text.push_str("\n> ");
+                text.push_str(message.trim());
+            }
+            Some(text)
+        },
+        TicketEvent::StatusChanged(_, from, to) if *to != Status::Done => Some(format!(
+            ":arrows_counterclockwise: {name} moved from {from} to {to}"
+        )),
+        _ => None,
+    }
+}
+
+/// Slack payload posting `text` along `route`
+pub fn payload(text: &str, route: Route<'_>) -> Value {
+    let mut payload = json!({ "text": text });
+    if let Some(channel) = route.channel {
+        payload["channel"] = json!(channel);
+    }
+    payload
+}
+
+/// Outbox entry posting `text` along the route of `priority`
+///
+/// # Errors
+///
+/// Returns an error if no webhook URL is configured.
+pub fn entry(
+    config: &SlackConfig,
+    priority: Option<Priority>,
+    kind: &str,
+    text: &str,
+) -> Result<OutboxEntry> {
+    let route = route(config, priority)?;
+    Ok(OutboxEntry::new(
+        route.webhook_url,
+        format!("{PLUGIN_NAME}.{kind}"),
+        payload(text, route),
+    ))
+}
+
+/// Plugin announcing ticket events in Slack
+#[derive(Debug, Clone)]
+pub struct SlackPlugin {
+    config: SlackConfig,
+}
+
+impl SlackPlugin {
+    /// Creates the plugin with its configuration
+    pub const fn new(config: SlackConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for SlackPlugin {
+    fn name(&self) -> &'static str {
+        PLUGIN_NAME
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn on_event(&self, event: &TicketEvent, context: &PluginContext) -> PluginResult<()> {
+        if !self.config.events.iter().any(|kind| kind == event.kind()) {
+            return Ok(());
+        }
+        let ticket = match (event, event.ticket_id()) {
+            (TicketEvent::Created(ticket), _) => ticket.clone(),
+            (_, Some(id)) => FileStorage::new(context.vibe_dir()).load(id)?,
+            (_, None) => return Ok(()),
+        };
+        let Some(text) = message(event, &ticket) else {
+            return Ok(());
+        };
+
+        let entry = entry(&self.config, Some(ticket.priority), event.kind(), &text)?;
+        if Outbox::new(context.vibe_dir()).deliver_or_enqueue(entry, http_deliver)? {
+            Ok(())
+        } else {
+            Err(VibeTicketError::custom(
+                "Slack is unreachable; the message was queued for retry",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_by_priority() {
+        let mut config = SlackConfig {
+            webhook_url: "https://hooks.slack.com/services/default".to_string(),
+            channel: Some("#tickets".to_string()),
+            ..SlackConfig::default()
+        };
+        config
+            .channels
+            .insert(Priority::Critical, "#incidents".to_string());
+        config.channels.insert(
+            Priority::High,
+            "https://hooks.slack.com/services/alerts".to_string(),
+        );
+
+        let low = route(&config, Some(Priority::Low)).unwrap();
+        assert_eq!(low.webhook_url, config.webhook_url);
+        assert_eq!(low.channel, Some("#tickets"));
+        assert_eq!(
+            route(&config, Some(Priority::Critical)).unwrap().channel,
+            Some("#incidents")
+        );
+        let high = route(&config, Some(Priority::High)).unwrap();
+        assert_eq!(high.webhook_url, "https://hooks.slack.com/services/alerts");
+        assert_eq!(payload("hi", high), json!({"text": "hi"}));
+
+        assert_eq!(route(&config, None).unwrap(), low);
+
+        config.webhook_url.clear();
+        assert!(route(&config, Some(Priority::Low)).is_err());
+        assert!(route(&config, Some(Priority::High)).is_ok());
+    }
+
+    #[test]
+    fn test_messages() {
+        let mut ticket = Ticket::new("fix-login".to_string(), "Fix login".to_string());
+        ticket.priority = Priority::High;
+
+        let created = message(&TicketEvent::Created(ticket.clone()), &ticket).unwrap();
+        assert_eq!(
+            created,
+            ":new: New high priority ticket *Fix login* (`fix-login`)"
+        );
+
+        let closed = TicketEvent::Closed(ticket.id.clone(), "Shipped".to_string());
+        assert!(message(&closed, &ticket).unwrap().ends_with("\n> Shipped"));
+
+        let started = TicketEvent::StatusChanged(ticket.id.clone(), Status::Todo, Status::Doing);
+        assert!(message(&started, &ticket).unwrap().contains("moved from"));
+        let done = TicketEvent::StatusChanged(ticket.id.clone(), Status::Doing, Status::Done);
+        assert!(message(&done, &ticket).is_none());
+        assert!(message(&TicketEvent::Updated(ticket.clone()), &ticket).is_none());
+    }
+}
@@ -0,0 +1,282 @@
+//! High-level access to a vibe-ticket project
+//!
+//! [`Project`] bundles the storage, configuration and spec manager of a
+//! project for programs embedding vibe-ticket. Its operations behave like
+//! the corresponding CLI commands: tickets are resolved by ID, ID prefix or
+//! slug, changes go through the audit log, and plugin hooks are run.
+//!
+//! ```no_run
+//! use vibe_ticket::Project;
+//! use vibe_ticket::core::{Priority, Ticket};
+//!
+//! let project = Project::discover(".")?;
+//! let ticket = project.create(
+//!     Ticket::builder()
+//!         .slug("fix-login")
+//!         .priority(Priority::High)
+//!         .task("write tests")
+//!         .build()?,
+//! )?;
+//! project.start(&ticket.slug)?;
+//! project.close(&ticket.slug, Some("Fixed"))?;
+//! # Ok::<(), vibe_ticket::VibeTicketError>(())
+//! ```
+//!
+//! The project must have been initialized with `vibe-ticket init`.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::cli::find_project_root;
+use crate::cli::handlers::resolve_ticket_ref;
+use crate::config::Config;
+use crate::core::closing::CLOSE_MESSAGE_KEY;
+use crate::core::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketEvent, emit_cli_event};
+use crate::specs::SpecManager;
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+
+/// An initialized vibe-ticket project
+pub struct Project {
+    root: PathBuf,
+    storage: FileStorage,
+    config: Config,
+}
+
+impl Project {
+    /// Opens the project whose root directory is `root`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` has no `.vibe-ticket` directory or the
+    /// configuration cannot be loaded
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let vibe_dir = root.join(".vibe-ticket");
+        if !vibe_dir.is_dir() {
+            return Err(VibeTicketError::ProjectNotInitialized);
+        }
+
+        Ok(Self {
+            config: Config::load_for_project(&root)?,
+            storage: FileStorage::new(vibe_dir),
+            root,
+        })
+    }
+
+    /// Opens the project containing `start`, searching parent directories
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no project is found or it cannot be opened
+    pub fn discover(start: impl AsRef<Path>) -> Result<Self> {
+        Self::open(find_project_root(Some(&start.as_ref().to_string_lossy()))?)
+    }
+
+    /// Root directory of the project
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The project's `.vibe-ticket` directory
+    pub fn vibe_dir(&self) -> PathBuf {
+        self.root.join(".vibe-ticket")
+    }
+
+    /// Storage of the project's tickets
+    pub const fn storage(&self) -> &FileStorage {
+        &self.storage
+    }
+
+    /// Configuration of the project
+    pub const fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Manager of the project's specifications
+    pub fn specs(&self) -> SpecManager {
+        SpecManager::new(self.vibe_dir().join("specs"))
+    }
+
+    /// Loads all tickets
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tickets cannot be read
+    pub fn tickets(&self) -> Result<Vec<Ticket>> {
+        self.storage.load_all()
+    }
+
+    /// Loads a ticket by ID, ID prefix or slug
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no single ticket matches `reference`
+    pub fn ticket(&self, reference: &str) -> Result<Ticket> {
+        self.storage.load(&self.resolve(reference)?)
+    }
+
+    /// Loads the active ticket, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the active ticket cannot be read
+    pub fn active_ticket(&self) -> Result<Option<Ticket>> {
+        self.storage
+            .get_active()?
+            .map(|id| self.storage.load(&id))
+            .transpose()
+    }
+
+    /// Saves a new ticket
+    ///
+    /// Descriptions over the configured size limit are moved to an
+    /// attachment, as with `vibe-ticket new`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a ticket with the same slug exists or the ticket
+    /// cannot be saved
+    pub fn create(&self, mut ticket: Ticket) -> Result<Ticket> {
+        if self.storage.ticket_exists_with_slug(&ticket.slug)? {
+            return Err(VibeTicketError::DuplicateTicket { slug: ticket.slug });
+        }
+
+        self.storage
+            .spill_description(&mut ticket, self.config.project.description_size_limit)?;
+        self.storage.save(&ticket)?;
+        self.emit(&TicketEvent::Created(ticket.clone()));
+        Ok(ticket)
+    }
+
+    /// Applies `update` to a ticket and saves it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket is not found or cannot be saved
+    pub fn update<F>(&self, reference: &str, update: F) -> Result<Ticket>
+    where
+        F: FnOnce(&mut Ticket),
+    {
+        let ticket = self
+            .storage
+            .update_ticket(&self.resolve(reference)?, update)?;
+        self.emit(&TicketEvent::Updated(ticket.clone()));
+        Ok(ticket)
+    }
+
+    /// Starts work on a ticket and makes it the active ticket
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket is not found or cannot be saved
+    pub fn start(&self, reference: &str) -> Result<Ticket> {
+        let mut ticket = self.ticket(reference)?;
+        let previous = ticket.status;
+        ticket.start();
+        self.storage.save(&ticket)?;
+        self.storage.set_active(&ticket.id)?;
+        self.emit(&TicketEvent::StatusChanged(
+            ticket.id.clone(),
+            previous,
+            ticket.status,
+        ));
+        Ok(ticket)
+    }
+
+    /// Closes a ticket, recording `message` as its close message
+    ///
+    /// Closing the last open ticket exported from a spec's tasks completes
+    /// the spec's tasks phase, as with `vibe-ticket close`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket is not found, is already closed,
+    /// depends on open tickets or cannot be saved
+    pub fn close(&self, reference: &str, message: Option<&str>) -> Result<Ticket> {
+        let mut ticket = self.ticket(reference)?;
+        if ticket.status == Status::Done {
+            return Err(VibeTicketError::custom(format!(
+                "Ticket '{}' is already closed",
+                ticket.slug
+            )));
+        }
+        ticket.status = Status::Done;
+        ticket.closed_at = Some(Utc::now());
+        if let Some(message) = message {
+            ticket
+                .metadata
+                .insert(CLOSE_MESSAGE_KEY.to_string(), message.into());
+        }
+        self.storage.save(&ticket)?;
+        if self.storage.get_active()?.as_ref() == Some(&ticket.id) {
+            self.storage.clear_active()?;
+        }
+        self.emit(&TicketEvent::Closed(
+            ticket.id.clone(),
+            message.unwrap_or_default().to_string(),
+        ));
+        Ok(ticket)
+    }
+
+    /// Resolves an ID, ID prefix or slug to a ticket ID
+    fn resolve(&self, reference: &str) -> Result<TicketId> {
+        resolve_ticket_ref(&self.storage, reference)
+    }
+
+    /// Runs the plugin hooks for an event
+    fn emit(&self, event: &TicketEvent) {
+        emit_cli_event(&self.root, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project() -> (TempDir, Project) {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".vibe-ticket")).unwrap();
+        let project = Project::open(temp_dir.path()).unwrap();
+        (temp_dir, project)
+    }
+
+    #[test]
+    fn test_project_ticket_lifecycle() {
+        let (_temp_dir, project) = project();
+
+        let ticket = Ticket::builder()
+            .slug("fix-login")
+            .task("write tests")
+            .build()
+            .unwrap();
+        project.create(ticket.clone()).unwrap();
+        assert!(project.create(ticket).is_err(), "duplicate slug");
+
+        project.start("fix-login").unwrap();
+        assert_eq!(project.active_ticket().unwrap().unwrap().slug, "fix-login");
+
+        let updated = project
+            .update("fix-login", |t| t.tags.push("auth".to_string()))
+            .unwrap();
+        assert_eq!(updated.tags, vec!["auth"]);
+
+        let closed = project.close("fix-login", Some("Fixed")).unwrap();
+        assert_eq!(closed.status, Status::Done);
+        assert_eq!(closed.metadata["close_message"], "Fixed");
+        assert!(project.active_ticket().unwrap().is_none());
+        assert!(project.close("fix-login", None).is_err());
+    }
+
+    #[test]
+    fn test_open_requires_initialized_project() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            Project::open(temp_dir.path()),
+            Err(VibeTicketError::ProjectNotInitialized)
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+//! Review decisions on spec phases
+//!
+//! Reviewers approve a phase document, or request changes to it, with
+//! `spec approve`. Each decision is kept as an [`Approval`] in the progress
+//! of the spec, and a reviewer's later decision on a phase replaces their
+//! earlier one. A phase is approved once as many reviewers as
+//! `spec.required_approvals.<phase>` approve it and no reviewer requests
+//! changes.
+//!
+//! Until then, work on the next phase and activating the spec are refused
+//! (see [`SpecProgress::check_approved`]). Phases without required
+//! approvals are never blocked.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{SpecDocumentType, SpecProgress};
+use crate::config::SpecConfig;
+use crate::error::{Result, VibeTicketError};
+
+/// Decision of a reviewer on a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    /// The document is ready
+    Approve,
+    /// The document needs changes first
+    RequestChanges,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Approve => write!(f, "approved"),
+            Self::RequestChanges => write!(f, "requested changes"),
+        }
+    }
+}
+
+/// A reviewer's decision on a phase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Approval {
+    /// Who decided
+    pub reviewer: String,
+
+    /// Phase document decided on
+    pub phase: SpecDocumentType,
+
+    /// The decision
+    pub decision: Decision,
+
+    /// Message of the reviewer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Timestamp of the decision
+    pub decided_at: DateTime<Utc>,
+}
+
+/// Approval state of a phase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PhaseApproval {
+    /// The phase
+    pub phase: SpecDocumentType,
+
+    /// Reviewers whose latest decision approves the phase
+    pub approved_by: Vec<String>,
+
+    /// Reviewers whose latest decision requests changes
+    pub changes_requested_by: Vec<String>,
+
+    /// Number of approvals the phase needs
+    pub required: u32,
+}
+
+impl PhaseApproval {
+    /// Whether the phase has its approvals and no requested changes
+    pub fn is_approved(&self) -> bool {
+        self.changes_requested_by.is_empty()
+            && self.approved_by.len() >= usize::try_from(self.required).unwrap_or(usize::MAX)
+    }
+}
+
+/// Number of approvals `phase` needs under `config`
+pub fn required(config: &SpecConfig, phase: SpecDocumentType) -> u32 {
+    config
+        .required_approvals
+        .get(phase.as_str())
+        .copied()
+        .unwrap_or_default()
+}
+
+impl SpecProgress {
+    /// Approval state of `phase`, which needs `required` approvals
+    pub fn approval(&self, phase: SpecDocumentType, required: u32) -> PhaseApproval {
+        let mut latest = BTreeMap::new();
+        for approval in self.approvals.iter().filter(|a| a.phase == phase) {
+            latest.insert(approval.reviewer.as_str(), approval.decision);
+        }
+        let reviewers = |decision: Decision| {
+            latest
+                .iter()
+                .filter(|(_, d)| **d == decision)
+                .map(|(reviewer, _)| (*reviewer).to_string())
+                .collect()
+        };
+        PhaseApproval {
+            phase,
+            approved_by: reviewers(Decision::Approve),
+            changes_requested_by: reviewers(Decision::RequestChanges),
+            required,
+        }
+    }
+
+    /// Approval state of every phase under `config`
+    pub fn approvals_by_phase(&self, config: &SpecConfig) -> Vec<PhaseApproval> {
+        [
+            SpecDocumentType::Requirements,
+            SpecDocumentType::Design,
+            SpecDocumentType::Tasks,
+        ]
+        .into_iter()
+        .map(|phase| self.approval(phase, required(config, phase)))
+        .collect()
+    }
+
+    /// Records a decision and updates the approval flag of its phase
+    ///
+    /// Returns the new approval state of the phase.
+    pub fn record_approval(&mut self, approval: Approval, required: u32) -> PhaseApproval {
+        let phase = approval.phase;
+        self.approvals.push(approval);
+        let state = self.approval(phase, required);
+        let approved = state.is_approved();
+        match phase {
+            SpecDocumentType::Requirements => self.requirements_approved = approved,
+            SpecDocumentType::Design => self.design_approved = approved,
+            SpecDocumentType::Tasks => self.tasks_approved = approved,
+        }
+        state
+    }
+
+    /// Checks that `phase` has the approvals `config` requires before
+    /// `action`, such as "work on the design"
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VibeTicketError::InvalidInput`] naming the missing
+    /// approvals or the reviewers who requested changes.
+    pub fn check_approved(
+        &self,
+        config: &SpecConfig,
+        phase: SpecDocumentType,
+        action: &str,
+    ) -> Result<()> {
+        let required = required(config, phase);
+        if required == 0 {
+            return Ok(());
+        }
+        let state = self.approval(phase, required);
+        if !state.changes_requested_by.is_empty() {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "Cannot {action}: {} requested changes to the {} phase",
+                state.changes_requested_by.join(", "),
+                phase.as_str()
+            )));
+        }
+        if !state.is_approved() {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "Cannot {action}: the {} phase has {} of {required} required approvals",
+                phase.as_str(),
+                state.approved_by.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decide(progress: &mut SpecProgress, reviewer: &str, decision: Decision) -> PhaseApproval {
+        progress.record_approval(
+            Approval {
+                reviewer: reviewer.to_string(),
+                phase: SpecDocumentType::Design,
+                decision,
+                message: None,
+                decided_at: Utc::now(),
+            },
+            2,
+        )
+    }
+
+    #[test]
+    fn test_record_approval() {
+        let mut progress = SpecProgress::default();
+
+        let state = decide(&mut progress, "alice", Decision::Approve);
+        assert_eq!(state.approved_by, vec!["alice"]);
+        assert!(!progress.design_approved);
+
+        // A reviewer's later decision replaces the earlier one
+        decide(&mut progress, "bob", Decision::RequestChanges);
+        let state = decide(&mut progress, "bob", Decision::Approve);
+        assert_eq!(state.approved_by, vec!["alice", "bob"]);
+        assert!(state.is_approved());
+        assert!(progress.design_approved);
+
+        let state = decide(&mut progress, "carol", Decision::RequestChanges);
+        assert_eq!(state.changes_requested_by, vec!["carol"]);
+        assert!(!progress.design_approved);
+        assert!(
+            progress
+                .approval(SpecDocumentType::Tasks, 2)
+                .approved_by
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_check_approved() {
+        let mut config = SpecConfig::default();
+        let mut progress = SpecProgress::default();
+        let design = SpecDocumentType::Design;
+
+        // Nothing is required by default
+        assert!(progress.check_approved(&config, design, "plan").is_ok());
+
+        config.required_approvals.insert("design".to_string(), 2);
+        decide(&mut progress, "alice", Decision::Approve);
+        let err = progress
+            .check_approved(&config, design, "plan the tasks")
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Cannot plan the tasks: the design phase has 1 of 2 required approvals")
+        );
+
+        decide(&mut progress, "bob", Decision::RequestChanges);
+        let err = progress
+            .check_approved(&config, design, "plan")
+            .unwrap_err();
+        assert!(err.to_string().contains("bob requested changes"));
+
+        decide(&mut progress, "bob", Decision::Approve);
+        assert!(progress.check_approved(&config, design, "plan").is_ok());
+    }
+}
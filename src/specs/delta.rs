@@ -0,0 +1,260 @@
+//! Delta specs amending shipped specifications
+//!
+//! A delta spec (`spec init --amends <spec>`) starts with a copy of the
+//! requirements of a completed spec. Every inherited section heading carries
+//! a change marker naming the requirement it came from:
+//!
+//! ```markdown
+//! ### 4.1 User Stories <!-- amends: 41-user-stories unchanged -->
+//! ```
+//!
+//! Editing the delta, the marker is changed to `modified` or `removed`;
+//! headings without a marker are added requirements. A requirement is
+//! identified by the anchor of its heading in the amended spec, so the
+//! heading text may change. Modified and removed requirements, including
+//! inherited sections deleted from the delta, are superseded.
+
+use std::fmt::{self, Write as FmtWrite};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::SpecMetadata;
+use super::templates::heading_anchor;
+use crate::error::{Result, VibeTicketError};
+
+/// Opening of a change marker
+const MARKER_START: &str = "<!-- amends:";
+
+/// Closing of a change marker
+const MARKER_END: &str = "-->";
+
+/// How a delta spec changes a requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Inherited as is
+    Unchanged,
+    /// Inherited and changed
+    Modified,
+    /// Dropped from the amended spec
+    Removed,
+    /// New in the delta spec
+    Added,
+}
+
+impl ChangeKind {
+    /// Returns true if the requirement of the amended spec no longer holds
+    pub const fn supersedes(self) -> bool {
+        matches!(self, Self::Modified | Self::Removed)
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unchanged => "unchanged",
+            Self::Modified => "modified",
+            Self::Removed => "removed",
+            Self::Added => "added",
+        })
+    }
+}
+
+impl FromStr for ChangeKind {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "unchanged" => Ok(Self::Unchanged),
+            "modified" => Ok(Self::Modified),
+            "removed" => Ok(Self::Removed),
+            "added" => Ok(Self::Added),
+            _ => Err(VibeTicketError::InvalidInput(format!(
+                "Unknown change marker: {s}. Expected unchanged, modified or removed"
+            ))),
+        }
+    }
+}
+
+/// Change of one requirement section in a delta spec
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequirementChange {
+    /// Requirement ID: the heading anchor in the amended spec, or in the
+    /// delta for added requirements
+    pub id: String,
+    /// Heading text
+    pub title: String,
+    /// Kind of change
+    pub kind: ChangeKind,
+}
+
+/// Level 2 and 3 headings of a document, outside code blocks
+///
+/// Yields the line index, the heading prefix (`## ` or `### `) and the rest
+/// of the line.
+fn headings(markdown: &str) -> Vec<(usize, &str, &str)> {
+    let mut in_code_block = false;
+    markdown
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                return None;
+            }
+            if in_code_block {
+                return None;
+            }
+            ["### ", "## "]
+                .into_iter()
+                .find_map(|prefix| line.strip_prefix(prefix).map(|rest| (index, prefix, rest)))
+        })
+        .collect()
+}
+
+/// Splits a heading into its text and change marker, if any
+fn split_marker(heading: &str) -> (&str, Option<(&str, &str)>) {
+    let Some(start) = heading.find(MARKER_START) else {
+        return (heading.trim(), None);
+    };
+    let marker = heading[start + MARKER_START.len()..]
+        .split(MARKER_END)
+        .next()
+        .unwrap_or_default();
+    let mut words = marker.split_whitespace();
+    let text = heading[..start].trim();
+    match (words.next(), words.next()) {
+        (Some(id), Some(kind)) => (text, Some((id, kind))),
+        (Some(id), None) => (text, Some((id, "unchanged"))),
+        _ => (text, None),
+    }
+}
+
+/// Builds the initial requirements of a delta spec amending `base`
+///
+/// Every section heading of `requirements` gets an `unchanged` marker.
+pub fn inherit(base: &SpecMetadata, requirements: &str) -> String {
+    let mut lines: Vec<String> = requirements.lines().map(str::to_string).collect();
+    for (index, prefix, heading) in headings(requirements) {
+        // Markers of an amended delta spec are replaced
+        let (text, _) = split_marker(heading);
+        lines[index] = format!(
+            "{prefix}{text} {MARKER_START} {} {} {MARKER_END}",
+            heading_anchor(text),
+            ChangeKind::Unchanged
+        );
+    }
+
+    let mut content = String::new();
+    let _ = writeln!(
+        content,
+        "> Amends spec \"{}\" ({}) version {}.",
+        base.title, base.id, base.version
+    );
+    let _ = writeln!(
+        content,
+        "> Mark inherited sections as `modified` or `removed` in their \
+         `{MARKER_START} ... {MARKER_END}` marker; sections without a marker are added requirements."
+    );
+    content.push('\n');
+    content.push_str(&lines.join("\n"));
+    content.push('\n');
+    content
+}
+
+/// Lists the requirement changes of a delta spec
+///
+/// `base` is the requirements document of the amended spec; its sections
+/// missing from `delta` are reported as removed.
+///
+/// # Errors
+///
+/// Returns an error if a change marker is not `unchanged`, `modified` or
+/// `removed`
+pub fn changes(delta: &str, base: Option<&str>) -> Result<Vec<RequirementChange>> {
+    let mut changes = Vec::new();
+    for (_, _, heading) in headings(delta) {
+        let (text, marker) = split_marker(heading);
+        let change = match marker {
+            Some((id, kind)) => RequirementChange {
+                id: id.to_string(),
+                title: text.to_string(),
+                kind: kind.parse()?,
+            },
+            None => RequirementChange {
+                id: heading_anchor(text),
+                title: text.to_string(),
+                kind: ChangeKind::Added,
+            },
+        };
+        changes.push(change);
+    }
+
+    for (_, _, heading) in headings(base.unwrap_or_default()) {
+        let (text, _) = split_marker(heading);
+        let id = heading_anchor(text);
+        if !changes
+            .iter()
+            .any(|c| c.id == id && c.kind != ChangeKind::Added)
+        {
+            changes.push(RequirementChange {
+                id,
+                title: text.to_string(),
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// IDs of the requirements superseded by `changes`
+pub fn superseded(changes: &[RequirementChange]) -> Vec<String> {
+    changes
+        .iter()
+        .filter(|c| c.kind.supersedes())
+        .map(|c| c.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "# Requirements\n\n## 1. Overview\n\nText\n\n### 1.1 Login\n\n```\n## not a heading\n```\n\n### 1.2 Logout\n";
+
+    #[test]
+    fn test_inherit_and_track_changes() {
+        let base = SpecMetadata::new("Auth".to_string(), String::new());
+        let delta = inherit(&base, BASE);
+        assert!(delta.contains("### 1.1 Login <!-- amends: 11-login unchanged -->"));
+        assert!(delta.contains("## not a heading\n"));
+
+        let unchanged = changes(&delta, Some(BASE)).unwrap();
+        assert_eq!(unchanged.len(), 3);
+        assert!(superseded(&unchanged).is_empty());
+
+        let edited = delta
+            .replace("11-login unchanged", "11-login modified")
+            .replace("### 1.1 Login", "### 1.1 Login with SSO")
+            .replace("### 1.2 Logout <!-- amends: 12-logout unchanged -->\n", "")
+            + "\n### 1.3 Sessions\n";
+        let changes = changes(&edited, Some(BASE)).unwrap();
+        let kinds: Vec<(&str, ChangeKind)> =
+            changes.iter().map(|c| (c.id.as_str(), c.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("1-overview", ChangeKind::Unchanged),
+                ("11-login", ChangeKind::Modified),
+                ("13-sessions", ChangeKind::Added),
+                ("12-logout", ChangeKind::Removed),
+            ]
+        );
+        assert_eq!(superseded(&changes), vec!["11-login", "12-logout"]);
+
+        let invalid = delta.replace("11-login unchanged", "11-login rewritten");
+        assert!(super::changes(&invalid, None).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Story point estimates on spec tasks
+//!
+//! Tasks in `tasks.md` are markdown checklist items. An estimate is written
+//! at the end of the item in parentheses, for example
+//! `- [ ] implement API (3pt)`; `pt`, `pts`, `point` and `points` are all
+//! accepted. Estimates are summed per spec and can be compared with the
+//! estimates of the tickets created for the spec.
+
+use serde::Serialize;
+
+use super::SpecMetadata;
+use crate::core::{Ticket, assignment};
+
+/// Metadata key linking a ticket to the spec it implements
+pub const SPEC_KEY: &str = "spec_id";
+
+/// Planning poker deck of story points
+pub const DECK: [u32; 8] = [0, 1, 2, 3, 5, 8, 13, 21];
+
+/// Checklist item of a tasks document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecTask {
+    /// Index of the line in the document
+    pub line: usize,
+    /// Text of the task without its estimate
+    pub title: String,
+    /// Whether the item is checked
+    pub done: bool,
+    /// Estimate in story points
+    pub points: Option<u32>,
+    /// Indentation of the item, giving its nesting under earlier items
+    pub indent: usize,
+}
+
+/// Totals of the estimates of a spec's tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct EstimateSummary {
+    /// Number of tasks
+    pub tasks: usize,
+    /// Tasks without an estimate
+    pub unestimated: usize,
+    /// Points of all tasks
+    pub total_points: u32,
+    /// Points of the checked tasks
+    pub done_points: u32,
+}
+
+impl EstimateSummary {
+    /// Sums the estimates of `tasks`
+    pub fn of(tasks: &[SpecTask]) -> Self {
+        tasks.iter().fold(Self::default(), |mut summary, task| {
+            summary.tasks += 1;
+            match task.points {
+                Some(points) => {
+                    summary.total_points += points;
+                    if task.done {
+                        summary.done_points += points;
+                    }
+                },
+                None => summary.unestimated += 1,
+            }
+            summary
+        })
+    }
+}
+
+impl std::fmt::Display for EstimateSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}pt total, {}pt done",
+            self.total_points, self.done_points
+        )?;
+        if self.unestimated > 0 {
+            write!(
+                f,
+                " ({} of {} tasks unestimated)",
+                self.unestimated, self.tasks
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Estimates recorded on the tickets implementing a spec
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TicketEstimates {
+    /// Tickets implementing the spec
+    pub tickets: usize,
+    /// Tickets with an estimate
+    pub estimated: usize,
+    /// Sum of the ticket estimates
+    pub points: u32,
+}
+
+impl TicketEstimates {
+    /// Sums the estimates of the tickets implementing `spec`
+    pub fn of(spec: &SpecMetadata, tickets: &[Ticket]) -> Self {
+        spec_tickets(spec, tickets)
+            .iter()
+            .fold(Self::default(), |mut totals, ticket| {
+                totals.tickets += 1;
+                if let Some(points) = assignment::explicit_estimate(ticket) {
+                    totals.estimated += 1;
+                    totals.points += points;
+                }
+                totals
+            })
+    }
+}
+
+/// Returns the tickets implementing `spec`
+///
+/// These are the tickets whose [`SPEC_KEY`] metadata names the spec and the
+/// children of the spec's ticket; without any, the spec's ticket itself.
+pub fn spec_tickets<'a>(spec: &SpecMetadata, tickets: &'a [Ticket]) -> Vec<&'a Ticket> {
+    let spec_ticket = spec.ticket_id.as_deref().and_then(|reference| {
+        tickets
+            .iter()
+            .find(|t| t.id.to_string() == reference || t.slug == reference)
+    });
+    let linked: Vec<&Ticket> = tickets
+        .iter()
+        .filter(|t| {
+            t.metadata.get(SPEC_KEY).and_then(|v| v.as_str()) == Some(spec.id.as_str())
+                || spec_ticket.is_some_and(|parent| t.parent.as_ref() == Some(&parent.id))
+        })
+        .collect();
+
+    if linked.is_empty() {
+        spec_ticket.into_iter().collect()
+    } else {
+        linked
+    }
+}
+
+/// Lists the checklist items of a tasks document
+pub fn parse_tasks(content: &str) -> Vec<SpecTask> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line, text)| {
+            let (done, _, item) = checklist_item(text)?;
+            let (title, points) = split_points(item);
+            Some(SpecTask {
+                line,
+                title: title.to_string(),
+                done,
+                points,
+                indent: text.len() - text.trim_start().len(),
+            })
+        })
+        .collect()
+}
+
+/// Sets the estimate of the task on line `line`, or removes it with `None`
+///
+/// Returns the document unchanged if the line is not a checklist item.
+pub fn set_points(content: &str, line: usize, points: Option<u32>) -> String {
+    let mut lines: Vec<String> = content.lines().map(ToString::to_string).collect();
+    if let Some(text) = lines.get_mut(line) {
+        if let Some((_, start, item)) = checklist_item(text) {
+            let (title, _) = split_points(item);
+            let suffix = points
+                .map(|points| format!(" ({points}pt)"))
+                .unwrap_or_default();
+            let updated = format!("{}{title}{suffix}", &text[..start]);
+            *text = updated;
+        }
+    }
+
+    let mut result = lines.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Agreed estimate of a planning poker round, if all votes are equal
+pub fn consensus(votes: &[u32]) -> Option<u32> {
+    let first = *votes.first()?;
+    votes.iter().all(|vote| *vote == first).then_some(first)
+}
+
+/// Median of the votes, rounded up to the next card of the deck
+pub fn suggestion(votes: &[u32]) -> Option<u32> {
+    let mut sorted = votes.to_vec();
+    sorted.sort_unstable();
+    let median = *sorted.get(sorted.len() / 2)?;
+    Some(
+        DECK.iter()
+            .copied()
+            .find(|card| *card >= median)
+            .unwrap_or(median),
+    )
+}
+
+/// Splits a checklist line into its checked state, the byte offset of the
+/// item text and the item text
+fn checklist_item(line: &str) -> Option<(bool, usize, &str)> {
+    let line = line.trim_end();
+    let rest = line.trim_start();
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))?;
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let item = rest[3..].trim_start();
+    Some((done, line.len() - item.len(), item))
+}
+
+/// Splits the trailing `(Npt)` estimate off an item
+fn split_points(item: &str) -> (&str, Option<u32>) {
+    let Some(open) = item.rfind('(') else {
+        return (item, None);
+    };
+    let Some(inner) = item[open + 1..].strip_suffix(')') else {
+        return (item, None);
+    };
+    let inner = inner.trim().to_ascii_lowercase();
+    let digits = inner.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == ' ');
+    let unit = inner[digits.len()..].trim();
+    match (digits.parse(), unit) {
+        (Ok(points), "pt" | "pts" | "point" | "points") => (item[..open].trim_end(), Some(points)),
+        _ => (item, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS: &str = "# Tasks\n\n- [ ] implement API (3pt)\n- [x] write schema (2 pts)\n  * [ ] add docs\n- [ ] review (see notes)\n";
+
+    #[test]
+    fn test_parse_and_summarize() {
+        let tasks = parse_tasks(TASKS);
+        let parsed: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.line, t.title.as_str(), t.done, t.points))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (2, "implement API", false, Some(3)),
+                (3, "write schema", true, Some(2)),
+                (4, "add docs", false, None),
+                (5, "review (see notes)", false, None),
+            ]
+        );
+
+        let summary = EstimateSummary::of(&tasks);
+        assert_eq!((summary.total_points, summary.done_points), (5, 2));
+        assert_eq!(
+            summary.to_string(),
+            "5pt total, 2pt done (2 of 4 tasks unestimated)"
+        );
+    }
+
+    #[test]
+    fn test_set_points() {
+        let updated = set_points(TASKS, 4, Some(5));
+        let updated = set_points(&updated, 2, Some(8));
+        let updated = set_points(&updated, 3, None);
+        assert_eq!(
+            updated,
+            "# Tasks\n\n- [ ] implement API (8pt)\n- [x] write schema\n  * [ ] add docs (5pt)\n- [ ] review (see notes)\n"
+        );
+        assert_eq!(set_points(TASKS, 0, Some(1)), TASKS);
+    }
+
+    #[test]
+    fn test_ticket_estimates() {
+        let mut spec = SpecMetadata::new("Auth".to_string(), String::new());
+        let mut epic = Ticket::new("epic", "Epic");
+        epic.metadata.insert("estimate".to_string(), 20.into());
+        let mut child = Ticket::new("child", "Child");
+        child.parent = Some(epic.id.clone());
+        child.metadata.insert("estimate".to_string(), 5.into());
+        let mut exported = Ticket::new("exported", "Exported");
+        exported
+            .metadata
+            .insert(SPEC_KEY.to_string(), spec.id.clone().into());
+        let tickets = vec![epic, child, exported, Ticket::new("other", "Other")];
+
+        let totals = TicketEstimates::of(&spec, &tickets);
+        assert_eq!((totals.tickets, totals.estimated, totals.points), (1, 0, 0));
+
+        spec.ticket_id = Some("epic".to_string());
+        let totals = TicketEstimates::of(&spec, &tickets);
+        assert_eq!((totals.tickets, totals.estimated, totals.points), (2, 1, 5));
+    }
+
+    #[test]
+    fn test_poker_votes() {
+        assert_eq!(consensus(&[3, 3, 3]), Some(3));
+        assert_eq!(consensus(&[3, 5]), None);
+        assert_eq!(consensus(&[]), None);
+        assert_eq!(suggestion(&[2, 5, 8]), Some(5));
+        assert_eq!(suggestion(&[3, 4, 4]), Some(5));
+    }
+}
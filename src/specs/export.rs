@@ -0,0 +1,145 @@
+//! Export of spec tasks to tickets
+//!
+//! `spec tasks --export-tickets` turns each checklist item of `tasks.md` into
+//! a ticket. An item nested under another one becomes a child of that item's
+//! ticket, and top-level items become children of the spec's ticket, if any.
+//! Exported tickets carry the spec ID under [`SPEC_KEY`] and the task's
+//! estimate under `estimate`, so that they count towards the spec's ticket
+//! estimates.
+//!
+//! The ticket of each task is recorded in [`SpecMetadata::task_tickets`]
+//! under the task's key: its title, prefixed with the titles of the items it
+//! is nested under. Exporting again only creates tickets for new tasks and
+//! for tasks whose ticket was deleted.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+
+use super::SpecMetadata;
+use super::estimate::{SPEC_KEY, SpecTask};
+use crate::core::{Status, Ticket, TicketId};
+
+/// Separator of the titles in a task key
+pub const KEY_SEPARATOR: &str = " / ";
+
+/// Checklist item placed in the hierarchy of its tasks document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskNode<'a> {
+    /// The checklist item
+    pub task: &'a SpecTask,
+    /// Key of the task in [`SpecMetadata::task_tickets`]
+    pub key: String,
+    /// Index of the item this one is nested under
+    pub parent: Option<usize>,
+}
+
+/// Places `tasks` in their hierarchy, in document order
+///
+/// An item is nested under the closest earlier item with less indentation.
+/// Items with the same key are numbered, as in `review (2)`.
+pub fn hierarchy(tasks: &[SpecTask]) -> Vec<TaskNode<'_>> {
+    let mut nodes: Vec<TaskNode<'_>> = Vec::with_capacity(tasks.len());
+    let mut ancestors: Vec<usize> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for task in tasks {
+        while ancestors
+            .last()
+            .is_some_and(|&index| nodes[index].task.indent >= task.indent)
+        {
+            ancestors.pop();
+        }
+        let parent = ancestors.last().copied();
+        let key = parent.map_or_else(
+            || task.title.clone(),
+            |index| format!("{}{KEY_SEPARATOR}{}", nodes[index].key, task.title),
+        );
+        let count = seen.entry(key.clone()).or_default();
+        *count += 1;
+        let key = if *count == 1 {
+            key
+        } else {
+            format!("{key} ({count})")
+        };
+
+        ancestors.push(nodes.len());
+        nodes.push(TaskNode { task, key, parent });
+    }
+    nodes
+}
+
+/// Builds the ticket of an exported task
+///
+/// Checked items are exported as closed tickets.
+pub fn ticket(
+    spec: &SpecMetadata,
+    task: &SpecTask,
+    slug: &str,
+    parent: Option<TicketId>,
+) -> Ticket {
+    let mut ticket = Ticket::new(slug, &task.title);
+    ticket.description = format!("Task of spec '{}'", spec.title);
+    ticket.parent = parent;
+    ticket
+        .metadata
+        .insert(SPEC_KEY.to_string(), spec.id.clone().into());
+    if let Some(points) = task.points {
+        ticket
+            .metadata
+            .insert("estimate".to_string(), points.into());
+    }
+    if task.done {
+        ticket.status = Status::Done;
+        ticket.closed_at = Some(Utc::now());
+    }
+    ticket
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::estimate::parse_tasks;
+
+    #[test]
+    fn test_hierarchy() {
+        let tasks = parse_tasks(
+            "# Tasks\n\n- [ ] API\n  - [ ] routes\n    - [x] auth\n  - [ ] review\n- [ ] docs\n\t- [ ] review\n- [ ] API\n",
+        );
+        let nodes: Vec<_> = hierarchy(&tasks)
+            .into_iter()
+            .map(|node| (node.key, node.parent))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                ("API".to_string(), None),
+                ("API / routes".to_string(), Some(0)),
+                ("API / routes / auth".to_string(), Some(1)),
+                ("API / review".to_string(), Some(0)),
+                ("docs".to_string(), None),
+                ("docs / review".to_string(), Some(4)),
+                ("API (2)".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ticket() {
+        let spec = SpecMetadata::new("Auth".to_string(), String::new());
+        let tasks = parse_tasks("- [x] write schema (2pt)\n- [ ] add docs\n");
+        let parent = TicketId::new();
+
+        let done = ticket(&spec, &tasks[0], "write-schema", Some(parent.clone()));
+        assert_eq!(done.title, "write schema");
+        assert_eq!(done.parent, Some(parent));
+        assert_eq!(done.status, Status::Done);
+        assert!(done.closed_at.is_some());
+        assert_eq!(done.metadata[SPEC_KEY], spec.id.as_str());
+        assert_eq!(done.metadata["estimate"], 2);
+
+        let open = ticket(&spec, &tasks[1], "add-docs", None);
+        assert_eq!(open.status, Status::Todo);
+        assert!(!open.metadata.contains_key("estimate"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Version history of spec documents
+//!
+//! Whenever [`SpecManager`](super::SpecManager) saves a document with new
+//! content, a copy is kept under `history/<version>/<document>.md` in the
+//! spec directory, where the version is the spec version after the save.
+//! The content of a document at any version is its latest copy up to that
+//! version, so versions that only changed other documents need no copy.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{SpecDocumentType, SpecVersion};
+use crate::error::{ErrorContext, Result};
+
+/// Directory of the document copies, within a spec directory
+pub const HISTORY_DIR: &str = "history";
+
+/// Documents in the order they are listed and diffed
+pub const DOCUMENTS: [SpecDocumentType; 3] = [
+    SpecDocumentType::Requirements,
+    SpecDocumentType::Design,
+    SpecDocumentType::Tasks,
+];
+
+/// A version of a spec and the documents it changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Snapshot {
+    /// Spec version
+    pub version: SpecVersion,
+    /// Documents saved with new content in this version
+    pub documents: Vec<SpecDocumentType>,
+    /// When the last of these documents was saved
+    pub saved_at: Option<DateTime<Utc>>,
+}
+
+/// Copies of the documents of a spec
+#[derive(Debug, Clone)]
+pub struct History {
+    dir: PathBuf,
+}
+
+impl History {
+    /// History of the spec stored in `spec_dir`
+    pub fn new(spec_dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: spec_dir.as_ref().join(HISTORY_DIR),
+        }
+    }
+
+    fn path(&self, version: &SpecVersion, document: SpecDocumentType) -> PathBuf {
+        self.dir
+            .join(version.to_string())
+            .join(document.file_name())
+    }
+
+    /// Keeps a copy of `content` as `document` at `version`
+    ///
+    /// Nothing is stored if the content is the same as at the previous
+    /// version. Returns whether a copy was stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history cannot be read or written
+    pub fn record(
+        &self,
+        version: &SpecVersion,
+        document: SpecDocumentType,
+        content: &str,
+    ) -> Result<bool> {
+        if self.content_at(document, version)?.as_deref() == Some(content) {
+            return Ok(false);
+        }
+        let path = self.path(version, document);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context("Failed to create spec history directory")?;
+        }
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write spec history: {}", path.display()))?;
+        Ok(true)
+    }
+
+    /// Versions with stored copies, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history directory cannot be read
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&self.dir).context("Failed to read spec history")?;
+        let mut snapshots: Vec<Snapshot> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let version: SpecVersion = entry.file_name().to_str()?.parse().ok()?;
+                let stored: Vec<_> = DOCUMENTS
+                    .into_iter()
+                    .filter_map(|document| {
+                        let modified = fs::metadata(self.path(&version, document))
+                            .and_then(|metadata| metadata.modified())
+                            .ok()?;
+                        Some((document, DateTime::<Utc>::from(modified)))
+                    })
+                    .collect();
+                (!stored.is_empty()).then(|| Snapshot {
+                    saved_at: stored.iter().map(|(_, at)| *at).max(),
+                    documents: stored.into_iter().map(|(document, _)| document).collect(),
+                    version,
+                })
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(snapshots)
+    }
+
+    /// Content of `document` at `version`, if a copy exists up to it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history cannot be read
+    pub fn content_at(
+        &self,
+        document: SpecDocumentType,
+        version: &SpecVersion,
+    ) -> Result<Option<String>> {
+        let latest = self.snapshots()?.into_iter().rfind(|snapshot| {
+            snapshot.version <= *version && snapshot.documents.contains(&document)
+        });
+        latest
+            .map(|snapshot| {
+                fs::read_to_string(self.path(&snapshot.version, document))
+                    .context("Failed to read spec history")
+            })
+            .transpose()
+    }
+}
+
+/// Unified diff of two versions of a document, empty if they are equal
+///
+/// `from` and `to` label the versions in the diff header.
+pub fn diff(old: &str, new: &str, from: &str, to: &str) -> String {
+    let old_lines = lines(old);
+    let new_lines = lines(new);
+    let hunks = difflib::unified_diff(&old_lines, &new_lines, "", "", "", "", 3);
+    if hunks.is_empty() {
+        return String::new();
+    }
+    // Replace the header, whose empty dates leave trailing tabs
+    let mut diff = format!("--- {from}\n+++ {to}\n");
+    diff.extend(hunks.into_iter().skip(2));
+    diff
+}
+
+/// Lines of `text`, each ending with a newline
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(|line| format!("{line}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn version(s: &str) -> SpecVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_record_and_content_at() {
+        let temp_dir = TempDir::new().unwrap();
+        let history = History::new(temp_dir.path());
+        let design = SpecDocumentType::Design;
+
+        assert!(history.record(&version("0.1.1"), design, "v1\n").unwrap());
+        assert!(
+            history
+                .record(&version("0.1.2"), SpecDocumentType::Tasks, "- [ ] a\n")
+                .unwrap()
+        );
+        // Unchanged content is not copied again
+        assert!(!history.record(&version("0.1.3"), design, "v1\n").unwrap());
+        assert!(history.record(&version("0.1.10"), design, "v2\n").unwrap());
+
+        let snapshots = history.snapshots().unwrap();
+        let versions: Vec<_> = snapshots
+            .iter()
+            .map(|s| (s.version.to_string(), s.documents.clone()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("0.1.1".to_string(), vec![design]),
+                ("0.1.2".to_string(), vec![SpecDocumentType::Tasks]),
+                ("0.1.10".to_string(), vec![design]),
+            ]
+        );
+
+        let at = |v: &str| history.content_at(design, &version(v)).unwrap();
+        assert_eq!(at("0.1.0"), None);
+        assert_eq!(at("0.1.3").as_deref(), Some("v1\n"));
+        assert_eq!(at("0.2.0").as_deref(), Some("v2\n"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff = diff(
+            "# Design\nkeep\nold\n",
+            "# Design\nkeep\nnew\nadded",
+            "design.md@0.1.0",
+            "design.md@0.1.1",
+        );
+        assert_eq!(
+            diff,
+            "--- design.md@0.1.0\n+++ design.md@0.1.1\n@@ -1,3 +1,4 @@\n # Design\n keep\n-old\n+new\n+added\n"
+        );
+        assert!(super::diff("same\n", "same", "a", "b").is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! Links between specs and tickets
+//!
+//! A spec is linked to the ticket it was written for through its
+//! `ticket_id`, set with `spec init --ticket` or `spec link`, and to the
+//! tickets exported from its tasks (see [`super::export`]) through their
+//! [`SPEC_KEY`] metadata. Tickets show the phase of their specs, and closing
+//! the last open exported ticket of a spec completes its tasks phase; the
+//! storage rolls up every close this way, whatever closed the ticket.
+
+use super::estimate::SPEC_KEY;
+use super::{SpecManager, SpecMetadata};
+use crate::core::{Status, Ticket};
+use crate::error::Result;
+
+/// Whether `spec` was written for `ticket`
+///
+/// The spec's `ticket_id` may hold the ticket's ID, an ID prefix, its short
+/// ID or its slug.
+pub fn references(spec: &SpecMetadata, ticket: &Ticket) -> bool {
+    spec.ticket_id.as_deref().is_some_and(|reference| {
+        !reference.is_empty()
+            && (ticket.id.to_string().starts_with(reference)
+                || ticket.slug == reference
+                || ticket.short_id.as_deref() == Some(reference))
+    })
+}
+
+/// Specs written for `ticket` or whose tasks it was exported from
+pub fn specs_of<'a>(specs: &'a [SpecMetadata], ticket: &Ticket) -> Vec<&'a SpecMetadata> {
+    let exported_from = ticket.metadata.get(SPEC_KEY).and_then(|v| v.as_str());
+    specs
+        .iter()
+        .filter(|spec| references(spec, ticket) || exported_from == Some(spec.id.as_str()))
+        .collect()
+}
+
+/// Whether all existing tickets exported from the tasks of `spec` are closed
+///
+/// Returns false if no exported ticket exists.
+pub fn tasks_done(spec: &SpecMetadata, tickets: &[Ticket]) -> bool {
+    let mut exported = tickets
+        .iter()
+        .filter(|ticket| {
+            spec.task_tickets
+                .values()
+                .any(|id| *id == ticket.id.to_string())
+        })
+        .peekable();
+    exported.peek().is_some() && exported.all(|ticket| ticket.status == Status::Done)
+}
+
+/// Completes the tasks phase of the spec `closed` was exported from, once
+/// all its exported tickets are closed
+///
+/// `tickets` loads the tickets of the project, including `closed`; it is
+/// only called for exported tickets. Returns the spec if its tasks phase was
+/// completed.
+///
+/// # Errors
+///
+/// Returns an error if the tickets or the spec cannot be loaded or saved
+pub fn roll_up(
+    manager: &SpecManager,
+    closed: &Ticket,
+    tickets: impl FnOnce() -> Result<Vec<Ticket>>,
+) -> Result<Option<SpecMetadata>> {
+    let Some(spec_id) = closed.metadata.get(SPEC_KEY).and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    let Ok(spec) = manager.load(spec_id) else {
+        return Ok(None);
+    };
+    let mut metadata = spec.metadata;
+    if metadata.progress.tasks_completed || !tasks_done(&metadata, &tickets()?) {
+        return Ok(None);
+    }
+
+    metadata.progress.tasks_completed = true;
+    metadata.update_phase();
+    manager.save_metadata(&metadata)?;
+    Ok(Some(metadata))
+}
+
+/// The spec `ticket` was exported from, if its tasks phase is complete
+///
+/// Tells whether closing the ticket completed the spec, once the storage
+/// rolled the close up.
+pub fn completed_spec(manager: &SpecManager, ticket: &Ticket) -> Option<SpecMetadata> {
+    let spec_id = ticket.metadata.get(SPEC_KEY).and_then(|v| v.as_str())?;
+    let metadata = manager.load(spec_id).ok()?.metadata;
+    metadata.progress.tasks_completed.then_some(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::Specification;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_specs_of() {
+        let mut ticket = Ticket::new("login", "Login");
+        ticket.short_id = Some("abc123".to_string());
+        let mut by_slug = SpecMetadata::new("By slug".to_string(), String::new());
+        by_slug.ticket_id = Some("login".to_string());
+        let mut by_id = SpecMetadata::new("By ID".to_string(), String::new());
+        by_id.ticket_id = Some(ticket.id.to_string()[..8].to_string());
+        let mut other = SpecMetadata::new("Other".to_string(), String::new());
+        other.ticket_id = Some("logout".to_string());
+        let exported = SpecMetadata::new("Exported".to_string(), String::new());
+        ticket
+            .metadata
+            .insert(SPEC_KEY.to_string(), exported.id.clone().into());
+
+        let specs = vec![by_slug, by_id, other, exported];
+        let titles: Vec<_> = specs_of(&specs, &ticket)
+            .iter()
+            .map(|spec| spec.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["By slug", "By ID", "Exported"]);
+    }
+
+    #[test]
+    fn test_roll_up() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = SpecManager::new(temp_dir.path().to_path_buf());
+        let mut spec = Specification::new("Auth".to_string(), String::new(), None, Vec::new());
+        spec.metadata.progress.requirements_completed = true;
+        spec.metadata.progress.design_completed = true;
+
+        let mut tickets = vec![Ticket::new("a", "A"), Ticket::new("b", "B")];
+        for ticket in &mut tickets {
+            ticket
+                .metadata
+                .insert(SPEC_KEY.to_string(), spec.metadata.id.clone().into());
+            spec.metadata
+                .task_tickets
+                .insert(ticket.slug.clone(), ticket.id.to_string());
+        }
+        manager.save_metadata(&spec.metadata).unwrap();
+
+        tickets[0].status = Status::Done;
+        let rolled = roll_up(&manager, &tickets[0], || Ok(tickets.clone())).unwrap();
+        assert!(rolled.is_none());
+
+        tickets[1].status = Status::Done;
+        let rolled = roll_up(&manager, &tickets[1], || Ok(tickets.clone())).unwrap();
+        assert!(rolled.unwrap().progress.tasks_completed);
+        let saved = manager.load(&spec.metadata.id).unwrap().metadata;
+        assert!(saved.progress.is_completed());
+
+        assert!(
+            roll_up(&manager, &Ticket::new("c", "C"), || unreachable!())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
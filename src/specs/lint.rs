@@ -0,0 +1,291 @@
+//! Structural checks of spec documents
+//!
+//! `spec lint` checks that a spec is complete enough to implement:
+//!
+//! - every document exists and has its required level 2 sections. Specs
+//!   created from a template pack require the sections of the pack's
+//!   templates, the others those of [`default_sections`]. Section numbers
+//!   are ignored, and a bilingual heading such as `機能要件 / Functional
+//!   Requirements` matches either of its names.
+//! - the design cites every requirement, and only defined ones (see
+//!   [`super::refs`]).
+//! - every design section citing requirements is covered by a task citing
+//!   one of the same requirements.
+//!
+//! Open tasks are counted but are not problems.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::SpecDocumentType;
+use super::estimate::parse_tasks;
+use super::history::DOCUMENTS;
+use super::refs::{self, RefIssue};
+
+/// Problem found by [`lint`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LintIssue {
+    /// A document that has not been written
+    MissingDocument {
+        /// The document
+        document: SpecDocumentType,
+    },
+    /// A required section missing from a document
+    MissingSection {
+        /// The document
+        document: SpecDocumentType,
+        /// Name of the section
+        section: String,
+    },
+    /// A requirement reference problem of the design
+    Reference {
+        /// The problem
+        issue: RefIssue,
+    },
+    /// A design section citing requirements that no task cites
+    UncoveredDesign {
+        /// Heading of the section
+        section: String,
+        /// Line of the section heading
+        line: usize,
+        /// Requirements the section cites
+        refs: Vec<String>,
+    },
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingDocument { document } => {
+                write!(f, "{} is missing", document.file_name())
+            },
+            Self::MissingSection { document, section } => {
+                write!(f, "{} has no '{section}' section", document.file_name())
+            },
+            Self::Reference { issue } => write!(f, "{issue}"),
+            Self::UncoveredDesign {
+                section,
+                line,
+                refs,
+            } => write!(
+                f,
+                "design.md:{line} '{section}' is not covered by a task citing {}",
+                refs.join(", ")
+            ),
+        }
+    }
+}
+
+/// Counts of the checklist items of the tasks document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TaskCounts {
+    /// All tasks
+    pub total: usize,
+    /// Checked tasks
+    pub done: usize,
+    /// Unchecked tasks
+    pub open: usize,
+}
+
+/// Result of [`lint`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LintReport {
+    /// Problems found, in document order
+    pub issues: Vec<LintIssue>,
+    /// Tasks of the tasks document
+    pub tasks: TaskCounts,
+}
+
+impl LintReport {
+    /// Whether no problems were found
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A document of a spec and the sections it requires
+#[derive(Debug, Clone, Copy)]
+pub struct LintDocument<'a> {
+    /// Content of the document, if it exists
+    pub content: Option<&'a str>,
+    /// Names of the required sections
+    pub sections: &'a [String],
+}
+
+/// Sections required of documents not created from a template pack
+pub fn default_sections(document: SpecDocumentType) -> Vec<String> {
+    let sections: &[&str] = match document {
+        SpecDocumentType::Requirements => &[
+            "Overview",
+            "Scope",
+            "Functional Requirements",
+            "Non-Functional Requirements",
+        ],
+        SpecDocumentType::Design => &["Overview", "Architecture", "Detailed Design"],
+        SpecDocumentType::Tasks => &["Overview", "Detailed Tasks", "Definition of Done"],
+    };
+    sections.iter().map(ToString::to_string).collect()
+}
+
+/// Names of the level 2 sections of a markdown document, without their
+/// numbers
+pub fn sections(markdown: &str) -> Vec<String> {
+    markdown
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .map(|heading| {
+            heading
+                .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.')
+                .trim()
+                .to_string()
+        })
+        .filter(|heading| !heading.is_empty())
+        .collect()
+}
+
+/// Lints the requirements, design and tasks documents of a spec, in that
+/// order
+pub fn lint(documents: [LintDocument<'_>; 3]) -> LintReport {
+    let mut report = LintReport::default();
+    for (document, LintDocument { content, sections }) in DOCUMENTS.into_iter().zip(documents) {
+        let Some(content) = content else {
+            report.issues.push(LintIssue::MissingDocument { document });
+            continue;
+        };
+        report.issues.extend(
+            missing_sections(content, sections)
+                .map(|section| LintIssue::MissingSection { document, section }),
+        );
+    }
+
+    let [requirements, design, tasks] = documents.map(|document| document.content);
+    if let (Some(requirements), Some(design)) = (requirements, design) {
+        report.issues.extend(
+            refs::check(requirements, design)
+                .into_iter()
+                .map(|issue| LintIssue::Reference { issue }),
+        );
+    }
+    if let Some(tasks) = tasks {
+        let tasks = parse_tasks(tasks);
+        let done = tasks.iter().filter(|task| task.done).count();
+        report.tasks = TaskCounts {
+            total: tasks.len(),
+            done,
+            open: tasks.len() - done,
+        };
+        if let Some(design) = design {
+            let cited: Vec<String> = tasks
+                .iter()
+                .flat_map(|task| refs::ids(&task.title))
+                .collect();
+            report.issues.extend(
+                refs::design_sections(design)
+                    .into_iter()
+                    .filter(|section| {
+                        !section.refs.is_empty()
+                            && !section.refs.iter().any(|id| cited.contains(id))
+                    })
+                    .map(|section| LintIssue::UncoveredDesign {
+                        section: section.heading,
+                        line: section.line,
+                        refs: section.refs,
+                    }),
+            );
+        }
+    }
+    report
+}
+
+/// Required sections missing from `content`
+fn missing_sections<'a>(
+    content: &str,
+    required: &'a [String],
+) -> impl Iterator<Item = String> + 'a {
+    let present: Vec<String> = sections(content)
+        .iter()
+        .flat_map(|heading| names(heading))
+        .collect();
+    required
+        .iter()
+        .filter(move |section| !names(section).iter().any(|name| present.contains(name)))
+        .cloned()
+}
+
+/// Lowercase names of a section heading: the whole heading and each part of
+/// a heading such as `機能要件 / Functional Requirements`
+fn names(heading: &str) -> Vec<String> {
+    let mut names = vec![heading.trim().to_lowercase()];
+    if heading.contains(" / ") {
+        names.extend(heading.split(" / ").map(|part| part.trim().to_lowercase()));
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUIREMENTS: &str = "# Requirements\n\n## 1. 概要 / Overview\n\n## 3. Scope\n\n## 4. Functional Requirements\n\n- REQ-1 Login\n- REQ-2 Logout\n";
+
+    const DESIGN: &str = "# Design\n\n## 1. Overview\n\n## 2. Architecture\n\n### 2.1 Auth service\nImplements REQ-1.\n\n### 2.2 Sessions\nImplements REQ-2.\n\n## 3. Detailed Design\n";
+
+    const TASKS: &str = "## Overview\n\n## Detailed Tasks\n- [x] Login form (REQ-1)\n- [ ] Tests\n\n## Definition of Done\n";
+
+    fn document<'a>(content: Option<&'a str>, sections: &'a [String]) -> LintDocument<'a> {
+        LintDocument { content, sections }
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(
+            sections(REQUIREMENTS),
+            vec!["概要 / Overview", "Scope", "Functional Requirements"]
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let requirements = default_sections(SpecDocumentType::Requirements);
+        let design = default_sections(SpecDocumentType::Design);
+        let tasks = default_sections(SpecDocumentType::Tasks);
+        let report = lint([
+            document(Some(REQUIREMENTS), &requirements),
+            document(Some(DESIGN), &design),
+            document(Some(TASKS), &tasks),
+        ]);
+        let messages: Vec<_> = report.issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "requirements.md has no 'Non-Functional Requirements' section",
+                "design.md:10 '2.2 Sessions' is not covered by a task citing REQ-2",
+            ]
+        );
+        assert_eq!(
+            report.tasks,
+            TaskCounts {
+                total: 2,
+                done: 1,
+                open: 1
+            }
+        );
+        assert!(!report.passed());
+
+        let report = lint([
+            document(Some(REQUIREMENTS), &[]),
+            document(Some("## Overview\nREQ-1\n"), &[]),
+            document(None, &tasks),
+        ]);
+        let messages: Vec<_> = report.issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "tasks.md is missing",
+                "REQ-2 is not referenced in the design",
+            ]
+        );
+    }
+}
@@ -3,9 +3,12 @@
 //! This module provides the core functionality for managing specifications,
 //! including creation, loading, saving, and version control.
 
+use super::history::History;
+use super::review::{self, ReviewComment};
 use super::{SpecDocumentType, SpecMetadata, SpecPhase, Specification};
 use crate::error::{Result, VibeTicketError};
 use crate::specs::storage::{DocumentOperations, FileSystemStore};
+use crate::storage::IgnoreRules;
 use std::path::PathBuf;
 
 /// Manages specifications in a project
@@ -85,13 +88,64 @@ impl SpecManager {
         }
         metadata.update_phase();
         self.save_metadata(&metadata)?;
+        self.history(spec_id)
+            .record(&metadata.version, doc_type, content)?;
 
         Ok(())
     }
 
+    /// Replace a document without marking its phase complete
+    ///
+    /// Used for edits such as estimates that do not finish the phase. New
+    /// content bumps the patch version and is kept in the spec's history.
+    pub fn update_document(
+        &self,
+        spec_id: &str,
+        doc_type: SpecDocumentType,
+        content: &str,
+    ) -> Result<()> {
+        let changed = self.load_document(spec_id, doc_type)?.as_deref() != Some(content);
+        self.ops
+            .save_text_in_subdir(spec_id, doc_type.file_name(), content)?;
+        if changed {
+            return self.record_edit(spec_id, doc_type);
+        }
+
+        let mut metadata = self.load_metadata(spec_id)?;
+        metadata.updated_at = chrono::Utc::now();
+        self.save_metadata(&metadata)
+    }
+
+    /// Record a document changed on disk, for example in an editor
+    ///
+    /// Bumps the patch version and keeps the content in the spec's history.
+    pub fn record_edit(&self, spec_id: &str, doc_type: SpecDocumentType) -> Result<()> {
+        let content = self.load_document(spec_id, doc_type)?.unwrap_or_default();
+        let mut metadata = self.load_metadata(spec_id)?;
+        metadata.updated_at = chrono::Utc::now();
+        metadata.version.bump_patch();
+        self.save_metadata(&metadata)?;
+        self.history(spec_id)
+            .record(&metadata.version, doc_type, &content)?;
+        Ok(())
+    }
+
+    /// Version history of the documents of a spec
+    pub fn history(&self, spec_id: &str) -> History {
+        History::new(self.get_spec_dir(spec_id))
+    }
+
     /// List all specifications
+    ///
+    /// Spec directories excluded by `.vibe-ticket/ignore` are skipped.
     pub fn list_specs(&self) -> Result<Vec<SpecMetadata>> {
         let spec_dirs = self.ops.list_subdirs()?;
+        let ignore = self
+            .ops
+            .base_dir()
+            .parent()
+            .map(IgnoreRules::load)
+            .unwrap_or_default();
         let mut specs = Vec::new();
 
         for spec_dir in spec_dirs {
@@ -102,6 +156,9 @@ impl SpecManager {
             let Some(spec_id) = dir_name.to_str() else {
                 continue;
             };
+            if ignore.is_ignored(format!("specs/{spec_id}"), true) {
+                continue;
+            }
 
             match self.load_metadata(spec_id) {
                 Ok(metadata) => specs.push(metadata),
@@ -167,7 +224,10 @@ impl SpecManager {
     }
 
     /// Save metadata for a spec
-    fn save_metadata(&self, metadata: &SpecMetadata) -> Result<()> {
+    ///
+    /// Unlike [`Self::save`], the documents and phase progress are left as
+    /// they are.
+    pub fn save_metadata(&self, metadata: &SpecMetadata) -> Result<()> {
         self.ops.save_in_subdir(&metadata.id, "spec.json", metadata)
     }
 
@@ -177,6 +237,22 @@ impl SpecManager {
             .load_text_from_subdir(spec_id, doc_type.file_name())
     }
 
+    /// Load the review comments of a spec
+    pub fn load_comments(&self, spec_id: &str) -> Result<Vec<ReviewComment>> {
+        self.load_metadata(spec_id)?;
+        let path = self.get_spec_dir(spec_id).join(review::COMMENTS_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        self.ops.load_from_subdir(spec_id, review::COMMENTS_FILE)
+    }
+
+    /// Save the review comments of a spec
+    pub fn save_comments(&self, spec_id: &str, comments: &[ReviewComment]) -> Result<()> {
+        self.ops
+            .save_in_subdir(spec_id, review::COMMENTS_FILE, &comments)
+    }
+
     /// Find spec by title (partial match)
     pub fn find_spec_by_title(&self, query: &str) -> Result<Option<SpecMetadata>> {
         let specs = self.list_specs()?;
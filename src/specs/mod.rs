@@ -9,7 +9,8 @@
 //! # Architecture
 //!
 //! The specs are stored as Markdown files in `.vibe-ticket/specs/` directory
-//! with a `spec.json` file tracking the progress and metadata.
+//! with a `spec.json` file tracking the progress and metadata, and a
+//! `comments.json` file holding review comments (see [`review`]).
 //!
 //! # Workflow
 //!
@@ -21,14 +22,29 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+pub mod approval;
+pub mod delta;
+pub mod estimate;
+pub mod export;
+pub mod history;
+pub mod link;
+pub mod lint;
 pub mod manager;
+pub mod packs;
+pub mod refs;
+pub mod requirement;
+pub mod review;
 pub mod storage;
 pub mod templates;
 
+pub use approval::{Approval, Decision, PhaseApproval};
 pub use manager::{SpecManager, delete, get_document_path, list, load, save};
-pub use templates::{SpecTemplate, TemplateEngine};
+pub use requirement::Requirement;
+pub use review::ReviewComment;
+pub use templates::{DataModelField, RequirementLink, SpecTemplate, TemplateEngine};
 
 /// Specification metadata and progress tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +75,27 @@ pub struct SpecMetadata {
 
     /// Tags for categorization
     pub tags: Vec<String>,
+
+    /// Custom key-value fields (e.g. team, risk level, target release)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+
+    /// ID of the completed spec this delta spec amends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amends: Option<String>,
+
+    /// IDs of the requirements of the amended spec that this spec supersedes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub superseded: Vec<String>,
+
+    /// IDs of the tickets exported from the tasks document, by task key
+    /// (see [`export`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub task_tickets: BTreeMap<String, String>,
+
+    /// Template pack the documents are generated from (see [`packs`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
 }
 
 /// Progress tracking for spec documents
@@ -88,6 +125,10 @@ pub struct SpecProgress {
 
     /// Approval status with additional metadata
     pub approval_status: Option<std::collections::HashMap<String, serde_json::Value>>,
+
+    /// Review decisions on the phases, oldest first (see [`approval`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<Approval>,
 }
 
 /// Current phase of the specification
@@ -114,7 +155,7 @@ pub enum SpecPhase {
 }
 
 /// Version information for spec documents
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct SpecVersion {
     /// Major version (breaking changes)
     pub major: u32,
@@ -127,7 +168,8 @@ pub struct SpecVersion {
 }
 
 /// Specification document type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SpecDocumentType {
     /// Requirements definition document
     Requirements,
@@ -189,9 +231,41 @@ impl SpecMetadata {
             progress: SpecProgress::default(),
             version: SpecVersion::default(),
             tags: Vec::new(),
+            fields: BTreeMap::new(),
+            amends: None,
+            superseded: Vec::new(),
+            task_tickets: BTreeMap::new(),
+            template: None,
         }
     }
 
+    /// Render the metadata as YAML front matter for exported documents
+    pub fn front_matter(&self) -> String {
+        #[derive(Serialize)]
+        struct FrontMatter<'a> {
+            id: &'a str,
+            title: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            ticket: Option<&'a str>,
+            version: String,
+            #[serde(skip_serializing_if = "<[String]>::is_empty")]
+            tags: &'a [String],
+            #[serde(flatten)]
+            fields: &'a BTreeMap<String, String>,
+        }
+
+        let front_matter = FrontMatter {
+            id: &self.id,
+            title: &self.title,
+            ticket: self.ticket_id.as_deref(),
+            version: self.version.to_string(),
+            tags: &self.tags,
+            fields: &self.fields,
+        };
+        let yaml = serde_yaml::to_string(&front_matter).unwrap_or_default();
+        format!("---\n{yaml}---\n")
+    }
+
     /// Update the current phase based on progress
     pub fn update_phase(&mut self) {
         self.progress.current_phase = match (
@@ -213,6 +287,11 @@ impl SpecProgress {
     pub const fn current_phase(&self) -> SpecPhase {
         self.current_phase
     }
+
+    /// Returns true once requirements, design and tasks are all complete
+    pub const fn is_completed(&self) -> bool {
+        self.requirements_completed && self.design_completed && self.tasks_completed
+    }
 }
 
 impl Default for SpecProgress {
@@ -226,6 +305,7 @@ impl Default for SpecProgress {
             tasks_approved: false,
             current_phase: SpecPhase::Initial,
             approval_status: None,
+            approvals: Vec::new(),
         }
     }
 }
@@ -246,6 +326,31 @@ impl std::fmt::Display for SpecVersion {
     }
 }
 
+impl std::str::FromStr for SpecVersion {
+    type Err = crate::error::VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().trim_start_matches('v').split('.').collect();
+        match parts.as_slice() {
+            [major, minor, patch] => match (major.parse(), minor.parse(), patch.parse()) {
+                (Ok(major), Ok(minor), Ok(patch)) => Ok(Self {
+                    major,
+                    minor,
+                    patch,
+                }),
+                _ => Err(invalid_version(s)),
+            },
+            _ => Err(invalid_version(s)),
+        }
+    }
+}
+
+fn invalid_version(s: &str) -> crate::error::VibeTicketError {
+    crate::error::VibeTicketError::InvalidInput(format!(
+        "Invalid version '{s}'. Use MAJOR.MINOR.PATCH, e.g. 0.1.2"
+    ))
+}
+
 impl SpecVersion {
     /// Increment patch version
     pub fn bump_patch(&mut self) {
@@ -267,6 +372,15 @@ impl SpecVersion {
 }
 
 impl SpecDocumentType {
+    /// Get the name of the phase, as accepted by `FromStr`
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Requirements => "requirements",
+            Self::Design => "design",
+            Self::Tasks => "tasks",
+        }
+    }
+
     /// Get file name for this document type
     pub const fn file_name(&self) -> &'static str {
         match self {
@@ -286,6 +400,21 @@ impl SpecDocumentType {
     }
 }
 
+impl std::str::FromStr for SpecDocumentType {
+    type Err = crate::error::VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "requirements" => Ok(Self::Requirements),
+            "design" => Ok(Self::Design),
+            "tasks" => Ok(Self::Tasks),
+            _ => Err(crate::error::VibeTicketError::InvalidInput(format!(
+                "Invalid phase '{s}'. Must be one of: requirements, design, tasks"
+            ))),
+        }
+    }
+}
+
 impl std::fmt::Display for SpecPhase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -304,6 +433,28 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_front_matter_includes_custom_fields() {
+        let mut metadata = SpecMetadata::new("Auth".to_string(), String::new());
+        metadata.tags = vec!["security".to_string()];
+        metadata
+            .fields
+            .insert("team".to_string(), "core".to_string());
+        metadata
+            .fields
+            .insert("risk".to_string(), "high".to_string());
+
+        let front_matter = metadata.front_matter();
+        assert!(front_matter.starts_with("---\n"));
+        assert!(front_matter.ends_with("---\n"));
+        assert!(front_matter.contains("title: Auth\n"));
+        assert!(front_matter.contains("risk: high\nteam: core\n"));
+        assert!(!front_matter.contains("ticket:"));
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["fields"]["team"], "core");
+    }
+
     #[test]
     fn test_spec_metadata_new() {
         let metadata = SpecMetadata::new("Test Spec".to_string(), "Test Description".to_string());
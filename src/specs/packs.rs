@@ -0,0 +1,181 @@
+//! Custom spec templates
+//!
+//! Projects can replace the built-in spec documents with their own Markdown
+//! templates in `.vibe-ticket/templates/specs/`. A template pack is a
+//! directory named after the template holding any of `requirements.md`,
+//! `design.md` and `tasks.md`; a single `<name>.md` file is a pack with only
+//! a requirements document.
+//!
+//! `spec init --template <name>` records the pack on the spec, and each
+//! document is generated from the pack when it is first created. Documents
+//! the pack lacks come from the built-in templates. Templates may use the
+//! variables `title`, `description`, `ticket`, `date`, `author` and
+//! `spec_id`, written `{{title}}` or `{{ title }}`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::SpecDocumentType;
+use super::history::DOCUMENTS;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// A set of templates for the documents of a spec
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplatePack {
+    /// Name given to `spec init --template`
+    pub name: String,
+
+    /// Directory of the pack, or the file of a single-file pack
+    pub path: PathBuf,
+
+    /// Documents the pack has templates for
+    pub documents: Vec<SpecDocumentType>,
+}
+
+impl TemplatePack {
+    /// Loads the pack at `path`, a directory or a Markdown file
+    ///
+    /// Returns `None` for other files and for directories without templates.
+    fn load(path: PathBuf) -> Option<Self> {
+        let (name, documents) = if path.is_dir() {
+            let documents: Vec<_> = DOCUMENTS
+                .into_iter()
+                .filter(|doc| path.join(doc.file_name()).is_file())
+                .collect();
+            (path.file_name()?.to_string_lossy().into_owned(), documents)
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            (
+                path.file_stem()?.to_string_lossy().into_owned(),
+                vec![SpecDocumentType::Requirements],
+            )
+        } else {
+            return None;
+        };
+        (!documents.is_empty()).then_some(Self {
+            name,
+            path,
+            documents,
+        })
+    }
+
+    /// Reads the template of `doc`, if the pack has one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template cannot be read.
+    pub fn document(&self, doc: SpecDocumentType) -> Result<Option<String>> {
+        if !self.documents.contains(&doc) {
+            return Ok(None);
+        }
+        let path = if self.path.is_dir() {
+            self.path.join(doc.file_name())
+        } else {
+            self.path.clone()
+        };
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read spec template: {}", path.display()))
+            .map(Some)
+    }
+}
+
+/// Directory of the spec templates of the project in `project_dir`
+pub fn templates_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join("templates").join("specs")
+}
+
+/// Lists the template packs in `dir` by name
+///
+/// A missing directory has no packs. When a directory and a file have the
+/// same name, the directory wins.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read.
+pub fn list(dir: &Path) -> Result<Vec<TemplatePack>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut packs = Vec::new();
+    for entry in fs::read_dir(dir)
+        .with_context(|| format!("Failed to read spec templates: {}", dir.display()))?
+    {
+        let entry = entry.context("Failed to read spec templates")?;
+        packs.extend(TemplatePack::load(entry.path()));
+    }
+    packs.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| b.path.is_dir().cmp(&a.path.is_dir()))
+    });
+    packs.dedup_by(|later, first| later.name == first.name);
+    Ok(packs)
+}
+
+/// Finds the template pack `name` in `dir`
+///
+/// # Errors
+///
+/// Returns [`VibeTicketError::InvalidInput`] naming the available packs if
+/// there is no such pack.
+pub fn find(dir: &Path, name: &str) -> Result<TemplatePack> {
+    let packs = list(dir)?;
+    let names: Vec<_> = packs.iter().map(|pack| pack.name.clone()).collect();
+    packs
+        .into_iter()
+        .find(|pack| pack.name == name)
+        .ok_or_else(|| {
+            VibeTicketError::InvalidInput(format!(
+                "Unknown spec template '{name}'. Available: {}",
+                if names.is_empty() {
+                    format!("none (add templates to {})", dir.display())
+                } else {
+                    names.join(", ")
+                }
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_and_find() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir(dir.join("rfc")).unwrap();
+        fs::write(dir.join("rfc/requirements.md"), "# RFC: {{title}}").unwrap();
+        fs::write(dir.join("rfc/tasks.md"), "- [ ] {{title}}").unwrap();
+        fs::write(dir.join("rfc.md"), "shadowed").unwrap();
+        fs::write(dir.join("adr.md"), "# ADR: {{ title }}").unwrap();
+        fs::write(dir.join("notes.txt"), "not a template").unwrap();
+        fs::create_dir(dir.join("empty")).unwrap();
+
+        let packs = list(dir).unwrap();
+        let names: Vec<_> = packs.iter().map(|pack| pack.name.as_str()).collect();
+        assert_eq!(names, vec!["adr", "rfc"]);
+
+        let rfc = find(dir, "rfc").unwrap();
+        assert_eq!(
+            rfc.documents,
+            vec![SpecDocumentType::Requirements, SpecDocumentType::Tasks]
+        );
+        assert_eq!(
+            rfc.document(SpecDocumentType::Requirements)
+                .unwrap()
+                .as_deref(),
+            Some("# RFC: {{title}}")
+        );
+        assert_eq!(rfc.document(SpecDocumentType::Design).unwrap(), None);
+
+        let adr = find(dir, "adr").unwrap();
+        assert_eq!(adr.documents, vec![SpecDocumentType::Requirements]);
+
+        let err = find(dir, "prd").unwrap_err();
+        assert!(err.to_string().contains("Available: adr, rfc"));
+        assert!(list(&dir.join("missing")).unwrap().is_empty());
+    }
+}
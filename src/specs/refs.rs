@@ -0,0 +1,236 @@
+//! Requirement references between spec documents
+//!
+//! Requirements get IDs such as `REQ-1`, `FR-12` or `NFR-3`, written as the
+//! first word of a heading or list item of `requirements.md`:
+//!
+//! ```markdown
+//! ### REQ-1 Login with email
+//! - **FR-2**: Lock the account after five failed attempts
+//! ```
+//!
+//! Each section of `design.md` cites the requirements it addresses by their
+//! IDs. [`check`] reports requirements never cited in the design and design
+//! sections citing IDs that no requirement defines. Specs whose requirements
+//! define no IDs are not checked.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+
+/// Prefixes of requirement IDs
+pub const PREFIXES: [&str; 3] = ["REQ", "FR", "NFR"];
+
+/// Section of a design document and the requirements it cites
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DesignSection {
+    /// Heading of the section, without the `#` marks
+    pub heading: String,
+    /// Line of the heading, starting at 1
+    pub line: usize,
+    /// Requirement IDs cited in the heading or body, in order of appearance
+    pub refs: Vec<String>,
+}
+
+/// Problem found by [`check`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RefIssue {
+    /// A requirement that no design section cites
+    Unreferenced {
+        /// Requirement ID
+        id: String,
+    },
+    /// A design section citing an ID that no requirement defines
+    Unknown {
+        /// Cited ID
+        id: String,
+        /// Heading of the citing section
+        section: String,
+        /// Line of the section heading
+        line: usize,
+    },
+}
+
+impl fmt::Display for RefIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreferenced { id } => write!(f, "{id} is not referenced in the design"),
+            Self::Unknown { id, section, line } => write!(
+                f,
+                "design.md:{line} '{section}' cites {id}, which no requirement defines"
+            ),
+        }
+    }
+}
+
+/// IDs of the requirements defined in a requirements document, in order
+pub fn requirement_ids(requirements: &str) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    prose_lines(requirements)
+        .filter_map(|(_, line)| {
+            let line = line.trim_start();
+            let item = line
+                .strip_prefix('#')
+                .map(|rest| rest.trim_start_matches('#'))
+                .or_else(|| line.strip_prefix("- "))
+                .or_else(|| line.strip_prefix("* "))?;
+            let item = item.trim_start().trim_start_matches(['*', '_', '[', '`']);
+            ids(item).next().filter(|id| item.starts_with(id.as_str()))
+        })
+        .filter(|id| seen.insert(id.clone()))
+        .collect()
+}
+
+/// Splits a design document into its level 2 and 3 sections
+///
+/// Text before the first such heading is not part of any section.
+pub fn design_sections(design: &str) -> Vec<DesignSection> {
+    let mut sections: Vec<DesignSection> = Vec::new();
+    for (index, line) in prose_lines(design) {
+        let heading = line
+            .strip_prefix("### ")
+            .or_else(|| line.strip_prefix("## "));
+        if let Some(heading) = heading {
+            sections.push(DesignSection {
+                heading: heading.trim().to_string(),
+                line: index + 1,
+                refs: Vec::new(),
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            for id in ids(line) {
+                if !section.refs.contains(&id) {
+                    section.refs.push(id);
+                }
+            }
+        }
+    }
+    sections
+}
+
+/// Checks that the design cites every requirement, and only defined ones
+pub fn check(requirements: &str, design: &str) -> Vec<RefIssue> {
+    let defined = requirement_ids(requirements);
+    if defined.is_empty() {
+        return Vec::new();
+    }
+
+    let sections = design_sections(design);
+    let cited: BTreeSet<&str> = sections
+        .iter()
+        .flat_map(|section| section.refs.iter().map(String::as_str))
+        .collect();
+    let unreferenced = defined
+        .iter()
+        .filter(|id| !cited.contains(id.as_str()))
+        .map(|id| RefIssue::Unreferenced { id: id.clone() });
+    let unknown = sections.iter().flat_map(|section| {
+        section
+            .refs
+            .iter()
+            .filter(|id| !defined.contains(id))
+            .map(|id| RefIssue::Unknown {
+                id: id.clone(),
+                section: section.heading.clone(),
+                line: section.line,
+            })
+    });
+    unreferenced.chain(unknown).collect()
+}
+
+/// Lines of a markdown document outside code blocks, with their index
+fn prose_lines(markdown: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut in_code_block = false;
+    markdown.lines().enumerate().filter(move |(_, line)| {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            return false;
+        }
+        !in_code_block
+    })
+}
+
+/// Requirement IDs in a line of text
+pub fn ids(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .filter(|word| {
+            word.split_once('-').is_some_and(|(prefix, number)| {
+                PREFIXES.contains(&prefix)
+                    && !number.is_empty()
+                    && number.chars().all(|c| c.is_ascii_digit())
+            })
+        })
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUIREMENTS: &str = "# Requirements\n\n## 4. Functional Requirements\n\n### REQ-1 Login\nUsers log in with email, see REQ-9.\n\n- **FR-2**: Lock accounts\n- [NFR-3] p99 under 200ms\n- Encoding is UTF-8, not FR-x\n\n```\n- REQ-7 in a code block\n```\n";
+
+    const DESIGN: &str = "# Design\n\nCovers REQ-1 overall.\n\n## 2. Architecture\n\n### 2.1 Auth service (REQ-1)\nImplements REQ-1 and FR-2, and REQ-4.\n\n### 2.2 Caching\nKeeps NFR-3 and REQ-4.\n";
+
+    #[test]
+    fn test_requirement_ids() {
+        assert_eq!(
+            requirement_ids(REQUIREMENTS),
+            vec!["REQ-1", "FR-2", "NFR-3"]
+        );
+        assert!(requirement_ids("# Requirements\n\n- login\n").is_empty());
+    }
+
+    #[test]
+    fn test_design_sections() {
+        let sections = design_sections(DESIGN);
+        let summary: Vec<_> = sections
+            .iter()
+            .map(|s| (s.heading.as_str(), s.line, s.refs.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2. Architecture", 5, vec![]),
+                (
+                    "2.1 Auth service (REQ-1)",
+                    7,
+                    vec!["REQ-1".to_string(), "FR-2".to_string(), "REQ-4".to_string()]
+                ),
+                (
+                    "2.2 Caching",
+                    10,
+                    vec!["NFR-3".to_string(), "REQ-4".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let issues = check(REQUIREMENTS, DESIGN);
+        let messages: Vec<_> = issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "design.md:7 '2.1 Auth service (REQ-1)' cites REQ-4, which no requirement defines",
+                "design.md:10 '2.2 Caching' cites REQ-4, which no requirement defines",
+            ]
+        );
+
+        let issues = check(REQUIREMENTS, "## Overview\nOnly REQ-1.\n");
+        assert_eq!(
+            issues,
+            vec![
+                RefIssue::Unreferenced {
+                    id: "FR-2".to_string()
+                },
+                RefIssue::Unreferenced {
+                    id: "NFR-3".to_string()
+                },
+            ]
+        );
+
+        assert!(check("# Requirements\n", "## Overview\nREQ-1\n").is_empty());
+    }
+}
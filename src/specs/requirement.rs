@@ -0,0 +1,478 @@
+//! Structured requirements
+//!
+//! Requirements in `requirements.md` may be written in EARS syntax, one
+//! statement per line or list item:
+//!
+//! ```markdown
+//! - The service shall store passwords hashed.
+//! - **REQ-2**: When a login fails five times, the service shall lock the account.
+//! ```
+//!
+//! or as Given/When/Then scenarios, with or without a `Scenario:` line and
+//! inside or outside a `gherkin` code block:
+//!
+//! ```gherkin
+//! @REQ-3
+//! Scenario: Unlock after an hour
+//!   Given a locked account
+//!   When an hour has passed
+//!   Then the user can log in again
+//! ```
+//!
+//! A requirement's ID is the requirement ID (see [`super::refs`]) it starts
+//! with or is tagged with. A requirement without one takes the ID of its
+//! heading when it is the only such requirement under that heading;
+//! otherwise it gets `R-` and six hex digits of the hash of its text, which
+//! stays the same as long as the text does. [`trace`] finds the design
+//! sections and tasks citing each requirement.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::estimate::parse_tasks;
+use super::refs;
+
+/// EARS pattern of a requirement statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EarsPattern {
+    /// `The <system> shall <response>`
+    Ubiquitous,
+    /// `When <trigger>, the <system> shall <response>`
+    EventDriven,
+    /// `While <state>, the <system> shall <response>`
+    StateDriven,
+    /// `If <condition>, then the <system> shall <response>`
+    UnwantedBehavior,
+    /// `Where <feature>, the <system> shall <response>`
+    Optional,
+}
+
+impl EarsPattern {
+    /// Pattern of an EARS statement, or `None` if `text` is not one
+    pub fn of(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        if !text.contains(" shall ") {
+            return None;
+        }
+        [
+            ("when ", Self::EventDriven),
+            ("while ", Self::StateDriven),
+            ("if ", Self::UnwantedBehavior),
+            ("where ", Self::Optional),
+            ("the ", Self::Ubiquitous),
+        ]
+        .into_iter()
+        .find(|(keyword, _)| text.starts_with(keyword))
+        .map(|(_, pattern)| pattern)
+    }
+}
+
+impl fmt::Display for EarsPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ubiquitous => write!(f, "ubiquitous"),
+            Self::EventDriven => write!(f, "event-driven"),
+            Self::StateDriven => write!(f, "state-driven"),
+            Self::UnwantedBehavior => write!(f, "unwanted behavior"),
+            Self::Optional => write!(f, "optional"),
+        }
+    }
+}
+
+/// Syntax a requirement is written in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "syntax", rename_all = "snake_case")]
+pub enum RequirementSyntax {
+    /// An EARS statement
+    Ears {
+        /// Pattern of the statement
+        pattern: EarsPattern,
+    },
+    /// A Given/When/Then scenario
+    Scenario {
+        /// Steps of the scenario, such as `Given a locked account`
+        steps: Vec<String>,
+    },
+}
+
+/// A structured requirement of a requirements document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Requirement {
+    /// Requirement ID, given or derived from the text
+    pub id: String,
+    /// The statement, or the title of the scenario
+    pub text: String,
+    /// Line of the requirement, starting at 1
+    pub line: usize,
+    /// How the requirement is written
+    #[serde(flatten)]
+    pub syntax: RequirementSyntax,
+}
+
+/// Where a requirement is cited
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trace {
+    /// Requirement ID
+    pub id: String,
+    /// Headings of the design sections citing the requirement
+    pub design: Vec<String>,
+    /// Titles of the tasks citing the requirement
+    pub tasks: Vec<String>,
+}
+
+/// Counts of the structured requirements of a spec and their citations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RequirementSummary {
+    /// Structured requirements
+    pub total: usize,
+    /// Requirements written in EARS
+    pub ears: usize,
+    /// Requirements written as scenarios
+    pub scenarios: usize,
+    /// Requirements cited in the design
+    pub in_design: usize,
+    /// Requirements cited by tasks
+    pub in_tasks: usize,
+}
+
+impl RequirementSummary {
+    /// Counts `requirements` and their `traces`
+    pub fn of(requirements: &[Requirement], traces: &[Trace]) -> Self {
+        let ears = requirements
+            .iter()
+            .filter(|r| matches!(r.syntax, RequirementSyntax::Ears { .. }))
+            .count();
+        Self {
+            total: requirements.len(),
+            ears,
+            scenarios: requirements.len() - ears,
+            in_design: traces.iter().filter(|t| !t.design.is_empty()).count(),
+            in_tasks: traces.iter().filter(|t| !t.tasks.is_empty()).count(),
+        }
+    }
+}
+
+/// Gherkin step keywords
+const STEP_KEYWORDS: [&str; 5] = ["Given", "When", "Then", "And", "But"];
+
+/// A requirement being parsed, with the heading it is under
+struct Parsed {
+    id: Option<String>,
+    heading: usize,
+    requirement: Requirement,
+}
+
+/// Parses the structured requirements of a requirements document
+pub fn parse(requirements: &str) -> Vec<Requirement> {
+    // IDs and titles of the headings, the first standing for no heading
+    let mut headings: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    let mut parsed: Vec<Parsed> = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+    let mut in_scenario = false;
+
+    for (index, line) in requirements.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let (id, title) = split_id(heading.trim_start_matches('#').trim());
+            headings.push((id, title.to_string()));
+            in_scenario = false;
+            continue;
+        }
+        let text = strip_list_marker(trimmed);
+        if text.starts_with('@') {
+            tags.extend(
+                text.split_whitespace()
+                    .map(|tag| tag.trim_start_matches('@').to_string()),
+            );
+            continue;
+        }
+
+        let heading = headings.len() - 1;
+        let (id, statement) = split_id(text);
+        if let Some(title) = text
+            .strip_prefix("Scenario Outline:")
+            .or_else(|| text.strip_prefix("Scenario:"))
+        {
+            let (id, title) = split_id(title.trim());
+            let id = id.or_else(|| std::mem::take(&mut tags).into_iter().find(|tag| is_id(tag)));
+            parsed.push(Parsed {
+                id,
+                heading,
+                requirement: scenario(title, index),
+            });
+            in_scenario = true;
+        } else if let Some(pattern) = EarsPattern::of(statement) {
+            in_scenario = false;
+            parsed.push(Parsed {
+                id,
+                heading,
+                requirement: Requirement {
+                    id: String::new(),
+                    text: statement.to_string(),
+                    line: index + 1,
+                    syntax: RequirementSyntax::Ears { pattern },
+                },
+            });
+        } else if let Some(step) = step(text) {
+            // Steps outside a `Scenario:` start a scenario titled by the heading
+            if !in_scenario || parsed.last().is_none_or(|p| p.heading != heading) {
+                parsed.push(Parsed {
+                    id: None,
+                    heading,
+                    requirement: scenario(&headings[heading].1, index),
+                });
+                in_scenario = true;
+            }
+            if let Some(Parsed {
+                requirement:
+                    Requirement {
+                        syntax: RequirementSyntax::Scenario { steps },
+                        ..
+                    },
+                ..
+            }) = parsed.last_mut()
+            {
+                steps.push(step);
+            }
+        } else if !text.is_empty() && !text.starts_with("```") {
+            in_scenario = false;
+        }
+        tags.clear();
+    }
+
+    // Requirements without an ID of their own, by heading
+    let mut per_heading: BTreeMap<usize, usize> = BTreeMap::new();
+    for p in parsed.iter().filter(|p| p.id.is_none()) {
+        *per_heading.entry(p.heading).or_default() += 1;
+    }
+    parsed
+        .into_iter()
+        .map(|p| {
+            let heading_id = (per_heading.get(&p.heading) == Some(&1))
+                .then(|| headings[p.heading].0.clone())
+                .flatten();
+            let mut requirement = p.requirement;
+            requirement.id =
+                p.id.or(heading_id)
+                    .unwrap_or_else(|| hashed_id(&requirement));
+            requirement
+        })
+        .collect()
+}
+
+/// Finds the design sections and tasks citing each requirement
+pub fn trace(requirements: &[Requirement], design: &str, tasks: &str) -> Vec<Trace> {
+    let sections = sections(design);
+    let tasks = parse_tasks(tasks);
+    requirements
+        .iter()
+        .map(|requirement| Trace {
+            id: requirement.id.clone(),
+            design: sections
+                .iter()
+                .filter(|(_, body)| cites(body, &requirement.id))
+                .map(|(heading, _)| heading.clone())
+                .collect(),
+            tasks: tasks
+                .iter()
+                .filter(|task| cites(&task.title, &requirement.id))
+                .map(|task| task.title.clone())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Starts a scenario titled `title` on line `index`
+fn scenario(title: &str, index: usize) -> Requirement {
+    Requirement {
+        id: String::new(),
+        text: title.to_string(),
+        line: index + 1,
+        syntax: RequirementSyntax::Scenario { steps: Vec::new() },
+    }
+}
+
+/// A Gherkin step such as `Given a locked account`
+fn step(text: &str) -> Option<String> {
+    let (keyword, rest) = text.split_once(' ')?;
+    STEP_KEYWORDS
+        .contains(&keyword)
+        .then(|| format!("{keyword} {}", rest.trim()))
+}
+
+/// Removes a list marker or checkbox from the start of a line
+fn strip_list_marker(text: &str) -> &str {
+    let text = text
+        .strip_prefix("- ")
+        .or_else(|| text.strip_prefix("* "))
+        .unwrap_or(text);
+    let numbered = text
+        .split_once(". ")
+        .filter(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    numbered.map_or(text, |(_, rest)| rest).trim_start()
+}
+
+/// Splits a leading requirement ID, such as `**REQ-2**:`, off `text`
+fn split_id(text: &str) -> (Option<String>, &str) {
+    let item = text.trim_start_matches(['*', '_', '[', '`']);
+    match refs::ids(item).next() {
+        Some(id) if item.starts_with(id.as_str()) => {
+            let rest = item[id.len()..].trim_start_matches(['*', '_', ']', '`', ':', ' ']);
+            (Some(id), rest)
+        },
+        _ => (None, text),
+    }
+}
+
+/// Whether `text` is exactly a requirement ID
+fn is_id(text: &str) -> bool {
+    refs::ids(text).next().is_some_and(|id| id == text)
+}
+
+/// ID derived from the text of a requirement
+fn hashed_id(requirement: &Requirement) -> String {
+    let text = requirement
+        .text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let digest = Sha256::digest(text.as_bytes());
+    format!("R-{}", &format!("{digest:x}")[..6])
+}
+
+/// Level 2 and 3 sections of a document, as headings and their text
+fn sections(markdown: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    for line in markdown.lines() {
+        let heading = line
+            .strip_prefix("### ")
+            .or_else(|| line.strip_prefix("## "));
+        if let Some(heading) = heading {
+            sections.push((heading.trim().to_string(), String::new()));
+        }
+        if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections
+}
+
+/// Whether `text` cites `id` as a word
+fn cites(text: &str, id: &str) -> bool {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .any(|word| word == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUIREMENTS: &str = "# Requirements
+
+## Login
+
+### REQ-1 Passwords
+- The service shall store passwords hashed.
+
+### Lockout
+- **REQ-2**: When a login fails five times, the service shall lock the account.
+- If the mail server is down, then the service shall queue the unlock mail.
+- Accounts are per tenant.
+
+### REQ-4 Reset
+- Given a user with a reset link
+- When they open it twice
+- Then the second attempt fails
+
+```gherkin
+@REQ-3
+Scenario: Unlock after an hour
+  Given a locked account
+  When an hour has passed
+  Then the user can log in again
+```
+";
+
+    #[test]
+    fn test_parse() {
+        let requirements = parse(REQUIREMENTS);
+        let summary: Vec<_> = requirements
+            .iter()
+            .map(|r| (r.id.as_str(), r.line))
+            .collect();
+        let hashed = &requirements[2].id;
+        assert!(hashed.starts_with("R-") && hashed.len() == 8);
+        assert_eq!(
+            summary,
+            vec![
+                ("REQ-1", 6),
+                ("REQ-2", 9),
+                (hashed.as_str(), 10),
+                ("REQ-4", 14),
+                ("REQ-3", 20),
+            ]
+        );
+        assert_eq!(
+            requirements[1].syntax,
+            RequirementSyntax::Ears {
+                pattern: EarsPattern::EventDriven
+            }
+        );
+        assert_eq!(
+            requirements[1].text,
+            "When a login fails five times, the service shall lock the account."
+        );
+        assert_eq!(
+            requirements[2].syntax,
+            RequirementSyntax::Ears {
+                pattern: EarsPattern::UnwantedBehavior
+            }
+        );
+        assert_eq!(requirements[3].text, "Reset");
+        assert_eq!(requirements[4].text, "Unlock after an hour");
+        assert_eq!(
+            requirements[4].syntax,
+            RequirementSyntax::Scenario {
+                steps: vec![
+                    "Given a locked account".to_string(),
+                    "When an hour has passed".to_string(),
+                    "Then the user can log in again".to_string(),
+                ]
+            }
+        );
+
+        // Hashed IDs do not depend on the position of the requirement
+        let moved = REQUIREMENTS.replace("### REQ-1 Passwords\n", "### REQ-1 Passwords\n\n\n");
+        assert_eq!(parse(&moved)[2].id, *hashed);
+    }
+
+    #[test]
+    fn test_trace() {
+        let requirements = parse(REQUIREMENTS);
+        let design = "## Auth\n\n### Hashing\nCovers REQ-1.\n\n### Lockout\nREQ-2 and REQ-3.\n";
+        let tasks = "- [ ] Hash passwords (REQ-1)\n- [x] Lock accounts REQ-2\n";
+        let traces = trace(&requirements, design, tasks);
+        assert_eq!(traces[0].design, vec!["Hashing"]);
+        assert_eq!(traces[0].tasks, vec!["Hash passwords (REQ-1)"]);
+        assert_eq!(traces[4].design, vec!["Lockout"]);
+        assert!(traces[4].tasks.is_empty());
+
+        let summary = RequirementSummary::of(&requirements, &traces);
+        assert_eq!(
+            summary,
+            RequirementSummary {
+                total: 5,
+                ears: 3,
+                scenarios: 2,
+                in_design: 3,
+                in_tasks: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Review comments on specification documents
+//!
+//! Reviewers leave comments on a phase document, optionally anchored to a
+//! line, and resolve them once addressed. Comments are stored per spec in
+//! `comments.json`, next to `spec.json`, and numbered per spec starting at 1.
+//!
+//! With `spec.strict_review` enabled, a phase cannot be approved while its
+//! document has unresolved comments.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::SpecDocumentType;
+use crate::error::{Result, VibeTicketError};
+
+/// File holding the review comments of a spec
+pub const COMMENTS_FILE: &str = "comments.json";
+
+/// A review comment on a spec document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewComment {
+    /// Number of the comment within its spec
+    pub id: u32,
+
+    /// Document the comment is about
+    pub phase: SpecDocumentType,
+
+    /// Line of the document the comment refers to (1-based)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /// Author of the comment
+    pub author: String,
+
+    /// Timestamp when the comment was written
+    pub created_at: DateTime<Utc>,
+
+    /// Text of the comment
+    pub body: String,
+
+    /// Who resolved the comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<String>,
+
+    /// Timestamp when the comment was resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ReviewComment {
+    /// Whether the comment was resolved
+    pub const fn is_resolved(&self) -> bool {
+        self.resolved_at.is_some()
+    }
+
+    /// Location of the comment, such as `design.md:42`
+    pub fn location(&self) -> String {
+        self.line.map_or_else(
+            || self.phase.file_name().to_string(),
+            |line| format!("{}:{line}", self.phase.file_name()),
+        )
+    }
+}
+
+/// Adds a comment to `comments`, numbered after the highest existing one
+///
+/// `document` is the current content of the commented document; a line
+/// must exist in it.
+///
+/// # Errors
+///
+/// Returns [`VibeTicketError::InvalidInput`] if the body is empty, the
+/// document does not exist yet or the line is outside it.
+pub fn add_comment(
+    comments: &mut Vec<ReviewComment>,
+    phase: SpecDocumentType,
+    document: Option<&str>,
+    line: Option<usize>,
+    author: &str,
+    body: &str,
+) -> Result<ReviewComment> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Comment text cannot be empty".to_string(),
+        ));
+    }
+    let Some(document) = document else {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "The spec has no {} document to comment on",
+            phase.display_name()
+        )));
+    };
+    if let Some(line) = line {
+        let lines = document.lines().count();
+        if line == 0 || line > lines {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "Line {line} is outside {} ({lines} lines)",
+                phase.file_name()
+            )));
+        }
+    }
+
+    let comment = ReviewComment {
+        id: comments.iter().map(|c| c.id).max().unwrap_or(0) + 1,
+        phase,
+        line,
+        author: author.to_string(),
+        created_at: Utc::now(),
+        body: body.to_string(),
+        resolved_by: None,
+        resolved_at: None,
+    };
+    comments.push(comment.clone());
+    Ok(comment)
+}
+
+/// Marks comment `id` as resolved by `by`, or reopens it
+///
+/// # Errors
+///
+/// Returns [`VibeTicketError::InvalidInput`] if there is no such comment.
+pub fn resolve_comment(
+    comments: &mut [ReviewComment],
+    id: u32,
+    by: Option<&str>,
+) -> Result<ReviewComment> {
+    let comment = comments
+        .iter_mut()
+        .find(|c| c.id == id)
+        .ok_or_else(|| VibeTicketError::InvalidInput(format!("No review comment #{id}")))?;
+    if let Some(by) = by {
+        comment.resolved_by = Some(by.to_string());
+        comment.resolved_at = Some(Utc::now());
+    } else {
+        comment.resolved_by = None;
+        comment.resolved_at = None;
+    }
+    Ok(comment.clone())
+}
+
+/// Unresolved comments on the document of `phase`
+pub fn unresolved(
+    comments: &[ReviewComment],
+    phase: SpecDocumentType,
+) -> impl Iterator<Item = &ReviewComment> {
+    comments
+        .iter()
+        .filter(move |c| c.phase == phase && !c.is_resolved())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESIGN: &str = "# Design\n\nUse a worker pool.\n";
+
+    #[test]
+    fn test_add_comment_validates_line() {
+        let mut comments = Vec::new();
+        let first = add_comment(
+            &mut comments,
+            SpecDocumentType::Design,
+            Some(DESIGN),
+            Some(3),
+            "alice",
+            "why not use a queue?",
+        )
+        .unwrap();
+        assert_eq!(first.id, 1);
+        assert_eq!(first.location(), "design.md:3");
+
+        for line in [0, 4] {
+            assert!(
+                add_comment(
+                    &mut comments,
+                    SpecDocumentType::Design,
+                    Some(DESIGN),
+                    Some(line),
+                    "alice",
+                    "x",
+                )
+                .is_err()
+            );
+        }
+        assert!(
+            add_comment(
+                &mut comments,
+                SpecDocumentType::Tasks,
+                None,
+                None,
+                "alice",
+                "x"
+            )
+            .is_err()
+        );
+        assert_eq!(comments.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_and_reopen() {
+        let mut comments = Vec::new();
+        for body in ["a", "b"] {
+            add_comment(
+                &mut comments,
+                SpecDocumentType::Design,
+                Some(DESIGN),
+                None,
+                "alice",
+                body,
+            )
+            .unwrap();
+        }
+        assert_eq!(unresolved(&comments, SpecDocumentType::Design).count(), 2);
+
+        resolve_comment(&mut comments, 1, Some("bob")).unwrap();
+        let open: Vec<u32> = unresolved(&comments, SpecDocumentType::Design)
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(open, [2]);
+        assert_eq!(
+            unresolved(&comments, SpecDocumentType::Requirements).count(),
+            0
+        );
+
+        resolve_comment(&mut comments, 1, None).unwrap();
+        assert_eq!(unresolved(&comments, SpecDocumentType::Design).count(), 2);
+        assert!(resolve_comment(&mut comments, 9, Some("bob")).is_err());
+    }
+}
@@ -6,6 +6,7 @@
 use super::SpecDocumentType;
 use chrono::Local;
 use std::collections::HashMap;
+use std::fmt::Write as FmtWrite;
 
 /// Template engine for generating spec documents
 pub struct TemplateEngine {
@@ -32,12 +33,16 @@ impl TemplateEngine {
 
     /// Generate document from template
     pub fn generate(&self, template: &SpecTemplate) -> String {
-        let mut content = template.content();
+        self.render(&template.content())
+    }
 
-        // Replace variables
+    /// Replace the variables in `text`, written `{{key}}` or `{{ key }}`
+    pub fn render(&self, text: &str) -> String {
+        let mut content = text.to_string();
         for (key, value) in &self.variables {
-            let placeholder = format!("{{{{{key}}}}}");
-            content = content.replace(&placeholder, value);
+            for placeholder in [format!("{{{{{key}}}}}"), format!("{{{{ {key} }}}}")] {
+                content = content.replace(&placeholder, value);
+            }
         }
 
         content
@@ -53,6 +58,10 @@ pub enum SpecTemplate {
     Design {
         title: String,
         requirements_summary: String,
+        /// Fields of the linked ticket, rendered as the data model table
+        data_model: Vec<DataModelField>,
+        /// Headings of the requirements document to link to
+        requirement_links: Vec<RequirementLink>,
     },
 
     /// Implementation plan template
@@ -77,6 +86,8 @@ impl SpecTemplate {
             SpecDocumentType::Design => Self::Design {
                 title,
                 requirements_summary: context.unwrap_or_default(),
+                data_model: Vec::new(),
+                requirement_links: Vec::new(),
             },
             SpecDocumentType::Tasks => Self::Tasks {
                 title,
@@ -125,7 +136,7 @@ impl SpecTemplate {
 <!-- As a [role], I want [feature] so that [benefit] 形式で記述 -->
 
 ### 4.2 機能一覧 / Feature List
-<!-- 実装すべき機能のリスト -->
+<!-- 実装すべき機能のリスト。設計書から参照できるよう ID を付ける / Give each requirement an ID the design can cite, e.g. `- REQ-1: ...` -->
 
 ### 4.3 画面/インターフェース要件 / UI/Interface Requirements
 <!-- ユーザーインターフェースやAPIインターフェースの要件 -->
@@ -170,7 +181,11 @@ impl SpecTemplate {
             Self::Design {
                 title,
                 requirements_summary,
+                data_model,
+                requirement_links,
             } => {
+                let traceability = render_requirement_links(requirement_links);
+                let data_model_table = render_data_model(data_model);
                 format!(
                     r"# 技術設計書 / Technical Design Document
 
@@ -186,22 +201,45 @@ impl SpecTemplate {
 ### 1.2 設計方針 / Design Principles
 <!-- この設計で重視する原則や方針 -->
 
+### 1.3 要件トレーサビリティ / Requirements Traceability
+{traceability}
 ## 2. アーキテクチャ / Architecture
 
 ### 2.1 全体構成図 / System Architecture
 <!-- システム全体のアーキテクチャ図 -->
-```
-[Component A] --> [Component B]
-      |               |
-      v               v
-[Component C] --> [Database]
+```mermaid
+flowchart LR
+    A[Component A] --> B[Component B]
+    A --> C[Component C]
+    B --> D[(Database)]
+    C --> D
 ```
 
 ### 2.2 コンポーネント設計 / Component Design
 <!-- 各コンポーネントの責務と相互作用 -->
+```mermaid
+classDiagram
+    class ComponentA {{
+        +handle()
+    }}
+    class ComponentB {{
+        +process()
+    }}
+    ComponentA --> ComponentB
+```
 
 ### 2.3 データフロー / Data Flow
 <!-- データの流れと処理の順序 -->
+```mermaid
+sequenceDiagram
+    participant User
+    participant ComponentA
+    participant Database
+    User->>ComponentA: Request
+    ComponentA->>Database: Query
+    Database-->>ComponentA: Result
+    ComponentA-->>User: Response
+```
 
 ## 3. 詳細設計 / Detailed Design
 
@@ -222,7 +260,12 @@ src/
 
 ### 3.3 データモデル / Data Model
 <!-- データ構造、スキーマ、エンティティの定義 -->
+```mermaid
+erDiagram
+    ENTITY_A ||--o{{ ENTITY_B : has
+```
 
+{data_model_table}
 ## 4. 実装詳細 / Implementation Details
 
 ### 4.1 主要アルゴリズム / Key Algorithms
@@ -402,6 +445,146 @@ src/
     }
 }
 
+/// A field shown in the data model table of a design document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataModelField {
+    /// Field name
+    pub name: String,
+
+    /// Field type (string, number, boolean, array, object)
+    pub field_type: String,
+
+    /// Example value
+    pub example: String,
+}
+
+impl DataModelField {
+    /// Maximum length of an example value in the table
+    const MAX_EXAMPLE_LEN: usize = 40;
+
+    /// Describes a field from its name and a sample JSON value
+    pub fn from_value(name: impl Into<String>, value: &serde_json::Value) -> Self {
+        let field_type = match value {
+            serde_json::Value::Null => "null",
+            serde_json::Value::Bool(_) => "boolean",
+            serde_json::Value::Number(_) => "number",
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Array(_) => "array",
+            serde_json::Value::Object(_) => "object",
+        };
+
+        let mut example = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        if example.chars().count() > Self::MAX_EXAMPLE_LEN {
+            example = example
+                .chars()
+                .take(Self::MAX_EXAMPLE_LEN)
+                .collect::<String>()
+                + "…";
+        }
+
+        Self {
+            name: name.into(),
+            field_type: field_type.to_string(),
+            example: example.replace('|', "\\|").replace('\n', " "),
+        }
+    }
+}
+
+/// A link to a heading of the requirements document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementLink {
+    /// Heading text
+    pub title: String,
+
+    /// Markdown anchor of the heading (without `#`)
+    pub anchor: String,
+}
+
+impl RequirementLink {
+    /// Collects links to the level 2 and 3 headings of a requirements document
+    pub fn from_markdown(markdown: &str) -> Vec<Self> {
+        let mut in_code_block = false;
+        markdown
+            .lines()
+            .filter_map(|line| {
+                if line.trim_start().starts_with("```") {
+                    in_code_block = !in_code_block;
+                    return None;
+                }
+                if in_code_block {
+                    return None;
+                }
+                let title = line
+                    .strip_prefix("### ")
+                    .or_else(|| line.strip_prefix("## "))?
+                    .trim();
+                Some(Self {
+                    title: title.to_string(),
+                    anchor: heading_anchor(title),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Converts a heading into a GitHub-style Markdown anchor
+pub fn heading_anchor(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Renders the requirements traceability list of a design document
+fn render_requirement_links(links: &[RequirementLink]) -> String {
+    if links.is_empty() {
+        return "<!-- 要件ドキュメントの各セクションへのリンク / Links to requirements sections -->\n"
+            .to_string();
+    }
+
+    let mut output = String::new();
+    for link in links {
+        writeln!(
+            output,
+            "- [{}]({}#{})",
+            link.title,
+            SpecDocumentType::Requirements.file_name(),
+            link.anchor
+        )
+        .unwrap();
+    }
+    output
+}
+
+/// Renders the data model table of a design document
+fn render_data_model(fields: &[DataModelField]) -> String {
+    let mut output = String::from("| フィールド / Field | 型 / Type | 例 / Example |\n");
+    output.push_str("|--------------------|-----------|--------------|\n");
+
+    if fields.is_empty() {
+        output.push_str("| field_name | string | - |\n");
+    }
+    for field in fields {
+        writeln!(
+            output,
+            "| {} | {} | {} |",
+            field.name, field.field_type, field.example
+        )
+        .unwrap();
+    }
+    output
+}
+
 impl Default for TemplateEngine {
     fn default() -> Self {
         Self::new()
@@ -426,4 +609,40 @@ mod tests {
         assert!(content.contains("Test TestProject"));
         assert!(content.contains("Description for TestProject"));
     }
+
+    #[test]
+    fn test_render_custom_template() {
+        let mut engine = TemplateEngine::new();
+        engine.set_variable("title".to_string(), "Auth".to_string());
+        engine.set_variable("author".to_string(), "alice".to_string());
+
+        let content = engine.render("# RFC: {{title}}\n\nBy {{ author }} on {{date}}, {{other}}");
+        assert!(content.starts_with("# RFC: Auth\n\nBy alice on "));
+        assert!(!content.contains("{{date}}"));
+        assert!(content.ends_with("{{other}}"));
+    }
+
+    #[test]
+    fn test_design_template_scaffolding() {
+        let requirements =
+            "# Req\n\n## 1. 概要 / Overview\n\n### 4.2 Feature List\n```\n## not a heading\n```\n";
+        let template = SpecTemplate::Design {
+            title: "Auth".to_string(),
+            requirements_summary: String::new(),
+            data_model: vec![DataModelField::from_value(
+                "sla_hours",
+                &serde_json::json!(4),
+            )],
+            requirement_links: RequirementLink::from_markdown(requirements),
+        };
+
+        let content = TemplateEngine::new().generate(&template);
+        assert!(content.contains("```mermaid\nflowchart LR"));
+        assert!(content.contains("sequenceDiagram"));
+        assert!(content.contains("erDiagram"));
+        assert!(content.contains("| sla_hours | number | 4 |"));
+        assert!(content.contains("- [1. 概要 / Overview](requirements.md#1-概要--overview)"));
+        assert!(content.contains("- [4.2 Feature List](requirements.md#42-feature-list)"));
+        assert!(!content.contains("not a heading"));
+    }
 }
@@ -0,0 +1,243 @@
+//! Files attached to tickets
+//!
+//! Attached files are copied to `attachments/<ticket-id>/`, next to the
+//! description spilled from oversized tickets. The names of the attached
+//! files are also kept in the ticket's [`ATTACHMENTS_KEY`] metadata, so
+//! exports carry references to them and attaching shows up in the audit log.
+//! The directory is removed when a deleted ticket is purged from the trash.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::FileStorage;
+use crate::core::{Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Metadata key listing the names of the attached files
+pub const ATTACHMENTS_KEY: &str = "attachments";
+
+/// File name of a spilled description, which cannot be attached over
+const RESERVED_NAME: &str = "description.md";
+
+/// File in a ticket's attachment directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Attachment {
+    /// Path relative to the ticket's attachment directory
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// Names of the files attached to `ticket`, from its metadata
+pub fn attachment_names(ticket: &Ticket) -> Vec<String> {
+    ticket
+        .metadata
+        .get(ATTACHMENTS_KEY)
+        .and_then(serde_json::Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Stores `names` in the attachments metadata of `ticket`
+fn set_attachment_names(ticket: &mut Ticket, names: &[String]) {
+    if names.is_empty() {
+        ticket.metadata.remove(ATTACHMENTS_KEY);
+    } else {
+        ticket
+            .metadata
+            .insert(ATTACHMENTS_KEY.to_string(), serde_json::json!(names));
+    }
+}
+
+/// Checks that `name` is a plain file name
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains(['/', '\\'])
+        || name == RESERVED_NAME
+    {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Invalid attachment name: '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+impl FileStorage {
+    /// Returns the path of an attached file
+    pub fn attachment_path(&self, id: &TicketId, name: &str) -> PathBuf {
+        self.attachments_dir(id).join(name)
+    }
+
+    /// Lists the files in a ticket's attachment directory, sorted by path
+    ///
+    /// Files excluded by `.vibe-ticket/ignore` are left out.
+    pub fn list_attachments(&self, id: &TicketId) -> Vec<Attachment> {
+        let dir = self.attachments_dir(id);
+        let ignore = self.ignore_rules();
+        let mut found = Vec::new();
+        let mut pending = vec![dir.clone()];
+        while let Some(current) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&current) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                let Ok(relative) = path.strip_prefix(&dir) else {
+                    continue;
+                };
+                let name = relative.to_string_lossy().replace('\\', "/");
+                if ignore.is_ignored(format!("attachments/{id}/{name}"), metadata.is_dir()) {
+                    continue;
+                }
+                if metadata.is_dir() {
+                    pending.push(path);
+                } else {
+                    found.push(Attachment {
+                        name,
+                        size: metadata.len(),
+                    });
+                }
+            }
+        }
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        found
+    }
+
+    /// Copies `source` into the ticket's attachment directory
+    ///
+    /// The file keeps its name unless `name` is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket does not exist or cannot be changed,
+    /// the name is taken or invalid, or the file cannot be copied
+    pub fn attach_file(
+        &self,
+        id: &TicketId,
+        source: &Path,
+        name: Option<&str>,
+    ) -> Result<Attachment> {
+        let name = name.map_or_else(
+            || {
+                source
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            },
+            str::to_string,
+        );
+        validate_name(&name)?;
+        if !source.is_file() {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "Not a file: {}",
+                source.display()
+            )));
+        }
+
+        let mut names = attachment_names(&self.load_ticket(id)?);
+        let target = self.attachment_path(id, &name);
+        if names.contains(&name) || target.exists() {
+            return Err(VibeTicketError::custom(format!(
+                "Ticket {} already has an attachment named '{name}'",
+                id.short()
+            )));
+        }
+
+        fs::create_dir_all(self.attachments_dir(id))
+            .context("Failed to create attachments directory")?;
+        let size = fs::copy(source, &target)
+            .with_context(|| format!("Failed to copy {}", source.display()))?;
+
+        names.push(name.clone());
+        names.sort();
+        if let Err(e) = self.update_ticket(id, |ticket| set_attachment_names(ticket, &names)) {
+            let _ = fs::remove_file(&target);
+            return Err(e);
+        }
+
+        Ok(Attachment { name, size })
+    }
+
+    /// Removes an attached file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket has no such attachment or cannot be
+    /// changed
+    pub fn detach_file(&self, id: &TicketId, name: &str) -> Result<()> {
+        validate_name(name)?;
+        let mut names = attachment_names(&self.load_ticket(id)?);
+        let path = self.attachment_path(id, name);
+        if !names.iter().any(|n| n == name) && !path.is_file() {
+            return Err(VibeTicketError::custom(format!(
+                "Ticket {} has no attachment named '{name}'",
+                id.short()
+            )));
+        }
+
+        names.retain(|n| n != name);
+        self.update_ticket(id, |ticket| set_attachment_names(ticket, &names))?;
+        if path.is_file() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_attach_and_detach() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path().join(".vibe-ticket"));
+        let ticket = Ticket::new("with-files", "With files");
+        storage.save(&ticket).unwrap();
+        let log = temp.path().join("build.log");
+        fs::write(&log, "error: boom\n").unwrap();
+
+        let attachment = storage.attach_file(&ticket.id, &log, None).unwrap();
+        assert_eq!(attachment.name, "build.log");
+        assert_eq!(attachment.size, 12);
+        storage
+            .attach_file(&ticket.id, &log, Some("second.log"))
+            .unwrap();
+        assert!(storage.attach_file(&ticket.id, &log, None).is_err());
+        assert!(
+            storage
+                .attach_file(&ticket.id, &log, Some("../escape.log"))
+                .is_err()
+        );
+
+        let fresh = FileStorage::new(temp.path().join(".vibe-ticket"));
+        let stored = fresh.load(&ticket.id).unwrap();
+        assert_eq!(attachment_names(&stored), vec!["build.log", "second.log"]);
+        assert_eq!(fresh.list_attachments(&ticket.id).len(), 2);
+
+        fresh.detach_file(&ticket.id, "build.log").unwrap();
+        assert!(!fresh.attachment_path(&ticket.id, "build.log").exists());
+        let fresh = FileStorage::new(temp.path().join(".vibe-ticket"));
+        assert_eq!(
+            attachment_names(&fresh.load(&ticket.id).unwrap()),
+            vec!["second.log"]
+        );
+        assert!(fresh.detach_file(&ticket.id, "build.log").is_err());
+    }
+}
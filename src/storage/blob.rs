@@ -0,0 +1,327 @@
+//! Blob store abstraction for ticket data
+//!
+//! `FileStorage` reads and writes its files through a [`BlobStore`], so the
+//! place where ticket data lives can be swapped without touching the ticket
+//! logic. Keys are relative, `/`-separated paths such as
+//! `tickets/<id>.yaml`, which map naturally onto file systems as well as
+//! object stores (S3) and `WebDAV` collections.
+//!
+//! # Sync interface
+//!
+//! The trait is synchronous: the CLI and the storage layer are synchronous,
+//! and making every storage call async would ripple through all handlers.
+//! Remote backends are expected to wrap an async client and bridge by
+//! blocking on it internally (for example with a dedicated current-thread
+//! Tokio runtime). Async callers such as the MCP server should keep calling
+//! storage from blocking contexts (`tokio::task::spawn_blocking`) so that
+//! such a backend never blocks a runtime worker.
+//!
+//! Locking is not part of the trait. `FileStorage` keeps taking its
+//! lock files in the local `.vibe-ticket` directory; a remote backend that
+//! is shared between machines will need its own locking scheme.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Key-value storage for the files of a project
+pub trait BlobStore: Send + Sync {
+    /// Reads the blob stored under `key`, or `None` if there is none
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Stores `data` under `key`, replacing any existing blob
+    fn put(&self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// Removes the blob stored under `key`
+    ///
+    /// Returns `true` if a blob was removed.
+    fn delete(&self, key: &str) -> Result<bool>;
+
+    /// Lists the keys of the blobs directly under the `prefix` directory, sorted
+    ///
+    /// `prefix` names a directory without trailing slash (e.g., `tickets`).
+    /// Keys of nested directories are not included.
+    fn list(&self, prefix: &str) -> Result<Vec<String>>;
+
+    /// Returns true if a blob is stored under `key`
+    fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.get(key)?.is_some())
+    }
+
+    /// Time the blob under `key` was last written, if the store tracks it
+    ///
+    /// Used to tell whether cached data about a blob is still current;
+    /// `None` means the blob must be read again. Stores that do not track
+    /// write times return `None`.
+    fn modified(&self, _key: &str) -> Result<Option<SystemTime>> {
+        Ok(None)
+    }
+
+    /// Moves the blob stored under `from` to `to`, replacing any blob there
+    ///
+    /// Stores that can replace a blob atomically, such as [`LocalFs`], do
+    /// so; the default copies the blob and deletes the original.
+    fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let data = self
+            .get(from)?
+            .ok_or_else(|| VibeTicketError::custom(format!("Blob '{from}' does not exist")))?;
+        self.put(to, &data)?;
+        self.delete(from)?;
+        Ok(())
+    }
+
+    /// Reads the blob stored under `key` as UTF-8 text
+    fn get_string(&self, key: &str) -> Result<Option<String>> {
+        self.get(key)?
+            .map(|data| {
+                String::from_utf8(data).map_err(|e| {
+                    VibeTicketError::custom(format!("Blob '{key}' is not valid UTF-8: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Writes out data the store holds back (see [`super::Coalescing`])
+    ///
+    /// Stores that write through do nothing.
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Blob store on the local file system, the default backend
+#[derive(Debug, Clone)]
+pub struct LocalFs {
+    /// Directory the keys are relative to
+    root: PathBuf,
+}
+
+impl LocalFs {
+    /// Creates a store rooted at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the file path of a key
+    pub fn path(&self, key: &str) -> PathBuf {
+        key.split('/')
+            .fold(self.root.clone(), |path, part| path.join(part))
+    }
+}
+
+impl BlobStore for LocalFs {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path(key);
+        match fs::read(&path) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        fs::write(&path, data).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let (from, to) = (self.path(from), self.path(to));
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        fs::rename(&from, &to)
+            .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        let path = self.path(key);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {}", path.display())),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.path(prefix);
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", dir.display()));
+            },
+        };
+
+        let mut keys = Vec::new();
+        for entry in entries {
+            let entry = entry.context("Failed to read directory entry")?;
+            if !entry.file_type().is_ok_and(|t| t.is_file()) {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                keys.push(join_key(prefix, name));
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.path(key).is_file())
+    }
+
+    fn modified(&self, key: &str) -> Result<Option<SystemTime>> {
+        let path = self.path(key);
+        match fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => Ok(Some(modified)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+}
+
+/// Blob store kept in memory, for tests and ephemeral projects
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    blobs: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` on the stored blobs
+    fn with_blobs<T>(&self, f: impl FnOnce(&mut BTreeMap<String, Vec<u8>>) -> T) -> T {
+        // A panic while holding the lock cannot leave a map half-updated
+        let mut blobs = self.blobs.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut blobs)
+    }
+}
+
+impl BlobStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.with_blobs(|blobs| blobs.get(key).cloned()))
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.with_blobs(|blobs| blobs.insert(key.to_string(), data.to_vec()));
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        Ok(self.with_blobs(|blobs| blobs.remove(key).is_some()))
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = join_key(prefix, "");
+        Ok(self.with_blobs(|blobs| {
+            blobs
+                .keys()
+                .filter(|key| {
+                    key.strip_prefix(&dir)
+                        .is_some_and(|rest| !rest.contains('/'))
+                })
+                .cloned()
+                .collect()
+        }))
+    }
+
+    fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.with_blobs(|blobs| blobs.contains_key(key)))
+    }
+}
+
+/// Joins a directory key and a name
+pub(super) fn join_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}/{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Behavior every `BlobStore` implementation must share
+    fn check_conformance(store: &dyn BlobStore) {
+        // Missing keys
+        assert_eq!(store.get("tickets/missing.yaml").unwrap(), None);
+        assert!(!store.exists("tickets/missing.yaml").unwrap());
+        assert!(!store.delete("tickets/missing.yaml").unwrap());
+        assert!(store.list("tickets").unwrap().is_empty());
+
+        // Round trip and overwrite
+        store.put("tickets/b.yaml", b"first").unwrap();
+        store.put("tickets/b.yaml", b"second").unwrap();
+        assert_eq!(store.get("tickets/b.yaml").unwrap().unwrap(), b"second");
+        assert_eq!(
+            store.get_string("tickets/b.yaml").unwrap().as_deref(),
+            Some("second")
+        );
+        assert!(store.exists("tickets/b.yaml").unwrap());
+
+        // Listing is sorted and does not descend into nested directories
+        store.put("tickets/a.yaml", b"").unwrap();
+        store.put("tickets/nested/c.yaml", b"").unwrap();
+        store.put("tickets-old/d.yaml", b"").unwrap();
+        store.put("state.yaml", b"").unwrap();
+        assert_eq!(
+            store.list("tickets").unwrap(),
+            vec!["tickets/a.yaml", "tickets/b.yaml"]
+        );
+        assert_eq!(store.list("").unwrap(), vec!["state.yaml"]);
+
+        // Invalid UTF-8 is reported rather than replaced
+        store.put("binary", &[0xff, 0xfe]).unwrap();
+        assert!(store.get_string("binary").is_err());
+
+        // Deletion
+        assert!(store.delete("tickets/b.yaml").unwrap());
+        assert_eq!(store.get("tickets/b.yaml").unwrap(), None);
+        assert_eq!(store.list("tickets").unwrap(), vec!["tickets/a.yaml"]);
+
+        // Renaming replaces the target
+        store.put("staged/a.yaml", b"staged").unwrap();
+        store.rename("staged/a.yaml", "tickets/a.yaml").unwrap();
+        assert_eq!(store.get("tickets/a.yaml").unwrap().unwrap(), b"staged");
+        assert!(!store.exists("staged/a.yaml").unwrap());
+        assert!(store.rename("staged/a.yaml", "tickets/a.yaml").is_err());
+    }
+
+    #[test]
+    fn test_local_fs_conformance() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LocalFs::new(temp_dir.path());
+        check_conformance(&store);
+        assert!(temp_dir.path().join("tickets").join("a.yaml").is_file());
+    }
+
+    #[test]
+    fn test_memory_store_conformance() {
+        check_conformance(&MemoryStore::new());
+    }
+
+    #[test]
+    fn test_coalescing_conformance() {
+        let store = super::super::Coalescing::new(
+            std::sync::Arc::new(MemoryStore::new()),
+            std::time::Duration::from_secs(60),
+        );
+        check_conformance(&store);
+    }
+}
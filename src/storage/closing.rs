@@ -0,0 +1,89 @@
+//! Effects of closing a ticket
+//!
+//! Tickets are closed by `close`, `edit --status done`, the MCP tools, the
+//! aging policy and transactions alike, and all of them write through
+//! `FileStorage`. Once a write that closes a ticket is stored, the storage
+//! runs [`FileStorage::closed`]: it warns when the ticket is an epic whose
+//! children are still open, since closing an epic does not close them, and
+//! completes the tasks phase of the spec the ticket was exported from once
+//! all the spec's exported tickets are closed (see [`link::roll_up`]).
+//!
+//! The ticket is closed by then, so these effects only warn on failure.
+
+use super::FileStorage;
+use crate::core::{Ticket, hierarchy};
+use crate::specs::{SpecManager, link};
+
+impl FileStorage {
+    /// Runs the effects of closing `ticket`, once it is stored
+    pub(super) fn closed(&self, ticket: &Ticket) {
+        let tickets = match self.load_all_tickets() {
+            Ok(tickets) => tickets,
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to load tickets after closing {}: {e}",
+                    ticket.slug
+                );
+                return;
+            },
+        };
+
+        let open: Vec<&str> = hierarchy::open_descendants(ticket, &tickets)
+            .into_iter()
+            .map(|t| t.slug.as_str())
+            .collect();
+        if !open.is_empty() {
+            eprintln!(
+                "Warning: Epic '{}' still has {} open child ticket(s): {}",
+                ticket.slug,
+                open.len(),
+                open.join(", ")
+            );
+        }
+
+        let specs = SpecManager::new(self.get_path("specs"));
+        if let Err(e) = link::roll_up(&specs, ticket, || Ok(tickets)) {
+            eprintln!("Warning: Failed to update the spec of {}: {e}", ticket.slug);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::Specification;
+    use crate::specs::estimate::SPEC_KEY;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_any_close_completes_the_spec() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let specs = SpecManager::new(temp.path().join("specs"));
+        let mut spec = Specification::new("Auth".to_string(), String::new(), None, Vec::new());
+        let mut tickets = vec![Ticket::new("a", "A"), Ticket::new("b", "B")];
+        for ticket in &mut tickets {
+            ticket
+                .metadata
+                .insert(SPEC_KEY.to_string(), spec.metadata.id.clone().into());
+            spec.metadata
+                .task_tickets
+                .insert(ticket.slug.clone(), ticket.id.to_string());
+            storage.save(ticket).unwrap();
+        }
+        specs.save_metadata(&spec.metadata).unwrap();
+
+        // Closed by a plain save, as `edit --status done` does
+        tickets[0].close();
+        storage.save(&tickets[0]).unwrap();
+        assert!(link::completed_spec(&specs, &tickets[0]).is_none());
+
+        // Closed by an update, as the aging policy does
+        let closed = storage
+            .update_ticket(&tickets[1].id, Ticket::close)
+            .unwrap();
+        let completed = link::completed_spec(&specs, &closed).unwrap();
+        assert_eq!(completed.id, spec.metadata.id);
+    }
+}
@@ -0,0 +1,425 @@
+//! Coalescing of rapid successive writes
+//!
+//! Task toggles and edits rewrite the whole ticket file, and MCP agents often
+//! make many of them in a row. [`Coalescing`] wraps a [`BlobStore`] and holds
+//! each write for a short window; writes of the same key within the window
+//! replace the pending data, so a burst of saves costs one disk write.
+//!
+//! Reads, listings and deletions through the wrapper see pending writes, so
+//! the process that wrote keeps reading its own writes. Other processes only
+//! see a write once it is flushed, one window after the first write of the
+//! burst at the latest, which is why coalescing is meant for long-running
+//! processes such as the MCP server. Pending writes are also flushed by
+//! [`BlobStore::flush`] and when the store is dropped.
+//!
+//! A pending write is only written out if the stored data is still what it
+//! was when the write was first held back. If another process wrote the key
+//! meanwhile, its write is kept and the pending one is discarded with a
+//! warning. [`Coalescing::locked`] holds back only the keys under a prefix,
+//! such as the ticket files, and takes the lock of each file for the check
+//! and the write; logs rewritten to append, such as the audit logs, the
+//! journal and the index, must not be held back, as two processes appending
+//! would always conflict.
+
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::blob::{BlobStore, join_key};
+use super::lock::{FileLock, LockSettings};
+use crate::error::{Result, VibeTicketError};
+
+/// Blob store holding back writes for a short window
+pub struct Coalescing {
+    shared: Arc<Shared>,
+    /// Thread writing out pending writes once their window has passed
+    flusher: Option<JoinHandle<()>>,
+}
+
+/// State shared with the flusher thread
+struct Shared {
+    inner: Arc<dyn BlobStore>,
+    window: Duration,
+    /// Keys held back and the locks guarding them; all keys if `None`
+    scope: Option<Scope>,
+    pending: Mutex<Pending>,
+    /// Signalled when a write is added or the store is dropped
+    changed: Condvar,
+}
+
+/// Keys held back by [`Coalescing::locked`]
+struct Scope {
+    /// Directory the keys are held back in, ending with `/`
+    dir: String,
+    /// Directory the lock files of the keys are relative to
+    base_dir: PathBuf,
+    lock_settings: LockSettings,
+}
+
+#[derive(Default)]
+struct Pending {
+    /// Data not yet written to the inner store, by key
+    writes: BTreeMap<String, Write>,
+    /// Whether the store was dropped
+    closed: bool,
+}
+
+struct Write {
+    data: Vec<u8>,
+    /// Stored data when the write was first held back
+    base: Option<Vec<u8>>,
+    /// Time of the first write of the burst
+    since: Instant,
+}
+
+impl Coalescing {
+    /// Wraps `inner`, writing to it at most once per `window` and key
+    pub fn new(inner: Arc<dyn BlobStore>, window: Duration) -> Self {
+        Self::build(inner, window, None)
+    }
+
+    /// Wraps `inner`, holding back only the writes of keys directly under
+    /// `prefix`
+    ///
+    /// Pending writes are written out under the lock of the file at the key
+    /// in `base_dir`, the lock `FileStorage` takes to write the file.
+    pub fn locked(
+        inner: Arc<dyn BlobStore>,
+        window: Duration,
+        prefix: &str,
+        base_dir: impl Into<PathBuf>,
+        lock_settings: LockSettings,
+    ) -> Self {
+        let scope = Scope {
+            dir: join_key(prefix, ""),
+            base_dir: base_dir.into(),
+            lock_settings,
+        };
+        Self::build(inner, window, Some(scope))
+    }
+
+    fn build(inner: Arc<dyn BlobStore>, window: Duration, scope: Option<Scope>) -> Self {
+        let shared = Arc::new(Shared {
+            inner,
+            window,
+            scope,
+            pending: Mutex::new(Pending::default()),
+            changed: Condvar::new(),
+        });
+        let flusher = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || shared.run())
+        };
+        Self {
+            shared,
+            flusher: Some(flusher),
+        }
+    }
+
+    /// Number of writes not yet written to the inner store
+    pub fn pending(&self) -> usize {
+        self.shared.lock().writes.len()
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Pending> {
+        // Writes are only removed once stored, so the map stays consistent
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether writes of `key` are held back
+    fn holds(&self, key: &str) -> bool {
+        self.scope.as_ref().is_none_or(|scope| {
+            key.strip_prefix(&scope.dir)
+                .is_some_and(|rest| !rest.contains('/'))
+        })
+    }
+
+    /// Loop of the flusher thread
+    fn run(&self) {
+        loop {
+            let pending = self.lock();
+            if pending.closed {
+                return;
+            }
+            let now = Instant::now();
+            let next = pending
+                .writes
+                .values()
+                .map(|write| write.since + self.window)
+                .min();
+            match next {
+                None => drop(self.changed.wait(pending)),
+                Some(due) if due > now => drop(self.changed.wait_timeout(pending, due - now)),
+                Some(_) => {
+                    let due: Vec<String> = pending
+                        .writes
+                        .iter()
+                        .filter(|(_, write)| write.since + self.window <= now)
+                        .map(|(key, _)| key.clone())
+                        .collect();
+                    // Writers take the file lock before the pending writes
+                    drop(pending);
+                    self.write_due(&due);
+                },
+            }
+        }
+    }
+
+    /// Writes out the writes whose window has passed
+    ///
+    /// A failed write stays pending and is retried one window later.
+    fn write_due(&self, due: &[String]) {
+        for key in due {
+            if let Err(e) = self.write_out(key) {
+                eprintln!("Warning: Failed to write {key}: {e}");
+            }
+        }
+    }
+
+    /// Writes out the pending write of `key`, if any
+    ///
+    /// The write is discarded if the stored data changed since it was first
+    /// held back. A failed write stays pending.
+    fn write_out(&self, key: &str) -> Result<()> {
+        let _lock = self.lock_key(key)?;
+        let mut pending = self.lock();
+        let Some(write) = pending.writes.remove(key) else {
+            return Ok(());
+        };
+        let result = self.inner.get(key).and_then(|current| {
+            if current == write.base {
+                self.inner.put(key, &write.data)
+            } else {
+                eprintln!(
+                    "Warning: Discarded a pending write of {key}, which another process changed meanwhile"
+                );
+                Ok(())
+            }
+        });
+        if result.is_err() {
+            pending.writes.insert(
+                key.to_string(),
+                Write {
+                    since: Instant::now(),
+                    ..write
+                },
+            );
+        }
+        result
+    }
+
+    /// Takes the lock of the file at `key` if the store is [`Coalescing::locked`]
+    fn lock_key(&self, key: &str) -> Result<Option<FileLock>> {
+        let Some(scope) = &self.scope else {
+            return Ok(None);
+        };
+        FileLock::acquire_with(
+            &scope.base_dir.join(key),
+            Some("flush".to_string()),
+            &scope.lock_settings,
+        )
+        .map(Some)
+        .map_err(|e| VibeTicketError::custom(format!("Failed to acquire lock for {key}: {e}")))
+    }
+
+    /// Stops the flusher thread and writes out all pending writes
+    fn close(&self) -> Result<()> {
+        self.lock().closed = true;
+        self.write_all()
+    }
+
+    /// Writes out all pending writes
+    fn write_all(&self) -> Result<()> {
+        let keys: Vec<String> = self.lock().writes.keys().cloned().collect();
+        for key in keys {
+            self.write_out(&key)?;
+        }
+        Ok(())
+    }
+}
+
+impl BlobStore for Coalescing {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let data = self
+            .shared
+            .lock()
+            .writes
+            .get(key)
+            .map(|write| write.data.clone());
+        data.map_or_else(|| self.shared.inner.get(key), |data| Ok(Some(data)))
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        if !self.shared.holds(key) {
+            return self.shared.inner.put(key, data);
+        }
+        let added = match self.shared.lock().writes.entry(key.to_string()) {
+            Entry::Occupied(mut write) => {
+                data.clone_into(&mut write.get_mut().data);
+                false
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(Write {
+                    data: data.to_vec(),
+                    base: self.shared.inner.get(key)?,
+                    since: Instant::now(),
+                });
+                true
+            },
+        };
+        if added {
+            self.shared.changed.notify_one();
+        }
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        let was_pending = self.shared.lock().writes.remove(key).is_some();
+        Ok(self.shared.inner.delete(key)? || was_pending)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = join_key(prefix, "");
+        // Pending keys first, so that a key written out meanwhile is listed
+        let pending: Vec<String> = self
+            .shared
+            .lock()
+            .writes
+            .keys()
+            .filter(|key| {
+                key.strip_prefix(&dir)
+                    .is_some_and(|rest| !rest.contains('/'))
+            })
+            .cloned()
+            .collect();
+        let mut keys = self.shared.inner.list(prefix)?;
+        keys.extend(pending);
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+
+    fn exists(&self, key: &str) -> Result<bool> {
+        let pending = self.shared.lock().writes.contains_key(key);
+        Ok(pending || self.shared.inner.exists(key)?)
+    }
+
+    fn modified(&self, key: &str) -> Result<Option<SystemTime>> {
+        // The inner store has not seen a pending write yet
+        if self.shared.lock().writes.contains_key(key) {
+            return Ok(None);
+        }
+        self.shared.inner.modified(key)
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.shared.write_all()
+    }
+}
+
+impl Drop for Coalescing {
+    fn drop(&mut self) {
+        if let Err(e) = self.shared.close() {
+            eprintln!("Warning: Failed to write pending changes: {e}");
+        }
+        self.shared.changed.notify_one();
+        if let Some(flusher) = self.flusher.take() {
+            let _ = flusher.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStore;
+
+    #[test]
+    fn test_coalesces_writes() {
+        let inner = Arc::new(MemoryStore::new());
+        let store = Coalescing::new(inner.clone(), Duration::from_secs(60));
+
+        store.put("tickets/a.yaml", b"1").unwrap();
+        store.put("tickets/a.yaml", b"2").unwrap();
+        store.put("state.yaml", b"s").unwrap();
+        assert_eq!(store.pending(), 2);
+        assert_eq!(inner.get("tickets/a.yaml").unwrap(), None);
+
+        // Pending writes are visible through the store
+        assert_eq!(store.get("tickets/a.yaml").unwrap().unwrap(), b"2");
+        assert!(store.exists("tickets/a.yaml").unwrap());
+        assert_eq!(store.list("tickets").unwrap(), vec!["tickets/a.yaml"]);
+
+        store.flush().unwrap();
+        assert_eq!(store.pending(), 0);
+        assert_eq!(inner.get("tickets/a.yaml").unwrap().unwrap(), b"2");
+
+        store.put("tickets/b.yaml", b"b").unwrap();
+        assert!(store.delete("tickets/b.yaml").unwrap());
+        assert!(!store.exists("tickets/b.yaml").unwrap());
+
+        store.put("tickets/c.yaml", b"c").unwrap();
+        drop(store);
+        assert_eq!(inner.get("tickets/c.yaml").unwrap().unwrap(), b"c");
+        assert_eq!(inner.get("tickets/b.yaml").unwrap(), None);
+    }
+
+    #[test]
+    fn test_keeps_writes_of_other_processes() {
+        let inner = Arc::new(MemoryStore::new());
+        inner.put("tickets/a.yaml", b"0").unwrap();
+        let store = Coalescing::new(inner.clone(), Duration::from_secs(60));
+
+        store.put("tickets/a.yaml", b"1").unwrap();
+        store.put("tickets/b.yaml", b"1").unwrap();
+        // Another process writes the key while the write is held back
+        inner.put("tickets/a.yaml", b"other").unwrap();
+        store.flush().unwrap();
+
+        assert_eq!(inner.get("tickets/a.yaml").unwrap().unwrap(), b"other");
+        assert_eq!(inner.get("tickets/b.yaml").unwrap().unwrap(), b"1");
+        assert_eq!(store.pending(), 0);
+    }
+
+    #[test]
+    fn test_locked_holds_back_only_prefix() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let inner = Arc::new(MemoryStore::new());
+        let store = Coalescing::locked(
+            inner.clone(),
+            Duration::from_secs(60),
+            "tickets",
+            temp_dir.path(),
+            LockSettings::default(),
+        );
+
+        store.put("tickets/a.yaml", b"1").unwrap();
+        store.put("history/a.jsonl", b"h").unwrap();
+        store.put("index.json", b"i").unwrap();
+        assert_eq!(store.pending(), 1);
+        assert_eq!(inner.get("history/a.jsonl").unwrap().unwrap(), b"h");
+        assert_eq!(inner.get("index.json").unwrap().unwrap(), b"i");
+
+        std::fs::create_dir_all(temp_dir.path().join("tickets")).unwrap();
+        store.flush().unwrap();
+        assert_eq!(inner.get("tickets/a.yaml").unwrap().unwrap(), b"1");
+    }
+
+    #[test]
+    fn test_flushes_after_window() {
+        let inner = Arc::new(MemoryStore::new());
+        let store = Coalescing::new(inner.clone(), Duration::from_millis(20));
+
+        store.put("tickets/a.yaml", b"1").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while store.pending() > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(inner.get("tickets/a.yaml").unwrap().unwrap(), b"1");
+    }
+}
@@ -11,10 +11,12 @@ mod tests {
     fn create_test_ticket(title: &str) -> Ticket {
         Ticket {
             id: Uuid::new_v4().into(),
+            short_id: None,
             slug: format!("test-{}", title.to_lowercase().replace(' ', "-")),
             title: title.to_string(),
             description: format!("Description for {}", title),
             priority: Priority::Medium,
+            severity: None,
             status: Status::Todo,
             tags: vec!["test".to_string()],
             created_at: chrono::Utc::now(),
@@ -22,6 +24,16 @@ mod tests {
             closed_at: None,
             assignee: None,
             tasks: vec![],
+            work_log: Vec::new(),
+            comments: Vec::new(),
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: Vec::new(),
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: std::collections::HashMap::new(),
         }
     }
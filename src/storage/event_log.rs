@@ -0,0 +1,288 @@
+//! Append-only log of storage changes shared between processes
+//!
+//! Every ticket write or deletion and every change of the active ticket
+//! appends one JSON line to `.vibe-ticket/events.log`. An append is a
+//! single write to the file opened in append mode, which the OS keeps whole
+//! for lines this short, so writers never take a lock for it. Long-running
+//! processes call `FileStorage::poll_events` to learn about changes made by
+//! other processes, including CLI invocations in other worktrees sharing
+//! the `.vibe-ticket` directory, reading only what was appended since
+//! their last poll.
+//!
+//! The first line of a log names it with a random ID. Once the log grows
+//! beyond [`EVENT_LOG_LIMIT`], the next writer starts a new one; a poller
+//! whose cursor belongs to another log is told to reload everything (see
+//! [`EventPoll::reset`]). The log is local state and ignored by git.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom, Write as IoWrite};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::FileStorage;
+use crate::core::TicketId;
+use crate::error::{ErrorContext, Result};
+
+/// File name of the event log in the `.vibe-ticket` directory
+pub const EVENT_LOG_FILE: &str = "events.log";
+
+/// Size in bytes beyond which the event log is started anew
+pub const EVENT_LOG_LIMIT: u64 = 1024 * 1024;
+
+/// What changed in the storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEventKind {
+    /// A ticket was created or changed
+    Saved,
+    /// A ticket was deleted
+    Deleted,
+    /// The active ticket was set or cleared
+    ActiveChanged,
+}
+
+/// One line of the event log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageEvent {
+    /// When the change was made
+    pub at: DateTime<Utc>,
+
+    /// Process that made the change
+    pub pid: u32,
+
+    /// What changed
+    pub kind: StorageEventKind,
+
+    /// The changed ticket, if the change concerns one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket_id: Option<TicketId>,
+}
+
+impl StorageEvent {
+    /// Whether another process made the change
+    pub fn is_foreign(&self) -> bool {
+        self.pid != std::process::id()
+    }
+}
+
+/// Position in the event log up to which events were read
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    /// ID of the log, empty before the first poll
+    log: String,
+
+    /// Byte offset of the next unread event
+    offset: u64,
+}
+
+/// Result of [`FileStorage::poll_events`]
+#[derive(Debug, Clone, Default)]
+pub struct EventPoll {
+    /// Events appended since the cursor, oldest first
+    pub events: Vec<StorageEvent>,
+
+    /// Cursor to poll from next time
+    pub cursor: EventCursor,
+
+    /// Whether the log was started anew since the cursor, so that changes
+    /// may have been missed and everything should be reloaded
+    pub reset: bool,
+}
+
+/// First line of an event log
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    log: String,
+}
+
+impl FileStorage {
+    /// Appends a change to the event log
+    ///
+    /// The change has already been saved, so failures are ignored: pollers
+    /// then miss it like a change made by an older version.
+    pub(super) fn log_event(&self, kind: StorageEventKind, ticket_id: Option<&TicketId>) {
+        let event = StorageEvent {
+            at: Utc::now(),
+            pid: std::process::id(),
+            kind,
+            ticket_id: ticket_id.cloned(),
+        };
+        let _ = self.append_event(&event);
+    }
+
+    fn append_event(&self, event: &StorageEvent) -> Result<()> {
+        let path = self.get_path(EVENT_LOG_FILE);
+        if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > EVENT_LOG_LIMIT) {
+            let _ = fs::remove_file(&path);
+        }
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        let mut file = match OpenOptions::new().append(true).open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => create_log(&path)?,
+            Err(e) => return Err(e).context("Failed to open the event log"),
+        };
+        file.write_all(&line)
+            .context("Failed to append to the event log")
+    }
+
+    /// Cursor at the end of the event log, to poll for later changes only
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log exists but cannot be read
+    pub fn event_cursor(&self) -> Result<EventCursor> {
+        let path = self.get_path(EVENT_LOG_FILE);
+        let Some((mut reader, log, _)) = open_log(&path)? else {
+            return Ok(EventCursor::default());
+        };
+        let offset = reader
+            .seek(SeekFrom::End(0))
+            .context("Failed to read the event log")?;
+        Ok(EventCursor { log, offset })
+    }
+
+    /// Reads the events appended to the event log since `since`
+    ///
+    /// Only complete lines are read, so an event being appended is returned
+    /// by the next poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log exists but cannot be read
+    pub fn poll_events(&self, since: &EventCursor) -> Result<EventPoll> {
+        let path = self.get_path(EVENT_LOG_FILE);
+        let Some((mut reader, log, start)) = open_log(&path)? else {
+            return Ok(EventPoll {
+                reset: !since.log.is_empty(),
+                ..EventPoll::default()
+            });
+        };
+        let (offset, reset) = if since.log == log && since.offset >= start {
+            (since.offset, false)
+        } else {
+            (start, !since.log.is_empty())
+        };
+
+        reader
+            .seek(SeekFrom::Start(offset))
+            .context("Failed to read the event log")?;
+        let mut content = Vec::new();
+        reader
+            .read_to_end(&mut content)
+            .context("Failed to read the event log")?;
+        let complete = content
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |end| end + 1);
+        let events = content[..complete]
+            .split(|&byte| byte == b'\n')
+            .filter_map(|line| serde_json::from_slice(line).ok())
+            .collect();
+
+        Ok(EventPoll {
+            events,
+            cursor: EventCursor {
+                log,
+                offset: offset + complete as u64,
+            },
+            reset,
+        })
+    }
+}
+
+/// Creates the event log with its header, or opens the one another
+/// process created meanwhile
+fn create_log(path: &Path) -> Result<File> {
+    // The header is written before the log appears, so that no event can
+    // come before it
+    let header = Header {
+        log: uuid::Uuid::new_v4().to_string(),
+    };
+    let staged = path.with_extension(format!("{}.tmp", header.log));
+    let mut line = serde_json::to_vec(&header)?;
+    line.push(b'\n');
+    fs::write(&staged, &line).context("Failed to create the event log")?;
+    let linked = fs::hard_link(&staged, path);
+    let _ = fs::remove_file(&staged);
+    match linked {
+        Ok(()) => {},
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {},
+        Err(e) => return Err(e).context("Failed to create the event log"),
+    }
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .context("Failed to open the event log")
+}
+
+/// Opens the event log, returning a reader, the ID of the log and the
+/// offset of its first event, or `None` if there is no log
+fn open_log(path: &Path) -> Result<Option<(BufReader<File>, String, u64)>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context("Failed to open the event log"),
+    };
+    let mut reader = BufReader::new(file);
+    let mut header = String::new();
+    let start = reader
+        .read_line(&mut header)
+        .context("Failed to read the event log")?;
+    let log = serde_json::from_str::<Header>(&header)
+        .map(|header| header.log)
+        .unwrap_or_default();
+    Ok(Some((reader, log, start as u64)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_poll_events_since_cursor() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let start = storage.event_cursor().unwrap();
+        assert_eq!(start, EventCursor::default());
+
+        let ticket = Ticket::new("logged", "Logged");
+        storage.save(&ticket).unwrap();
+        storage.set_active_ticket(&ticket.id).unwrap();
+
+        let poll = storage.poll_events(&start).unwrap();
+        assert!(!poll.reset);
+        let kinds: Vec<_> = poll.events.iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            [StorageEventKind::Saved, StorageEventKind::ActiveChanged]
+        );
+        assert_eq!(poll.events[0].ticket_id.as_ref(), Some(&ticket.id));
+        assert!(!poll.events[0].is_foreign());
+
+        // A half-written line waits for the next poll
+        let path = temp.path().join(EVENT_LOG_FILE);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"at\":").unwrap();
+        let poll = storage.poll_events(&poll.cursor).unwrap();
+        assert!(poll.events.is_empty());
+        assert_eq!(
+            poll.cursor,
+            storage.poll_events(&poll.cursor).unwrap().cursor
+        );
+
+        // A new log resets pollers of the old one
+        fs::remove_file(&path).unwrap();
+        storage.delete(&ticket.id).unwrap();
+        let after = storage.poll_events(&poll.cursor).unwrap();
+        assert!(after.reset);
+        assert_eq!(after.events[0].kind, StorageEventKind::Deleted);
+        assert_eq!(after.cursor, storage.event_cursor().unwrap());
+    }
+}
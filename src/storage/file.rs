@@ -1,36 +1,263 @@
 use std::fs;
-use std::path::PathBuf;
-use std::sync::Arc;
-
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use super::blob::{BlobStore, LocalFs};
+use super::event_log::StorageEventKind;
+use super::ignore::IgnoreRules;
+use super::integrity::TicketIndex;
+use super::lock::{FileLock, HeldLock, LockSettings};
 use crate::cache::TicketCache;
-use crate::core::{Ticket, TicketId};
+use crate::config::{
+    Config, Identity, UserConfig, default_short_id_length, default_trash_retention_days,
+};
+use crate::core::audit::{AuditEvent, Change};
+use crate::core::closing::{self, CloseRequirement};
+use crate::core::{Status, Ticket, TicketId, dependencies};
 
 use crate::error::{ErrorContext, Result, VibeTicketError};
 
+/// Blob store key of the tickets directory
+pub(super) const TICKETS_KEY: &str = "tickets";
+
+/// Blob store key of the active ticket file
+const ACTIVE_TICKET_KEY: &str = "active_ticket";
+
+/// Metadata keys that archiving sets on closed tickets
+const ARCHIVE_KEYS: [&str; 2] = ["archived", "archived_at"];
+
+/// Blob store key of the project state file
+const STATE_KEY: &str = "state.yaml";
+
+/// A ticket save that passed its checks, ready to be stored
+pub(super) struct PreparedWrite {
+    /// The ticket as stored, with its short ID
+    pub(super) ticket: Ticket,
+    /// The stored version, `Some(Err)` if it no longer parses
+    before: Option<std::result::Result<Ticket, serde_yaml::Error>>,
+    /// Whether the ticket differs from the stored version
+    changed: bool,
+    /// Close requirements waived by the save
+    waived: Vec<Change>,
+    /// Whether the save closes the ticket
+    pub(super) closes: bool,
+    /// Serialized ticket
+    pub(super) yaml: String,
+}
+
 /// File-based storage implementation for tickets
 ///
 /// This implementation stores tickets as YAML files in a directory structure
-/// within the project's .vibe-ticket directory.
+/// within the project's .vibe-ticket directory. Tickets, the active ticket and
+/// the project state are read and written through a [`BlobStore`], which is
+/// the local file system unless another store is given.
 #[derive(Clone)]
 pub struct FileStorage {
     /// Base directory for storing ticket data
     base_dir: PathBuf,
+    /// Store holding the ticket files
+    pub(super) blobs: Arc<dyn BlobStore>,
     /// Cache for improved performance
     pub(crate) cache: Arc<TicketCache>,
+    /// Patterns from `.vibe-ticket/ignore`
+    ignore: Arc<IgnoreRules>,
+    /// Whether closed tickets are read-only until reopened
+    immutable_closed: bool,
+    /// Days deleted tickets are kept in the trash
+    trash_retention_days: u32,
+    /// Length of the short IDs given to new tickets
+    short_id_length: usize,
+    /// Fields a ticket must have before it can be closed
+    close_requirements: Vec<CloseRequirement>,
+    /// Whether tickets may be closed without meeting the close requirements
+    /// or while they depend on open tickets
+    force_close: bool,
+    /// Configured identity of the user
+    user: UserConfig,
+    /// Identity of the user, resolved when first needed
+    identity: Arc<OnceLock<Identity>>,
+    /// How long to wait for the locks of other processes
+    lock_settings: LockSettings,
 }
 
 impl FileStorage {
     /// Creates a new `FileStorage` instance
     pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        let base_dir = base_dir.into();
+        let blobs = Arc::new(LocalFs::new(base_dir.clone()));
+        Self::with_blob_store(base_dir, blobs)
+    }
+
+    /// Creates a `FileStorage` keeping its files in `blobs`
+    ///
+    /// `base_dir` is still used for lock files, attachments, the ignore file
+    /// and the configuration.
+    pub fn with_blob_store(base_dir: impl Into<PathBuf>, blobs: Arc<dyn BlobStore>) -> Self {
+        let base_dir = base_dir.into();
+        let config_path = base_dir.join("config.yaml");
+        let config = config_path
+            .exists()
+            .then(|| Config::load_from_path(&config_path).ok())
+            .flatten();
         Self {
-            base_dir: base_dir.into(),
+            ignore: Arc::new(IgnoreRules::load(&base_dir)),
+            immutable_closed: config
+                .as_ref()
+                .is_some_and(|config| config.project.immutable_closed),
+            trash_retention_days: config
+                .as_ref()
+                .map_or_else(default_trash_retention_days, |config| {
+                    config.project.trash_retention_days
+                }),
+            short_id_length: config
+                .as_ref()
+                .map_or_else(default_short_id_length, |config| {
+                    config.project.short_id_length
+                }),
+            user: config
+                .as_ref()
+                .map(|config| config.user.clone())
+                .unwrap_or_default(),
+            lock_settings: LockSettings::configured(
+                &config
+                    .as_ref()
+                    .map(|config| config.storage.lock)
+                    .unwrap_or_default(),
+            ),
+            close_requirements: config
+                .map(|config| config.project.close_requirements)
+                .unwrap_or_default(),
+            force_close: false,
+            identity: Arc::new(OnceLock::new()),
+            base_dir,
+            blobs,
             cache: Arc::new(TicketCache::with_default_ttl()),
         }
     }
 
+    /// Sets whether closed tickets are read-only until reopened
+    ///
+    /// Defaults to `project.immutable_closed` of the project configuration.
+    #[must_use]
+    pub const fn with_immutable_closed(mut self, immutable_closed: bool) -> Self {
+        self.immutable_closed = immutable_closed;
+        self
+    }
+
+    /// Holds back ticket writes for `window`, coalescing successive saves of
+    /// the same ticket into one write (see [`super::Coalescing`])
+    ///
+    /// Writes stay visible to this storage and its clones while pending, but
+    /// other processes only see them once written. A pending save is
+    /// discarded if another process saves the ticket first. Audit logs, the
+    /// journal and the index are written through. A zero window writes
+    /// everything through.
+    #[must_use]
+    pub fn with_write_coalescing(mut self, window: Duration) -> Self {
+        if !window.is_zero() {
+            self.blobs = Arc::new(super::Coalescing::locked(
+                self.blobs,
+                window,
+                TICKETS_KEY,
+                self.base_dir.clone(),
+                self.lock_settings,
+            ));
+        }
+        self
+    }
+
+    /// Writes out the writes held back by [`Self::with_write_coalescing`]
+    pub fn flush(&self) -> Result<()> {
+        self.blobs.flush()
+    }
+
+    /// Sets the fields a ticket must have before it can be closed
+    ///
+    /// Defaults to `project.close_requirements` of the project configuration.
+    #[must_use]
+    pub fn with_close_requirements(mut self, requirements: Vec<CloseRequirement>) -> Self {
+        self.close_requirements = requirements;
+        self
+    }
+
+    /// Fields a ticket must have before it can be closed
+    pub fn close_requirements(&self) -> &[CloseRequirement] {
+        &self.close_requirements
+    }
+
+    /// Sets whether tickets may be closed without meeting the close
+    /// requirements or while they depend on open tickets
+    ///
+    /// A forced close records the missing requirements in the audit log.
+    #[must_use]
+    pub const fn with_forced_close(mut self, force_close: bool) -> Self {
+        self.force_close = force_close;
+        self
+    }
+
+    /// Sets how long to wait for locks held by other processes
+    ///
+    /// Defaults to `storage.lock` of the project configuration, with the
+    /// wait given by `--wait` (see [`super::override_lock_wait`]).
+    #[must_use]
+    pub const fn with_lock_settings(mut self, lock_settings: LockSettings) -> Self {
+        self.lock_settings = lock_settings;
+        self
+    }
+
+    /// How long to wait for locks held by other processes
+    pub const fn lock_settings(&self) -> &LockSettings {
+        &self.lock_settings
+    }
+
+    /// Takes the lock of `path` for `operation`, waiting as
+    /// [`Self::lock_settings`] say
+    pub(super) fn lock(&self, path: &Path, operation: &str) -> anyhow::Result<FileLock> {
+        FileLock::acquire_with(path, Some(operation.to_string()), &self.lock_settings)
+    }
+
+    /// Lock files currently held in the storage directory, oldest first
+    pub fn held_locks(&self) -> Vec<HeldLock> {
+        HeldLock::find(&self.base_dir)
+    }
+
+    /// Days deleted tickets are kept in the trash before `gc` removes them
+    ///
+    /// Defaults to `project.trash_retention_days` of the project
+    /// configuration.
+    pub const fn trash_retention_days(&self) -> u32 {
+        self.trash_retention_days
+    }
+
+    /// Length of the short IDs given to new tickets
+    ///
+    /// Defaults to `project.short_id_length` of the project configuration.
+    pub const fn short_id_length(&self) -> usize {
+        self.short_id_length
+    }
+
+    /// Identity of the user running the command
+    ///
+    /// Resolved from `user` of the project configuration, then git (see
+    /// [`Identity`]); recorded as the actor of audit log entries.
+    pub fn identity(&self) -> &Identity {
+        self.identity.get_or_init(|| Identity::resolve(&self.user))
+    }
+
+    /// Patterns of the files that listings skip (see [`IgnoreRules`])
+    pub fn ignore_rules(&self) -> &IgnoreRules {
+        &self.ignore
+    }
+
+    /// Returns the blob store key of a ticket
+    pub(super) fn ticket_key(id: &TicketId) -> String {
+        format!("{TICKETS_KEY}/{id}.yaml")
+    }
+
     /// Returns the path to the tickets directory
-    fn tickets_dir(&self) -> PathBuf {
-        self.get_path("tickets")
+    pub(crate) fn tickets_dir(&self) -> PathBuf {
+        self.get_path(TICKETS_KEY)
     }
 
     /// Returns the path to a specific ticket file
@@ -38,18 +265,35 @@ impl FileStorage {
         self.tickets_dir().join(format!("{id}.yaml"))
     }
 
+    /// Invalidates the cached tickets when another process changes them
+    ///
+    /// Long-running processes keep the returned watcher alive for as long as
+    /// they serve tickets from this storage and its clones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tickets directory cannot be watched
+    pub fn watch_tickets(&self) -> Result<crate::cache::CacheWatcher> {
+        self.cache.watch(&self.tickets_dir())
+    }
+
+    /// Returns the directory holding attachments for a ticket
+    pub fn attachments_dir(&self, id: &TicketId) -> PathBuf {
+        self.get_path("attachments").join(id.to_string())
+    }
+
     /// Returns the path to the active ticket file
     fn active_ticket_path(&self) -> PathBuf {
-        self.get_path("active_ticket")
+        self.get_path(ACTIVE_TICKET_KEY)
     }
 
-    /// Returns the path to the project state file
-    fn state_path(&self) -> PathBuf {
-        self.get_path("state.yaml")
+    /// Returns the `.vibe-ticket` directory the storage reads from
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
     }
 
     /// Helper method to get a path relative to base directory
-    fn get_path(&self, name: &str) -> PathBuf {
+    pub(super) fn get_path(&self, name: &str) -> PathBuf {
         self.base_dir.join(name)
     }
 
@@ -60,24 +304,111 @@ impl FileStorage {
     }
 
     /// Saves a ticket to storage with file locking for concurrent access protection
+    ///
+    /// The save is rejected if it breaks an integrity constraint (see
+    /// [`super::TicketIndex`]).
     pub fn save_ticket(&self, ticket: &Ticket) -> Result<()> {
+        self.write_ticket(ticket, None)
+    }
+
+    /// Write path of [`Self::save_ticket`], checking slugs and references
+    /// against `index` if given, or else against the stored tickets
+    pub(super) fn write_ticket(&self, ticket: &Ticket, index: Option<&TicketIndex>) -> Result<()> {
         self.ensure_directories()?;
 
         let path = self.ticket_path(&ticket.id);
 
         // Acquire lock before modifying the file
-        let _lock =
-            super::FileLock::acquire(&path, Some("save_ticket".to_string())).map_err(|e| {
-                VibeTicketError::custom(format!("Failed to acquire lock for saving ticket: {}", e))
-            })?;
+        let _lock = self.lock(&path, "save_ticket").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for saving ticket: {}", e))
+        })?;
+
+        let write = self.prepare_write(ticket, index)?;
+        self.journal_write(&write)?;
+        self.blobs
+            .put(&Self::ticket_key(&write.ticket.id), write.yaml.as_bytes())
+            .with_context(|| format!("Failed to write ticket to {}", path.display()))?;
+        let closed = write.closes.then(|| write.ticket.clone());
+        self.finish_write(write)?;
+        if let Some(ticket) = closed {
+            self.closed(&ticket);
+        }
+        Ok(())
+    }
+
+    /// Checks a save of `ticket` and serializes the ticket to store
+    ///
+    /// The caller must hold the lock of the ticket file.
+    pub(super) fn prepare_write(
+        &self,
+        ticket: &Ticket,
+        index: Option<&TicketIndex>,
+    ) -> Result<PreparedWrite> {
+        let path = self.ticket_path(&ticket.id);
+        let previous = self
+            .blobs
+            .get_string(&Self::ticket_key(&ticket.id))
+            .with_context(|| format!("Failed to read ticket from {}", path.display()))?;
+        // A stored version that no longer parses cannot be compared against
+        let before = previous.as_deref().map(serde_yaml::from_str::<Ticket>);
+        let stored = match &before {
+            Some(Ok(before)) => Some(before),
+            _ => None,
+        };
+        let closes = stored.is_some_and(|before| before.status != Status::Done)
+            && ticket.status == Status::Done;
+        let (waived, changed) = match stored {
+            Some(before) => {
+                self.check_writable(before, ticket)?;
+                (
+                    self.check_close_requirements(before, ticket, index)?,
+                    before != ticket,
+                )
+            },
+            None => (Vec::new(), true),
+        };
+        self.check_integrity(ticket, stored, index)?;
+        let mut ticket = ticket.clone();
+        if let Some(short_id) = self.short_id_to_store(&ticket, stored)? {
+            ticket.short_id = Some(short_id);
+        }
+        let yaml = serde_yaml::to_string(&ticket).context("Failed to serialize ticket")?;
+
+        Ok(PreparedWrite {
+            ticket,
+            before,
+            changed,
+            waived,
+            closes,
+            yaml,
+        })
+    }
 
-        let yaml = serde_yaml::to_string(ticket).context("Failed to serialize ticket")?;
+    /// Records the state of a ticket before a prepared write in the journal
+    pub(super) fn journal_write(&self, write: &PreparedWrite) -> Result<()> {
+        match &write.before {
+            None => self.journal(&write.ticket.id, None),
+            Some(Ok(before)) if write.changed => self.journal(&write.ticket.id, Some(before)),
+            Some(_) => Ok(()),
+        }
+    }
 
-        fs::write(&path, yaml)
-            .with_context(|| format!("Failed to write ticket to {}", path.display()))?;
+    /// Updates the listing index, audit log and cache after a prepared
+    /// write was stored
+    pub(super) fn finish_write(&self, write: PreparedWrite) -> Result<()> {
+        let key = Self::ticket_key(&write.ticket.id);
+        self.update_index(&key, Some(&write.ticket));
+        self.log_event(StorageEventKind::Saved, Some(&write.ticket.id));
+
+        match write.before {
+            None => self.record_changes(None, &write.ticket)?,
+            Some(Ok(before)) => self.record_changes(Some(&before), &write.ticket)?,
+            Some(Err(_)) => {},
+        }
+        self.record(&write.ticket.id, write.waived)?;
 
         // Invalidate cache for this ticket
-        self.cache.invalidate_ticket(&ticket.id);
+        self.cache.invalidate_ticket(&write.ticket.id);
 
         Ok(())
     }
@@ -89,20 +420,23 @@ impl FileStorage {
             return Ok(ticket);
         }
 
-        let path = self.ticket_path(id);
+        let key = Self::ticket_key(id);
 
-        if !path.exists() {
+        if !self.blobs.exists(&key)? {
             return Err(VibeTicketError::TicketNotFound { id: id.to_string() });
         }
 
         // Acquire lock for reading to ensure consistency
-        let _lock =
-            super::FileLock::acquire(&path, Some("load_ticket".to_string())).map_err(|e| {
-                VibeTicketError::custom(format!("Failed to acquire lock for loading ticket: {}", e))
-            })?;
+        let path = self.ticket_path(id);
+        let _lock = self.lock(&path, "load_ticket").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for loading ticket: {}", e))
+        })?;
 
-        let yaml = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read ticket from {}", path.display()))?;
+        let yaml = self
+            .blobs
+            .get_string(&key)
+            .with_context(|| format!("Failed to read ticket from {}", path.display()))?
+            .ok_or_else(|| VibeTicketError::TicketNotFound { id: id.to_string() })?;
 
         let ticket: Ticket = serde_yaml::from_str(&yaml).context("Failed to deserialize ticket")?;
 
@@ -112,6 +446,158 @@ impl FileStorage {
         Ok(ticket)
     }
 
+    /// Applies `update` to a stored ticket while holding its lock
+    ///
+    /// Unlike a separate load and save, the lock is held across the whole
+    /// read-modify-write cycle, so concurrent updates from other threads or
+    /// processes are not lost. The ticket is always read from disk.
+    pub fn update_ticket<F>(&self, id: &TicketId, update: F) -> Result<Ticket>
+    where
+        F: FnOnce(&mut Ticket),
+    {
+        self.apply_update(id, update, true)
+    }
+
+    /// Reopens a closed ticket, moving it to `status` and applying `update`
+    ///
+    /// This is the only way to change a closed ticket while closed tickets
+    /// are read-only (see [`Self::with_immutable_closed`]).
+    pub fn reopen_ticket<F>(&self, id: &TicketId, status: Status, update: F) -> Result<Ticket>
+    where
+        F: FnOnce(&mut Ticket),
+    {
+        if self.load_ticket(id)?.status != Status::Done {
+            return Err(VibeTicketError::InvalidInput(format!(
+                "Ticket {} is not closed",
+                id.short()
+            )));
+        }
+        self.apply_update(
+            id,
+            |ticket| {
+                ticket.status = status;
+                ticket.closed_at = None;
+                update(ticket);
+            },
+            false,
+        )
+    }
+
+    /// Read-modify-write cycle of [`Self::update_ticket`]; `guarded` rejects
+    /// changes to read-only closed tickets
+    fn apply_update<F>(&self, id: &TicketId, update: F, guarded: bool) -> Result<Ticket>
+    where
+        F: FnOnce(&mut Ticket),
+    {
+        let path = self.ticket_path(id);
+
+        let _lock = self.lock(&path, "update_ticket").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for updating ticket: {e}"))
+        })?;
+
+        let key = Self::ticket_key(id);
+        let yaml = self
+            .blobs
+            .get_string(&key)
+            .with_context(|| format!("Failed to read ticket from {}", path.display()))?
+            .ok_or_else(|| VibeTicketError::TicketNotFound { id: id.to_string() })?;
+        let mut ticket: Ticket =
+            serde_yaml::from_str(&yaml).context("Failed to deserialize ticket")?;
+
+        let before = ticket.clone();
+        update(&mut ticket);
+        let waived = if guarded {
+            self.check_writable(&before, &ticket)?;
+            self.check_close_requirements(&before, &ticket, None)?
+        } else {
+            Vec::new()
+        };
+        self.check_integrity(&ticket, Some(&before), None)?;
+        if before != ticket {
+            self.journal(id, Some(&before))?;
+        }
+
+        let yaml = serde_yaml::to_string(&ticket).context("Failed to serialize ticket")?;
+        self.blobs
+            .put(&key, yaml.as_bytes())
+            .with_context(|| format!("Failed to write ticket to {}", path.display()))?;
+        self.update_index(&key, Some(&ticket));
+        self.log_event(StorageEventKind::Saved, Some(id));
+        self.record_changes(Some(&before), &ticket)?;
+        self.record(id, waived)?;
+
+        self.cache.invalidate_ticket(id);
+
+        if before.status != Status::Done && ticket.status == Status::Done {
+            self.closed(&ticket);
+        }
+        Ok(ticket)
+    }
+
+    /// Rejects changes to a closed ticket while closed tickets are read-only
+    ///
+    /// Archiving and unarchiving remain possible.
+    fn check_writable(&self, before: &Ticket, after: &Ticket) -> Result<()> {
+        if !self.immutable_closed || before.status != Status::Done {
+            return Ok(());
+        }
+        let without_archive = |ticket: &Ticket| {
+            let mut ticket = ticket.clone();
+            for key in ARCHIVE_KEYS {
+                ticket.metadata.remove(key);
+            }
+            ticket
+        };
+        if without_archive(before) == without_archive(after) {
+            Ok(())
+        } else {
+            Err(VibeTicketError::TicketClosed {
+                slug: before.slug.clone(),
+            })
+        }
+    }
+
+    /// Checks the close requirements and the dependencies when `after`
+    /// closes the ticket
+    ///
+    /// Tickets closed by the batch of `index`, if given, count as closed.
+    /// Returns the audit event of a forced close that misses requirements.
+    fn check_close_requirements(
+        &self,
+        before: &Ticket,
+        after: &Ticket,
+        index: Option<&TicketIndex>,
+    ) -> Result<Vec<Change>> {
+        if before.status == Status::Done || after.status != Status::Done {
+            return Ok(Vec::new());
+        }
+        if !self.force_close && !after.depends_on.is_empty() {
+            let mut tickets = self.load_all_tickets()?;
+            for ticket in &mut tickets {
+                if index.is_some_and(|index| index.closes(&ticket.id)) {
+                    ticket.status = Status::Done;
+                }
+            }
+            dependencies::ensure_closable(after, &tickets)?;
+        }
+        let missing = closing::missing(after, &self.close_requirements);
+        if missing.is_empty() {
+            Ok(Vec::new())
+        } else if self.force_close {
+            Ok(vec![Change::CloseRequirementsWaived {
+                missing: missing.iter().map(ToString::to_string).collect(),
+            }])
+        } else {
+            Err(VibeTicketError::CloseRequirementsNotMet {
+                slug: after.slug.clone(),
+                missing: missing
+                    .iter()
+                    .map(|requirement| requirement.description().to_string())
+                    .collect(),
+            })
+        }
+    }
+
     /// Loads all tickets from storage
     pub fn load_all_tickets(&self) -> Result<Vec<Ticket>> {
         // Check cache first
@@ -119,32 +605,18 @@ impl FileStorage {
             return Ok(tickets);
         }
 
-        let tickets_dir = self.tickets_dir();
-
-        if !tickets_dir.exists() {
-            return Ok(Vec::new());
-        }
-
         let mut tickets = Vec::new();
 
-        for entry in fs::read_dir(&tickets_dir).context("Failed to read tickets directory")? {
-            let entry = entry.context("Failed to read directory entry")?;
-            let path = entry.path();
-
-            if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
-                let yaml = fs::read_to_string(&path)
-                    .with_context(|| format!("Failed to read {}", path.display()))?;
-
-                match serde_yaml::from_str::<Ticket>(&yaml) {
-                    Ok(ticket) => tickets.push(ticket),
-                    Err(e) => {
-                        // Log error but continue loading other tickets
-                        eprintln!(
-                            "Warning: Failed to load ticket from {}: {e}",
-                            path.display()
-                        );
-                    },
-                }
+        for (path, yaml) in self.ticket_files()? {
+            match serde_yaml::from_str::<Ticket>(&yaml) {
+                Ok(ticket) => tickets.push(ticket),
+                Err(e) => {
+                    // Log error but continue loading other tickets
+                    eprintln!(
+                        "Warning: Failed to load ticket from {}: {e}",
+                        path.display()
+                    );
+                },
             }
         }
 
@@ -154,25 +626,82 @@ impl FileStorage {
         Ok(tickets)
     }
 
+    /// Reads the raw YAML of every ticket file with its path
+    ///
+    /// Files excluded by `.vibe-ticket/ignore` are skipped.
+    pub(crate) fn ticket_files(&self) -> Result<Vec<(PathBuf, String)>> {
+        let mut files = Vec::new();
+
+        for key in self.ticket_keys()? {
+            // A ticket deleted since listing is simply skipped
+            if let Some(file) = self.read_ticket_file(&key)? {
+                files.push(file);
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Blob store keys of the ticket files, except those excluded by
+    /// `.vibe-ticket/ignore`
+    pub(crate) fn ticket_keys(&self) -> Result<Vec<String>> {
+        let keys = self
+            .blobs
+            .list(TICKETS_KEY)
+            .context("Failed to read tickets directory")?;
+        Ok(keys
+            .into_iter()
+            .filter(|key| {
+                key.strip_prefix(&format!("{TICKETS_KEY}/"))
+                    .is_some_and(|file_name| {
+                        Path::new(file_name).extension().and_then(|s| s.to_str()) == Some("yaml")
+                    })
+                    && !self.ignore.is_ignored(key, false)
+            })
+            .collect())
+    }
+
+    /// Reads the raw YAML of the ticket file under `key` with its path
+    ///
+    /// Returns `None` if the file no longer exists.
+    pub(crate) fn read_ticket_file(&self, key: &str) -> Result<Option<(PathBuf, String)>> {
+        let file_name = key.strip_prefix(&format!("{TICKETS_KEY}/")).unwrap_or(key);
+        let path = self.tickets_dir().join(file_name);
+        let yaml = self
+            .blobs
+            .get_string(key)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(yaml.map(|yaml| (path, yaml)))
+    }
+
     /// Deletes a ticket from storage with locking
     pub fn delete_ticket(&self, id: &TicketId) -> Result<()> {
-        let path = self.ticket_path(id);
+        let key = Self::ticket_key(id);
 
-        if !path.exists() {
+        if !self.blobs.exists(&key)? {
             return Err(VibeTicketError::TicketNotFound { id: id.to_string() });
         }
 
         // Acquire lock before deleting
-        let _lock =
-            super::FileLock::acquire(&path, Some("delete_ticket".to_string())).map_err(|e| {
-                VibeTicketError::custom(format!(
-                    "Failed to acquire lock for deleting ticket: {}",
-                    e
-                ))
-            })?;
-
-        fs::remove_file(&path)
+        let path = self.ticket_path(id);
+        let _lock = self.lock(&path, "delete_ticket").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for deleting ticket: {}", e))
+        })?;
+
+        let before = self
+            .blobs
+            .get_string(&key)
+            .with_context(|| format!("Failed to read ticket from {}", path.display()))?
+            .and_then(|yaml| serde_yaml::from_str::<Ticket>(&yaml).ok());
+        if let Some(before) = &before {
+            self.journal(id, Some(before))?;
+        }
+        self.blobs
+            .delete(&key)
             .with_context(|| format!("Failed to delete ticket at {}", path.display()))?;
+        self.update_index(&key, None);
+        self.log_event(StorageEventKind::Deleted, Some(id));
+        self.record(id, vec![crate::core::audit::Change::Deleted])?;
 
         // Invalidate cache for this ticket
         self.cache.invalidate_ticket(id);
@@ -180,32 +709,99 @@ impl FileStorage {
         Ok(())
     }
 
+    /// Imports a ticket moved from another project, with `history` as its
+    /// audit log
+    ///
+    /// The import is neither journaled nor recorded in the audit log.
+    /// `finish` runs while the ticket is still locked; if it fails, the
+    /// ticket and its audit log are removed again without a trace and its
+    /// error is returned.
+    ///
+    /// Returns the ticket as stored, with a short ID of this project.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project already has the ticket, the ticket
+    /// breaks an integrity constraint, cannot be written, or `finish` fails
+    pub fn import_ticket(
+        &self,
+        ticket: &Ticket,
+        history: &[AuditEvent],
+        finish: impl FnOnce() -> Result<()>,
+    ) -> Result<Ticket> {
+        self.ensure_directories()?;
+
+        let path = self.ticket_path(&ticket.id);
+        let _lock = self.lock(&path, "import_ticket").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for importing ticket: {e}"))
+        })?;
+
+        let write = self.prepare_write(ticket, None)?;
+        if write.before.is_some() {
+            return Err(VibeTicketError::custom(format!(
+                "The project already has a ticket with ID {}",
+                ticket.id
+            )));
+        }
+        let mut log = Vec::new();
+        for event in history {
+            serde_json::to_writer(&mut log, event)?;
+            log.push(b'\n');
+        }
+
+        let key = Self::ticket_key(&ticket.id);
+        let history_key = Self::history_key(&ticket.id);
+        let imported = self
+            .blobs
+            .put(&history_key, &log)
+            .context("Failed to write ticket history")
+            .and_then(|()| {
+                self.blobs
+                    .put(&key, write.yaml.as_bytes())
+                    .with_context(|| format!("Failed to write ticket to {}", path.display()))
+            })
+            .and_then(|()| finish());
+        if let Err(e) = imported {
+            let _ = self.blobs.delete(&key);
+            let _ = self.blobs.delete(&history_key);
+            self.cache.invalidate_ticket(&ticket.id);
+            return Err(e);
+        }
+
+        self.update_index(&key, Some(&write.ticket));
+        self.log_event(StorageEventKind::Saved, Some(&ticket.id));
+        self.cache.invalidate_ticket(&ticket.id);
+        Ok(write.ticket)
+    }
+
     /// Sets the active ticket with locking
     pub fn set_active_ticket(&self, id: &TicketId) -> Result<()> {
         let path = self.active_ticket_path();
 
         // Acquire lock for the active ticket file
-        let _lock = super::FileLock::acquire(&path, Some("set_active_ticket".to_string()))
-            .map_err(|e| {
-                VibeTicketError::custom(format!(
-                    "Failed to acquire lock for setting active ticket: {}",
-                    e
-                ))
-            })?;
-
-        fs::write(&path, id.to_string()).context("Failed to write active ticket")?;
+        let _lock = self.lock(&path, "set_active_ticket").map_err(|e| {
+            VibeTicketError::custom(format!(
+                "Failed to acquire lock for setting active ticket: {}",
+                e
+            ))
+        })?;
+
+        self.blobs
+            .put(ACTIVE_TICKET_KEY, id.to_string().as_bytes())
+            .context("Failed to write active ticket")?;
+        self.log_event(StorageEventKind::ActiveChanged, Some(id));
         Ok(())
     }
 
     /// Gets the active ticket ID
     pub fn get_active_ticket(&self) -> Result<Option<TicketId>> {
-        let path = self.active_ticket_path();
-
-        if !path.exists() {
+        let Some(content) = self
+            .blobs
+            .get_string(ACTIVE_TICKET_KEY)
+            .context("Failed to read active ticket")?
+        else {
             return Ok(None);
-        }
-
-        let content = fs::read_to_string(&path).context("Failed to read active ticket")?;
+        };
 
         let id = TicketId::parse_str(content.trim()).context("Failed to parse active ticket ID")?;
 
@@ -214,19 +810,21 @@ impl FileStorage {
 
     /// Clears the active ticket with locking
     pub fn clear_active_ticket(&self) -> Result<()> {
-        let path = self.active_ticket_path();
+        if self.blobs.exists(ACTIVE_TICKET_KEY)? {
+            let path = self.active_ticket_path();
 
-        if path.exists() {
             // Acquire lock before removing
-            let _lock = super::FileLock::acquire(&path, Some("clear_active_ticket".to_string()))
-                .map_err(|e| {
-                    VibeTicketError::custom(format!(
-                        "Failed to acquire lock for clearing active ticket: {}",
-                        e
-                    ))
-                })?;
-
-            fs::remove_file(&path).context("Failed to clear active ticket")?;
+            let _lock = self.lock(&path, "clear_active_ticket").map_err(|e| {
+                VibeTicketError::custom(format!(
+                    "Failed to acquire lock for clearing active ticket: {}",
+                    e
+                ))
+            })?;
+
+            self.blobs
+                .delete(ACTIVE_TICKET_KEY)
+                .context("Failed to clear active ticket")?;
+            self.log_event(StorageEventKind::ActiveChanged, None);
         }
 
         Ok(())
@@ -234,14 +832,16 @@ impl FileStorage {
 
     /// Checks if a ticket with the given slug already exists
     pub fn ticket_exists_with_slug(&self, slug: &str) -> Result<bool> {
-        let tickets = self.load_all_tickets()?;
-        Ok(tickets.iter().any(|t| t.slug == slug))
+        Ok(self.summaries()?.iter().any(|t| t.slug == slug))
     }
 
     /// Finds a ticket by its slug
     pub fn find_ticket_by_slug(&self, slug: &str) -> Result<Option<Ticket>> {
-        let tickets = self.load_all_tickets()?;
-        Ok(tickets.into_iter().find(|t| t.slug == slug))
+        self.summaries()?
+            .into_iter()
+            .find(|t| t.slug == slug)
+            .map(|summary| self.load_ticket(&summary.id))
+            .transpose()
     }
 }
 
@@ -267,23 +867,22 @@ pub struct ProjectState {
 impl FileStorage {
     /// Saves the project state
     pub fn save_state(&self, state: &ProjectState) -> Result<()> {
-        let path = self.state_path();
         let yaml = serde_yaml::to_string(state).context("Failed to serialize project state")?;
 
-        fs::write(&path, yaml).context("Failed to write project state")?;
+        self.blobs
+            .put(STATE_KEY, yaml.as_bytes())
+            .context("Failed to write project state")?;
 
         Ok(())
     }
 
     /// Loads the project state
     pub fn load_state(&self) -> Result<ProjectState> {
-        let path = self.state_path();
-
-        if !path.exists() {
-            return Err(VibeTicketError::ProjectNotInitialized);
-        }
-
-        let yaml = fs::read_to_string(&path).context("Failed to read project state")?;
+        let yaml = self
+            .blobs
+            .get_string(STATE_KEY)
+            .context("Failed to read project state")?
+            .ok_or(VibeTicketError::ProjectNotInitialized)?;
 
         let state: ProjectState =
             serde_yaml::from_str(&yaml).context("Failed to deserialize project state")?;
@@ -292,6 +891,77 @@ impl FileStorage {
     }
 }
 
+/// Metadata key recording where an oversized description was moved to
+pub const DESCRIPTION_ATTACHMENT_KEY: &str = "description_attachment";
+
+/// Number of characters kept inline when a description spills into an attachment
+const DESCRIPTION_SUMMARY_CHARS: usize = 500;
+
+impl FileStorage {
+    /// Moves an oversized description into an attachment file
+    ///
+    /// When the description exceeds `limit` bytes, the full text is written to
+    /// `attachments/<ticket-id>/description.md` and the inline description is
+    /// replaced by a short summary with a link to the file. A description that
+    /// fits within the limit drops any previously spilled attachment.
+    ///
+    /// Returns `true` if the description was moved out of the ticket.
+    pub fn spill_description(&self, ticket: &mut Ticket, limit: usize) -> Result<bool> {
+        let dir = self.attachments_dir(&ticket.id);
+        let path = dir.join("description.md");
+
+        if ticket.description.len() <= limit {
+            if ticket.metadata.remove(DESCRIPTION_ATTACHMENT_KEY).is_some() && path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            return Ok(false);
+        }
+
+        fs::create_dir_all(&dir).context("Failed to create attachments directory")?;
+        fs::write(&path, &ticket.description)
+            .with_context(|| format!("Failed to write description to {}", path.display()))?;
+
+        let relative = format!("attachments/{}/description.md", ticket.id);
+        let summary = summarize(&ticket.description, DESCRIPTION_SUMMARY_CHARS);
+        ticket.description = format!(
+            "{summary}\n\n[Full description ({} bytes) moved to {relative}; \
+             use `vibe-ticket show --full-description` to view it]",
+            ticket.description.len()
+        );
+        ticket.metadata.insert(
+            DESCRIPTION_ATTACHMENT_KEY.to_string(),
+            serde_json::Value::String(relative),
+        );
+
+        Ok(true)
+    }
+
+    /// Returns the full description of a ticket, reading a spilled attachment if present
+    pub fn load_full_description(&self, ticket: &Ticket) -> Result<String> {
+        let Some(relative) = ticket
+            .metadata
+            .get(DESCRIPTION_ATTACHMENT_KEY)
+            .and_then(serde_json::Value::as_str)
+        else {
+            return Ok(ticket.description.clone());
+        };
+
+        let path = self.get_path(relative);
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read description from {}", path.display()))
+    }
+}
+
+/// Returns the leading part of `text`, cut at a line boundary when possible
+fn summarize(text: &str, max_chars: usize) -> String {
+    let head: String = text.chars().take(max_chars).collect();
+    match head.rfind('\n') {
+        Some(pos) if pos > 0 => head[..pos].trim_end().to_string(),
+        _ => head.trim_end().to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,6 +1001,159 @@ mod tests {
         assert_eq!(tickets.len(), 2);
     }
 
+    #[test]
+    fn test_load_all_skips_ignored_tickets() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = Ticket::new("kept", "Kept");
+        let vendored = Ticket::new("vendored", "Vendored");
+        FileStorage::new(temp_dir.path())
+            .save_ticket(&kept)
+            .unwrap();
+        FileStorage::new(temp_dir.path())
+            .save_ticket(&vendored)
+            .unwrap();
+        fs::write(
+            temp_dir.path().join("ignore"),
+            format!("tickets/{}.yaml\n", vendored.id),
+        )
+        .unwrap();
+
+        let storage = FileStorage::new(temp_dir.path());
+        let tickets = storage.load_all_tickets().unwrap();
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].slug, "kept");
+        assert_eq!(storage.load_ticket(&vendored.id).unwrap().slug, "vendored");
+    }
+
+    #[test]
+    fn test_update_ticket() {
+        let (storage, _temp) = create_test_storage();
+        let ticket = Ticket::new("test", "Test");
+        storage.save_ticket(&ticket).unwrap();
+
+        // Prime the cache, then update behind it
+        storage.load_ticket(&ticket.id).unwrap();
+        let updated = storage
+            .update_ticket(&ticket.id, |t| {
+                t.add_task("First");
+            })
+            .unwrap();
+        assert_eq!(updated.tasks.len(), 1);
+        assert_eq!(storage.load_ticket(&ticket.id).unwrap().tasks.len(), 1);
+
+        assert!(storage.update_ticket(&TicketId::new(), |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_close_requirements() {
+        let (storage, temp) = create_test_storage();
+        let storage = storage.with_close_requirements(vec![CloseRequirement::Tag]);
+        let mut ticket = Ticket::new("needs-tag", "Needs tag");
+        storage.save_ticket(&ticket).unwrap();
+
+        ticket.close();
+        assert!(matches!(
+            storage.save_ticket(&ticket),
+            Err(VibeTicketError::CloseRequirementsNotMet { .. })
+        ));
+        assert!(
+            storage
+                .update_ticket(&ticket.id, |t| t.status = Status::Done)
+                .is_err()
+        );
+
+        storage
+            .clone()
+            .with_forced_close(true)
+            .save_ticket(&ticket)
+            .unwrap();
+        let history = FileStorage::new(temp.path())
+            .load_history(&ticket.id)
+            .unwrap();
+        assert_eq!(
+            history.last().unwrap().change,
+            Change::CloseRequirementsWaived {
+                missing: vec!["tag".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_memory_blob_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let blobs = Arc::new(super::super::MemoryStore::new());
+        let storage = FileStorage::with_blob_store(temp_dir.path(), blobs.clone());
+
+        let ticket = Ticket::new("in-memory", "In Memory");
+        storage.save_ticket(&ticket).unwrap();
+        storage.set_active_ticket(&ticket.id).unwrap();
+
+        // Only lock files touch the disk
+        assert!(!storage.ticket_path(&ticket.id).exists());
+        assert!(blobs.exists(&FileStorage::ticket_key(&ticket.id)).unwrap());
+
+        let fresh = FileStorage::with_blob_store(temp_dir.path(), blobs);
+        assert_eq!(fresh.load_all_tickets().unwrap().len(), 1);
+        assert_eq!(fresh.get_active_ticket().unwrap(), Some(ticket.id.clone()));
+
+        fresh.delete_ticket(&ticket.id).unwrap();
+        assert!(fresh.load_ticket(&ticket.id).is_err());
+    }
+
+    #[test]
+    fn test_write_coalescing() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage =
+            FileStorage::new(temp_dir.path()).with_write_coalescing(Duration::from_secs(60));
+
+        let ticket = Ticket::new("coalesced", "Coalesced");
+        storage.save_ticket(&ticket).unwrap();
+        for i in 0..5 {
+            storage
+                .update_ticket(&ticket.id, |ticket| ticket.title = format!("Edit {i}"))
+                .unwrap();
+        }
+
+        // Pending writes are read back, but not yet on disk
+        assert!(!storage.ticket_path(&ticket.id).exists());
+        assert_eq!(storage.load_ticket(&ticket.id).unwrap().title, "Edit 4");
+        assert_eq!(storage.load_all_tickets().unwrap().len(), 1);
+
+        storage.flush().unwrap();
+        let fresh = FileStorage::new(temp_dir.path());
+        assert_eq!(fresh.load_ticket(&ticket.id).unwrap().title, "Edit 4");
+    }
+
+    #[test]
+    fn test_write_coalescing_keeps_other_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let ticket = Ticket::new("shared", "Shared");
+        FileStorage::new(temp_dir.path())
+            .save_ticket(&ticket)
+            .unwrap();
+
+        let server =
+            FileStorage::new(temp_dir.path()).with_write_coalescing(Duration::from_secs(60));
+        server
+            .update_ticket(&ticket.id, |ticket| ticket.title = "Server".to_string())
+            .unwrap();
+
+        // Another process saves the ticket while the server holds its save
+        let cli = FileStorage::new(temp_dir.path());
+        cli.update_ticket(&ticket.id, |ticket| {
+            ticket.description = "From the CLI".to_string();
+        })
+        .unwrap();
+        server.flush().unwrap();
+
+        let stored = FileStorage::new(temp_dir.path())
+            .load_ticket(&ticket.id)
+            .unwrap();
+        assert_eq!(stored.description, "From the CLI");
+        assert_eq!(stored.title, "Shared");
+        assert_eq!(cli.load_history(&ticket.id).unwrap().len(), 3);
+    }
+
     #[test]
     fn test_active_ticket() {
         let (storage, _temp) = create_test_storage();
@@ -346,6 +1169,25 @@ mod tests {
         let active_id = storage.get_active_ticket().unwrap();
         assert_eq!(active_id, None);
     }
+
+    #[test]
+    fn test_spill_description() {
+        let (storage, _temp) = create_test_storage();
+        let mut ticket = Ticket::new("big-log", "Big Log");
+        let original = "log line\n".repeat(200);
+        ticket.description.clone_from(&original);
+
+        assert!(storage.spill_description(&mut ticket, 1024).unwrap());
+        assert!(ticket.description.len() < original.len());
+        assert!(ticket.metadata.contains_key(DESCRIPTION_ATTACHMENT_KEY));
+        assert_eq!(storage.load_full_description(&ticket).unwrap(), original);
+
+        // A description within the limit drops the attachment again
+        ticket.description = "short".to_string();
+        assert!(!storage.spill_description(&mut ticket, 1024).unwrap());
+        assert!(!ticket.metadata.contains_key(DESCRIPTION_ATTACHMENT_KEY));
+        assert_eq!(storage.load_full_description(&ticket).unwrap(), "short");
+    }
 }
 // Include concurrent tests
 #[cfg(test)]
@@ -0,0 +1,270 @@
+//! Garbage collection of orphaned project data
+//!
+//! Attachments and audit logs are kept per ticket, and spec documents per
+//! spec. Deleting a ticket keeps its history (the log ends with a `Deleted`
+//! event), and removing a spec's metadata by hand leaves its documents
+//! behind. [`FileStorage::find_orphans`] finds such data whose owner no
+//! longer exists, so that it can be reported and removed.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::FileStorage;
+use super::file::TICKETS_KEY;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the history directory
+const HISTORY_KEY: &str = "history";
+
+/// Metadata file every spec directory contains
+const SPEC_METADATA_FILE: &str = "spec.json";
+
+/// Kind of orphaned data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanKind {
+    /// Attachment directory of a ticket that no longer exists
+    Attachments,
+    /// Audit log of a ticket that no longer exists
+    History,
+    /// Spec directory without spec metadata
+    SpecDocuments,
+    /// Deleted ticket kept in the trash beyond the retention period
+    Trash,
+}
+
+impl fmt::Display for OrphanKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Attachments => "attachments",
+            Self::History => "history",
+            Self::SpecDocuments => "spec documents",
+            Self::Trash => "expired trash",
+        })
+    }
+}
+
+/// Data whose owning ticket or spec no longer exists
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Orphan {
+    /// What kind of data this is
+    pub kind: OrphanKind,
+    /// ID of the missing ticket or spec
+    pub owner: String,
+    /// File or directory holding the data
+    pub path: PathBuf,
+    /// Space taken by the data in bytes
+    pub bytes: u64,
+}
+
+impl FileStorage {
+    /// Finds attachments, audit logs and spec documents without an owner
+    ///
+    /// Tickets in the trash still own their data; trash entries older than
+    /// the retention period are reported themselves. The result is sorted by
+    /// kind and owner.
+    pub fn find_orphans(&self) -> Result<Vec<Orphan>> {
+        let mut tickets: HashSet<String> = self
+            .blobs
+            .list(TICKETS_KEY)
+            .context("Failed to read tickets directory")?
+            .iter()
+            .filter_map(|key| key_stem(key, TICKETS_KEY, ".yaml"))
+            .collect();
+        let trash = self.list_trash()?;
+        tickets.extend(trash.iter().map(|entry| entry.ticket.id.to_string()));
+
+        let mut orphans = Vec::new();
+
+        for dir in sub_dirs(&self.get_path("attachments"))? {
+            let owner = file_name(&dir);
+            if !tickets.contains(&owner) {
+                orphans.push(Orphan {
+                    kind: OrphanKind::Attachments,
+                    bytes: disk_usage(&dir)?,
+                    owner,
+                    path: dir,
+                });
+            }
+        }
+
+        for key in self
+            .blobs
+            .list(HISTORY_KEY)
+            .context("Failed to read history directory")?
+        {
+            let Some(owner) = key_stem(&key, HISTORY_KEY, ".jsonl") else {
+                continue;
+            };
+            if tickets.contains(&owner) {
+                continue;
+            }
+            let bytes = self.blobs.get(&key)?.map_or(0, |data| data.len() as u64);
+            orphans.push(Orphan {
+                kind: OrphanKind::History,
+                path: self.get_path(HISTORY_KEY).join(format!("{owner}.jsonl")),
+                owner,
+                bytes,
+            });
+        }
+
+        for dir in sub_dirs(&self.get_path("specs"))? {
+            if !dir.join(SPEC_METADATA_FILE).is_file() {
+                orphans.push(Orphan {
+                    kind: OrphanKind::SpecDocuments,
+                    owner: file_name(&dir),
+                    bytes: disk_usage(&dir)?,
+                    path: dir,
+                });
+            }
+        }
+
+        let now = chrono::Utc::now();
+        let mut expired: Vec<_> = trash
+            .iter()
+            .filter(|entry| entry.is_expired(self.trash_retention_days(), now))
+            .map(|entry| {
+                let key = Self::trash_key(&entry.ticket.id);
+                let bytes = self.blobs.get(&key)?.map_or(0, |data| data.len() as u64);
+                Ok(Orphan {
+                    kind: OrphanKind::Trash,
+                    owner: entry.ticket.id.to_string(),
+                    path: self.get_path(&key),
+                    bytes,
+                })
+            })
+            .collect::<Result<_>>()?;
+        expired.sort_by(|a: &Orphan, b| a.owner.cmp(&b.owner));
+        orphans.append(&mut expired);
+
+        Ok(orphans)
+    }
+
+    /// Deletes orphaned data found by [`find_orphans`](Self::find_orphans)
+    pub fn remove_orphan(&self, orphan: &Orphan) -> Result<()> {
+        match orphan.kind {
+            OrphanKind::History => {
+                self.blobs
+                    .delete(&format!("{HISTORY_KEY}/{}.jsonl", orphan.owner))
+                    .context("Failed to delete ticket history")?;
+            },
+            OrphanKind::Attachments | OrphanKind::SpecDocuments => {
+                fs::remove_dir_all(&orphan.path)
+                    .with_context(|| format!("Failed to delete {}", orphan.path.display()))?;
+            },
+            OrphanKind::Trash => {
+                let id = crate::core::TicketId::parse_str(&orphan.owner).map_err(|_| {
+                    VibeTicketError::custom(format!("Invalid ticket ID in trash: {}", orphan.owner))
+                })?;
+                self.purge_from_trash(&id)?;
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Returns the file name of `key` under `prefix` without `suffix`
+fn key_stem(key: &str, prefix: &str, suffix: &str) -> Option<String> {
+    key.strip_prefix(prefix)?
+        .strip_prefix('/')?
+        .strip_suffix(suffix)
+        .map(ToString::to_string)
+}
+
+/// Returns the last component of a path as a string
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Lists the directories directly under `dir`, sorted
+fn sub_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let entry = entry.context("Failed to read directory entry")?;
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            dirs.push(entry.path());
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Total size of the files in a directory tree
+fn disk_usage(path: &Path) -> Result<u64> {
+    let metadata =
+        fs::symlink_metadata(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path).with_context(|| format!("Failed to read {}", path.display()))? {
+        total += disk_usage(&entry.context("Failed to read directory entry")?.path())?;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::specs::{SpecManager, Specification};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_and_removes_orphans() {
+        let temp_dir = TempDir::new().unwrap();
+        let vibe_dir = temp_dir.path().join(".vibe-ticket");
+        let storage = FileStorage::new(&vibe_dir);
+
+        let kept = Ticket::new("kept", "Kept");
+        storage.save_ticket(&kept).unwrap();
+        fs::create_dir_all(storage.attachments_dir(&kept.id)).unwrap();
+
+        let deleted = Ticket::new("deleted", "Deleted");
+        storage.save_ticket(&deleted).unwrap();
+        let attachments = storage.attachments_dir(&deleted.id);
+        fs::create_dir_all(&attachments).unwrap();
+        fs::write(attachments.join("trace.log"), "0123456789").unwrap();
+        storage.delete_ticket(&deleted.id).unwrap();
+
+        let spec = Specification::new("Kept spec".to_string(), String::new(), None, Vec::new());
+        SpecManager::new(vibe_dir.join("specs"))
+            .save(&spec)
+            .unwrap();
+        let stray = vibe_dir.join("specs").join("stray");
+        fs::create_dir_all(&stray).unwrap();
+        fs::write(stray.join("design.md"), "# Design").unwrap();
+
+        let orphans = storage.find_orphans().unwrap();
+        let found: Vec<_> = orphans.iter().map(|o| (o.kind, o.owner.as_str())).collect();
+        let deleted_id = deleted.id.to_string();
+        assert_eq!(
+            found,
+            vec![
+                (OrphanKind::Attachments, deleted_id.as_str()),
+                (OrphanKind::History, deleted_id.as_str()),
+                (OrphanKind::SpecDocuments, "stray"),
+            ]
+        );
+        assert_eq!(orphans[0].bytes, 10);
+        assert_eq!(orphans[2].bytes, 8);
+
+        for orphan in &orphans {
+            storage.remove_orphan(orphan).unwrap();
+        }
+        assert!(storage.find_orphans().unwrap().is_empty());
+        assert!(!storage.load_history(&kept.id).unwrap().is_empty());
+        assert!(storage.attachments_dir(&kept.id).is_dir());
+    }
+}
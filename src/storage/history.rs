@@ -0,0 +1,232 @@
+//! Persistence of the ticket audit log
+//!
+//! The audit log of a ticket is stored as JSON lines under
+//! `history/<ticket-id>.jsonl`, one [`AuditEvent`] per line. `FileStorage`
+//! appends to it whenever it writes or deletes a ticket; the caller already
+//! holds the ticket lock, so appends to one log never interleave.
+//!
+//! The events appended for a save are also the source of the notifications
+//! queued for the ticket's watchers.
+
+use chrono::Utc;
+
+use super::FileStorage;
+use crate::core::audit::{self, AuditEvent, Change};
+use crate::core::{FieldDelta, Ticket, TicketId, WatchNotification};
+use crate::error::{ErrorContext, Result};
+
+/// Blob store key of the history directory
+const HISTORY_KEY: &str = "history";
+
+/// Blob store key of the queued watcher notifications
+const NOTIFICATIONS_KEY: &str = "notifications";
+
+impl FileStorage {
+    /// Returns the blob store key of a ticket's audit log
+    pub(super) fn history_key(id: &TicketId) -> String {
+        format!("{HISTORY_KEY}/{id}.jsonl")
+    }
+
+    /// Loads the audit log of a ticket, oldest event first
+    ///
+    /// Returns an empty log for tickets saved before the log existed. Lines
+    /// that cannot be parsed are skipped.
+    pub fn load_history(&self, id: &TicketId) -> Result<Vec<AuditEvent>> {
+        let Some(content) = self
+            .blobs
+            .get_string(&Self::history_key(id))
+            .context("Failed to read ticket history")?
+        else {
+            return Ok(Vec::new());
+        };
+
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Appends the changes from `before` to `after` to the ticket's audit log
+    ///
+    /// Changes to watched fields are queued as a notification for the
+    /// watchers of the ticket.
+    pub(crate) fn record_changes(&self, before: Option<&Ticket>, after: &Ticket) -> Result<()> {
+        let events = self.record(&after.id, audit::diff(before, after))?;
+        if let Some(notification) = WatchNotification::from_events(after, &events) {
+            self.queue_notification(&notification)?;
+        }
+        Ok(())
+    }
+
+    /// Appends events for `changes` to the audit log of the ticket
+    ///
+    /// Returns the appended events.
+    pub(crate) fn record(&self, id: &TicketId, changes: Vec<Change>) -> Result<Vec<AuditEvent>> {
+        if changes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let key = Self::history_key(id);
+        let mut content = self
+            .blobs
+            .get(&key)
+            .context("Failed to read ticket history")?
+            .unwrap_or_default();
+        let actor = Some(self.identity().name.clone());
+        let events: Vec<AuditEvent> = changes
+            .into_iter()
+            .map(|change| AuditEvent::now(change, actor.clone()))
+            .collect();
+        for event in &events {
+            serde_json::to_writer(&mut content, event)?;
+            content.push(b'\n');
+        }
+
+        self.blobs
+            .put(&key, &content)
+            .context("Failed to write ticket history")?;
+        Ok(events)
+    }
+
+    /// Returns the blob store key of a queued notification
+    fn notification_key(id: &str) -> String {
+        format!("{NOTIFICATIONS_KEY}/{id}.json")
+    }
+
+    /// Queues a notification of `changes` for the watchers of a ticket
+    ///
+    /// Used for changes the audit log does not report to watchers by
+    /// itself. Returns `false` if nobody else watches the ticket.
+    pub fn notify_watchers(&self, ticket: &Ticket, changes: Vec<FieldDelta>) -> Result<bool> {
+        let Some(notification) = WatchNotification::new(
+            ticket,
+            Some(self.identity().name.clone()),
+            Utc::now(),
+            changes,
+        ) else {
+            return Ok(false);
+        };
+        self.queue_notification(&notification)?;
+        Ok(true)
+    }
+
+    /// Queues a notification until it is dispatched
+    fn queue_notification(&self, notification: &WatchNotification) -> Result<()> {
+        let json = serde_json::to_vec_pretty(notification)?;
+        self.blobs
+            .put(&Self::notification_key(&notification.id), &json)
+            .context("Failed to queue watcher notification")
+    }
+
+    /// Lists the queued watcher notifications, oldest first
+    ///
+    /// Entries that cannot be parsed are skipped.
+    pub fn pending_notifications(&self) -> Result<Vec<WatchNotification>> {
+        let mut notifications = Vec::new();
+        for key in self.blobs.list(NOTIFICATIONS_KEY)? {
+            let Some(content) = self.blobs.get(&key)? else {
+                continue;
+            };
+            if let Ok(notification) = serde_json::from_slice(&content) {
+                notifications.push(notification);
+            }
+        }
+        notifications.sort_by_key(|n: &WatchNotification| n.at);
+        Ok(notifications)
+    }
+
+    /// Removes a dispatched notification from the queue
+    pub fn remove_notification(&self, id: &str) -> Result<()> {
+        self.blobs
+            .delete(&Self::notification_key(id))
+            .context("Failed to remove watcher notification")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Status;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_saves_are_recorded() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+
+        let mut ticket = Ticket::new("audit", "Audit me");
+        storage.save(&ticket).unwrap();
+        storage.save(&ticket).unwrap();
+        ticket.start();
+        storage.save(&ticket).unwrap();
+        storage
+            .update_ticket(&ticket.id, |t| t.assignee = Some("alice".to_string()))
+            .unwrap();
+        storage.delete(&ticket.id).unwrap();
+
+        let changes: Vec<Change> = storage
+            .load_history(&ticket.id)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.change)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                Change::Created,
+                Change::StatusChanged {
+                    from: Status::Todo,
+                    to: Status::Doing
+                },
+                Change::AssigneeChanged {
+                    from: None,
+                    to: Some("alice".to_string())
+                },
+                Change::Deleted,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_watched_changes_are_queued() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+
+        let mut ticket = Ticket::new("watched", "Watch me");
+        ticket.watch("someone-else");
+        storage.save(&ticket).unwrap();
+        assert!(storage.pending_notifications().unwrap().is_empty());
+
+        ticket.title = "Renamed".to_string();
+        storage.save(&ticket).unwrap();
+        assert!(storage.pending_notifications().unwrap().is_empty());
+
+        ticket.start();
+        storage.save(&ticket).unwrap();
+        let pending = storage.pending_notifications().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].recipients, vec!["someone-else"]);
+        assert_eq!(pending[0].changes[0].field, "status");
+
+        storage.remove_notification(&pending[0].id).unwrap();
+        assert!(storage.pending_notifications().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_configured_user_is_the_actor() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = crate::config::Config::default();
+        config.user.name = Some("Alice".to_string());
+        config
+            .save_to_path(temp_dir.path().join("config.yaml"))
+            .unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+
+        let ticket = Ticket::new("actor", "Record the actor");
+        storage.save(&ticket).unwrap();
+        let history = storage.load_history(&ticket.id).unwrap();
+        assert_eq!(history[0].actor.as_deref(), Some("Alice"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Exclude patterns for ticket discovery
+//!
+//! `.vibe-ticket/ignore` holds `.gitignore`-style patterns, relative to the
+//! `.vibe-ticket` directory, for data that commands listing the project's
+//! contents should skip, for example vendored or experimental sub-stores:
+//!
+//! ```text
+//! # Tickets synced from another tracker
+//! tickets/vendor-*.yaml
+//! # Specs still being drafted
+//! specs/draft-*/
+//! # Large traces
+//! attachments/**/*.trace
+//! ```
+//!
+//! Ignored tickets are left out of `load_all` and everything built on it
+//! (list, search, export, reports); they can still be opened by ID. Ignored
+//! spec directories are left out of spec listings, and ignored attachments
+//! out of shared documents.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Name of the ignore file in the `.vibe-ticket` directory
+pub const IGNORE_FILE: &str = "ignore";
+
+/// Patterns loaded from `.vibe-ticket/ignore`
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    matcher: Option<Gitignore>,
+}
+
+impl IgnoreRules {
+    /// Loads the ignore file of a `.vibe-ticket` directory
+    ///
+    /// A missing file ignores nothing. Invalid patterns are reported on
+    /// stderr and skipped, like unreadable ticket files.
+    pub fn load(vibe_dir: &Path) -> Self {
+        let path = vibe_dir.join(IGNORE_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                eprintln!("Warning: Failed to read {}: {e}", path.display());
+                return Self::default();
+            },
+        };
+        Self::parse(vibe_dir, &content)
+    }
+
+    /// Builds rules from the content of an ignore file
+    pub fn parse(vibe_dir: &Path, content: &str) -> Self {
+        let mut builder = GitignoreBuilder::new(vibe_dir);
+        for line in content.lines() {
+            if let Err(e) = builder.add_line(None, line) {
+                eprintln!("Warning: Skipping ignore pattern '{line}': {e}");
+            }
+        }
+
+        match builder.build() {
+            Ok(matcher) if !matcher.is_empty() => Self {
+                matcher: Some(matcher),
+            },
+            Ok(_) => Self::default(),
+            Err(e) => {
+                eprintln!("Warning: Failed to load ignore patterns: {e}");
+                Self::default()
+            },
+        }
+    }
+
+    /// Returns true if no patterns are configured
+    pub const fn is_empty(&self) -> bool {
+        self.matcher.is_none()
+    }
+
+    /// Returns true if `path`, relative to the `.vibe-ticket` directory
+    /// (e.g., `tickets/<id>.yaml`), or one of its parent directories is
+    /// excluded
+    pub fn is_ignored(&self, path: impl AsRef<Path>, is_dir: bool) -> bool {
+        self.matcher.as_ref().is_some_and(|matcher| {
+            matcher
+                .matched_path_or_any_parents(path, is_dir)
+                .is_ignore()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitignore_semantics() {
+        let rules = IgnoreRules::parse(
+            Path::new("/project/.vibe-ticket"),
+            "# comment\n\ntickets/vendor-*.yaml\n!tickets/vendor-keep.yaml\nspecs/draft-*/\nattachments/**/*.trace\n",
+        );
+
+        assert!(rules.is_ignored("tickets/vendor-1.yaml", false));
+        assert!(!rules.is_ignored("tickets/vendor-keep.yaml", false));
+        assert!(!rules.is_ignored("tickets/own.yaml", false));
+        assert!(rules.is_ignored("specs/draft-api", true));
+        assert!(rules.is_ignored("specs/draft-api/spec.json", false));
+        assert!(!rules.is_ignored("specs/api", true));
+        assert!(rules.is_ignored("attachments/abc/logs/run.trace", false));
+        assert!(!rules.is_ignored("attachments/abc/run.log", false));
+    }
+
+    #[test]
+    fn test_missing_file_ignores_nothing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let rules = IgnoreRules::load(temp_dir.path());
+        assert!(rules.is_empty());
+        assert!(!rules.is_ignored("tickets/a.yaml", false));
+    }
+}
@@ -0,0 +1,79 @@
+//! Persistence of the quick-capture inbox
+//!
+//! Inbox entries are stored together in `inbox.yaml`, oldest first. Captures
+//! and removals lock the file, so that an entry captured in one terminal
+//! while another processes the inbox is kept.
+
+use super::FileStorage;
+use crate::core::InboxEntry;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the inbox file
+const INBOX_KEY: &str = "inbox.yaml";
+
+impl FileStorage {
+    /// Loads the inbox entries, oldest first
+    ///
+    /// Returns an empty list if nothing was ever captured.
+    pub fn load_inbox(&self) -> Result<Vec<InboxEntry>> {
+        let Some(content) = self
+            .blobs
+            .get_string(INBOX_KEY)
+            .context("Failed to read the inbox")?
+        else {
+            return Ok(Vec::new());
+        };
+
+        serde_yaml::from_str(&content)
+            .map_err(|e| VibeTicketError::deserialization_error("inbox", e))
+    }
+
+    /// Appends `entry` to the inbox and returns the number of entries
+    pub fn capture(&self, entry: InboxEntry) -> Result<usize> {
+        self.update_inbox(|entries| entries.push(entry))
+    }
+
+    /// Removes `entry` from the inbox, once it was processed, and returns the
+    /// number of remaining entries
+    pub fn remove_from_inbox(&self, entry: &InboxEntry) -> Result<usize> {
+        self.update_inbox(|entries| entries.retain(|e| e != entry))
+    }
+
+    /// Applies `update` to the entries while holding the inbox lock
+    fn update_inbox(&self, update: impl FnOnce(&mut Vec<InboxEntry>)) -> Result<usize> {
+        let path = self.get_path(INBOX_KEY);
+        let _lock = self.lock(&path, "inbox").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for the inbox: {e}"))
+        })?;
+
+        let mut entries = self.load_inbox()?;
+        update(&mut entries);
+        let yaml = serde_yaml::to_string(&entries).context("Failed to serialize the inbox")?;
+        self.blobs
+            .put(INBOX_KEY, yaml.as_bytes())
+            .context("Failed to write the inbox")?;
+        Ok(entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_capture_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        assert!(storage.load_inbox().unwrap().is_empty());
+
+        let first = InboxEntry::new("random idea about caching");
+        assert_eq!(storage.capture(first.clone()).unwrap(), 1);
+        assert_eq!(storage.capture(InboxEntry::new("call back")).unwrap(), 2);
+
+        assert_eq!(storage.remove_from_inbox(&first).unwrap(), 1);
+        let entries = storage.load_inbox().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].text, "call back");
+    }
+}
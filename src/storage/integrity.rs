@@ -0,0 +1,319 @@
+//! Integrity constraints enforced when tickets are saved
+//!
+//! Every [`TicketRepository`](super::TicketRepository) backend must reject a
+//! save that would:
+//!
+//! - give the ticket the slug of another ticket,
+//! - add a reference (parent, dependency, blocked ticket or relation) to a
+//!   ticket that does not exist, or to the ticket itself,
+//! - change the status without keeping the close time consistent: closing a
+//!   ticket must record `closed_at`, moving it out of `done` must clear it.
+//!
+//! References already stored are not checked again, so tickets whose
+//! dependencies were deleted can still be edited. Slugs and references are
+//! checked against a [`TicketIndex`] of the IDs and slugs of all tickets,
+//! which backends only build when a save changes the slug or adds a
+//! reference.
+
+use std::collections::{HashMap, HashSet};
+
+use super::FileStorage;
+use crate::core::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+
+/// IDs and slugs of the tickets of a repository
+#[derive(Debug, Clone, Default)]
+pub struct TicketIndex {
+    /// Ticket of each slug
+    by_slug: HashMap<String, TicketId>,
+
+    /// Slug of each ticket
+    slugs: HashMap<TicketId, String>,
+
+    /// Tickets of the batch saved along that are closed
+    closed: HashSet<TicketId>,
+}
+
+impl TicketIndex {
+    /// Builds the index of the given tickets
+    pub fn new<'a>(tickets: impl IntoIterator<Item = (&'a TicketId, &'a str)>) -> Self {
+        let mut index = Self::default();
+        for (id, slug) in tickets {
+            index.insert(id, slug);
+        }
+        index
+    }
+
+    /// Adds a ticket, or updates its slug
+    pub fn insert(&mut self, id: &TicketId, slug: &str) {
+        if let Some(previous) = self.slugs.insert(id.clone(), slug.to_string()) {
+            if self.by_slug.get(&previous) == Some(id) {
+                self.by_slug.remove(&previous);
+            }
+        }
+        self.by_slug.insert(slug.to_string(), id.clone());
+    }
+
+    /// Adds a ticket about to be saved, rejecting a slug another ticket has
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VibeTicketError::DuplicateTicket`] if the slug is taken.
+    pub fn add(&mut self, ticket: &Ticket) -> Result<()> {
+        if self.slug_taken(ticket) {
+            return Err(VibeTicketError::DuplicateTicket {
+                slug: ticket.slug.clone(),
+            });
+        }
+        self.insert(&ticket.id, &ticket.slug);
+        if ticket.status == Status::Done {
+            self.closed.insert(ticket.id.clone());
+        } else {
+            self.closed.remove(&ticket.id);
+        }
+        Ok(())
+    }
+
+    /// Whether the ticket `id` is closed by the batch saved along, added
+    /// with [`Self::add`]
+    pub fn closes(&self, id: &TicketId) -> bool {
+        self.closed.contains(id)
+    }
+
+    /// Whether another ticket has the slug of `ticket`
+    fn slug_taken(&self, ticket: &Ticket) -> bool {
+        self.by_slug
+            .get(&ticket.slug)
+            .is_some_and(|id| id != &ticket.id)
+    }
+
+    /// Whether the index has the ticket `id`
+    pub fn contains(&self, id: &TicketId) -> bool {
+        self.slugs.contains_key(id)
+    }
+
+    /// Checks that `ticket` keeps its slug unique and only adds references
+    /// to existing tickets, compared with its stored version `before`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VibeTicketError::DuplicateTicket`] if another ticket has
+    /// the slug, and [`VibeTicketError::DanglingReference`] for a reference
+    /// to a missing ticket or to the ticket itself.
+    pub fn check(&self, ticket: &Ticket, before: Option<&Ticket>) -> Result<()> {
+        if self.slug_taken(ticket) {
+            return Err(VibeTicketError::DuplicateTicket {
+                slug: ticket.slug.clone(),
+            });
+        }
+
+        for (field, id) in added_references(ticket, before) {
+            if id == &ticket.id || !self.contains(id) {
+                return Err(VibeTicketError::DanglingReference {
+                    slug: ticket.slug.clone(),
+                    field: field.to_string(),
+                    id: id.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FileStorage {
+    /// Builds the index of the stored tickets
+    pub fn ticket_index(&self) -> Result<TicketIndex> {
+        let summaries = self.summaries()?;
+        Ok(TicketIndex::new(
+            summaries
+                .iter()
+                .map(|summary| (&summary.id, summary.slug.as_str())),
+        ))
+    }
+
+    /// Checks the integrity constraints of saving `ticket` over `before`,
+    /// building the index of the stored tickets if `index` is not given
+    /// and the save needs it
+    pub(super) fn check_integrity(
+        &self,
+        ticket: &Ticket,
+        before: Option<&Ticket>,
+        index: Option<&TicketIndex>,
+    ) -> Result<()> {
+        check_close_time(ticket, before)?;
+        match index {
+            Some(index) => index.check(ticket, before),
+            None if needs_index(ticket, before) => self.ticket_index()?.check(ticket, before),
+            None => Ok(()),
+        }
+    }
+
+    /// Saves several tickets, which may refer to each other
+    ///
+    /// The tickets are written in one [`super::Transaction`]: every ticket
+    /// is checked before any is written, so a batch that breaks a
+    /// constraint leaves the storage unchanged, and a crash while writing
+    /// leaves either all tickets written or none.
+    pub fn save_tickets(&self, tickets: &[Ticket]) -> Result<()> {
+        let mut transaction = self.transaction();
+        for ticket in tickets {
+            transaction.save(ticket.clone());
+        }
+        transaction.commit()
+    }
+}
+
+/// Whether saving `ticket` over `before` needs the index: it is new, its
+/// slug changes or it gains references
+pub fn needs_index(ticket: &Ticket, before: Option<&Ticket>) -> bool {
+    before.is_none_or(|before| before.slug != ticket.slug)
+        || added_references(ticket, before).next().is_some()
+}
+
+/// Checks that a status change keeps the close time consistent
+///
+/// # Errors
+///
+/// Returns [`VibeTicketError::InconsistentCloseTime`] if `ticket` is closed
+/// without `closed_at`, or leaves `done` with `closed_at` still set.
+pub fn check_close_time(ticket: &Ticket, before: Option<&Ticket>) -> Result<()> {
+    let Some(before) = before else {
+        return Ok(());
+    };
+    let reason = match (before.status, ticket.status) {
+        (from, to) if from == to => return Ok(()),
+        (_, Status::Done) if ticket.closed_at.is_none() => "a closed ticket needs a close time",
+        (Status::Done, _) if ticket.closed_at.is_some() => {
+            "a reopened ticket must clear its close time"
+        },
+        _ => return Ok(()),
+    };
+    Err(VibeTicketError::InconsistentCloseTime {
+        slug: ticket.slug.clone(),
+        from: before.status.to_string(),
+        to: ticket.status.to_string(),
+        reason: reason.to_string(),
+    })
+}
+
+/// References of `ticket` that `before` does not have, with their field
+fn added_references<'a>(
+    ticket: &'a Ticket,
+    before: Option<&'a Ticket>,
+) -> impl Iterator<Item = (&'static str, &'a TicketId)> {
+    let parent = ticket
+        .parent
+        .as_ref()
+        .filter(|parent| before.is_none_or(|before| before.parent.as_ref() != Some(*parent)))
+        .map(|parent| ("parent", parent));
+    let depends_on = ticket
+        .depends_on
+        .iter()
+        .filter(move |id| before.is_none_or(|before| !before.depends_on.contains(id)))
+        .map(|id| ("depends_on", id));
+    let blocks = ticket
+        .blocks
+        .iter()
+        .filter(move |id| before.is_none_or(|before| !before.blocks.contains(id)))
+        .map(|id| ("blocks", id));
+    let relations = ticket
+        .relations
+        .iter()
+        .filter(move |relation| before.is_none_or(|before| !before.relations.contains(relation)))
+        .map(|relation| ("relations", &relation.ticket));
+    parent
+        .into_iter()
+        .chain(depends_on)
+        .chain(blocks)
+        .chain(relations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_added_references_are_checked() {
+        let parent = Ticket::new("parent", "Parent");
+        let mut ticket = Ticket::new("child", "Child");
+        let index = TicketIndex::new([
+            (&parent.id, parent.slug.as_str()),
+            (&ticket.id, ticket.slug.as_str()),
+        ]);
+
+        let before = ticket.clone();
+        ticket.parent = Some(parent.id.clone());
+        assert!(needs_index(&ticket, Some(&before)));
+        index.check(&ticket, Some(&before)).unwrap();
+
+        // A deleted dependency does not block later saves
+        let mut stale = ticket.clone();
+        stale.depends_on.push(TicketId::new());
+        assert!(!needs_index(&stale, Some(&stale)));
+        assert!(matches!(
+            index.check(&stale, Some(&ticket)),
+            Err(VibeTicketError::DanglingReference { .. })
+        ));
+    }
+
+    #[test]
+    fn test_index_tracks_renamed_slugs() {
+        let a = Ticket::new("a", "A");
+        let b = Ticket::new("b", "B");
+        let mut index = TicketIndex::new([(&a.id, "a"), (&b.id, "b")]);
+
+        let mut renamed = b.clone();
+        renamed.slug = "a".to_string();
+        assert!(matches!(
+            index.check(&renamed, Some(&b)),
+            Err(VibeTicketError::DuplicateTicket { .. })
+        ));
+
+        index.insert(&a.id, "c");
+        index.check(&renamed, Some(&b)).unwrap();
+    }
+
+    #[test]
+    fn test_save_tickets_restores_the_batch_on_failure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = FileStorage::with_blob_store(
+            temp_dir.path(),
+            std::sync::Arc::new(crate::storage::MemoryStore::new()),
+        )
+        .with_immutable_closed(true);
+        let open = Ticket::new("open", "Open");
+        let mut closed = Ticket::new("closed", "Closed");
+        closed.status = Status::Done;
+        closed.closed_at = Some(chrono::Utc::now());
+        storage.save_ticket(&open).unwrap();
+        storage.save_ticket(&closed).unwrap();
+        let open = storage.load_ticket(&open.id).unwrap();
+
+        let mut edited = [open.clone(), closed];
+        for ticket in &mut edited {
+            ticket.tags.push("bulk".to_string());
+        }
+        assert!(matches!(
+            storage.save_tickets(&edited),
+            Err(VibeTicketError::TicketClosed { .. })
+        ));
+        assert_eq!(storage.load_ticket(&open.id).unwrap(), open);
+    }
+
+    #[test]
+    fn test_status_changes_keep_the_close_time() {
+        let open = Ticket::new("a", "A");
+        let mut closed = open.clone();
+        closed.status = Status::Done;
+        assert!(check_close_time(&closed, Some(&open)).is_err());
+
+        closed.closed_at = Some(chrono::Utc::now());
+        check_close_time(&closed, Some(&open)).unwrap();
+
+        let mut reopened = closed.clone();
+        reopened.status = Status::Doing;
+        assert!(check_close_time(&reopened, Some(&closed)).is_err());
+        reopened.closed_at = None;
+        check_close_time(&reopened, Some(&closed)).unwrap();
+    }
+}
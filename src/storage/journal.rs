@@ -0,0 +1,331 @@
+//! Operation journal backing `undo`
+//!
+//! Before `FileStorage` writes or deletes a ticket, it appends the ticket's
+//! previous state (or `None` for a new ticket) to the journal of the current
+//! operation, stored as JSON lines under `journal/<operation-id>.jsonl`.
+//! An operation is one CLI invocation, or one MCP tool call; operation IDs
+//! start with a timestamp, so the journal files sort oldest first. MCP tool
+//! calls may run concurrently, so their operation is tracked per task (see
+//! [`in_operation`]) rather than for the process. Appends to the journal
+//! take the journal lock, so concurrent writers do not lose entries.
+//!
+//! Undoing an operation writes the recorded states back in reverse order,
+//! so a ticket changed several times ends in the state it had before the
+//! operation. Restoring bypasses the journal itself and the read-only
+//! policy for closed tickets, since undoing a close must work under it.
+//! Only the last [`JOURNAL_LIMIT`] operations are kept.
+
+use std::future::Future;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::FileStorage;
+use super::event_log::StorageEventKind;
+use crate::core::{Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the journal directory
+const JOURNAL_KEY: &str = "journal";
+
+/// Number of operations kept in the journal
+pub const JOURNAL_LIMIT: usize = 50;
+
+/// Operation that storage writes of the process are recorded under
+static OPERATION: Mutex<Option<OperationId>> = Mutex::new(None);
+
+tokio::task_local! {
+    /// Operation that storage writes of the task are recorded under
+    static TASK_OPERATION: OperationId;
+}
+
+/// Identity of an operation
+#[derive(Debug, Clone)]
+struct OperationId {
+    id: String,
+    command: String,
+}
+
+/// State of one ticket before an operation changed it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Command line or tool call of the operation
+    pub command: String,
+    /// When the ticket was changed
+    pub at: DateTime<Utc>,
+    /// The changed ticket
+    pub ticket_id: TicketId,
+    /// The ticket before the change, `None` if it was created
+    pub before: Option<Ticket>,
+}
+
+/// Journaled changes of one operation
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// Operation ID, the journal file name without extension
+    pub id: String,
+    /// Changes in the order they were made
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Operation {
+    /// Command line or tool call of the operation
+    pub fn command(&self) -> &str {
+        self.entries.first().map_or("", |e| e.command.as_str())
+    }
+
+    /// IDs of the changed tickets, without repetitions
+    pub fn ticket_ids(&self) -> Vec<&TicketId> {
+        let mut ids: Vec<&TicketId> = Vec::new();
+        for entry in &self.entries {
+            if !ids.contains(&&entry.ticket_id) {
+                ids.push(&entry.ticket_id);
+            }
+        }
+        ids
+    }
+}
+
+/// Starts a new operation; later storage writes are undone together
+///
+/// The CLI does not need to call this: without it, all writes of the
+/// process form one operation labeled with its command line.
+pub fn begin_operation(command: impl Into<String>) {
+    let mut current = OPERATION
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    *current = Some(OperationId::new(command.into()));
+}
+
+/// Runs `call` as an operation of its own, undone together
+///
+/// Storage writes made while `call` runs are recorded under this operation
+/// whatever other tasks of the process do in the meantime.
+pub async fn in_operation<F: Future>(command: impl Into<String>, call: F) -> F::Output {
+    TASK_OPERATION
+        .scope(OperationId::new(command.into()), call)
+        .await
+}
+
+/// The current operation: that of the task, or else that of the process,
+/// started on first use
+fn current_operation() -> OperationId {
+    if let Ok(operation) = TASK_OPERATION.try_with(Clone::clone) {
+        return operation;
+    }
+    let mut current = OPERATION
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    current
+        .get_or_insert_with(|| {
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            OperationId::new(args.join(" "))
+        })
+        .clone()
+}
+
+impl OperationId {
+    fn new(command: String) -> Self {
+        static COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+        let sequence = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self {
+            id: format!(
+                "{}-{}-{sequence:06}",
+                Utc::now().format("%Y%m%dT%H%M%S%.6f"),
+                std::process::id()
+            ),
+            command,
+        }
+    }
+}
+
+impl FileStorage {
+    /// Returns the blob store key of an operation's journal
+    fn journal_key(operation: &str) -> String {
+        format!("{JOURNAL_KEY}/{operation}.jsonl")
+    }
+
+    /// Appends the state of a ticket before a change to the journal
+    pub(crate) fn journal(&self, ticket_id: &TicketId, before: Option<&Ticket>) -> Result<()> {
+        let operation = current_operation();
+        let _lock = self
+            .lock(&self.get_path(JOURNAL_KEY), "journal")
+            .map_err(|e| {
+                VibeTicketError::custom(format!("Failed to acquire lock for the journal: {e}"))
+            })?;
+        let key = Self::journal_key(&operation.id);
+        let mut content = self.blobs.get(&key).context("Failed to read the journal")?;
+        let new_operation = content.is_none();
+
+        let entry = JournalEntry {
+            command: operation.command,
+            at: Utc::now(),
+            ticket_id: ticket_id.clone(),
+            before: before.cloned(),
+        };
+        let content = content.get_or_insert_with(Vec::new);
+        serde_json::to_writer(&mut *content, &entry)?;
+        content.push(b'\n');
+        self.blobs
+            .put(&key, content)
+            .context("Failed to write the journal")?;
+
+        if new_operation {
+            self.prune_journal()?;
+        }
+        Ok(())
+    }
+
+    /// Removes the oldest operations beyond [`JOURNAL_LIMIT`]
+    fn prune_journal(&self) -> Result<()> {
+        let keys = self
+            .blobs
+            .list(JOURNAL_KEY)
+            .context("Failed to read the journal")?;
+        for key in keys.iter().take(keys.len().saturating_sub(JOURNAL_LIMIT)) {
+            self.blobs
+                .delete(key)
+                .context("Failed to prune the journal")?;
+        }
+        Ok(())
+    }
+
+    /// Loads the journaled operations, most recent first
+    ///
+    /// Lines that cannot be parsed are skipped.
+    pub fn load_journal(&self) -> Result<Vec<Operation>> {
+        let mut operations = Vec::new();
+        for key in self
+            .blobs
+            .list(JOURNAL_KEY)
+            .context("Failed to read the journal")?
+            .into_iter()
+            .rev()
+        {
+            let Some(content) = self
+                .blobs
+                .get_string(&key)
+                .context("Failed to read the journal")?
+            else {
+                continue;
+            };
+            let id = key
+                .strip_prefix(&format!("{JOURNAL_KEY}/"))
+                .and_then(|name| name.strip_suffix(".jsonl"))
+                .unwrap_or(&key)
+                .to_string();
+            let entries: Vec<JournalEntry> = content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect();
+            if !entries.is_empty() {
+                operations.push(Operation { id, entries });
+            }
+        }
+        Ok(operations)
+    }
+
+    /// Reverts the most recent operation and removes it from the journal
+    ///
+    /// Returns the reverted operation, or `None` if the journal is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a ticket cannot be locked or written
+    pub fn undo_operation(&self) -> Result<Option<Operation>> {
+        let Some(operation) = self.load_journal()?.into_iter().next() else {
+            return Ok(None);
+        };
+
+        for entry in operation.entries.iter().rev() {
+            self.restore(&entry.ticket_id, entry.before.as_ref())?;
+        }
+        self.blobs
+            .delete(&Self::journal_key(&operation.id))
+            .context("Failed to write the journal")?;
+
+        Ok(Some(operation))
+    }
+
+    /// Writes back the state of a ticket, deleting it if it did not exist
+    pub(super) fn restore(&self, id: &TicketId, before: Option<&Ticket>) -> Result<()> {
+        let path = self.ticket_path(id);
+        let _lock = self.lock(&path, "undo").map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for undoing: {e}"))
+        })?;
+
+        let key = Self::ticket_key(id);
+        let current = self
+            .blobs
+            .get_string(&key)
+            .with_context(|| format!("Failed to read ticket from {}", path.display()))?
+            .and_then(|yaml| serde_yaml::from_str::<Ticket>(&yaml).ok());
+
+        match before {
+            Some(ticket) => {
+                let yaml = serde_yaml::to_string(ticket).context("Failed to serialize ticket")?;
+                self.blobs
+                    .put(&key, yaml.as_bytes())
+                    .with_context(|| format!("Failed to write ticket to {}", path.display()))?;
+                self.update_index(&key, Some(ticket));
+                self.log_event(StorageEventKind::Saved, Some(id));
+                self.record_changes(current.as_ref(), ticket)?;
+            },
+            None => {
+                if self
+                    .blobs
+                    .delete(&key)
+                    .with_context(|| format!("Failed to delete ticket at {}", path.display()))?
+                {
+                    self.update_index(&key, None);
+                    self.log_event(StorageEventKind::Deleted, Some(id));
+                    self.record(id, vec![crate::core::audit::Change::Deleted])?;
+                }
+            },
+        }
+
+        self.cache.invalidate_ticket(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Status;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_undo_restores_previous_states() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path()).with_immutable_closed(true);
+
+        begin_operation("new");
+        let mut ticket = Ticket::new("undo-me", "Undo me");
+        storage.save(&ticket).unwrap();
+
+        begin_operation("close undo-me");
+        ticket.status = Status::Done;
+        ticket.closed_at = Some(Utc::now());
+        storage.save(&ticket).unwrap();
+        let created = Ticket::new("created", "Created");
+        storage.save(&created).unwrap();
+
+        let journal = storage.load_journal().unwrap();
+        assert_eq!(journal.len(), 2);
+        assert_eq!(journal[0].command(), "close undo-me");
+        assert_eq!(journal[0].ticket_ids().len(), 2);
+
+        let undone = storage.undo_operation().unwrap().unwrap();
+        assert_eq!(undone.command(), "close undo-me");
+        let fresh = FileStorage::new(temp.path());
+        assert_eq!(fresh.load(&ticket.id).unwrap().status, Status::Todo);
+        assert!(fresh.load(&created.id).is_err());
+
+        storage.undo_operation().unwrap().unwrap();
+        assert!(FileStorage::new(temp.path()).load(&ticket.id).is_err());
+        assert!(storage.undo_operation().unwrap().is_none());
+    }
+}
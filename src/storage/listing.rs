@@ -0,0 +1,320 @@
+//! Listing index of the ticket files
+//!
+//! `list`, slug lookups and title or tag searches only need a few fields of
+//! every ticket. The listing index, `.vibe-ticket/index.json`, keeps a
+//! [`TicketSummary`] of each ticket file together with the time the file was
+//! last written, and is updated whenever `FileStorage` writes or deletes a
+//! ticket.
+//!
+//! The index is a cache, never the source of truth. Reading it compares the
+//! recorded write times with those of the ticket files and parses only the
+//! files that were added or changed behind its back (by another process that
+//! lost a race to update it, a `git pull`, or a hand edit). Files of a store
+//! that does not track write times are always parsed. An index that cannot
+//! be read is rebuilt; `vibe-ticket doctor --rebuild-index` does so on
+//! request.
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::{FileStorage, TicketSummary};
+use crate::core::Ticket;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the listing index
+pub const INDEX_KEY: &str = "index.json";
+
+/// Version of the index format; indexes of other versions are rebuilt
+const INDEX_VERSION: u32 = 1;
+
+/// Contents of `index.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ListingIndex {
+    /// Format version
+    version: u32,
+
+    /// Entry of each ticket file, by blob store key
+    entries: BTreeMap<String, IndexEntry>,
+}
+
+/// What the index keeps of a ticket file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    /// Time the file was written, if the store tracks it
+    modified: Option<SystemTime>,
+
+    /// Summary of the ticket in the file
+    ticket: TicketSummary,
+}
+
+/// Differences between the listing index and the ticket files
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexStatus {
+    /// Whether the index file exists and could be read
+    pub readable: bool,
+
+    /// Number of ticket files
+    pub tickets: usize,
+
+    /// Ticket files the index has no entry for
+    pub missing: Vec<String>,
+
+    /// Ticket files written since their entry was recorded
+    pub stale: Vec<String>,
+
+    /// Entries of ticket files that no longer exist
+    pub removed: Vec<String>,
+}
+
+impl IndexStatus {
+    /// Whether the index matches the ticket files
+    pub fn is_current(&self) -> bool {
+        self.readable && self.missing.is_empty() && self.stale.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl FileStorage {
+    /// Loads the summary of every ticket through the listing index
+    ///
+    /// Ticket files changed since they were indexed are parsed and the index
+    /// is brought up to date. Tickets that cannot be parsed are reported and
+    /// skipped, matching the behavior of `load_all_tickets`.
+    pub fn summaries(&self) -> Result<Vec<TicketSummary>> {
+        let mut index = self.read_index().unwrap_or_default();
+        let keys = self.ticket_keys()?;
+        let mut changed = index.entries.len() != keys.len();
+        let mut entries = BTreeMap::new();
+
+        for key in keys {
+            let modified = self.blobs.modified(&key)?;
+            let cached = index
+                .entries
+                .remove(&key)
+                .filter(|entry| entry.modified.is_some() && entry.modified == modified);
+            if let Some(entry) = cached {
+                entries.insert(key, entry);
+            } else if let Some(ticket) = self.parse_summary(&key)? {
+                changed |= modified.is_some();
+                entries.insert(key, IndexEntry { modified, ticket });
+            } else {
+                changed = true;
+            }
+        }
+
+        let summaries = entries.values().map(|entry| entry.ticket.clone()).collect();
+        if changed {
+            index.entries = entries;
+            // The index is only a cache; the next read repairs a failed write
+            let _ = self.write_index(&mut index);
+        }
+        Ok(summaries)
+    }
+
+    /// Rebuilds the listing index from the ticket files
+    ///
+    /// Returns the number of indexed tickets.
+    pub fn rebuild_index(&self) -> Result<usize> {
+        Ok(self.rebuild_index_with(|_, _| true)?.unwrap_or_default())
+    }
+
+    /// Like [`Self::rebuild_index`], reporting each ticket file read
+    ///
+    /// `step` receives the number of files read and their total, and stops
+    /// the rebuild by returning false, leaving the index as it was. Returns
+    /// `None` if the rebuild was stopped.
+    pub fn rebuild_index_with<F>(&self, mut step: F) -> Result<Option<usize>>
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let mut index = ListingIndex::default();
+        let keys = self.ticket_keys()?;
+        let total = keys.len();
+        for (read, key) in (1..).zip(keys) {
+            let modified = self.blobs.modified(&key)?;
+            if let Some(ticket) = self.parse_summary(&key)? {
+                index.entries.insert(key, IndexEntry { modified, ticket });
+            }
+            if !step(read, total) {
+                return Ok(None);
+            }
+        }
+        self.write_index(&mut index)?;
+        Ok(Some(index.entries.len()))
+    }
+
+    /// Compares the listing index with the ticket files without changing it
+    pub fn index_status(&self) -> Result<IndexStatus> {
+        let index = self.read_index();
+        let mut status = IndexStatus {
+            readable: index.is_some(),
+            ..IndexStatus::default()
+        };
+        let mut entries = index.map(|index| index.entries).unwrap_or_default();
+
+        for key in self.ticket_keys()? {
+            status.tickets += 1;
+            let modified = self.blobs.modified(&key)?;
+            match entries.remove(&key) {
+                None => status.missing.push(key),
+                Some(entry) if entry.modified.is_none() || entry.modified != modified => {
+                    status.stale.push(key);
+                },
+                Some(_) => {},
+            }
+        }
+        status.removed = entries.into_keys().collect();
+        Ok(status)
+    }
+
+    /// Records the ticket just written under `key`, or its deletion when
+    /// `ticket` is `None`, in the listing index
+    ///
+    /// An index that cannot be read is left for the next read to rebuild.
+    /// Failures are ignored: the entry then no longer matches the write time
+    /// of the file and the next read refreshes it.
+    pub(super) fn update_index(&self, key: &str, ticket: Option<&Ticket>) {
+        let update = || -> Result<()> {
+            let path = self.get_path(INDEX_KEY);
+            let _lock = self
+                .lock(&path, "update_index")
+                .map_err(|e| VibeTicketError::custom(e.to_string()))?;
+            let Some(mut index) = self.read_index() else {
+                return Ok(());
+            };
+            match ticket {
+                Some(ticket) => {
+                    let entry = IndexEntry {
+                        modified: self.blobs.modified(key)?,
+                        ticket: TicketSummary::from(ticket),
+                    };
+                    index.entries.insert(key.to_string(), entry);
+                },
+                None => {
+                    index.entries.remove(key);
+                },
+            }
+            self.write_index(&mut index)
+        };
+        let _ = update();
+    }
+
+    /// Reads the listing index, or `None` if it is missing, unreadable or
+    /// of another version
+    fn read_index(&self) -> Option<ListingIndex> {
+        let json = self.blobs.get_string(INDEX_KEY).ok()??;
+        serde_json::from_str::<ListingIndex>(&json)
+            .ok()
+            .filter(|index| index.version == INDEX_VERSION)
+    }
+
+    /// Writes the listing index
+    fn write_index(&self, index: &mut ListingIndex) -> Result<()> {
+        index.version = INDEX_VERSION;
+        let json = serde_json::to_vec(index).context("Failed to serialize the index")?;
+        self.blobs
+            .put(INDEX_KEY, &json)
+            .context("Failed to write the index")
+    }
+
+    /// Reads the summary of the ticket file under `key`
+    ///
+    /// Returns `None` for a ticket deleted since listing, or one that cannot
+    /// be parsed, which is reported.
+    fn parse_summary(&self, key: &str) -> Result<Option<TicketSummary>> {
+        let Some((path, yaml)) = self.read_ticket_file(key)? else {
+            return Ok(None);
+        };
+        match serde_yaml::from_str::<TicketSummary>(&yaml) {
+            Ok(summary) => Ok(Some(summary)),
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to load ticket from {}: {e}",
+                    path.display()
+                );
+                Ok(None)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Status;
+    use crate::storage::{MemoryStore, TicketRepository};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_follows_saves() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        let mut ticket = Ticket::new("index-me", "Index me");
+        storage.save(&ticket).unwrap();
+        storage.save(&Ticket::new("other", "Other")).unwrap();
+
+        assert_eq!(storage.summaries().unwrap().len(), 2);
+        assert!(storage.index_status().unwrap().is_current());
+
+        ticket.status = Status::Doing;
+        ticket.tags.push("search".to_string());
+        storage.save(&ticket).unwrap();
+        let status = storage.index_status().unwrap();
+        assert!(status.is_current(), "{status:?}");
+        let summary = storage
+            .summaries()
+            .unwrap()
+            .into_iter()
+            .find(|summary| summary.id == ticket.id)
+            .unwrap();
+        assert_eq!(summary.status, Status::Doing);
+        assert_eq!(summary.tags, vec!["search"]);
+
+        storage.delete(&ticket.id).unwrap();
+        assert!(storage.index_status().unwrap().is_current());
+        assert_eq!(storage.summaries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_index_notices_changed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        let ticket = Ticket::new("edited", "Before");
+        storage.save(&ticket).unwrap();
+        storage.summaries().unwrap();
+
+        // An edit behind the storage's back
+        let path = storage.ticket_path(&ticket.id);
+        let yaml = std::fs::read_to_string(&path).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        std::fs::write(&path, yaml.replace("title: Before", "title: After")).unwrap();
+        assert_eq!(storage.index_status().unwrap().stale.len(), 1);
+        assert_eq!(storage.summaries().unwrap()[0].title, "After");
+        assert!(storage.index_status().unwrap().is_current());
+
+        // A corrupt index is rebuilt
+        std::fs::write(temp_dir.path().join(INDEX_KEY), "{").unwrap();
+        assert!(!storage.index_status().unwrap().readable);
+        assert_eq!(storage.summaries().unwrap().len(), 1);
+        std::fs::write(temp_dir.path().join(INDEX_KEY), "{").unwrap();
+        assert_eq!(storage.rebuild_index_with(|_, _| false).unwrap(), None);
+        assert!(!storage.index_status().unwrap().readable);
+        assert_eq!(storage.rebuild_index().unwrap(), 1);
+        assert!(storage.index_status().unwrap().is_current());
+    }
+
+    #[test]
+    fn test_summaries_without_write_times() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::with_blob_store(temp_dir.path(), Arc::new(MemoryStore::new()));
+        let ticket = Ticket::new("memory", "Memory");
+        storage.save(&ticket).unwrap();
+        assert_eq!(storage.summaries().unwrap()[0].slug, "memory");
+        storage
+            .update_ticket(&ticket.id, |t| t.title = "Renamed".to_string())
+            .unwrap();
+        assert_eq!(storage.summaries().unwrap()[0].title, "Renamed");
+    }
+}
@@ -7,17 +7,68 @@ use serde::{Deserialize, Serialize};
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Write as IoWrite};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-/// Maximum time a lock can be held before it's considered stale
-const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+use crate::config::LockConfig;
 
-/// Maximum number of retry attempts for acquiring a lock
-const MAX_RETRY_ATTEMPTS: u32 = 10;
+/// Longest wait for a lock given on the command line with `--wait`
+static WAIT_OVERRIDE: OnceLock<Duration> = OnceLock::new();
 
-/// Delay between retry attempts
-const RETRY_DELAY: Duration = Duration::from_millis(100);
+/// Makes every lock taken by this process wait up to `wait`, whatever the
+/// configuration says
+///
+/// Only the first call has an effect.
+pub fn override_lock_wait(wait: Duration) {
+    let _ = WAIT_OVERRIDE.set(wait);
+}
+
+/// How long to wait for a lock and when to take it over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockSettings {
+    /// Attempts to take the lock before giving up
+    pub retries: u32,
+    /// Delay between attempts
+    pub retry_delay: Duration,
+    /// Age beyond which a lock is considered stale and taken over
+    pub stale_after: Duration,
+}
+
+impl Default for LockSettings {
+    fn default() -> Self {
+        Self::from_config(&LockConfig::default())
+    }
+}
+
+impl LockSettings {
+    /// Settings of `storage.lock` in the configuration
+    pub fn from_config(config: &LockConfig) -> Self {
+        Self {
+            retries: config.retries.max(1),
+            retry_delay: Duration::from_millis(config.retry_delay_ms),
+            stale_after: Duration::from_secs(config.stale_after_secs),
+        }
+    }
+
+    /// Settings of `storage.lock`, with the wait of [`override_lock_wait`]
+    /// if one was given
+    pub fn configured(config: &LockConfig) -> Self {
+        let settings = Self::from_config(config);
+        WAIT_OVERRIDE
+            .get()
+            .map_or(settings, |wait| settings.with_wait(*wait))
+    }
+
+    /// Retries as often as fits into `wait`
+    #[must_use]
+    pub fn with_wait(mut self, wait: Duration) -> Self {
+        let delay = self.retry_delay.max(Duration::from_millis(1));
+        let retries = wait.as_millis().div_ceil(delay.as_millis()) + 1;
+        self.retries = u32::try_from(retries).unwrap_or(u32::MAX);
+        self
+    }
+}
 
 /// Information stored in a lock file
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,6 +83,78 @@ pub(crate) struct LockInfo {
     pub(crate) operation: Option<String>,
 }
 
+/// Lock file found on disk, for diagnostics
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeldLock {
+    /// Path of the lock file
+    pub path: PathBuf,
+    /// Process holding the lock
+    pub pid: u32,
+    /// Operation the lock was taken for
+    pub operation: Option<String>,
+    /// Seconds since the lock was taken
+    pub age_secs: u64,
+}
+
+impl HeldLock {
+    /// Reads the lock file at `lock_path`, if it exists and is readable
+    pub fn read(lock_path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(lock_path).ok()?;
+        let info: LockInfo = serde_json::from_str(&contents).ok()?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(Self {
+            path: lock_path.to_path_buf(),
+            pid: info.pid,
+            operation: info.operation,
+            age_secs: now.saturating_sub(info.acquired_at),
+        })
+    }
+
+    /// Lists the lock files under `dir` and its subdirectories, oldest
+    /// first
+    ///
+    /// Lock files that cannot be read, usually because they were just
+    /// released, are left out.
+    pub fn find(dir: &Path) -> Vec<Self> {
+        let mut locks = Vec::new();
+        let mut dirs = vec![dir.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for path in fs::read_dir(&dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+            {
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path
+                    .extension()
+                    .is_some_and(|extension| extension == "lock")
+                {
+                    locks.extend(Self::read(&path));
+                }
+            }
+        }
+        locks.sort_by_key(|lock| std::cmp::Reverse(lock.age_secs));
+        locks
+    }
+
+    /// Whether the lock is old enough for the next writer to take it over
+    pub const fn is_stale(&self, settings: &LockSettings) -> bool {
+        self.age_secs > settings.stale_after.as_secs()
+    }
+}
+
+impl std::fmt::Display for HeldLock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "by process {}", self.pid)?;
+        if let Some(operation) = &self.operation {
+            write!(f, " for {operation}")?;
+        }
+        write!(f, " since {}s", self.age_secs)
+    }
+}
+
 /// A file lock that automatically releases on drop
 pub struct FileLock {
     path: PathBuf,
@@ -48,11 +171,24 @@ impl FileLock {
     /// # Returns
     /// A FileLock that will automatically release when dropped
     pub fn acquire(path: &Path, operation: Option<String>) -> Result<Self> {
+        Self::acquire_with(path, operation, &LockSettings::default())
+    }
+
+    /// Attempts to acquire a lock for the given path, waiting as `settings`
+    /// say
+    ///
+    /// If the lock is still held after the last attempt, the error names its
+    /// holder.
+    pub fn acquire_with(
+        path: &Path,
+        operation: Option<String>,
+        settings: &LockSettings,
+    ) -> Result<Self> {
         let lock_path = Self::lock_path(path);
         let holder_id = Uuid::new_v4().to_string();
 
         // Try to acquire the lock with retries
-        for attempt in 0..MAX_RETRY_ATTEMPTS {
+        for attempt in 0..settings.retries {
             match Self::try_acquire_once(&lock_path, &holder_id, &operation) {
                 Ok(_) => {
                     return Ok(FileLock {
@@ -61,19 +197,25 @@ impl FileLock {
                     });
                 },
                 Err(e) => {
-                    if attempt == MAX_RETRY_ATTEMPTS - 1 {
-                        return Err(e).context("Failed to acquire lock after maximum retries");
+                    if attempt == settings.retries - 1 {
+                        let holder = HeldLock::read(&lock_path)
+                            .map(|held| format!(" (held {held})"))
+                            .unwrap_or_default();
+                        return Err(e).context(format!(
+                            "Failed to acquire lock after {} attempts{holder}",
+                            settings.retries
+                        ));
                     }
 
                     // Check if the existing lock is stale
-                    if Self::is_lock_stale(&lock_path)? {
+                    if Self::is_lock_stale(&lock_path, settings.stale_after) {
                         // Try to remove stale lock
                         let _ = fs::remove_file(&lock_path);
                         continue;
                     }
 
                     // Wait before retrying
-                    std::thread::sleep(RETRY_DELAY);
+                    std::thread::sleep(settings.retry_delay);
                 },
             }
         }
@@ -111,25 +253,34 @@ impl FileLock {
         Ok(())
     }
 
-    /// Checks if a lock file is stale (older than LOCK_TIMEOUT)
-    fn is_lock_stale(lock_path: &Path) -> Result<bool> {
-        if !lock_path.exists() {
-            return Ok(false);
-        }
-
-        let mut file = File::open(lock_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-
-        let lock_info: LockInfo = serde_json::from_str(&contents)?;
+    /// Checks if a lock file is stale (older than `stale_after`)
+    ///
+    /// A lock released meanwhile is not stale. A lock file its holder is
+    /// still writing, or that a crash left half-written, is aged by its
+    /// modification time.
+    fn is_lock_stale(lock_path: &Path, stale_after: Duration) -> bool {
+        let Ok(contents) = fs::read_to_string(lock_path) else {
+            return false;
+        };
+        let acquired_at = match serde_json::from_str::<LockInfo>(&contents) {
+            Ok(lock_info) => Some(lock_info.acquired_at),
+            Err(_) => fs::metadata(lock_path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|since_epoch| since_epoch.as_secs()),
+        };
+        let Some(acquired_at) = acquired_at else {
+            return false;
+        };
 
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
-        let age = now.saturating_sub(lock_info.acquired_at);
-        Ok(age > LOCK_TIMEOUT.as_secs())
+        let age = now.saturating_sub(acquired_at);
+        age > stale_after.as_secs()
     }
 
     /// Gets the lock file path for a given file
@@ -203,6 +354,33 @@ mod tests {
         let _lock2 = FileLock::acquire(&file_path, None).unwrap();
     }
 
+    #[test]
+    fn test_wait_and_holder_diagnostics() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.yaml");
+        let _lock = FileLock::acquire(&file_path, Some("save_ticket".to_string())).unwrap();
+
+        let held = HeldLock::find(temp_dir.path());
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].pid, std::process::id());
+        assert_eq!(held[0].operation.as_deref(), Some("save_ticket"));
+        assert!(!held[0].is_stale(&LockSettings::default()));
+
+        let settings = LockSettings {
+            retry_delay: Duration::from_millis(10),
+            ..LockSettings::default()
+        }
+        .with_wait(Duration::from_millis(50));
+        assert_eq!(settings.retries, 6);
+        let Err(error) = FileLock::acquire_with(&file_path, None, &settings) else {
+            panic!("the lock is held");
+        };
+        assert!(
+            format!("{error:#}").contains("after 6 attempts (held by process"),
+            "{error:#}"
+        );
+    }
+
     #[test]
     fn test_stale_lock_removal() {
         let temp_dir = TempDir::new().unwrap();
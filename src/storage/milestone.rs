@@ -0,0 +1,66 @@
+//! Persistence of milestones
+//!
+//! Milestones are stored together in `milestones.yaml`, in the order they
+//! were created. Tickets refer to them by name.
+
+use super::FileStorage;
+use crate::core::Milestone;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the milestones file
+const MILESTONES_KEY: &str = "milestones.yaml";
+
+impl FileStorage {
+    /// Loads all milestones
+    ///
+    /// Returns an empty list if no milestone was ever created.
+    pub fn load_milestones(&self) -> Result<Vec<Milestone>> {
+        let Some(content) = self
+            .blobs
+            .get_string(MILESTONES_KEY)
+            .context("Failed to read milestones")?
+        else {
+            return Ok(Vec::new());
+        };
+
+        serde_yaml::from_str(&content)
+            .map_err(|e| VibeTicketError::deserialization_error("milestones", e))
+    }
+
+    /// Replaces the stored milestones
+    pub fn save_milestones(&self, milestones: &[Milestone]) -> Result<()> {
+        let yaml = serde_yaml::to_string(milestones).context("Failed to serialize milestones")?;
+        self.blobs
+            .put(MILESTONES_KEY, yaml.as_bytes())
+            .context("Failed to write milestones")
+    }
+
+    /// Loads the milestone called `name`
+    pub fn load_milestone(&self, name: &str) -> Result<Milestone> {
+        self.load_milestones()?
+            .into_iter()
+            .find(|milestone| milestone.name == name)
+            .ok_or_else(|| VibeTicketError::custom(format!("Milestone '{name}' not found")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStore;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_milestones_round_trip() {
+        let storage = FileStorage::with_blob_store("/unused", Arc::new(MemoryStore::new()));
+        assert!(storage.load_milestones().unwrap().is_empty());
+
+        let mut milestone = Milestone::new("v1.0");
+        milestone.description = Some("First release".to_string());
+        storage.save_milestones(&[milestone.clone()]).unwrap();
+
+        assert_eq!(storage.load_milestones().unwrap(), vec![milestone.clone()]);
+        assert_eq!(storage.load_milestone("v1.0").unwrap(), milestone);
+        assert!(storage.load_milestone("v2.0").is_err());
+    }
+}
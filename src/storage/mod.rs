@@ -33,6 +33,95 @@
 //! This ensures data integrity even when multiple users or processes access
 //! tickets simultaneously.
 //!
+//! # Blob Stores
+//!
+//! `FileStorage` reads and writes ticket files through the [`BlobStore`]
+//! trait. [`LocalFs`] is the default; [`MemoryStore`] keeps everything in
+//! memory. The trait is synchronous so that remote stores (S3, `WebDAV`) can
+//! be added later without making the storage layer async.
+//!
+//! # Write Coalescing
+//!
+//! Long-running processes such as the MCP server can hold back ticket
+//! writes for a short window with `FileStorage::with_write_coalescing`, so
+//! that a burst of saves of the same ticket costs one disk write (see
+//! [`Coalescing`]). Pending writes are visible to the process that made
+//! them and reach the disk after the window or on `FileStorage::flush`,
+//! unless another process saved the ticket meanwhile. Audit logs, the
+//! journal and the index are never held back.
+//!
+//! # Audit Log
+//!
+//! Every ticket write is compared with the stored version and the
+//! differences are appended to the ticket's audit log (see
+//! `FileStorage::load_history`).
+//!
+//! # Garbage Collection
+//!
+//! Attachments, audit logs and spec documents can outlive the ticket or
+//! spec they belong to. `FileStorage::find_orphans` reports them and
+//! `FileStorage::remove_orphan` deletes them (see `vibe-ticket gc`).
+//!
+//! # Ignore File
+//!
+//! Patterns in `.vibe-ticket/ignore` exclude ticket files, spec directories
+//! and attachments from listings, using `.gitignore` syntax (see
+//! [`IgnoreRules`]). Tickets are filtered in the walker shared by
+//! `load_all` and the projections.
+//!
+//! # Projections
+//!
+//! Reporting commands read tickets through partial structs such as
+//! [`TicketSummary`] via `FileStorage::load_projections`, which skips fields
+//! they do not need instead of deserializing whole tickets.
+//!
+//! `FileStorage::summaries` reads the summaries from the listing index,
+//! `.vibe-ticket/index.json`, which every ticket write updates and which
+//! only re-reads ticket files written since they were indexed (see
+//! [`IndexStatus`]).
+//!
+//! `FileStorage::scan_tickets` parses the tickets on several threads and
+//! hands them over as they are found, for searches that show results early
+//! and stop after enough of them.
+//!
+//! # Event Log
+//!
+//! Every ticket write and deletion, and every change of the active ticket,
+//! is appended to `.vibe-ticket/events.log`, which other processes read
+//! with `FileStorage::poll_events` to notice changes cheaply (see
+//! [`StorageEvent`]).
+//!
+//! # Journal
+//!
+//! Every ticket write and deletion first records the ticket's previous state
+//! in the journal of the current operation, which `FileStorage::undo_operation`
+//! writes back (see [`begin_operation`] and [`in_operation`]).
+//!
+//! # Integrity
+//!
+//! Saves that would duplicate a slug, add a reference to a missing ticket or
+//! change the status without keeping `closed_at` consistent are rejected
+//! (see [`TicketIndex`]). These constraints are part of the
+//! [`TicketRepository`] contract; `TicketRepository::save_all` saves tickets
+//! that refer to each other.
+//!
+//! # Closing
+//!
+//! Every write that closes a ticket, whether by `close`, `edit`, an MCP
+//! tool or the aging policy, must meet the project's close requirements and
+//! find the tickets it depends on closed, unless forced closes are allowed
+//! (see `FileStorage::with_forced_close`). Tickets closed in the same
+//! transaction count as closed. Once such a write is stored, the effects
+//! of closing run for every path alike, such as warning about the open
+//! children of a closed epic.
+//!
+//! # Transactions
+//!
+//! `FileStorage::transaction` saves several tickets atomically: they are
+//! staged and committed with a marker before any ticket file is replaced,
+//! so a crash leaves either all of them written or none (see
+//! [`Transaction`]). `save_all` uses a transaction.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -54,10 +143,42 @@
 //! - Permission errors
 //! - Lock acquisition failures
 
+mod attachments;
+mod blob;
+mod closing;
+mod coalesce;
+mod event_log;
 mod file;
+mod gc;
+mod history;
+mod ignore;
+mod inbox;
+mod integrity;
+mod journal;
+mod listing;
 mod lock;
+mod milestone;
+mod projection;
 mod repository;
+mod scan;
+mod short_id;
+mod transaction;
+mod trash;
 
-pub use file::{FileStorage, ProjectState};
-pub use lock::{FileLock, LockGuard};
+pub use attachments::{ATTACHMENTS_KEY, Attachment, attachment_names};
+pub use blob::{BlobStore, LocalFs, MemoryStore};
+pub use coalesce::Coalescing;
+pub use event_log::{
+    EVENT_LOG_FILE, EVENT_LOG_LIMIT, EventCursor, EventPoll, StorageEvent, StorageEventKind,
+};
+pub use file::{DESCRIPTION_ATTACHMENT_KEY, FileStorage, ProjectState};
+pub use gc::{Orphan, OrphanKind};
+pub use ignore::{IGNORE_FILE, IgnoreRules};
+pub use integrity::TicketIndex;
+pub use journal::{JOURNAL_LIMIT, JournalEntry, Operation, begin_operation, in_operation};
+pub use listing::{INDEX_KEY, IndexStatus};
+pub use lock::{FileLock, HeldLock, LockGuard, LockSettings, override_lock_wait};
+pub use projection::{StatusProjection, TicketSummary};
 pub use repository::{ActiveTicketRepository, Repository, TicketRepository};
+pub use transaction::Transaction;
+pub use trash::TrashedTicket;
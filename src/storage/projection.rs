@@ -0,0 +1,205 @@
+//! Read-side projections of ticket files
+//!
+//! Reporting commands such as `list` and `check --stats` only look at a few
+//! fields of every ticket. Deserializing whole tickets means allocating
+//! descriptions, tasks and metadata that are thrown away immediately. A
+//! projection is a partial serde struct: fields it does not declare are
+//! skipped by the deserializer instead of being materialized.
+//!
+//! [`TicketSummary`] is also what the listing index keeps of every ticket
+//! (see `FileStorage::summaries`).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::FileStorage;
+use crate::core::{Priority, Severity, Status, Ticket, TicketId};
+use crate::error::Result;
+
+/// Status and priority of a ticket, enough for project statistics
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusProjection {
+    /// Current status of the ticket
+    pub status: Status,
+
+    /// Priority level of the ticket
+    pub priority: Priority,
+}
+
+/// Summary of a ticket used for filtering, sorting and board views
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TicketSummary {
+    /// Unique identifier for the ticket
+    pub id: TicketId,
+
+    /// Short ID of the ticket, if it has been assigned one
+    #[serde(default)]
+    pub short_id: Option<String>,
+
+    /// URL-friendly slug derived from the title
+    pub slug: String,
+
+    /// Human-readable title of the ticket
+    pub title: String,
+
+    /// Priority level of the ticket
+    pub priority: Priority,
+
+    /// Incident severity, if any
+    #[serde(default)]
+    pub severity: Option<Severity>,
+
+    /// Current status of the ticket
+    pub status: Status,
+
+    /// Tags of the ticket
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Timestamp when the ticket was created
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp when work on the ticket started
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+
+    /// Timestamp when the ticket was closed
+    #[serde(default)]
+    pub closed_at: Option<DateTime<Utc>>,
+
+    /// Username of the person assigned to the ticket
+    #[serde(default)]
+    pub assignee: Option<String>,
+
+    /// Name of the milestone the ticket belongs to
+    #[serde(default)]
+    pub milestone: Option<String>,
+
+    /// Date the ticket is due
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+
+    /// Subset of the ticket metadata relevant to reporting
+    #[serde(default)]
+    metadata: SummaryMetadata,
+}
+
+/// Metadata keys read by [`TicketSummary`]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct SummaryMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    archived: Option<serde_json::Value>,
+}
+
+impl From<&Ticket> for TicketSummary {
+    fn from(ticket: &Ticket) -> Self {
+        Self {
+            id: ticket.id.clone(),
+            short_id: ticket.short_id.clone(),
+            slug: ticket.slug.clone(),
+            title: ticket.title.clone(),
+            priority: ticket.priority,
+            severity: ticket.severity,
+            status: ticket.status,
+            tags: ticket.tags.clone(),
+            created_at: ticket.created_at,
+            started_at: ticket.started_at,
+            closed_at: ticket.closed_at,
+            assignee: ticket.assignee.clone(),
+            milestone: ticket.milestone.clone(),
+            due: ticket.due,
+            metadata: SummaryMetadata {
+                archived: ticket.metadata.get("archived").cloned(),
+            },
+        }
+    }
+}
+
+impl TicketSummary {
+    /// ID shown for the ticket (see [`Ticket::display_id`])
+    ///
+    /// [`Ticket::display_id`]: crate::core::Ticket::display_id
+    pub fn display_id(&self) -> String {
+        self.short_id.clone().unwrap_or_else(|| self.id.short())
+    }
+
+    /// Returns true if the ticket has been archived
+    pub fn is_archived(&self) -> bool {
+        self.metadata
+            .archived
+            .as_ref()
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
+impl FileStorage {
+    /// Loads a projection of every ticket in storage
+    ///
+    /// Tickets that cannot be read into the projection are reported and
+    /// skipped, matching the behavior of `load_all_tickets`.
+    pub fn load_projections<P: DeserializeOwned>(&self) -> Result<Vec<P>> {
+        let mut projections = Vec::new();
+
+        for (path, yaml) in self.ticket_files()? {
+            match serde_yaml::from_str::<P>(&yaml) {
+                Ok(projection) => projections.push(projection),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to load ticket from {}: {e}",
+                        path.display()
+                    );
+                },
+            }
+        }
+
+        Ok(projections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Ticket;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_projections() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.ensure_directories().unwrap();
+
+        let mut archived = Ticket::new("old", "Old work");
+        archived.description = "x".repeat(1024);
+        archived
+            .metadata
+            .insert("archived".to_string(), serde_json::json!(true));
+        archived
+            .metadata
+            .insert("estimate".to_string(), serde_json::json!({"points": 3}));
+        storage.save(&archived).unwrap();
+
+        let mut doing = Ticket::new("new", "New work");
+        doing.status = Status::Doing;
+        storage.save(&doing).unwrap();
+
+        let mut summaries = storage.load_projections::<TicketSummary>().unwrap();
+        summaries.sort_by(|a, b| a.slug.cmp(&b.slug));
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].id, doing.id);
+        assert!(!summaries[0].is_archived());
+        assert_eq!(summaries[1].title, "Old work");
+        assert!(summaries[1].is_archived());
+
+        let statuses = storage.load_projections::<StatusProjection>().unwrap();
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|p| p.status == Status::Doing)
+                .count(),
+            1
+        );
+    }
+}
@@ -5,10 +5,22 @@ use crate::error::Result;
 ///
 /// This trait defines the interface for storing and retrieving tickets,
 /// allowing for different storage implementations.
+///
+/// Every implementation enforces the same integrity constraints on save:
+/// slugs are unique, added parents, dependencies, blocked tickets and
+/// relations refer to existing tickets, and status changes keep `closed_at`
+/// consistent (see [`super::TicketIndex`]).
 pub trait TicketRepository: Send + Sync {
-    /// Saves a ticket to the repository
+    /// Saves a ticket to the repository, rejecting it if it breaks an
+    /// integrity constraint
     fn save(&self, ticket: &Ticket) -> Result<()>;
 
+    /// Saves several tickets, which may refer to each other
+    ///
+    /// Either all of them are saved or none: nothing is saved if any of
+    /// them breaks an integrity constraint or cannot be written.
+    fn save_all(&self, tickets: &[Ticket]) -> Result<()>;
+
     /// Loads a ticket by ID
     fn load(&self, id: &TicketId) -> Result<Ticket>;
 
@@ -57,6 +69,10 @@ impl TicketRepository for FileStorage {
         self.save_ticket(ticket)
     }
 
+    fn save_all(&self, tickets: &[Ticket]) -> Result<()> {
+        self.save_tickets(tickets)
+    }
+
     fn load(&self, id: &TicketId) -> Result<Ticket> {
         self.load_ticket(id)
     }
@@ -111,8 +127,11 @@ impl ActiveTicketRepository for FileStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::relations::{Relation, RelationKind};
     use crate::core::{Priority, Status, Ticket};
-    use crate::storage::FileStorage;
+    use crate::error::VibeTicketError;
+    use crate::storage::{FileStorage, MemoryStore};
+    use std::sync::Arc;
     use tempfile::TempDir;
 
     fn create_test_ticket(slug: &str) -> Ticket {
@@ -309,4 +328,97 @@ mod tests {
             Some(id)
         );
     }
+
+    /// Integrity constraints every `TicketRepository` must enforce
+    fn check_integrity_conformance(repo: &impl TicketRepository) {
+        let parent = create_test_ticket("parent");
+        repo.save(&parent).unwrap();
+
+        // Duplicate slugs
+        assert!(matches!(
+            repo.save(&create_test_ticket("parent")),
+            Err(VibeTicketError::DuplicateTicket { .. })
+        ));
+
+        // Dangling references
+        let mut orphan = create_test_ticket("orphan");
+        orphan.parent = Some(TicketId::new());
+        assert!(matches!(
+            repo.save(&orphan),
+            Err(VibeTicketError::DanglingReference { .. })
+        ));
+        orphan.parent = None;
+        orphan.depends_on.push(orphan.id.clone());
+        assert!(matches!(
+            repo.save(&orphan),
+            Err(VibeTicketError::DanglingReference { .. })
+        ));
+        assert!(!repo.exists(&orphan.id).unwrap());
+
+        let mut child = create_test_ticket("child");
+        child.parent = Some(parent.id.clone());
+        repo.save(&child).unwrap();
+
+        // Status changes keep the close time consistent
+        let mut closed = child.clone();
+        closed.status = Status::Done;
+        assert!(matches!(
+            repo.save(&closed),
+            Err(VibeTicketError::InconsistentCloseTime { .. })
+        ));
+        closed.closed_at = Some(chrono::Utc::now());
+        repo.save(&closed).unwrap();
+        let mut reopened = closed.clone();
+        reopened.status = Status::Todo;
+        assert!(matches!(
+            repo.save(&reopened),
+            Err(VibeTicketError::InconsistentCloseTime { .. })
+        ));
+        reopened.closed_at = None;
+        repo.save(&reopened).unwrap();
+
+        // A reference left by a deleted ticket does not block other edits
+        repo.delete(&parent.id).unwrap();
+        reopened.priority = Priority::High;
+        repo.save(&reopened).unwrap();
+
+        // Batches may refer to each other, and are saved all or nothing
+        let mut epic = create_test_ticket("epic");
+        let mut task = create_test_ticket("task");
+        task.parent = Some(epic.id.clone());
+        epic.relations.push(Relation {
+            kind: RelationKind::RelatesTo,
+            ticket: task.id.clone(),
+        });
+        let dangling = {
+            let mut ticket = create_test_ticket("dangling");
+            ticket.depends_on.push(TicketId::new());
+            ticket
+        };
+        assert!(
+            repo.save_all(&[epic.clone(), task.clone(), dangling])
+                .is_err()
+        );
+        assert!(!repo.exists(&epic.id).unwrap());
+        assert!(
+            repo.save_all(&[epic.clone(), create_test_ticket("epic")])
+                .is_err()
+        );
+        repo.save_all(&[task.clone(), epic.clone()]).unwrap();
+        assert_eq!(repo.load(&task.id).unwrap().parent, Some(epic.id));
+    }
+
+    #[test]
+    fn test_integrity_conformance_local_fs() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+        check_integrity_conformance(&storage);
+    }
+
+    #[test]
+    fn test_integrity_conformance_memory_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::with_blob_store(temp_dir.path(), Arc::new(MemoryStore::new()));
+        check_integrity_conformance(&storage);
+    }
 }
@@ -0,0 +1,227 @@
+//! Parallel scans of the ticket files
+//!
+//! `search` reads and parses every ticket, which dominates its run time on
+//! projects with long descriptions. [`FileStorage::scan_tickets`] spreads
+//! the reading and parsing over several threads and hands each ticket of
+//! interest to the caller as soon as it is found, so that results can be
+//! shown while the scan goes on and the scan can stop early. Scans that
+//! only care about indexed fields skip the other tickets without reading
+//! them ([`FileStorage::scan_indexed_tickets`]).
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::{FileStorage, TicketSummary};
+use crate::core::Ticket;
+use crate::error::Result;
+
+/// Progress of a scan, shared by its threads
+struct Scan {
+    /// Keys of the ticket files to parse
+    keys: Vec<String>,
+    /// Index of the next key to parse
+    next: AtomicUsize,
+    /// Number of tickets parsed
+    scanned: AtomicUsize,
+    /// Whether the caller has enough results
+    stop: AtomicBool,
+}
+
+impl FileStorage {
+    /// Reads and parses the tickets in storage on `jobs` threads
+    ///
+    /// `select` runs on the scanning threads and turns the tickets of
+    /// interest into results. `found` runs on the calling thread and
+    /// receives the results in the order they are found; the scan stops
+    /// once it returns false. Tickets that cannot be parsed are reported and
+    /// skipped, matching the behavior of `load_all_tickets`.
+    ///
+    /// Returns the number of tickets parsed.
+    pub fn scan_tickets<T, S, F>(&self, jobs: usize, select: S, found: F) -> Result<usize>
+    where
+        T: Send,
+        S: Fn(Ticket) -> Option<T> + Sync,
+        F: FnMut(T) -> bool,
+    {
+        self.scan_keys(self.ticket_keys()?, jobs, select, found)
+    }
+
+    /// Like [`Self::scan_tickets`], but only parses the tickets whose
+    /// summary in the listing index passes `candidate`
+    pub fn scan_indexed_tickets<T, C, S, F>(
+        &self,
+        jobs: usize,
+        candidate: C,
+        select: S,
+        found: F,
+    ) -> Result<usize>
+    where
+        T: Send,
+        C: Fn(&TicketSummary) -> bool,
+        S: Fn(Ticket) -> Option<T> + Sync,
+        F: FnMut(T) -> bool,
+    {
+        let keys = self
+            .summaries()?
+            .iter()
+            .filter(|summary| candidate(summary))
+            .map(|summary| Self::ticket_key(&summary.id))
+            .collect();
+        self.scan_keys(keys, jobs, select, found)
+    }
+
+    /// Parses the tickets under `keys` on `jobs` threads (see
+    /// [`Self::scan_tickets`])
+    fn scan_keys<T, S, F>(
+        &self,
+        keys: Vec<String>,
+        jobs: usize,
+        select: S,
+        mut found: F,
+    ) -> Result<usize>
+    where
+        T: Send,
+        S: Fn(Ticket) -> Option<T> + Sync,
+        F: FnMut(T) -> bool,
+    {
+        let scan = Scan {
+            keys,
+            next: AtomicUsize::new(0),
+            scanned: AtomicUsize::new(0),
+            stop: AtomicBool::new(false),
+        };
+
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            let workers: Vec<_> = (0..jobs.clamp(1, scan.keys.len().max(1)))
+                .map(|_| {
+                    let sender = sender.clone();
+                    let (scan, select) = (&scan, &select);
+                    scope.spawn(move || self.scan_worker(scan, select, &sender))
+                })
+                .collect();
+            drop(sender);
+
+            for result in receiver {
+                if !found(result) {
+                    // Workers stop before their next ticket, or at their next
+                    // result once the receiver is dropped
+                    scan.stop.store(true, Ordering::Relaxed);
+                    break;
+                }
+            }
+
+            workers.into_iter().try_for_each(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+        })?;
+
+        Ok(scan.scanned.into_inner())
+    }
+
+    /// Loop of a scanning thread, parsing tickets until none are left or the
+    /// scan is stopped
+    fn scan_worker<T, S>(&self, scan: &Scan, select: &S, sender: &mpsc::Sender<T>) -> Result<()>
+    where
+        S: Fn(Ticket) -> Option<T>,
+    {
+        while !scan.stop.load(Ordering::Relaxed) {
+            let Some(key) = scan.keys.get(scan.next.fetch_add(1, Ordering::Relaxed)) else {
+                break;
+            };
+            let Some(ticket) = self.parse_ticket_file(key)? else {
+                continue;
+            };
+            scan.scanned.fetch_add(1, Ordering::Relaxed);
+            if let Some(result) = select(ticket)
+                && sender.send(result).is_err()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads and parses the ticket file under `key`
+    ///
+    /// Returns `None` for a ticket deleted since listing, or one that cannot
+    /// be parsed, which is reported.
+    fn parse_ticket_file(&self, key: &str) -> Result<Option<Ticket>> {
+        let Some((path, yaml)) = self.read_ticket_file(key)? else {
+            return Ok(None);
+        };
+        match serde_yaml::from_str::<Ticket>(&yaml) {
+            Ok(ticket) => Ok(Some(ticket)),
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to load ticket from {}: {e}",
+                    path.display()
+                );
+                Ok(None)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_tickets() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        for i in 0..20 {
+            let mut ticket = Ticket::new(&format!("ticket-{i}"), &format!("Ticket {i}"));
+            if i % 2 == 0 {
+                ticket.tags.push("even".to_string());
+            }
+            storage.save_ticket(&ticket).unwrap();
+        }
+        let even = |ticket: Ticket| ticket.tags.contains(&"even".to_string()).then_some(ticket);
+
+        let mut found = Vec::new();
+        let scanned = storage
+            .scan_tickets(4, even, |ticket| {
+                found.push(ticket.slug);
+                true
+            })
+            .unwrap();
+        assert_eq!(scanned, 20);
+        found.sort();
+        assert_eq!(found.len(), 10);
+        assert!(found.iter().all(|slug| {
+            let n: usize = slug["ticket-".len()..].parse().unwrap();
+            n % 2 == 0
+        }));
+
+        // Stops once enough results are found
+        let mut found = 0;
+        storage
+            .scan_tickets(4, even, |_| {
+                found += 1;
+                found < 3
+            })
+            .unwrap();
+        assert_eq!(found, 3);
+
+        // Only candidates from the index are parsed
+        let mut found = 0;
+        let scanned = storage
+            .scan_indexed_tickets(
+                4,
+                |summary| summary.tags.iter().any(|tag| tag == "even"),
+                Some,
+                |_| {
+                    found += 1;
+                    true
+                },
+            )
+            .unwrap();
+        assert_eq!((scanned, found), (10, 10));
+    }
+}
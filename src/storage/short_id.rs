@@ -0,0 +1,109 @@
+//! Assignment of the short IDs of tickets
+//!
+//! A ticket gets its short ID (see [`TicketId::generate_short_id`]) when it
+//! is first saved, and keeps it afterwards. Tickets saved before short IDs
+//! were introduced get one the next time they are saved.
+
+use super::FileStorage;
+use crate::core::Ticket;
+use crate::error::Result;
+
+impl FileStorage {
+    /// Gives `ticket` a short ID unless it already has one no other ticket
+    /// uses
+    ///
+    /// Saving assigns short IDs as well; assigning one up front lets callers
+    /// show it right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored tickets cannot be read
+    pub fn assign_short_id(&self, ticket: &mut Ticket) -> Result<()> {
+        if let Some(short_id) = self.free_short_id(ticket)? {
+            ticket.short_id = Some(short_id);
+        }
+        Ok(())
+    }
+
+    /// Short ID to store `ticket` with, if it differs from its own
+    ///
+    /// The short ID of the stored version `before` is kept; otherwise a
+    /// free one is assigned.
+    pub(super) fn short_id_to_store(
+        &self,
+        ticket: &Ticket,
+        before: Option<&Ticket>,
+    ) -> Result<Option<String>> {
+        match (
+            &ticket.short_id,
+            before.and_then(|before| before.short_id.as_ref()),
+        ) {
+            (Some(_), Some(_)) => Ok(None),
+            (None, Some(stored)) => Ok(Some(stored.clone())),
+            _ => self.free_short_id(ticket),
+        }
+    }
+
+    /// A short ID for `ticket` if it has none or another ticket uses its own
+    fn free_short_id(&self, ticket: &Ticket) -> Result<Option<String>> {
+        // Short IDs must not shadow the short ID or slug of another ticket
+        let taken: Vec<String> = self
+            .summaries()?
+            .into_iter()
+            .filter(|other| other.id != ticket.id)
+            .flat_map(|other| std::iter::once(other.slug).chain(other.short_id))
+            .collect();
+        let is_taken = |candidate: &str| taken.iter().any(|name| name == candidate);
+
+        if ticket
+            .short_id
+            .as_deref()
+            .is_some_and(|short_id| !is_taken(short_id))
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            ticket
+                .id
+                .generate_short_id(self.short_id_length(), is_taken),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_short_ids_are_assigned_once_and_unique() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+
+        let first = Ticket::new("first", "First");
+        storage.save(&first).unwrap();
+        let short_id = storage.load(&first.id).unwrap().short_id.unwrap();
+        assert_eq!(short_id.len(), 6);
+
+        // Saving a copy without the short ID keeps the stored one
+        storage.save(&first).unwrap();
+        assert_eq!(
+            storage.load(&first.id).unwrap().short_id.as_ref(),
+            Some(&short_id)
+        );
+
+        // An imported ticket reusing the short ID gets another one
+        let mut copy = Ticket::new("copy", "Copy");
+        copy.short_id = Some(short_id.clone());
+        storage.save(&copy).unwrap();
+        let copy_short_id = storage.load(&copy.id).unwrap().short_id.unwrap();
+        assert_ne!(copy_short_id, short_id);
+
+        let mut assigned = Ticket::new("assigned", "Assigned");
+        storage.assign_short_id(&mut assigned).unwrap();
+        let expected = assigned.short_id.clone();
+        storage.save(&assigned).unwrap();
+        assert_eq!(storage.load(&assigned.id).unwrap().short_id, expected);
+    }
+}
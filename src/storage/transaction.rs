@@ -0,0 +1,430 @@
+//! Atomic multi-ticket writes
+//!
+//! Saving a batch of tickets one file at a time leaves part of the batch
+//! written if the process dies halfway. A [`Transaction`] first checks every
+//! ticket, then stages them as `transactions/<transaction>.<ticket-id>.yaml`
+//! and records their previous states in the journal. Once all of them are
+//! staged it writes the commit marker `transactions/<transaction>.commit`,
+//! and only then moves the staged files over the ticket files, each with
+//! an atomic rename.
+//!
+//! A transaction interrupted before its marker was written changed no
+//! ticket; one interrupted after it is committed. Both are cleaned up by
+//! [`FileStorage::recover_transactions`], which discards the staged files
+//! of the former and moves those of the latter into place. It runs before
+//! every transaction and from `doctor`. The audit log of tickets moved into
+//! place by recovery misses the changes of the interrupted transaction.
+//!
+//! The committing process holds the locks of all tickets of the
+//! transaction from the checks to the last rename, so recovery, which
+//! takes the lock of a ticket before touching its staged file, never
+//! interferes with a transaction that is still running.
+
+use std::collections::HashSet;
+
+use super::FileStorage;
+use super::event_log::StorageEventKind;
+use super::file::PreparedWrite;
+use crate::core::{Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the directory holding staged transactions
+const TRANSACTIONS_KEY: &str = "transactions";
+
+/// Extension of the commit marker of a transaction
+const COMMIT_EXTENSION: &str = "commit";
+
+/// Tickets saved together: either all of them are written or none
+///
+/// Created by [`FileStorage::transaction`].
+pub struct Transaction<'a> {
+    storage: &'a FileStorage,
+    tickets: Vec<Ticket>,
+}
+
+impl Transaction<'_> {
+    /// Adds a ticket to save, replacing an earlier version of it
+    pub fn save(&mut self, ticket: Ticket) -> &mut Self {
+        match self.tickets.iter_mut().find(|t| t.id == ticket.id) {
+            Some(staged) => *staged = ticket,
+            None => self.tickets.push(ticket),
+        }
+        self
+    }
+
+    /// Number of tickets to save
+    pub fn len(&self) -> usize {
+        self.tickets.len()
+    }
+
+    /// Returns true if there is nothing to save
+    pub fn is_empty(&self) -> bool {
+        self.tickets.is_empty()
+    }
+
+    /// Writes all tickets, which may refer to each other
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving every ticket unchanged, if a ticket breaks
+    /// an integrity constraint, is read-only or cannot be locked or staged.
+    /// Once staged, the tickets are written even if the process dies.
+    pub fn commit(self) -> Result<()> {
+        self.commit_with(|_, _| true).map(|_| ())
+    }
+
+    /// Like [`Self::commit`], reporting the staging of each ticket
+    ///
+    /// `step` receives the number of staged tickets and their total, and
+    /// stops the transaction by returning false: the staged tickets are then
+    /// discarded and no ticket is written. Once every ticket is staged the
+    /// transaction can no longer be stopped.
+    ///
+    /// Returns false if `step` stopped the transaction.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::commit`]
+    pub fn commit_with<F>(self, step: F) -> Result<bool>
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let storage = self.storage;
+        if self.tickets.is_empty() {
+            return Ok(true);
+        }
+        storage.ensure_directories()?;
+        storage.recover_transactions()?;
+
+        let mut index = storage.ticket_index()?;
+        for ticket in &self.tickets {
+            index.add(ticket)?;
+        }
+
+        // Locked in ID order, so that transactions over the same tickets
+        // cannot deadlock
+        let mut ids: Vec<&TicketId> = self.tickets.iter().map(|t| &t.id).collect();
+        ids.sort_by_key(ToString::to_string);
+        let _locks = ids
+            .into_iter()
+            .map(|id| storage.lock(&storage.ticket_path(id), "transaction"))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                VibeTicketError::custom(format!("Failed to acquire lock for transaction: {e}"))
+            })?;
+
+        let writes = self
+            .tickets
+            .iter()
+            .map(|ticket| storage.prepare_write(ticket, Some(&index)))
+            .collect::<Result<Vec<PreparedWrite>>>()?;
+
+        let transaction = uuid::Uuid::new_v4().to_string();
+        match storage.stage(&transaction, &writes, step) {
+            Ok(true) => {},
+            Ok(false) => {
+                storage.discard_staged(&transaction, &writes);
+                return Ok(false);
+            },
+            Err(e) => {
+                storage.discard_staged(&transaction, &writes);
+                return Err(e);
+            },
+        }
+
+        // Past the marker the transaction is committed: recovery finishes it
+        let marker = commit_key(&transaction);
+        storage
+            .blobs
+            .put(&marker, b"")
+            .and_then(|()| storage.blobs.flush())
+            .context("Failed to commit the transaction")?;
+
+        let mut closed = Vec::new();
+        for write in writes {
+            storage
+                .blobs
+                .rename(
+                    &staged_key(&transaction, &write.ticket.id),
+                    &FileStorage::ticket_key(&write.ticket.id),
+                )
+                .context("Failed to write a ticket of the transaction")?;
+            if write.closes {
+                closed.push(write.ticket.clone());
+            }
+            storage.finish_write(write)?;
+        }
+        storage
+            .blobs
+            .delete(&marker)
+            .context("Failed to finish the transaction")?;
+
+        // Effects of closing see the whole transaction written
+        for ticket in &closed {
+            storage.closed(ticket);
+        }
+        Ok(true)
+    }
+}
+
+impl FileStorage {
+    /// Starts a transaction saving several tickets atomically
+    pub const fn transaction(&self) -> Transaction<'_> {
+        Transaction {
+            storage: self,
+            tickets: Vec::new(),
+        }
+    }
+
+    /// Stages the tickets of a transaction and journals their previous
+    /// states
+    ///
+    /// Returns false, before journaling anything, if `step` stops the
+    /// transaction (see [`Transaction::commit_with`]).
+    fn stage<F>(&self, transaction: &str, writes: &[PreparedWrite], mut step: F) -> Result<bool>
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        for (staged, write) in (1..).zip(writes) {
+            self.blobs
+                .put(
+                    &staged_key(transaction, &write.ticket.id),
+                    write.yaml.as_bytes(),
+                )
+                .context("Failed to stage a ticket of the transaction")?;
+            if !step(staged, writes.len()) {
+                return Ok(false);
+            }
+        }
+        for write in writes {
+            self.journal_write(write)?;
+        }
+        Ok(true)
+    }
+
+    /// Removes what a failed transaction staged
+    fn discard_staged(&self, transaction: &str, writes: &[PreparedWrite]) {
+        for write in writes {
+            let _ = self
+                .blobs
+                .delete(&staged_key(transaction, &write.ticket.id));
+        }
+    }
+
+    /// Finishes or discards transactions interrupted by a crash
+    ///
+    /// Committed transactions are finished and the others discarded (see
+    /// the module documentation). Staged tickets still locked by a running
+    /// transaction are left alone.
+    ///
+    /// Returns the number of finished transactions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the staged tickets cannot be listed or moved
+    pub fn recover_transactions(&self) -> Result<usize> {
+        let keys = self
+            .blobs
+            .list(TRANSACTIONS_KEY)
+            .context("Failed to read the transactions")?;
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let committed: HashSet<&str> = keys
+            .iter()
+            .filter_map(|key| {
+                let (transaction, extension) = split_key(key)?;
+                (extension == COMMIT_EXTENSION).then_some(transaction)
+            })
+            .collect();
+        let mut unfinished = HashSet::new();
+        for key in &keys {
+            let Some((transaction, rest)) = split_key(key) else {
+                continue;
+            };
+            let Some(id) = rest
+                .strip_suffix(".yaml")
+                .and_then(|id| TicketId::parse_str(id).ok())
+            else {
+                continue;
+            };
+
+            // A running transaction holds the lock until it is done
+            let Ok(_lock) = self.lock(&self.ticket_path(&id), "recover") else {
+                unfinished.insert(transaction);
+                continue;
+            };
+            if !self.blobs.exists(key)? {
+                continue;
+            }
+            if committed.contains(transaction) {
+                self.move_staged(key, &id)?;
+            } else {
+                self.blobs
+                    .delete(key)
+                    .context("Failed to discard a staged ticket")?;
+            }
+        }
+
+        let mut finished = 0;
+        for transaction in committed.difference(&unfinished) {
+            if self
+                .blobs
+                .delete(&commit_key(transaction))
+                .context("Failed to finish the transaction")?
+            {
+                finished += 1;
+            }
+        }
+        Ok(finished)
+    }
+
+    /// Moves a staged ticket of a committed transaction into place
+    fn move_staged(&self, key: &str, id: &TicketId) -> Result<()> {
+        let ticket_key = Self::ticket_key(id);
+        self.blobs
+            .rename(key, &ticket_key)
+            .context("Failed to recover a ticket of a transaction")?;
+        let ticket = self
+            .blobs
+            .get_string(&ticket_key)?
+            .and_then(|yaml| serde_yaml::from_str::<Ticket>(&yaml).ok());
+        self.update_index(&ticket_key, ticket.as_ref());
+        self.log_event(StorageEventKind::Saved, Some(id));
+        self.cache.invalidate_ticket(id);
+        Ok(())
+    }
+}
+
+/// Blob store key of a staged ticket
+fn staged_key(transaction: &str, id: &TicketId) -> String {
+    format!("{TRANSACTIONS_KEY}/{transaction}.{id}.yaml")
+}
+
+/// Blob store key of the commit marker of a transaction
+fn commit_key(transaction: &str) -> String {
+    format!("{TRANSACTIONS_KEY}/{transaction}.{COMMIT_EXTENSION}")
+}
+
+/// Splits a key under the transactions directory into the transaction and
+/// the rest of the file name
+fn split_key(key: &str) -> Option<(&str, &str)> {
+    key.strip_prefix(&format!("{TRANSACTIONS_KEY}/"))?
+        .split_once('.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Status;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commit_saves_all_or_nothing() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let parent = Ticket::new("parent", "Parent");
+        let mut child = Ticket::new("child", "Child");
+        child.parent = Some(parent.id.clone());
+
+        let mut transaction = storage.transaction();
+        transaction.save(child.clone()).save(parent.clone());
+        assert_eq!(transaction.len(), 2);
+        transaction.commit().unwrap();
+        assert_eq!(storage.load_all().unwrap().len(), 2);
+        assert!(storage.blobs.list(TRANSACTIONS_KEY).unwrap().is_empty());
+
+        // A dangling reference rejects the whole transaction
+        let mut renamed = parent.clone();
+        renamed.title = "Renamed".to_string();
+        let mut dangling = Ticket::new("dangling", "Dangling");
+        dangling.depends_on.push(TicketId::new());
+        let mut transaction = storage.transaction();
+        transaction.save(renamed).save(dangling.clone());
+        assert!(transaction.commit().is_err());
+        let fresh = FileStorage::new(temp.path());
+        assert_eq!(fresh.load(&parent.id).unwrap().title, "Parent");
+        assert!(fresh.load(&dangling.id).is_err());
+        assert!(storage.blobs.list(TRANSACTIONS_KEY).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stopped_commit_writes_nothing() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let mut transaction = storage.transaction();
+        transaction
+            .save(Ticket::new("first", "First"))
+            .save(Ticket::new("second", "Second"));
+
+        let mut steps = Vec::new();
+        let committed = transaction
+            .commit_with(|staged, total| {
+                steps.push((staged, total));
+                staged < 2
+            })
+            .unwrap();
+        assert!(!committed);
+        assert_eq!(steps, vec![(1, 2), (2, 2)]);
+        assert!(storage.load_all().unwrap().is_empty());
+        assert!(storage.blobs.list(TRANSACTIONS_KEY).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_close_with_dependencies_closed_along() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let mut dependency = Ticket::new("dependency", "Dependency");
+        let mut ticket = Ticket::new("dependent", "Dependent");
+        ticket.depends_on.push(dependency.id.clone());
+        storage.save(&dependency).unwrap();
+        storage.save(&ticket).unwrap();
+
+        ticket.close();
+        assert!(matches!(
+            storage.save(&ticket),
+            Err(VibeTicketError::OpenDependencies { .. })
+        ));
+        dependency.close();
+        let mut transaction = storage.transaction();
+        transaction.save(ticket.clone()).save(dependency.clone());
+        transaction.commit().unwrap();
+        assert_eq!(storage.load(&ticket.id).unwrap().status, Status::Done);
+    }
+
+    #[test]
+    fn test_recover_interrupted_transactions() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let mut ticket = Ticket::new("recovered", "Recovered");
+        storage.save(&ticket).unwrap();
+        let discarded = Ticket::new("discarded", "Discarded");
+
+        // One transaction crashed after its commit marker, one before
+        ticket.status = Status::Doing;
+        let yaml = serde_yaml::to_string(&ticket).unwrap();
+        storage
+            .blobs
+            .put(&staged_key("committed", &ticket.id), yaml.as_bytes())
+            .unwrap();
+        storage.blobs.put(&commit_key("committed"), b"").unwrap();
+        let yaml = serde_yaml::to_string(&discarded).unwrap();
+        storage
+            .blobs
+            .put(&staged_key("crashed", &discarded.id), yaml.as_bytes())
+            .unwrap();
+
+        assert_eq!(storage.recover_transactions().unwrap(), 1);
+        assert!(storage.blobs.list(TRANSACTIONS_KEY).unwrap().is_empty());
+        let fresh = FileStorage::new(temp.path());
+        assert_eq!(fresh.load(&ticket.id).unwrap().status, Status::Doing);
+        assert!(fresh.load(&discarded.id).is_err());
+        assert_eq!(
+            fresh.summaries().unwrap()[0].status,
+            Status::Doing,
+            "the listing index follows recovered tickets"
+        );
+    }
+}
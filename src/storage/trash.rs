@@ -0,0 +1,221 @@
+//! Trash for deleted tickets
+//!
+//! Deleting a ticket moves it to `trash/<ticket-id>.yaml` together with the
+//! time of deletion, from where it can be restored. The ticket's history and
+//! attachments stay in place and are not orphans while the ticket is in the
+//! trash. Entries older than the retention period are reported by
+//! [`FileStorage::find_orphans`], so `gc` removes them for good.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::FileStorage;
+use crate::core::{Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+
+/// Blob store key of the trash directory
+pub(super) const TRASH_KEY: &str = "trash";
+
+/// A deleted ticket kept in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedTicket {
+    /// When the ticket was deleted
+    pub deleted_at: DateTime<Utc>,
+    /// The ticket as it was when deleted
+    pub ticket: Ticket,
+}
+
+impl TrashedTicket {
+    /// Returns true if the entry is older than `retention_days`
+    pub fn is_expired(&self, retention_days: u32, now: DateTime<Utc>) -> bool {
+        now - self.deleted_at > Duration::days(i64::from(retention_days))
+    }
+}
+
+impl FileStorage {
+    /// Returns the blob store key of a trash entry
+    pub(super) fn trash_key(id: &TicketId) -> String {
+        format!("{TRASH_KEY}/{id}.yaml")
+    }
+
+    /// Moves a ticket to the trash
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket does not exist or cannot be moved
+    pub fn trash_ticket(&self, id: &TicketId) -> Result<TrashedTicket> {
+        let entry = TrashedTicket {
+            deleted_at: Utc::now(),
+            ticket: self.load_ticket(id)?,
+        };
+        let yaml = serde_yaml::to_string(&entry).context("Failed to serialize ticket")?;
+        self.blobs
+            .put(&Self::trash_key(id), yaml.as_bytes())
+            .context("Failed to move ticket to the trash")?;
+        self.delete_ticket(id)?;
+        Ok(entry)
+    }
+
+    /// Lists the trash, most recently deleted first
+    ///
+    /// Entries that cannot be parsed are skipped.
+    pub fn list_trash(&self) -> Result<Vec<TrashedTicket>> {
+        let mut entries = Vec::new();
+        for key in self
+            .blobs
+            .list(TRASH_KEY)
+            .context("Failed to read the trash")?
+        {
+            if let Some(yaml) = self
+                .blobs
+                .get_string(&key)
+                .context("Failed to read the trash")?
+                && let Ok(entry) = serde_yaml::from_str::<TrashedTicket>(&yaml)
+            {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+        Ok(entries)
+    }
+
+    /// Finds a trashed ticket by ID, ID prefix or slug
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no entry or several entries match
+    pub fn find_in_trash(&self, reference: &str) -> Result<TrashedTicket> {
+        let entries = self.list_trash()?;
+        if let Some(entry) = entries
+            .iter()
+            .find(|e| e.ticket.id.to_string() == reference || e.ticket.slug == reference)
+        {
+            return Ok(entry.clone());
+        }
+
+        let matches: Vec<&TrashedTicket> = entries
+            .iter()
+            .filter(|e| e.ticket.id.to_string().starts_with(reference))
+            .collect();
+        match matches.as_slice() {
+            [entry] => Ok((*entry).clone()),
+            [] => Err(VibeTicketError::TicketNotFound {
+                id: format!("{reference} (in trash)"),
+            }),
+            _ => Err(VibeTicketError::custom(format!(
+                "Multiple trashed tickets match '{reference}'"
+            ))),
+        }
+    }
+
+    /// Restores a trashed ticket
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a ticket with the same ID or slug exists again
+    pub fn restore_from_trash(&self, id: &TicketId) -> Result<Ticket> {
+        let key = Self::trash_key(id);
+        let yaml = self
+            .blobs
+            .get_string(&key)
+            .context("Failed to read the trash")?
+            .ok_or_else(|| VibeTicketError::TicketNotFound {
+                id: format!("{id} (in trash)"),
+            })?;
+        let entry: TrashedTicket =
+            serde_yaml::from_str(&yaml).context("Failed to deserialize ticket")?;
+
+        if self.blobs.exists(&Self::ticket_key(id))? {
+            return Err(VibeTicketError::custom(format!(
+                "Ticket {} already exists",
+                id.short()
+            )));
+        }
+        if self.ticket_exists_with_slug(&entry.ticket.slug)? {
+            return Err(VibeTicketError::DuplicateTicket {
+                slug: entry.ticket.slug,
+            });
+        }
+
+        self.save_ticket(&entry.ticket)?;
+        self.blobs
+            .delete(&key)
+            .context("Failed to remove ticket from the trash")?;
+        Ok(entry.ticket)
+    }
+
+    /// Removes entries from the trash for good
+    ///
+    /// With `retention_days`, only entries older than that are removed.
+    /// Returns the removed entries.
+    pub fn empty_trash(&self, retention_days: Option<u32>) -> Result<Vec<TrashedTicket>> {
+        let now = Utc::now();
+        let mut removed = Vec::new();
+        for entry in self.list_trash()? {
+            if retention_days.is_some_and(|days| !entry.is_expired(days, now)) {
+                continue;
+            }
+            self.purge_from_trash(&entry.ticket.id)?;
+            removed.push(entry);
+        }
+        Ok(removed)
+    }
+
+    /// Deletes a trash entry with the history and attachments of the ticket
+    pub(super) fn purge_from_trash(&self, id: &TicketId) -> Result<()> {
+        self.blobs
+            .delete(&Self::trash_key(id))
+            .context("Failed to empty the trash")?;
+        self.blobs
+            .delete(&Self::history_key(id))
+            .context("Failed to delete ticket history")?;
+        let attachments = self.attachments_dir(id);
+        if attachments.is_dir() {
+            std::fs::remove_dir_all(&attachments)
+                .with_context(|| format!("Failed to delete {}", attachments.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_trash_and_restore() {
+        let temp = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp.path());
+        let ticket = Ticket::new("trashed", "Trashed");
+        storage.save(&ticket).unwrap();
+        let ticket = storage.load(&ticket.id).unwrap();
+
+        storage.trash_ticket(&ticket.id).unwrap();
+        let fresh = || FileStorage::new(temp.path());
+        assert!(fresh().load(&ticket.id).is_err());
+        assert_eq!(
+            fresh().find_in_trash("trashed").unwrap().ticket.id,
+            ticket.id
+        );
+        assert!(fresh().find_orphans().unwrap().is_empty());
+
+        let restored = fresh().restore_from_trash(&ticket.id).unwrap();
+        assert_eq!(restored, ticket);
+        assert!(fresh().list_trash().unwrap().is_empty());
+        assert_eq!(fresh().load(&ticket.id).unwrap().title, "Trashed");
+
+        fresh().trash_ticket(&ticket.id).unwrap();
+        assert!(fresh().empty_trash(Some(30)).unwrap().is_empty());
+        assert_eq!(fresh().empty_trash(None).unwrap().len(), 1);
+        assert!(fresh().load_history(&ticket.id).unwrap().is_empty());
+
+        let expired = TrashedTicket {
+            deleted_at: Utc::now() - Duration::days(31),
+            ticket,
+        };
+        assert!(expired.is_expired(30, Utc::now()));
+        assert!(!expired.is_expired(60, Utc::now()));
+    }
+}
@@ -19,14 +19,26 @@ pub mod test {
     pub fn create_test_ticket_with_id(id: TicketId) -> Ticket {
         Ticket {
             id,
+            short_id: None,
             slug: "test-ticket".to_string(),
             title: "Test Ticket".to_string(),
             description: "Test description".to_string(),
             status: Status::Todo,
             priority: Priority::Medium,
+            severity: None,
             tags: vec!["test".to_string()],
             assignee: None,
             tasks: vec![],
+            work_log: Vec::new(),
+            comments: Vec::new(),
+            depends_on: Vec::new(),
+            blocks: Vec::new(),
+            relations: Vec::new(),
+            parent: None,
+            due: None,
+            milestone: None,
+            watchers: Vec::new(),
+            incident: None,
             metadata: HashMap::new(),
             created_at: Utc::now(),
             started_at: None,
@@ -0,0 +1,37 @@
+//! Multi-process chaos test for the file storage
+//!
+//! Run with `cargo test --features chaos --test chaos_test`.
+
+#![cfg(feature = "chaos")]
+
+use std::process::Command;
+
+use tempfile::TempDir;
+use vibe_ticket::chaos::{self, ChaosConfig};
+
+/// Entry point of the worker processes; does nothing when run directly
+#[test]
+fn chaos_worker() {
+    chaos::worker_from_env().unwrap();
+}
+
+#[test]
+fn chaos_processes_keep_store_consistent() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = ChaosConfig {
+        workers: 4,
+        operations: 40,
+        ..ChaosConfig::default()
+    };
+
+    let report = chaos::run_processes(temp_dir.path(), &config, |_| {
+        let mut command = Command::new(std::env::current_exe().unwrap());
+        command.args(["chaos_worker", "--exact", "--quiet", "--test-threads=1"]);
+        command
+    })
+    .unwrap();
+
+    report.assert_consistent();
+    assert_eq!(report.workers.len(), 4);
+    assert_eq!(report.succeeded() + report.failed(), 160);
+}
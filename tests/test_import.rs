@@ -88,6 +88,8 @@ fn test_import_json_array() {
         Some("json"),
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -151,6 +153,8 @@ fn test_import_json_object() {
         None, // Test auto-detection
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -201,6 +205,8 @@ fn test_import_yaml() {
         Some("yaml"),
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -238,6 +244,8 @@ fn test_import_csv() {
         Some("csv"),
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -295,6 +303,8 @@ fn test_dry_run_import() {
         Some("json"),
         false,
         true, // dry_run = true
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -317,10 +327,12 @@ fn test_skip_existing_tickets() {
     // Create an existing ticket
     let existing_ticket = Ticket {
         id: TicketId::parse_str("a50e8400-e29b-41d4-a716-446655440001").unwrap(),
+        short_id: None,
         slug: "existing-ticket".to_string(),
         title: "Existing Ticket".to_string(),
         description: "This ticket already exists".to_string(),
         priority: Priority::Medium,
+        severity: None,
         status: Status::Todo,
         tags: vec![],
         created_at: chrono::Utc::now(),
@@ -328,6 +340,16 @@ fn test_skip_existing_tickets() {
         closed_at: None,
         assignee: None,
         tasks: vec![],
+        work_log: Vec::new(),
+        comments: Vec::new(),
+        depends_on: Vec::new(),
+        blocks: Vec::new(),
+        relations: Vec::new(),
+        parent: None,
+        due: None,
+        milestone: None,
+        watchers: Vec::new(),
+        incident: None,
         metadata: std::collections::HashMap::new(),
     };
 
@@ -377,6 +399,8 @@ fn test_skip_existing_tickets() {
         Some("json"),
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -450,6 +474,8 @@ fn test_validation_duplicate_ids() {
         Some("json"),
         true, // skip_validation = true
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -476,6 +502,8 @@ fn test_invalid_json_format() {
         Some("json"),
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -497,6 +525,8 @@ fn test_auto_format_detection() {
         None, // Let it auto-detect
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -530,6 +560,8 @@ tickets:
         None, // Let it auto-detect
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -594,6 +626,8 @@ fn test_import_with_complex_metadata() {
         Some("json"),
         false,
         false,
+        false,
+        false,
         Some(temp_dir.path().to_str().unwrap()),
         &formatter,
     );
@@ -618,3 +652,233 @@ fn test_import_with_complex_metadata() {
     assert!(ticket.metadata.contains_key("custom_field"));
     assert!(ticket.metadata.contains_key("nested"));
 }
+
+#[test]
+fn test_import_verifies_export_manifest() {
+    use vibe_ticket::cli::handlers::handle_export_command;
+
+    let (source_dir, formatter) = setup_test_project();
+    let source = FileStorage::new(source_dir.path().join(".vibe-ticket"));
+    source.save(&Ticket::new("first", "First")).unwrap();
+    source.save(&Ticket::new("second", "Second")).unwrap();
+
+    let export_file = source_dir.path().join("export.yaml");
+    handle_export_command(
+        "yaml",
+        Some(export_file.to_str().unwrap().to_string()),
+        false,
+        "slug",
+        false,
+        false,
+        Some(source_dir.path().to_str().unwrap()),
+        &formatter,
+    )
+    .unwrap();
+    assert!(source_dir.path().join("export.yaml.manifest.json").exists());
+
+    // A truncated export is rejected before anything is written
+    let (target_dir, formatter) = setup_test_project();
+    let target = Some(target_dir.path().to_str().unwrap());
+    let content = fs::read_to_string(&export_file).unwrap();
+    fs::write(&export_file, &content[..content.len() - 40]).unwrap();
+    let error = handle_import_command(
+        export_file.to_str().unwrap(),
+        None,
+        false,
+        false,
+        true,
+        false,
+        target,
+        &formatter,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("failed verification"));
+    let target_storage = || FileStorage::new(target_dir.path().join(".vibe-ticket"));
+    assert!(target_storage().load_all().unwrap().is_empty());
+
+    fs::write(&export_file, &content).unwrap();
+    handle_import_command(
+        export_file.to_str().unwrap(),
+        None,
+        false,
+        false,
+        true,
+        false,
+        target,
+        &formatter,
+    )
+    .unwrap();
+    assert_eq!(target_storage().load_all().unwrap().len(), 2);
+}
+
+#[test]
+fn test_csv_round_trip_with_tasks() {
+    use vibe_ticket::cli::handlers::handle_export_command;
+
+    let (source_dir, formatter) = setup_test_project();
+    let source = FileStorage::new(source_dir.path().join(".vibe-ticket"));
+    let mut ticket = Ticket::new("with-tasks", "With tasks");
+    let done = ticket.add_task("Reproduce, then fix");
+    ticket.add_task("Add a regression test");
+    ticket.complete_task(&done).unwrap();
+    source.save(&ticket).unwrap();
+
+    let export_file = source_dir.path().join("export.csv");
+    handle_export_command(
+        "csv",
+        Some(export_file.to_str().unwrap().to_string()),
+        false,
+        "slug",
+        false,
+        true,
+        Some(source_dir.path().to_str().unwrap()),
+        &formatter,
+    )
+    .unwrap();
+    assert!(source_dir.path().join("export.tasks.csv").exists());
+
+    let (target_dir, formatter) = setup_test_project();
+    handle_import_command(
+        export_file.to_str().unwrap(),
+        None,
+        false,
+        false,
+        false,
+        false,
+        Some(target_dir.path().to_str().unwrap()),
+        &formatter,
+    )
+    .unwrap();
+
+    let imported = FileStorage::new(target_dir.path().join(".vibe-ticket"))
+        .load(&ticket.id)
+        .unwrap();
+    assert_eq!(imported.tasks, ticket.tasks);
+}
+
+#[test]
+fn test_csv_round_trip_with_fields() {
+    use vibe_ticket::cli::handlers::handle_export_command;
+    use vibe_ticket::config::Config;
+
+    let declare_fields = |dir: &TempDir| {
+        let mut config = Config::default();
+        config
+            .fields
+            .insert("estimate".to_string(), "number".parse().unwrap());
+        config
+            .fields
+            .insert("team".to_string(), "enum:core,web".parse().unwrap());
+        config
+            .save_to_path(dir.path().join(".vibe-ticket/config.yaml"))
+            .unwrap();
+    };
+
+    let (source_dir, formatter) = setup_test_project();
+    declare_fields(&source_dir);
+    let mut ticket = Ticket::new("sized", "Sized");
+    ticket
+        .metadata
+        .insert("estimate".to_string(), serde_json::json!(3));
+    ticket
+        .metadata
+        .insert("team".to_string(), serde_json::json!("web"));
+    FileStorage::new(source_dir.path().join(".vibe-ticket"))
+        .save(&ticket)
+        .unwrap();
+
+    let export_file = source_dir.path().join("export.csv");
+    handle_export_command(
+        "csv",
+        Some(export_file.to_str().unwrap().to_string()),
+        false,
+        "slug",
+        false,
+        false,
+        Some(source_dir.path().to_str().unwrap()),
+        &formatter,
+    )
+    .unwrap();
+    let content = fs::read_to_string(&export_file).unwrap();
+    assert!(content.lines().next().unwrap().ends_with(",estimate,team"));
+
+    let (target_dir, formatter) = setup_test_project();
+    declare_fields(&target_dir);
+    let import = |file: &str| {
+        handle_import_command(
+            file,
+            None,
+            false,
+            false,
+            false,
+            false,
+            Some(target_dir.path().to_str().unwrap()),
+            &formatter,
+        )
+    };
+    import(export_file.to_str().unwrap()).unwrap();
+    let imported = FileStorage::new(target_dir.path().join(".vibe-ticket"))
+        .load(&ticket.id)
+        .unwrap();
+    assert_eq!(imported.metadata["estimate"], serde_json::json!(3));
+    assert_eq!(imported.metadata["team"], serde_json::json!("web"));
+
+    // Values outside the declared type are rejected
+    let invalid = target_dir.path().join("invalid.csv");
+    fs::write(
+        &invalid,
+        content
+            .replace(",web", ",mobile")
+            .replace(&ticket.id.to_string(), &TicketId::new().to_string())
+            .replace("sized", "other"),
+    )
+    .unwrap();
+    assert!(import(invalid.to_str().unwrap()).is_err());
+}
+
+#[test]
+fn test_import_markdown_plan() {
+    let (temp_dir, formatter) = setup_test_project();
+
+    let plan = "# Onboarding\n\nMake the first day smooth.\n\n## Accounts\n### Email\n- [ ] Create mailbox\n- [x] Pick address\n### Laptop\n";
+    let plan_file = temp_dir.path().join("prd.md");
+    fs::write(&plan_file, plan).unwrap();
+
+    let result = handle_import_command(
+        plan_file.to_str().unwrap(),
+        None, // Detected from the extension
+        false,
+        false,
+        false,
+        true,
+        Some(temp_dir.path().to_str().unwrap()),
+        &formatter,
+    );
+    assert!(result.is_ok());
+
+    let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+    let tickets = storage.load_all_tickets().unwrap();
+    assert_eq!(tickets.len(), 3);
+
+    let epic = tickets.iter().find(|t| t.title == "Onboarding").unwrap();
+    let email = tickets.iter().find(|t| t.title == "Email").unwrap();
+    assert_eq!(email.parent.as_ref(), Some(&epic.id));
+    assert_eq!(email.tags, vec!["accounts"]);
+    assert_eq!(email.total_tasks_count(), 2);
+    assert_eq!(email.completed_tasks_count(), 1);
+
+    // --epic only applies to markdown plans
+    let json_file = temp_dir.path().join("tickets.json");
+    fs::write(&json_file, "[]").unwrap();
+    let result = handle_import_command(
+        json_file.to_str().unwrap(),
+        None,
+        false,
+        false,
+        false,
+        true,
+        Some(temp_dir.path().to_str().unwrap()),
+        &formatter,
+    );
+    assert!(result.is_err());
+}